@@ -18,10 +18,13 @@ use clap::{Parser, Subcommand};
 
 use crate::ui::{format, table, ColorMode, MessageType, Progress, ProgressMode, Style};
 
+use gik_core::kg::rules::RuleSeverity;
 use gik_core::memory::{MemoryEntry, MemoryScope, MemorySource};
 use gik_core::{
-    AddOptions, CommitOptions, GikEngine, GikError, KgExportFormat, ReindexOptions, ReleaseMode,
-    ReleaseOptions, ReleaseRange, RevisionId, ShowOptions, StatsQuery,
+    AddOptions, AskFeedbackOptions, CommitOptions, EvalOptions, EvalSuite, GikEngine, GikError,
+    GitHookKind, GitLogImportOptions, HookAction, IndexMigrateOptions, KgCheckOptions,
+    KgCheckReport, KgExportFormat, PreciseMode, PromptOptions, ReindexOptions, ReleaseMode,
+    ReleaseOptions, ReleaseRange, RevisionId, ScipImportReport, ShowOptions, StatsQuery,
 };
 
 // ============================================================================
@@ -187,6 +190,9 @@ pub enum Command {
     # Show ask query history
     gik log --kind ask
 
+    # Replay the most recent saved ask
+    gik log --kind ask --show 1
+
     # Filter by date range
     gik log --since 2025-01-01T00:00:00Z --limit 10
 
@@ -225,6 +231,10 @@ pub enum Command {
         /// Output in JSONL format (one JSON object per line)
         #[arg(long)]
         jsonl: bool,
+
+        /// Replay a saved ask: position in the listing (1 = newest) or ask ID
+        #[arg(long, value_name = "ASK")]
+        show: Option<String>,
     },
 
     /// Query knowledge (RAG/stack/memory/KG) and return context
@@ -243,6 +253,18 @@ pub enum Command {
 
     # Output as JSON for scripting
     gik ask "database schema" --json
+
+    # Paste-ready context block within a 4000-token budget
+    gik ask "How are sessions stored?" --format prompt --max-tokens 4000
+
+    # Same context as Markdown
+    gik ask "release process" --format markdown
+
+    # Keep the full bundle to replay it or record feedback later
+    gik ask "How are sessions stored?" --save
+
+    # Only code owned by a team in CODEOWNERS (or authored by them)
+    gik ask "How are refunds issued?" --owner @acme/payments
 "#)]
     Ask {
         /// The question to ask
@@ -267,6 +289,121 @@ pub enum Command {
         /// Pretty-print JSON output
         #[arg(long)]
         pretty: bool,
+
+        /// Render a prompt-ready context block (prompt or markdown)
+        #[arg(long, value_parser = ["prompt", "markdown"], conflicts_with_all = ["json", "pretty"])]
+        format: Option<String>,
+
+        /// Token budget for --format output; lower-ranked items are dropped to fit
+        #[arg(long, requires = "format")]
+        max_tokens: Option<usize>,
+
+        /// Save the full context bundle for `gik log --kind ask --show` and `gik feedback`
+        #[arg(long)]
+        save: bool,
+
+        /// Only return chunks from files owned or primarily authored by OWNER
+        /// (`@user`, `@org/team`, email or Git author name)
+        #[arg(long)]
+        owner: Option<String>,
+    },
+
+    /// Record relevance judgements for the results of a logged ask
+    #[command(after_help = r#"EXAMPLES:
+    # Mark the first result of a saved ask as relevant and the third as not
+    gik feedback ask-3f2a9c41d7e0 --good 1 --bad 3
+
+    # Judge by file or line range (works without a saved bundle)
+    gik feedback ask-3f2a9c41d7e0 --good src/session.rs --bad docs/old.md:10-40
+"#)]
+    Feedback {
+        /// Ask ID (or unique prefix), or position in `gik log --kind ask`
+        ask: String,
+
+        /// Chunk judged relevant: result position, PATH or PATH:START-END (repeatable)
+        #[arg(long, value_name = "CHUNK")]
+        good: Vec<String>,
+
+        /// Chunk judged irrelevant: result position, PATH or PATH:START-END (repeatable)
+        #[arg(long, value_name = "CHUNK")]
+        bad: Vec<String>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Measure retrieval quality (recall@k, MRR, nDCG) against a question suite
+    #[command(after_help = r#"EXAMPLES:
+    # Run every configuration in the suite and print a comparison table
+    gik eval --suite questions.yaml
+
+    # Compare two configurations, scoring the top 10 chunks
+    gik eval --suite questions.yaml --configuration baseline --configuration dense-heavy --k 10
+
+    # Full per-question report as JSON
+    gik eval --suite questions.yaml --json
+
+    # Score the current settings against files judged good with `gik feedback`
+    gik eval --from-feedback
+
+A configuration with an `embedding` block (same keys as `embeddings.default` in
+the global config) compares embedding models: the branch is re-embedded with
+that model in a scratch directory before its questions run.
+"#)]
+    Eval {
+        /// YAML suite listing questions, expected paths/symbols and configurations
+        #[arg(long, required_unless_present = "from_feedback")]
+        suite: Option<PathBuf>,
+
+        /// Build the suite from `gik feedback` judgements instead of a file
+        #[arg(long, conflicts_with = "suite")]
+        from_feedback: bool,
+
+        /// Only run the named configuration (repeatable)
+        #[arg(long = "configuration", value_name = "NAME")]
+        configurations: Vec<String>,
+
+        /// Number of top chunks scored per question (overrides the suite's k)
+        #[arg(long)]
+        k: Option<usize>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Report frequent questions, knowledge gaps and hot spots from the ask log
+    #[command(after_help = r#"EXAMPLES:
+    # Full report over the whole ask log
+    gik insights
+
+    # Last week only, as JSON for tracking over time
+    gik insights --since 2025-01-06T00:00:00Z --json
+
+    # Treat asks whose best chunk scores below 0.5 as gaps
+    gik insights --low-score 0.5
+"#)]
+    Insights {
+        /// Only analyse asks since this time (RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only analyse asks until this time (RFC 3339)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Asks whose best chunk scores below this are knowledge gaps
+        #[arg(long, default_value_t = gik_core::DEFAULT_LOW_SCORE)]
+        low_score: f32,
+
+        /// Maximum rows per section
+        #[arg(short = 'n', long, default_value_t = gik_core::DEFAULT_INSIGHTS_LIMIT)]
+        limit: usize,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show aggregated stats for all bases or a single base
@@ -319,6 +456,19 @@ pub enum Command {
         json: bool,
     },
 
+    /// Manage a base's vector index
+    #[command(after_help = r#"EXAMPLES:
+    # Move the code base to the embedded HNSW backend
+    gik index migrate --base code --to hnsw
+
+    # Move the docs base back to LanceDB
+    gik index migrate --base docs --to lancedb
+"#)]
+    Index {
+        #[command(subcommand)]
+        action: IndexAction,
+    },
+
     /// Generate CHANGELOG.md from commit history
     #[command(after_help = r#"EXAMPLES:
     # Generate changelog for unreleased changes
@@ -435,6 +585,309 @@ pub enum Command {
         #[command(subcommand)]
         action: ConfigAction,
     },
+
+    /// Manage Git hooks that stage changed files automatically
+    #[command(after_help = r#"EXAMPLES:
+    # Install post-commit, post-merge and post-checkout hooks
+    gik hooks install
+
+    # Replace existing hooks not written by gik
+    gik hooks install --force
+
+    # Remove gik hooks
+    gik hooks uninstall
+"#)]
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+
+    /// Import external history into memory
+    #[command(after_help = r#"EXAMPLES:
+    # Import commit messages with a body or breaking-change marker
+    gik import git-log
+
+    # Import only commits since a tag
+    gik import git-log --since v1.2.0
+
+    # Include subject-only commits and preview without writing
+    gik import git-log --all --dry-run
+"#)]
+    Import {
+        #[command(subcommand)]
+        action: ImportAction,
+    },
+
+    /// Find symbols by name (fuzzy, like "go to symbol")
+    #[command(after_help = r#"EXAMPLES:
+    # Exact and prefix matches rank first, then substrings and subsequences
+    gik symbols parse
+
+    # Subsequence match: finds parseFile, parse_file, ...
+    gik symbols pf
+
+    # Only Rust structs and enums under src/kg
+    gik symbols Node --kind struct --kind enum --lang rust --path src/kg
+
+    # Qualified names match too
+    gik symbols Parser::parse --json
+"#)]
+    Symbols {
+        /// Symbol name, prefix, or subsequence
+        query: String,
+
+        /// Only symbols of this kind (repeatable, e.g. function, struct, class)
+        #[arg(short, long = "kind", value_name = "KIND")]
+        kinds: Vec<String>,
+
+        /// Only symbols in this language (repeatable, e.g. rust, ts, python)
+        #[arg(short, long = "lang", value_name = "LANG")]
+        langs: Vec<String>,
+
+        /// Only symbols in files under this path or matching this glob
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Maximum number of matches (0 for no limit)
+        #[arg(short = 'n', long, default_value_t = gik_core::kg::symbols::DEFAULT_SYMBOL_LIMIT)]
+        limit: usize,
+
+        /// Branch to search (defaults to current branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Inspect and check the knowledge graph
+    #[command(after_help = r#"EXAMPLES:
+    # Check import cycles, forbidden dependencies and unreferenced files
+    gik kg check
+
+    # Fail on warnings too and emit JSON for CI
+    gik kg check --strict --json
+
+    # Record new violations as observation memory entries
+    gik kg check --record
+
+    # Import a SCIP index (e.g. from rust-analyzer scip . or scip-typescript)
+    gik kg import --scip index.scip
+
+    # Drop heuristic symbols the index does not define
+    gik kg import --scip index.scip --replace
+
+    # Import an LSIF dump (e.g. from lsif-node or lsif-go)
+    gik kg import --lsif dump.lsif
+
+    # Remove the imported index and rebuild the KG
+    gik kg import --clear
+
+    # Export the full graph for Gephi or yEd
+    gik kg export --format gexf --output kg.gexf
+
+    # Export CSV tables for neo4j-admin database import
+    gik kg export --format neo4j --output kg-csv/
+
+    # Files under src/api that import a module defining a POST endpoint
+    gik kg query "MATCH (f:file)-[:imports]->(m:file)-[:definesEndpoint]->(e:endpoint)
+                  WHERE f.path GLOB 'src/api/**' AND e.httpMethod = 'POST'
+                  RETURN DISTINCT f.path, m.path, e.route"
+
+    # Most imported files
+    gik kg query "MATCH (f:file)<-[:imports]-(src) RETURN f.path, count(src) AS importers
+                  ORDER BY importers DESC LIMIT 10" --json
+
+    # Endpoints in files owned by a team
+    gik kg query "MATCH (o:owner {label: '@acme/payments'})-[:owns]->(f:file)-[:definesEndpoint]->(e:endpoint)
+                  RETURN e.httpMethod, e.route, f.path"
+
+    # Tests to run after changing a file or symbol
+    gik kg tests src/billing/invoice.rs
+    gik kg tests InvoiceService --json
+"#)]
+    Kg {
+        #[command(subcommand)]
+        action: KgAction,
+    },
+}
+
+/// KG subcommands
+#[derive(Subcommand, Debug)]
+pub enum KgAction {
+    /// Check the import graph against .guided/knowledge/rules.yaml
+    Check {
+        /// Branch to check (defaults to current branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Fail on warnings (e.g. unreferenced files) as well as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Record new violations as observation memory entries
+        #[arg(long)]
+        record: bool,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Import a precise code-intelligence index (SCIP or LSIF) into the KG
+    Import {
+        /// SCIP index file to import
+        #[arg(long, value_name = "FILE", required_unless_present_any = ["lsif", "clear"])]
+        scip: Option<PathBuf>,
+
+        /// LSIF dump (JSON lines or a JSON array) to import
+        #[arg(long, value_name = "FILE", conflicts_with = "scip")]
+        lsif: Option<PathBuf>,
+
+        /// Drop heuristic symbols of covered files that the index does not define
+        #[arg(long)]
+        replace: bool,
+
+        /// Remove the imported index and rebuild the KG
+        #[arg(long, conflicts_with_all = ["scip", "lsif", "replace"])]
+        clear: bool,
+
+        /// Branch to import into (defaults to current branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export the full knowledge graph for graph tools
+    Export {
+        /// Output format
+        #[arg(short, long, default_value = "graphml", value_parser = ["graphml", "gexf", "neo4j", "jsonld", "json", "dot", "mermaid"])]
+        format: String,
+
+        /// Output file (directory for neo4j); defaults to stdout
+        #[arg(short, long, value_name = "PATH", required_if_eq("format", "neo4j"))]
+        output: Option<PathBuf>,
+
+        /// Branch to export (defaults to current branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Print the export summary as JSON
+        #[arg(long, requires = "output")]
+        json: bool,
+    },
+
+    /// Run a Cypher-like graph query (MATCH ... WHERE ... RETURN ...)
+    Query {
+        /// The query, e.g. "MATCH (f:file)-[:imports]->(g:file) RETURN f.path, g.path"
+        query: String,
+
+        /// Branch to query (defaults to current branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List the tests covering a file or symbol
+    Tests {
+        /// A workspace-relative file path or a symbol name
+        target: String,
+
+        /// Branch to query (defaults to current branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Hooks subcommands
+#[derive(Subcommand, Debug)]
+pub enum HooksAction {
+    /// Install gik-managed Git hooks
+    Install {
+        /// Overwrite existing hooks that were not written by gik
+        #[arg(long)]
+        force: bool,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Remove gik-managed Git hooks
+    Uninstall {
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Run a hook (invoked by the installed Git hooks)
+    #[command(hide = true)]
+    Run {
+        /// Hook name (post-commit, post-merge, post-checkout)
+        hook: String,
+
+        /// Arguments passed by Git to the hook
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
+
+/// Index subcommands
+#[derive(Subcommand, Debug)]
+pub enum IndexAction {
+    /// Move a base's vectors to another backend without re-embedding
+    Migrate {
+        /// The base to migrate
+        #[arg(long, required = true)]
+        base: String,
+
+        /// Target backend
+        #[arg(long, required = true, value_parser = ["lancedb", "hnsw", "simple_file"])]
+        to: String,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Import subcommands
+#[derive(Subcommand, Debug)]
+pub enum ImportAction {
+    /// Import Git commit messages as commit_context memory entries
+    #[command(name = "git-log")]
+    GitLog {
+        /// Only import commits after this ref (e.g., a tag or SHA)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Maximum number of commits to read
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Also import subject-only commits
+        #[arg(long)]
+        all: bool,
+
+        /// Show what would be imported without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Config subcommands
@@ -599,8 +1052,9 @@ pub fn run() -> ExitCode {
             limit,
             json,
             jsonl,
+            show,
         } => handle_log(
-            &style, &engine, &workspace, kind, op, base, since, until, limit, json, jsonl,
+            &style, &engine, &workspace, kind, op, base, since, until, limit, json, jsonl, show,
         ),
         Command::Ask {
             query,
@@ -609,6 +1063,10 @@ pub fn run() -> ExitCode {
             min_score,
             json,
             pretty,
+            format,
+            max_tokens,
+            save,
+            owner,
         } => handle_ask(
             &style,
             &engine,
@@ -619,8 +1077,44 @@ pub fn run() -> ExitCode {
             min_score,
             json,
             pretty,
+            format,
+            max_tokens,
+            save,
+            owner,
             cli.verbose,
         ),
+        Command::Feedback {
+            ask,
+            good,
+            bad,
+            json,
+        } => handle_feedback(&style, &engine, &workspace, ask, good, bad, json),
+        Command::Eval {
+            suite,
+            from_feedback,
+            configurations,
+            k,
+            json,
+        } => handle_eval(
+            &style,
+            &engine,
+            &workspace,
+            &branch,
+            suite,
+            from_feedback,
+            configurations,
+            k,
+            json,
+        ),
+        Command::Insights {
+            since,
+            until,
+            low_score,
+            limit,
+            json,
+        } => handle_insights(
+            &style, &engine, &workspace, &branch, since, until, low_score, limit, json,
+        ),
         Command::Stats { base, json } => handle_stats(&style, &engine, &workspace, &branch, base, json),
         Command::Reindex {
             base,
@@ -628,6 +1122,7 @@ pub fn run() -> ExitCode {
             dry_run,
             json,
         } => handle_reindex(&style, &engine, &workspace, base, force, dry_run, json),
+        Command::Index { action } => handle_index(&style, &engine, &workspace, action),
         Command::Release {
             tag,
             branch: release_branch,
@@ -671,6 +1166,27 @@ pub fn run() -> ExitCode {
             max_kg_edges,
         ),
         Command::Config { action } => handle_config(&style, &engine, &workspace, action),
+        Command::Hooks { action } => handle_hooks(&style, &engine, &workspace, action),
+        Command::Import { action } => handle_import(&style, &engine, &workspace, action),
+        Command::Symbols {
+            query,
+            kinds,
+            langs,
+            path,
+            limit,
+            branch,
+            json,
+        } => {
+            let mut opts = gik_core::SymbolSearchOptions::new()
+                .with_kinds(kinds)
+                .with_languages(langs)
+                .with_limit(limit);
+            if let Some(path) = path {
+                opts = opts.with_path(path);
+            }
+            handle_symbols(&style, &engine, &workspace, &query, &opts, branch, json)
+        }
+        Command::Kg { action } => handle_kg(&style, &engine, &workspace, action),
     };
 
     match result {
@@ -1234,6 +1750,7 @@ fn handle_log(
     limit: Option<usize>,
     json: bool,
     jsonl: bool,
+    show: Option<String>,
 ) -> Result<(), GikError> {
     use gik_core::{LogEntry, LogKind, LogQueryScope, TimelineOperationKind};
 
@@ -1277,6 +1794,23 @@ fn handle_log(
         scope = scope.with_limit(lim);
     }
 
+    // Replay a saved ask bundle
+    if let Some(selector) = show {
+        if log_kind != LogKind::Ask {
+            return Err(GikError::InvalidArgument(
+                "--show requires --kind ask".to_string(),
+            ));
+        }
+        let bundle = engine.show_ask(workspace, scope, &selector)?;
+        if json || jsonl {
+            let output = serde_json::to_string_pretty(&bundle).map_err(GikError::Json)?;
+            println!("{}", output);
+        } else {
+            print_ask_bundle(style, &bundle, false);
+        }
+        return Ok(());
+    }
+
     // Run query
     let result = engine.log_query(workspace, scope)?;
 
@@ -1348,14 +1882,22 @@ fn handle_log(
             LogKind::Ask => {
                 println!("{}", style.section("ASK LOG"));
                 println!();
-                for entry in &result.entries {
+                for (i, entry) in result.entries.iter().enumerate() {
                     if let LogEntry::Ask(a) = entry {
+                        let saved = if a.bundle_path.is_some() {
+                            ", saved"
+                        } else {
+                            ""
+                        };
                         println!(
-                            "  {} [{}] \"{}\" ({} hits)",
+                            "  {:>3}. {} {} [{}] \"{}\" ({} hits{})",
+                            i + 1,
+                            a.id.as_deref().unwrap_or("-"),
                             format::format_relative_time(a.timestamp),
                             a.bases.join(","),
                             format::truncate_str(&a.question, 50),
-                            a.total_hits
+                            a.total_hits,
+                            saved
                         );
                     }
                 }
@@ -1376,6 +1918,10 @@ fn handle_ask(
     min_score: Option<f32>,
     json: bool,
     pretty: bool,
+    format: Option<String>,
+    max_tokens: Option<usize>,
+    save: bool,
+    owner: Option<String>,
     verbose: bool,
 ) -> Result<(), GikError> {
     // Get current branch
@@ -1387,7 +1933,8 @@ fn handle_ask(
     let opts = gik_core::AskPipelineOptions::new(&query)
         .with_top_k(top_k)
         .with_final_k(top_k)  // Override reranker's finalK with CLI value
-        .with_stack(true);
+        .with_stack(true)
+        .with_save_bundle(save);
 
     let opts = if let Some(b) = bases {
         opts.with_bases(b)
@@ -1401,106 +1948,444 @@ fn handle_ask(
         opts
     };
 
+    let opts = if let Some(owner) = owner {
+        opts.with_owner(owner)
+    } else {
+        opts
+    };
+
     // Run the ask pipeline
     let result = engine.ask(workspace, &branch, opts)?;
 
-    if json || pretty {
-        let output = if pretty {
-            serde_json::to_string_pretty(&result).unwrap_or_default()
-        } else {
-            serde_json::to_string(&result).unwrap_or_default()
+    if let Some(format) = format {
+        let prompt_opts = PromptOptions {
+            format: format.parse().unwrap_or_default(),
+            max_tokens,
         };
-        println!("{}", output);
-    } else {
-        // Human-readable output
-        println!("{}", style.section("QUERY"));
-        println!();
-        println!("  {}", style.key_value("Query", &query));
-        println!("  {}", style.key_value("Revision", &style.revision(result.revision_id.as_str())));
-        println!("  {}", style.key_value("Bases", &result.bases.join(", ")));
+        let rendered = gik_core::render_prompt(&result, &prompt_opts);
+        print!("{}", rendered.text);
 
-        if result.rag_chunks.is_empty() {
-            println!();
-            println!("{}", style.message(MessageType::Info, "No relevant chunks found."));
-        } else {
-            println!();
-            println!("{}", style.section("RESULTS"));
-            println!();
-            println!(
+        if rendered.is_trimmed() {
+            eprintln!(
                 "{}",
                 style.message(
-                    MessageType::Ok,
-                    &format!("Retrieved {} chunks", result.rag_chunks.len())
+                    MessageType::Info,
+                    &format!(
+                        "Dropped {} source(s), {} memory event(s), {} graph summary(ies){} to fit {} tokens",
+                        rendered.omitted_sources,
+                        rendered.omitted_memory_events,
+                        rendered.omitted_kg_results,
+                        if rendered.omitted_stack { " and the stack summary" } else { "" },
+                        max_tokens.unwrap_or_default()
+                    )
                 )
             );
-            println!();
+        }
+    } else if json || pretty {
+        let output = if pretty {
+            serde_json::to_string_pretty(&result).unwrap_or_default()
+        } else {
+            serde_json::to_string(&result).unwrap_or_default()
+        };
+        println!("{}", output);
+    } else {
+        print_ask_bundle(style, &result, verbose);
+    }
+    Ok(())
+}
+
+/// Print an ask bundle in human-readable form.
+fn print_ask_bundle(style: &Style, result: &gik_core::AskContextBundle, verbose: bool) {
+    // Human-readable output
+    println!("{}", style.section("QUERY"));
+    println!();
+    println!("  {}", style.key_value("Query", &result.question));
+    if let Some(ref ask_id) = result.ask_id {
+        println!("  {}", style.key_value("Ask", ask_id));
+    }
+    println!("  {}", style.key_value("Revision", &style.revision(result.revision_id.as_str())));
+    println!("  {}", style.key_value("Bases", &result.bases.join(", ")));
+
+    if result.rag_chunks.is_empty() {
+        println!();
+        println!("{}", style.message(MessageType::Info, "No relevant chunks found."));
+    } else {
+        println!();
+        println!("{}", style.section("RESULTS"));
+        println!();
+        println!(
+            "{}",
+            style.message(
+                MessageType::Ok,
+                &format!("Retrieved {} chunks", result.rag_chunks.len())
+            )
+        );
+        println!();
 
-            for (i, chunk) in result.rag_chunks.iter().enumerate() {
+        for (i, chunk) in result.rag_chunks.iter().enumerate() {
+            println!(
+                "  {}. [{}] {} (lines {}-{}) - score: {}",
+                i + 1,
+                chunk.base,
+                style.file_path(&chunk.path),
+                chunk.start_line,
+                chunk.end_line,
+                style.score(chunk.score)
+            );
+
+            // Show snippet preview (first 100 chars)
+            let snippet_preview: String = chunk
+                .snippet
+                .chars()
+                .take(100)
+                .collect::<String>()
+                .replace('\n', " ");
+            if !snippet_preview.is_empty() {
                 println!(
-                    "  {}. [{}] {} (lines {}-{}) - score: {}",
-                    i + 1,
-                    chunk.base,
-                    style.file_path(&chunk.path),
-                    chunk.start_line,
-                    chunk.end_line,
-                    style.score(chunk.score)
+                    "     {}{}",
+                    snippet_preview,
+                    if chunk.snippet.len() > 100 { "..." } else { "" }
                 );
-
-                // Show snippet preview (first 100 chars)
-                let snippet_preview: String = chunk
-                    .snippet
-                    .chars()
-                    .take(100)
-                    .collect::<String>()
-                    .replace('\n', " ");
-                if !snippet_preview.is_empty() {
-                    println!(
-                        "     {}{}",
-                        snippet_preview,
-                        if chunk.snippet.len() > 100 { "..." } else { "" }
-                    );
+            }
+            if let Some(ownership) = &chunk.ownership {
+                let mut parts = Vec::new();
+                if !ownership.owners.is_empty() {
+                    parts.push(format!("owners: {}", ownership.owners.join(", ")));
+                }
+                if !ownership.authors.is_empty() {
+                    parts.push(format!("authors: {}", ownership.authors.join(", ")));
+                }
+                if !parts.is_empty() {
+                    println!("     {}", parts.join(" | "));
                 }
-                println!();
             }
+            println!();
+        }
+    }
+
+    // Show stack summary if available
+    if let Some(stack) = &result.stack_summary {
+        println!("{}", style.section("STACK"));
+        println!();
+        if !stack.languages.is_empty() {
+            println!("  {}", style.key_value("Languages", &stack.languages.join(", ")));
+        }
+        if !stack.frameworks.is_empty() {
+            println!("  {}", style.key_value("Frameworks", &stack.frameworks.join(", ")));
         }
+        if !stack.services.is_empty() {
+            println!("  {}", style.key_value("Services", &stack.services.join(", ")));
+        }
+        if !stack.managers.is_empty() {
+            println!("  {}", style.key_value("Managers", &stack.managers.join(", ")));
+        }
+        if let Some(count) = stack.total_files {
+            println!("  {}", style.key_value("Files", &count.to_string()));
+        }
+    }
 
-        // Show stack summary if available
-        if let Some(stack) = &result.stack_summary {
-            println!("{}", style.section("STACK"));
-            println!();
-            if !stack.languages.is_empty() {
-                println!("  {}", style.key_value("Languages", &stack.languages.join(", ")));
-            }
-            if !stack.frameworks.is_empty() {
-                println!("  {}", style.key_value("Frameworks", &stack.frameworks.join(", ")));
-            }
-            if !stack.services.is_empty() {
-                println!("  {}", style.key_value("Services", &stack.services.join(", ")));
-            }
-            if !stack.managers.is_empty() {
-                println!("  {}", style.key_value("Managers", &stack.managers.join(", ")));
+    // Show timing info only with --verbose flag
+    if verbose {
+        if let Some(embed_ms) = result.debug.embed_time_ms {
+            if let Some(search_ms) = result.debug.search_time_ms {
+                println!();
+                println!(
+                    "{}",
+                    style.message(
+                        MessageType::Info,
+                        &format!("Timing: embed {}ms, search {}ms", embed_ms, search_ms)
+                    )
+                );
             }
-            if let Some(count) = stack.total_files {
-                println!("  {}", style.key_value("Files", &count.to_string()));
+        }
+    }
+}
+
+fn handle_feedback(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    ask: String,
+    good: Vec<String>,
+    bad: Vec<String>,
+    json: bool,
+) -> Result<(), GikError> {
+    let entries = engine.feedback(workspace, AskFeedbackOptions { ask, good, bad })?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&entries).map_err(GikError::Json)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        style.message(
+            MessageType::Ok,
+            &format!(
+                "Recorded {} judgement(s) for {}",
+                entries.len(),
+                entries[0].ask_id
+            )
+        )
+    );
+    for entry in &entries {
+        let location = match (entry.start_line, entry.end_line) {
+            (Some(start), Some(end)) => format!("{}:{}-{}", entry.path, start, end),
+            _ => entry.path.clone(),
+        };
+        println!(
+            "{}",
+            style.message_detail(&entry.verdict.to_string(), &style.file_path(&location))
+        );
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_eval(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    branch: &gik_core::BranchName,
+    suite_path: Option<PathBuf>,
+    from_feedback: bool,
+    configurations: Vec<String>,
+    k: Option<usize>,
+    json: bool,
+) -> Result<(), GikError> {
+    let suite = match suite_path {
+        Some(path) if !from_feedback => EvalSuite::load(&path)?,
+        _ => engine.feedback_eval_suite(workspace)?,
+    };
+    let opts = EvalOptions { k, configurations };
+
+    let report = if !json {
+        let mode = if style.color_mode() == ColorMode::Never {
+            ProgressMode::Quiet
+        } else {
+            ProgressMode::Interactive
+        };
+        let progress = Progress::spinner(
+            &format!("Evaluating {} question(s)...", suite.questions.len()),
+            mode,
+        );
+        let result = engine.eval(workspace, branch, &suite, opts);
+        match &result {
+            Ok(_) => progress.finish_clear(),
+            Err(_) => progress.finish_err("Failed"),
+        }
+        result?
+    } else {
+        engine.eval(workspace, branch, &suite, opts)?
+    };
+
+    if json {
+        let output = serde_json::to_string_pretty(&report).map_err(GikError::Json)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        style.message(
+            MessageType::Ok,
+            &format!(
+                "Evaluated {} question(s) under {} configuration(s)",
+                report.questions,
+                report.configurations.len()
+            )
+        )
+    );
+    println!();
+
+    let rows: Vec<table::EvalRow> = report
+        .configurations
+        .iter()
+        .map(|c| table::EvalRow {
+            name: c.name.clone(),
+            recall: c.recall_at_k,
+            mrr: c.mrr,
+            ndcg: c.ndcg_at_k,
+            latency_mean_ms: c.latency_mean_ms,
+            latency_p95_ms: c.latency_p95_ms,
+            failed: c.failed,
+        })
+        .collect();
+    println!("{}", table::render_eval_table(&rows, report.k));
+
+    let failed: usize = report.configurations.iter().map(|c| c.failed).sum();
+    if failed > 0 {
+        eprintln!();
+        eprintln!(
+            "{}",
+            style.message(
+                MessageType::Warn,
+                &format!("{} ask(s) failed; run with --json for details", failed)
+            )
+        );
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_insights(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    branch: &gik_core::BranchName,
+    since: Option<String>,
+    until: Option<String>,
+    low_score: f32,
+    limit: usize,
+    json: bool,
+) -> Result<(), GikError> {
+    use gik_core::{InsightsOptions, SuggestionReason};
+
+    let since = since
+        .map(|s| {
+            chrono::DateTime::parse_from_rfc3339(&s)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .map_err(|e| GikError::Other(anyhow::anyhow!("Invalid --since timestamp: {}", e)))
+        })
+        .transpose()?;
+    let until = until
+        .map(|s| {
+            chrono::DateTime::parse_from_rfc3339(&s)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .map_err(|e| GikError::Other(anyhow::anyhow!("Invalid --until timestamp: {}", e)))
+        })
+        .transpose()?;
+
+    let opts = InsightsOptions {
+        since,
+        until,
+        low_score,
+        limit,
+    };
+    let report = engine.insights(workspace, branch, opts)?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&report).map_err(GikError::Json)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    if report.asks == 0 {
+        println!(
+            "{}",
+            style.message(
+                MessageType::Info,
+                "No asks recorded yet. Run `gik ask` first."
+            )
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        style.message(MessageType::Ok, &format!("Analysed {} ask(s)", report.asks))
+    );
+
+    let score = |s: Option<f32>| match s {
+        Some(s) => format!("{:.2}", s),
+        None => "-".to_string(),
+    };
+
+    println!();
+    println!("{}", style.section("FREQUENT QUESTIONS"));
+    if report.frequent_questions.is_empty() {
+        println!("  (no question asked more than once)");
+    }
+    for q in &report.frequent_questions {
+        println!(
+            "  {:>3}x \"{}\" (best {}, last {})",
+            q.count,
+            format::truncate_str(&q.question, 60),
+            score(q.best_score),
+            format::format_relative_time(q.last_asked)
+        );
+    }
+
+    println!();
+    println!("{}", style.section("KNOWLEDGE GAPS"));
+    if report.knowledge_gaps.is_empty() {
+        println!("  (none below {:.2})", report.low_score);
+    }
+    for q in &report.knowledge_gaps {
+        println!(
+            "  {:>3}x \"{}\" ({} hits, best {})",
+            q.count,
+            format::truncate_str(&q.question, 60),
+            q.last_hits,
+            score(q.best_score)
+        );
+    }
+
+    println!();
+    println!("{}", style.section("HOT SPOTS"));
+    if report.top_files.is_empty() {
+        println!("  (no returned files recorded)");
+    }
+    for f in &report.top_files {
+        println!(
+            "  {:>3}x [{}] {} (mean {:.2})",
+            f.count,
+            f.base,
+            style.file_path(&f.path),
+            f.mean_score
+        );
+    }
+    for b in &report.top_bases {
+        println!(
+            "  {}",
+            style.key_value(&b.base, &format!("{} ask(s), {} file(s)", b.asks, b.files))
+        );
+    }
+
+    if !report.stale_asks.is_empty() {
+        println!();
+        println!("{}", style.section("STALE ANSWERS"));
+        for a in &report.stale_asks {
+            println!(
+                "  {} {} \"{}\"",
+                a.id.as_deref().unwrap_or("-"),
+                format::format_relative_time(a.timestamp),
+                format::truncate_str(&a.question, 50)
+            );
+            for path in &a.paths {
+                println!(
+                    "{}",
+                    style.message_detail("changed", &style.file_path(path))
+                );
             }
         }
+    }
 
-        // Show timing info only with --verbose flag
-        if verbose {
-            if let Some(embed_ms) = result.debug.embed_time_ms {
-                if let Some(search_ms) = result.debug.search_time_ms {
-                    println!();
-                    println!(
-                        "{}",
-                        style.message(
-                            MessageType::Info,
-                            &format!("Timing: embed {}ms, search {}ms", embed_ms, search_ms)
-                        )
-                    );
+    if !report.suggestions.is_empty() {
+        println!();
+        println!("{}", style.section("SUGGESTED ADDS"));
+        for s in &report.suggestions {
+            let why = match s.reason {
+                SuggestionReason::Stale => format!("changed since indexed, {} ask(s)", s.asks),
+                SuggestionReason::Gap => {
+                    format!("{} gap question(s): {}", s.asks, s.matched_terms.join(", "))
                 }
-            }
+            };
+            println!("{}", style.list_item("+", &format!("{} ({})", s.path, why)));
         }
+        println!();
+        println!(
+            "{}",
+            style.message(
+                MessageType::Hint,
+                "Run `gik add <path>` then `gik commit` to index them."
+            )
+        );
     }
+
     Ok(())
 }
 
@@ -1570,42 +2455,71 @@ fn handle_stats(
                 println!("  {}", style.key_value("Managers", &stack.managers.join(", ")));
             }
         }
+
+        // Most central files in the KG import graph
+        if !result.central_files.is_empty() {
+            println!();
+            println!("{}", style.section("CENTRAL FILES"));
+            println!();
+            for (rank, file) in result.central_files.iter().enumerate() {
+                println!(
+                    "{}",
+                    style.list_item(
+                        &format!("{:>2}.", rank + 1),
+                        &format!(
+                            "{} (centrality {:.2}, imported by {})",
+                            file.path, file.centrality, file.in_degree
+                        )
+                    )
+                );
+            }
+        }
     }
     Ok(())
 }
 
-fn handle_reindex(
+fn handle_index(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    action: IndexAction,
+) -> Result<(), GikError> {
+    match action {
+        IndexAction::Migrate { base, to, json } => {
+            handle_index_migrate(style, engine, workspace, base, to, json)
+        }
+    }
+}
+
+fn handle_index_migrate(
     style: &Style,
     engine: &GikEngine,
     workspace: &gik_core::Workspace,
     base: String,
-    force: bool,
-    dry_run: bool,
+    to: String,
     json: bool,
 ) -> Result<(), GikError> {
-    let opts = ReindexOptions {
+    let opts = IndexMigrateOptions {
         base: base.clone(),
         branch: None, // Use current branch
-        force,
-        dry_run,
+        to: to.clone(),
     };
 
-    // Show progress during reindex (skip for dry-run)
-    let result = if !dry_run && !json {
+    let result = if !json {
         let mode = if style.color_mode() == ColorMode::Never {
             ProgressMode::Quiet
         } else {
             ProgressMode::Interactive
         };
-        let progress = Progress::spinner(&format!("Reindexing base '{}'...", base), mode);
-        let result = engine.reindex(workspace, opts);
+        let progress = Progress::spinner(&format!("Migrating base '{}' to {}...", base, to), mode);
+        let result = engine.migrate_index(workspace, opts);
         match &result {
             Ok(_) => progress.finish_clear(),
             Err(_) => progress.finish_err("Failed"),
         }
         result?
     } else {
-        engine.reindex(workspace, opts)?
+        engine.migrate_index(workspace, opts)?
     };
 
     if json {
@@ -1614,16 +2528,91 @@ fn handle_reindex(
         return Ok(());
     }
 
-    // Human-readable output
-    if dry_run {
-        println!(
-            "{}",
-            style.message(MessageType::Info, &format!("Dry run for base '{}'", base))
-        );
-    } else if result.bases.iter().any(|b| b.reindexed) {
-        println!(
-            "{}",
-            style.message(MessageType::Ok, &format!("Reindexed base '{}'", base))
+    println!(
+        "{}",
+        style.message(
+            MessageType::Ok,
+            &format!("Migrated base '{}' to {}", result.base, result.to_backend)
+        )
+    );
+    println!(
+        "{}",
+        style.message_detail(
+            "Backend",
+            &format!("{} -> {}", result.from_backend, result.to_backend)
+        )
+    );
+    println!(
+        "{}",
+        style.message_detail("Vectors", &result.vectors.to_string())
+    );
+
+    if let Some(ref configured) = result.config_backend {
+        eprintln!(
+            "{}",
+            style.message(
+                MessageType::Warn,
+                &format!(
+                    "Config still selects '{}' for base '{}'; set `indexes.bases.{}.backend: {}` to keep using the migrated index",
+                    configured, result.base, result.base, result.to_backend
+                )
+            )
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_reindex(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    base: String,
+    force: bool,
+    dry_run: bool,
+    json: bool,
+) -> Result<(), GikError> {
+    let opts = ReindexOptions {
+        base: base.clone(),
+        branch: None, // Use current branch
+        force,
+        dry_run,
+    };
+
+    // Show progress during reindex (skip for dry-run)
+    let result = if !dry_run && !json {
+        let mode = if style.color_mode() == ColorMode::Never {
+            ProgressMode::Quiet
+        } else {
+            ProgressMode::Interactive
+        };
+        let progress = Progress::spinner(&format!("Reindexing base '{}'...", base), mode);
+        let result = engine.reindex(workspace, opts);
+        match &result {
+            Ok(_) => progress.finish_clear(),
+            Err(_) => progress.finish_err("Failed"),
+        }
+        result?
+    } else {
+        engine.reindex(workspace, opts)?
+    };
+
+    if json {
+        let output = serde_json::to_string_pretty(&result).map_err(GikError::Json)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    // Human-readable output
+    if dry_run {
+        println!(
+            "{}",
+            style.message(MessageType::Info, &format!("Dry run for base '{}'", base))
+        );
+    } else if result.bases.iter().any(|b| b.reindexed) {
+        println!(
+            "{}",
+            style.message(MessageType::Ok, &format!("Reindexed base '{}'", base))
         );
     } else {
         println!(
@@ -1988,3 +2977,674 @@ fn handle_config_show(
     
     Ok(())
 }
+
+// ============================================================================
+// Hooks command handler
+// ============================================================================
+
+fn handle_hooks(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    action: HooksAction,
+) -> Result<(), GikError> {
+    match action {
+        HooksAction::Install { force, json } => {
+            let report = engine.install_git_hooks(workspace, force)?;
+            print_hooks_report(style, &report, json);
+            Ok(())
+        }
+        HooksAction::Uninstall { json } => {
+            let report = engine.uninstall_git_hooks(workspace)?;
+            print_hooks_report(style, &report, json);
+            Ok(())
+        }
+        HooksAction::Run { hook, args } => {
+            let hook: GitHookKind = hook.parse().map_err(GikError::InvalidArgument)?;
+            if let Some(result) = engine.run_git_hook(workspace, hook, &args)? {
+                println!(
+                    "{}",
+                    style.message(
+                        MessageType::Ok,
+                        &format!("Staged {} source(s) from {}", result.created.len(), hook)
+                    )
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_hooks_report(style: &Style, report: &gik_core::HooksReport, json: bool) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(report).unwrap_or_default()
+        );
+        return;
+    }
+
+    if let Some(warning) = &report.warning {
+        eprintln!("{}", style.message(MessageType::Warn, warning));
+    }
+    for status in &report.hooks {
+        let (msg_type, verb) = match status.action {
+            HookAction::Installed => (MessageType::Ok, "Installed"),
+            HookAction::Updated => (MessageType::Ok, "Updated"),
+            HookAction::Removed => (MessageType::Ok, "Removed"),
+            HookAction::Skipped => (MessageType::Skip, "Skipped"),
+        };
+        let mut line = format!("{} {}", verb, status.hook);
+        if let Some(reason) = &status.reason {
+            line.push_str(&format!(" ({})", reason));
+        }
+        println!("{}", style.message(msg_type, &line));
+    }
+    println!("  {}", style.key_value("Hooks dir", &report.hooks_dir.display().to_string()));
+}
+
+// ============================================================================
+// Import command handler
+// ============================================================================
+
+fn handle_import(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    action: ImportAction,
+) -> Result<(), GikError> {
+    match action {
+        ImportAction::GitLog {
+            since,
+            limit,
+            all,
+            dry_run,
+            json,
+        } => {
+            let opts = GitLogImportOptions {
+                since,
+                limit,
+                include_all: all,
+                dry_run,
+            };
+            let result = engine.import_git_log(workspace, &opts)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&result).unwrap_or_default()
+                );
+                return Ok(());
+            }
+
+            let verb = if dry_run { "Would import" } else { "Imported" };
+            println!(
+                "{}",
+                style.message(
+                    MessageType::Ok,
+                    &format!(
+                        "{} {} of {} commit(s) into memory",
+                        verb,
+                        result.imported.len(),
+                        result.scanned
+                    )
+                )
+            );
+            for commit in &result.imported {
+                let short = &commit.sha[..commit.sha.len().min(8)];
+                let marker = if commit.is_breaking() { " [breaking]" } else { "" };
+                println!("  {} {}{}", short, commit.subject, marker);
+            }
+            if result.already_imported > 0 {
+                println!(
+                    "  {}",
+                    style.key_value("Already imported", &result.already_imported.to_string())
+                );
+            }
+            if result.skipped_without_context > 0 {
+                println!(
+                    "  {}",
+                    style.key_value(
+                        "Skipped (no body)",
+                        &result.skipped_without_context.to_string()
+                    )
+                );
+                println!(
+                    "{}",
+                    style.message(MessageType::Hint, "Use --all to include subject-only commits")
+                );
+            }
+            if let Some(rev_id) = &result.revision_id {
+                println!("  {}", style.key_value("Revision", rev_id));
+            }
+            Ok(())
+        }
+    }
+}
+
+// ============================================================================
+// Symbols command handler
+// ============================================================================
+
+fn handle_symbols(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    query: &str,
+    opts: &gik_core::SymbolSearchOptions,
+    branch: Option<String>,
+    json: bool,
+) -> Result<(), GikError> {
+    let Some(report) = engine.search_symbols(workspace, branch.as_deref(), query, opts)? else {
+        eprintln!(
+            "{}",
+            style.message(MessageType::Warn, "No Knowledge Graph found for this branch")
+        );
+        eprintln!(
+            "{}",
+            style.message(MessageType::Hint, "Run `gik commit` to build the KG first")
+        );
+        return Ok(());
+    };
+
+    if json {
+        let output = serde_json::to_string_pretty(&report).map_err(GikError::Json)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    if report.symbols.is_empty() {
+        println!(
+            "{}",
+            style.message(
+                MessageType::Info,
+                &format!(
+                    "No symbols match '{}' ({} indexed)",
+                    report.query, report.indexed_symbols
+                )
+            )
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<table::SymbolRow> = report
+        .symbols
+        .iter()
+        .map(|m| table::SymbolRow {
+            location: m.entry.location(),
+            kind: m.entry.kind.clone(),
+            name: m
+                .entry
+                .qualified_name
+                .clone()
+                .unwrap_or_else(|| m.entry.name.clone()),
+            match_kind: m.match_kind.as_str().to_string(),
+        })
+        .collect();
+    println!("{}", table::render_symbols_table(&rows));
+
+    if report.total_matches > report.symbols.len() {
+        println!();
+        println!(
+            "{}",
+            style.message(
+                MessageType::Hint,
+                &format!(
+                    "Showing {} of {} matches; use --limit to see more",
+                    report.symbols.len(),
+                    report.total_matches
+                )
+            )
+        );
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// KG command handlers
+// ============================================================================
+
+fn handle_kg(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    action: KgAction,
+) -> Result<(), GikError> {
+    match action {
+        KgAction::Check {
+            branch,
+            strict,
+            record,
+            json,
+        } => {
+            let opts = KgCheckOptions { record };
+            let report = engine.kg_check(workspace, branch.as_deref(), &opts)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).unwrap_or_default()
+                );
+            } else {
+                print_kg_check_report(style, &report);
+            }
+
+            if report.passed(strict) {
+                Ok(())
+            } else {
+                let count = if strict {
+                    report.errors + report.warnings
+                } else {
+                    report.errors
+                };
+                Err(GikError::KgCheckFailed { count })
+            }
+        }
+        KgAction::Import {
+            scip,
+            lsif,
+            replace,
+            clear,
+            branch,
+            json,
+        } => {
+            if clear {
+                let removed = engine.kg_clear_scip(workspace, branch.as_deref())?;
+                if json {
+                    println!("{}", serde_json::json!({ "cleared": removed }));
+                } else if removed {
+                    println!(
+                        "{}",
+                        style.message(MessageType::Ok, "Removed the imported SCIP index")
+                    );
+                } else {
+                    println!(
+                        "{}",
+                        style.message(MessageType::Skip, "No imported SCIP index")
+                    );
+                }
+                return Ok(());
+            }
+
+            // clap guarantees --scip or --lsif unless --clear
+            let Some(source) = scip.or(lsif) else {
+                return Ok(());
+            };
+            let mode = if replace {
+                PreciseMode::Replace
+            } else {
+                PreciseMode::Merge
+            };
+            let report = engine.kg_import_scip(workspace, branch.as_deref(), &source, mode)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).unwrap_or_default()
+                );
+            } else {
+                print_scip_import_report(style, &report);
+            }
+            Ok(())
+        }
+        KgAction::Export {
+            format,
+            output,
+            branch,
+            json,
+        } => handle_kg_export(style, engine, workspace, &format, output, branch, json),
+        KgAction::Query {
+            query,
+            branch,
+            json,
+        } => handle_kg_query(style, engine, workspace, &query, branch, json),
+        KgAction::Tests {
+            target,
+            branch,
+            json,
+        } => handle_kg_tests(style, engine, workspace, &target, branch, json),
+    }
+}
+
+/// Run `gik kg tests` and list the covering tests.
+fn handle_kg_tests(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    target: &str,
+    branch: Option<String>,
+    json: bool,
+) -> Result<(), GikError> {
+    let Some(report) = engine.kg_tests(workspace, branch.as_deref(), target)? else {
+        eprintln!(
+            "{}",
+            style.message(MessageType::Warn, "No Knowledge Graph found for this branch")
+        );
+        eprintln!(
+            "{}",
+            style.message(MessageType::Hint, "Run `gik commit` to build the KG first")
+        );
+        return Ok(());
+    };
+
+    if json {
+        let output = serde_json::to_string_pretty(&report).map_err(GikError::Json)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    if report.resolved.is_empty() {
+        println!(
+            "{}",
+            style.message(
+                MessageType::Info,
+                &format!("No file or symbol named '{}' in the KG", target)
+            )
+        );
+        return Ok(());
+    }
+    if report.tests.is_empty() {
+        println!(
+            "{}",
+            style.message(MessageType::Info, &format!("No tests cover {}", target))
+        );
+        return Ok(());
+    }
+
+    println!("{}", style.section("TESTS"));
+    println!();
+    for test in &report.tests {
+        println!(
+            "  {} ({})",
+            style.file_path(&test.path),
+            test.via.join(", ")
+        );
+        if !test.symbols.is_empty() {
+            println!(
+                "{}",
+                style.message_detail("Symbols", &test.symbols.join(", "))
+            );
+        }
+    }
+    println!();
+    println!(
+        "{}",
+        style.message(
+            MessageType::Info,
+            &format!("{} test file(s)", report.tests.len())
+        )
+    );
+
+    Ok(())
+}
+
+/// Run `gik kg query` and print the rows as a table or JSON.
+fn handle_kg_query(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    query: &str,
+    branch: Option<String>,
+    json: bool,
+) -> Result<(), GikError> {
+    let Some(report) = engine.kg_query(workspace, branch.as_deref(), query)? else {
+        eprintln!(
+            "{}",
+            style.message(MessageType::Warn, "No Knowledge Graph found for this branch")
+        );
+        eprintln!(
+            "{}",
+            style.message(MessageType::Hint, "Run `gik commit` to build the KG first")
+        );
+        return Ok(());
+    };
+
+    if json {
+        let output = serde_json::to_string_pretty(&report).map_err(GikError::Json)?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    if report.rows.is_empty() {
+        println!("{}", style.message(MessageType::Info, "No matches"));
+        return Ok(());
+    }
+
+    // Nodes and relationships show as their IDs
+    let cell = |value: &serde_json::Value| match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Object(obj) => match obj.get("id") {
+            Some(serde_json::Value::String(id)) => id.clone(),
+            _ => value.to_string(),
+        },
+        other => other.to_string(),
+    };
+    let rows: Vec<Vec<String>> = report
+        .rows
+        .iter()
+        .map(|row| report.columns.iter().map(|col| cell(&row[col])).collect())
+        .collect();
+    println!("{}", table::render_query_table(&report.columns, &rows));
+    println!();
+    println!(
+        "{}",
+        style.message(MessageType::Info, &format!("{} row(s)", rows.len()))
+    );
+
+    Ok(())
+}
+
+/// Run `gik kg export`. The graph goes to stdout unless `--output` is set.
+fn handle_kg_export(
+    style: &Style,
+    engine: &GikEngine,
+    workspace: &gik_core::Workspace,
+    format: &str,
+    output: Option<PathBuf>,
+    branch: Option<String>,
+    json: bool,
+) -> Result<(), GikError> {
+    let format: KgExportFormat = format.parse().map_err(GikError::InvalidArgument)?;
+    let branch = match branch {
+        Some(b) => b,
+        None => engine.current_branch(workspace)?.to_string(),
+    };
+
+    // Check before creating the output so an existing file is left alone
+    if !gik_core::kg_exists(workspace, &branch) {
+        eprintln!(
+            "{}",
+            style.message(
+                MessageType::Warn,
+                &format!("No Knowledge Graph found for branch '{}'", branch)
+            )
+        );
+        eprintln!(
+            "{}",
+            style.message(MessageType::Hint, "Run `gik commit` to build the KG first")
+        );
+        return Ok(());
+    }
+
+    let report = match (&output, format) {
+        (Some(dir), KgExportFormat::Neo4jCsv) => {
+            engine.export_kg_neo4j(workspace, Some(&branch), dir)?
+        }
+        (Some(path), _) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+            engine
+                .export_kg_graph(workspace, Some(&branch), format, &mut out)?
+                .map(|report| gik_core::KgExportReport {
+                    files: vec![path.clone()],
+                    ..report
+                })
+        }
+        (None, _) => {
+            let stdout = std::io::stdout();
+            let mut out = std::io::BufWriter::new(stdout.lock());
+            engine.export_kg_graph(workspace, Some(&branch), format, &mut out)?
+        }
+    };
+
+    // Without --output the graph itself is the output
+    let Some(report) = report.filter(|_| output.is_some()) else {
+        return Ok(());
+    };
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        style.message(
+            MessageType::Ok,
+            &format!(
+                "Exported {} node(s) and {} edge(s) from branch {} as {}",
+                report.nodes, report.edges, report.branch, report.format
+            )
+        )
+    );
+    for file in &report.files {
+        println!("  {}", style.key_value("File", &file.display().to_string()));
+    }
+    if report.skipped_edges > 0 {
+        println!(
+            "{}",
+            style.message(
+                MessageType::Warn,
+                &format!(
+                    "Skipped {} edge(s) whose endpoints are not in the graph",
+                    report.skipped_edges
+                )
+            )
+        );
+    }
+    Ok(())
+}
+
+fn print_scip_import_report(style: &Style, report: &ScipImportReport) {
+    let tool = report
+        .tool
+        .as_ref()
+        .map(|t| format!(" ({})", t))
+        .unwrap_or_default();
+    println!(
+        "{}",
+        style.message(
+            MessageType::Ok,
+            &format!(
+                "Imported {}{} into branch {} ({} mode)",
+                report.source.display(),
+                tool,
+                report.branch,
+                report.mode
+            )
+        )
+    );
+    println!(
+        "  {}",
+        style.key_value(
+            "Documents",
+            &format!(
+                "{} ({} indexed in code base)",
+                report.documents, report.files_matched
+            )
+        )
+    );
+    println!(
+        "  {}",
+        style.key_value("Symbols", &report.symbols.to_string())
+    );
+    println!(
+        "  {}",
+        style.key_value("References", &report.references.to_string())
+    );
+    println!(
+        "  {}",
+        style.key_value("Implementations", &report.implementations.to_string())
+    );
+    println!(
+        "  {}",
+        style.key_value(
+            "KG",
+            &format!(
+                "{} node(s), {} edge(s), {} precise symbol(s)",
+                report.nodes_written, report.edges_written, report.precise_nodes
+            )
+        )
+    );
+    if report.files_matched == 0 {
+        println!(
+            "{}",
+            style.message(
+                MessageType::Hint,
+                "No index documents match files in the code base; check the indexer's project root"
+            )
+        );
+    }
+}
+
+fn print_kg_check_report(style: &Style, report: &KgCheckReport) {
+    if report.files_checked == 0 {
+        println!(
+            "{}",
+            style.message(MessageType::Skip, "No files in the knowledge graph")
+        );
+        println!(
+            "{}",
+            style.message(MessageType::Hint, "Run `gik commit` to build the KG first")
+        );
+        return;
+    }
+
+    let rules = report
+        .rules_file
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "default rules".to_string());
+    println!(
+        "{}",
+        style.message(
+            MessageType::Info,
+            &format!(
+                "Checked {} file(s) and {} import edge(s) against {}",
+                report.files_checked, report.import_edges, rules
+            )
+        )
+    );
+
+    for violation in &report.violations {
+        let kind = match violation.severity {
+            RuleSeverity::Error => MessageType::Err,
+            RuleSeverity::Warning => MessageType::Warn,
+        };
+        println!(
+            "{}",
+            style.message(kind, &format!("[{}] {}", violation.rule, violation.message))
+        );
+    }
+
+    if report.violations.is_empty() {
+        println!("{}", style.message(MessageType::Ok, "No violations found"));
+    } else {
+        println!(
+            "  {}",
+            style.key_value(
+                "Violations",
+                &format!("{} error(s), {} warning(s)", report.errors, report.warnings)
+            )
+        );
+    }
+    if let Some(rev_id) = &report.revision_id {
+        println!("  {}", style.key_value("Revision", rev_id));
+    }
+}
@@ -10,6 +10,9 @@
 //! | `gik status` | `render_bases_table()` (compact) |
 //! | `gik stats` | `render_stats_breakdown()` |
 //! | `gik log` | `render_timeline_table()` |
+//! | `gik eval` | `render_eval_table()` |
+//! | `gik symbols` | `render_symbols_table()` |
+//! | `gik kg query` | `render_query_table()` |
 
 use comfy_table::presets::NOTHING;
 use comfy_table::{Cell, CellAlignment, ColumnConstraint, Table, Width};
@@ -36,6 +39,38 @@ pub struct CategoryCount {
     pub count: u64,
 }
 
+/// Retrieval metrics of one configuration for `gik eval`.
+#[derive(Debug, Clone)]
+pub struct EvalRow {
+    /// Configuration name
+    pub name: String,
+    /// Mean recall@k
+    pub recall: f64,
+    /// Mean reciprocal rank
+    pub mrr: f64,
+    /// Mean nDCG@k
+    pub ndcg: f64,
+    /// Mean latency in milliseconds
+    pub latency_mean_ms: f64,
+    /// 95th percentile latency in milliseconds
+    pub latency_p95_ms: u64,
+    /// Number of failed questions
+    pub failed: usize,
+}
+
+/// One matched symbol for `gik symbols`.
+#[derive(Debug, Clone)]
+pub struct SymbolRow {
+    /// Location as `path:line`
+    pub location: String,
+    /// Symbol kind (e.g., "function")
+    pub kind: String,
+    /// Symbol name, or qualified name when known
+    pub name: String,
+    /// How the query matched (exact, prefix, substring, fuzzy)
+    pub match_kind: String,
+}
+
 /// Base information for table rendering.
 ///
 /// This is a simplified view of base data for display purposes.
@@ -338,6 +373,141 @@ pub fn render_release_preview(categories: &[CategoryCount]) -> String {
     table.trim_fmt().to_string()
 }
 
+/// Render a retrieval comparison table for `gik eval`.
+///
+/// # Example Output
+///
+/// ```text
+/// CONFIG        RECALL@5    MRR   NDCG@5   MEAN MS   P95 MS   FAILED
+/// baseline         0.750  0.583    0.612       142      210        0
+/// dense-heavy      0.800  0.650    0.671       139      198        0
+/// ```
+pub fn render_eval_table(rows: &[EvalRow], k: usize) -> String {
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+
+    table.set_header(vec![
+        Cell::new("CONFIG"),
+        Cell::new(format!("RECALL@{}", k)).set_alignment(CellAlignment::Right),
+        Cell::new("MRR").set_alignment(CellAlignment::Right),
+        Cell::new(format!("NDCG@{}", k)).set_alignment(CellAlignment::Right),
+        Cell::new("MEAN MS").set_alignment(CellAlignment::Right),
+        Cell::new("P95 MS").set_alignment(CellAlignment::Right),
+        Cell::new("FAILED").set_alignment(CellAlignment::Right),
+    ]);
+
+    table.set_constraints(vec![
+        ColumnConstraint::LowerBoundary(Width::Fixed(12)), // CONFIG
+        ColumnConstraint::LowerBoundary(Width::Fixed(9)),  // RECALL@K
+        ColumnConstraint::LowerBoundary(Width::Fixed(6)),  // MRR
+        ColumnConstraint::LowerBoundary(Width::Fixed(7)),  // NDCG@K
+        ColumnConstraint::LowerBoundary(Width::Fixed(8)),  // MEAN MS
+        ColumnConstraint::LowerBoundary(Width::Fixed(7)),  // P95 MS
+        ColumnConstraint::LowerBoundary(Width::Fixed(6)),  // FAILED
+    ]);
+
+    for row in rows {
+        table.add_row(vec![
+            Cell::new(truncate_str(&row.name, 24)),
+            Cell::new(format!("{:.3}", row.recall)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.3}", row.mrr)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.3}", row.ndcg)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.0}", row.latency_mean_ms)).set_alignment(CellAlignment::Right),
+            Cell::new(row.latency_p95_ms).set_alignment(CellAlignment::Right),
+            Cell::new(row.failed).set_alignment(CellAlignment::Right),
+        ]);
+    }
+
+    table.trim_fmt().to_string()
+}
+
+/// Render matched symbols for `gik symbols`.
+///
+/// # Example Output
+///
+/// ```text
+/// LOCATION             KIND      NAME           MATCH
+/// src/parser.rs:3      struct    Parser         prefix
+/// src/parser.rs:10     function  parse_file     prefix
+/// ```
+pub fn render_symbols_table(rows: &[SymbolRow]) -> String {
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+
+    table.set_header(vec![
+        Cell::new("LOCATION"),
+        Cell::new("KIND"),
+        Cell::new("NAME"),
+        Cell::new("MATCH"),
+    ]);
+
+    table.set_constraints(vec![
+        ColumnConstraint::LowerBoundary(Width::Fixed(20)), // LOCATION
+        ColumnConstraint::LowerBoundary(Width::Fixed(8)),  // KIND
+        ColumnConstraint::LowerBoundary(Width::Fixed(12)), // NAME
+        ColumnConstraint::LowerBoundary(Width::Fixed(6)),  // MATCH
+    ]);
+
+    for row in rows {
+        table.add_row(vec![
+            Cell::new(&row.location),
+            Cell::new(&row.kind),
+            Cell::new(truncate_str(&row.name, 48)),
+            Cell::new(&row.match_kind),
+        ]);
+    }
+
+    table.trim_fmt().to_string()
+}
+
+/// Render `gik kg query` rows under their column names.
+///
+/// Column names are kept as written in the query's `RETURN` clause.
+///
+/// # Example Output
+///
+/// ```text
+/// f.path             importers
+/// src/db.ts                  4
+/// src/config.ts              2
+/// ```
+pub fn render_query_table(columns: &[String], rows: &[Vec<String>]) -> String {
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+
+    table.set_header(columns.iter().map(Cell::new).collect::<Vec<_>>());
+
+    for row in rows {
+        table.add_row(
+            row.iter()
+                .map(|value| {
+                    let cell = Cell::new(truncate_str(value, 80));
+                    // Right-align numbers, like the other tables
+                    if !value.is_empty() && value.parse::<f64>().is_ok() {
+                        cell.set_alignment(CellAlignment::Right)
+                    } else {
+                        cell
+                    }
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    table.trim_fmt().to_string()
+}
+
 /// Render a simple key-value metrics table.
 ///
 /// # Example Output
@@ -514,6 +684,57 @@ mod tests {
         assert!(output.contains("8"));
     }
 
+    #[test]
+    fn test_eval_table() {
+        let rows = vec![EvalRow {
+            name: "dense-heavy".to_string(),
+            recall: 0.75,
+            mrr: 0.5,
+            ndcg: 0.6123,
+            latency_mean_ms: 141.6,
+            latency_p95_ms: 210,
+            failed: 1,
+        }];
+
+        let output = render_eval_table(&rows, 5);
+
+        assert!(output.contains("RECALL@5"));
+        assert!(output.contains("NDCG@5"));
+        assert!(output.contains("dense-heavy"));
+        assert!(output.contains("0.750"));
+        assert!(output.contains("0.612"));
+        assert!(output.contains("142"));
+    }
+
+    #[test]
+    fn test_symbols_table() {
+        let rows = vec![SymbolRow {
+            location: "src/parser.rs:10".to_string(),
+            kind: "function".to_string(),
+            name: "parse_file".to_string(),
+            match_kind: "prefix".to_string(),
+        }];
+
+        let output = render_symbols_table(&rows);
+
+        assert!(output.contains("LOCATION"));
+        assert!(output.contains("src/parser.rs:10"));
+        assert!(output.contains("parse_file"));
+        assert!(output.contains("prefix"));
+    }
+
+    #[test]
+    fn test_query_table() {
+        let columns = vec!["f.path".to_string(), "importers".to_string()];
+        let rows = vec![vec!["src/db.ts".to_string(), "4".to_string()]];
+
+        let output = render_query_table(&columns, &rows);
+
+        assert!(output.contains("f.path"));
+        assert!(output.contains("importers"));
+        assert!(output.contains("src/db.ts"));
+    }
+
     #[test]
     fn test_empty_tables() {
         assert_eq!(render_bases_table(&[], false), "");
@@ -521,5 +742,8 @@ mod tests {
         assert_eq!(render_timeline_table(&[]), "");
         assert_eq!(render_memory_age_table(&[]), "");
         assert_eq!(render_release_preview(&[]), "");
+        assert_eq!(render_eval_table(&[], 5), "");
+        assert_eq!(render_symbols_table(&[]), "");
+        assert_eq!(render_query_table(&["n".to_string()], &[]), "");
     }
 }
@@ -1,6 +1,16 @@
 //! Shared test utilities for gik-cli integration tests.
+//!
+//! Each test binary compiles this module and uses only part of it.
+#![allow(dead_code)]
 
 use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+use gik_core::kg::{append_edges, append_nodes};
+use gik_core::workspace::Workspace;
+use gik_core::{KgEdge, KgNode};
 
 /// Get a Command for the gik binary.
 ///
@@ -12,3 +22,62 @@ use assert_cmd::Command;
 pub fn gik_cmd() -> Command {
     Command::cargo_bin("gik").expect("gik binary should exist")
 }
+
+/// Create a source file in src/ directory.
+pub fn create_source(dir: &Path, filename: &str, content: &str) {
+    let src_dir = dir.join("src");
+    fs::create_dir_all(&src_dir).expect("create src dir");
+    fs::write(src_dir.join(filename), content).expect("write source file");
+}
+
+/// Initialize a workspace, write `sources` as `(filename, content)` pairs
+/// under `src/`, then add and commit them with `message`.
+pub fn indexed_workspace(sources: &[(&str, &str)], message: &str) -> TempDir {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("init")
+        .assert()
+        .success();
+
+    for (filename, content) in sources {
+        create_source(workspace, filename, content);
+    }
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("add")
+        .arg("src/")
+        .assert()
+        .success();
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("commit")
+        .arg("-m")
+        .arg(message)
+        .assert()
+        .success();
+
+    temp
+}
+
+/// Initialize a workspace and write `nodes` and `edges` to the `main` KG.
+pub fn workspace_with_kg(nodes: &[KgNode], edges: &[KgEdge]) -> TempDir {
+    let temp = TempDir::new().expect("create temp dir");
+    gik_cmd()
+        .current_dir(temp.path())
+        .arg("init")
+        .assert()
+        .success();
+
+    let workspace = Workspace::from_root(temp.path()).expect("workspace");
+    append_nodes(&workspace, "main", nodes).expect("write nodes");
+    if !edges.is_empty() {
+        append_edges(&workspace, "main", edges).expect("write edges");
+    }
+
+    temp
+}
@@ -0,0 +1,251 @@
+//! Integration tests for the `gik eval` command.
+//!
+//! These tests validate the retrieval evaluation harness:
+//! - Running a suite under several configurations
+//! - JSON report structure
+//! - Suite validation errors
+//! - Saved asks, feedback and `--from-feedback` suites
+
+mod common;
+
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+use common::{gik_cmd, indexed_workspace};
+
+/// Setup an indexed workspace with two small modules.
+fn setup_indexed_workspace() -> TempDir {
+    indexed_workspace(
+        &[
+            (
+                "calculator.rs",
+                r#"//! Calculator module for basic arithmetic operations.
+
+/// Add two numbers together.
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+"#,
+            ),
+            (
+                "greeting.rs",
+                r#"//! Greeting helpers.
+
+/// Build a greeting for a user.
+pub fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+"#,
+            ),
+        ],
+        "feat: add modules",
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_eval_reports_metrics_per_configuration() {
+    let temp = setup_indexed_workspace();
+    let workspace = temp.path();
+
+    fs::write(
+        workspace.join("questions.yaml"),
+        r#"k: 3
+configurations:
+  - name: baseline
+  - name: dense-only
+    retrieval:
+      hybrid:
+        enabled: false
+questions:
+  - id: add
+    question: How are two numbers added?
+    expectedPaths: [src/calculator.rs]
+    expectedSymbols: [add]
+  - question: How is a user greeted?
+    expectedPaths: [src/greeting.rs]
+"#,
+    )
+    .expect("write suite");
+
+    let output = gik_cmd()
+        .current_dir(workspace)
+        .arg("eval")
+        .arg("--suite")
+        .arg("questions.yaml")
+        .arg("--json")
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let report: serde_json::Value =
+        serde_json::from_str(&stdout).expect("eval --json should return valid JSON");
+
+    assert_eq!(report["k"], 3);
+    assert_eq!(report["questions"], 2);
+
+    let configurations = report["configurations"]
+        .as_array()
+        .expect("configurations array");
+    assert_eq!(configurations.len(), 2);
+    assert_eq!(configurations[0]["name"], "baseline");
+    assert_eq!(configurations[1]["name"], "dense-only");
+
+    for config in configurations {
+        for metric in ["recallAtK", "mrr", "ndcgAtK"] {
+            let value = config[metric].as_f64().expect("metric is a number");
+            assert!((0.0..=1.0).contains(&value), "{} out of range", metric);
+        }
+        let results = config["results"].as_array().expect("results array");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["id"], "add");
+        assert_eq!(results[1]["id"], "q2");
+    }
+
+    // Table output with a configuration filter
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("eval")
+        .arg("--suite")
+        .arg("questions.yaml")
+        .arg("--configuration")
+        .arg("dense-only")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("RECALL@3"))
+        .stdout(predicate::str::contains("dense-only"))
+        .stdout(predicate::str::contains("baseline").not());
+}
+
+#[test]
+fn test_eval_rejects_invalid_suite() {
+    let temp = setup_indexed_workspace();
+    let workspace = temp.path();
+
+    fs::write(
+        workspace.join("questions.yaml"),
+        "questions:\n  - question: Anything?\n",
+    )
+    .expect("write suite");
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("eval")
+        .arg("--suite")
+        .arg("questions.yaml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expectedPaths"));
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("eval")
+        .arg("--suite")
+        .arg("missing.yaml")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_feedback_on_saved_ask_seeds_eval_suite() {
+    let temp = setup_indexed_workspace();
+    let workspace = temp.path();
+
+    // No judgements yet
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("eval")
+        .arg("--from-feedback")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("gik feedback"));
+
+    let output = gik_cmd()
+        .current_dir(workspace)
+        .arg("ask")
+        .arg("How are two numbers added?")
+        .arg("--save")
+        .arg("--json")
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let bundle: serde_json::Value = serde_json::from_str(&stdout).expect("ask --json");
+    let ask_id = bundle["askId"].as_str().expect("askId").to_string();
+    assert!(ask_id.starts_with("ask-"));
+
+    // Replay by position and by ID
+    for selector in ["1", ask_id.as_str()] {
+        let output = gik_cmd()
+            .current_dir(workspace)
+            .arg("log")
+            .arg("--kind")
+            .arg("ask")
+            .arg("--show")
+            .arg(selector)
+            .arg("--json")
+            .assert()
+            .success();
+        let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+        let replayed: serde_json::Value = serde_json::from_str(&stdout).expect("log --show");
+        assert_eq!(replayed["askId"], bundle["askId"]);
+        assert_eq!(replayed["question"], bundle["question"]);
+        assert_eq!(replayed["ragChunks"], bundle["ragChunks"]);
+    }
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("log")
+        .arg("--show")
+        .arg("1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--kind ask"));
+
+    let output = gik_cmd()
+        .current_dir(workspace)
+        .arg("feedback")
+        .arg(&ask_id)
+        .arg("--good")
+        .arg("src/calculator.rs")
+        .arg("--bad")
+        .arg("src/greeting.rs:1-6")
+        .arg("--json")
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let entries: serde_json::Value = serde_json::from_str(&stdout).expect("feedback --json");
+    let entries = entries.as_array().expect("entries array");
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["verdict"], "good");
+    assert_eq!(entries[1]["verdict"], "bad");
+    assert_eq!(entries[1]["startLine"], 1);
+
+    assert!(workspace
+        .join(".guided/knowledge/asks/feedback.jsonl")
+        .exists());
+
+    let output = gik_cmd()
+        .current_dir(workspace)
+        .arg("eval")
+        .arg("--from-feedback")
+        .arg("--json")
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect("eval --json");
+    assert_eq!(report["questions"], 1);
+    assert_eq!(report["configurations"][0]["results"][0]["id"], ask_id);
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("feedback")
+        .arg("ask-does-not-exist")
+        .arg("--good")
+        .arg("src/calculator.rs")
+        .assert()
+        .failure();
+}
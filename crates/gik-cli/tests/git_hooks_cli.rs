@@ -0,0 +1,209 @@
+//! Integration tests for Git integration commands.
+//!
+//! These tests validate:
+//! - `gik hooks install` / `gik hooks uninstall`
+//! - `gik hooks run post-commit` staging files from the last commit
+//! - `gik import git-log --dry-run` selecting commits with context
+
+mod common;
+
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+use common::gik_cmd;
+
+/// Run a git command in `dir`, panicking on failure.
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "Test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// Create a git repository. Installed hooks exit early when no `gik`
+/// binary is on PATH, so test commits never depend on one.
+fn init_repo(dir: &Path) {
+    git(dir, &["init", "-q", "-b", "main"]);
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_hooks_install_and_uninstall() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    init_repo(workspace);
+
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["hooks", "install"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed post-commit"))
+        .stdout(predicate::str::contains("Installed post-checkout"));
+
+    let hook = fs::read_to_string(workspace.join(".git/hooks/post-merge")).expect("read hook");
+    assert!(hook.contains("gik --quiet hooks run post-merge"));
+
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["hooks", "uninstall"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed post-merge"));
+
+    assert!(!workspace.join(".git/hooks/post-merge").exists());
+}
+
+#[test]
+fn test_hooks_install_honors_core_hooks_path() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    init_repo(workspace);
+    git(workspace, &["config", "core.hooksPath", ".githooks"]);
+
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["hooks", "install"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed post-commit"))
+        .stderr(predicate::str::contains(
+            "core.hooksPath is set to '.githooks'",
+        ));
+
+    assert!(workspace.join(".githooks/post-commit").exists());
+    assert!(!workspace.join(".git/hooks/post-commit").exists());
+}
+
+#[test]
+fn test_hooks_install_in_worktree() {
+    let temp = TempDir::new().expect("create temp dir");
+    let main = temp.path().join("main");
+    fs::create_dir_all(&main).unwrap();
+    init_repo(&main);
+    git(
+        &main,
+        &["commit", "-q", "--allow-empty", "-m", "chore: initial"],
+    );
+
+    let worktree = temp.path().join("wt");
+    git(
+        &main,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "feature",
+            worktree.to_str().unwrap(),
+        ],
+    );
+
+    gik_cmd()
+        .current_dir(&worktree)
+        .args(["hooks", "install"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed post-commit"));
+
+    // Worktrees share the main repository's hooks
+    assert!(main.join(".git/hooks/post-commit").exists());
+}
+
+#[test]
+fn test_hooks_install_outside_git_fails() {
+    let temp = TempDir::new().expect("create temp dir");
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["hooks", "install"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not a Git repository"));
+}
+
+#[test]
+fn test_hooks_run_post_commit_stages_changed_files() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    init_repo(workspace);
+
+    fs::create_dir_all(workspace.join("src")).unwrap();
+    fs::write(workspace.join("src/lib.rs"), "pub fn a() {}\n").unwrap();
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("init")
+        .assert()
+        .success();
+
+    git(workspace, &["add", "src/lib.rs"]);
+    git(workspace, &["commit", "-q", "-m", "feat: add lib"]);
+
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["hooks", "run", "post-commit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Staged 1 source(s) from post-commit",
+        ));
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/lib.rs"));
+}
+
+#[test]
+fn test_import_git_log_dry_run() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    init_repo(workspace);
+
+    fs::write(workspace.join("a.txt"), "a").unwrap();
+    git(workspace, &["add", "a.txt"]);
+    git(workspace, &["commit", "-q", "-m", "chore: initial"]);
+
+    fs::write(workspace.join("b.txt"), "b").unwrap();
+    git(workspace, &["add", "b.txt"]);
+    git(
+        workspace,
+        &[
+            "commit",
+            "-q",
+            "-m",
+            "feat!: switch storage",
+            "-m",
+            "Move to append-only files for crash safety.",
+        ],
+    );
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("init")
+        .assert()
+        .success();
+
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["import", "git-log", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would import 1 of 2 commit(s)"))
+        .stdout(predicate::str::contains("feat!: switch storage [breaking]"));
+}
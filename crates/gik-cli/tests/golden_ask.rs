@@ -19,44 +19,25 @@ mod common;
 use std::fs;
 use tempfile::TempDir;
 
-use common::gik_cmd;
-
-/// Create a source file in src/ directory.
-fn create_source(dir: &std::path::Path, filename: &str, content: &str) {
-    let src_dir = dir.join("src");
-    fs::create_dir_all(&src_dir).expect("create src dir");
-    fs::write(src_dir.join(filename), content).expect("write source file");
-}
+use common::{create_source, gik_cmd, indexed_workspace};
 
 /// Setup a minimal indexed workspace for golden tests.
 fn setup_indexed_workspace() -> TempDir {
-    let temp = TempDir::new().expect("create temp dir");
-    let workspace = temp.path();
-
-    // Init
-    gik_cmd()
-        .current_dir(workspace)
-        .arg("init")
-        .assert()
-        .success();
-
-    // Create source files
-    create_source(
-        workspace,
-        "main.rs",
-        r#"//! Main application entry point.
+    indexed_workspace(
+        &[
+            (
+                "main.rs",
+                r#"//! Main application entry point.
 //! Handles CLI argument parsing and application bootstrap.
 
 fn main() {
     println!("Hello, world!");
 }
 "#,
-    );
-
-    create_source(
-        workspace,
-        "lib.rs",
-        r#"//! Library module providing core functionality.
+            ),
+            (
+                "lib.rs",
+                r#"//! Library module providing core functionality.
 
 /// Add two numbers together.
 pub fn add(a: i32, b: i32) -> i32 {
@@ -68,25 +49,10 @@ pub fn subtract(a: i32, b: i32) -> i32 {
     a - b
 }
 "#,
-    );
-
-    // Add and commit
-    gik_cmd()
-        .current_dir(workspace)
-        .arg("add")
-        .arg("src/")
-        .assert()
-        .success();
-
-    gik_cmd()
-        .current_dir(workspace)
-        .arg("commit")
-        .arg("-m")
-        .arg("feat: initial project setup")
-        .assert()
-        .success();
-
-    temp
+            ),
+        ],
+        "feat: initial project setup",
+    )
 }
 
 // ============================================================================
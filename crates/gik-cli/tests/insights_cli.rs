@@ -0,0 +1,170 @@
+//! Integration tests for the `gik insights` command.
+//!
+//! These tests validate ask-log analytics:
+//! - Frequent questions and hot spots
+//! - Knowledge gaps and suggested paths to add
+//! - Stale answers after a file changes on disk
+
+mod common;
+
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+use common::gik_cmd;
+
+/// Setup an indexed workspace with two small modules and one unindexed note.
+fn setup_indexed_workspace() -> TempDir {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("init")
+        .assert()
+        .success();
+
+    let src_dir = workspace.join("src");
+    fs::create_dir_all(&src_dir).expect("create src dir");
+    fs::write(
+        src_dir.join("calculator.rs"),
+        "/// Add two numbers together.\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    )
+    .expect("write calculator");
+    fs::write(
+        src_dir.join("greeting.rs"),
+        "/// Build a greeting for a user.\npub fn greet(name: &str) -> String {\n    format!(\"Hello, {}!\", name)\n}\n",
+    )
+    .expect("write greeting");
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("add")
+        .arg("src/")
+        .assert()
+        .success();
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("commit")
+        .arg("-m")
+        .arg("feat: add modules")
+        .assert()
+        .success();
+
+    // Present on disk but never added
+    let notes_dir = workspace.join("notes");
+    fs::create_dir_all(&notes_dir).expect("create notes dir");
+    fs::write(
+        notes_dir.join("payments.md"),
+        "# Payments\n\nRefunds take 5 days.\n",
+    )
+    .expect("write note");
+
+    temp
+}
+
+fn ask(workspace: &std::path::Path, question: &str) {
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("ask")
+        .arg(question)
+        .arg("--json")
+        .assert()
+        .success();
+}
+
+fn insights_json(workspace: &std::path::Path, extra: &[&str]) -> serde_json::Value {
+    let output = gik_cmd()
+        .current_dir(workspace)
+        .arg("insights")
+        .args(extra)
+        .arg("--json")
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    serde_json::from_str(&stdout).expect("insights --json should return valid JSON")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_insights_reports_frequent_questions_and_hot_spots() {
+    let temp = setup_indexed_workspace();
+    let workspace = temp.path();
+
+    // Empty ask log
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("insights")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No asks recorded"));
+
+    ask(workspace, "How are two numbers added?");
+    ask(workspace, "how are two numbers added");
+    ask(workspace, "How is a user greeted?");
+
+    let report = insights_json(workspace, &[]);
+    assert_eq!(report["asks"], 3);
+
+    let frequent = report["frequentQuestions"].as_array().expect("frequent");
+    assert_eq!(frequent.len(), 1);
+    assert_eq!(frequent[0]["count"], 2);
+
+    let top_files = report["topFiles"].as_array().expect("topFiles");
+    assert!(!top_files.is_empty());
+    assert!(top_files.iter().any(|f| f["path"] == "src/calculator.rs"));
+    assert_eq!(report["topBases"][0]["base"], "code");
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("insights")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FREQUENT QUESTIONS"))
+        .stdout(predicate::str::contains("HOT SPOTS"));
+}
+
+#[test]
+fn test_insights_flags_gaps_and_stale_answers() {
+    let temp = setup_indexed_workspace();
+    let workspace = temp.path();
+
+    ask(workspace, "How are payments refunded?");
+
+    // Every ask counts as a gap above the maximum score
+    let report = insights_json(workspace, &["--low-score", "1.01"]);
+    let gaps = report["knowledgeGaps"].as_array().expect("gaps");
+    assert_eq!(gaps.len(), 1);
+    let suggestions = report["suggestions"].as_array().expect("suggestions");
+    assert!(suggestions
+        .iter()
+        .any(|s| s["path"] == "notes/payments.md" && s["reason"] == "gap"));
+    assert!(!suggestions.iter().any(|s| s["path"] == "src/greeting.rs"));
+
+    // Change an indexed file, then ask about it
+    fs::write(
+        workspace.join("src/calculator.rs"),
+        "/// Add two numbers together.\npub fn add(a: i64, b: i64) -> i64 {\n    a + b\n}\n\n/// Subtract.\npub fn sub(a: i64, b: i64) -> i64 {\n    a - b\n}\n",
+    )
+    .expect("modify calculator");
+    ask(workspace, "How are two numbers added?");
+
+    let report = insights_json(workspace, &[]);
+    let stale = report["staleAsks"].as_array().expect("staleAsks");
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0]["paths"][0], "src/calculator.rs");
+    assert_eq!(report["suggestions"][0]["path"], "src/calculator.rs");
+    assert_eq!(report["suggestions"][0]["reason"], "stale");
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("insights")
+        .arg("--since")
+        .arg("not-a-date")
+        .assert()
+        .failure();
+}
@@ -362,6 +362,19 @@ pub fn multiply(a: i32, b: i32) -> i32 {
         "Bundle should have revisionId"
     );
 
+    // === ASK --format markdown ===
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("ask")
+        .arg("--format")
+        .arg("markdown")
+        .arg("--max-tokens")
+        .arg("2000")
+        .arg("What arithmetic operations are available?")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("# Question"));
+
     // Verify ask log was created (branch-agnostic path)
     assert!(
         workspace
@@ -435,6 +448,82 @@ fn test_reindex_rebuilds_index() {
         .stdout(predicate::str::contains("TIMELINE"));
 }
 
+#[test]
+fn test_index_migrate_moves_base_between_backends() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+
+    gik_cmd()
+        .current_dir(workspace)
+        .arg("init")
+        .assert()
+        .success();
+
+    create_test_source(workspace, "lib.rs", "pub fn migrate_me() -> u32 { 42 }");
+
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["add", "src/"])
+        .assert()
+        .success();
+
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["commit", "-m", "chore: initial setup"])
+        .assert()
+        .success();
+
+    let meta_path = workspace.join(".guided/knowledge/main/bases/code/index/meta.json");
+    let read_meta = || -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(&meta_path).expect("read meta.json"))
+            .expect("parse meta.json")
+    };
+    let before = read_meta();
+
+    // === MIGRATE to hnsw ===
+    let output = gik_cmd()
+        .current_dir(workspace)
+        .args([
+            "index", "migrate", "--base", "code", "--to", "hnsw", "--json",
+        ])
+        .output()
+        .expect("run index migrate");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).expect("parse JSON");
+    assert_eq!(result["toBackend"], "hnsw");
+    assert_eq!(result["fromBackend"], before["backend"]);
+    assert!(result["vectors"].as_u64().unwrap() > 0);
+
+    let after = read_meta();
+    assert_eq!(after["backend"], "hnsw");
+    assert_eq!(after["embeddingModelId"], before["embeddingModelId"]);
+    assert_eq!(after["dimension"], before["dimension"]);
+
+    // Migrating to the current backend is rejected
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["index", "migrate", "--base", "code", "--to", "hnsw"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already uses the 'hnsw' backend"));
+
+    // === MIGRATE back to simple_file ===
+    gik_cmd()
+        .current_dir(workspace)
+        .args(["index", "migrate", "--base", "code", "--to", "simple_file"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Migrated base 'code' to simple_file",
+        ));
+    assert_eq!(read_meta()["backend"], "simple_file");
+}
+
 #[test]
 fn test_bases_command() {
     let temp = TempDir::new().expect("create temp dir");
@@ -0,0 +1,92 @@
+//! Integration tests for `gik kg check`.
+//!
+//! These tests validate:
+//! - Forbidden imports from `.guided/knowledge/rules.yaml` fail the check
+//! - `--json` emits the full report
+//! - Warnings only fail the check with `--strict`
+
+mod common;
+
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+use common::gik_cmd;
+use gik_core::{KgEdge, KgNode};
+
+/// Initialize a workspace and write a small import graph for `main`.
+fn workspace_with_kg() -> TempDir {
+    let file = |path: &str| {
+        KgNode::new(format!("file:{}", path), "file", path)
+            .with_props(serde_json::json!({ "base": "code", "path": path }))
+    };
+    let imports = |from: &str, to: &str| {
+        KgEdge::new(format!("file:{}", from), format!("file:{}", to), "imports")
+    };
+    common::workspace_with_kg(
+        &[
+            file("src/main.ts"),
+            file("src/domain/order.ts"),
+            file("src/infra/repo.ts"),
+            file("src/util/unused.ts"),
+        ],
+        &[
+            imports("src/main.ts", "src/domain/order.ts"),
+            imports("src/domain/order.ts", "src/infra/repo.ts"),
+        ],
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_kg_check_forbidden_import_fails_with_json_report() {
+    let temp = workspace_with_kg();
+    fs::write(
+        temp.path().join(".guided/knowledge/rules.yaml"),
+        "forbidden:\n  - name: domain-is-pure\n    from: \"src/domain/**\"\n    to: \"src/infra/**\"\n",
+    )
+    .unwrap();
+
+    let output = gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "check", "--branch", "main", "--json"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output).expect("JSON report");
+    assert_eq!(report["filesChecked"], 4);
+    assert_eq!(report["errors"], 1);
+    assert_eq!(report["warnings"], 1);
+    let forbidden = &report["violations"][0];
+    assert_eq!(forbidden["rule"], "forbidden");
+    assert_eq!(forbidden["name"], "domain-is-pure");
+    assert_eq!(report["violations"][1]["paths"][0], "src/util/unused.ts");
+}
+
+#[test]
+fn test_kg_check_warnings_fail_only_in_strict_mode() {
+    let temp = workspace_with_kg();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "check", "--branch", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("against default rules"))
+        .stdout(predicate::str::contains(
+            "src/util/unused.ts is not imported by any file",
+        ));
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "check", "--branch", "main", "--strict"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("KG check failed: 1 violation(s)"));
+}
@@ -0,0 +1,88 @@
+//! Integration tests for `gik kg export`.
+//!
+//! These tests validate:
+//! - GraphML goes to stdout by default
+//! - `--format neo4j` writes `nodes.csv` and `relationships.csv`
+//! - `--format neo4j` requires `--output`
+
+mod common;
+
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+use common::gik_cmd;
+use gik_core::{KgEdge, KgNode};
+
+/// Initialize a workspace and write a two-file KG for `main`.
+fn workspace_with_kg() -> TempDir {
+    common::workspace_with_kg(
+        &[
+            KgNode::new("file:src/main.rs", "file", "main.rs")
+                .with_props(serde_json::json!({ "path": "src/main.rs", "centrality": 0.5 })),
+            KgNode::new("file:src/lib.rs", "file", "lib.rs")
+                .with_props(serde_json::json!({ "path": "src/lib.rs", "centrality": 1.0 })),
+        ],
+        &[KgEdge::new(
+            "file:src/main.rs",
+            "file:src/lib.rs",
+            "imports",
+        )],
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_kg_export_graphml_to_stdout() {
+    let temp = workspace_with_kg();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "export", "--branch", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<graphml"))
+        .stdout(predicate::str::contains(
+            r#"attr.name="centrality" attr.type="double""#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"source="file:src/main.rs" target="file:src/lib.rs""#,
+        ));
+}
+
+#[test]
+fn test_kg_export_neo4j_writes_csv_tables() {
+    let temp = workspace_with_kg();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "export", "--format", "neo4j", "--branch", "main"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--output"));
+
+    let output = gik_cmd()
+        .current_dir(temp.path())
+        .args([
+            "kg", "export", "--format", "neo4j", "--branch", "main", "--output", "kg-csv", "--json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output).expect("JSON report");
+    assert_eq!(report["nodes"], 2);
+    assert_eq!(report["edges"], 1);
+
+    let nodes = fs::read_to_string(temp.path().join("kg-csv/nodes.csv")).unwrap();
+    assert!(nodes
+        .starts_with("id:ID,label,kind,branch,createdAt,updatedAt,centrality:double,path,:LABEL"));
+    assert!(nodes.contains("KgNode;File"));
+    let relationships = fs::read_to_string(temp.path().join("kg-csv/relationships.csv")).unwrap();
+    assert!(relationships.contains(r#""file:src/main.rs","file:src/lib.rs",IMPORTS"#));
+}
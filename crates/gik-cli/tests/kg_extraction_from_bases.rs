@@ -416,3 +416,77 @@ fn test_kg_extraction_handles_nested_imports() {
         "handler.ts should import utils.ts via ../utils"
     );
 }
+
+// ============================================================================
+// Ownership Tests
+// ============================================================================
+
+/// Run `git` in `dir` as `author`, panicking on failure.
+fn git_as(dir: &std::path::Path, author: &str, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(["-c", &format!("user.name={}", author)])
+        .args(["-c", "user.email=dev@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_kg_extraction_creates_owner_nodes() {
+    let (temp_dir, workspace) = create_workspace_with_code_base();
+    let root = temp_dir.path();
+
+    fs::create_dir_all(root.join(".github")).unwrap();
+    fs::write(
+        root.join(".github/CODEOWNERS"),
+        "* @acme/core\n/src/api/ @acme/api alice@acme.io\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src/api")).unwrap();
+    fs::write(root.join("src/utils.ts"), "export {}\n").unwrap();
+    fs::write(root.join("src/api/handler.ts"), "export {}\n").unwrap();
+    git_as(root, "Alice", &["init", "-q"]);
+    git_as(root, "Alice", &["add", "src"]);
+    git_as(root, "Alice", &["commit", "-q", "-m", "initial"]);
+
+    sync_without_symbols(&workspace, "main");
+
+    let nodes = read_all_nodes(&workspace, "main").unwrap();
+    let owners: Vec<_> = nodes.iter().filter(|n| n.kind == "owner").collect();
+    let mut ids: Vec<&str> = owners.iter().map(|n| n.id.as_str()).collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            "owner:@acme/api",
+            "owner:@acme/core",
+            "owner:alice@acme.io",
+            "owner:git:Alice"
+        ]
+    );
+
+    let edges = read_all_edges(&workspace, "main").unwrap();
+    let owners_of = |file: &str| {
+        let mut from: Vec<&str> = edges
+            .iter()
+            .filter(|e| e.kind == "owns" && e.to == file)
+            .map(|e| e.from.as_str())
+            .collect();
+        from.sort();
+        from
+    };
+    assert_eq!(
+        owners_of("file:src/api/handler.ts"),
+        vec!["owner:@acme/api", "owner:alice@acme.io", "owner:git:Alice"]
+    );
+    assert_eq!(owners_of("file:src/index.ts"), vec!["owner:@acme/core"]);
+
+    let index = gik_core::kg::read_ownership_index(&workspace, "main").unwrap();
+    let handler = index.get("src/api/handler.ts").unwrap();
+    let mut handler_owners = handler.owners.clone();
+    handler_owners.sort();
+    assert_eq!(handler_owners, vec!["@acme/api", "alice@acme.io"]);
+    assert_eq!(handler.authors, vec!["Alice"]);
+}
@@ -0,0 +1,67 @@
+//! Integration tests for `gik kg import`.
+//!
+//! These tests validate:
+//! - Files that are not SCIP indexes are rejected
+//! - `--scip` is required unless `--clear` is given
+//! - `--clear` without an imported index is a no-op
+
+mod common;
+
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+use common::gik_cmd;
+
+fn init_workspace() -> TempDir {
+    let temp = TempDir::new().expect("create temp dir");
+    gik_cmd()
+        .current_dir(temp.path())
+        .arg("init")
+        .assert()
+        .success();
+    temp
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_kg_import_rejects_invalid_index() {
+    let temp = init_workspace();
+    fs::write(temp.path().join("index.scip"), "not a scip index").unwrap();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "import", "--scip", "index.scip", "--branch", "main"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid code-intelligence index at index.scip",
+        ));
+
+    assert!(!temp
+        .path()
+        .join(".guided/knowledge/main/precise/scip.json")
+        .exists());
+}
+
+#[test]
+fn test_kg_import_requires_scip_or_clear() {
+    let temp = init_workspace();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "import"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--scip <FILE>"));
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "import", "--clear", "--branch", "main", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"cleared\":false"));
+}
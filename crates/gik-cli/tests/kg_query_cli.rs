@@ -0,0 +1,103 @@
+//! Integration tests for `gik kg query`.
+//!
+//! These tests validate:
+//! - Path patterns with property predicates, as a table and as JSON
+//! - Syntax errors fail with the column of the error
+
+mod common;
+
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+use common::gik_cmd;
+use gik_core::{KgEdge, KgNode};
+
+fn file(path: &str) -> KgNode {
+    KgNode::new(format!("file:{}", path), "file", path)
+        .with_props(serde_json::json!({ "path": path }))
+}
+
+/// Initialize a workspace with `api -> routes -> db` imports for `main`.
+fn workspace_with_kg() -> TempDir {
+    common::workspace_with_kg(
+        &[
+            file("src/api/users.ts"),
+            file("src/routes/users.ts"),
+            file("src/db.ts"),
+        ],
+        &[
+            KgEdge::new(
+                "file:src/api/users.ts",
+                "file:src/routes/users.ts",
+                "imports",
+            ),
+            KgEdge::new("file:src/routes/users.ts", "file:src/db.ts", "imports"),
+        ],
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_kg_query_table_and_json() {
+    let temp = workspace_with_kg();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args([
+            "kg",
+            "query",
+            "MATCH (f:file)-[:imports*]->(dep) WHERE f.path GLOB 'src/api/**' RETURN dep.path",
+            "--branch",
+            "main",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dep.path"))
+        .stdout(predicate::str::contains("src/routes/users.ts"))
+        .stdout(predicate::str::contains("src/db.ts"))
+        .stdout(predicate::str::contains("2 row(s)"));
+
+    let output = gik_cmd()
+        .current_dir(temp.path())
+        .args([
+            "kg",
+            "query",
+            "MATCH (f:file)<-[:imports]-(src) RETURN f.path AS file, count(src) AS importers \
+             ORDER BY file",
+            "--branch",
+            "main",
+            "--json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output).expect("JSON report");
+    assert_eq!(report["columns"], serde_json::json!(["file", "importers"]));
+    assert_eq!(
+        report["rows"],
+        serde_json::json!([
+            { "file": "src/db.ts", "importers": 1 },
+            { "file": "src/routes/users.ts", "importers": 1 },
+        ])
+    );
+}
+
+#[test]
+fn test_kg_query_syntax_error() {
+    let temp = workspace_with_kg();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "query", "MATCH (f:file RETURN f", "--branch", "main"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid KG query: expected ')' at column 15",
+        ));
+}
@@ -0,0 +1,157 @@
+//! Integration tests for test-to-code links and `gik kg tests`.
+//!
+//! These tests validate:
+//! - KG extraction marks test files and adds `tests` edges
+//! - `gik kg tests` lists covering tests for a file and a symbol, as a table
+//!   and as JSON
+//! - Unknown targets report nothing matched
+
+mod common;
+
+use std::fs;
+
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+use common::gik_cmd;
+use gik_core::kg::{
+    read_all_edges, read_all_nodes, sync_branch_kg, DefaultKgExtractor, KgExtractionConfig,
+};
+use gik_core::workspace::Workspace;
+
+fn source(id: &str, path: &str, text: &str) -> String {
+    serde_json::json!({
+        "id": id,
+        "base": "code",
+        "branch": "main",
+        "filePath": path,
+        "startLine": 1,
+        "endLine": text.lines().count(),
+        "text": text,
+        "vectorId": 1,
+        "indexedAt": "2025-01-01T00:00:00Z",
+        "revisionId": "rev-001",
+        "sourceId": id,
+    })
+    .to_string()
+}
+
+/// Initialize a workspace whose `main` KG has a module, its test and an
+/// unrelated test that calls one of its functions.
+fn workspace_with_tests() -> TempDir {
+    let temp = TempDir::new().expect("create temp dir");
+    gik_cmd()
+        .current_dir(temp.path())
+        .arg("init")
+        .assert()
+        .success();
+
+    let workspace = Workspace::from_root(temp.path()).expect("workspace");
+    let code_base = workspace.knowledge_root().join("main/bases/code");
+    fs::create_dir_all(&code_base).expect("create code base");
+    let sources = [
+        source(
+            "c1",
+            "src/users.ts",
+            "export function createUser(name: string) {\n  return { name };\n}\n",
+        ),
+        source(
+            "c2",
+            "src/users.test.ts",
+            "import { createUser } from './users';\n\ntest('creates', () => createUser('a'));\n",
+        ),
+        source(
+            "c3",
+            "tests/api.spec.ts",
+            "it('signs up', () => {\n  createUser('b');\n});\n",
+        ),
+    ];
+    fs::write(code_base.join("sources.jsonl"), sources.join("\n")).expect("write sources");
+
+    let cfg = KgExtractionConfig::default().without_endpoints();
+    sync_branch_kg(&workspace, "main", &DefaultKgExtractor::new(), &cfg).expect("sync KG");
+
+    temp
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_kg_extraction_links_tests() {
+    let temp = workspace_with_tests();
+    let workspace = Workspace::from_root(temp.path()).expect("workspace");
+
+    let nodes = read_all_nodes(&workspace, "main").unwrap();
+    let is_test = |path: &str| {
+        nodes
+            .iter()
+            .find(|n| n.kind == "file" && n.props["path"] == path)
+            .map(|n| n.props["isTest"] == true)
+            .unwrap()
+    };
+    assert!(is_test("src/users.test.ts"));
+    assert!(is_test("tests/api.spec.ts"));
+    assert!(!is_test("src/users.ts"));
+
+    let edges = read_all_edges(&workspace, "main").unwrap();
+    let link = edges
+        .iter()
+        .find(|e| {
+            e.kind == "tests" && e.from == "file:src/users.test.ts" && e.to == "file:src/users.ts"
+        })
+        .expect("tests edge");
+    assert_eq!(
+        link.props["via"],
+        serde_json::json!(["import", "name", "symbol"])
+    );
+    assert!(edges.iter().any(|e| e.kind == "tests"
+        && e.from == "file:tests/api.spec.ts"
+        && e.to.ends_with(":function:createUser")));
+}
+
+#[test]
+fn test_kg_tests_for_file_and_symbol() {
+    let temp = workspace_with_tests();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "tests", "src/users.ts", "--branch", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "src/users.test.ts (import, name, symbol)",
+        ))
+        .stdout(predicate::str::contains("tests/api.spec.ts (symbol)"))
+        .stdout(predicate::str::contains("Symbols: createUser"))
+        .stdout(predicate::str::contains("2 test file(s)"));
+
+    let output = gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "tests", "createUser", "--branch", "main", "--json"])
+        .output()
+        .expect("run gik");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(report["target"], "createUser");
+    let paths: Vec<&str> = report["tests"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["src/users.test.ts", "tests/api.spec.ts"]);
+}
+
+#[test]
+fn test_kg_tests_unknown_target() {
+    let temp = workspace_with_tests();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["kg", "tests", "src/missing.ts", "--branch", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No file or symbol named"));
+}
@@ -0,0 +1,127 @@
+//! Integration tests for `gik symbols`.
+//!
+//! These tests validate:
+//! - Exact and prefix matches rank before fuzzy ones, as `path:line`
+//! - `--kind` and `--lang` filters
+//! - A missing KG is reported without failing
+
+mod common;
+
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+use common::{gik_cmd, workspace_with_kg};
+use gik_core::KgNode;
+
+fn symbol(path: &str, kind: &str, name: &str, line: u32) -> KgNode {
+    KgNode::new(format!("sym:rs:{}:{}:{}", path, kind, name), kind, name).with_props(
+        serde_json::json!({
+            "path": path,
+            "language": if path.ends_with(".ts") { "ts" } else { "rs" },
+            "symbolKind": kind,
+            "startLine": line,
+        }),
+    )
+}
+
+/// Initialize a workspace and write a few symbol nodes for `main`.
+fn workspace_with_symbols() -> TempDir {
+    workspace_with_kg(
+        &[
+            KgNode::new("file:src/parser.rs", "file", "parser.rs")
+                .with_props(serde_json::json!({ "path": "src/parser.rs" })),
+            symbol("src/parser.rs", "struct", "Parser", 3),
+            symbol("src/parser.rs", "function", "parse_file", 10),
+            symbol("src/config.rs", "function", "print_frame", 12),
+            symbol("web/app.ts", "function", "parseFile", 7),
+        ],
+        &[],
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_symbols_ranks_matches_with_locations() {
+    let temp = workspace_with_symbols();
+
+    let output = gik_cmd()
+        .current_dir(temp.path())
+        .args(["symbols", "pf", "--branch", "main", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output).expect("JSON report");
+    assert_eq!(report["indexedSymbols"], 4);
+    let names: Vec<&str> = report["symbols"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["parseFile", "parse_file", "print_frame"]);
+    assert_eq!(report["symbols"][0]["matchKind"], "fuzzy");
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["symbols", "parser", "--branch", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/parser.rs:3"))
+        .stdout(predicate::str::contains("exact"));
+
+    assert!(temp
+        .path()
+        .join(".guided/knowledge/main/kg/symbols.json")
+        .exists());
+}
+
+#[test]
+fn test_symbols_filters_by_kind_and_language() {
+    let temp = workspace_with_symbols();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["symbols", "p", "--kind", "struct", "--branch", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Parser"))
+        .stdout(predicate::str::contains("parse_file").not());
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args([
+            "symbols",
+            "parse",
+            "--lang",
+            "typescript",
+            "--branch",
+            "main",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("web/app.ts:7"))
+        .stdout(predicate::str::contains("src/parser.rs").not());
+}
+
+#[test]
+fn test_symbols_without_kg() {
+    let temp = TempDir::new().expect("create temp dir");
+    gik_cmd()
+        .current_dir(temp.path())
+        .arg("init")
+        .assert()
+        .success();
+
+    gik_cmd()
+        .current_dir(temp.path())
+        .args(["symbols", "parse", "--branch", "main"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No Knowledge Graph found"));
+}
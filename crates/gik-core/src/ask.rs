@@ -22,16 +22,18 @@ use serde::{Deserialize, Serialize};
 
 use crate::base::{base_root, load_base_sources, BaseSourceEntry};
 use crate::bm25::{load_bm25_index, rrf_fusion, Bm25Index, HybridSearchConfig};
-use crate::config::{DevicePreference, GlobalConfig};
+use crate::config::{DevicePreference, GlobalConfig, ProjectConfig};
 use crate::embedding::{create_backend, EmbeddingBackend};
 use crate::errors::GikError;
+use crate::kg::centrality::read_centrality_by_path;
+use crate::kg::{read_ownership_index, FileOwnership, OwnershipIndex};
 use crate::query_expansion::{average_embeddings, QueryExpander};
 use crate::reranker::get_or_init_reranker_backend;
 use crate::stack::{read_stats_json, read_tech_jsonl, StackStats, StackTechEntry};
 use crate::timeline::{read_head, RevisionId};
 use crate::vector_index::{
-    load_index_meta, open_vector_index, VectorIndexBackend, VectorIndexBackendKind,
-    VectorIndexConfig, VectorMetric, VectorSearchResult,
+    load_index_meta, open_vector_index, VectorIndexBackend, VectorIndexConfig, VectorSearchResult,
+    INDEX_META_FILENAME,
 };
 use crate::workspace::{BranchName, Workspace};
 
@@ -51,6 +53,10 @@ const FILENAME_EXACT_BOOST: f32 = 0.50;
 /// Boost applied to chunks with partial path match.
 const FILENAME_PARTIAL_BOOST: f32 = 0.25;
 
+/// Factor applied to `top_k` per base when filtering by owner, so enough
+/// candidates survive the filter.
+const OWNER_FILTER_OVERSAMPLE: usize = 4;
+
 // ============================================================================
 // FilenameMatch - for filename-aware ask
 // ============================================================================
@@ -208,6 +214,10 @@ pub fn detect_filename_in_query(query: &str) -> Option<FilenameMatch> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AskContextBundle {
+    /// Identifier of this ask in the ask log (set by the engine).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ask_id: Option<String>,
+
     /// The knowledge revision used for this query.
     pub revision_id: RevisionId,
 
@@ -277,6 +287,10 @@ pub struct RagChunk {
     /// Cross-encoder reranker score (when reranker is used).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reranker_score: Option<f32>,
+
+    /// `CODEOWNERS` owners and primary Git authors of the file, from the KG.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ownership: Option<FileOwnership>,
 }
 
 impl RagChunk {
@@ -291,6 +305,7 @@ impl RagChunk {
             snippet: entry.text.clone().unwrap_or_default(),
             dense_score: Some(result.score),
             reranker_score: None,
+            ownership: None,
         }
     }
 }
@@ -340,6 +355,10 @@ pub struct MemoryEvent {
     /// Relevance score from vector search.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub score: Option<f32>,
+
+    /// Source-specific metadata (e.g., `gitCommit` and `paths` for commit context).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Value>,
 }
 
 impl MemoryEvent {
@@ -395,6 +414,9 @@ impl MemoryEvent {
             })
             .unwrap_or_default();
 
+        // Extract source-specific metadata (optional)
+        let memory_extra = extra.get("extra").filter(|v| !v.is_null()).cloned();
+
         // Get text from entry
         let text = entry.text.clone().unwrap_or_default();
 
@@ -407,6 +429,7 @@ impl MemoryEvent {
             source,
             tags,
             score: Some(score),
+            extra: memory_extra,
         })
     }
 }
@@ -682,6 +705,14 @@ pub struct AskOptions {
     /// When set, this takes precedence over the config file's `retrieval.reranker.finalK`.
     /// This allows `--top-k` to control the final output count.
     pub final_k: Option<usize>,
+
+    /// Save the full bundle next to the ask log entry so it can be replayed
+    /// with `gik log --kind ask --show` and judged with `gik feedback`.
+    pub save_bundle: bool,
+
+    /// Only keep chunks from files owned or primarily authored by this owner
+    /// (`@user`, `@org/team`, an email or a Git author name).
+    pub owner: Option<String>,
 }
 
 impl Default for AskOptions {
@@ -693,6 +724,8 @@ impl Default for AskOptions {
             include_stack: true,
             min_score: None,
             final_k: None,
+            save_bundle: false,
+            owner: None,
         }
     }
 }
@@ -736,6 +769,18 @@ impl AskOptions {
         self.final_k = Some(final_k);
         self
     }
+
+    /// Set whether to save the full bundle for later replay and feedback.
+    pub fn with_save_bundle(mut self, save: bool) -> Self {
+        self.save_bundle = save;
+        self
+    }
+
+    /// Only return chunks from files owned or authored by `owner`.
+    pub fn with_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
 }
 
 // ============================================================================
@@ -850,6 +895,11 @@ pub fn run_ask(
 
     // 5. Search each base and collect results
     // Memory base is handled separately to populate memory_events instead of rag_chunks
+    let search_k = if opts.owner.is_some() {
+        opts.top_k * OWNER_FILTER_OVERSAMPLE
+    } else {
+        opts.top_k
+    };
     let mut all_chunks: Vec<RagChunk> = Vec::new();
     let mut memory_events: Vec<MemoryEvent> = Vec::new();
     let mut per_base_counts: Vec<AskBaseCount> = Vec::new();
@@ -885,7 +935,7 @@ pub fn run_ask(
                 branch,
                 base_name,
                 &query_embedding,
-                opts.top_k,
+                search_k,
                 global_config,
                 retrieval_config,
                 filename_match.as_ref(),
@@ -924,6 +974,38 @@ pub fn run_ask(
             .unwrap_or(std::cmp::Ordering::Equal)
     });
 
+    // 6a. Centrality prior: boost chunks from files central in the import graph.
+    // Applied before the owner filter and reranker cut the candidate list.
+    let centrality_cfg = &retrieval_config.centrality;
+    if centrality_cfg.enabled && centrality_cfg.weight > 0.0 && !all_chunks.is_empty() {
+        match read_centrality_by_path(workspace, branch.as_str()) {
+            Ok(by_path) => {
+                let boosted =
+                    apply_centrality_prior(&mut all_chunks, &by_path, centrality_cfg.weight);
+                tracing::debug!("Centrality prior boosted {} chunks", boosted);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load KG centrality: {}. Skipping prior.", e);
+            }
+        }
+    }
+
+    // 6a-2. Load file ownership from the KG and apply the owner filter
+    let ownership = read_ownership_index(workspace, branch.as_str()).unwrap_or_else(|e| {
+        tracing::warn!("Failed to load KG ownership: {}. Skipping owners.", e);
+        OwnershipIndex::default()
+    });
+    if let Some(owner) = &opts.owner {
+        let before_count = all_chunks.len();
+        all_chunks = filter_chunks_by_owner(all_chunks, &ownership, owner, opts.top_k);
+        tracing::debug!(
+            "Owner filter '{}' kept {} of {} chunks",
+            owner,
+            all_chunks.len(),
+            before_count
+        );
+    }
+
     // 6b. Apply reranker if enabled (Phase 8.2)
     // Use opts.final_k to override config's finalK if specified (e.g., from --top-k CLI flag)
     let (reranker_used, rerank_time_ms, filename_detected) =
@@ -945,6 +1027,9 @@ pub fn run_ask(
 
     // 6d. Deduplicate overlapping chunks (keep highest-scored)
     all_chunks = deduplicate_overlapping_chunks(all_chunks);
+    for chunk in &mut all_chunks {
+        chunk.ownership = ownership.get(&chunk.path).cloned();
+    }
 
     // 7. Build stack summary if requested (Phase 8.4: scoped to RAG context)
     let stack_summary = if opts.include_stack {
@@ -996,6 +1081,7 @@ pub fn run_ask(
     );
 
     Ok(AskContextBundle {
+        ask_id: None,
         revision_id,
         question: opts.question,
         bases: bases_to_query,
@@ -1055,6 +1141,27 @@ fn deduplicate_overlapping_chunks(mut chunks: Vec<RagChunk>) -> Vec<RagChunk> {
     kept
 }
 
+/// Keep chunks from files owned or authored by `owner`, at most `top_k` per base.
+///
+/// Chunks must already be sorted by score.
+fn filter_chunks_by_owner(
+    chunks: Vec<RagChunk>,
+    ownership: &OwnershipIndex,
+    owner: &str,
+    top_k: usize,
+) -> Vec<RagChunk> {
+    let mut per_base: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    chunks
+        .into_iter()
+        .filter(|chunk| ownership.get(&chunk.path).is_some_and(|o| o.matches(owner)))
+        .filter(|chunk| {
+            let count = per_base.entry(chunk.base.clone()).or_default();
+            *count += 1;
+            *count <= top_k
+        })
+        .collect()
+}
+
 /// Check if two line ranges overlap.
 ///
 /// Two ranges [a_start, a_end] and [b_start, b_end] overlap if:
@@ -1116,6 +1223,27 @@ fn create_ask_backend(
     create_backend(config, device_pref)
 }
 
+/// Resolve the vector index config used to query a base.
+///
+/// Starts from the configured index settings (so per-base HNSW `efSearch`
+/// applies) and takes the backend from the index metadata when present.
+fn resolve_query_index_config(
+    workspace: &Workspace,
+    base_name: &str,
+    index_dir: &Path,
+    embedding_config: &crate::embedding::EmbeddingConfig,
+    global_config: &GlobalConfig,
+) -> Result<VectorIndexConfig, GikError> {
+    let project_config = ProjectConfig::load_from_workspace(workspace.root())?;
+    let mut vector_config =
+        project_config.resolve_vector_index_config(base_name, embedding_config, global_config);
+
+    if let Ok(Some(meta)) = load_index_meta(&index_dir.join(INDEX_META_FILENAME)) {
+        vector_config.backend = meta.backend.parse().unwrap_or_default();
+    }
+    Ok(vector_config)
+}
+
 /// Search a single base for relevant chunks using hybrid search (dense + BM25).
 ///
 /// When hybrid search is enabled:
@@ -1145,26 +1273,13 @@ fn search_base(
 
     // Get embedding config for this base from global config
     let embedding_config = global_config.resolve_embedding_config(base_name);
-    let dimension = embedding_config.dimension.unwrap_or(384);
-
-    // Create default vector config with resolved embedding dimension
-    let mut vector_config = VectorIndexConfig::new(
-        VectorIndexBackendKind::SimpleFile,
-        VectorMetric::Cosine,
-        dimension,
+    let vector_config = resolve_query_index_config(
+        workspace,
         base_name,
-    );
-
-    // Check existing index metadata to determine actual backend
-    let meta_path = index_dir.join("meta.json");
-    if let Ok(Some(meta)) = load_index_meta(&meta_path) {
-        // Use the backend from existing index metadata
-        if meta.backend == "simple_file" {
-            vector_config.backend = VectorIndexBackendKind::SimpleFile;
-        } else if meta.backend == "lancedb" {
-            vector_config.backend = VectorIndexBackendKind::LanceDb;
-        }
-    }
+        &index_dir,
+        &embedding_config,
+        global_config,
+    )?;
 
     // Load the vector index using the unified factory
     let index: Box<dyn VectorIndexBackend> =
@@ -1248,6 +1363,7 @@ fn search_base(
                     score: 0.5,
                     dense_score: Some(0.5),
                     reranker_score: None,
+                    ownership: None,
                 };
                 tracing::debug!(
                     "Pre-filter: adding '{}' matching filename pattern '{}'",
@@ -1364,25 +1480,13 @@ fn search_base_hybrid_with_query(
 
     // Get embedding config for this base from global config
     let embedding_config = global_config.resolve_embedding_config(base_name);
-    let dimension = embedding_config.dimension.unwrap_or(384);
-
-    // Create default vector config
-    let mut vector_config = VectorIndexConfig::new(
-        VectorIndexBackendKind::SimpleFile,
-        VectorMetric::Cosine,
-        dimension,
+    let vector_config = resolve_query_index_config(
+        workspace,
         base_name,
-    );
-
-    // Check existing index metadata
-    let meta_path = index_dir.join("meta.json");
-    if let Ok(Some(meta)) = load_index_meta(&meta_path) {
-        if meta.backend == "simple_file" {
-            vector_config.backend = VectorIndexBackendKind::SimpleFile;
-        } else if meta.backend == "lancedb" {
-            vector_config.backend = VectorIndexBackendKind::LanceDb;
-        }
-    }
+        &index_dir,
+        &embedding_config,
+        global_config,
+    )?;
 
     // Load the vector index using the unified factory
     let index: Box<dyn VectorIndexBackend> =
@@ -1465,6 +1569,7 @@ fn search_base_hybrid_with_query(
                             1.0 / (r as f32 + 1.0)
                         }),
                         reranker_score: None,
+                        ownership: None,
                     }
                 })
         })
@@ -1492,26 +1597,13 @@ fn search_memory_base(
 
     // Get embedding config for memory base from global config
     let embedding_config = global_config.resolve_embedding_config(crate::memory::MEMORY_BASE_NAME);
-    let dimension = embedding_config.dimension.unwrap_or(384);
-
-    // Create default vector config with resolved embedding dimension
-    let mut vector_config = VectorIndexConfig::new(
-        VectorIndexBackendKind::SimpleFile,
-        VectorMetric::Cosine,
-        dimension,
+    let vector_config = resolve_query_index_config(
+        workspace,
         crate::memory::MEMORY_BASE_NAME,
-    );
-
-    // Check existing index metadata to determine actual backend
-    let meta_path = index_dir.join("meta.json");
-    if let Ok(Some(meta)) = load_index_meta(&meta_path) {
-        // Use the backend from existing index metadata
-        if meta.backend == "simple_file" {
-            vector_config.backend = VectorIndexBackendKind::SimpleFile;
-        } else if meta.backend == "lancedb" {
-            vector_config.backend = VectorIndexBackendKind::LanceDb;
-        }
-    }
+        &index_dir,
+        &embedding_config,
+        global_config,
+    )?;
 
     // Load the vector index using the unified factory
     let index: Box<dyn VectorIndexBackend> =
@@ -1738,6 +1830,35 @@ fn compute_hybrid_score(dense_score: f32, reranker_score: f32) -> f32 {
     ALPHA * norm_reranker + BETA * norm_dense
 }
 
+/// Add `weight * centrality` of each chunk's file to its score and re-sort.
+///
+/// Files without a centrality score (no KG, or outside the import graph) are
+/// left as-is. Returns the number of boosted chunks.
+fn apply_centrality_prior(
+    chunks: &mut [RagChunk],
+    centrality: &std::collections::HashMap<String, f32>,
+    weight: f32,
+) -> usize {
+    let mut boosted = 0;
+    for chunk in chunks.iter_mut() {
+        if let Some(&c) = centrality.get(&chunk.path) {
+            if c > 0.0 {
+                chunk.score += weight * c;
+                boosted += 1;
+            }
+        }
+    }
+
+    if boosted > 0 {
+        chunks.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+    boosted
+}
+
 // ============================================================================
 
 /// Apply cross-encoder reranking to chunks.
@@ -2068,6 +2189,37 @@ mod tests {
         assert!(summary.services.is_empty());
     }
 
+    #[test]
+    fn test_resolve_query_index_config_rejects_malformed_project_config() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let config_path = ProjectConfig::config_path_for_workspace(temp.path());
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        let workspace = Workspace::from_root(temp.path()).unwrap();
+        let global_config = GlobalConfig::default_for_testing();
+        let embedding_config = global_config.resolve_embedding_config("code");
+        let index_dir = temp.path().join("index");
+
+        assert!(resolve_query_index_config(
+            &workspace,
+            "code",
+            &index_dir,
+            &embedding_config,
+            &global_config
+        )
+        .is_ok());
+
+        // A typo must surface instead of silently selecting the default backend
+        std::fs::write(&config_path, "indexes: [unterminated\n").unwrap();
+        let result = resolve_query_index_config(
+            &workspace,
+            "code",
+            &index_dir,
+            &embedding_config,
+            &global_config,
+        );
+        assert!(matches!(result, Err(GikError::InvalidProjectConfig(_))));
+    }
+
     #[test]
     fn test_rag_chunk_serialization() {
         let chunk = RagChunk {
@@ -2079,6 +2231,7 @@ mod tests {
             snippet: "fn main() {}".to_string(),
             dense_score: Some(0.85),
             reranker_score: Some(0.12),
+            ownership: None,
         };
 
         let json = serde_json::to_string(&chunk).unwrap();
@@ -2089,6 +2242,90 @@ mod tests {
         assert!(json.contains("\"rerankerScore\":0.12"));
     }
 
+    #[test]
+    fn test_apply_centrality_prior() {
+        let chunk = |path: &str, score: f32| RagChunk {
+            base: "code".to_string(),
+            score,
+            path: path.to_string(),
+            start_line: 1,
+            end_line: 10,
+            snippet: String::new(),
+            dense_score: Some(score),
+            reranker_score: None,
+            ownership: None,
+        };
+        let mut chunks = vec![
+            chunk("src/helpers/pad.rs", 0.62),
+            chunk("src/engine.rs", 0.60),
+        ];
+        let centrality: std::collections::HashMap<String, f32> = [
+            ("src/engine.rs".to_string(), 1.0),
+            ("src/helpers/pad.rs".to_string(), 0.05),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(apply_centrality_prior(&mut chunks, &centrality, 0.1), 2);
+        // Similar text, but the core module now ranks first
+        assert_eq!(chunks[0].path, "src/engine.rs");
+        assert!((chunks[0].score - 0.70).abs() < 1e-6);
+        assert!((chunks[1].score - 0.625).abs() < 1e-6);
+
+        // Files outside the graph keep their score
+        let mut other = vec![chunk("README.md", 0.5)];
+        assert_eq!(apply_centrality_prior(&mut other, &centrality, 0.1), 0);
+        assert_eq!(other[0].score, 0.5);
+    }
+
+    #[test]
+    fn test_filter_chunks_by_owner() {
+        use crate::kg::{KgEdge, KgNode};
+
+        let chunk = |base: &str, path: &str| RagChunk {
+            base: base.to_string(),
+            score: 0.5,
+            path: path.to_string(),
+            start_line: 1,
+            end_line: 10,
+            snippet: String::new(),
+            dense_score: None,
+            reranker_score: None,
+            ownership: None,
+        };
+        let owns = |owner: &str, path: &str, source: &str| {
+            KgEdge::new(owner, format!("file:{}", path), "owns")
+                .with_props(serde_json::json!({ "path": path, "source": source }))
+        };
+        let index = OwnershipIndex::from_graph(
+            &[
+                KgNode::new("owner:@payments", "owner", "@payments"),
+                KgNode::new("owner:git:Alice", "owner", "Alice"),
+            ],
+            &[
+                owns("owner:@payments", "src/billing.rs", "codeowners"),
+                owns("owner:@payments", "src/invoice.rs", "codeowners"),
+                owns("owner:git:Alice", "docs/billing.md", "git"),
+            ],
+        );
+        let chunks = vec![
+            chunk("code", "src/billing.rs"),
+            chunk("code", "src/main.rs"),
+            chunk("code", "src/invoice.rs"),
+            chunk("docs", "docs/billing.md"),
+        ];
+
+        let kept = filter_chunks_by_owner(chunks.clone(), &index, "payments", 1);
+        let paths: Vec<&str> = kept.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/billing.rs"]);
+
+        let kept = filter_chunks_by_owner(chunks.clone(), &index, "@Payments", 8);
+        assert_eq!(kept.len(), 2);
+
+        let kept = filter_chunks_by_owner(chunks, &index, "alice", 8);
+        assert_eq!(kept[0].path, "docs/billing.md");
+    }
+
     #[test]
     fn test_rag_chunk_serialization_without_optional_scores() {
         // Test that optional scores are omitted from JSON when None
@@ -2101,6 +2338,7 @@ mod tests {
             snippet: "# Title".to_string(),
             dense_score: None,
             reranker_score: None,
+            ownership: None,
         };
 
         let json = serde_json::to_string(&chunk).unwrap();
@@ -2111,6 +2349,7 @@ mod tests {
     #[test]
     fn test_ask_context_bundle_serialization() {
         let bundle = AskContextBundle {
+            ask_id: None,
             revision_id: RevisionId::new("rev-123"),
             question: "How does it work?".to_string(),
             bases: vec!["code".to_string()],
@@ -2158,6 +2397,7 @@ mod tests {
             source_id: "src-001".to_string(),
             indexed_mtime: None,
             indexed_size: None,
+            content_hash: None,
             extra: Some(serde_json::json!({
                 "memory_id": "mem-abc123",
                 "memory_scope": "project",
@@ -2200,6 +2440,7 @@ mod tests {
             source_id: "src-002".to_string(),
             indexed_mtime: None,
             indexed_size: None,
+            content_hash: None,
             extra: None,
         };
 
@@ -2220,6 +2461,7 @@ mod tests {
             source: crate::memory::MemorySource::Decision,
             tags: vec!["language".to_string()],
             score: Some(0.88),
+            extra: None,
         };
 
         let json = serde_json::to_string(&event).unwrap();
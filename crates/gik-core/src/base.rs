@@ -151,6 +151,12 @@ pub struct BaseSourceEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_size: Option<u64>,
 
+    /// BLAKE3 hash (hex) of the full file content when indexed.
+    /// Authoritative for change detection when present; entries written
+    /// before content hashing fall back to mtime/size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+
     /// Additional metadata (language, tags, etc.).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra: Option<serde_json::Value>,
@@ -184,6 +190,7 @@ impl BaseSourceEntry {
             source_id: source_id.into(),
             indexed_mtime: None,
             indexed_size: None,
+            content_hash: None,
             extra: None,
         }
     }
@@ -201,6 +208,12 @@ impl BaseSourceEntry {
         self
     }
 
+    /// Set the content hash of the indexed file (see [`stable_content_hash`]).
+    pub fn with_content_hash(mut self, hash: impl Into<String>) -> Self {
+        self.content_hash = Some(hash.into());
+        self
+    }
+
     /// Set extra metadata.
     pub fn with_extra(mut self, extra: serde_json::Value) -> Self {
         self.extra = Some(extra);
@@ -614,6 +627,14 @@ pub fn content_hash(content: &str) -> u64 {
     hasher.finish()
 }
 
+/// Compute a stable, content-addressed hash (BLAKE3, hex-encoded).
+///
+/// Unlike [`content_hash`], the result is stable across Rust versions and
+/// platforms, so it can be persisted for change detection and cache keys.
+pub fn stable_content_hash(content: &[u8]) -> String {
+    blake3::hash(content).to_hex().to_string()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -764,6 +785,22 @@ mod tests {
         assert_eq!(loaded.file_count, 5);
     }
 
+    #[test]
+    fn test_stable_content_hash() {
+        let hash1 = stable_content_hash(b"hello world");
+        let hash2 = stable_content_hash(b"hello world");
+        let hash3 = stable_content_hash(b"hello world!");
+
+        assert_eq!(hash1, hash2);
+        assert_ne!(hash1, hash3);
+        assert_eq!(hash1.len(), 64);
+        // Known BLAKE3 digest, guards against accidental algorithm changes
+        assert_eq!(
+            hash1,
+            "d74981efa70a0c880b8d8c1985d075dbcbf679b99a5f9914e5aaf96b831a9e24"
+        );
+    }
+
     #[test]
     fn test_content_hash() {
         let hash1 = content_hash("hello world");
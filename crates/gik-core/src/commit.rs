@@ -42,13 +42,14 @@ use crate::base::{
     BaseSourceEntry, BaseStats, ChunkId,
 };
 use crate::bm25::{load_bm25_index, save_bm25_index, Bm25Config, Bm25Index};
-use crate::config::{DevicePreference, GlobalConfig};
+use crate::config::{GlobalConfig, ProjectConfig};
 #[cfg(test)]
 use crate::embedding::create_mock_backend;
 use crate::embedding::{
     check_model_compatibility, create_backend, read_model_info, write_model_info, EmbeddingBackend,
     EmbeddingConfig, ModelCompatibility, ModelInfo,
 };
+use crate::embedding_cache::with_embedding_cache;
 
 use crate::errors::GikError;
 use crate::stack::{
@@ -202,6 +203,8 @@ struct ValidatedSource {
     line_count: usize,
     /// Content hash for chunk ID generation.
     content_hash: u64,
+    /// Stable content hash persisted for change detection.
+    file_hash: String,
     /// File modification time (Unix timestamp) for change detection.
     file_mtime: u64,
     /// File size in bytes for change detection.
@@ -303,19 +306,25 @@ pub fn run_commit(
     let mut base_data: Vec<BaseCommitData> = Vec::new();
     let mut touched_bases: Vec<String> = Vec::new();
 
+    let project_config = ProjectConfig::load_from_workspace(workspace.root())?;
     for (base_name, sources) in sources_by_base {
         let base_dir = base_root(knowledge_root, branch_str, &base_name);
 
         // Initialize base data with config
         let embedding_config = global_config.resolve_embedding_config(&base_name);
+        let index_config = project_config.resolve_vector_index_config(
+            &base_name,
+            &embedding_config,
+            global_config,
+        );
         let data = prepare_base_for_commit(
-            branch_str,
             &base_name,
             &base_dir,
             sources,
             opts.use_mock_backend,
             &embedding_config,
-            global_config.device,
+            index_config,
+            global_config,
         )?;
         touched_bases.push(base_name.clone());
         base_data.push(data);
@@ -351,7 +360,10 @@ pub fn run_commit(
         source_count: total_sources,
     };
 
-    let revision = Revision::new(branch_str, parent_id, message, vec![operation]);
+    let mut revision = Revision::new(branch_str, parent_id, message, vec![operation]);
+    if let Some(sha) = crate::git::read_head_commit(workspace.root()) {
+        revision = revision.with_git_commit(sha);
+    }
     let revision_id = revision.id.as_str().to_string();
 
     // 7. Write revision to timeline and update HEAD
@@ -469,14 +481,17 @@ pub fn run_commit(
 
 /// Prepare a base for commit by creating/loading embedding backend and vector index.
 fn prepare_base_for_commit(
-    _branch: &str,
     base_name: &str,
     base_dir: &Path,
     sources: Vec<PendingSource>,
     use_mock_backend: bool,
     embedding_config: &EmbeddingConfig,
-    device_pref: DevicePreference,
+    mut index_config: VectorIndexConfig,
+    global_config: &GlobalConfig,
 ) -> Result<BaseCommitData, GikError> {
+    let device_pref = global_config.device;
+    let cache_enabled = global_config.performance.embedding_cache;
+
     // Create base directory if needed
     fs::create_dir_all(base_dir).map_err(|e| GikError::BaseStoreIo {
         path: base_dir.to_path_buf(),
@@ -507,17 +522,26 @@ fn prepare_base_for_commit(
     // Create embedding backend
     // In tests, use_mock_backend allows using MockEmbeddingBackend.
     // In production, we always use the real backend and fail if unavailable.
+    // Real backends are wrapped with the embedding cache (mock embeddings are never cached).
     #[cfg(test)]
     let backend: Box<dyn EmbeddingBackend> = if use_mock_backend {
         create_mock_backend(embedding_config)
     } else {
-        create_backend(embedding_config, device_pref)?
+        with_embedding_cache(
+            create_backend(embedding_config, device_pref)?,
+            embedding_config,
+            cache_enabled,
+        )
     };
 
     #[cfg(not(test))]
     let backend: Box<dyn EmbeddingBackend> = {
         let _ = use_mock_backend; // Silence unused warning in production
-        create_backend(embedding_config, device_pref)?
+        with_embedding_cache(
+            create_backend(embedding_config, device_pref)?,
+            embedding_config,
+            cache_enabled,
+        )
     };
 
     let dimension = backend.dimension();
@@ -526,7 +550,8 @@ fn prepare_base_for_commit(
     let index_meta_file = index_meta_path(base_dir);
     let index_meta = load_index_meta(&index_meta_file)?;
 
-    let index_config = VectorIndexConfig::default_for_base(base_name, dimension);
+    // The backend reports the authoritative dimension
+    index_config.dimension = dimension;
     let index_compat =
         check_index_compatibility(&index_config, embedding_config, index_meta.as_ref());
 
@@ -560,10 +585,7 @@ fn prepare_base_for_commit(
 
     // Write index metadata if new
     if index_meta.is_none() {
-        let meta = VectorIndexMeta::from_config(
-            &VectorIndexConfig::default_for_base(base_name, dimension),
-            embedding_config,
-        );
+        let meta = VectorIndexMeta::from_config(&index_config, embedding_config);
         write_index_meta(&index_meta_file, &meta)?;
     }
 
@@ -683,7 +705,8 @@ fn process_base_sources(
             &validated_source.source_id,
         )
         .with_text(validated_source.content.clone())
-        .with_file_metadata(validated_source.file_mtime, validated_source.file_size);
+        .with_file_metadata(validated_source.file_mtime, validated_source.file_size)
+        .with_content_hash(validated_source.file_hash);
 
         // Add document to BM25 index for hybrid search
         data.bm25_index
@@ -750,12 +773,15 @@ fn validate_source(
                 hasher.finish()
             };
 
+            let file_hash = crate::base::stable_content_hash(content.as_bytes());
+
             return Ok(ValidatedSource {
                 source_id,
                 content,
                 uri: source.uri.clone(),
                 line_count,
                 content_hash,
+                file_hash,
                 file_mtime: mtime,
                 file_size: content_size,
             });
@@ -852,12 +878,15 @@ fn validate_source(
         hasher.finish()
     };
 
+    let file_hash = crate::base::stable_content_hash(content.as_bytes());
+
     Ok(ValidatedSource {
         source_id,
         content,
         uri: source.uri.clone(),
         line_count,
         content_hash,
+        file_hash,
         file_mtime,
         file_size,
     })
@@ -1009,6 +1038,33 @@ mod tests {
         assert_eq!(commit_summary.bases[0].chunk_count, 1);
     }
 
+    #[test]
+    fn test_commit_rejects_malformed_project_config() {
+        let (_temp, workspace) = setup_test_workspace();
+        let branch = BranchName::new_unchecked("main");
+        setup_initialized_branch(&workspace, "main");
+
+        create_test_file(&workspace, "src/main.rs", "fn main() {}\n");
+        let new_source =
+            NewPendingSource::new("code", "src/main.rs").with_kind(PendingSourceKind::FilePath);
+        add_source(&workspace, "main", new_source);
+
+        // A broken config must not silently fall back to the default backend
+        let config_path = ProjectConfig::config_path_for_workspace(workspace.root());
+        fs::write(&config_path, "indexes: [unterminated\n").unwrap();
+
+        let opts = CommitOptions {
+            message: None,
+            use_mock_backend: true,
+        };
+        let result = run_commit(&workspace, &branch, &opts, &test_global_config());
+        assert!(
+            matches!(result, Err(GikError::InvalidProjectConfig(_))),
+            "expected config error, got {:?}",
+            result
+        );
+    }
+
     #[test]
     fn test_commit_url_fails() {
         let (_temp, workspace) = setup_test_workspace();
@@ -43,6 +43,10 @@ pub const DEFAULT_EMBEDDING_WARMUP: bool = true;
 /// Uses rayon to read and validate files concurrently.
 pub const DEFAULT_PARALLEL_FILE_READING: bool = true;
 
+/// Whether to use the content-addressed embedding cache (default: true).
+/// Identical text embedded with the same model is served from `~/.gik/cache`.
+pub const DEFAULT_EMBEDDING_CACHE: bool = true;
+
 // ============================================================================
 // DevicePreference
 // ============================================================================
@@ -272,7 +276,7 @@ impl GlobalConfig {
     /// Resolution precedence (highest to lowest):
     /// 1. Global per-base override (`indexes.bases.<base>`)
     /// 2. Global default (`indexes.default`)
-    /// 3. Hard-coded default (SimpleFile + Cosine)
+    /// 3. Hard-coded default (LanceDB + Cosine)
     ///
     /// Note: Project-level overrides are handled separately by passing
     /// `ProjectConfig` to `resolve_vector_index_config_with_project`.
@@ -286,40 +290,18 @@ impl GlobalConfig {
         base: &str,
         embedding: &CoreEmbeddingConfig,
     ) -> crate::vector_index::VectorIndexConfig {
-        use crate::vector_index::{VectorIndexBackendKind, VectorIndexConfig, VectorMetric};
+        use crate::vector_index::VectorIndexConfig;
 
         let dimension = embedding.dimension.unwrap_or(DEFAULT_DIMENSION);
 
         // Check per-base override
         if let Some(base_config) = self.indexes.bases.get(base) {
-            let backend = base_config
-                .backend
-                .as_deref()
-                .map(|s| s.parse().unwrap_or(VectorIndexBackendKind::SimpleFile))
-                .unwrap_or(VectorIndexBackendKind::SimpleFile);
-            let metric = base_config
-                .metric
-                .as_deref()
-                .map(|s| s.parse().unwrap_or(VectorMetric::Cosine))
-                .unwrap_or(VectorMetric::Cosine);
-
-            return VectorIndexConfig::new(backend, metric, dimension, base);
+            return base_config.to_vector_index_config(base, dimension);
         }
 
         // Check global default
         if let Some(default_config) = &self.indexes.default {
-            let backend = default_config
-                .backend
-                .as_deref()
-                .map(|s| s.parse().unwrap_or(VectorIndexBackendKind::SimpleFile))
-                .unwrap_or(VectorIndexBackendKind::SimpleFile);
-            let metric = default_config
-                .metric
-                .as_deref()
-                .map(|s| s.parse().unwrap_or(VectorMetric::Cosine))
-                .unwrap_or(VectorMetric::Cosine);
-
-            return VectorIndexConfig::new(backend, metric, dimension, base);
+            return default_config.to_vector_index_config(base, dimension);
         }
 
         // Fall back to hard-coded default
@@ -344,49 +326,7 @@ impl GlobalConfig {
         let mut config = self.retrieval.clone();
 
         if let Some(ref proj_retrieval) = project.retrieval {
-            // Apply reranker overrides
-            if let Some(ref proj_reranker) = proj_retrieval.reranker {
-                if let Some(enabled) = proj_reranker.enabled {
-                    tracing::debug!("Project override: reranker.enabled = {}", enabled);
-                    config.reranker.enabled = enabled;
-                }
-                if let Some(top_k) = proj_reranker.top_k {
-                    tracing::debug!("Project override: reranker.topK = {}", top_k);
-                    config.reranker.top_k = top_k;
-                }
-                if let Some(final_k) = proj_reranker.final_k {
-                    tracing::debug!("Project override: reranker.finalK = {}", final_k);
-                    config.reranker.final_k = final_k;
-                }
-            }
-
-            // Apply hybrid overrides
-            if let Some(ref proj_hybrid) = proj_retrieval.hybrid {
-                if let Some(enabled) = proj_hybrid.enabled {
-                    tracing::debug!("Project override: hybrid.enabled = {}", enabled);
-                    config.hybrid.enabled = enabled;
-                }
-                if let Some(dense_weight) = proj_hybrid.dense_weight {
-                    tracing::debug!("Project override: hybrid.denseWeight = {}", dense_weight);
-                    config.hybrid.dense_weight = dense_weight;
-                }
-                if let Some(sparse_weight) = proj_hybrid.sparse_weight {
-                    tracing::debug!("Project override: hybrid.sparseWeight = {}", sparse_weight);
-                    config.hybrid.sparse_weight = sparse_weight;
-                }
-                if let Some(rrf_k) = proj_hybrid.rrf_k {
-                    tracing::debug!("Project override: hybrid.rrfK = {}", rrf_k);
-                    config.hybrid.rrf_k = rrf_k;
-                }
-                if let Some(dense_top_k) = proj_hybrid.dense_top_k {
-                    tracing::debug!("Project override: hybrid.denseTopK = {}", dense_top_k);
-                    config.hybrid.dense_top_k = dense_top_k;
-                }
-                if let Some(sparse_top_k) = proj_hybrid.sparse_top_k {
-                    tracing::debug!("Project override: hybrid.sparseTopK = {}", sparse_top_k);
-                    config.hybrid.sparse_top_k = sparse_top_k;
-                }
-            }
+            config.apply_override(proj_retrieval);
         }
 
         config
@@ -622,12 +562,18 @@ impl EmbeddingOverride {
 /// ```yaml
 /// indexes:
 ///   default:
-///     backend: simple_file
+///     backend: lancedb
 ///     metric: cosine
 ///   bases:
 ///     code:
-///       backend: lancedb
-///       metric: dot
+///       backend: hnsw
+///       metric: cosine
+///       m: 16
+///       efConstruction: 200
+///       efSearch: 64
+///     notes:
+///       backend: simple_file
+///       storage: f16
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IndexesSection {
@@ -646,13 +592,67 @@ pub struct IndexesSection {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IndexOverride {
-    /// Backend type: "simple_file", "lancedb", etc.
+    /// Backend type: "lancedb", "hnsw", "simple_file", etc.
     #[serde(default)]
     pub backend: Option<String>,
 
     /// Similarity metric: "cosine", "dot", "l2".
     #[serde(default)]
     pub metric: Option<String>,
+
+    /// simple_file: on-disk vector element type, "f32" or "f16".
+    #[serde(default)]
+    pub storage: Option<String>,
+
+    /// HNSW: maximum neighbors per graph node.
+    #[serde(default)]
+    pub m: Option<u32>,
+
+    /// HNSW: candidate list size while building the graph.
+    #[serde(default)]
+    pub ef_construction: Option<u32>,
+
+    /// HNSW: candidate list size while searching.
+    #[serde(default)]
+    pub ef_search: Option<u32>,
+}
+
+impl IndexOverride {
+    /// Build a vector index config for `base`, filling unset fields with defaults.
+    pub fn to_vector_index_config(
+        &self,
+        base: &str,
+        dimension: u32,
+    ) -> crate::vector_index::VectorIndexConfig {
+        use crate::vector_index::{HnswParams, VectorIndexConfig, VectorMetric, VectorStorage};
+
+        let backend = self
+            .backend
+            .as_deref()
+            .map(|s| s.parse().unwrap_or_default())
+            .unwrap_or_default();
+        let metric = self
+            .metric
+            .as_deref()
+            .map(|s| s.parse().unwrap_or(VectorMetric::Cosine))
+            .unwrap_or(VectorMetric::Cosine);
+        let storage = self
+            .storage
+            .as_deref()
+            .map(|s| s.parse().unwrap_or_default())
+            .unwrap_or(VectorStorage::F32);
+
+        let defaults = HnswParams::default();
+        let hnsw = HnswParams {
+            m: self.m.unwrap_or(defaults.m),
+            ef_construction: self.ef_construction.unwrap_or(defaults.ef_construction),
+            ef_search: self.ef_search.unwrap_or(defaults.ef_search),
+        };
+
+        VectorIndexConfig::new(backend, metric, dimension, base)
+            .with_storage(storage)
+            .with_hnsw(hnsw)
+    }
 }
 
 // ======================================================================
@@ -673,6 +673,7 @@ pub struct IndexOverride {
 ///   maxFileLines: 10000
 ///   enableWarmup: true
 ///   parallelFileReading: true
+///   embeddingCache: true
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -698,6 +699,11 @@ pub struct PerformanceConfig {
     /// Whether to read and validate files in parallel using rayon.
     #[serde(default = "default_parallel_file_reading")]
     pub parallel_file_reading: bool,
+
+    /// Whether to reuse embeddings from the content-addressed cache
+    /// (`~/.gik/cache/embeddings`) across commits, reindexes and branches.
+    #[serde(default = "default_embedding_cache")]
+    pub embedding_cache: bool,
 }
 
 fn default_embedding_batch_size() -> usize {
@@ -715,6 +721,9 @@ fn default_enable_warmup() -> bool {
 fn default_parallel_file_reading() -> bool {
     DEFAULT_PARALLEL_FILE_READING
 }
+fn default_embedding_cache() -> bool {
+    DEFAULT_EMBEDDING_CACHE
+}
 
 impl Default for PerformanceConfig {
     fn default() -> Self {
@@ -724,6 +733,7 @@ impl Default for PerformanceConfig {
             max_file_lines: DEFAULT_MAX_FILE_LINES,
             enable_warmup: DEFAULT_EMBEDDING_WARMUP,
             parallel_file_reading: DEFAULT_PARALLEL_FILE_READING,
+            embedding_cache: DEFAULT_EMBEDDING_CACHE,
         }
     }
 }
@@ -824,6 +834,9 @@ const DEFAULT_RERANKER_FINAL_K: usize = 5;
 ///     bm25:
 ///       k1: 1.2
 ///       b: 0.75
+///   centrality:
+///     enabled: false
+///     weight: 0.1
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -835,6 +848,76 @@ pub struct RetrievalConfig {
     /// Hybrid search configuration (BM25 + Dense).
     #[serde(default)]
     pub hybrid: crate::bm25::HybridSearchConfig,
+
+    /// Score prior for files that are central in the KG import graph.
+    #[serde(default)]
+    pub centrality: CentralityPriorConfig,
+}
+
+impl RetrievalConfig {
+    /// Apply a set of overrides on top of this configuration.
+    ///
+    /// Used for project-level overrides and for named eval configurations.
+    pub fn apply_override(&mut self, overrides: &RetrievalConfigOverride) {
+        // Apply reranker overrides
+        if let Some(ref reranker) = overrides.reranker {
+            if let Some(enabled) = reranker.enabled {
+                tracing::debug!("Retrieval override: reranker.enabled = {}", enabled);
+                self.reranker.enabled = enabled;
+            }
+            if let Some(top_k) = reranker.top_k {
+                tracing::debug!("Retrieval override: reranker.topK = {}", top_k);
+                self.reranker.top_k = top_k;
+            }
+            if let Some(final_k) = reranker.final_k {
+                tracing::debug!("Retrieval override: reranker.finalK = {}", final_k);
+                self.reranker.final_k = final_k;
+            }
+        }
+
+        // Apply hybrid overrides
+        if let Some(ref hybrid) = overrides.hybrid {
+            if let Some(enabled) = hybrid.enabled {
+                tracing::debug!("Retrieval override: hybrid.enabled = {}", enabled);
+                self.hybrid.enabled = enabled;
+            }
+            if let Some(dense_weight) = hybrid.dense_weight {
+                tracing::debug!("Retrieval override: hybrid.denseWeight = {}", dense_weight);
+                self.hybrid.dense_weight = dense_weight;
+            }
+            if let Some(sparse_weight) = hybrid.sparse_weight {
+                tracing::debug!(
+                    "Retrieval override: hybrid.sparseWeight = {}",
+                    sparse_weight
+                );
+                self.hybrid.sparse_weight = sparse_weight;
+            }
+            if let Some(rrf_k) = hybrid.rrf_k {
+                tracing::debug!("Retrieval override: hybrid.rrfK = {}", rrf_k);
+                self.hybrid.rrf_k = rrf_k;
+            }
+            if let Some(dense_top_k) = hybrid.dense_top_k {
+                tracing::debug!("Retrieval override: hybrid.denseTopK = {}", dense_top_k);
+                self.hybrid.dense_top_k = dense_top_k;
+            }
+            if let Some(sparse_top_k) = hybrid.sparse_top_k {
+                tracing::debug!("Retrieval override: hybrid.sparseTopK = {}", sparse_top_k);
+                self.hybrid.sparse_top_k = sparse_top_k;
+            }
+        }
+
+        // Apply centrality prior overrides
+        if let Some(ref centrality) = overrides.centrality {
+            if let Some(enabled) = centrality.enabled {
+                tracing::debug!("Retrieval override: centrality.enabled = {}", enabled);
+                self.centrality.enabled = enabled;
+            }
+            if let Some(weight) = centrality.weight {
+                tracing::debug!("Retrieval override: centrality.weight = {}", weight);
+                self.centrality.weight = weight;
+            }
+        }
+    }
 }
 
 /// Cross-encoder reranker configuration.
@@ -970,6 +1053,49 @@ impl RerankerConfig {
     }
 }
 
+/// Default boost for the most central file in the import graph.
+const DEFAULT_CENTRALITY_WEIGHT: f32 = 0.10;
+
+/// Centrality prior for `ask` scoring.
+///
+/// KG sync stores a `centrality` score in `[0, 1]` on file nodes (PageRank
+/// over the import graph, scaled to the most central file). When enabled,
+/// each chunk's score gets `weight * centrality` of its file added, so core
+/// modules outrank obscure helpers with similar text.
+///
+/// # Fields
+///
+/// - `enabled`: Whether the prior is applied (default: false).
+/// - `weight`: Boost for the most central file (default: 0.10).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CentralityPriorConfig {
+    /// Whether the centrality prior is applied.
+    #[serde(default = "default_centrality_enabled")]
+    pub enabled: bool,
+
+    /// Boost added for the most central file; others scale down with centrality.
+    #[serde(default = "default_centrality_weight")]
+    pub weight: f32,
+}
+
+fn default_centrality_enabled() -> bool {
+    false
+}
+
+fn default_centrality_weight() -> f32 {
+    DEFAULT_CENTRALITY_WEIGHT
+}
+
+impl Default for CentralityPriorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_centrality_enabled(),
+            weight: default_centrality_weight(),
+        }
+    }
+}
+
 // ======================================================================
 // RetrievalConfigOverride (Project-level overrides)
 // ======================================================================
@@ -998,6 +1124,10 @@ pub struct RetrievalConfigOverride {
     /// Hybrid search configuration overrides.
     #[serde(default)]
     pub hybrid: Option<HybridConfigOverride>,
+
+    /// Centrality prior overrides.
+    #[serde(default)]
+    pub centrality: Option<CentralityPriorOverride>,
 }
 
 /// Project-level reranker configuration overrides.
@@ -1041,6 +1171,19 @@ pub struct HybridConfigOverride {
     pub sparse_top_k: Option<usize>,
 }
 
+/// Project-level centrality prior overrides.
+///
+/// All fields optional; unset fields inherit from global config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CentralityPriorOverride {
+    /// Override for enabled state.
+    pub enabled: Option<bool>,
+
+    /// Override for weight.
+    pub weight: Option<f32>,
+}
+
 // ======================================================================
 // ProjectConfig
 // ======================================================================
@@ -1066,7 +1209,7 @@ pub struct HybridConfigOverride {
 /// indexes:
 ///   bases:
 ///     code:
-///       backend: simple_file
+///       backend: hnsw
 ///       metric: cosine
 ///
 /// # Phase 8.2: Per-project retrieval overrides
@@ -1177,7 +1320,7 @@ impl ProjectConfig {
     /// 1. Project per-base override (`indexes.bases.<base>`)
     /// 2. Global per-base override (`global_config.indexes.bases.<base>`)
     /// 3. Global default (`global_config.indexes.default`)
-    /// 4. Hard-coded default (SimpleFile + Cosine)
+    /// 4. Hard-coded default (LanceDB + Cosine)
     ///
     /// # Arguments
     ///
@@ -1190,24 +1333,10 @@ impl ProjectConfig {
         embedding: &CoreEmbeddingConfig,
         global_config: &GlobalConfig,
     ) -> crate::vector_index::VectorIndexConfig {
-        use crate::vector_index::{VectorIndexBackendKind, VectorIndexConfig, VectorMetric};
-
-        let dimension = embedding.dimension.unwrap_or(DEFAULT_DIMENSION);
-
         // Check project per-base override
         if let Some(base_config) = self.indexes.bases.get(base) {
-            let backend = base_config
-                .backend
-                .as_deref()
-                .map(|s| s.parse().unwrap_or(VectorIndexBackendKind::SimpleFile))
-                .unwrap_or(VectorIndexBackendKind::SimpleFile);
-            let metric = base_config
-                .metric
-                .as_deref()
-                .map(|s| s.parse().unwrap_or(VectorMetric::Cosine))
-                .unwrap_or(VectorMetric::Cosine);
-
-            return VectorIndexConfig::new(backend, metric, dimension, base);
+            let dimension = embedding.dimension.unwrap_or(DEFAULT_DIMENSION);
+            return base_config.to_vector_index_config(base, dimension);
         }
 
         // Delegate to global config resolution
@@ -1288,6 +1417,16 @@ impl ProjectConfig {
                     }
                 }
             }
+
+            // Validate centrality overrides
+            if let Some(weight) = retrieval.centrality.as_ref().and_then(|c| c.weight) {
+                if weight < 0.0 {
+                    return Err(GikError::InvalidConfiguration {
+                        message: format!("retrieval.centrality.weight ({}) cannot be negative", weight),
+                        hint: "Use a value between 0.0 and 0.5 (default: 0.1)".to_string(),
+                    });
+                }
+            }
         }
 
         Ok(warnings)
@@ -1538,6 +1677,60 @@ embeddings:
         assert_eq!(docs_config.model_id.as_str(), "global-model");
     }
 
+    #[test]
+    fn test_resolve_vector_index_config_hnsw_override() {
+        use crate::vector_index::{VectorIndexBackendKind, VectorMetric};
+
+        let project_yaml = r#"
+indexes:
+  bases:
+    code:
+      backend: hnsw
+      m: 8
+      efSearch: 128
+"#;
+        let global = GlobalConfig::default();
+        let project: ProjectConfig = serde_yaml::from_str(project_yaml).unwrap();
+        let embedding = global.resolve_embedding_config("code");
+
+        let config = project.resolve_vector_index_config("code", &embedding, &global);
+        assert_eq!(config.backend, VectorIndexBackendKind::Hnsw);
+        assert_eq!(config.metric, VectorMetric::Cosine);
+        assert_eq!(config.hnsw.m, 8);
+        assert_eq!(config.hnsw.ef_search, 128);
+        assert_eq!(
+            config.hnsw.ef_construction,
+            crate::vector_index::HnswParams::default().ef_construction
+        );
+
+        // Bases without an override use the default backend
+        let docs = project.resolve_vector_index_config("docs", &embedding, &global);
+        assert_eq!(docs.backend, VectorIndexBackendKind::LanceDb);
+    }
+
+    #[test]
+    fn test_resolve_vector_index_config_storage_override() {
+        use crate::vector_index::{VectorIndexBackendKind, VectorStorage};
+
+        let project_yaml = r#"
+indexes:
+  bases:
+    notes:
+      backend: simple_file
+      storage: f16
+"#;
+        let global = GlobalConfig::default();
+        let project: ProjectConfig = serde_yaml::from_str(project_yaml).unwrap();
+        let embedding = global.resolve_embedding_config("notes");
+
+        let config = project.resolve_vector_index_config("notes", &embedding, &global);
+        assert_eq!(config.backend, VectorIndexBackendKind::SimpleFile);
+        assert_eq!(config.storage, VectorStorage::F16);
+
+        let docs = project.resolve_vector_index_config("docs", &embedding, &global);
+        assert_eq!(docs.storage, VectorStorage::F32);
+    }
+
     #[test]
     fn test_embedding_override_to_core_config() {
         let override_config = EmbeddingOverride {
@@ -1812,4 +2005,36 @@ performance:
         // rrfK should inherit from global
         assert!((resolved.hybrid.rrf_k - global.retrieval.hybrid.rrf_k).abs() < 0.001);
     }
+
+    #[test]
+    fn test_resolve_retrieval_config_with_centrality_overrides() {
+        let global = GlobalConfig::default();
+        assert!(!global.retrieval.centrality.enabled);
+
+        let project = ProjectConfig {
+            retrieval: Some(RetrievalConfigOverride {
+                centrality: Some(CentralityPriorOverride {
+                    enabled: Some(true),
+                    weight: Some(0.25),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let resolved = global.resolve_retrieval_config(&project);
+        assert!(resolved.centrality.enabled);
+        assert!((resolved.centrality.weight - 0.25).abs() < 0.001);
+
+        let invalid = ProjectConfig {
+            retrieval: Some(RetrievalConfigOverride {
+                centrality: Some(CentralityPriorOverride {
+                    weight: Some(-0.1),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
 }
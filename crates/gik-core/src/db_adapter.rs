@@ -32,9 +32,10 @@ use std::sync::Arc;
 
 use crate::errors::GikError;
 use crate::vector_index::{
-    VectorId as CoreVectorId, VectorIndexBackendKind, VectorIndexConfig as CoreVectorIndexConfig,
-    VectorIndexStats, VectorInsert as CoreVectorInsert, VectorMetric as CoreVectorMetric,
-    VectorSearchFilter, VectorSearchResult as CoreVectorSearchResult,
+    HnswParams, VectorId as CoreVectorId, VectorIndexBackendKind,
+    VectorIndexConfig as CoreVectorIndexConfig, VectorIndexStats, VectorInsert as CoreVectorInsert,
+    VectorMetric as CoreVectorMetric, VectorSearchFilter,
+    VectorSearchResult as CoreVectorSearchResult, VectorStorage,
 };
 
 // ============================================================================
@@ -236,6 +237,32 @@ impl DbKgStore {
         Ok(db_edges.into_iter().map(from_db_kg_edge).collect())
     }
 
+    /// Get up to `limit` nodes after skipping `offset`, in stable scan order.
+    pub fn get_nodes_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<crate::kg::KgNode>, GikError> {
+        let db_nodes = self.inner.get_nodes_page(offset, limit).into_gik_result()?;
+        Ok(db_nodes.into_iter().map(from_db_kg_node).collect())
+    }
+
+    /// Get up to `limit` edges after skipping `offset`, in stable scan order.
+    pub fn get_edges_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<crate::kg::KgEdge>, GikError> {
+        let db_edges = self.inner.get_edges_page(offset, limit).into_gik_result()?;
+        Ok(db_edges.into_iter().map(from_db_kg_edge).collect())
+    }
+
+    /// Get nodes by their IDs.
+    pub fn get_nodes_by_ids(&self, ids: &[&str]) -> Result<Vec<crate::kg::KgNode>, GikError> {
+        let db_nodes = self.inner.get_nodes_by_ids(ids).into_gik_result()?;
+        Ok(db_nodes.into_iter().map(from_db_kg_node).collect())
+    }
+
     /// Get nodes by kind.
     pub fn get_nodes_by_kind(&self, kind: &str) -> Result<Vec<crate::kg::KgNode>, GikError> {
         let db_nodes = self.inner.get_nodes_by_kind(kind).into_gik_result()?;
@@ -307,10 +334,15 @@ impl DbVectorIndex {
     pub fn open(db_config: &gik_db::vector::VectorIndexConfig) -> Result<Self, GikError> {
         let backend = gik_db::vector::open_vector_index(db_config).into_gik_result()?;
         let core_config = CoreVectorIndexConfig {
-            backend: VectorIndexBackendKind::LanceDb,
+            backend: db_config.backend.parse().unwrap_or_default(),
             metric: CoreVectorMetric::Cosine,
             dimension: db_config.dimension as u32,
             base: String::new(), // Base is not stored in gik-db config
+            storage: match db_config.storage {
+                gik_db::vector::VectorStorage::F32 => VectorStorage::F32,
+                gik_db::vector::VectorStorage::F16 => VectorStorage::F16,
+            },
+            hnsw: HnswParams::default(),
         };
         Ok(Self::new(backend, core_config))
     }
@@ -360,6 +392,23 @@ fn from_db_search_result(result: gik_db::vector::VectorSearchResult) -> CoreVect
     }
 }
 
+/// Convert gik-core HNSW parameters to a gik-db HnswConfig.
+pub(crate) fn to_db_hnsw_config(params: &HnswParams) -> gik_db::vector::HnswConfig {
+    gik_db::vector::HnswConfig {
+        m: params.m as usize,
+        ef_construction: params.ef_construction as usize,
+        ef_search: params.ef_search as usize,
+    }
+}
+
+/// Convert a gik-core vector storage type to a gik-db VectorStorage.
+pub(crate) fn to_db_storage(storage: VectorStorage) -> gik_db::vector::VectorStorage {
+    match storage {
+        VectorStorage::F32 => gik_db::vector::VectorStorage::F32,
+        VectorStorage::F16 => gik_db::vector::VectorStorage::F16,
+    }
+}
+
 /// Convert a gik-core VectorSearchFilter to a gik-db VectorSearchFilter.
 fn to_db_filter(filter: &VectorSearchFilter) -> gik_db::vector::VectorSearchFilter {
     gik_db::vector::VectorSearchFilter {
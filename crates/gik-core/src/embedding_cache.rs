@@ -0,0 +1,448 @@
+//! Content-addressed embedding cache.
+//!
+//! Embeddings are deterministic for a given (provider, model, text), so they can
+//! be shared across commits, reindexes and branches. This module stores them
+//! under `~/.gik/cache/embeddings/`, keyed by model and the BLAKE3 hash of the
+//! embedded text. The model key also carries `maxTokens` and a hash of
+//! `localPath` when set, since either changes the vectors produced:
+//!
+//! ```text
+//! ~/.gik/cache/embeddings/
+//! └── candle--sentence-transformers--all-MiniLM-L6-v2--384/
+//!     └── d7/
+//!         └── d74981ef...e24.f32    # little-endian f32 vector
+//! ```
+//!
+//! [`CachedEmbeddingBackend`] wraps any [`EmbeddingBackend`]: cache hits skip
+//! inference entirely, and only misses are sent to the inner backend (still
+//! batched). The cache is best-effort: I/O failures are logged and treated as
+//! misses, never as errors.
+//!
+//! Set `GIK_CACHE_DIR` to relocate the cache root, or disable it with
+//! `performance.embeddingCache: false`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::base::stable_content_hash;
+use crate::config::GlobalConfig;
+use crate::embedding::{
+    EmbeddingBackend, EmbeddingConfig, EmbeddingModelId, EmbeddingProviderKind,
+};
+use crate::errors::GikError;
+
+/// Environment variable overriding the cache root (default: `~/.gik/cache`).
+pub const CACHE_DIR_ENV: &str = "GIK_CACHE_DIR";
+
+/// Subdirectory of the cache root holding embeddings.
+pub const EMBEDDINGS_CACHE_DIR: &str = "embeddings";
+
+/// File extension for cached vectors.
+const VECTOR_EXT: &str = "f32";
+
+// ============================================================================
+// EmbeddingCache
+// ============================================================================
+
+/// On-disk embedding store for a single model configuration.
+#[derive(Debug, Clone)]
+pub struct EmbeddingCache {
+    dir: PathBuf,
+    dimension: u32,
+}
+
+impl EmbeddingCache {
+    /// Create a cache rooted at `root` for the model described by `config`.
+    ///
+    /// `dimension` is the backend's actual output dimension, which takes
+    /// precedence over `config.dimension`.
+    pub fn new(root: &Path, config: &EmbeddingConfig, dimension: u32) -> Self {
+        let mut key = format!(
+            "{}--{}--{}",
+            config.provider,
+            config.model_id.as_str(),
+            dimension
+        );
+        // Truncation length and model files both change the vectors produced
+        if let Some(max_tokens) = config.max_tokens {
+            key.push_str(&format!("--t{}", max_tokens));
+        }
+        if let Some(local_path) = &config.local_path {
+            let hash = stable_content_hash(local_path.to_string_lossy().as_bytes());
+            key.push_str(&format!("--p{}", &hash[..12]));
+        }
+        let dir = root.join(EMBEDDINGS_CACHE_DIR).join(sanitize(&key));
+        Self { dir, dimension }
+    }
+
+    /// Resolve the cache root: `$GIK_CACHE_DIR`, else `~/.gik/cache`.
+    pub fn default_root() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os(CACHE_DIR_ENV) {
+            return Some(PathBuf::from(dir));
+        }
+        GlobalConfig::default_dir().map(|d| d.join("cache"))
+    }
+
+    /// Directory holding this model's vectors.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, text: &str) -> PathBuf {
+        let hash = stable_content_hash(text.as_bytes());
+        self.dir
+            .join(&hash[..2])
+            .join(format!("{}.{}", hash, VECTOR_EXT))
+    }
+
+    /// Look up the embedding for `text`.
+    ///
+    /// Returns `None` on a miss, or if the stored vector is unreadable or has
+    /// the wrong dimension.
+    pub fn get(&self, text: &str) -> Option<Vec<f32>> {
+        let bytes = fs::read(self.entry_path(text)).ok()?;
+        if bytes.len() != self.dimension as usize * 4 {
+            return None;
+        }
+        Some(
+            bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+        )
+    }
+
+    /// Store the embedding for `text`.
+    ///
+    /// Writes to a temporary file and renames it into place, so concurrent
+    /// writers never expose a partial vector.
+    pub fn put(&self, text: &str, embedding: &[f32]) -> Result<(), GikError> {
+        if embedding.len() != self.dimension as usize {
+            return Err(GikError::InvalidArgument(format!(
+                "Embedding dimension {} does not match cache dimension {}",
+                embedding.len(),
+                self.dimension
+            )));
+        }
+
+        let path = self.entry_path(text);
+        if path.exists() {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let bytes: Vec<u8> = embedding.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let tmp = path.with_extension(format!("{}.tmp-{}", VECTOR_EXT, std::process::id()));
+        fs::write(&tmp, bytes)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+/// Make a model key safe to use as a single path component.
+fn sanitize(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// CachedEmbeddingBackend
+// ============================================================================
+
+/// An [`EmbeddingBackend`] that serves repeated texts from an [`EmbeddingCache`].
+pub struct CachedEmbeddingBackend {
+    inner: Box<dyn EmbeddingBackend>,
+    cache: EmbeddingCache,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CachedEmbeddingBackend {
+    /// Wrap `inner`, created from `config`, with a cache rooted at `root`.
+    pub fn new(inner: Box<dyn EmbeddingBackend>, config: &EmbeddingConfig, root: &Path) -> Self {
+        let cache = EmbeddingCache::new(root, config, inner.dimension());
+        Self {
+            inner,
+            cache,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of texts served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of texts sent to the inner backend.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+impl EmbeddingBackend for CachedEmbeddingBackend {
+    fn provider_kind(&self) -> EmbeddingProviderKind {
+        self.inner.provider_kind()
+    }
+
+    fn model_id(&self) -> &EmbeddingModelId {
+        self.inner.model_id()
+    }
+
+    fn dimension(&self) -> u32 {
+        self.inner.dimension()
+    }
+
+    fn embed_batch(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, GikError> {
+        let mut results: Vec<Option<Vec<f32>>> =
+            inputs.iter().map(|text| self.cache.get(text)).collect();
+
+        let miss_indices: Vec<usize> = results
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.is_none())
+            .map(|(i, _)| i)
+            .collect();
+
+        self.hits.fetch_add(
+            (inputs.len() - miss_indices.len()) as u64,
+            Ordering::Relaxed,
+        );
+        self.misses
+            .fetch_add(miss_indices.len() as u64, Ordering::Relaxed);
+
+        if !miss_indices.is_empty() {
+            let miss_texts: Vec<String> = miss_indices.iter().map(|&i| inputs[i].clone()).collect();
+            let embedded = self.inner.embed_batch(&miss_texts)?;
+            if embedded.len() != miss_texts.len() {
+                return Err(GikError::EmbeddingConfigError {
+                    message: format!(
+                        "embed_batch returned {} embeddings for {} inputs",
+                        embedded.len(),
+                        miss_texts.len()
+                    ),
+                });
+            }
+
+            for (&i, embedding) in miss_indices.iter().zip(embedded) {
+                if let Err(e) = self.cache.put(&inputs[i], &embedding) {
+                    tracing::debug!(error = %e, "Failed to write embedding cache entry");
+                }
+                results[i] = Some(embedding);
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Callers only warm up when `performance.enableWarmup` is set, so the
+    /// request is forwarded to the inner backend unchanged.
+    fn warm_up(&self) -> Result<(), GikError> {
+        self.inner.warm_up()
+    }
+}
+
+impl Drop for CachedEmbeddingBackend {
+    fn drop(&mut self) {
+        let (hits, misses) = (self.hits(), self.misses());
+        if hits + misses > 0 {
+            tracing::debug!(hits, misses, dir = %self.cache.dir().display(), "Embedding cache usage");
+        }
+    }
+}
+
+/// Wrap `backend`, created from `config`, with the default embedding cache
+/// when `enabled`.
+///
+/// Returns the backend unchanged if caching is disabled or no cache root can
+/// be resolved (e.g., no home directory).
+pub fn with_embedding_cache(
+    backend: Box<dyn EmbeddingBackend>,
+    config: &EmbeddingConfig,
+    enabled: bool,
+) -> Box<dyn EmbeddingBackend> {
+    if !enabled {
+        return backend;
+    }
+    match EmbeddingCache::default_root() {
+        Some(root) => Box::new(CachedEmbeddingBackend::new(backend, config, &root)),
+        None => backend,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::MockEmbeddingBackend;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// Mock backend that counts how many texts reach inference.
+    struct CountingBackend {
+        inner: MockEmbeddingBackend,
+        calls: Arc<AtomicUsize>,
+        warmups: Arc<AtomicUsize>,
+    }
+
+    impl EmbeddingBackend for CountingBackend {
+        fn provider_kind(&self) -> EmbeddingProviderKind {
+            self.inner.provider_kind()
+        }
+        fn model_id(&self) -> &EmbeddingModelId {
+            self.inner.model_id()
+        }
+        fn dimension(&self) -> u32 {
+            self.inner.dimension()
+        }
+        fn embed_batch(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, GikError> {
+            self.calls.fetch_add(inputs.len(), Ordering::SeqCst);
+            self.inner.embed_batch(inputs)
+        }
+        fn warm_up(&self) -> Result<(), GikError> {
+            self.warmups.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn counting_backend() -> (Box<dyn EmbeddingBackend>, Arc<AtomicUsize>) {
+        let (backend, calls, _) = counting_backend_with_warmups();
+        (backend, calls)
+    }
+
+    fn counting_backend_with_warmups() -> (
+        Box<dyn EmbeddingBackend>,
+        Arc<AtomicUsize>,
+        Arc<AtomicUsize>,
+    ) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let warmups = Arc::new(AtomicUsize::new(0));
+        let backend = CountingBackend {
+            inner: MockEmbeddingBackend::new(EmbeddingConfig::default()),
+            calls: calls.clone(),
+            warmups: warmups.clone(),
+        };
+        (Box::new(backend), calls, warmups)
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let config = EmbeddingConfig::new(EmbeddingProviderKind::Candle, "org/model");
+        let cache = EmbeddingCache::new(temp.path(), &config, 3);
+
+        assert!(cache.get("hello").is_none());
+        cache.put("hello", &[0.1, -0.2, 0.3]).unwrap();
+        assert_eq!(cache.get("hello").unwrap(), vec![0.1, -0.2, 0.3]);
+        assert!(cache.put("hello", &[1.0]).is_err());
+
+        // Model ID is sanitized into a single directory
+        assert!(cache.dir().ends_with("candle--org-model--3"));
+    }
+
+    #[test]
+    fn test_cached_backend_skips_inference_on_hits() {
+        let temp = TempDir::new().unwrap();
+        let texts = vec!["fn a() {}".to_string(), "fn b() {}".to_string()];
+
+        let (backend, calls) = counting_backend();
+        let cached = CachedEmbeddingBackend::new(backend, &EmbeddingConfig::default(), temp.path());
+        let first = cached.embed_batch(&texts).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(cached.misses(), 2);
+
+        // A fresh backend (e.g., another branch or a reindex) reuses the cache
+        let (backend, calls) = counting_backend();
+        let cached = CachedEmbeddingBackend::new(backend, &EmbeddingConfig::default(), temp.path());
+        let mixed = vec![texts[1].clone(), "fn c() {}".to_string(), texts[0].clone()];
+        let second = cached.embed_batch(&mixed).unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(cached.hits(), 2);
+        assert_eq!(second[0], first[1]);
+        assert_eq!(second[2], first[0]);
+        assert_eq!(second[1].len(), cached.dimension() as usize);
+    }
+
+    #[test]
+    fn test_cache_key_includes_max_tokens_and_local_path() {
+        let temp = TempDir::new().unwrap();
+        let base = EmbeddingConfig::new(EmbeddingProviderKind::Candle, "org/model");
+        let dir = |config: &EmbeddingConfig| {
+            EmbeddingCache::new(temp.path(), config, 3)
+                .dir()
+                .to_path_buf()
+        };
+
+        let truncated = base.clone().with_max_tokens(128);
+        let local_a = base.clone().with_local_path("/models/a");
+        let local_b = base.clone().with_local_path("/models/b");
+
+        assert_ne!(dir(&base), dir(&truncated));
+        assert_ne!(dir(&base), dir(&local_a));
+        assert_ne!(dir(&local_a), dir(&local_b));
+        assert_eq!(
+            dir(&local_a),
+            dir(&base.clone().with_local_path("/models/a"))
+        );
+    }
+
+    /// Mock backend that drops the last embedding of every batch.
+    struct ShortBackend(MockEmbeddingBackend);
+
+    impl EmbeddingBackend for ShortBackend {
+        fn provider_kind(&self) -> EmbeddingProviderKind {
+            self.0.provider_kind()
+        }
+        fn model_id(&self) -> &EmbeddingModelId {
+            self.0.model_id()
+        }
+        fn dimension(&self) -> u32 {
+            self.0.dimension()
+        }
+        fn embed_batch(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, GikError> {
+            let mut embedded = self.0.embed_batch(inputs)?;
+            embedded.pop();
+            Ok(embedded)
+        }
+    }
+
+    #[test]
+    fn test_cached_backend_rejects_short_batches() {
+        let temp = TempDir::new().unwrap();
+        let backend = ShortBackend(MockEmbeddingBackend::new(EmbeddingConfig::default()));
+        let cached = CachedEmbeddingBackend::new(
+            Box::new(backend),
+            &EmbeddingConfig::default(),
+            temp.path(),
+        );
+        let texts = vec!["fn a() {}".to_string(), "fn b() {}".to_string()];
+
+        assert!(cached.embed_batch(&texts).is_err());
+        // Nothing is cached from a mismatched batch
+        assert!(cached.cache.get(&texts[0]).is_none());
+    }
+
+    #[test]
+    fn test_cached_backend_forwards_warm_up() {
+        let temp = TempDir::new().unwrap();
+        let (backend, _, warmups) = counting_backend_with_warmups();
+        let cached = CachedEmbeddingBackend::new(backend, &EmbeddingConfig::default(), temp.path());
+        cached.warm_up().unwrap();
+        assert_eq!(warmups.load(Ordering::SeqCst), 1);
+    }
+}
@@ -38,8 +38,9 @@ use crate::timeline::{
 };
 use crate::types::{
     AddOptions, AddResult, AddSourceSkip, BaseName, CommitOptions, CommitResult, CommitResultBase,
-    MemoryIngestResult, MemoryMetricsResult, MemoryPruneEngineResult, ReindexOptions,
-    ReindexResult, StatsQuery, StatsReport, UnstageOptions, UnstageResult, UnstageSourceSkip,
+    IndexMigrateOptions, IndexMigrateResult, MemoryIngestResult, MemoryMetricsResult,
+    MemoryPruneEngineResult, ReindexOptions, ReindexResult, StatsQuery, StatsReport,
+    UnstageOptions, UnstageResult, UnstageSourceSkip,
 };
 use crate::workspace::{BranchName, Workspace, GUIDED_DIR, KNOWLEDGE_DIR};
 
@@ -204,7 +205,10 @@ impl GikEngine {
 
         // Priority 2: Read from Git HEAD
         if workspace.has_git() {
-            let head_path = workspace.root().join(".git/HEAD");
+            // Linked worktrees keep HEAD in their own Git directory
+            let head_path = crate::git::git_dir(workspace.root())
+                .unwrap_or_else(|| workspace.root().join(".git"))
+                .join("HEAD");
             let content = fs::read_to_string(&head_path).map_err(|e| {
                 GikError::BranchDetectionFailed(format!("Failed to read .git/HEAD: {}", e))
             })?;
@@ -751,6 +755,7 @@ impl GikEngine {
                         file_path: entry.file_path,
                         indexed_mtime: entry.indexed_mtime,
                         indexed_size: entry.indexed_size,
+                        content_hash: entry.content_hash,
                     },
                 );
             }
@@ -1066,27 +1071,7 @@ impl GikEngine {
         let embedding_config = self.embedding_config_for_base(workspace, &opts.base);
 
         // Get current git commit if available
-        let git_commit = if workspace.has_git() {
-            // Try to read git HEAD
-            let head_path = workspace.root().join(".git/HEAD");
-            fs::read_to_string(&head_path).ok().and_then(|content| {
-                let content = content.trim();
-                // Handle symbolic ref
-                if let Some(refpath) = content.strip_prefix("ref: ") {
-                    let ref_file = workspace.root().join(".git").join(refpath);
-                    fs::read_to_string(ref_file)
-                        .ok()
-                        .map(|s| s.trim().to_string())
-                } else if content.len() == 40 && content.chars().all(|c| c.is_ascii_hexdigit()) {
-                    // Detached HEAD - content is the commit hash
-                    Some(content.to_string())
-                } else {
-                    None
-                }
-            })
-        } else {
-            None
-        };
+        let git_commit = crate::git::read_head_commit(workspace.root());
 
         // Run reindex with resolved branch
         let resolved_opts = ReindexOptions {
@@ -1099,7 +1084,7 @@ impl GikEngine {
             &embedding_config,
             Some(&RevisionId::generate()),
             git_commit.as_deref(),
-            self.global_config.device,
+            &self.global_config,
         )?;
 
         // If we have a revision and not dry_run, append to timeline
@@ -1142,6 +1127,64 @@ impl GikEngine {
         Ok(result)
     }
 
+    /// Move a base's vector index to another backend without re-embedding.
+    ///
+    /// Copies the stored vectors and payloads into the target backend,
+    /// verifies the vector count and rewrites the index metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace containing the base.
+    /// * `opts` - Options including the base name and target backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized,
+    /// [`GikError::BaseNotFound`] if the base does not exist,
+    /// [`GikError::BaseNotIndexed`] if it has no index, or
+    /// [`GikError::IndexMigrateError`] if the migration fails.
+    pub fn migrate_index(
+        &self,
+        workspace: &Workspace,
+        opts: IndexMigrateOptions,
+    ) -> Result<IndexMigrateResult, GikError> {
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+
+        // Resolve branch
+        let branch = match opts.branch {
+            Some(branch) => branch,
+            None => self.current_branch(workspace)?.to_string(),
+        };
+
+        if !crate::base::base_exists(workspace.knowledge_root(), &branch, &opts.base) {
+            return Err(GikError::BaseNotFound(opts.base.clone()));
+        }
+
+        let embedding_config = self.embedding_config_for_base(workspace, &opts.base);
+        let to = opts.to.parse().unwrap_or_default();
+
+        let result = crate::reindex::migrate_base_index(
+            workspace,
+            &branch,
+            &opts.base,
+            to,
+            &embedding_config,
+            &self.global_config,
+        )?;
+
+        tracing::info!(
+            base = %result.base,
+            from = %result.from_backend,
+            to = %result.to_backend,
+            vectors = result.vectors,
+            "Index migration complete"
+        );
+
+        Ok(result)
+    }
+
     // -------------------------------------------------------------------------
     // Memory Ingestion
     // -------------------------------------------------------------------------
@@ -1213,7 +1256,11 @@ impl GikEngine {
         let revision_id = RevisionId::generate();
 
         // Run ingestion
-        let opts = MemoryIngestionOptions::default();
+        let opts = MemoryIngestionOptions {
+            device_pref: self.global_config.device,
+            performance: self.global_config.performance.clone(),
+            ..Default::default()
+        };
         let result = ingest_memory_entries(
             workspace.knowledge_root(),
             branch_str,
@@ -1241,7 +1288,10 @@ impl GikEngine {
             count: result.ingested_count,
         };
 
-        let revision = Revision::new(branch_str, parent_id, message, vec![operation]);
+        let mut revision = Revision::new(branch_str, parent_id, message, vec![operation]);
+        if let Some(sha) = crate::git::read_head_commit(workspace.root()) {
+            revision = revision.with_git_commit(sha);
+        }
 
         // Append to timeline and update HEAD
         append_revision(&timeline_path, &revision)?;
@@ -1457,6 +1507,225 @@ impl GikEngine {
         })
     }
 
+    // -------------------------------------------------------------------------
+    // Git Integration
+    // -------------------------------------------------------------------------
+
+    /// Install GIK-managed Git hooks (`post-commit`, `post-merge`, `post-checkout`).
+    ///
+    /// Each hook calls `gik hooks run <hook>` to stage the files changed by the
+    /// Git operation, so the next `gik commit` picks them up.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace whose Git repository receives the hooks.
+    /// * `force` - Overwrite existing hooks that were not written by GIK.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotAGitRepository`] if the workspace has no `.git` directory.
+    pub fn install_git_hooks(
+        &self,
+        workspace: &Workspace,
+        force: bool,
+    ) -> Result<crate::git::HooksReport, GikError> {
+        if !workspace.has_git() {
+            return Err(GikError::NotAGitRepository(workspace.root().to_path_buf()));
+        }
+        crate::git::install_hooks(workspace.root(), force)
+    }
+
+    /// Remove GIK-managed Git hooks, leaving user-authored hooks untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotAGitRepository`] if the workspace has no `.git` directory.
+    pub fn uninstall_git_hooks(
+        &self,
+        workspace: &Workspace,
+    ) -> Result<crate::git::HooksReport, GikError> {
+        if !workspace.has_git() {
+            return Err(GikError::NotAGitRepository(workspace.root().to_path_buf()));
+        }
+        crate::git::uninstall_hooks(workspace.root())
+    }
+
+    /// Handle a Git hook invocation by staging the files it changed.
+    ///
+    /// Hooks must never get in the way of Git, so this returns `Ok(None)` when
+    /// there is nothing to do: the workspace or branch is not initialized, or
+    /// the operation changed no stageable files.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to stage into.
+    /// * `hook` - The hook being run.
+    /// * `args` - Arguments Git passed to the hook.
+    ///
+    /// # Returns
+    ///
+    /// The [`AddResult`] of staging the changed files, if any were staged.
+    pub fn run_git_hook(
+        &self,
+        workspace: &Workspace,
+        hook: crate::git::GitHookKind,
+        args: &[String],
+    ) -> Result<Option<AddResult>, GikError> {
+        if !workspace.is_initialized() || !workspace.has_git() {
+            return Ok(None);
+        }
+
+        let branch = self.current_branch(workspace)?;
+        if !self.branch_exists(workspace, &branch) {
+            return Ok(None);
+        }
+
+        let targets: Vec<String> =
+            crate::git::changed_paths_for_hook(workspace.root(), hook, args)?
+                .into_iter()
+                .filter(|p| !p.starts_with(GUIDED_DIR))
+                .filter(|p| {
+                    let ext = Path::new(p)
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .unwrap_or("");
+                    !is_binary_extension(ext)
+                })
+                .map(|p| workspace.root().join(p))
+                .filter(|p| p.is_file())
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect();
+
+        if targets.is_empty() {
+            return Ok(None);
+        }
+
+        tracing::debug!(hook = %hook, count = targets.len(), "Staging files from git hook");
+
+        let result = self.add(
+            workspace,
+            &branch,
+            AddOptions {
+                targets,
+                base: None,
+            },
+        )?;
+        Ok(Some(result))
+    }
+
+    /// Import Git commit history into the memory base.
+    ///
+    /// Commits become `commit_context` memory entries tagged with their
+    /// Conventional Commits type and, when applicable, `breaking-change`. The
+    /// commit SHA and touched paths are kept in the entry metadata. Commits that
+    /// were already imported are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to import into.
+    /// * `opts` - Range, limit, and filtering options.
+    ///
+    /// # Returns
+    ///
+    /// A [`GitLogImportResult`](crate::git::GitLogImportResult) with the imported
+    /// commits and the revision ID (if a revision was created).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized,
+    /// [`GikError::NotAGitRepository`] if it has no Git repository, or
+    /// [`GikError::GitCommandFailed`] if `git log` fails.
+    pub fn import_git_log(
+        &self,
+        workspace: &Workspace,
+        opts: &crate::git::GitLogImportOptions,
+    ) -> Result<crate::git::GitLogImportResult, GikError> {
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+        if !workspace.has_git() {
+            return Err(GikError::NotAGitRepository(workspace.root().to_path_buf()));
+        }
+
+        let branch = self.current_branch(workspace)?;
+        let commits =
+            crate::git::read_git_log(workspace.root(), opts.since.as_deref(), opts.limit)?;
+        let scanned = commits.len();
+
+        // Collect memory IDs that are already present so re-imports are no-ops
+        let existing_ids = self.memory_entry_ids(workspace, &branch)?;
+
+        let mut imported = Vec::new();
+        let mut already_imported = 0;
+        let mut skipped_without_context = 0;
+        for commit in commits {
+            if existing_ids.contains(crate::git::git_memory_id(&commit.sha).as_str()) {
+                already_imported += 1;
+            } else if !opts.include_all && !commit.has_context() {
+                skipped_without_context += 1;
+            } else {
+                imported.push(commit);
+            }
+        }
+
+        let mut result = crate::git::GitLogImportResult {
+            scanned,
+            imported,
+            already_imported,
+            skipped_without_context,
+            revision_id: None,
+        };
+
+        if opts.dry_run || result.imported.is_empty() {
+            return Ok(result);
+        }
+
+        // Ingest oldest first so memory order follows history
+        let entries: Vec<MemoryEntry> = result
+            .imported
+            .iter()
+            .rev()
+            .map(|c| c.to_memory_entry())
+            .collect();
+        let message = format!(
+            "Import {} commit{} from git log",
+            entries.len(),
+            if entries.len() == 1 { "" } else { "s" }
+        );
+
+        let ingest = self.ingest_memory(workspace, entries, Some(&message))?;
+        result.revision_id = ingest.revision_id;
+
+        Ok(result)
+    }
+
+    /// IDs of the memory entries already indexed in the memory base.
+    fn memory_entry_ids(
+        &self,
+        workspace: &Workspace,
+        branch: &BranchName,
+    ) -> Result<std::collections::HashSet<String>, GikError> {
+        let sources_file = crate::base::sources_path(&crate::base::base_root(
+            workspace.knowledge_root(),
+            branch.as_str(),
+            MEMORY_BASE_NAME,
+        ));
+        if !sources_file.exists() {
+            return Ok(std::collections::HashSet::new());
+        }
+
+        Ok(crate::base::load_base_sources(&sources_file)?
+            .into_iter()
+            .filter_map(|s| {
+                s.extra
+                    .as_ref()?
+                    .get("memory_id")?
+                    .as_str()
+                    .map(|id| id.to_string())
+            })
+            .collect())
+    }
+
     // -------------------------------------------------------------------------
     // Knowledge Graph (KG) Operations
     // -------------------------------------------------------------------------
@@ -1774,6 +2043,504 @@ impl GikEngine {
         Ok(Some(output))
     }
 
+    /// Export the whole branch KG to a writer, without node or edge caps.
+    ///
+    /// GraphML, GEXF and JSON-LD are read from the store in pages and
+    /// streamed by a [`KgGraphWriter`](crate::kg::KgGraphWriter), so the
+    /// graph is never loaded whole. DOT, Mermaid and JSON are rendered in
+    /// memory by [`export_kg`](crate::kg::export_kg). Neo4j CSV needs two
+    /// files, see [`export_kg_neo4j`](Self::export_kg_neo4j).
+    ///
+    /// # Returns
+    ///
+    /// A [`KgExportReport`](crate::kg::KgExportReport), or None if no KG exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized,
+    /// [`GikError::InvalidArgument`] for Neo4j CSV, or [`GikError::Io`] if
+    /// writing fails.
+    pub fn export_kg_graph(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+        format: crate::kg::KgExportFormat,
+        out: &mut dyn std::io::Write,
+    ) -> Result<Option<crate::kg::KgExportReport>, GikError> {
+        use crate::kg::export_stream::{
+            write_graph_paged, GexfWriter, GraphMlWriter, JsonLdWriter, EXPORT_PAGE_SIZE,
+        };
+        use crate::kg::KgExportFormat;
+        use std::collections::HashSet;
+
+        if format == KgExportFormat::Neo4jCsv {
+            return Err(GikError::InvalidArgument(
+                "Neo4j CSV export writes nodes.csv and relationships.csv; pass an output directory"
+                    .to_string(),
+            ));
+        }
+        let Some((branch, store)) = self.open_kg_for_export(workspace, branch)? else {
+            return Ok(None);
+        };
+        let title = format!("GIK Knowledge Graph ({})", branch);
+
+        let counts = match format {
+            KgExportFormat::GraphMl => write_graph_paged(
+                &mut GraphMlWriter::new(out).with_title(title),
+                &store,
+                EXPORT_PAGE_SIZE,
+            )?,
+            KgExportFormat::Gexf => write_graph_paged(
+                &mut GexfWriter::new(out).with_title(title),
+                &store,
+                EXPORT_PAGE_SIZE,
+            )?,
+            KgExportFormat::JsonLd => write_graph_paged(
+                &mut JsonLdWriter::new(out).with_title(title),
+                &store,
+                EXPORT_PAGE_SIZE,
+            )?,
+            _ => {
+                let nodes = store.get_all_nodes()?;
+                let edges = store.get_all_edges()?;
+                let opts = crate::kg::KgExportOptions::new().with_title(title);
+                writeln!(
+                    out,
+                    "{}",
+                    crate::kg::export_kg(&nodes, &edges, format, opts)
+                )?;
+                let node_ids: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+                let linked = edges
+                    .iter()
+                    .filter(|e| {
+                        node_ids.contains(e.from.as_str()) && node_ids.contains(e.to.as_str())
+                    })
+                    .count();
+                crate::kg::export_stream::KgWriteCounts {
+                    nodes: nodes.len(),
+                    edges: linked,
+                    skipped_edges: edges.len() - linked,
+                }
+            }
+        };
+
+        Ok(Some(crate::kg::KgExportReport {
+            branch: branch.to_string(),
+            format: format.to_string(),
+            nodes: counts.nodes,
+            edges: counts.edges,
+            skipped_edges: counts.skipped_edges,
+            files: Vec::new(),
+        }))
+    }
+
+    /// Export the whole branch KG as `neo4j-admin database import` CSV.
+    ///
+    /// Writes `nodes.csv` and `relationships.csv` into `dir`, creating it if
+    /// needed.
+    ///
+    /// # Returns
+    ///
+    /// A [`KgExportReport`](crate::kg::KgExportReport) listing both files, or
+    /// None if no KG exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized,
+    /// or [`GikError::Io`] if the files cannot be written.
+    pub fn export_kg_neo4j(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+        dir: &Path,
+    ) -> Result<Option<crate::kg::KgExportReport>, GikError> {
+        use crate::kg::export_stream::{
+            write_graph_paged, Neo4jCsvWriter, EXPORT_PAGE_SIZE, NEO4J_NODES_FILENAME,
+            NEO4J_RELATIONSHIPS_FILENAME,
+        };
+        use std::io::BufWriter;
+
+        let Some((branch, store)) = self.open_kg_for_export(workspace, branch)? else {
+            return Ok(None);
+        };
+
+        fs::create_dir_all(dir)?;
+        let nodes_path = dir.join(NEO4J_NODES_FILENAME);
+        let relationships_path = dir.join(NEO4J_RELATIONSHIPS_FILENAME);
+        let mut writer = Neo4jCsvWriter::new(
+            BufWriter::new(fs::File::create(&nodes_path)?),
+            BufWriter::new(fs::File::create(&relationships_path)?),
+        );
+        let counts = write_graph_paged(&mut writer, &store, EXPORT_PAGE_SIZE)?;
+
+        Ok(Some(crate::kg::KgExportReport {
+            branch: branch.to_string(),
+            format: crate::kg::KgExportFormat::Neo4jCsv.to_string(),
+            nodes: counts.nodes,
+            edges: counts.edges,
+            skipped_edges: counts.skipped_edges,
+            files: vec![nodes_path, relationships_path],
+        }))
+    }
+
+    /// Resolve the branch and open its KG store, or None if it has no KG.
+    fn open_kg_for_export(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+    ) -> Result<Option<(BranchName, crate::db_adapter::DbKgStore)>, GikError> {
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+
+        let branch = match branch {
+            Some(b) => BranchName::try_new(b)?,
+            None => self.current_branch(workspace)?,
+        };
+        if !crate::kg_exists(workspace, branch.as_str()) {
+            return Ok(None);
+        }
+
+        let store = crate::kg::open_kg_store(workspace, branch.as_str())?;
+        Ok(Some((branch, store)))
+    }
+
+    /// Check the KG import graph against the architecture rules.
+    ///
+    /// Loads `.guided/knowledge/rules.yaml` (or the default rules when the
+    /// file is missing) and reports import cycles, forbidden dependency
+    /// directions, and unreferenced files. With `opts.record`, new violations
+    /// are stored as `Observation` memory entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to check.
+    /// * `branch` - Optional branch override. If None, uses current branch.
+    /// * `opts` - Check options.
+    ///
+    /// # Returns
+    ///
+    /// A [`KgCheckReport`](crate::kg::rules::KgCheckReport). An empty KG
+    /// yields a report with no files checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized,
+    /// or [`GikError::KgRulesInvalid`] if the rules file cannot be parsed.
+    pub fn kg_check(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+        opts: &crate::kg::rules::KgCheckOptions,
+    ) -> Result<crate::kg::rules::KgCheckReport, GikError> {
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+
+        let branch = match branch {
+            Some(b) => BranchName::try_new(b)?,
+            None => self.current_branch(workspace)?,
+        };
+
+        let (rules, rules_file) =
+            crate::kg::rules::KgRules::load_or_default(workspace.knowledge_root())?;
+        let nodes = crate::kg::read_all_nodes(workspace, branch.as_str())?;
+        let edges = crate::kg::read_all_edges(workspace, branch.as_str())?;
+
+        let mut report = crate::kg::rules::check_rules(branch.as_str(), &nodes, &edges, &rules);
+        report.rules_file = rules_file;
+
+        if !opts.record || report.violations.is_empty() {
+            return Ok(report);
+        }
+
+        let existing_ids = self.memory_entry_ids(workspace, &branch)?;
+        let entries: Vec<MemoryEntry> = report
+            .violations
+            .iter()
+            .map(|v| v.to_memory_entry(branch.as_str()))
+            .filter(|e| !existing_ids.contains(e.id.as_str()))
+            .collect();
+        if entries.is_empty() {
+            return Ok(report);
+        }
+
+        let message = format!(
+            "Record {} KG check violation{}",
+            entries.len(),
+            if entries.len() == 1 { "" } else { "s" }
+        );
+        let ingest = self.ingest_memory(workspace, entries, Some(&message))?;
+        report.revision_id = ingest.revision_id;
+
+        Ok(report)
+    }
+
+    /// Import a SCIP index or LSIF dump into the branch KG.
+    ///
+    /// The index is decoded and stored as `<branch>/precise/scip.json`, then
+    /// the KG is rebuilt so the snapshot is merged over the heuristic symbols
+    /// of the files it covers. Later rebuilds keep merging it until it is
+    /// cleared with [`kg_clear_scip`](Self::kg_clear_scip) or replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to import into.
+    /// * `branch` - Optional branch override. If None, uses current branch.
+    /// * `source` - Path to the `.scip` file or LSIF dump.
+    /// * `mode` - Whether to keep or drop heuristic symbols the index lacks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized,
+    /// or [`GikError::KgImportInvalid`] if the file is neither a SCIP index nor
+    /// an LSIF dump.
+    pub fn kg_import_scip(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+        source: &Path,
+        mode: crate::kg::scip::PreciseMode,
+    ) -> Result<crate::kg::scip::ScipImportReport, GikError> {
+        use crate::kg::scip::{load_precise_file, scip_snapshot_path, write_precise_index};
+        use std::collections::HashSet;
+
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+
+        let branch = match branch {
+            Some(b) => BranchName::try_new(b)?,
+            None => self.current_branch(workspace)?,
+        };
+
+        let index = load_precise_file(source, workspace.root(), mode)?;
+        write_precise_index(&scip_snapshot_path(workspace, branch.as_str()), &index)?;
+        let sync = crate::kg::sync_branch_kg_default(workspace, branch.as_str())?;
+
+        let nodes = crate::kg::read_all_nodes(workspace, branch.as_str())?;
+        let file_paths: HashSet<&str> = nodes
+            .iter()
+            .filter(|n| n.kind == "file")
+            .filter_map(|n| n.props["path"].as_str())
+            .collect();
+
+        Ok(crate::kg::scip::ScipImportReport {
+            branch: branch.to_string(),
+            source: source.to_path_buf(),
+            tool: index.tool.clone(),
+            mode,
+            documents: index.documents.len(),
+            files_matched: index
+                .documents
+                .iter()
+                .filter(|d| file_paths.contains(d.path.as_str()))
+                .count(),
+            symbols: index.symbol_count(),
+            references: index.reference_count(),
+            implementations: index.implementation_count(),
+            precise_nodes: nodes
+                .iter()
+                .filter(|n| n.props["precise"].as_bool() == Some(true))
+                .count(),
+            nodes_written: sync.nodes_written,
+            edges_written: sync.edges_written,
+        })
+    }
+
+    /// Remove the imported SCIP index of a branch and rebuild its KG.
+    ///
+    /// Returns `false` if the branch had no imported index.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized.
+    pub fn kg_clear_scip(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+    ) -> Result<bool, GikError> {
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+
+        let branch = match branch {
+            Some(b) => BranchName::try_new(b)?,
+            None => self.current_branch(workspace)?,
+        };
+
+        let path = crate::kg::scip::scip_snapshot_path(workspace, branch.as_str());
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(&path).map_err(|e| GikError::BaseStoreIo {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+        crate::kg::sync_branch_kg_default(workspace, branch.as_str())?;
+
+        Ok(true)
+    }
+
+    /// Find KG symbols by name, with fuzzy matching.
+    ///
+    /// Searches the symbol name index written by KG sync. A KG synced before
+    /// the index existed gets its index built from the stored nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to search.
+    /// * `branch` - Branch to search (defaults to the current branch).
+    /// * `query` - Symbol name, prefix, or subsequence.
+    /// * `opts` - Kind, language, and path filters plus the match limit.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the branch has no KG.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized.
+    pub fn search_symbols(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+        query: &str,
+        opts: &crate::kg::SymbolSearchOptions,
+    ) -> Result<Option<crate::kg::SymbolSearchReport>, GikError> {
+        use crate::kg::symbols::{symbol_index_path, SymbolIndex};
+
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+
+        let branch = match branch {
+            Some(b) => BranchName::try_new(b)?,
+            None => self.current_branch(workspace)?,
+        };
+        if !crate::kg_exists(workspace, branch.as_str()) {
+            return Ok(None);
+        }
+
+        let path = symbol_index_path(workspace, branch.as_str());
+        let index = match SymbolIndex::read(&path)? {
+            Some(index) => index,
+            None => {
+                let nodes = crate::kg::read_all_nodes(workspace, branch.as_str())?;
+                let index = SymbolIndex::from_nodes(&nodes);
+                index.write(&path)?;
+                index
+            }
+        };
+
+        Ok(Some(crate::kg::SymbolSearchReport::new(
+            branch.as_str(),
+            query,
+            &index,
+            opts,
+        )))
+    }
+
+    /// Run a declarative graph query against the KG.
+    ///
+    /// See [`crate::kg::graph_query`] for the query language.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to query.
+    /// * `branch` - Branch to query (defaults to the current branch).
+    /// * `query` - The query text, e.g. `MATCH (f:file)-[:imports]->(g) RETURN f.path, g.path`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the branch has no KG.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized,
+    /// or [`GikError::KgQueryInvalid`] if the query can't be parsed or matches
+    /// too many rows.
+    pub fn kg_query(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+        query: &str,
+    ) -> Result<Option<crate::kg::GraphQueryReport>, GikError> {
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+
+        // Report syntax errors even when there is no KG yet
+        let parsed = crate::kg::parse_query(query)?;
+
+        let branch = match branch {
+            Some(b) => BranchName::try_new(b)?,
+            None => self.current_branch(workspace)?,
+        };
+        if !crate::kg_exists(workspace, branch.as_str()) {
+            return Ok(None);
+        }
+
+        let store = crate::kg::open_kg_store(workspace, branch.as_str())?;
+        let result = crate::kg::execute_query(&store, &parsed)?;
+
+        Ok(Some(crate::kg::GraphQueryReport::new(
+            branch.as_str(),
+            query,
+            &result,
+        )))
+    }
+
+    /// Find the tests covering a file or symbol.
+    ///
+    /// Uses the `tests` edges built at commit time (see
+    /// [`crate::kg::test_links`]), so agents know what to run after a change.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to query.
+    /// * `branch` - Branch to query (defaults to the current branch).
+    /// * `target` - A workspace-relative file path, a symbol name or qualified
+    ///   name, or a KG node ID.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the branch has no KG. A report with no `resolved` nodes if
+    /// the target matches nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::NotInitialized`] if the workspace is not initialized.
+    pub fn kg_tests(
+        &self,
+        workspace: &Workspace,
+        branch: Option<&str>,
+        target: &str,
+    ) -> Result<Option<crate::kg::TestCoverageReport>, GikError> {
+        if !workspace.is_initialized() {
+            return Err(GikError::NotInitialized);
+        }
+
+        let branch = match branch {
+            Some(b) => BranchName::try_new(b)?,
+            None => self.current_branch(workspace)?,
+        };
+        if !crate::kg_exists(workspace, branch.as_str()) {
+            return Ok(None);
+        }
+
+        let nodes = crate::kg::read_all_nodes(workspace, branch.as_str())?;
+        let store = crate::kg::open_kg_store(workspace, branch.as_str())?;
+        let edges = store.get_edges_by_kind("tests")?;
+
+        Ok(Some(crate::kg::TestCoverageReport::new(
+            branch.as_str(),
+            target,
+            &nodes,
+            &edges,
+        )))
+    }
+
     /// Get current status of the workspace.
     ///
     /// Returns comprehensive information about the workspace state including:
@@ -2055,16 +2822,17 @@ impl GikEngine {
         branch: &BranchName,
         opts: crate::ask::AskOptions,
     ) -> Result<crate::ask::AskContextBundle, GikError> {
-        // Capture question and bases before consuming opts
+        // Capture question, bases and save flag before consuming opts
         let question = opts.question.clone();
         let bases_filter = opts.bases.clone();
+        let save_bundle = opts.save_bundle;
 
         // Load project config and resolve retrieval settings (project overrides global)
         let project_config = self.load_project_config(workspace)?;
         let retrieval_config = self.global_config.resolve_retrieval_config(&project_config);
 
         // Run the ask pipeline with resolved configs
-        let bundle = crate::ask::run_ask(workspace, branch, opts, &self.global_config, &retrieval_config)?;
+        let mut bundle = crate::ask::run_ask(workspace, branch, opts, &self.global_config, &retrieval_config)?;
 
         // Persist ask log entry
         let bases = if let Some(ref filter) = bases_filter {
@@ -2074,12 +2842,27 @@ impl GikEngine {
             bundle.bases.clone()
         };
 
-        let entry = crate::log::AskLogEntry::new(
+        let mut entry = crate::log::AskLogEntry::new(
             branch.as_str(),
             question,
             bases,
             bundle.rag_chunks.len() as u32,
-        );
+        )
+        .with_results(&bundle);
+        let stale_paths = self.stale_returned_paths(workspace, branch, &entry.sources);
+        entry = entry.with_stale_paths(stale_paths);
+        bundle.ask_id = entry.id.clone();
+
+        // Save the full bundle so it can be replayed and judged later
+        if save_bundle {
+            if let Some(ref ask_id) = entry.id {
+                // Like the log entry below, a bundle that cannot be saved does not fail the ask
+                match crate::log::save_ask_bundle(workspace, branch.as_str(), ask_id, &bundle) {
+                    Ok(path) => entry = entry.with_bundle_path(path),
+                    Err(e) => tracing::warn!("Failed to save ask bundle {}: {}", ask_id, e),
+                }
+            }
+        }
 
         // Append to ask log (ignore errors for now, don't fail the ask)
         if let Err(e) = crate::log::append_ask_log(workspace, &entry) {
@@ -2089,6 +2872,281 @@ impl GikEngine {
         Ok(bundle)
     }
 
+    /// Returned files whose content changed on disk (or was deleted) since
+    /// they were indexed, so the answer was built from outdated chunks.
+    fn stale_returned_paths(
+        &self,
+        workspace: &Workspace,
+        branch: &BranchName,
+        sources: &[crate::log::AskLogSource],
+    ) -> Vec<String> {
+        if sources.is_empty() {
+            return Vec::new();
+        }
+        let indexed_files = match self.build_indexed_files_map(workspace, branch) {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::debug!("Skipping stale check for ask log: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut stale = Vec::new();
+        for source in sources {
+            if stale.contains(&source.path) {
+                continue;
+            }
+            let Some(info) = indexed_files.get(&source.path) else {
+                continue;
+            };
+            let full_path = workspace.root().join(&source.path);
+            let changed = !full_path.exists()
+                || matches!(
+                    detect_file_change(&full_path, Some(info)),
+                    Ok(ChangeType::Modified)
+                );
+            if changed {
+                stale.push(source.path.clone());
+            }
+        }
+        stale
+    }
+
+    /// Evaluate retrieval quality against a suite of questions.
+    ///
+    /// Runs every question under each named retrieval configuration of the
+    /// suite and reports recall@k, MRR, nDCG@k and latency per configuration.
+    /// Configurations override the resolved workspace retrieval settings, and
+    /// those naming an embedding model ask against a scratch copy of the
+    /// branch re-embedded with it. Asks made during evaluation are not written
+    /// to the ask log.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to query.
+    /// * `branch` - The branch to query.
+    /// * `suite` - The questions and configurations to run.
+    /// * `opts` - Optional `k` override and configuration filter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::InvalidArgument`] if `k` is zero or a requested
+    /// configuration is not defined in the suite, or the re-embedding error if
+    /// a configuration's model cannot be used. Failures of individual
+    /// questions are recorded in the report instead.
+    pub fn eval(
+        &self,
+        workspace: &Workspace,
+        branch: &BranchName,
+        suite: &crate::eval::EvalSuite,
+        opts: crate::eval::EvalOptions,
+    ) -> Result<crate::eval::EvalReport, GikError> {
+        let k = opts.k.or(suite.k).unwrap_or(crate::eval::DEFAULT_EVAL_K);
+        if k == 0 {
+            return Err(GikError::InvalidArgument(
+                "k must be at least 1".to_string(),
+            ));
+        }
+
+        let mut configurations = suite.effective_configurations();
+        if !opts.configurations.is_empty() {
+            for name in &opts.configurations {
+                if !configurations.iter().any(|c| &c.name == name) {
+                    return Err(GikError::InvalidArgument(format!(
+                        "Unknown eval configuration '{}'",
+                        name
+                    )));
+                }
+            }
+            configurations.retain(|c| opts.configurations.contains(&c.name));
+        }
+
+        let project_config = self.load_project_config(workspace)?;
+        let base_retrieval = self.global_config.resolve_retrieval_config(&project_config);
+
+        // Configurations naming an embedding model ask against their own
+        // re-embedded copy of the branch
+        let mut model_indexes = std::collections::HashMap::new();
+        for config in &configurations {
+            if let Some(ref embedding) = config.embedding {
+                let index = crate::eval::build_model_index(
+                    workspace,
+                    branch.as_str(),
+                    embedding,
+                    &self.global_config,
+                )?;
+                model_indexes.insert(config.name.clone(), index);
+            }
+        }
+
+        let report = crate::eval::run_eval(suite, &configurations, k, |config, question| {
+            let (workspace, global_config) = match model_indexes.get(&config.name) {
+                Some(index) => (&index.workspace, &index.global_config),
+                None => (workspace, &self.global_config),
+            };
+            let mut retrieval_config = base_retrieval.clone();
+            retrieval_config.apply_override(&config.retrieval);
+
+            let mut ask_opts = crate::ask::AskOptions::new(&question.question)
+                .with_top_k(config.top_k.unwrap_or(k.max(crate::ask::DEFAULT_TOP_K)))
+                .with_final_k(k)
+                .with_stack(false);
+            if let Some(ref bases) = question.bases {
+                ask_opts = ask_opts.with_bases(bases.clone());
+            }
+
+            crate::ask::run_ask(
+                workspace,
+                branch,
+                ask_opts,
+                global_config,
+                &retrieval_config,
+            )
+        });
+
+        Ok(report)
+    }
+
+    /// Load the saved bundle of a logged ask.
+    ///
+    /// `selector` is a 1-based position in the ask log listing produced by
+    /// `scope` (newest first), or an ask ID or unique ID prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::AskNotFound`] if no ask matches, or
+    /// [`GikError::AskBundleNotSaved`] if the ask was run without `--save`.
+    pub fn show_ask(
+        &self,
+        workspace: &Workspace,
+        scope: crate::log::LogQueryScope,
+        selector: &str,
+    ) -> Result<crate::ask::AskContextBundle, GikError> {
+        let entry = crate::log::find_ask_entry(workspace, &scope, selector)?;
+        let mut bundle = crate::log::load_ask_bundle(workspace, &entry)?;
+        if bundle.ask_id.is_none() {
+            bundle.ask_id = entry.id;
+        }
+        Ok(bundle)
+    }
+
+    /// Record relevance judgements for chunks returned by a logged ask.
+    ///
+    /// Chunks are referenced by result position (requires a saved bundle),
+    /// `path:start-end` or `path`. Judgements are appended to
+    /// `asks/feedback.jsonl`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::AskNotFound`] if the ask does not exist,
+    /// [`GikError::AskBundleNotSaved`] if a position is used for an ask
+    /// without a saved bundle, or [`GikError::InvalidArgument`] for malformed
+    /// chunk references.
+    pub fn feedback(
+        &self,
+        workspace: &Workspace,
+        opts: crate::feedback::AskFeedbackOptions,
+    ) -> Result<Vec<crate::feedback::AskFeedbackEntry>, GikError> {
+        let scope = crate::log::LogQueryScope::new().with_kind(crate::log::LogKind::Ask);
+        let entry = crate::log::find_ask_entry(workspace, &scope, &opts.ask)?;
+
+        let bundle = match crate::log::load_ask_bundle(workspace, &entry) {
+            Ok(bundle) => Some(bundle),
+            Err(GikError::AskBundleNotSaved(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let entries = crate::feedback::build_feedback_entries(&entry, bundle.as_ref(), &opts)?;
+        crate::feedback::append_feedback(workspace, &entries)?;
+        Ok(entries)
+    }
+
+    /// Build an eval suite from recorded ask feedback.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::EvalSuiteInvalid`] if no ask has a file judged
+    /// good.
+    pub fn feedback_eval_suite(
+        &self,
+        workspace: &Workspace,
+    ) -> Result<crate::eval::EvalSuite, GikError> {
+        let entries = crate::feedback::load_feedback(workspace)?;
+        let suite = crate::feedback::feedback_eval_suite(&entries);
+        if suite.questions.is_empty() {
+            return Err(GikError::EvalSuiteInvalid {
+                path: crate::feedback::feedback_path(workspace),
+                reason: "no ask has a chunk judged good; record some with `gik feedback`"
+                    .to_string(),
+            });
+        }
+        Ok(suite)
+    }
+
+    /// Analyse the ask log for frequent questions, knowledge gaps, hot spots
+    /// and stale answers.
+    ///
+    /// Files that are neither indexed nor staged on `branch` are matched
+    /// against gap questions to suggest paths to `gik add`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::InvalidArgument`] if the low-score threshold is not
+    /// a finite number or the limit is zero.
+    pub fn insights(
+        &self,
+        workspace: &Workspace,
+        branch: &BranchName,
+        opts: crate::insights::InsightsOptions,
+    ) -> Result<crate::insights::InsightsReport, GikError> {
+        if !opts.low_score.is_finite() {
+            return Err(GikError::InvalidArgument(
+                "--low-score must be a number".to_string(),
+            ));
+        }
+        if opts.limit == 0 {
+            return Err(GikError::InvalidArgument(
+                "--limit must be at least 1".to_string(),
+            ));
+        }
+
+        let mut scope = crate::log::LogQueryScope::new().with_kind(crate::log::LogKind::Ask);
+        if let Some(since) = opts.since {
+            scope = scope.with_since(since);
+        }
+        if let Some(until) = opts.until {
+            scope = scope.with_until(until);
+        }
+        let asks: Vec<crate::log::AskLogView> = self
+            .log_query(workspace, scope)?
+            .entries
+            .into_iter()
+            .filter_map(|entry| match entry {
+                crate::log::LogEntry::Ask(ask) => Some(ask),
+                crate::log::LogEntry::Timeline(_) => None,
+            })
+            .collect();
+
+        // Candidate paths for gap suggestions: not indexed, not staged
+        let indexed_files = self.build_indexed_files_map(workspace, branch)?;
+        let staged: std::collections::HashSet<String> =
+            list_pending_sources(&workspace.staging_pending_path(branch.as_str()))?
+                .into_iter()
+                .map(|source| source.uri)
+                .collect();
+        let unindexed: Vec<String> = self
+            .expand_directory(workspace, workspace.root())?
+            .into_iter()
+            .filter_map(|path| {
+                let relative = path.strip_prefix(workspace.root()).ok()?;
+                Some(relative.to_string_lossy().to_string())
+            })
+            .filter(|path| !indexed_files.contains_key(path) && !staged.contains(path))
+            .collect();
+
+        Ok(crate::insights::build_insights(&asks, &unindexed, &opts))
+    }
+
     /// List available knowledge bases for a branch.
     ///
     /// Returns the names of all bases in the workspace's knowledge directory
@@ -2532,6 +3590,7 @@ impl GikEngine {
                 total_documents: 0,
                 total_vectors: 0,
                 total_on_disk_bytes: 0,
+                central_files: vec![],
             });
         }
 
@@ -2563,6 +3622,15 @@ impl GikEngine {
         let stack_stats_path = workspace.stack_stats_path(branch.as_str());
         let stack = crate::stack::read_stats_json(&stack_stats_path).ok().flatten();
 
+        // Most central files from the KG import graph (code bases only)
+        let central_files = if query.base.as_deref().is_none_or(|b| b == "code") {
+            crate::kg::read_all_nodes(workspace, branch.as_str())
+                .map(|nodes| crate::kg::centrality::most_central(&nodes, 10))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
         Ok(StatsReport {
             branch: branch.to_string(),
             bases,
@@ -2570,6 +3638,7 @@ impl GikEngine {
             total_documents,
             total_vectors,
             total_on_disk_bytes,
+            central_files,
         })
     }
 
@@ -338,6 +338,14 @@ pub enum GikError {
         reason: String,
     },
 
+    /// No ask log entry matches the given selector.
+    #[error("Ask `{0}` not found in the ask log.")]
+    AskNotFound(String),
+
+    /// The ask was logged without its full bundle.
+    #[error("Ask `{0}` has no saved bundle. Re-run the question with `gik ask --save`.")]
+    AskBundleNotSaved(String),
+
     // =========================================================================
     // Reindex Errors
     // =========================================================================
@@ -366,6 +374,62 @@ pub enum GikError {
         reason: String,
     },
 
+    /// Moving a base's vector index to another backend failed.
+    #[error("Failed to migrate vector index of base `{base}`: {reason}")]
+    IndexMigrateError {
+        /// The affected base.
+        base: String,
+        /// Description of the failure.
+        reason: String,
+    },
+
+    // -------------------------------------------------------------------------
+    // Eval Errors
+    // -------------------------------------------------------------------------
+    /// The retrieval evaluation suite could not be loaded.
+    #[error("Invalid eval suite at {path}: {reason}")]
+    EvalSuiteInvalid {
+        /// The path to the suite file.
+        path: std::path::PathBuf,
+        /// Description of the failure.
+        reason: String,
+    },
+
+    // -------------------------------------------------------------------------
+    // KG Check Errors
+    // -------------------------------------------------------------------------
+    /// The KG rules file could not be loaded.
+    #[error("Invalid KG rules at {path}: {reason}")]
+    KgRulesInvalid {
+        /// The path to the rules file.
+        path: std::path::PathBuf,
+        /// Description of the failure.
+        reason: String,
+    },
+
+    /// A code-intelligence index passed to `gik kg import` could not be read.
+    #[error("Invalid code-intelligence index at {path}: {reason}")]
+    KgImportInvalid {
+        /// The path to the index file.
+        path: std::path::PathBuf,
+        /// Description of the failure.
+        reason: String,
+    },
+
+    /// A `gik kg query` query could not be parsed or evaluated.
+    #[error("Invalid KG query: {reason}")]
+    KgQueryInvalid {
+        /// Description of the failure, with its column when known.
+        reason: String,
+    },
+
+    /// `gik kg check` found violations that fail the check.
+    #[error("KG check failed: {count} violation(s)")]
+    KgCheckFailed {
+        /// Number of failing violations.
+        count: usize,
+    },
+
     // -------------------------------------------------------------------------
     // Log Errors
     // -------------------------------------------------------------------------
@@ -397,6 +461,22 @@ pub enum GikError {
         reason: String,
     },
 
+    // -------------------------------------------------------------------------
+    // Git Errors
+    // -------------------------------------------------------------------------
+    /// The workspace is not inside a Git repository.
+    #[error("Not a Git repository: {0}")]
+    NotAGitRepository(std::path::PathBuf),
+
+    /// A `git` command failed or could not be executed.
+    #[error("Git command `{command}` failed: {reason}")]
+    GitCommandFailed {
+        /// The command that was run.
+        command: String,
+        /// Description of the failure.
+        reason: String,
+    },
+
     /// An I/O error occurred.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -0,0 +1,856 @@
+//! Retrieval evaluation for `gik eval`.
+//!
+//! This module measures how well the ask pipeline finds the files and symbols
+//! a question is expected to surface:
+//! - [`EvalSuite`] - questions with expected paths/symbols, plus named
+//!   retrieval configurations to compare (loaded from YAML)
+//! - [`run_eval`] - runs every question under every configuration and scores
+//!   the retrieved chunks
+//! - [`EvalReport`] - recall@k, MRR, nDCG@k and latency per configuration
+//!
+//! ## Suite Format
+//!
+//! ```yaml
+//! k: 5
+//! configurations:
+//!   - name: baseline
+//!   - name: dense-heavy
+//!     retrieval:
+//!       hybrid:
+//!         denseWeight: 0.8
+//!         sparseWeight: 0.2
+//!   - name: bge-small
+//!     embedding:
+//!       modelId: BAAI/bge-small-en-v1.5
+//!       localPath: /opt/models/bge-small-en-v1.5
+//! questions:
+//!   - id: commit-embedding
+//!     question: How does commit embed staged sources?
+//!     expectedPaths: [crates/gik-core/src/commit.rs]
+//!     expectedSymbols: [run_commit]
+//! ```
+//!
+//! `retrieval` uses the same keys as the project-level `retrieval` overrides in
+//! `.guided/knowledge/config.yaml`. When no configuration is listed, a single
+//! `default` configuration runs with the resolved workspace settings.
+//!
+//! `embedding` uses the keys of `embeddings.default` in the global config and
+//! compares embedding models: before its questions run, the branch's indexed
+//! bases are copied to a scratch directory and re-embedded with that model
+//! (see [`build_model_index`]), and queries are embedded with it too. The
+//! scratch copy is removed when the run ends.
+//!
+//! ## Scoring
+//!
+//! Each expected path or symbol is a target. A chunk hits a path target when
+//! its path equals it (or starts with it, for targets ending in `/`), and a
+//! symbol target when its snippet contains the symbol as a whole identifier.
+//! Only the top `k` chunks are scored; a chunk contributes to nDCG only when it
+//! hits a target no earlier chunk already hit.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ask::{AskContextBundle, RagChunk};
+use crate::config::{EmbeddingOverride, GlobalConfig, RetrievalConfigOverride};
+use crate::errors::GikError;
+use crate::workspace::Workspace;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Default number of top chunks scored per question.
+pub const DEFAULT_EVAL_K: usize = 5;
+
+/// Name of the configuration used when a suite lists none.
+pub const DEFAULT_EVAL_CONFIGURATION: &str = "default";
+
+// ============================================================================
+// Suite
+// ============================================================================
+
+/// A retrieval evaluation suite.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalSuite {
+    /// Number of top chunks scored per question (default: 5).
+    #[serde(default)]
+    pub k: Option<usize>,
+
+    /// Named retrieval configurations to compare.
+    #[serde(default)]
+    pub configurations: Vec<EvalConfiguration>,
+
+    /// Questions to ask.
+    pub questions: Vec<EvalQuestion>,
+}
+
+/// A question with the paths and symbols it should retrieve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalQuestion {
+    /// Optional stable identifier (defaults to `q<n>`).
+    #[serde(default)]
+    pub id: Option<String>,
+
+    /// The question passed to the ask pipeline.
+    pub question: String,
+
+    /// Workspace-relative file paths expected in the results.
+    #[serde(default)]
+    pub expected_paths: Vec<String>,
+
+    /// Identifiers expected to appear in retrieved snippets.
+    #[serde(default)]
+    pub expected_symbols: Vec<String>,
+
+    /// Restrict the question to specific bases.
+    #[serde(default)]
+    pub bases: Option<Vec<String>>,
+}
+
+/// A named retrieval configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalConfiguration {
+    /// Configuration name shown in reports.
+    pub name: String,
+
+    /// Candidates retrieved per base before reranking (default: `k`).
+    #[serde(default)]
+    pub top_k: Option<usize>,
+
+    /// Retrieval overrides applied on top of the workspace settings.
+    #[serde(default)]
+    pub retrieval: RetrievalConfigOverride,
+
+    /// Embedding model to ask with instead of each base's indexed model.
+    #[serde(default)]
+    pub embedding: Option<EmbeddingOverride>,
+}
+
+impl EvalSuite {
+    /// Load and validate a suite from a YAML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::EvalSuiteInvalid`] if the file cannot be read or
+    /// parsed, has no questions, has a question without expectations, or
+    /// repeats a configuration name.
+    pub fn load(path: &Path) -> Result<Self, GikError> {
+        let invalid = |reason: String| GikError::EvalSuiteInvalid {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let content = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        let suite: EvalSuite =
+            serde_yaml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+        suite.validate().map_err(invalid)?;
+        Ok(suite)
+    }
+
+    /// Check the suite for structural problems.
+    fn validate(&self) -> Result<(), String> {
+        if self.questions.is_empty() {
+            return Err("suite has no questions".to_string());
+        }
+        if self.k == Some(0) {
+            return Err("k must be at least 1".to_string());
+        }
+
+        for (i, question) in self.questions.iter().enumerate() {
+            let id = question_id(question, i);
+            if question.question.trim().is_empty() {
+                return Err(format!("question '{}' is empty", id));
+            }
+            if question.expected_paths.is_empty() && question.expected_symbols.is_empty() {
+                return Err(format!(
+                    "question '{}' lists no expectedPaths or expectedSymbols",
+                    id
+                ));
+            }
+        }
+
+        let mut names = HashSet::new();
+        for config in &self.configurations {
+            if config.name.trim().is_empty() {
+                return Err("configuration name cannot be empty".to_string());
+            }
+            if !names.insert(config.name.as_str()) {
+                return Err(format!("configuration '{}' is defined twice", config.name));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The configurations to run, falling back to a single default one.
+    pub fn effective_configurations(&self) -> Vec<EvalConfiguration> {
+        if self.configurations.is_empty() {
+            vec![EvalConfiguration {
+                name: DEFAULT_EVAL_CONFIGURATION.to_string(),
+                ..Default::default()
+            }]
+        } else {
+            self.configurations.clone()
+        }
+    }
+}
+
+/// Identifier of a question, defaulting to its 1-based position.
+fn question_id(question: &EvalQuestion, index: usize) -> String {
+    question
+        .id
+        .clone()
+        .unwrap_or_else(|| format!("q{}", index + 1))
+}
+
+// ============================================================================
+// Options
+// ============================================================================
+
+/// Options for [`crate::GikEngine::eval`].
+#[derive(Debug, Clone, Default)]
+pub struct EvalOptions {
+    /// Override the suite's `k`.
+    pub k: Option<usize>,
+
+    /// Only run these configurations (empty = all).
+    pub configurations: Vec<String>,
+}
+
+// ============================================================================
+// Model Comparison
+// ============================================================================
+
+/// A branch's bases re-embedded with another model, for one configuration.
+///
+/// The knowledge lives in a scratch directory removed on drop. The workspace
+/// root is unchanged, so sources stored without text are re-read from disk.
+pub struct ModelIndex {
+    /// Keeps the scratch directory alive.
+    _dir: tempfile::TempDir,
+
+    /// The workspace with its knowledge root at the scratch copy.
+    pub workspace: Workspace,
+
+    /// Global settings that resolve every base to the model.
+    pub global_config: GlobalConfig,
+}
+
+/// Copy `branch` to a scratch knowledge root and re-embed its indexed bases
+/// with `embedding`.
+///
+/// # Errors
+///
+/// Returns an error if the copy fails or a base cannot be re-embedded (for
+/// example, when the model cannot be loaded).
+pub fn build_model_index(
+    workspace: &Workspace,
+    branch: &str,
+    embedding: &EmbeddingOverride,
+    global_config: &GlobalConfig,
+) -> Result<ModelIndex, GikError> {
+    let dir = tempfile::Builder::new().prefix("gik-eval-").tempdir()?;
+    copy_branch(&workspace.branch_dir(branch), &dir.path().join(branch))?;
+    let scratch = workspace.with_knowledge_root(dir.path().to_path_buf());
+
+    let mut model_config = global_config.clone();
+    model_config.embeddings.default = Some(embedding.clone());
+    model_config.embeddings.bases.clear();
+    let embedding_config = embedding.to_core_config();
+
+    let mut bases: Vec<String> = fs::read_dir(scratch.branch_dir(branch).join("bases"))?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|base| crate::base::is_base_indexed(scratch.knowledge_root(), branch, base))
+        .collect();
+    bases.sort();
+
+    for base in &bases {
+        let result = crate::reindex::reindex_base(
+            &scratch,
+            branch,
+            base,
+            &embedding_config,
+            true,
+            false,
+            &model_config,
+        )?;
+        for error in &result.errors {
+            tracing::warn!("Eval re-embedding of '{}': {}", base, error);
+        }
+    }
+
+    Ok(ModelIndex {
+        _dir: dir,
+        workspace: scratch,
+        global_config: model_config,
+    })
+}
+
+/// Recursively copy a branch directory, leaving out base vector indexes,
+/// which are rebuilt.
+fn copy_branch(from: &Path, to: &Path) -> Result<(), GikError> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            let in_base = from.parent().and_then(|p| p.file_name()) == Some("bases".as_ref());
+            if !(in_base && entry.file_name() == "index") {
+                copy_branch(&entry.path(), &target)?;
+            }
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// Result of an evaluation run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalReport {
+    /// Number of top chunks scored per question.
+    pub k: usize,
+
+    /// Number of questions in the suite.
+    pub questions: usize,
+
+    /// Per-configuration results, in suite order.
+    pub configurations: Vec<EvalConfigReport>,
+}
+
+/// Aggregate metrics for one configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalConfigReport {
+    /// Configuration name.
+    pub name: String,
+
+    /// Mean recall@k over all questions.
+    pub recall_at_k: f64,
+
+    /// Mean reciprocal rank of the first hit.
+    pub mrr: f64,
+
+    /// Mean nDCG@k.
+    pub ndcg_at_k: f64,
+
+    /// Mean ask latency in milliseconds (successful questions only).
+    pub latency_mean_ms: f64,
+
+    /// 95th percentile ask latency in milliseconds.
+    pub latency_p95_ms: u64,
+
+    /// Number of questions whose ask failed (scored as zero).
+    pub failed: usize,
+
+    /// Per-question results.
+    pub results: Vec<EvalQuestionResult>,
+}
+
+/// Scores for a single question under one configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalQuestionResult {
+    /// Question identifier.
+    pub id: String,
+
+    /// Fraction of expected paths/symbols found in the top k.
+    pub recall: f64,
+
+    /// 1 / rank of the first chunk that hits a target (0 if none).
+    pub reciprocal_rank: f64,
+
+    /// Normalized discounted cumulative gain over the top k.
+    pub ndcg: f64,
+
+    /// Ask latency in milliseconds.
+    pub latency_ms: u64,
+
+    /// Retrieved chunks (top k) as `path:start-end`.
+    pub retrieved: Vec<String>,
+
+    /// Expected paths/symbols not found in the top k.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+
+    /// Error message if the ask failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Running
+// ============================================================================
+
+/// Run every question of a suite under each configuration.
+///
+/// `ask` runs the ask pipeline for one question under one configuration; it
+/// is timed to produce the latency figures. A failing ask is recorded with
+/// zero scores instead of aborting the run.
+pub fn run_eval<F>(
+    suite: &EvalSuite,
+    configurations: &[EvalConfiguration],
+    k: usize,
+    mut ask: F,
+) -> EvalReport
+where
+    F: FnMut(&EvalConfiguration, &EvalQuestion) -> Result<AskContextBundle, GikError>,
+{
+    let mut reports = Vec::with_capacity(configurations.len());
+
+    for config in configurations {
+        let mut results = Vec::with_capacity(suite.questions.len());
+
+        for (i, question) in suite.questions.iter().enumerate() {
+            let id = question_id(question, i);
+            let start = Instant::now();
+            let outcome = ask(config, question);
+            let latency_ms = start.elapsed().as_millis() as u64;
+
+            let result = match outcome {
+                Ok(bundle) => score_question(id, question, &bundle.rag_chunks, k, latency_ms),
+                Err(e) => {
+                    tracing::warn!(
+                        "Eval question '{}' failed under '{}': {}",
+                        id,
+                        config.name,
+                        e
+                    );
+                    EvalQuestionResult {
+                        id,
+                        recall: 0.0,
+                        reciprocal_rank: 0.0,
+                        ndcg: 0.0,
+                        latency_ms,
+                        retrieved: Vec::new(),
+                        missing: targets(question).iter().map(|t| t.label()).collect(),
+                        error: Some(e.to_string()),
+                    }
+                }
+            };
+            results.push(result);
+        }
+
+        reports.push(aggregate(&config.name, results));
+    }
+
+    EvalReport {
+        k,
+        questions: suite.questions.len(),
+        configurations: reports,
+    }
+}
+
+/// Combine per-question results into configuration-level metrics.
+fn aggregate(name: &str, results: Vec<EvalQuestionResult>) -> EvalConfigReport {
+    let n = results.len().max(1) as f64;
+    let mean = |f: fn(&EvalQuestionResult) -> f64| results.iter().map(f).sum::<f64>() / n;
+
+    let mut latencies: Vec<u64> = results
+        .iter()
+        .filter(|r| r.error.is_none())
+        .map(|r| r.latency_ms)
+        .collect();
+    latencies.sort_unstable();
+
+    let latency_mean_ms = if latencies.is_empty() {
+        0.0
+    } else {
+        latencies.iter().sum::<u64>() as f64 / latencies.len() as f64
+    };
+    let latency_p95_ms = if latencies.is_empty() {
+        0
+    } else {
+        let rank = ((latencies.len() as f64) * 0.95).ceil() as usize;
+        latencies[rank.saturating_sub(1)]
+    };
+
+    EvalConfigReport {
+        name: name.to_string(),
+        recall_at_k: mean(|r| r.recall),
+        mrr: mean(|r| r.reciprocal_rank),
+        ndcg_at_k: mean(|r| r.ndcg),
+        latency_mean_ms,
+        latency_p95_ms,
+        failed: results.iter().filter(|r| r.error.is_some()).count(),
+        results,
+    }
+}
+
+// ============================================================================
+// Scoring
+// ============================================================================
+
+/// Something a question expects to retrieve.
+enum Target<'a> {
+    Path(&'a str),
+    Symbol(&'a str),
+}
+
+impl Target<'_> {
+    fn matches(&self, chunk: &RagChunk) -> bool {
+        match self {
+            Target::Path(expected) => {
+                let path = normalize_path(&chunk.path);
+                let expected = normalize_path(expected);
+                if expected.ends_with('/') {
+                    path.starts_with(&expected)
+                } else {
+                    path == expected
+                }
+            }
+            Target::Symbol(symbol) => contains_identifier(&chunk.snippet, symbol),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Target::Path(path) => (*path).to_string(),
+            Target::Symbol(symbol) => format!("symbol:{}", symbol),
+        }
+    }
+}
+
+fn targets(question: &EvalQuestion) -> Vec<Target<'_>> {
+    question
+        .expected_paths
+        .iter()
+        .map(|p| Target::Path(p))
+        .chain(question.expected_symbols.iter().map(|s| Target::Symbol(s)))
+        .collect()
+}
+
+/// Score the top `k` chunks of one question.
+fn score_question(
+    id: String,
+    question: &EvalQuestion,
+    chunks: &[RagChunk],
+    k: usize,
+    latency_ms: u64,
+) -> EvalQuestionResult {
+    let targets = targets(question);
+    let top = &chunks[..chunks.len().min(k)];
+
+    let mut found = vec![false; targets.len()];
+    let mut reciprocal_rank = 0.0;
+    let mut dcg = 0.0;
+
+    for (rank, chunk) in top.iter().enumerate() {
+        let mut hit = false;
+        let mut new_hit = false;
+        for (t, target) in targets.iter().enumerate() {
+            if target.matches(chunk) {
+                hit = true;
+                if !found[t] {
+                    found[t] = true;
+                    new_hit = true;
+                }
+            }
+        }
+        if hit && reciprocal_rank == 0.0 {
+            reciprocal_rank = 1.0 / (rank + 1) as f64;
+        }
+        if new_hit {
+            dcg += 1.0 / ((rank + 2) as f64).log2();
+        }
+    }
+
+    let ideal: f64 = (0..targets.len().min(k))
+        .map(|rank| 1.0 / ((rank + 2) as f64).log2())
+        .sum();
+    let hits = found.iter().filter(|f| **f).count();
+
+    EvalQuestionResult {
+        id,
+        recall: if targets.is_empty() {
+            0.0
+        } else {
+            hits as f64 / targets.len() as f64
+        },
+        reciprocal_rank,
+        ndcg: if ideal > 0.0 { dcg / ideal } else { 0.0 },
+        latency_ms,
+        retrieved: top
+            .iter()
+            .map(|c| format!("{}:{}-{}", c.path, c.start_line, c.end_line))
+            .collect(),
+        missing: targets
+            .iter()
+            .zip(&found)
+            .filter(|(_, found)| !**found)
+            .map(|(target, _)| target.label())
+            .collect(),
+        error: None,
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+/// Whether `text` contains `symbol` not surrounded by identifier characters.
+fn contains_identifier(text: &str, symbol: &str) -> bool {
+    if symbol.is_empty() {
+        return false;
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(symbol).any(|(pos, _)| {
+        let before = text[..pos].chars().next_back();
+        let after = text[pos + symbol.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ask::AskDebugInfo;
+    use crate::timeline::RevisionId;
+
+    fn chunk(path: &str, snippet: &str) -> RagChunk {
+        RagChunk {
+            base: "code".to_string(),
+            score: 0.5,
+            path: path.to_string(),
+            start_line: 1,
+            end_line: 10,
+            snippet: snippet.to_string(),
+            dense_score: None,
+            reranker_score: None,
+            ownership: None,
+        }
+    }
+
+    fn bundle(rag_chunks: Vec<RagChunk>) -> AskContextBundle {
+        AskContextBundle {
+            ask_id: None,
+            revision_id: RevisionId::new("rev-1"),
+            question: "q".to_string(),
+            bases: vec!["code".to_string()],
+            rag_chunks,
+            kg_results: vec![],
+            memory_events: vec![],
+            stack_summary: None,
+            debug: AskDebugInfo {
+                embedding_model_id: "test-model".to_string(),
+                used_bases: vec!["code".to_string()],
+                per_base_counts: vec![],
+                embed_time_ms: None,
+                search_time_ms: None,
+                reranker_used: false,
+                rerank_time_ms: None,
+                hybrid_search_used: false,
+                dense_result_count: None,
+                sparse_result_count: None,
+                filename_detected: None,
+            },
+        }
+    }
+
+    fn question(paths: &[&str], symbols: &[&str]) -> EvalQuestion {
+        EvalQuestion {
+            id: None,
+            question: "How is it done?".to_string(),
+            expected_paths: paths.iter().map(|s| s.to_string()).collect(),
+            expected_symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            bases: None,
+        }
+    }
+
+    #[test]
+    fn test_score_question_metrics() {
+        let q = question(&["src/commit.rs", "src/ask.rs"], &[]);
+        let chunks = vec![
+            chunk("src/lib.rs", ""),
+            chunk("src/commit.rs", ""),
+            chunk("src/commit.rs", ""),
+            chunk("src/other.rs", ""),
+        ];
+
+        let result = score_question("q1".to_string(), &q, &chunks, 3, 0);
+
+        assert_eq!(result.recall, 0.5);
+        assert_eq!(result.reciprocal_rank, 0.5);
+        // One new hit at rank 2; ideal has hits at ranks 1 and 2.
+        let expected_ndcg = (1.0 / 3f64.log2()) / (1.0 + 1.0 / 3f64.log2());
+        assert!((result.ndcg - expected_ndcg).abs() < 1e-9);
+        assert_eq!(result.retrieved.len(), 3);
+        assert_eq!(result.missing, vec!["src/ask.rs"]);
+    }
+
+    #[test]
+    fn test_symbol_and_directory_targets() {
+        let q = question(&["./src/kg/"], &["run_commit"]);
+        let chunks = vec![
+            chunk("src/commit.rs", "fn run_commit_inner() {}"),
+            chunk("src/kg/store.rs", "pub fn run_commit(ws: &Workspace) {}"),
+        ];
+
+        let result = score_question("q1".to_string(), &q, &chunks, 5, 0);
+
+        assert_eq!(result.recall, 1.0);
+        assert_eq!(result.reciprocal_rank, 0.5);
+        assert!(result.missing.is_empty());
+    }
+
+    #[test]
+    fn test_run_eval_aggregates_per_configuration() {
+        let suite = EvalSuite {
+            k: None,
+            configurations: vec![],
+            questions: vec![question(&["src/a.rs"], &[]), question(&["src/b.rs"], &[])],
+        };
+        let configs = vec![
+            EvalConfiguration {
+                name: "good".to_string(),
+                ..Default::default()
+            },
+            EvalConfiguration {
+                name: "broken".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let report = run_eval(&suite, &configs, DEFAULT_EVAL_K, |config, q| {
+            if config.name == "broken" {
+                return Err(GikError::InvalidArgument("boom".to_string()));
+            }
+            Ok(bundle(vec![chunk(&q.expected_paths[0], "")]))
+        });
+
+        assert_eq!(report.questions, 2);
+        assert_eq!(report.configurations.len(), 2);
+
+        let good = &report.configurations[0];
+        assert_eq!(good.recall_at_k, 1.0);
+        assert_eq!(good.mrr, 1.0);
+        assert_eq!(good.ndcg_at_k, 1.0);
+        assert_eq!(good.failed, 0);
+        assert_eq!(good.results[1].id, "q2");
+
+        let broken = &report.configurations[1];
+        assert_eq!(broken.recall_at_k, 0.0);
+        assert_eq!(broken.failed, 2);
+        assert_eq!(broken.results[0].missing, vec!["src/a.rs"]);
+    }
+
+    #[test]
+    fn test_load_suite_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("questions.yaml");
+
+        std::fs::write(
+            &path,
+            r#"
+k: 3
+configurations:
+  - name: dense
+    retrieval:
+      hybrid:
+        denseWeight: 0.9
+questions:
+  - question: Where is the commit pipeline?
+    expectedPaths: [src/commit.rs]
+"#,
+        )
+        .unwrap();
+        let suite = EvalSuite::load(&path).unwrap();
+        assert_eq!(suite.k, Some(3));
+        assert_eq!(
+            suite.configurations[0]
+                .retrieval
+                .hybrid
+                .as_ref()
+                .and_then(|h| h.dense_weight),
+            Some(0.9)
+        );
+
+        std::fs::write(
+            &path,
+            "configurations:\n  - name: minilm\n    embedding:\n      modelId: sentence-transformers/all-MiniLM-L6-v2\nquestions:\n  - question: Anything?\n    expectedPaths: [src/a.rs]\n",
+        )
+        .unwrap();
+        let suite = EvalSuite::load(&path).unwrap();
+        let embedding = suite.configurations[0].embedding.as_ref().unwrap();
+        assert_eq!(
+            embedding.model_id.as_deref(),
+            Some("sentence-transformers/all-MiniLM-L6-v2")
+        );
+
+        std::fs::write(&path, "questions:\n  - question: Anything?\n").unwrap();
+        let err = EvalSuite::load(&path).unwrap_err();
+        assert!(matches!(err, GikError::EvalSuiteInvalid { .. }));
+        assert!(err.to_string().contains("expectedPaths"));
+    }
+
+    #[test]
+    fn test_build_model_index_copies_branch_without_touching_it() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".guided/knowledge")).unwrap();
+        let workspace = Workspace::from_root(temp.path()).unwrap();
+
+        let code = crate::base::base_root(workspace.knowledge_root(), "main", "code");
+        fs::create_dir_all(code.join("index")).unwrap();
+        fs::write(code.join("index/vectors.bin"), b"old").unwrap();
+        let source = crate::base::BaseSourceEntry::new(
+            crate::base::ChunkId::new("chunk-1"),
+            "code",
+            "main",
+            "src/lib.rs",
+            1,
+            1,
+            1,
+            "rev-1",
+            "src-1",
+        )
+        .with_text("pub fn add() {}");
+        crate::base::append_base_sources(&crate::base::sources_path(&code), &[source]).unwrap();
+
+        let scratch = tempfile::TempDir::new().unwrap();
+        copy_branch(&workspace.branch_dir("main"), &scratch.path().join("main")).unwrap();
+        let copied = crate::base::base_root(scratch.path(), "main", "code");
+        assert!(copied.join("sources.jsonl").exists());
+        assert!(!copied.join("index").exists());
+
+        // An unusable model fails the build and leaves the branch as it was
+        let embedding = EmbeddingOverride {
+            provider: Some("unknown".to_string()),
+            ..Default::default()
+        };
+        let err = build_model_index(&workspace, "main", &embedding, &GlobalConfig::default())
+            .err()
+            .unwrap();
+        assert!(matches!(err, GikError::EmbeddingProviderUnavailable { .. }));
+        assert_eq!(fs::read(code.join("index/vectors.bin")).unwrap(), b"old");
+    }
+
+    #[test]
+    fn test_effective_configurations_defaults() {
+        let suite = EvalSuite {
+            k: None,
+            configurations: vec![],
+            questions: vec![],
+        };
+        let configs = suite.effective_configurations();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].name, DEFAULT_EVAL_CONFIGURATION);
+    }
+}
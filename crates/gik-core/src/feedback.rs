@@ -0,0 +1,497 @@
+//! Relevance feedback on ask results for `gik feedback`.
+//!
+//! Users judge retrieved chunks of a logged ask as good or bad. Judgements
+//! are appended to `asks/feedback.jsonl` (next to the ask log) and can be
+//! turned into an [`EvalSuite`] so that `gik eval --from-feedback` measures
+//! retrieval against real questions.
+//!
+//! ## Chunk References
+//!
+//! A judged chunk is referenced by:
+//! - its 1-based position in the ask's results (requires a saved bundle)
+//! - `path:start-end` for a specific line range
+//! - `path` for a whole file
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::ask::AskContextBundle;
+use crate::errors::GikError;
+use crate::eval::{EvalQuestion, EvalSuite};
+use crate::log::{AskLogView, ASKS_DIR};
+use crate::workspace::Workspace;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// The filename for recorded feedback.
+pub const FEEDBACK_FILENAME: &str = "feedback.jsonl";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Relevance judgement for a retrieved chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedbackVerdict {
+    /// The chunk was relevant to the question.
+    Good,
+    /// The chunk was not relevant to the question.
+    Bad,
+}
+
+impl std::fmt::Display for FeedbackVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Good => write!(f, "good"),
+            Self::Bad => write!(f, "bad"),
+        }
+    }
+}
+
+/// A recorded relevance judgement (stored format in feedback.jsonl).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AskFeedbackEntry {
+    /// When the judgement was recorded.
+    pub timestamp: DateTime<Utc>,
+    /// The judged ask.
+    pub ask_id: String,
+    /// Branch the ask was run on.
+    pub branch: String,
+    /// The question asked.
+    pub question: String,
+    /// Good or bad.
+    pub verdict: FeedbackVerdict,
+    /// Path of the judged chunk.
+    pub path: String,
+    /// Base of the judged chunk (when known).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Start line of the judged chunk (absent for whole-file judgements).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u32>,
+    /// End line of the judged chunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+}
+
+/// Options for recording feedback on an ask.
+#[derive(Debug, Clone, Default)]
+pub struct AskFeedbackOptions {
+    /// Ask ID, unique ID prefix, or 1-based position in the ask log.
+    pub ask: String,
+    /// Chunks judged relevant.
+    pub good: Vec<String>,
+    /// Chunks judged irrelevant.
+    pub bad: Vec<String>,
+}
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+/// Path of the feedback file.
+pub fn feedback_path(workspace: &Workspace) -> PathBuf {
+    workspace
+        .knowledge_root()
+        .join(ASKS_DIR)
+        .join(FEEDBACK_FILENAME)
+}
+
+/// Build feedback entries for an ask from chunk references.
+///
+/// # Errors
+///
+/// Returns [`GikError::InvalidArgument`] if a reference is malformed or a
+/// position is out of range, and [`GikError::AskBundleNotSaved`] if a
+/// position is used for an ask without a saved bundle.
+pub fn build_feedback_entries(
+    ask: &AskLogView,
+    bundle: Option<&AskContextBundle>,
+    opts: &AskFeedbackOptions,
+) -> Result<Vec<AskFeedbackEntry>, GikError> {
+    let ask_id = ask
+        .id
+        .clone()
+        .ok_or_else(|| GikError::InvalidArgument("Ask has no ID to attach feedback to".into()))?;
+
+    let judged = opts
+        .good
+        .iter()
+        .map(|r| (FeedbackVerdict::Good, r))
+        .chain(opts.bad.iter().map(|r| (FeedbackVerdict::Bad, r)));
+
+    let mut entries = Vec::new();
+    for (verdict, reference) in judged {
+        let (path, base, start_line, end_line) = resolve_chunk(&ask_id, bundle, reference)?;
+        entries.push(AskFeedbackEntry {
+            timestamp: Utc::now(),
+            ask_id: ask_id.clone(),
+            branch: ask.branch.clone(),
+            question: ask.question.clone(),
+            verdict,
+            path,
+            base,
+            start_line,
+            end_line,
+        });
+    }
+
+    if entries.is_empty() {
+        return Err(GikError::InvalidArgument(
+            "Nothing to record: pass --good or --bad with a chunk".to_string(),
+        ));
+    }
+    Ok(entries)
+}
+
+type ResolvedChunk = (String, Option<String>, Option<u32>, Option<u32>);
+
+/// Resolve a chunk reference to a path, base and line range.
+fn resolve_chunk(
+    ask_id: &str,
+    bundle: Option<&AskContextBundle>,
+    reference: &str,
+) -> Result<ResolvedChunk, GikError> {
+    let reference = reference.trim();
+
+    if let Ok(position) = reference.parse::<usize>() {
+        let bundle = bundle.ok_or_else(|| GikError::AskBundleNotSaved(ask_id.to_string()))?;
+        let chunk = position
+            .checked_sub(1)
+            .and_then(|i| bundle.rag_chunks.get(i))
+            .ok_or_else(|| {
+                GikError::InvalidArgument(format!(
+                    "Chunk {} is out of range (ask returned {} chunks)",
+                    reference,
+                    bundle.rag_chunks.len()
+                ))
+            })?;
+        return Ok((
+            chunk.path.clone(),
+            Some(chunk.base.clone()),
+            Some(chunk.start_line),
+            Some(chunk.end_line),
+        ));
+    }
+
+    if reference.is_empty() {
+        return Err(GikError::InvalidArgument(
+            "Chunk reference cannot be empty".to_string(),
+        ));
+    }
+
+    // `path:start-end`
+    if let Some((path, range)) = reference.rsplit_once(':') {
+        if let Some((start, end)) = range.split_once('-') {
+            let (start, end) = match (start.parse::<u32>(), end.parse::<u32>()) {
+                (Ok(start), Ok(end)) if start <= end => (start, end),
+                _ => {
+                    return Err(GikError::InvalidArgument(format!(
+                        "Invalid line range in chunk reference `{}`",
+                        reference
+                    )))
+                }
+            };
+            let base = bundle.and_then(|b| {
+                b.rag_chunks
+                    .iter()
+                    .find(|c| c.path == path && c.start_line == start && c.end_line == end)
+                    .map(|c| c.base.clone())
+            });
+            return Ok((path.to_string(), base, Some(start), Some(end)));
+        }
+    }
+
+    let base = bundle.and_then(|b| {
+        b.rag_chunks
+            .iter()
+            .find(|c| c.path == reference)
+            .map(|c| c.base.clone())
+    });
+    Ok((reference.to_string(), base, None, None))
+}
+
+/// Append feedback entries to the feedback file.
+pub fn append_feedback(
+    workspace: &Workspace,
+    entries: &[AskFeedbackEntry],
+) -> Result<(), GikError> {
+    let path = feedback_path(workspace);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| GikError::LogIoError {
+            path: dir.to_path_buf(),
+            reason: format!("Failed to create asks directory: {}", e),
+        })?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| GikError::LogIoError {
+            path: path.clone(),
+            reason: format!("Failed to open feedback log: {}", e),
+        })?;
+
+    for entry in entries {
+        let json = serde_json::to_string(entry).map_err(|e| GikError::LogIoError {
+            path: path.clone(),
+            reason: format!("Failed to serialize feedback entry: {}", e),
+        })?;
+        writeln!(file, "{}", json).map_err(|e| GikError::LogIoError {
+            path: path.clone(),
+            reason: format!("Failed to write feedback entry: {}", e),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Load all recorded feedback (oldest first).
+pub fn load_feedback(workspace: &Workspace) -> Result<Vec<AskFeedbackEntry>, GikError> {
+    let path = feedback_path(workspace);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let file = File::open(&path).map_err(|e| GikError::LogIoError {
+        path: path.clone(),
+        reason: e.to_string(),
+    })?;
+
+    let mut entries = Vec::new();
+    for (line_num, line_result) in BufReader::new(file).lines().enumerate() {
+        let line = line_result.map_err(|e| GikError::LogIoError {
+            path: path.clone(),
+            reason: format!("Failed to read line {}: {}", line_num + 1, e),
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|e| GikError::LogIoError {
+            path: path.clone(),
+            reason: format!("Failed to parse line {}: {}", line_num + 1, e),
+        })?;
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+// ============================================================================
+// Eval Seeding
+// ============================================================================
+
+/// Build an eval suite from recorded feedback.
+///
+/// Each judged ask becomes a question whose expected paths are the files
+/// judged good. The latest judgement of a path wins, so a file first marked
+/// good and later bad is not expected. Asks without any good file are
+/// skipped.
+pub fn feedback_eval_suite(entries: &[AskFeedbackEntry]) -> EvalSuite {
+    // ask id -> (question, path -> latest verdict)
+    let mut asks: BTreeMap<&str, (&str, BTreeMap<&str, FeedbackVerdict>)> = BTreeMap::new();
+    let mut order: Vec<&str> = Vec::new();
+
+    for entry in entries {
+        let (_, verdicts) = asks.entry(entry.ask_id.as_str()).or_insert_with(|| {
+            order.push(entry.ask_id.as_str());
+            (entry.question.as_str(), BTreeMap::new())
+        });
+        verdicts.insert(entry.path.as_str(), entry.verdict);
+    }
+
+    let questions = order
+        .into_iter()
+        .filter_map(|ask_id| {
+            let (question, verdicts) = &asks[ask_id];
+            let expected_paths: Vec<String> = verdicts
+                .iter()
+                .filter(|(_, v)| **v == FeedbackVerdict::Good)
+                .map(|(path, _)| path.to_string())
+                .collect();
+            if expected_paths.is_empty() {
+                return None;
+            }
+            Some(EvalQuestion {
+                id: Some(ask_id.to_string()),
+                question: question.to_string(),
+                expected_paths,
+                expected_symbols: vec![],
+                bases: None,
+            })
+        })
+        .collect();
+
+    EvalSuite {
+        k: None,
+        configurations: vec![],
+        questions,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ask::{AskDebugInfo, RagChunk};
+    use crate::timeline::RevisionId;
+
+    fn ask_view() -> AskLogView {
+        AskLogView {
+            id: Some("ask-0123456789ab".to_string()),
+            branch: "main".to_string(),
+            timestamp: Utc::now(),
+            question: "How are users loaded?".to_string(),
+            bases: vec!["code".to_string()],
+            total_hits: 2,
+            bundle_path: Some("main/asks/ask-0123456789ab.json".to_string()),
+            revision_id: None,
+            top_score: None,
+            sources: Vec::new(),
+            stale_paths: Vec::new(),
+        }
+    }
+
+    fn bundle() -> AskContextBundle {
+        let chunk = |path: &str, start: u32, end: u32| RagChunk {
+            base: "code".to_string(),
+            score: 0.5,
+            path: path.to_string(),
+            start_line: start,
+            end_line: end,
+            snippet: String::new(),
+            dense_score: None,
+            reranker_score: None,
+            ownership: None,
+        };
+        AskContextBundle {
+            ask_id: Some("ask-0123456789ab".to_string()),
+            revision_id: RevisionId::new("rev-1"),
+            question: "How are users loaded?".to_string(),
+            bases: vec!["code".to_string()],
+            rag_chunks: vec![chunk("src/users.rs", 1, 40), chunk("src/db.rs", 10, 30)],
+            kg_results: vec![],
+            memory_events: vec![],
+            stack_summary: None,
+            debug: AskDebugInfo {
+                embedding_model_id: "test-model".to_string(),
+                used_bases: vec!["code".to_string()],
+                per_base_counts: vec![],
+                embed_time_ms: None,
+                search_time_ms: None,
+                reranker_used: false,
+                rerank_time_ms: None,
+                hybrid_search_used: false,
+                dense_result_count: None,
+                sparse_result_count: None,
+                filename_detected: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_build_feedback_entries_resolves_references() {
+        let opts = AskFeedbackOptions {
+            ask: "1".to_string(),
+            good: vec!["1".to_string(), "src/db.rs:10-30".to_string()],
+            bad: vec!["README.md".to_string()],
+        };
+
+        let entries = build_feedback_entries(&ask_view(), Some(&bundle()), &opts).unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].path, "src/users.rs");
+        assert_eq!(entries[0].start_line, Some(1));
+        assert_eq!(entries[0].verdict, FeedbackVerdict::Good);
+        assert_eq!(entries[1].base.as_deref(), Some("code"));
+        assert_eq!(entries[1].end_line, Some(30));
+        assert_eq!(entries[2].verdict, FeedbackVerdict::Bad);
+        assert_eq!(entries[2].start_line, None);
+        assert_eq!(entries[2].base, None);
+    }
+
+    #[test]
+    fn test_build_feedback_entries_errors() {
+        let by_position = AskFeedbackOptions {
+            good: vec!["1".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            build_feedback_entries(&ask_view(), None, &by_position),
+            Err(GikError::AskBundleNotSaved(_))
+        ));
+
+        let out_of_range = AskFeedbackOptions {
+            good: vec!["3".to_string()],
+            ..Default::default()
+        };
+        assert!(build_feedback_entries(&ask_view(), Some(&bundle()), &out_of_range).is_err());
+
+        let bad_range = AskFeedbackOptions {
+            bad: vec!["src/db.rs:30-10".to_string()],
+            ..Default::default()
+        };
+        assert!(build_feedback_entries(&ask_view(), None, &bad_range).is_err());
+
+        assert!(build_feedback_entries(&ask_view(), None, &AskFeedbackOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_append_and_load_feedback() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let workspace = Workspace::from_root(temp_dir.path()).unwrap();
+
+        let opts = AskFeedbackOptions {
+            good: vec!["src/users.rs".to_string()],
+            ..Default::default()
+        };
+        let entries = build_feedback_entries(&ask_view(), None, &opts).unwrap();
+        append_feedback(&workspace, &entries).unwrap();
+        append_feedback(&workspace, &entries).unwrap();
+
+        let loaded = load_feedback(&workspace).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].ask_id, "ask-0123456789ab");
+    }
+
+    #[test]
+    fn test_feedback_eval_suite_latest_verdict_wins() {
+        let opts = AskFeedbackOptions {
+            good: vec!["src/users.rs".to_string(), "src/db.rs".to_string()],
+            ..Default::default()
+        };
+        let mut entries = build_feedback_entries(&ask_view(), None, &opts).unwrap();
+        let retract = AskFeedbackOptions {
+            bad: vec!["src/db.rs".to_string()],
+            ..Default::default()
+        };
+        entries.extend(build_feedback_entries(&ask_view(), None, &retract).unwrap());
+
+        let mut other = ask_view();
+        other.id = Some("ask-ffffffffffff".to_string());
+        let only_bad = AskFeedbackOptions {
+            bad: vec!["src/db.rs".to_string()],
+            ..Default::default()
+        };
+        entries.extend(build_feedback_entries(&other, None, &only_bad).unwrap());
+
+        let suite = feedback_eval_suite(&entries);
+
+        assert_eq!(suite.questions.len(), 1);
+        assert_eq!(suite.questions[0].id.as_deref(), Some("ask-0123456789ab"));
+        assert_eq!(suite.questions[0].expected_paths, vec!["src/users.rs"]);
+    }
+}
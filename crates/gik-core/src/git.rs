@@ -0,0 +1,1023 @@
+//! Git integration for GIK.
+//!
+//! This module connects the knowledge timeline to the surrounding Git
+//! repository:
+//! - [`read_head_commit`] - resolve the current Git commit SHA (recorded on revisions)
+//! - [`install_hooks`] / [`uninstall_hooks`] - manage `post-commit`, `post-merge`
+//!   and `post-checkout` hooks that stage changed files via `gik hooks run`
+//! - [`changed_paths_for_hook`] - list the files touched by a hook invocation
+//! - [`read_git_log`] - read commit history for `gik import git-log`
+//!
+//! ## Hooks
+//!
+//! Installed hooks are small POSIX shell scripts that call back into the CLI
+//! (`gik hooks run <hook> "$@"`). They never fail the Git operation: if `gik`
+//! is not on `PATH` or staging fails, the hook exits successfully.
+//!
+//! Hooks written by GIK carry a marker line so they can be updated or removed
+//! without touching user-authored hooks.
+//!
+//! ## Git History Import
+//!
+//! Commits are turned into [`MemorySource::CommitContext`] memory entries.
+//! Each entry keeps the commit SHA and touched paths in its `extra` metadata,
+//! and uses a SHA-derived ID so re-importing the same range is idempotent.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::GikError;
+use crate::memory::{MemoryEntry, MemoryEntryId, MemoryScope, MemorySource};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Marker line written into every hook installed by GIK.
+pub const GIK_HOOK_MARKER: &str = "# gik-managed-hook";
+
+/// Prefix for memory entry IDs created from Git commits.
+pub const GIT_MEMORY_ID_PREFIX: &str = "mem-git-";
+
+/// Field separator used in `git log` output parsing.
+const FIELD_SEP: char = '\x1f';
+
+/// Record separator used in `git log` output parsing.
+const RECORD_SEP: char = '\x1e';
+
+/// The all-zero SHA Git passes for "no previous commit" (e.g., after clone).
+const NULL_SHA: &str = "0000000000000000000000000000000000000000";
+
+// ============================================================================
+// GitHookKind
+// ============================================================================
+
+/// Git hooks managed by GIK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitHookKind {
+    /// Runs after `git commit`.
+    PostCommit,
+    /// Runs after `git merge` / `git pull`.
+    PostMerge,
+    /// Runs after `git checkout` / `git switch`.
+    PostCheckout,
+}
+
+impl GitHookKind {
+    /// All hooks installed by `gik hooks install`.
+    pub const ALL: [GitHookKind; 3] = [
+        GitHookKind::PostCommit,
+        GitHookKind::PostMerge,
+        GitHookKind::PostCheckout,
+    ];
+
+    /// The hook file name under `.git/hooks/`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GitHookKind::PostCommit => "post-commit",
+            GitHookKind::PostMerge => "post-merge",
+            GitHookKind::PostCheckout => "post-checkout",
+        }
+    }
+}
+
+impl std::fmt::Display for GitHookKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for GitHookKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "post-commit" => Ok(GitHookKind::PostCommit),
+            "post-merge" => Ok(GitHookKind::PostMerge),
+            "post-checkout" => Ok(GitHookKind::PostCheckout),
+            _ => Err(format!(
+                "Unknown hook '{}'. Valid hooks: post-commit, post-merge, post-checkout",
+                s
+            )),
+        }
+    }
+}
+
+// ============================================================================
+// Hook Install/Uninstall
+// ============================================================================
+
+/// Outcome of installing or removing a single hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HookAction {
+    /// Hook file was created.
+    Installed,
+    /// An existing GIK hook was rewritten.
+    Updated,
+    /// A GIK hook was removed.
+    Removed,
+    /// Hook was left untouched (user-authored hook or nothing to remove).
+    Skipped,
+}
+
+/// Per-hook status from [`install_hooks`] or [`uninstall_hooks`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookStatus {
+    /// The hook.
+    pub hook: GitHookKind,
+    /// Path to the hook file.
+    pub path: PathBuf,
+    /// What happened to the hook.
+    pub action: HookAction,
+    /// Why the hook was skipped (if applicable).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Result of `gik hooks install` / `gik hooks uninstall`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HooksReport {
+    /// Directory containing the hooks.
+    pub hooks_dir: PathBuf,
+    /// Per-hook statuses.
+    pub hooks: Vec<HookStatus>,
+    /// Set when `core.hooksPath` redirects hooks away from the Git directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+/// Render the shell script for a GIK-managed hook.
+fn hook_script(hook: GitHookKind) -> String {
+    format!(
+        "#!/bin/sh\n\
+         {marker}\n\
+         # Installed by `gik hooks install`. Stages files changed by this Git\n\
+         # operation for the next `gik commit`. Remove with `gik hooks uninstall`.\n\
+         command -v gik >/dev/null 2>&1 || exit 0\n\
+         gik --quiet hooks run {hook} \"$@\" >/dev/null 2>&1 || true\n\
+         exit 0\n",
+        marker = GIK_HOOK_MARKER,
+        hook = hook.as_str(),
+    )
+}
+
+/// Resolve the hooks directory for a repository root.
+///
+/// Asks `git rev-parse --git-path hooks`, which honors `core.hooksPath` and
+/// resolves worktrees and submodules (where `.git` is a file). Falls back to
+/// `.git/hooks` when the `git` binary cannot answer.
+pub fn hooks_dir(repo_root: &Path) -> Result<PathBuf, GikError> {
+    if !repo_root.join(".git").exists() {
+        return Err(GikError::NotAGitRepository(repo_root.to_path_buf()));
+    }
+    rev_parse_path(repo_root, &["rev-parse", "--git-path", "hooks"])
+        .or_else(|| {
+            let git_dir = repo_root.join(".git");
+            git_dir.is_dir().then(|| git_dir.join("hooks"))
+        })
+        .ok_or_else(|| GikError::NotAGitRepository(repo_root.to_path_buf()))
+}
+
+/// Describe a `core.hooksPath` setting that moves hooks out of the Git
+/// directory, since other tools (e.g., husky) may own that directory.
+fn hooks_path_warning(repo_root: &Path, dir: &Path) -> Option<String> {
+    let configured = run_git(repo_root, &["config", "--get", "core.hooksPath"]).ok()?;
+    let configured = configured.trim();
+    if configured.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "core.hooksPath is set to '{}'; hooks are managed in {}",
+        configured,
+        dir.display()
+    ))
+}
+
+/// Install GIK-managed Git hooks.
+///
+/// Existing hooks written by GIK are rewritten. User-authored hooks are left
+/// untouched unless `force` is set, in which case they are replaced.
+///
+/// # Errors
+///
+/// Returns [`GikError::NotAGitRepository`] if `repo_root` has no `.git`
+/// directory, or an I/O error if a hook cannot be written.
+pub fn install_hooks(repo_root: &Path, force: bool) -> Result<HooksReport, GikError> {
+    let dir = hooks_dir(repo_root)?;
+    let warning = hooks_path_warning(repo_root, &dir);
+    if let Some(warning) = &warning {
+        tracing::warn!("{}", warning);
+    }
+    if !dir.is_dir() && dir.exists() {
+        return Err(GikError::GitCommandFailed {
+            command: "gik hooks install".to_string(),
+            reason: format!(
+                "hooks path {} is not a directory (check core.hooksPath)",
+                dir.display()
+            ),
+        });
+    }
+    fs::create_dir_all(&dir)?;
+
+    let mut hooks = Vec::new();
+    for hook in GitHookKind::ALL {
+        let path = dir.join(hook.as_str());
+        let existing = fs::read_to_string(&path).ok();
+
+        let action = match &existing {
+            Some(content) if !content.contains(GIK_HOOK_MARKER) && !force => {
+                hooks.push(HookStatus {
+                    hook,
+                    path,
+                    action: HookAction::Skipped,
+                    reason: Some("existing hook not managed by gik (use --force)".to_string()),
+                });
+                continue;
+            }
+            Some(_) => HookAction::Updated,
+            None => HookAction::Installed,
+        };
+
+        fs::write(&path, hook_script(hook))?;
+        make_executable(&path)?;
+
+        hooks.push(HookStatus {
+            hook,
+            path,
+            action,
+            reason: None,
+        });
+    }
+
+    Ok(HooksReport {
+        hooks_dir: dir,
+        hooks,
+        warning,
+    })
+}
+
+/// Remove GIK-managed Git hooks.
+///
+/// Only hooks containing [`GIK_HOOK_MARKER`] are removed.
+pub fn uninstall_hooks(repo_root: &Path) -> Result<HooksReport, GikError> {
+    let dir = hooks_dir(repo_root)?;
+    let warning = hooks_path_warning(repo_root, &dir);
+
+    let mut hooks = Vec::new();
+    for hook in GitHookKind::ALL {
+        let path = dir.join(hook.as_str());
+        let (action, reason) = match fs::read_to_string(&path) {
+            Ok(content) if content.contains(GIK_HOOK_MARKER) => {
+                fs::remove_file(&path)?;
+                (HookAction::Removed, None)
+            }
+            Ok(_) => (
+                HookAction::Skipped,
+                Some("hook not managed by gik".to_string()),
+            ),
+            Err(_) => (HookAction::Skipped, Some("not installed".to_string())),
+        };
+        hooks.push(HookStatus {
+            hook,
+            path,
+            action,
+            reason,
+        });
+    }
+
+    Ok(HooksReport {
+        hooks_dir: dir,
+        hooks,
+        warning,
+    })
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), GikError> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), GikError> {
+    Ok(())
+}
+
+// ============================================================================
+// Git Command Helpers
+// ============================================================================
+
+/// Run a `git` command in `repo_root` and return its stdout.
+fn run_git(repo_root: &Path, args: &[&str]) -> Result<String, GikError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| GikError::GitCommandFailed {
+            command: format!("git {}", args.join(" ")),
+            reason: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GikError::GitCommandFailed {
+            command: format!("git {}", args.join(" ")),
+            reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Resolve a path printed by `git rev-parse` against `repo_root`.
+fn rev_parse_path(repo_root: &Path, args: &[&str]) -> Option<PathBuf> {
+    let output = run_git(repo_root, args).ok()?;
+    let path = PathBuf::from(output.trim());
+    Some(if path.is_absolute() {
+        path
+    } else {
+        repo_root.join(path)
+    })
+}
+
+/// Locate the Git directory of `repo_root`.
+///
+/// Uses `git rev-parse --git-dir` so linked worktrees and submodules resolve
+/// correctly, falling back to a plain `.git` directory without `git`.
+pub(crate) fn git_dir(repo_root: &Path) -> Option<PathBuf> {
+    if !repo_root.join(".git").exists() {
+        return None;
+    }
+    rev_parse_path(repo_root, &["rev-parse", "--git-dir"]).or_else(|| {
+        let git_dir = repo_root.join(".git");
+        git_dir.is_dir().then_some(git_dir)
+    })
+}
+
+/// Read the commit SHA that Git HEAD points to.
+///
+/// Reads `HEAD` from the Git directory, following symbolic refs through loose
+/// ref files and `packed-refs` (in the common directory for linked
+/// worktrees). Returns `None` if the workspace is not a Git repository or
+/// HEAD cannot be resolved (e.g., an unborn branch).
+pub fn read_head_commit(repo_root: &Path) -> Option<String> {
+    let git_dir = git_dir(repo_root)?;
+    let content = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let content = content.trim();
+
+    let Some(refpath) = content.strip_prefix("ref: ") else {
+        // Detached HEAD - content is the commit hash
+        return is_sha(content).then(|| content.to_string());
+    };
+
+    // Worktrees keep HEAD locally but share branch refs via `commondir`
+    let refs_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => git_dir.join(common.trim()),
+        Err(_) => git_dir,
+    };
+
+    if let Ok(sha) = fs::read_to_string(refs_dir.join(refpath)) {
+        let sha = sha.trim();
+        if is_sha(sha) {
+            return Some(sha.to_string());
+        }
+    }
+
+    // Fall back to packed-refs ("<sha> <refname>" per line)
+    let packed = fs::read_to_string(refs_dir.join("packed-refs")).ok()?;
+    packed.lines().find_map(|line| {
+        let (sha, name) = line.split_once(' ')?;
+        (name.trim() == refpath && is_sha(sha)).then(|| sha.to_string())
+    })
+}
+
+fn is_sha(s: &str) -> bool {
+    s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// List workspace-relative paths changed by a hook invocation.
+///
+/// - `post-commit`: files in the new HEAD commit
+/// - `post-merge`: files changed between `ORIG_HEAD` and `HEAD`
+/// - `post-checkout <prev> <new> <flag>`: files changed between `prev` and `new`
+///
+/// Paths are relative to `repo_root` even when it is a subdirectory of the
+/// repository; changes outside it are left out. Deleted files are excluded
+/// since they cannot be staged. Returns an empty list when there is nothing to
+/// compare (e.g., the checkout after a clone).
+pub fn changed_paths_for_hook(
+    repo_root: &Path,
+    hook: GitHookKind,
+    args: &[String],
+) -> Result<Vec<String>, GikError> {
+    let output = match hook {
+        GitHookKind::PostCommit => run_git(
+            repo_root,
+            &[
+                "diff-tree",
+                "--root",
+                "--no-commit-id",
+                "--relative",
+                "--name-only",
+                "-r",
+                "--diff-filter=ACMR",
+                "HEAD",
+            ],
+        )?,
+        GitHookKind::PostMerge => run_git(
+            repo_root,
+            &[
+                "diff",
+                "--relative",
+                "--name-only",
+                "--diff-filter=ACMR",
+                "ORIG_HEAD",
+                "HEAD",
+            ],
+        )?,
+        GitHookKind::PostCheckout => {
+            let (Some(prev), Some(new)) = (args.first(), args.get(1)) else {
+                return Err(GikError::InvalidArgument(
+                    "post-checkout expects <prev-head> <new-head> <branch-flag>".to_string(),
+                ));
+            };
+            if prev == NULL_SHA || prev == new {
+                return Ok(Vec::new());
+            }
+            run_git(
+                repo_root,
+                &[
+                    "diff",
+                    "--relative",
+                    "--name-only",
+                    "--diff-filter=ACMR",
+                    prev,
+                    new,
+                ],
+            )?
+        }
+    };
+
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+// ============================================================================
+// Git Log
+// ============================================================================
+
+/// A single commit read from `git log`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitInfo {
+    /// Full commit SHA.
+    pub sha: String,
+    /// Author name.
+    pub author: String,
+    /// Author timestamp.
+    pub timestamp: DateTime<Utc>,
+    /// First line of the commit message.
+    pub subject: String,
+    /// Remaining commit message (without the subject), trimmed.
+    pub body: String,
+    /// Workspace-relative paths touched by the commit.
+    pub paths: Vec<String>,
+}
+
+impl GitCommitInfo {
+    /// The Conventional Commits type (`feat`, `fix`, ...) if the subject follows it.
+    pub fn conventional_type(&self) -> Option<String> {
+        let (head, _) = self.subject.split_once(':')?;
+        let head = head.trim_end_matches('!');
+        let kind = head.split('(').next()?.trim();
+        if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        Some(kind.to_lowercase())
+    }
+
+    /// The Conventional Commits scope (`feat(api): ...` → `api`).
+    pub fn conventional_scope(&self) -> Option<String> {
+        let (head, _) = self.subject.split_once(':')?;
+        let start = head.find('(')?;
+        let end = head.find(')')?;
+        (end > start + 1).then(|| head[start + 1..end].trim().to_string())
+    }
+
+    /// Whether the commit declares a breaking change.
+    ///
+    /// Detects both the `type!:` subject marker and `BREAKING CHANGE:` /
+    /// `BREAKING-CHANGE:` footers.
+    pub fn is_breaking(&self) -> bool {
+        let bang = self
+            .subject
+            .split_once(':')
+            .map(|(head, _)| head.ends_with('!'))
+            .unwrap_or(false);
+        bang || self.body.lines().any(|l| {
+            let l = l.trim_start();
+            l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:")
+        })
+    }
+
+    /// Whether the commit carries context beyond its subject line.
+    pub fn has_context(&self) -> bool {
+        !self.body.is_empty() || self.is_breaking()
+    }
+
+    /// Convert this commit into a `CommitContext` memory entry.
+    ///
+    /// The entry ID is derived from the SHA, so importing the same commit twice
+    /// yields the same ID.
+    pub fn to_memory_entry(&self) -> MemoryEntry {
+        let mut text = if self.body.is_empty() {
+            self.subject.clone()
+        } else {
+            self.body.clone()
+        };
+        if !self.paths.is_empty() {
+            text.push_str("\n\nFiles: ");
+            text.push_str(&self.paths.join(", "));
+        }
+
+        let mut tags = vec!["git".to_string()];
+        if let Some(kind) = self.conventional_type() {
+            tags.push(kind);
+        }
+        if let Some(scope) = self.conventional_scope() {
+            tags.push(scope);
+        }
+        if self.is_breaking() {
+            tags.push("breaking-change".to_string());
+        }
+
+        let mut entry = MemoryEntry::new(MemoryScope::Project, MemorySource::CommitContext, text)
+            .with_title(self.subject.clone())
+            .with_tags(tags)
+            .with_extra(serde_json::json!({
+                "gitCommit": self.sha,
+                "author": self.author,
+                "paths": self.paths,
+                "breaking": self.is_breaking(),
+            }));
+        entry.id = git_memory_id(&self.sha);
+        entry.created_at = self.timestamp;
+        entry.updated_at = self.timestamp;
+        entry
+    }
+}
+
+/// Memory entry ID for a Git commit.
+pub fn git_memory_id(sha: &str) -> MemoryEntryId {
+    MemoryEntryId::new(format!("{}{}", GIT_MEMORY_ID_PREFIX, sha))
+}
+
+/// Read commits from `git log`, newest first.
+///
+/// # Arguments
+///
+/// * `repo_root` - Repository root.
+/// * `since` - Optional ref; only commits in `<since>..HEAD` are returned.
+/// * `limit` - Optional maximum number of commits.
+///
+/// Merge commits are skipped since they rarely carry their own rationale.
+/// Paths are relative to `repo_root`, as in [`read_path_history`].
+///
+/// # Errors
+///
+/// Returns [`GikError::InvalidArgument`] if `since` does not name a commit.
+pub fn read_git_log(
+    repo_root: &Path,
+    since: Option<&str>,
+    limit: Option<usize>,
+) -> Result<Vec<GitCommitInfo>, GikError> {
+    let format = format!(
+        "--format={rs}%H{fs}%an{fs}%aI{fs}%s{fs}%b{fs}",
+        rs = RECORD_SEP,
+        fs = FIELD_SEP
+    );
+    let range = since
+        .map(|s| resolve_commit(repo_root, s).map(|sha| format!("{}..HEAD", sha)))
+        .transpose()?;
+    let max_count = limit.map(|n| format!("--max-count={}", n));
+
+    let mut args = vec![
+        "log",
+        "--no-merges",
+        "--relative",
+        "--name-only",
+        format.as_str(),
+    ];
+    if let Some(n) = &max_count {
+        args.push(n);
+    }
+    if let Some(r) = &range {
+        args.push(r);
+    }
+
+    let output = run_git(repo_root, &args)?;
+    Ok(parse_git_log_output(&output))
+}
+
+/// Resolve a user-supplied revision to a commit SHA.
+///
+/// Values starting with `-` are rejected so they cannot be read as options.
+fn resolve_commit(repo_root: &Path, rev: &str) -> Result<String, GikError> {
+    let invalid = || GikError::InvalidArgument(format!("Not a commit: '{}'", rev));
+    if rev.is_empty() || rev.starts_with('-') {
+        return Err(invalid());
+    }
+    let spec = format!("{}^{{commit}}", rev);
+    let sha =
+        run_git(repo_root, &["rev-parse", "--verify", "--quiet", &spec]).map_err(|_| invalid())?;
+    Ok(sha.trim().to_string())
+}
+
+/// Read the author, date and changed paths of the last `limit` commits.
+///
+/// Used for file ownership: paths are relative to `repo_root` even when it is
+/// a subdirectory of the repository, author names go through `.mailmap`, and
+/// subjects are read but bodies are not.
+pub fn read_path_history(repo_root: &Path, limit: usize) -> Result<Vec<GitCommitInfo>, GikError> {
+    let format = format!(
+        "--format={rs}%H{fs}%aN{fs}%aI{fs}%s{fs}{fs}",
+        rs = RECORD_SEP,
+        fs = FIELD_SEP
+    );
+    let max_count = format!("--max-count={}", limit);
+    let args = [
+        "log",
+        "--no-merges",
+        "--relative",
+        "--name-only",
+        format.as_str(),
+        max_count.as_str(),
+    ];
+
+    let output = run_git(repo_root, &args)?;
+    Ok(parse_git_log_output(&output))
+}
+
+/// Parse output produced by [`read_git_log`]'s format string.
+pub(crate) fn parse_git_log_output(output: &str) -> Vec<GitCommitInfo> {
+    output
+        .split(RECORD_SEP)
+        .filter(|r| !r.trim().is_empty())
+        .filter_map(|record| {
+            let mut fields = record.splitn(6, FIELD_SEP);
+            let sha = fields.next()?.trim().to_string();
+            let author = fields.next()?.to_string();
+            let timestamp = DateTime::parse_from_rfc3339(fields.next()?.trim())
+                .ok()?
+                .with_timezone(&Utc);
+            let subject = fields.next()?.trim().to_string();
+            let body = fields.next()?.trim().to_string();
+            let paths = fields
+                .next()
+                .unwrap_or_default()
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect();
+
+            Some(GitCommitInfo {
+                sha,
+                author,
+                timestamp,
+                subject,
+                body,
+                paths,
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Import Options / Results
+// ============================================================================
+
+/// Options for `gik import git-log`.
+#[derive(Debug, Clone, Default)]
+pub struct GitLogImportOptions {
+    /// Only import commits after this ref (`<since>..HEAD`).
+    pub since: Option<String>,
+    /// Maximum number of commits to read.
+    pub limit: Option<usize>,
+    /// Import subject-only commits too (default: only commits with a body
+    /// or a breaking-change marker).
+    pub include_all: bool,
+    /// Report what would be imported without writing.
+    pub dry_run: bool,
+}
+
+/// Result of `gik import git-log`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLogImportResult {
+    /// Number of commits read from `git log`.
+    pub scanned: usize,
+    /// Commits imported (or that would be imported, for dry runs).
+    pub imported: Vec<GitCommitInfo>,
+    /// Number of commits skipped because they were already imported.
+    pub already_imported: usize,
+    /// Number of commits skipped because they had no body or breaking marker.
+    pub skipped_without_context: usize,
+    /// Revision created for the import (None for dry runs or empty imports).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision_id: Option<String>,
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit(subject: &str, body: &str) -> GitCommitInfo {
+        GitCommitInfo {
+            sha: "a".repeat(40),
+            author: "Dev".to_string(),
+            timestamp: Utc::now(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+            paths: vec!["src/lib.rs".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_hook_kind_roundtrip() {
+        for hook in GitHookKind::ALL {
+            assert_eq!(hook.as_str().parse::<GitHookKind>().unwrap(), hook);
+        }
+        assert!("pre-commit".parse::<GitHookKind>().is_err());
+    }
+
+    #[test]
+    fn test_install_and_uninstall_hooks() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".git")).unwrap();
+
+        let report = install_hooks(temp.path(), false).unwrap();
+        assert_eq!(report.hooks.len(), 3);
+        assert!(report
+            .hooks
+            .iter()
+            .all(|h| h.action == HookAction::Installed));
+        let script = fs::read_to_string(temp.path().join(".git/hooks/post-commit")).unwrap();
+        assert!(script.contains(GIK_HOOK_MARKER));
+        assert!(script.contains("gik --quiet hooks run post-commit"));
+
+        // Reinstall updates our own hooks
+        let report = install_hooks(temp.path(), false).unwrap();
+        assert!(report.hooks.iter().all(|h| h.action == HookAction::Updated));
+
+        let report = uninstall_hooks(temp.path()).unwrap();
+        assert!(report.hooks.iter().all(|h| h.action == HookAction::Removed));
+        assert!(!temp.path().join(".git/hooks/post-commit").exists());
+    }
+
+    #[test]
+    fn test_install_hooks_preserves_user_hooks() {
+        let temp = TempDir::new().unwrap();
+        let hooks = temp.path().join(".git/hooks");
+        fs::create_dir_all(&hooks).unwrap();
+        fs::write(hooks.join("post-merge"), "#!/bin/sh\necho custom\n").unwrap();
+
+        let report = install_hooks(temp.path(), false).unwrap();
+        let merge = report
+            .hooks
+            .iter()
+            .find(|h| h.hook == GitHookKind::PostMerge)
+            .unwrap();
+        assert_eq!(merge.action, HookAction::Skipped);
+        assert!(fs::read_to_string(hooks.join("post-merge"))
+            .unwrap()
+            .contains("custom"));
+
+        let report = install_hooks(temp.path(), true).unwrap();
+        let merge = report
+            .hooks
+            .iter()
+            .find(|h| h.hook == GitHookKind::PostMerge)
+            .unwrap();
+        assert_eq!(merge.action, HookAction::Updated);
+    }
+
+    #[test]
+    fn test_install_hooks_requires_git_dir() {
+        let temp = TempDir::new().unwrap();
+        let result = install_hooks(temp.path(), false);
+        assert!(matches!(result, Err(GikError::NotAGitRepository(_))));
+    }
+
+    #[test]
+    fn test_read_head_commit() {
+        let temp = TempDir::new().unwrap();
+        let git = temp.path().join(".git");
+        fs::create_dir_all(git.join("refs/heads")).unwrap();
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+
+        // Unborn branch
+        fs::write(git.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        assert_eq!(read_head_commit(temp.path()), None);
+
+        // Packed ref
+        fs::write(
+            git.join("packed-refs"),
+            format!("# pack-refs with: peeled\n{} refs/heads/main\n", sha),
+        )
+        .unwrap();
+        assert_eq!(read_head_commit(temp.path()).as_deref(), Some(sha));
+
+        // Loose ref
+        fs::write(git.join("refs/heads/main"), format!("{}\n", sha)).unwrap();
+        assert_eq!(read_head_commit(temp.path()).as_deref(), Some(sha));
+
+        // Detached HEAD
+        fs::write(git.join("HEAD"), format!("{}\n", sha)).unwrap();
+        assert_eq!(read_head_commit(temp.path()).as_deref(), Some(sha));
+    }
+
+    /// Run `git` in `dir` with a fixed identity, panicking on failure.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("run git");
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_worktree_head_and_hooks_dir() {
+        let temp = TempDir::new().unwrap();
+        let main = temp.path().join("main");
+        fs::create_dir_all(&main).unwrap();
+        git(&main, &["init", "-q", "-b", "main"]);
+        git(&main, &["commit", "-q", "--allow-empty", "-m", "initial"]);
+        let sha = git(&main, &["rev-parse", "HEAD"]);
+
+        let worktree = temp.path().join("wt");
+        git(
+            &main,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "feature",
+                worktree.to_str().unwrap(),
+            ],
+        );
+        assert!(worktree.join(".git").is_file());
+
+        // HEAD is per worktree, the branch ref lives in the main repository
+        assert_eq!(read_head_commit(&worktree).as_deref(), Some(sha.as_str()));
+
+        // Hooks are shared with the main repository
+        let dir = hooks_dir(&worktree).unwrap();
+        assert_eq!(
+            dir.canonicalize().unwrap(),
+            main.join(".git/hooks").canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_install_hooks_follows_core_hooks_path() {
+        let temp = TempDir::new().unwrap();
+        git(temp.path(), &["init", "-q"]);
+        git(temp.path(), &["config", "core.hooksPath", ".githooks"]);
+
+        let report = install_hooks(temp.path(), false).unwrap();
+        assert!(report.warning.unwrap().contains(".githooks"));
+        assert!(temp.path().join(".githooks/post-commit").exists());
+        assert!(!temp.path().join(".git/hooks/post-commit").exists());
+    }
+
+    #[test]
+    fn test_parse_git_log_output() {
+        let sha1 = "1".repeat(40);
+        let sha2 = "2".repeat(40);
+        let output = format!(
+            "\x1e{sha1}\x1fAlice\x1f2025-01-02T10:00:00+00:00\x1ffeat(api)!: new auth\x1fUse tokens.\n\nBREAKING CHANGE: sessions removed\n\x1f\n\nsrc/auth.rs\nsrc/api.rs\n\
+             \x1e{sha2}\x1fBob\x1f2025-01-01T10:00:00+00:00\x1fchore: bump\x1f\x1f\n\nCargo.toml\n",
+        );
+
+        let commits = parse_git_log_output(&output);
+        assert_eq!(commits.len(), 2);
+
+        assert_eq!(commits[0].sha, sha1);
+        assert_eq!(commits[0].author, "Alice");
+        assert_eq!(commits[0].subject, "feat(api)!: new auth");
+        assert!(commits[0].body.starts_with("Use tokens."));
+        assert_eq!(commits[0].paths, vec!["src/auth.rs", "src/api.rs"]);
+
+        assert_eq!(commits[1].body, "");
+        assert_eq!(commits[1].paths, vec!["Cargo.toml"]);
+    }
+
+    #[test]
+    fn test_read_path_history_is_relative_to_subdirectory() {
+        let temp = TempDir::new().unwrap();
+        let app = temp.path().join("app");
+        fs::create_dir_all(app.join("src")).unwrap();
+        git(temp.path(), &["init", "-q"]);
+        fs::write(temp.path().join("README.md"), "root\n").unwrap();
+        fs::write(app.join("src/main.rs"), "fn main() {}\n").unwrap();
+        git(temp.path(), &["add", "."]);
+        git(temp.path(), &["commit", "-q", "-m", "initial"]);
+
+        let commits = read_path_history(&app, 10).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].author, "Test");
+        assert_eq!(commits[0].paths, vec!["src/main.rs"]);
+    }
+
+    #[test]
+    fn test_hook_paths_and_log_are_relative_to_subdirectory() {
+        let temp = TempDir::new().unwrap();
+        let app = temp.path().join("app");
+        fs::create_dir_all(app.join("src")).unwrap();
+        git(temp.path(), &["init", "-q"]);
+        fs::write(temp.path().join("README.md"), "root\n").unwrap();
+        git(temp.path(), &["add", "."]);
+        git(temp.path(), &["commit", "-q", "-m", "initial"]);
+        let first = git(temp.path(), &["rev-parse", "HEAD"]);
+
+        fs::write(temp.path().join("README.md"), "root 2\n").unwrap();
+        fs::write(app.join("src/main.rs"), "fn main() {}\n").unwrap();
+        git(temp.path(), &["add", "."]);
+        git(temp.path(), &["commit", "-q", "-m", "feat: app"]);
+
+        let paths = changed_paths_for_hook(&app, GitHookKind::PostCommit, &[]).unwrap();
+        assert_eq!(paths, vec!["src/main.rs"]);
+
+        let commits = read_git_log(&app, Some(&first), None).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].paths, vec!["src/main.rs"]);
+
+        // Revisions that look like options or name no commit are rejected
+        for since in ["--all", "-p", "no-such-ref"] {
+            let err = read_git_log(&app, Some(since), None).unwrap_err();
+            assert!(matches!(err, GikError::InvalidArgument(_)), "{}", since);
+        }
+    }
+
+    #[test]
+    fn test_conventional_parsing() {
+        let c = commit("feat(api)!: new auth", "");
+        assert_eq!(c.conventional_type().as_deref(), Some("feat"));
+        assert_eq!(c.conventional_scope().as_deref(), Some("api"));
+        assert!(c.is_breaking());
+
+        let c = commit("Fix typo in readme", "");
+        assert_eq!(c.conventional_type(), None);
+        assert!(!c.is_breaking());
+        assert!(!c.has_context());
+
+        let c = commit(
+            "fix: handle nulls",
+            "Details.\n\nBREAKING CHANGE: returns Option",
+        );
+        assert!(c.is_breaking());
+        assert!(c.has_context());
+    }
+
+    #[test]
+    fn test_commit_to_memory_entry() {
+        let c = commit("feat(api)!: new auth", "Use tokens instead of sessions.");
+        let entry = c.to_memory_entry();
+
+        assert_eq!(entry.id, git_memory_id(&c.sha));
+        assert_eq!(entry.source, MemorySource::CommitContext);
+        assert_eq!(entry.title.as_deref(), Some("feat(api)!: new auth"));
+        assert!(entry.text.contains("Use tokens"));
+        assert!(entry.text.contains("src/lib.rs"));
+        assert!(entry.tags.contains(&"breaking-change".to_string()));
+        assert!(entry.tags.contains(&"feat".to_string()));
+
+        let extra = entry.extra.unwrap();
+        assert_eq!(extra["gitCommit"], c.sha);
+        assert_eq!(extra["paths"][0], "src/lib.rs");
+    }
+}
@@ -0,0 +1,587 @@
+//! Ask-log analytics for `gik insights`.
+//!
+//! This module turns the ask log into a report of where the indexed knowledge
+//! serves questions well and where it falls short:
+//! - frequent questions (asked at least twice)
+//! - knowledge gaps: questions that returned nothing or only low-scoring chunks
+//! - hot spots: the files and bases returned most often
+//! - stale asks: answers built from files that had changed since indexing
+//! - suggested paths to `gik add`
+//!
+//! Questions are grouped after normalization (case, whitespace and trailing
+//! punctuation are ignored). Score-based checks need the `topScore`,
+//! `sources` and `stalePaths` fields recorded by recent versions; older ask
+//! log entries only count towards frequency and zero-hit gaps.
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::log::AskLogView;
+
+/// Default score below which an ask counts as a knowledge gap.
+pub const DEFAULT_LOW_SCORE: f32 = 0.3;
+
+/// Default number of rows per report section.
+pub const DEFAULT_INSIGHTS_LIMIT: usize = 10;
+
+/// Words ignored when matching gap questions against file paths.
+const STOPWORDS: &[&str] = &[
+    "about", "and", "are", "can", "does", "for", "from", "has", "have", "how", "into", "the",
+    "there", "this", "use", "used", "what", "when", "where", "which", "who", "why", "with",
+];
+
+// ============================================================================
+// Options
+// ============================================================================
+
+/// Options for building an insights report.
+#[derive(Debug, Clone)]
+pub struct InsightsOptions {
+    /// Only analyse asks since this time (inclusive).
+    pub since: Option<DateTime<Utc>>,
+    /// Only analyse asks until this time (inclusive).
+    pub until: Option<DateTime<Utc>>,
+    /// Asks whose best chunk scores below this are knowledge gaps.
+    pub low_score: f32,
+    /// Maximum rows per report section.
+    pub limit: usize,
+}
+
+impl Default for InsightsOptions {
+    fn default() -> Self {
+        Self {
+            since: None,
+            until: None,
+            low_score: DEFAULT_LOW_SCORE,
+            limit: DEFAULT_INSIGHTS_LIMIT,
+        }
+    }
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// Ask-log analytics report.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsightsReport {
+    /// Number of asks analysed.
+    pub asks: usize,
+    /// Timestamp of the oldest ask analysed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<DateTime<Utc>>,
+    /// Timestamp of the newest ask analysed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<DateTime<Utc>>,
+    /// Score threshold used for knowledge gaps.
+    pub low_score: f32,
+    /// Questions asked at least twice, most frequent first.
+    pub frequent_questions: Vec<QuestionInsight>,
+    /// Questions with no hits or only low-scoring hits.
+    pub knowledge_gaps: Vec<QuestionInsight>,
+    /// Files returned most often.
+    pub top_files: Vec<FileInsight>,
+    /// Bases ranked by the number of asks they returned results for.
+    pub top_bases: Vec<BaseInsight>,
+    /// Asks answered from files that had changed since they were indexed.
+    pub stale_asks: Vec<StaleAskInsight>,
+    /// Paths worth staging with `gik add`.
+    pub suggestions: Vec<AddSuggestion>,
+}
+
+/// A (normalized) question and how it fared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionInsight {
+    /// The question as last asked.
+    pub question: String,
+    /// Number of times it was asked.
+    pub count: usize,
+    /// Best chunk score over all its asks (absent when never recorded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_score: Option<f32>,
+    /// Hits returned by the most recent ask.
+    pub last_hits: u32,
+    /// When it was last asked.
+    pub last_asked: DateTime<Utc>,
+}
+
+/// A file and how often asks returned it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInsight {
+    /// Workspace-relative file path.
+    pub path: String,
+    /// Base the file was returned from.
+    pub base: String,
+    /// Number of asks that returned it.
+    pub count: usize,
+    /// Mean of its best score per ask.
+    pub mean_score: f32,
+}
+
+/// A base and how often asks returned results from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseInsight {
+    /// Base name.
+    pub base: String,
+    /// Number of asks with at least one result from the base.
+    pub asks: usize,
+    /// Total files returned from the base over all asks.
+    pub files: usize,
+}
+
+/// An ask answered from stale files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleAskInsight {
+    /// Ask ID (if recorded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The question asked.
+    pub question: String,
+    /// When it was asked.
+    pub timestamp: DateTime<Utc>,
+    /// Revision the answer came from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision_id: Option<String>,
+    /// Returned files that had changed since indexing.
+    pub paths: Vec<String>,
+}
+
+/// Why a path is suggested for `gik add`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SuggestionReason {
+    /// Indexed, but returned while out of date; re-add to refresh it.
+    Stale,
+    /// Not indexed, and its path matches words from gap questions.
+    Gap,
+}
+
+impl std::fmt::Display for SuggestionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stale => write!(f, "stale"),
+            Self::Gap => write!(f, "gap"),
+        }
+    }
+}
+
+/// A path suggested for `gik add`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddSuggestion {
+    /// Workspace-relative file path.
+    pub path: String,
+    /// Why it is suggested.
+    pub reason: SuggestionReason,
+    /// Stale asks that returned it, or gap questions its path matches.
+    pub asks: usize,
+    /// Gap question words found in the path (empty for stale suggestions).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matched_terms: Vec<String>,
+}
+
+// ============================================================================
+// Analysis
+// ============================================================================
+
+/// Build an insights report from ask log entries.
+///
+/// `unindexed` lists workspace-relative files that are neither indexed nor
+/// staged; they are matched against gap questions to suggest paths to add.
+pub fn build_insights(
+    asks: &[AskLogView],
+    unindexed: &[String],
+    opts: &InsightsOptions,
+) -> InsightsReport {
+    let mut report = InsightsReport {
+        asks: asks.len(),
+        from: asks.iter().map(|a| a.timestamp).min(),
+        to: asks.iter().map(|a| a.timestamp).max(),
+        low_score: opts.low_score,
+        ..Default::default()
+    };
+
+    // Group by normalized question (oldest first so the latest ask wins)
+    let mut ordered: Vec<&AskLogView> = asks.iter().collect();
+    ordered.sort_by_key(|a| a.timestamp);
+
+    let mut questions: HashMap<String, QuestionInsight> = HashMap::new();
+    for ask in &ordered {
+        let insight = questions
+            .entry(normalize_question(&ask.question))
+            .or_insert_with(|| QuestionInsight {
+                question: ask.question.clone(),
+                count: 0,
+                best_score: None,
+                last_hits: 0,
+                last_asked: ask.timestamp,
+            });
+        insight.question = ask.question.clone();
+        insight.count += 1;
+        insight.last_hits = ask.total_hits;
+        insight.last_asked = ask.timestamp;
+        insight.best_score = match (insight.best_score, ask.top_score) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    let mut frequent: Vec<QuestionInsight> = questions
+        .values()
+        .filter(|q| q.count >= 2)
+        .cloned()
+        .collect();
+    frequent.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_asked.cmp(&a.last_asked)));
+    frequent.truncate(opts.limit);
+    report.frequent_questions = frequent;
+
+    let mut gaps: Vec<QuestionInsight> = questions
+        .into_values()
+        .filter(|q| is_gap(q, opts.low_score))
+        .collect();
+    gaps.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_asked.cmp(&a.last_asked)));
+
+    // Hot spots
+    let mut files: HashMap<(&str, &str), (usize, f32)> = HashMap::new();
+    let mut bases: HashMap<&str, BaseInsight> = HashMap::new();
+    for ask in asks {
+        let mut seen_bases = BTreeSet::new();
+        for source in &ask.sources {
+            let file = files
+                .entry((source.base.as_str(), source.path.as_str()))
+                .or_insert((0, 0.0));
+            file.0 += 1;
+            file.1 += source.score;
+
+            let base = bases
+                .entry(source.base.as_str())
+                .or_insert_with(|| BaseInsight {
+                    base: source.base.clone(),
+                    asks: 0,
+                    files: 0,
+                });
+            base.files += 1;
+            if seen_bases.insert(source.base.as_str()) {
+                base.asks += 1;
+            }
+        }
+    }
+
+    let mut top_files: Vec<FileInsight> = files
+        .into_iter()
+        .map(|((base, path), (count, total))| FileInsight {
+            path: path.to_string(),
+            base: base.to_string(),
+            count,
+            mean_score: total / count as f32,
+        })
+        .collect();
+    top_files.sort_by(|a, b| b.count.cmp(&a.count).then(a.path.cmp(&b.path)));
+    top_files.truncate(opts.limit);
+    report.top_files = top_files;
+
+    let mut top_bases: Vec<BaseInsight> = bases.into_values().collect();
+    top_bases.sort_by(|a, b| b.asks.cmp(&a.asks).then(a.base.cmp(&b.base)));
+    report.top_bases = top_bases;
+
+    // Stale asks, newest first
+    let mut stale_counts: HashMap<&str, usize> = HashMap::new();
+    let mut stale_asks: Vec<StaleAskInsight> = Vec::new();
+    for ask in ordered.iter().rev().filter(|a| !a.stale_paths.is_empty()) {
+        for path in &ask.stale_paths {
+            *stale_counts.entry(path.as_str()).or_default() += 1;
+        }
+        stale_asks.push(StaleAskInsight {
+            id: ask.id.clone(),
+            question: ask.question.clone(),
+            timestamp: ask.timestamp,
+            revision_id: ask.revision_id.clone(),
+            paths: ask.stale_paths.clone(),
+        });
+    }
+    stale_asks.truncate(opts.limit);
+    report.stale_asks = stale_asks;
+
+    // Suggestions: refresh stale files first, then unindexed gap matches
+    let mut suggestions: Vec<AddSuggestion> = stale_counts
+        .into_iter()
+        .map(|(path, asks)| AddSuggestion {
+            path: path.to_string(),
+            reason: SuggestionReason::Stale,
+            asks,
+            matched_terms: Vec::new(),
+        })
+        .collect();
+    suggestions.sort_by(|a, b| b.asks.cmp(&a.asks).then(a.path.cmp(&b.path)));
+
+    let mut gap_matches = suggest_for_gaps(&gaps, unindexed);
+    suggestions.append(&mut gap_matches);
+    suggestions.truncate(opts.limit);
+    report.suggestions = suggestions;
+
+    gaps.truncate(opts.limit);
+    report.knowledge_gaps = gaps;
+
+    report
+}
+
+/// Whether a question is a knowledge gap: its latest ask returned nothing,
+/// or no ask ever scored at or above the threshold.
+fn is_gap(question: &QuestionInsight, low_score: f32) -> bool {
+    question.last_hits == 0 || question.best_score.is_some_and(|s| s < low_score)
+}
+
+/// Match unindexed paths against the words of gap questions.
+fn suggest_for_gaps(gaps: &[QuestionInsight], unindexed: &[String]) -> Vec<AddSuggestion> {
+    let gap_terms: Vec<BTreeSet<String>> =
+        gaps.iter().map(|q| question_terms(&q.question)).collect();
+    if gap_terms.iter().all(|t| t.is_empty()) {
+        return Vec::new();
+    }
+
+    let mut suggestions: Vec<AddSuggestion> = Vec::new();
+    for path in unindexed {
+        let tokens = path_tokens(path);
+        let mut matched: BTreeSet<String> = BTreeSet::new();
+        let mut asks = 0;
+        for terms in &gap_terms {
+            let hits: Vec<&String> = terms
+                .iter()
+                .filter(|term| tokens.iter().any(|token| term_matches(term, token)))
+                .collect();
+            if !hits.is_empty() {
+                asks += 1;
+                matched.extend(hits.into_iter().cloned());
+            }
+        }
+        if asks > 0 {
+            suggestions.push(AddSuggestion {
+                path: path.clone(),
+                reason: SuggestionReason::Gap,
+                asks,
+                matched_terms: matched.into_iter().collect(),
+            });
+        }
+    }
+
+    suggestions.sort_by(|a, b| {
+        b.asks
+            .cmp(&a.asks)
+            .then(b.matched_terms.len().cmp(&a.matched_terms.len()))
+            .then(a.path.cmp(&b.path))
+    });
+    suggestions
+}
+
+/// Normalize a question for grouping.
+fn normalize_question(question: &str) -> String {
+    question
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['?', '.', '!'])
+        .to_lowercase()
+}
+
+/// Significant words of a question (lowercase, 3+ chars, no stopwords).
+fn question_terms(question: &str) -> BTreeSet<String> {
+    question
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.len() >= 3 && !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Lowercase alphanumeric tokens of a path, split on separators and
+/// camelCase boundaries.
+fn path_tokens(path: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for part in path.split(|c: char| !c.is_alphanumeric()) {
+        let mut current = String::new();
+        let mut prev_lower = false;
+        for c in part.chars() {
+            if c.is_uppercase() && prev_lower && !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+            current.extend(c.to_lowercase());
+        }
+        if !current.is_empty() {
+            tokens.push(current);
+        }
+    }
+    tokens
+}
+
+/// A term matches a path token when equal, or when one is a prefix of the
+/// other and the shorter has at least 4 chars (`session` ~ `sessions`).
+fn term_matches(term: &str, token: &str) -> bool {
+    if term == token {
+        return true;
+    }
+    let (short, long) = if term.len() <= token.len() {
+        (term, token)
+    } else {
+        (token, term)
+    };
+    short.len() >= 4 && long.starts_with(short)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::AskLogSource;
+    use chrono::Duration;
+
+    fn ask(question: &str, minutes_ago: i64, top_score: Option<f32>, paths: &[&str]) -> AskLogView {
+        AskLogView {
+            id: Some(format!("ask-{:012}", minutes_ago)),
+            branch: "main".to_string(),
+            timestamp: Utc::now() - Duration::minutes(minutes_ago),
+            question: question.to_string(),
+            bases: vec!["code".to_string()],
+            total_hits: paths.len() as u32,
+            bundle_path: None,
+            revision_id: Some("rev-1".to_string()),
+            top_score,
+            sources: paths
+                .iter()
+                .map(|p| AskLogSource {
+                    base: if p.ends_with(".md") { "docs" } else { "code" }.to_string(),
+                    path: p.to_string(),
+                    score: top_score.unwrap_or(0.0),
+                })
+                .collect(),
+            stale_paths: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_frequent_questions_group_normalized() {
+        let asks = vec![
+            ask(
+                "How are sessions stored?",
+                30,
+                Some(0.8),
+                &["src/session.rs"],
+            ),
+            ask(
+                "how are  sessions stored",
+                20,
+                Some(0.7),
+                &["src/session.rs"],
+            ),
+            ask("What is the CLI?", 10, Some(0.9), &["src/cli.rs"]),
+        ];
+
+        let report = build_insights(&asks, &[], &InsightsOptions::default());
+
+        assert_eq!(report.asks, 3);
+        assert_eq!(report.frequent_questions.len(), 1);
+        let frequent = &report.frequent_questions[0];
+        assert_eq!(frequent.count, 2);
+        assert_eq!(frequent.question, "how are  sessions stored");
+        assert_eq!(frequent.best_score, Some(0.8));
+        assert!(report.knowledge_gaps.is_empty());
+    }
+
+    #[test]
+    fn test_gaps_and_gap_suggestions() {
+        let asks = vec![
+            ask("How are payments refunded?", 20, None, &[]),
+            ask(
+                "Where is billing configured?",
+                10,
+                Some(0.1),
+                &["src/cli.rs"],
+            ),
+            ask("What is the CLI?", 5, Some(0.9), &["src/cli.rs"]),
+        ];
+        let unindexed = vec![
+            "src/payments/refund.rs".to_string(),
+            "docs/BillingGuide.md".to_string(),
+            "src/unrelated.rs".to_string(),
+        ];
+
+        let report = build_insights(&asks, &unindexed, &InsightsOptions::default());
+
+        let gaps: Vec<&str> = report
+            .knowledge_gaps
+            .iter()
+            .map(|q| q.question.as_str())
+            .collect();
+        assert_eq!(
+            gaps,
+            vec!["Where is billing configured?", "How are payments refunded?"]
+        );
+
+        let paths: Vec<&str> = report.suggestions.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["src/payments/refund.rs", "docs/BillingGuide.md"]
+        );
+        assert_eq!(report.suggestions[0].reason, SuggestionReason::Gap);
+        assert_eq!(
+            report.suggestions[0].matched_terms,
+            vec!["payments".to_string(), "refunded".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_hot_spots_and_stale_asks() {
+        let mut stale = ask(
+            "Where is config loaded?",
+            5,
+            Some(0.6),
+            &["src/config.rs", "README.md"],
+        );
+        stale.stale_paths = vec!["src/config.rs".to_string()];
+        let asks = vec![
+            ask(
+                "How does commit work?",
+                30,
+                Some(0.7),
+                &["src/commit.rs", "src/config.rs"],
+            ),
+            stale,
+        ];
+
+        let report = build_insights(&asks, &[], &InsightsOptions::default());
+
+        assert_eq!(report.top_files[0].path, "src/config.rs");
+        assert_eq!(report.top_files[0].count, 2);
+        assert!((report.top_files[0].mean_score - 0.65).abs() < 1e-6);
+
+        assert_eq!(report.top_bases[0].base, "code");
+        assert_eq!(report.top_bases[0].asks, 2);
+        assert_eq!(report.top_bases[0].files, 3);
+        assert_eq!(report.top_bases[1].base, "docs");
+
+        assert_eq!(report.stale_asks.len(), 1);
+        assert_eq!(
+            report.stale_asks[0].paths,
+            vec!["src/config.rs".to_string()]
+        );
+        assert_eq!(report.suggestions[0].path, "src/config.rs");
+        assert_eq!(report.suggestions[0].reason, SuggestionReason::Stale);
+    }
+
+    #[test]
+    fn test_path_tokens_and_term_matching() {
+        assert_eq!(
+            path_tokens("docs/BillingGuide.md"),
+            vec!["docs", "billing", "guide", "md"]
+        );
+        assert!(term_matches("sessions", "session"));
+        assert!(!term_matches("api", "apis"));
+        assert!(question_terms("How does the API work?").contains("api"));
+        assert!(!question_terms("How does the API work?").contains("how"));
+    }
+}
@@ -0,0 +1,361 @@
+//! Centrality scores for files in the KG import graph.
+//!
+//! KG sync runs [`annotate_centrality`] over the extracted graph and stores
+//! the scores as props on `file` and `doc` nodes:
+//!
+//! | Prop | Description |
+//! |------|-------------|
+//! | `pageRank` | PageRank over `imports` edges (damping 0.85); sums to 1 |
+//! | `inDegree` / `outDegree` | Distinct files importing / imported by this file |
+//! | `betweenness` | Normalized betweenness, sampled from at most 256 sources on large graphs |
+//! | `centrality` | `pageRank` scaled so the most central file is 1.0 |
+//!
+//! `gik stats` lists the most central files and `gik ask` uses `centrality`
+//! as a score prior (see [`crate::config::CentralityPriorConfig`]).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::errors::GikError;
+use crate::workspace::Workspace;
+
+use super::entities::{KgEdge, KgNode};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Node kinds ranked by centrality.
+const RANKED_KINDS: &[&str] = &["file", "doc"];
+
+/// Edge kinds that make up the ranked graph.
+const RANKED_EDGE_KINDS: &[&str] = &["imports"];
+
+/// PageRank damping factor.
+const DAMPING: f64 = 0.85;
+
+/// PageRank iteration cap.
+const MAX_ITERATIONS: usize = 100;
+
+/// PageRank convergence threshold (L1 change per iteration).
+const TOLERANCE: f64 = 1e-9;
+
+/// Betweenness uses every node as a source up to this many, then samples.
+const MAX_BETWEENNESS_SOURCES: usize = 256;
+
+// ============================================================================
+// Computation
+// ============================================================================
+
+/// Compute centrality for file/doc nodes and store it in their props.
+///
+/// Returns the number of annotated nodes. Graphs without any import edge
+/// between ranked nodes are left untouched, since every file would tie.
+pub fn annotate_centrality(nodes: &mut [KgNode], edges: &[KgEdge]) -> usize {
+    let ranked: Vec<usize> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| RANKED_KINDS.contains(&n.kind.as_str()))
+        .map(|(i, _)| i)
+        .collect();
+    let index: HashMap<&str, usize> = ranked
+        .iter()
+        .enumerate()
+        .map(|(local, &i)| (nodes[i].id.as_str(), local))
+        .collect();
+
+    let n = ranked.len();
+    let mut seen: HashSet<(usize, usize)> = HashSet::new();
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for edge in edges {
+        if !RANKED_EDGE_KINDS.contains(&edge.kind.as_str()) {
+            continue;
+        }
+        let (Some(&from), Some(&to)) = (index.get(edge.from.as_str()), index.get(edge.to.as_str()))
+        else {
+            continue;
+        };
+        if from != to && seen.insert((from, to)) {
+            outgoing[from].push(to);
+            incoming[to].push(from);
+        }
+    }
+    if seen.is_empty() {
+        return 0;
+    }
+
+    let page_rank = page_rank(&outgoing, &incoming);
+    let betweenness = betweenness(&outgoing);
+    let max_rank = page_rank.iter().cloned().fold(0.0, f64::max);
+
+    for (local, &i) in ranked.iter().enumerate() {
+        let centrality = if max_rank > 0.0 {
+            page_rank[local] / max_rank
+        } else {
+            0.0
+        };
+        if !nodes[i].props.is_object() {
+            nodes[i].props = json!({});
+        }
+        if let Some(obj) = nodes[i].props.as_object_mut() {
+            obj.insert("pageRank".to_string(), json!(round(page_rank[local])));
+            obj.insert("inDegree".to_string(), json!(incoming[local].len()));
+            obj.insert("outDegree".to_string(), json!(outgoing[local].len()));
+            obj.insert("betweenness".to_string(), json!(round(betweenness[local])));
+            obj.insert("centrality".to_string(), json!(round(centrality)));
+        }
+    }
+
+    n
+}
+
+/// PageRank with dangling nodes redistributing their rank uniformly.
+fn page_rank(outgoing: &[Vec<usize>], incoming: &[Vec<usize>]) -> Vec<f64> {
+    let n = outgoing.len();
+    let uniform = 1.0 / n as f64;
+    let mut rank = vec![uniform; n];
+
+    for _ in 0..MAX_ITERATIONS {
+        let dangling: f64 = (0..n)
+            .filter(|&i| outgoing[i].is_empty())
+            .map(|i| rank[i])
+            .sum();
+        let base = (1.0 - DAMPING) * uniform + DAMPING * dangling * uniform;
+
+        let next: Vec<f64> = (0..n)
+            .map(|i| {
+                let inflow: f64 = incoming[i]
+                    .iter()
+                    .map(|&j| rank[j] / outgoing[j].len() as f64)
+                    .sum();
+                base + DAMPING * inflow
+            })
+            .collect();
+
+        let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if delta < TOLERANCE {
+            break;
+        }
+    }
+
+    rank
+}
+
+/// Brandes betweenness on the unweighted directed graph, normalized by
+/// `(n - 1)(n - 2)`. Large graphs use evenly spaced sample sources.
+fn betweenness(outgoing: &[Vec<usize>]) -> Vec<f64> {
+    let n = outgoing.len();
+    let mut scores = vec![0.0; n];
+    if n < 3 {
+        return scores;
+    }
+
+    let step = n.div_ceil(MAX_BETWEENNESS_SOURCES).max(1);
+    let sources: Vec<usize> = (0..n).step_by(step).collect();
+
+    let mut stack: Vec<usize> = Vec::with_capacity(n);
+    let mut queue: VecDeque<usize> = VecDeque::with_capacity(n);
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut paths = vec![0.0f64; n];
+    let mut distance = vec![-1i64; n];
+    let mut dependency = vec![0.0f64; n];
+
+    for &source in &sources {
+        stack.clear();
+        for i in 0..n {
+            predecessors[i].clear();
+            paths[i] = 0.0;
+            distance[i] = -1;
+            dependency[i] = 0.0;
+        }
+        paths[source] = 1.0;
+        distance[source] = 0;
+        queue.push_back(source);
+
+        while let Some(v) = queue.pop_front() {
+            stack.push(v);
+            for &w in &outgoing[v] {
+                if distance[w] < 0 {
+                    distance[w] = distance[v] + 1;
+                    queue.push_back(w);
+                }
+                if distance[w] == distance[v] + 1 {
+                    paths[w] += paths[v];
+                    predecessors[w].push(v);
+                }
+            }
+        }
+
+        while let Some(w) = stack.pop() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != source {
+                scores[w] += dependency[w];
+            }
+        }
+    }
+
+    let scale = n as f64 / sources.len() as f64 / ((n - 1) * (n - 2)) as f64;
+    scores.iter_mut().for_each(|s| *s *= scale);
+    scores
+}
+
+/// Round to six decimals so stored props stay readable.
+fn round(value: f64) -> f64 {
+    (value * 1e6).round() / 1e6
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+/// A file ranked by centrality, as reported by `gik stats`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CentralNode {
+    /// Node ID.
+    pub id: String,
+    /// Workspace-relative path.
+    pub path: String,
+    /// PageRank scaled to the most central file (0.0-1.0).
+    pub centrality: f64,
+    /// Raw PageRank.
+    pub page_rank: f64,
+    /// Distinct files importing this file.
+    pub in_degree: u64,
+    /// Distinct files imported by this file.
+    pub out_degree: u64,
+    /// Normalized betweenness.
+    pub betweenness: f64,
+}
+
+/// Return the `limit` most central file/doc nodes, highest first.
+pub fn most_central(nodes: &[KgNode], limit: usize) -> Vec<CentralNode> {
+    let mut ranked: Vec<CentralNode> = nodes
+        .iter()
+        .filter(|n| RANKED_KINDS.contains(&n.kind.as_str()))
+        .filter_map(|n| {
+            let props = &n.props;
+            Some(CentralNode {
+                id: n.id.clone(),
+                path: props["path"].as_str().unwrap_or(&n.label).to_string(),
+                centrality: props["centrality"].as_f64()?,
+                page_rank: props["pageRank"].as_f64().unwrap_or(0.0),
+                in_degree: props["inDegree"].as_u64().unwrap_or(0),
+                out_degree: props["outDegree"].as_u64().unwrap_or(0),
+                betweenness: props["betweenness"].as_f64().unwrap_or(0.0),
+            })
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.centrality
+            .total_cmp(&a.centrality)
+            .then(b.in_degree.cmp(&a.in_degree))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Map each file path to its `centrality` score.
+pub fn centrality_by_path(nodes: &[KgNode]) -> HashMap<String, f32> {
+    nodes
+        .iter()
+        .filter(|n| RANKED_KINDS.contains(&n.kind.as_str()))
+        .filter_map(|n| {
+            let path = n.props["path"].as_str()?;
+            let centrality = n.props["centrality"].as_f64()?;
+            Some((path.to_string(), centrality as f32))
+        })
+        .collect()
+}
+
+/// Load `centrality` scores by file path for a branch; empty when there is no KG.
+///
+/// Only `file` and `doc` nodes are read, so this stays cheap on large graphs.
+pub fn read_centrality_by_path(
+    workspace: &Workspace,
+    branch: &str,
+) -> Result<HashMap<String, f32>, GikError> {
+    if !super::kg_exists(workspace, branch) {
+        return Ok(HashMap::new());
+    }
+    let store = super::open_kg_store(workspace, branch)?;
+    let mut scores = HashMap::new();
+    for kind in RANKED_KINDS {
+        scores.extend(centrality_by_path(&store.get_nodes_by_kind(kind)?));
+    }
+    Ok(scores)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str) -> KgNode {
+        KgNode::new(format!("file:{}", path), "file", path).with_props(json!({ "path": path }))
+    }
+
+    fn imports(from: &str, to: &str) -> KgEdge {
+        KgEdge::new(format!("file:{}", from), format!("file:{}", to), "imports")
+    }
+
+    #[test]
+    fn test_annotate_centrality_ranks_hub_first() {
+        // Three entry points import core.rs; core.rs imports util.rs
+        let mut nodes = vec![
+            file("a.rs"),
+            file("b.rs"),
+            file("c.rs"),
+            file("core.rs"),
+            file("util.rs"),
+            file("orphan.rs"),
+            KgNode::new("sym:rs:core.rs:function:run", "function", "run"),
+        ];
+        let edges = vec![
+            imports("a.rs", "core.rs"),
+            imports("b.rs", "core.rs"),
+            imports("c.rs", "core.rs"),
+            imports("c.rs", "core.rs"),
+            imports("core.rs", "util.rs"),
+            KgEdge::new("file:core.rs", "sym:rs:core.rs:function:run", "defines"),
+        ];
+
+        assert_eq!(annotate_centrality(&mut nodes, &edges), 6);
+
+        let core = &nodes[3].props;
+        assert_eq!(core["inDegree"], 3);
+        assert_eq!(core["outDegree"], 1);
+        // core.rs lies on every path into util.rs
+        assert!(core["betweenness"].as_f64().unwrap() > 0.0);
+        assert!(nodes[6].props.get("centrality").is_none());
+
+        let top = most_central(&nodes, 3);
+        // util.rs inherits all of core.rs's rank, so both outrank the leaves
+        assert_eq!(top[0].path, "util.rs");
+        assert_eq!(top[0].centrality, 1.0);
+        assert_eq!(top[1].path, "core.rs");
+        assert!(top[2].centrality < top[1].centrality);
+
+        let by_path = centrality_by_path(&nodes);
+        assert!(by_path["core.rs"] > by_path["a.rs"]);
+        assert!(by_path["a.rs"] > 0.0);
+    }
+
+    #[test]
+    fn test_annotate_centrality_skips_graph_without_imports() {
+        let mut nodes = vec![file("a.rs"), file("b.rs")];
+        assert_eq!(annotate_centrality(&mut nodes, &[]), 0);
+        assert!(most_central(&nodes, 5).is_empty());
+    }
+}
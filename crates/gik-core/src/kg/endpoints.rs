@@ -0,0 +1,1474 @@
+//! Framework-aware HTTP endpoint detection.
+//!
+//! Next.js routes are derived from file paths in [`super::extractor`]. Every
+//! other framework declares its routes in source, so they are found here with
+//! best-effort regex heuristics:
+//!
+//! | Framework | Patterns |
+//! |-----------|----------|
+//! | Express / Fastify | `app.get('/x', h)`, `router.route('/x').get(h)`, `fastify.route({ method, url, handler })` |
+//! | Flask / FastAPI | `@app.route('/x', methods=[...])`, `@router.get('/x')`, `Blueprint`/`APIRouter` prefixes |
+//! | Django | `path('x/', views.h)`, `re_path(r'^x$', ...)` in `urls.py` |
+//! | Spring | `@GetMapping`, `@PostMapping`, ..., `@RequestMapping` with class-level prefixes |
+//! | ASP.NET | `[HttpGet("x")]`, `[Route("api/[controller]")]`, `app.MapGet("/x", h)` |
+//! | Rails | `get '/x', to: 'c#a'`, `resources`/`resource`, `namespace`/`scope` in `routes.rb` |
+//! | Laravel | `Route::get('/x', [C::class, 'a'])`, `Route::resource`, `Route::prefix(...)->group` |
+//! | Gin / Fiber / Go | `r.GET("/x", h)`, `app.Get("/x", h)`, `Group` prefixes, `HandleFunc` |
+//! | axum / actix | `.route("/x", get(h).post(h2))`, `#[get("/x")]`, `web::resource("/x").route(web::get().to(h))` |
+//!
+//! Routes without a declared method (Django, `HandleFunc`, `@RequestMapping`
+//! without `method`) use the method `ANY`.
+
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+
+use super::lang::{language_from_path, FrameworkHint, LanguageKind};
+use super::text::line_at;
+
+// ============================================================================
+// EndpointCandidate
+// ============================================================================
+
+/// An HTTP endpoint declared in source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointCandidate {
+    /// Upper-case HTTP method, or `ANY`.
+    pub method: String,
+    /// Route pattern as declared, with prefixes applied (e.g. `/api/users/:id`).
+    pub route: String,
+    /// Handler reference as written (e.g. `createUser`, `users#index`).
+    pub handler: Option<String>,
+    /// 1-based line of the route declaration.
+    pub line: u32,
+    /// Framework the route was declared with.
+    pub framework: FrameworkHint,
+}
+
+impl EndpointCandidate {
+    fn new(
+        method: &str,
+        route: String,
+        handler: Option<String>,
+        line: u32,
+        framework: FrameworkHint,
+    ) -> Self {
+        let method = match method.to_uppercase().as_str() {
+            "ALL" | "ANY" | "MATCH" => "ANY".to_string(),
+            other => other.to_string(),
+        };
+        Self {
+            method,
+            route,
+            handler,
+            line,
+            framework,
+        }
+    }
+}
+
+/// Extract the endpoints declared in a source file.
+///
+/// Returns an empty list for files without recognizable route declarations.
+pub fn extract_endpoints(file_path: &str, text: &str) -> Vec<EndpointCandidate> {
+    let file_path = file_path.replace('\\', "/");
+    let endpoints = match language_from_path(&file_path) {
+        LanguageKind::JsTs => extract_js_routes(text),
+        LanguageKind::Python if file_path.ends_with("urls.py") => extract_django_routes(text),
+        LanguageKind::Python => extract_python_routes(text),
+        LanguageKind::Java | LanguageKind::Kotlin => extract_spring_routes(text),
+        LanguageKind::CSharp => extract_aspnet_routes(text),
+        LanguageKind::Ruby if file_path.ends_with("routes.rb") => extract_rails_routes(text),
+        LanguageKind::Php => extract_laravel_routes(&file_path, text),
+        LanguageKind::Go => extract_go_routes(text),
+        LanguageKind::Rust => extract_rust_routes(text),
+        _ => Vec::new(),
+    };
+
+    let mut seen = HashSet::new();
+    endpoints
+        .into_iter()
+        .filter(|e| seen.insert((e.method.clone(), e.route.clone())))
+        .collect()
+}
+
+// ============================================================================
+// JavaScript / TypeScript (Express, Fastify)
+// ============================================================================
+
+const HTTP_VERBS: &str = "get|post|put|patch|delete|head|options";
+
+/// Receivers that conventionally hold an app or router.
+fn is_router_receiver(name: &str) -> bool {
+    matches!(
+        name,
+        "app" | "router" | "server" | "fastify" | "instance" | "r" | "routes" | "api"
+    ) || ["Router", "router", "App", "Server", "Routes"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+fn extract_js_routes(text: &str) -> Vec<EndpointCandidate> {
+    let framework = if text.contains("fastify") {
+        FrameworkHint::Fastify
+    } else {
+        FrameworkHint::Express
+    };
+    let mut endpoints = Vec::new();
+
+    // app.get('/path', ...handlers)
+    let verb_re = Regex::new(&format!(
+        r#"\b([\w$]+)\.({}|all)\(\s*['"`](/[^'"`]*)['"`]"#,
+        HTTP_VERBS
+    ))
+    .expect("Invalid regex");
+    for cap in verb_re.captures_iter(text) {
+        if !is_router_receiver(&cap[1]) {
+            continue;
+        }
+        let whole = cap.get(0).expect("match");
+        let handler = call_args(text, cap.get(2).expect("verb").end())
+            .and_then(|args| handler_arg(split_args(args).get(1..).unwrap_or_default()));
+        endpoints.push(EndpointCandidate::new(
+            &cap[2],
+            normalize_route(&cap[3]),
+            handler,
+            line_at(text, whole.start()),
+            framework,
+        ));
+    }
+
+    // router.route('/path').get(h).post(h)
+    let chain_re = Regex::new(r#"\.route\(\s*['"`](/[^'"`]*)['"`]\s*\)"#).expect("Invalid regex");
+    let link_re = Regex::new(&format!(r#"^\s*\.({}|all)\("#, HTTP_VERBS)).expect("Invalid regex");
+    for cap in chain_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let mut pos = whole.end();
+        while let Some(link) = link_re.captures(&text[pos..]) {
+            let open = pos + link.get(0).expect("match").end() - 1;
+            let Some(args) = call_args(text, open) else {
+                break;
+            };
+            endpoints.push(EndpointCandidate::new(
+                &link[1],
+                normalize_route(&cap[1]),
+                handler_arg(&split_args(args)),
+                line_at(text, whole.start()),
+                framework,
+            ));
+            pos = open + args.len() + 2;
+        }
+    }
+
+    // fastify.route({ method: 'GET', url: '/path', handler })
+    let object_re = Regex::new(r#"\.route\(\s*\{"#).expect("Invalid regex");
+    let method_re =
+        Regex::new(r#"\bmethod\s*:\s*(\[[^\]]*\]|['"]\w+['"])"#).expect("Invalid regex");
+    let url_re = Regex::new(r#"\b(?:url|path)\s*:\s*['"`](/[^'"`]*)['"`]"#).expect("Invalid regex");
+    let handler_re = Regex::new(r#"\bhandler\s*:\s*([\w$.]+)"#).expect("Invalid regex");
+    let word_re = Regex::new(r#"\w+"#).expect("Invalid regex");
+    for m in object_re.find_iter(text) {
+        let Some(args) = call_args(text, m.start() + ".route".len()) else {
+            continue;
+        };
+        let (Some(methods), Some(url)) = (method_re.captures(args), url_re.captures(args)) else {
+            continue;
+        };
+        let handler = handler_re
+            .captures(args)
+            .map(|c| c[1].to_string())
+            .filter(|h| h != "async" && h != "function");
+        for method in word_re.find_iter(&methods[1]) {
+            endpoints.push(EndpointCandidate::new(
+                method.as_str(),
+                normalize_route(&url[1]),
+                handler.clone(),
+                line_at(text, m.start()),
+                FrameworkHint::Fastify,
+            ));
+        }
+    }
+
+    endpoints
+}
+
+// ============================================================================
+// Python (Flask, FastAPI, Django)
+// ============================================================================
+
+fn extract_python_routes(text: &str) -> Vec<EndpointCandidate> {
+    let framework = if text.contains("fastapi") {
+        FrameworkHint::FastApi
+    } else {
+        FrameworkHint::Flask
+    };
+
+    // bp = Blueprint(..., url_prefix="/x") / router = APIRouter(prefix="/x")
+    let mut prefixes: HashMap<String, String> = HashMap::new();
+    let router_re = Regex::new(r#"(\w+)\s*=\s*(?:APIRouter|Blueprint)\("#).expect("Invalid regex");
+    let prefix_re =
+        Regex::new(r#"\b(?:url_)?prefix\s*=\s*['"]([^'"]*)['"]"#).expect("Invalid regex");
+    for cap in router_re.captures_iter(text) {
+        let open = cap.get(0).expect("match").end() - 1;
+        if let Some(prefix) = call_args(text, open).and_then(|args| prefix_re.captures(args)) {
+            prefixes.insert(cap[1].to_string(), prefix[1].to_string());
+        }
+    }
+
+    let decorator_re = Regex::new(&format!(
+        r#"(?m)^[ \t]*@(\w+)\.({}|route|api_route)\("#,
+        HTTP_VERBS
+    ))
+    .expect("Invalid regex");
+    let path_re =
+        Regex::new(r#"^\s*(?:path\s*=\s*|rule\s*=\s*)?['"]([^'"]*)['"]"#).expect("Invalid regex");
+    let methods_re = Regex::new(r#"\bmethods\s*=\s*[\[(]([^\])]*)[\])]"#).expect("Invalid regex");
+    let string_re = Regex::new(r#"['"](\w+)['"]"#).expect("Invalid regex");
+    let def_re = Regex::new(r#"(?m)^[ \t]*(?:async\s+)?def\s+(\w+)"#).expect("Invalid regex");
+
+    let mut endpoints = Vec::new();
+    for cap in decorator_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let Some(args) = call_args(text, whole.end() - 1) else {
+            continue;
+        };
+        let Some(path) = path_re.captures(args) else {
+            continue;
+        };
+        let route = join_route(
+            prefixes.get(&cap[1]).map(String::as_str).unwrap_or(""),
+            &path[1],
+        );
+        let handler = def_re
+            .captures(&text[whole.end()..])
+            .map(|c| c[1].to_string());
+        let line = line_at(text, whole.start());
+
+        let methods: Vec<String> = match &cap[2] {
+            "route" | "api_route" => methods_re
+                .captures(args)
+                .map(|m| {
+                    string_re
+                        .captures_iter(&m[1])
+                        .map(|s| s[1].to_string())
+                        .collect()
+                })
+                .unwrap_or_else(|| vec!["GET".to_string()]),
+            verb => vec![verb.to_string()],
+        };
+        for method in methods {
+            endpoints.push(EndpointCandidate::new(
+                &method,
+                route.clone(),
+                handler.clone(),
+                line,
+                framework,
+            ));
+        }
+    }
+    endpoints
+}
+
+fn extract_django_routes(text: &str) -> Vec<EndpointCandidate> {
+    let route_re =
+        Regex::new(r#"\b(path|re_path|url)\(\s*r?['"]([^'"]*)['"]\s*,\s*"#).expect("Invalid regex");
+    let view_re = Regex::new(r#"^[\w.]+"#).expect("Invalid regex");
+
+    let mut endpoints = Vec::new();
+    for cap in route_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let Some(view) = view_re.find(&text[whole.end()..]) else {
+            continue;
+        };
+        // Class-based views: `TeamView.as_view()`
+        let view = view.as_str().trim_end_matches(".as_view");
+        if view == "include" {
+            continue;
+        }
+        let pattern = if &cap[1] == "path" {
+            cap[2].to_string()
+        } else {
+            cap[2]
+                .trim_start_matches('^')
+                .trim_end_matches('$')
+                .to_string()
+        };
+        endpoints.push(EndpointCandidate::new(
+            "ANY",
+            normalize_route(&pattern),
+            Some(view.to_string()),
+            line_at(text, whole.start()),
+            FrameworkHint::Django,
+        ));
+    }
+    endpoints
+}
+
+// ============================================================================
+// Annotated Controllers (Spring, ASP.NET)
+// ============================================================================
+
+/// A route annotation waiting for the declaration it applies to.
+#[derive(Debug, Default)]
+struct RouteAnnotation {
+    /// Declared methods (empty = any).
+    methods: Vec<String>,
+    /// Declared path templates (empty = the prefix itself).
+    paths: Vec<String>,
+    /// Whether this was a method-specific annotation (`@GetMapping`, `[HttpGet]`).
+    verb: bool,
+    /// 1-based line of the annotation.
+    line: u32,
+}
+
+/// Collect annotation lines and apply them to the next class or method
+/// declaration. `parse` turns one annotation into a [`RouteAnnotation`].
+fn extract_annotated_routes(
+    text: &str,
+    annotation_re: &Regex,
+    framework: FrameworkHint,
+    parse: impl Fn(&regex::Captures, &str) -> Option<RouteAnnotation>,
+    class_prefix: impl Fn(&str, &str) -> String,
+) -> Vec<EndpointCandidate> {
+    let class_re = Regex::new(r#"\b(?:class|interface|object)\s+(\w+)"#).expect("Invalid regex");
+    let method_re = Regex::new(r#"(\w+)\s*(?:<[^>]*>\s*)?\("#).expect("Invalid regex");
+
+    let mut endpoints = Vec::new();
+    let mut pending: Vec<RouteAnnotation> = Vec::new();
+    let mut prefix = String::new();
+    let mut pos = 0;
+
+    while pos < text.len() {
+        let next_line = line_end(text, pos);
+        let line_text = &text[pos..next_line];
+        let trimmed = line_text.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") || trimmed.starts_with('*') {
+            pos = next_line;
+            continue;
+        }
+
+        // Route annotations at the start of the line; arguments may span lines
+        let mut cursor = pos + (line_text.len() - line_text.trim_start().len());
+        while let Some(cap) = annotation_re.captures_at(text, cursor) {
+            let whole = cap.get(0).expect("match");
+            if whole.start() != cursor {
+                break;
+            }
+            let mut end = whole.end();
+            let args = if text[..end].ends_with('(') {
+                let args = call_args(text, end - 1).unwrap_or("");
+                end += args.len() + 1;
+                args
+            } else {
+                ""
+            };
+            if text[end..].starts_with(']') {
+                end += 1;
+            }
+            if let Some(mut annotation) = parse(&cap, args) {
+                annotation.line = line_at(text, whole.start());
+                pending.push(annotation);
+            }
+            cursor = end + (text[end..].len() - text[end..].trim_start_matches([' ', '\t']).len());
+        }
+
+        // The rest of the line is the declaration (or another annotation)
+        pos = line_end(text, cursor);
+        let decl = text[cursor..pos].trim();
+        if decl.is_empty() || decl.starts_with('@') || decl.starts_with('[') {
+            continue;
+        }
+
+        if let Some(class) = class_re.captures(decl) {
+            prefix = pending
+                .iter()
+                .find(|a| !a.verb)
+                .and_then(|a| a.paths.first())
+                .map(|p| class_prefix(p, &class[1]))
+                .unwrap_or_default();
+            pending.clear();
+            continue;
+        }
+
+        if pending.is_empty() {
+            continue;
+        }
+        // Not a method declaration (e.g. the tail of a multi-line annotation)
+        let Some(handler) = method_re.captures(decl).map(|c| c[1].to_string()) else {
+            continue;
+        };
+        let verb = pending.iter().find(|a| a.verb);
+        let route_paths: Vec<String> = pending.iter().flat_map(|a| a.paths.clone()).collect();
+        let methods: Vec<String> = pending.iter().flat_map(|a| a.methods.clone()).collect();
+        let line = verb.or(pending.first()).map(|a| a.line).unwrap_or(1);
+        let paths = if route_paths.is_empty() {
+            vec![String::new()]
+        } else {
+            route_paths
+        };
+        let methods = if methods.is_empty() {
+            vec!["ANY".to_string()]
+        } else {
+            methods
+        };
+        for path in &paths {
+            let path = path.replace("[action]", &handler);
+            // ASP.NET templates starting with `/` or `~/` ignore the controller prefix
+            let route = if path.starts_with('~')
+                || (framework == FrameworkHint::AspNet && path.starts_with('/'))
+            {
+                normalize_route(path.trim_start_matches('~'))
+            } else {
+                join_route(&prefix, &path)
+            };
+            for method in &methods {
+                endpoints.push(EndpointCandidate::new(
+                    method,
+                    route.clone(),
+                    Some(handler.clone()),
+                    line,
+                    framework,
+                ));
+            }
+        }
+        pending.clear();
+    }
+    endpoints
+}
+
+/// String literals in annotation arguments that set the path.
+fn annotation_paths(args: &str) -> Vec<String> {
+    let named_re = Regex::new(r#"\b(?:value|path|template|name)\s*=\s*"#).expect("Invalid regex");
+    let string_re = Regex::new(r#""([^"]*)""#).expect("Invalid regex");
+
+    let mut paths = Vec::new();
+    for arg in split_args(args) {
+        let arg = arg.trim();
+        let value = if let Some(m) = named_re.find(arg) {
+            if m.start() != 0 {
+                continue;
+            }
+            &arg[m.end()..]
+        } else if arg.starts_with('"') || arg.starts_with('{') || arg.starts_with('[') {
+            arg
+        } else {
+            continue;
+        };
+        paths.extend(string_re.captures_iter(value).map(|c| c[1].to_string()));
+    }
+    paths
+}
+
+fn extract_spring_routes(text: &str) -> Vec<EndpointCandidate> {
+    if !text.contains("Mapping") {
+        return Vec::new();
+    }
+    let annotation_re =
+        Regex::new(r#"@(Get|Post|Put|Patch|Delete|Request)Mapping\b\(?"#).expect("Invalid regex");
+    let method_re = Regex::new(r#"RequestMethod\.(\w+)"#).expect("Invalid regex");
+
+    extract_annotated_routes(
+        text,
+        &annotation_re,
+        FrameworkHint::Spring,
+        |cap, args| {
+            let verb = &cap[1] != "Request";
+            let methods = if verb {
+                vec![cap[1].to_uppercase()]
+            } else {
+                method_re
+                    .captures_iter(args)
+                    .map(|m| m[1].to_string())
+                    .collect()
+            };
+            Some(RouteAnnotation {
+                methods,
+                paths: annotation_paths(args),
+                verb,
+                line: 0,
+            })
+        },
+        |path, _class| path.to_string(),
+    )
+}
+
+fn extract_aspnet_routes(text: &str) -> Vec<EndpointCandidate> {
+    let mut endpoints = Vec::new();
+
+    if text.contains("[Http") || text.contains("[Route") {
+        let annotation_re =
+            Regex::new(r#"\[(?:Http(Get|Post|Put|Patch|Delete|Head|Options)|(Route))\b\(?"#)
+                .expect("Invalid regex");
+        endpoints.extend(extract_annotated_routes(
+            text,
+            &annotation_re,
+            FrameworkHint::AspNet,
+            |cap, args| {
+                Some(RouteAnnotation {
+                    methods: cap
+                        .get(1)
+                        .map(|m| vec![m.as_str().to_uppercase()])
+                        .unwrap_or_default(),
+                    paths: annotation_paths(args),
+                    verb: cap.get(1).is_some(),
+                    line: 0,
+                })
+            },
+            |path, class| path.replace("[controller]", class.trim_end_matches("Controller")),
+        ));
+    }
+
+    // Minimal APIs: app.MapGet("/path", handler)
+    let map_re =
+        Regex::new(r#"\.Map(Get|Post|Put|Patch|Delete)\(\s*"([^"]*)""#).expect("Invalid regex");
+    for cap in map_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let open = whole.start() + ".Map".len() + cap[1].len();
+        let handler = call_args(text, open)
+            .and_then(|args| handler_arg(split_args(args).get(1..).unwrap_or_default()));
+        endpoints.push(EndpointCandidate::new(
+            &cap[1],
+            normalize_route(&cap[2]),
+            handler,
+            line_at(text, whole.start()),
+            FrameworkHint::AspNet,
+        ));
+    }
+    endpoints
+}
+
+// ============================================================================
+// Rails
+// ============================================================================
+
+/// Standard `resources` actions: (action, method, path suffix, needs id).
+const RESOURCE_ACTIONS: &[(&str, &str, &str, bool)] = &[
+    ("index", "GET", "", false),
+    ("create", "POST", "", false),
+    ("new", "GET", "/new", false),
+    ("show", "GET", "", true),
+    ("edit", "GET", "/edit", true),
+    ("update", "PATCH", "", true),
+    ("destroy", "DELETE", "", true),
+];
+
+/// One open `do ... end` block in `routes.rb`.
+#[derive(Debug, Clone, Default)]
+struct RailsScope {
+    /// Path prefix contributed by this block.
+    path: String,
+    /// Controller module contributed by `namespace`.
+    module: String,
+    /// Controller for routes inside a `resources` block.
+    controller: Option<String>,
+    /// Whether this is a `resources` block (nested resources get `:<name>_id`).
+    resources: Option<String>,
+}
+
+fn extract_rails_routes(text: &str) -> Vec<EndpointCandidate> {
+    let verb_re = Regex::new(
+        r#"^(get|post|put|patch|delete|match)\s*\(?\s*(?:['"]([^'"]*)['"]|:(\w+))(.*)$"#,
+    )
+    .expect("Invalid regex");
+    let to_re = Regex::new(r#"(?:\bto:\s*|=>\s*)['"]([\w/]+#\w+)['"]"#).expect("Invalid regex");
+    let via_re = Regex::new(r#"\bvia:\s*(\[[^\]]*\]|:\w+)"#).expect("Invalid regex");
+    let resources_re = Regex::new(r#"^(resources?)\s*\(?\s*:(\w+)(.*)$"#).expect("Invalid regex");
+    let only_re = Regex::new(r#"\b(only|except):\s*(\[[^\]]*\]|:\w+)"#).expect("Invalid regex");
+    let namespace_re = Regex::new(r#"^namespace\s*\(?\s*:(\w+)"#).expect("Invalid regex");
+    let scope_re =
+        Regex::new(r#"^scope\s*\(?\s*(?:path:\s*)?['"]([^'"]*)['"]"#).expect("Invalid regex");
+    let root_re =
+        Regex::new(r#"^root\s*\(?\s*(?:to:\s*)?['"]([\w/]+#\w+)['"]"#).expect("Invalid regex");
+    let symbol_re = Regex::new(r#":(\w+)"#).expect("Invalid regex");
+    let block_re = Regex::new(r#"\bdo\s*(\|[^|]*\|)?\s*$"#).expect("Invalid regex");
+
+    let mut stack: Vec<RailsScope> = Vec::new();
+    let mut endpoints = Vec::new();
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = raw_line.trim();
+        let line_no = index as u32 + 1;
+        if line.starts_with('#') {
+            continue;
+        }
+        if line == "end" || line.starts_with("end ") {
+            stack.pop();
+            continue;
+        }
+
+        let prefix: String = stack.iter().map(|s| s.path.as_str()).collect();
+        let module: String = stack
+            .iter()
+            .filter(|s| !s.module.is_empty())
+            .map(|s| format!("{}/", s.module))
+            .collect();
+        let controller = stack.iter().rev().find_map(|s| s.controller.clone());
+        let opens_block = block_re.is_match(line);
+        let mut scope = RailsScope::default();
+
+        if let Some(cap) = namespace_re.captures(line) {
+            scope.path = format!("/{}", &cap[1]);
+            scope.module = cap[1].to_string();
+        } else if let Some(cap) = scope_re.captures(line) {
+            scope.path = normalize_route(&cap[1]);
+            if scope.path == "/" {
+                scope.path.clear();
+            }
+        } else if line == "member do" {
+            scope.path = "/:id".to_string();
+        } else if let Some(cap) = root_re.captures(line) {
+            endpoints.push(EndpointCandidate::new(
+                "GET",
+                join_route(&prefix, "/"),
+                Some(format!("{}{}", module, &cap[1])),
+                line_no,
+                FrameworkHint::Rails,
+            ));
+        } else if let Some(cap) = resources_re.captures(line) {
+            let plural = &cap[1] == "resources";
+            let name = cap[2].to_string();
+            let options = &cap[3];
+            // Nested resources hang off the parent's member path
+            let parent = match stack.last() {
+                Some(RailsScope {
+                    resources: Some(parent),
+                    ..
+                }) => format!("/:{}_id", singular(parent)),
+                _ => String::new(),
+            };
+            let base = format!("{}{}/{}", prefix, parent, name);
+            let filter = only_re.captures(options).map(|c| {
+                let actions: Vec<String> = symbol_re
+                    .captures_iter(&c[2])
+                    .map(|s| s[1].to_string())
+                    .collect();
+                (&c[1] == "only", actions)
+            });
+            let controller_name = if plural {
+                name.clone()
+            } else {
+                format!("{}s", name)
+            };
+            for (action, method, suffix, needs_id) in RESOURCE_ACTIONS {
+                if let Some((only, actions)) = &filter {
+                    if actions.iter().any(|a| a == action) != *only {
+                        continue;
+                    }
+                }
+                if !plural && *action == "index" {
+                    continue;
+                }
+                let id = if *needs_id && plural { "/:id" } else { "" };
+                endpoints.push(EndpointCandidate::new(
+                    method,
+                    normalize_route(&format!("{}{}{}", base, id, suffix)),
+                    Some(format!("{}{}#{}", module, controller_name, action)),
+                    line_no,
+                    FrameworkHint::Rails,
+                ));
+            }
+            scope.path = format!("{}/{}", parent, name);
+            scope.controller = Some(controller_name);
+            scope.resources = plural.then_some(name);
+        } else if let Some(cap) = verb_re.captures(line) {
+            let options = &cap[4];
+            let path = cap.get(2).or(cap.get(3)).map(|m| m.as_str()).unwrap_or("");
+            let handler = to_re
+                .captures(options)
+                .map(|c| format!("{}{}", module, &c[1]))
+                .or_else(|| {
+                    // `get :search` inside a resources block
+                    cap.get(3)
+                        .zip(controller.as_ref())
+                        .map(|(action, c)| format!("{}{}#{}", module, c, action.as_str()))
+                });
+            let methods: Vec<String> = if &cap[1] == "match" {
+                via_re
+                    .captures(options)
+                    .map(|v| {
+                        symbol_re
+                            .captures_iter(&v[1])
+                            .map(|s| s[1].to_string())
+                            .collect()
+                    })
+                    .unwrap_or_else(|| vec!["ANY".to_string()])
+            } else {
+                vec![cap[1].to_string()]
+            };
+            for method in methods {
+                endpoints.push(EndpointCandidate::new(
+                    &method,
+                    join_route(&prefix, path),
+                    handler.clone(),
+                    line_no,
+                    FrameworkHint::Rails,
+                ));
+            }
+        }
+
+        if opens_block {
+            stack.push(scope);
+        }
+    }
+    endpoints
+}
+
+/// Naive singular form for nested resource ids (`users` → `user`).
+fn singular(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else {
+        name.strip_suffix('s').unwrap_or(name).to_string()
+    }
+}
+
+// ============================================================================
+// Laravel
+// ============================================================================
+
+fn extract_laravel_routes(file_path: &str, text: &str) -> Vec<EndpointCandidate> {
+    if !text.contains("Route::") {
+        return Vec::new();
+    }
+
+    // routes/api.php is served under /api by default
+    let file_prefix = if file_path.ends_with("routes/api.php") {
+        "/api"
+    } else {
+        ""
+    };
+
+    // Route::prefix('x')->group(...) applies to everything inside the group call
+    let group_re =
+        Regex::new(r#"prefix\(\s*['"]([^'"]*)['"]\s*\)[^;]*?->group\("#).expect("Invalid regex");
+    let groups: Vec<(usize, usize, String)> = group_re
+        .captures_iter(text)
+        .filter_map(|cap| {
+            let open = cap.get(0).expect("match").end() - 1;
+            let args = call_args(text, open)?;
+            Some((open, open + args.len(), cap[1].to_string()))
+        })
+        .collect();
+
+    let route_re = Regex::new(&format!(
+        r#"Route::({}|any|match|resource|apiResource)\("#,
+        HTTP_VERBS
+    ))
+    .expect("Invalid regex");
+    let string_re = Regex::new(r#"^['"]([^'"]*)['"]$"#).expect("Invalid regex");
+    let action_re =
+        Regex::new(r#"^\[\s*([\w\\]+)::class\s*,\s*['"](\w+)['"]\s*\]$"#).expect("Invalid regex");
+    let class_re = Regex::new(r#"^([\w\\]+)::class$"#).expect("Invalid regex");
+    let word_re = Regex::new(r#"\w+"#).expect("Invalid regex");
+
+    let mut endpoints = Vec::new();
+    for cap in route_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let Some(args) = call_args(text, whole.end() - 1) else {
+            continue;
+        };
+        let args = split_args(args);
+        let group_prefix: String = groups
+            .iter()
+            .filter(|(start, end, _)| *start < whole.start() && whole.start() < *end)
+            .map(|(_, _, prefix)| normalize_route(prefix))
+            .collect();
+        let prefix = format!("{}{}", file_prefix, group_prefix);
+        let line = line_at(text, whole.start());
+        let literal = |arg: Option<&&str>| {
+            arg.and_then(|a| string_re.captures(a.trim()))
+                .map(|c| c[1].to_string())
+        };
+        let action = |arg: Option<&&str>| {
+            let arg = arg?.trim();
+            if let Some(c) = action_re.captures(arg) {
+                return Some(format!("{}@{}", short_class(&c[1]), &c[2]));
+            }
+            string_re
+                .captures(arg)
+                .map(|c| c[1].to_string())
+                .filter(|s| s.contains('@'))
+        };
+
+        match &cap[1] {
+            "resource" | "apiResource" => {
+                let (Some(name), Some(controller)) = (
+                    literal(args.first()),
+                    args.get(1).and_then(|a| class_re.captures(a.trim())),
+                ) else {
+                    continue;
+                };
+                let controller = short_class(&controller[1]).to_string();
+                let base = join_route(&prefix, &name);
+                let param = format!("/{{{}}}", singular(file_name_segment(&name)));
+                for (action, method, suffix, needs_id) in RESOURCE_ACTIONS {
+                    if &cap[1] == "apiResource" && matches!(*action, "new" | "edit") {
+                        continue;
+                    }
+                    let action = match *action {
+                        "new" => "create",
+                        "create" => "store",
+                        other => other,
+                    };
+                    let suffix = if *suffix == "/new" { "/create" } else { suffix };
+                    let id = if *needs_id { param.as_str() } else { "" };
+                    endpoints.push(EndpointCandidate::new(
+                        method,
+                        normalize_route(&format!("{}{}{}", base, id, suffix)),
+                        Some(format!("{}@{}", controller, action)),
+                        line,
+                        FrameworkHint::Laravel,
+                    ));
+                }
+            }
+            "match" => {
+                let (Some(methods), Some(path)) = (args.first(), literal(args.get(1))) else {
+                    continue;
+                };
+                for method in word_re.find_iter(methods) {
+                    endpoints.push(EndpointCandidate::new(
+                        method.as_str(),
+                        join_route(&prefix, &path),
+                        action(args.get(2)),
+                        line,
+                        FrameworkHint::Laravel,
+                    ));
+                }
+            }
+            verb => {
+                let Some(path) = literal(args.first()) else {
+                    continue;
+                };
+                endpoints.push(EndpointCandidate::new(
+                    verb,
+                    join_route(&prefix, &path),
+                    action(args.get(1)),
+                    line,
+                    FrameworkHint::Laravel,
+                ));
+            }
+        }
+    }
+    endpoints
+}
+
+/// `App\Http\Controllers\UserController` → `UserController`.
+fn short_class(class: &str) -> &str {
+    class.rsplit('\\').next().unwrap_or(class)
+}
+
+/// Last segment of a dotted or slashed resource name (`admin.users` → `users`).
+fn file_name_segment(name: &str) -> &str {
+    name.rsplit(['.', '/']).next().unwrap_or(name)
+}
+
+// ============================================================================
+// Go (Gin, Fiber, Echo, chi, net/http)
+// ============================================================================
+
+fn extract_go_routes(text: &str) -> Vec<EndpointCandidate> {
+    let framework = if text.contains("gin-gonic/gin") {
+        FrameworkHint::Gin
+    } else if text.contains("gofiber/fiber") {
+        FrameworkHint::Fiber
+    } else {
+        FrameworkHint::Generic
+    };
+
+    // api := r.Group("/api")
+    let mut prefixes: HashMap<String, String> = HashMap::new();
+    let group_re =
+        Regex::new(r#"(\w+)\s*:?=\s*(\w+)\.Group\(\s*"([^"]*)""#).expect("Invalid regex");
+    for cap in group_re.captures_iter(text) {
+        let parent = prefixes.get(&cap[2]).cloned().unwrap_or_default();
+        prefixes.insert(cap[1].to_string(), join_route(&parent, &cap[3]));
+    }
+
+    let verb_re = Regex::new(
+        r#"\b(\w+)\.(GET|POST|PUT|PATCH|DELETE|HEAD|OPTIONS|Any|Get|Post|Put|Patch|Delete|Head|Options|All)\(\s*"([^"]*)""#,
+    )
+    .expect("Invalid regex");
+    let handle_re =
+        Regex::new(r#"\b(\w+)\.(HandleFunc|Handle)\(\s*"([^"]*)""#).expect("Invalid regex");
+    let methods_re = Regex::new(r#"^\s*\.Methods\(([^)]*)\)"#).expect("Invalid regex");
+    let string_re = Regex::new(r#""(\w+)""#).expect("Invalid regex");
+
+    let mut endpoints = Vec::new();
+    for cap in verb_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let open = whole.start() + cap[1].len() + 1 + cap[2].len();
+        let handler = call_args(text, open)
+            .and_then(|args| handler_arg(split_args(args).get(1..).unwrap_or_default()));
+        let prefix = prefixes.get(&cap[1]).map(String::as_str).unwrap_or("");
+        endpoints.push(EndpointCandidate::new(
+            &cap[2],
+            join_route(prefix, &cap[3]),
+            handler,
+            line_at(text, whole.start()),
+            framework,
+        ));
+    }
+
+    for cap in handle_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let open = whole.start() + cap[1].len() + 1 + cap[2].len();
+        let Some(args) = call_args(text, open) else {
+            continue;
+        };
+        let handler = handler_arg(split_args(args).get(1..).unwrap_or_default());
+        // Go 1.22 patterns carry the method: "GET /users/{id}"
+        let (method, pattern) = match cap[3].split_once(' ') {
+            Some((method, pattern)) => (method.to_string(), pattern.trim().to_string()),
+            None => ("ANY".to_string(), cap[3].to_string()),
+        };
+        // gorilla/mux: .HandleFunc(...).Methods("GET", "POST")
+        let methods: Vec<String> = methods_re
+            .captures(&text[open + args.len() + 2..])
+            .map(|m| {
+                string_re
+                    .captures_iter(&m[1])
+                    .map(|s| s[1].to_string())
+                    .collect()
+            })
+            .unwrap_or_else(|| vec![method]);
+        let prefix = prefixes.get(&cap[1]).map(String::as_str).unwrap_or("");
+        for method in methods {
+            endpoints.push(EndpointCandidate::new(
+                &method,
+                join_route(prefix, &pattern),
+                handler.clone(),
+                line_at(text, whole.start()),
+                FrameworkHint::Generic,
+            ));
+        }
+    }
+    endpoints
+}
+
+// ============================================================================
+// Rust (axum, actix-web)
+// ============================================================================
+
+fn extract_rust_routes(text: &str) -> Vec<EndpointCandidate> {
+    let mut endpoints = Vec::new();
+    let verbs = format!("{}|any", HTTP_VERBS);
+
+    // actix: #[get("/path")] async fn handler
+    let attr_re = Regex::new(&format!(
+        r#"#\[(?:actix_web::)?({})\(\s*"([^"]*)""#,
+        HTTP_VERBS
+    ))
+    .expect("Invalid regex");
+    let fn_re = Regex::new(r#"\bfn\s+(\w+)"#).expect("Invalid regex");
+    for cap in attr_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        endpoints.push(EndpointCandidate::new(
+            &cap[1],
+            normalize_route(&cap[2]),
+            fn_re
+                .captures(&text[whole.end()..])
+                .map(|c| c[1].to_string()),
+            line_at(text, whole.start()),
+            FrameworkHint::Actix,
+        ));
+    }
+
+    // axum: .route("/path", get(h).post(h2)); actix: .route("/path", web::get().to(h))
+    let route_re = Regex::new(r#"\.route\(\s*"([^"]*)"\s*,"#).expect("Invalid regex");
+    let axum_re =
+        Regex::new(&format!(r#"\b({})\(\s*([\w:]+)\s*\)"#, verbs)).expect("Invalid regex");
+    let actix_re =
+        Regex::new(&format!(r#"web::({})\(\)\s*\.to\(\s*([\w:]+)"#, verbs)).expect("Invalid regex");
+    for cap in route_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let Some(args) = call_args(text, whole.start() + ".route".len()) else {
+            continue;
+        };
+        let router = split_args(args).get(1).copied().unwrap_or("");
+        let (re, framework) = if router.contains("web::") {
+            (&actix_re, FrameworkHint::Actix)
+        } else {
+            (&axum_re, FrameworkHint::Axum)
+        };
+        for route in re.captures_iter(router) {
+            endpoints.push(EndpointCandidate::new(
+                &route[1],
+                normalize_route(&cap[1]),
+                Some(route[2].to_string()),
+                line_at(text, whole.start()),
+                framework,
+            ));
+        }
+    }
+
+    // actix: web::resource("/path").route(web::get().to(h))
+    let resource_re = Regex::new(r#"web::resource\(\s*"([^"]*)"\s*\)"#).expect("Invalid regex");
+    for cap in resource_re.captures_iter(text) {
+        let whole = cap.get(0).expect("match");
+        let chain = &text[whole.end()..];
+        let end = ["web::resource(", ".route(\"", ";"]
+            .iter()
+            .filter_map(|stop| chain.find(stop))
+            .min()
+            .unwrap_or(chain.len());
+        for route in actix_re.captures_iter(&chain[..end]) {
+            endpoints.push(EndpointCandidate::new(
+                &route[1],
+                normalize_route(&cap[1]),
+                Some(route[2].to_string()),
+                line_at(text, whole.start()),
+                FrameworkHint::Actix,
+            ));
+        }
+    }
+    endpoints
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Byte offset just past the end of the line containing `offset`.
+fn line_end(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .find('\n')
+        .map(|i| offset + i + 1)
+        .unwrap_or(text.len())
+}
+
+/// The text between the parenthesis at `open` and its matching close.
+fn call_args(text: &str, open: usize) -> Option<&str> {
+    let bytes = text.as_bytes();
+    if bytes.get(open) != Some(&b'(') && bytes.get(open) != Some(&b'{') {
+        return None;
+    }
+    let mut depth = 0usize;
+    let mut quote: Option<u8> = None;
+    let mut escaped = false;
+    for (i, &b) in bytes.iter().enumerate().skip(open) {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == q {
+                quote = None;
+            }
+            continue;
+        }
+        match b {
+            b'"' | b'\'' | b'`' => quote = Some(b),
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[open + 1..i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split call arguments on top-level commas.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !args[start..].trim().is_empty() {
+        parts.push(args[start..].trim());
+    }
+    parts
+}
+
+/// The handler among route arguments: the last one, if it names a function.
+///
+/// Wrappers such as `asyncHandler(createUser)` are unwrapped; inline
+/// functions have no name and yield `None`.
+fn handler_arg(args: &[&str]) -> Option<String> {
+    let name_re = Regex::new(r#"^[\w$]+(?:(?:\.|::)[\w$]+)*$"#).expect("Invalid regex");
+    let wrapped_re =
+        Regex::new(r#"^[\w$.]+\(\s*([\w$]+(?:(?:\.|::)[\w$]+)*)\s*\)$"#).expect("Invalid regex");
+
+    let last = args.last()?.trim();
+    if name_re.is_match(last) {
+        return Some(last.to_string());
+    }
+    wrapped_re.captures(last).map(|c| c[1].to_string())
+}
+
+/// Ensure a leading slash and collapse duplicate slashes.
+fn normalize_route(route: &str) -> String {
+    let segments: Vec<&str> = route.split('/').filter(|s| !s.is_empty()).collect();
+    let mut normalized = format!("/{}", segments.join("/"));
+    if route.ends_with('/') && normalized.len() > 1 {
+        normalized.push('/');
+    }
+    normalized
+}
+
+/// Join a route prefix and a path; an empty path is the prefix itself.
+fn join_route(prefix: &str, path: &str) -> String {
+    if path.is_empty() {
+        normalize_route(prefix)
+    } else {
+        normalize_route(&format!("{}/{}", prefix, path))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn routes(file_path: &str, text: &str) -> Vec<(String, String, Option<String>)> {
+        extract_endpoints(file_path, text)
+            .into_iter()
+            .map(|e| (e.method, e.route, e.handler))
+            .collect()
+    }
+
+    fn route(method: &str, route: &str, handler: Option<&str>) -> (String, String, Option<String>) {
+        (
+            method.to_string(),
+            route.to_string(),
+            handler.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_express_and_fastify_routes() {
+        let code = r#"
+const router = express.Router();
+router.get('/users', listUsers);
+router.post('/users', auth, asyncHandler(createUser));
+router.delete('/users/:id', (req, res) => res.sendStatus(204));
+router.route('/teams/:id').get(getTeam).put(updateTeam);
+axios.get('/api/users');
+fastify.route({ method: ['GET', 'HEAD'], url: '/health', handler: health });
+"#;
+        assert_eq!(
+            routes("src/routes/users.ts", code),
+            vec![
+                route("GET", "/users", Some("listUsers")),
+                route("POST", "/users", Some("createUser")),
+                route("DELETE", "/users/:id", None),
+                route("GET", "/teams/:id", Some("getTeam")),
+                route("PUT", "/teams/:id", Some("updateTeam")),
+                route("GET", "/health", Some("health")),
+                route("HEAD", "/health", Some("health")),
+            ]
+        );
+        let endpoints = extract_endpoints("src/routes/users.ts", code);
+        assert_eq!(endpoints[0].line, 3);
+        assert_eq!(endpoints[6].framework, FrameworkHint::Fastify);
+    }
+
+    #[test]
+    fn test_flask_fastapi_and_django_routes() {
+        let flask = r#"
+bp = Blueprint("users", __name__, url_prefix="/users")
+
+@bp.route("/", methods=["GET", "POST"])
+def users():
+    pass
+
+@bp.get("/<int:id>")
+def get_user(id):
+    pass
+"#;
+        assert_eq!(
+            routes("app/users.py", flask),
+            vec![
+                route("GET", "/users/", Some("users")),
+                route("POST", "/users/", Some("users")),
+                route("GET", "/users/<int:id>", Some("get_user")),
+            ]
+        );
+
+        let fastapi = r#"
+from fastapi import APIRouter
+router = APIRouter(prefix="/items")
+
+@router.post("/{item_id}")
+async def update_item(item_id: int):
+    ...
+"#;
+        let endpoints = extract_endpoints("api/items.py", fastapi);
+        assert_eq!(endpoints[0].route, "/items/{item_id}");
+        assert_eq!(endpoints[0].handler.as_deref(), Some("update_item"));
+        assert_eq!(endpoints[0].framework, FrameworkHint::FastApi);
+
+        let django = r#"
+urlpatterns = [
+    path("users/<int:pk>/", views.user_detail, name="user-detail"),
+    path("teams/", TeamView.as_view()),
+    re_path(r"^legacy/(?P<slug>[-\w]+)$", views.legacy),
+    path("api/", include("api.urls")),
+]
+"#;
+        assert_eq!(
+            routes("shop/urls.py", django),
+            vec![
+                route("ANY", "/users/<int:pk>/", Some("views.user_detail")),
+                route("ANY", "/teams/", Some("TeamView")),
+                route("ANY", r"/legacy/(?P<slug>[-\w]+)", Some("views.legacy")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spring_routes_with_class_prefix() {
+        let java = r#"
+@RestController
+@RequestMapping("/api/users")
+public class UserController {
+    @GetMapping("/{id}")
+    public User getUser(@PathVariable Long id) { return null; }
+
+    @PostMapping
+    @ResponseStatus(HttpStatus.CREATED)
+    public User createUser(@RequestBody User user) { return user; }
+
+    @RequestMapping(
+        value = "/search",
+        method = { RequestMethod.GET, RequestMethod.POST })
+    public List<User> search() { return List.of(); }
+}
+"#;
+        assert_eq!(
+            routes("src/main/java/UserController.java", java),
+            vec![
+                route("GET", "/api/users/{id}", Some("getUser")),
+                route("POST", "/api/users", Some("createUser")),
+                route("GET", "/api/users/search", Some("search")),
+                route("POST", "/api/users/search", Some("search")),
+            ]
+        );
+
+        let kotlin = r#"
+@RestController
+@RequestMapping("/orders")
+class OrderController {
+    @DeleteMapping("/{id}") fun cancel(@PathVariable id: Long) {}
+}
+"#;
+        assert_eq!(
+            routes("src/main/kotlin/OrderController.kt", kotlin),
+            vec![route("DELETE", "/orders/{id}", Some("cancel"))]
+        );
+    }
+
+    #[test]
+    fn test_aspnet_routes() {
+        let cs = r#"
+[ApiController]
+[Route("api/[controller]")]
+public class InvoicesController : ControllerBase
+{
+    [HttpGet("{id}")]
+    public IActionResult Get(int id) => Ok();
+
+    [HttpPost]
+    [Authorize]
+    public async Task<IActionResult> Create(Invoice invoice) => Ok();
+}
+
+app.MapGet("/health", HealthCheck);
+"#;
+        assert_eq!(
+            routes("Controllers/InvoicesController.cs", cs),
+            vec![
+                route("GET", "/api/Invoices/{id}", Some("Get")),
+                route("POST", "/api/Invoices", Some("Create")),
+                route("GET", "/health", Some("HealthCheck")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rails_routes() {
+        let rb = r#"
+Rails.application.routes.draw do
+  root "home#index"
+  get "/login", to: "sessions#new"
+  namespace :api do
+    resources :users, only: [:index, :show] do
+      resources :posts, only: :index
+      member do
+        post :lock
+      end
+    end
+    resource :profile, only: [:show]
+  end
+end
+"#;
+        assert_eq!(
+            routes("config/routes.rb", rb),
+            vec![
+                route("GET", "/", Some("home#index")),
+                route("GET", "/login", Some("sessions#new")),
+                route("GET", "/api/users", Some("api/users#index")),
+                route("GET", "/api/users/:id", Some("api/users#show")),
+                route("GET", "/api/users/:user_id/posts", Some("api/posts#index")),
+                route("POST", "/api/users/:id/lock", Some("api/users#lock")),
+                route("GET", "/api/profile", Some("api/profiles#show")),
+            ]
+        );
+        assert!(routes("app/models/user.rb", rb).is_empty());
+    }
+
+    #[test]
+    fn test_laravel_routes() {
+        let php = r#"<?php
+Route::get('/users', [UserController::class, 'index']);
+Route::post('/users', 'UserController@store');
+Route::prefix('admin')->group(function () {
+    Route::match(['get', 'post'], '/settings', [SettingsController::class, 'edit']);
+});
+Route::apiResource('photos', PhotoController::class);
+"#;
+        let found = routes("routes/api.php", php);
+        assert_eq!(
+            found[..4],
+            [
+                route("GET", "/api/users", Some("UserController@index")),
+                route("POST", "/api/users", Some("UserController@store")),
+                route(
+                    "GET",
+                    "/api/admin/settings",
+                    Some("SettingsController@edit")
+                ),
+                route(
+                    "POST",
+                    "/api/admin/settings",
+                    Some("SettingsController@edit")
+                ),
+            ]
+        );
+        assert!(found.contains(&route(
+            "GET",
+            "/api/photos/{photo}",
+            Some("PhotoController@show")
+        )));
+        assert!(found.contains(&route(
+            "DELETE",
+            "/api/photos/{photo}",
+            Some("PhotoController@destroy")
+        )));
+        assert!(!found.iter().any(|r| r.1.ends_with("/edit")));
+    }
+
+    #[test]
+    fn test_go_routes() {
+        let gin = r#"
+import "github.com/gin-gonic/gin"
+
+func Register(r *gin.Engine) {
+	api := r.Group("/api")
+	v1 := api.Group("/v1")
+	v1.GET("/users/:id", handlers.GetUser)
+	v1.POST("/users", authMiddleware(), createUser)
+}
+"#;
+        let endpoints = extract_endpoints("internal/http/routes.go", gin);
+        assert_eq!(endpoints[0].route, "/api/v1/users/:id");
+        assert_eq!(endpoints[0].handler.as_deref(), Some("handlers.GetUser"));
+        assert_eq!(endpoints[0].framework, FrameworkHint::Gin);
+        assert_eq!(endpoints[1].method, "POST");
+        assert_eq!(endpoints[1].handler.as_deref(), Some("createUser"));
+
+        let std = r#"
+mux.HandleFunc("GET /health", health)
+r.HandleFunc("/orders", orders).Methods("POST", "PUT")
+app.Get("/ping", ping)
+"#;
+        assert_eq!(
+            routes("main.go", std),
+            vec![
+                route("GET", "/ping", Some("ping")),
+                route("GET", "/health", Some("health")),
+                route("POST", "/orders", Some("orders")),
+                route("PUT", "/orders", Some("orders")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_axum_and_actix_routes() {
+        let axum = r#"
+use axum::{routing::get, Router};
+
+pub fn app() -> Router {
+    Router::new()
+        .route("/users", get(list_users).post(handlers::create_user))
+        .route("/users/:id", axum::routing::delete(delete_user))
+}
+"#;
+        let endpoints = extract_endpoints("src/routes.rs", axum);
+        assert_eq!(
+            routes("src/routes.rs", axum),
+            vec![
+                route("GET", "/users", Some("list_users")),
+                route("POST", "/users", Some("handlers::create_user")),
+                route("DELETE", "/users/:id", Some("delete_user")),
+            ]
+        );
+        assert_eq!(endpoints[0].framework, FrameworkHint::Axum);
+
+        let actix = r#"
+#[get("/items/{id}")]
+async fn get_item(path: web::Path<u32>) -> impl Responder { "" }
+
+cfg.service(web::resource("/orders").route(web::post().to(create_order)))
+   .route("/ping", web::get().to(ping));
+"#;
+        assert_eq!(
+            routes("src/main.rs", actix),
+            vec![
+                route("GET", "/items/{id}", Some("get_item")),
+                route("GET", "/ping", Some("ping")),
+                route("POST", "/orders", Some("create_order")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_route_helpers() {
+        assert_eq!(normalize_route("users//:id"), "/users/:id");
+        assert_eq!(join_route("/api/", "/v1/"), "/api/v1/");
+        assert_eq!(join_route("", ""), "/");
+        assert_eq!(
+            split_args("'/x', [A::class, 'b'], f(1, 2)"),
+            vec!["'/x'", "[A::class, 'b']", "f(1, 2)"]
+        );
+        assert_eq!(
+            handler_arg(&["wrap(createUser)"]).as_deref(),
+            Some("createUser")
+        );
+        assert_eq!(handler_arg(&["async (req) => {}"]), None);
+    }
+}
@@ -4,6 +4,9 @@
 //! - DOT (Graphviz) for detailed graph visualization
 //! - Mermaid for embedding in Markdown documentation
 //!
+//! Lossless formats for graph tools (GraphML, GEXF, Neo4j CSV, JSON-LD) are
+//! written by the streaming writers in [`super::export_stream`].
+//!
 //! ## Usage
 //!
 //! ```ignore
@@ -32,6 +35,24 @@ pub enum KgExportFormat {
     Mermaid,
     /// JSON format (nodes and edges as JSON)
     Json,
+    /// GraphML (yEd, Gephi, NetworkX)
+    GraphMl,
+    /// GEXF 1.3 (Gephi)
+    Gexf,
+    /// CSV tables for `neo4j-admin database import`
+    Neo4jCsv,
+    /// JSON-LD
+    JsonLd,
+}
+
+impl KgExportFormat {
+    /// Whether the format is written by a [`KgGraphWriter`](super::export_stream::KgGraphWriter).
+    pub fn is_streaming(&self) -> bool {
+        matches!(
+            self,
+            Self::GraphMl | Self::Gexf | Self::Neo4jCsv | Self::JsonLd
+        )
+    }
 }
 
 impl std::fmt::Display for KgExportFormat {
@@ -40,6 +61,10 @@ impl std::fmt::Display for KgExportFormat {
             Self::Dot => write!(f, "dot"),
             Self::Mermaid => write!(f, "mermaid"),
             Self::Json => write!(f, "json"),
+            Self::GraphMl => write!(f, "graphml"),
+            Self::Gexf => write!(f, "gexf"),
+            Self::Neo4jCsv => write!(f, "neo4j"),
+            Self::JsonLd => write!(f, "jsonld"),
         }
     }
 }
@@ -52,6 +77,10 @@ impl std::str::FromStr for KgExportFormat {
             "dot" | "graphviz" => Ok(Self::Dot),
             "mermaid" | "md" => Ok(Self::Mermaid),
             "json" => Ok(Self::Json),
+            "graphml" => Ok(Self::GraphMl),
+            "gexf" => Ok(Self::Gexf),
+            "neo4j" | "neo4j-csv" | "csv" => Ok(Self::Neo4jCsv),
+            "jsonld" | "json-ld" => Ok(Self::JsonLd),
             other => Err(format!("Unknown export format: {}", other)),
         }
     }
@@ -256,6 +285,11 @@ pub fn export_to_json(nodes: &[KgNode], edges: &[KgEdge], opts: KgExportOptions)
 }
 
 /// Export KG in the specified format.
+///
+/// Streaming formats are rendered into memory with the same node and edge
+/// caps. For [`KgExportFormat::Neo4jCsv`] the node table is followed by a
+/// blank line and the relationship table; use
+/// [`Neo4jCsvWriter`](super::export_stream::Neo4jCsvWriter) to get two files.
 pub fn export_kg(
     nodes: &[KgNode],
     edges: &[KgEdge],
@@ -266,9 +300,66 @@ pub fn export_kg(
         KgExportFormat::Dot => export_to_dot(nodes, edges, opts),
         KgExportFormat::Mermaid => export_to_mermaid(nodes, edges, opts),
         KgExportFormat::Json => export_to_json(nodes, edges, opts),
+        _ => export_streaming(nodes, edges, format, opts),
     }
 }
 
+/// Render a streaming format into a string, applying the export caps.
+fn export_streaming(
+    nodes: &[KgNode],
+    edges: &[KgEdge],
+    format: KgExportFormat,
+    opts: KgExportOptions,
+) -> String {
+    use super::export_stream::{
+        write_graph, GexfWriter, GraphMlWriter, JsonLdWriter, Neo4jCsvWriter,
+    };
+
+    let nodes = &nodes[..nodes.len().min(opts.max_nodes.unwrap_or(usize::MAX))];
+    let node_ids: std::collections::HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let edges: Vec<KgEdge> = edges
+        .iter()
+        .filter(|e| node_ids.contains(e.from.as_str()) && node_ids.contains(e.to.as_str()))
+        .take(opts.max_edges.unwrap_or(usize::MAX))
+        .cloned()
+        .collect();
+    let title = opts
+        .title
+        .unwrap_or_else(|| "GIK Knowledge Graph".to_string());
+
+    let mut buf = Vec::new();
+    let mut relationships = Vec::new();
+    // Writing into a Vec cannot fail
+    let _ = match format {
+        KgExportFormat::GraphMl => write_graph(
+            &mut GraphMlWriter::new(&mut buf).with_title(title),
+            nodes,
+            &edges,
+        ),
+        KgExportFormat::Gexf => write_graph(
+            &mut GexfWriter::new(&mut buf).with_title(title),
+            nodes,
+            &edges,
+        ),
+        KgExportFormat::JsonLd => write_graph(
+            &mut JsonLdWriter::new(&mut buf).with_title(title),
+            nodes,
+            &edges,
+        ),
+        _ => write_graph(
+            &mut Neo4jCsvWriter::new(&mut buf, &mut relationships),
+            nodes,
+            &edges,
+        ),
+    };
+    if !relationships.is_empty() {
+        buf.push(b'\n');
+        buf.extend(relationships);
+    }
+
+    String::from_utf8(buf).unwrap_or_default()
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -406,6 +497,41 @@ mod tests {
             "json".parse::<KgExportFormat>().unwrap(),
             KgExportFormat::Json
         );
+        assert_eq!(
+            "graphml".parse::<KgExportFormat>().unwrap(),
+            KgExportFormat::GraphMl
+        );
+        assert_eq!(
+            "json-ld".parse::<KgExportFormat>().unwrap(),
+            KgExportFormat::JsonLd
+        );
+        assert_eq!(
+            "neo4j".parse::<KgExportFormat>().unwrap(),
+            KgExportFormat::Neo4jCsv
+        );
+        assert!(KgExportFormat::Gexf.is_streaming());
+        assert!(!KgExportFormat::Dot.is_streaming());
+    }
+
+    #[test]
+    fn test_export_kg_streaming_formats_apply_caps() {
+        let nodes = sample_nodes();
+        let edges = sample_edges();
+
+        let graphml = export_kg(
+            &nodes,
+            &edges,
+            KgExportFormat::GraphMl,
+            KgExportOptions::default(),
+        );
+        assert!(graphml
+            .contains(r#"<edge id="edge-1" source="file:src/main.rs" target="file:src/lib.rs">"#));
+
+        let opts = KgExportOptions::new().with_max_nodes(1);
+        let csv = export_kg(&nodes, &edges, KgExportFormat::Neo4jCsv, opts);
+        assert!(csv.contains("\"file:src/main.rs\""));
+        assert!(!csv.contains("lib.rs"));
+        assert!(csv.contains("\n\n:START_ID,:END_ID,:TYPE"));
     }
 
     #[test]
@@ -0,0 +1,1231 @@
+//! Lossless streaming exporters for analysing the full KG in external tools.
+//!
+//! Unlike [`super::export`], which renders capped subgraphs into a `String`,
+//! these writers emit every node and edge with all props straight to an
+//! [`io::Write`]. [`write_graph_paged`] reads the graph from the store in
+//! pages, so a large export holds one page and the set of node IDs in memory,
+//! never the whole graph or the rendered output:
+//!
+//! - [`GraphMlWriter`] - GraphML for yEd, Gephi, NetworkX
+//! - [`GexfWriter`] - GEXF 1.3 for Gephi
+//! - [`Neo4jCsvWriter`] - `nodes.csv` / `relationships.csv` for `neo4j-admin database import`
+//! - [`JsonLdWriter`] - JSON-LD with a `urn:gik:kg#` vocabulary
+//!
+//! Props become typed attributes/columns. Each prop's type is inferred over
+//! the whole graph by [`KgPropSchema`], in a first read-only pass over the
+//! pages before anything is written: integers are `long`, other numbers
+//! `double`, booleans `boolean`, and everything else (including arrays,
+//! objects and props whose type varies) a string holding the JSON value.
+//! JSON-LD keeps props as native JSON values.
+//!
+//! Edges whose endpoints are not nodes of the graph are skipped, since
+//! neither Gephi nor `neo4j-admin` can import them.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use gik_core::kg::export_stream::{write_graph, GraphMlWriter};
+//!
+//! let mut writer = GraphMlWriter::new(std::io::stdout().lock());
+//! let counts = write_graph(&mut writer, &nodes, &edges)?;
+//!
+//! let store = open_kg_store(&workspace, "main")?;
+//! let counts = write_graph_paged(&mut writer, &store, EXPORT_PAGE_SIZE)?;
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Write};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use super::entities::{KgEdge, KgNode};
+use crate::db_adapter::DbKgStore;
+use crate::errors::GikError;
+
+/// Nodes or edges read from the store per page by [`write_graph_paged`].
+pub const EXPORT_PAGE_SIZE: usize = 10_000;
+
+// ============================================================================
+// Schema
+// ============================================================================
+
+/// Built-in node fields written alongside props.
+const NODE_FIELDS: &[&str] = &["kind", "label", "branch", "createdAt", "updatedAt"];
+
+/// Built-in edge fields written alongside props.
+const EDGE_FIELDS: &[&str] = &["kind", "branch", "createdAt", "updatedAt"];
+
+/// Attribute type of a prop, inferred over all its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropType {
+    Boolean,
+    Long,
+    Double,
+    String,
+}
+
+impl PropType {
+    /// Type of a single value, or None for null.
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(Self::Boolean),
+            Value::Number(n) if n.is_i64() || n.is_u64() => Some(Self::Long),
+            Value::Number(_) => Some(Self::Double),
+            _ => Some(Self::String),
+        }
+    }
+
+    /// Widen to cover both types.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Long, Self::Double) | (Self::Double, Self::Long) => Self::Double,
+            _ => Self::String,
+        }
+    }
+
+    /// Name used by GraphML `attr.type` and GEXF `type`.
+    fn xml_name(self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Long => "long",
+            Self::Double => "double",
+            Self::String => "string",
+        }
+    }
+}
+
+/// A prop column: the prop key and the attribute name it is exported as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropColumn {
+    /// Key in the node/edge `props` object.
+    pub key: String,
+    /// Exported name; props that clash with a built-in field get a `props.` prefix.
+    pub name: String,
+    pub prop_type: PropType,
+}
+
+/// Typed prop columns for nodes and edges, sorted by key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KgPropSchema {
+    pub node_props: Vec<PropColumn>,
+    pub edge_props: Vec<PropColumn>,
+}
+
+impl KgPropSchema {
+    /// Infer the schema from every node and edge.
+    pub fn from_graph<'a>(
+        nodes: impl IntoIterator<Item = &'a KgNode>,
+        edges: impl IntoIterator<Item = &'a KgEdge>,
+    ) -> Self {
+        let mut node_types = PropTypes::default();
+        nodes.into_iter().for_each(|n| node_types.add(&n.props));
+        let mut edge_types = PropTypes::default();
+        edges.into_iter().for_each(|e| edge_types.add(&e.props));
+        Self {
+            node_props: node_types.into_columns(NODE_FIELDS),
+            edge_props: edge_types.into_columns(EDGE_FIELDS),
+        }
+    }
+}
+
+/// Prop types accumulated one node or edge at a time.
+#[derive(Debug, Default)]
+struct PropTypes(BTreeMap<String, PropType>);
+
+impl PropTypes {
+    fn add(&mut self, props: &Value) {
+        for (key, value) in props.as_object().into_iter().flatten() {
+            if let Some(t) = PropType::of(value) {
+                match self.0.get_mut(key) {
+                    Some(existing) => *existing = existing.merge(t),
+                    None => {
+                        self.0.insert(key.clone(), t);
+                    }
+                }
+            }
+        }
+    }
+
+    fn into_columns(self, reserved: &[&str]) -> Vec<PropColumn> {
+        self.0
+            .into_iter()
+            .map(|(key, prop_type)| PropColumn {
+                name: if reserved.contains(&key.as_str()) {
+                    format!("props.{}", key)
+                } else {
+                    key.clone()
+                },
+                key,
+                prop_type,
+            })
+            .collect()
+    }
+}
+
+/// Text of a prop value for a typed column. Strings are written raw,
+/// arrays and objects as JSON.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Non-null prop values of `props` in column order.
+fn column_values<'a>(
+    props: &'a Value,
+    columns: &'a [PropColumn],
+) -> impl Iterator<Item = (usize, &'a Value)> + 'a {
+    columns
+        .iter()
+        .enumerate()
+        .filter_map(move |(i, col)| props.get(&col.key).filter(|v| !v.is_null()).map(|v| (i, v)))
+}
+
+// ============================================================================
+// Writer Trait
+// ============================================================================
+
+/// A streaming graph exporter.
+///
+/// Callers invoke [`begin`](Self::begin) once, then all
+/// [`write_node`](Self::write_node) calls, then all
+/// [`write_edge`](Self::write_edge) calls, then [`finish`](Self::finish).
+/// [`write_graph`] drives a writer in that order.
+pub trait KgGraphWriter {
+    /// Write the header. The schema must cover every node and edge written.
+    fn begin(&mut self, schema: &KgPropSchema) -> io::Result<()>;
+
+    /// Write one node.
+    fn write_node(&mut self, node: &KgNode) -> io::Result<()>;
+
+    /// Write one edge.
+    fn write_edge(&mut self, edge: &KgEdge) -> io::Result<()>;
+
+    /// Write the footer and flush.
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+/// Counts from writing a graph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KgWriteCounts {
+    pub nodes: usize,
+    pub edges: usize,
+    /// Edges dropped because an endpoint is not a node of the graph.
+    pub skipped_edges: usize,
+}
+
+/// Result of `gik kg export`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KgExportReport {
+    pub branch: String,
+    pub format: String,
+    pub nodes: usize,
+    pub edges: usize,
+    pub skipped_edges: usize,
+    /// Files written; empty when writing to stdout.
+    pub files: Vec<std::path::PathBuf>,
+}
+
+/// Write a whole graph: infer the schema, then stream nodes and edges.
+pub fn write_graph(
+    writer: &mut dyn KgGraphWriter,
+    nodes: &[KgNode],
+    edges: &[KgEdge],
+) -> io::Result<KgWriteCounts> {
+    let node_ids: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let is_linked =
+        |e: &KgEdge| node_ids.contains(e.from.as_str()) && node_ids.contains(e.to.as_str());
+
+    writer.begin(&KgPropSchema::from_graph(
+        nodes,
+        edges.iter().filter(|e| is_linked(e)),
+    ))?;
+
+    let mut counts = KgWriteCounts::default();
+    for node in nodes {
+        writer.write_node(node)?;
+        counts.nodes += 1;
+    }
+    for edge in edges {
+        if is_linked(edge) {
+            writer.write_edge(edge)?;
+            counts.edges += 1;
+        } else {
+            counts.skipped_edges += 1;
+        }
+    }
+    writer.finish()?;
+
+    Ok(counts)
+}
+
+/// A stored graph read in pages of nodes and edges.
+///
+/// Pages must come back in the same order on every read, since
+/// [`write_graph_paged`] reads the graph twice.
+pub trait KgPageSource {
+    /// Up to `limit` nodes, skipping the first `offset`.
+    fn node_page(&self, offset: usize, limit: usize) -> Result<Vec<KgNode>, GikError>;
+
+    /// Up to `limit` edges, skipping the first `offset`.
+    fn edge_page(&self, offset: usize, limit: usize) -> Result<Vec<KgEdge>, GikError>;
+}
+
+impl KgPageSource for DbKgStore {
+    fn node_page(&self, offset: usize, limit: usize) -> Result<Vec<KgNode>, GikError> {
+        self.get_nodes_page(offset, limit)
+    }
+
+    fn edge_page(&self, offset: usize, limit: usize) -> Result<Vec<KgEdge>, GikError> {
+        self.get_edges_page(offset, limit)
+    }
+}
+
+/// Call `visit` with each page of `read` until a short page.
+fn for_each_page<T>(
+    page_size: usize,
+    read: impl Fn(usize, usize) -> Result<Vec<T>, GikError>,
+    mut visit: impl FnMut(Vec<T>) -> Result<(), GikError>,
+) -> Result<(), GikError> {
+    let page_size = page_size.max(1);
+    let mut offset = 0;
+    loop {
+        let page = read(offset, page_size)?;
+        let len = page.len();
+        visit(page)?;
+        if len < page_size {
+            return Ok(());
+        }
+        offset += len;
+    }
+}
+
+/// Write a stored graph page by page.
+///
+/// A first read-only pass collects node IDs and infers the schema from every
+/// node and linked edge; the second pass streams the pages to `writer`.
+pub fn write_graph_paged(
+    writer: &mut dyn KgGraphWriter,
+    source: &dyn KgPageSource,
+    page_size: usize,
+) -> Result<KgWriteCounts, GikError> {
+    let mut node_ids: HashSet<String> = HashSet::new();
+    let mut node_types = PropTypes::default();
+    for_each_page(
+        page_size,
+        |offset, limit| source.node_page(offset, limit),
+        |nodes| {
+            for node in nodes {
+                node_types.add(&node.props);
+                node_ids.insert(node.id);
+            }
+            Ok(())
+        },
+    )?;
+    let is_linked =
+        |e: &KgEdge| node_ids.contains(e.from.as_str()) && node_ids.contains(e.to.as_str());
+
+    let mut edge_types = PropTypes::default();
+    for_each_page(
+        page_size,
+        |offset, limit| source.edge_page(offset, limit),
+        |edges| {
+            edges
+                .iter()
+                .filter(|e| is_linked(e))
+                .for_each(|e| edge_types.add(&e.props));
+            Ok(())
+        },
+    )?;
+
+    writer.begin(&KgPropSchema {
+        node_props: node_types.into_columns(NODE_FIELDS),
+        edge_props: edge_types.into_columns(EDGE_FIELDS),
+    })?;
+
+    let mut counts = KgWriteCounts::default();
+    for_each_page(
+        page_size,
+        |offset, limit| source.node_page(offset, limit),
+        |nodes| {
+            for node in &nodes {
+                writer.write_node(node)?;
+                counts.nodes += 1;
+            }
+            Ok(())
+        },
+    )?;
+    for_each_page(
+        page_size,
+        |offset, limit| source.edge_page(offset, limit),
+        |edges| {
+            for edge in &edges {
+                if is_linked(edge) {
+                    writer.write_edge(edge)?;
+                    counts.edges += 1;
+                } else {
+                    counts.skipped_edges += 1;
+                }
+            }
+            Ok(())
+        },
+    )?;
+    writer.finish()?;
+
+    Ok(counts)
+}
+
+// ============================================================================
+// GraphML
+// ============================================================================
+
+/// GraphML writer. Built-in fields and props become `<key>` declarations.
+pub struct GraphMlWriter<W: Write> {
+    out: W,
+    title: Option<String>,
+    schema: KgPropSchema,
+}
+
+impl<W: Write> GraphMlWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            title: None,
+            schema: KgPropSchema::default(),
+        }
+    }
+
+    /// Set the graph description.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl<W: Write> KgGraphWriter for GraphMlWriter<W> {
+    fn begin(&mut self, schema: &KgPropSchema) -> io::Result<()> {
+        self.schema = schema.clone();
+        writeln!(self.out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            self.out,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">"#
+        )?;
+
+        let keys = [
+            ("n", "node", NODE_FIELDS, &self.schema.node_props),
+            ("e", "edge", EDGE_FIELDS, &self.schema.edge_props),
+        ];
+        for (prefix, domain, fields, props) in keys {
+            for (i, field) in fields.iter().enumerate() {
+                writeln!(
+                    self.out,
+                    r#"  <key id="{}{}" for="{}" attr.name="{}" attr.type="string"/>"#,
+                    prefix, i, domain, field
+                )?;
+            }
+            for (i, col) in props.iter().enumerate() {
+                writeln!(
+                    self.out,
+                    r#"  <key id="{}{}" for="{}" attr.name="{}" attr.type="{}"/>"#,
+                    prefix,
+                    fields.len() + i,
+                    domain,
+                    escape_xml(&col.name),
+                    col.prop_type.xml_name()
+                )?;
+            }
+        }
+
+        writeln!(self.out, r#"  <graph id="G" edgedefault="directed">"#)?;
+        if let Some(title) = &self.title {
+            writeln!(self.out, "    <desc>{}</desc>", escape_xml(title))?;
+        }
+        Ok(())
+    }
+
+    fn write_node(&mut self, node: &KgNode) -> io::Result<()> {
+        writeln!(self.out, r#"    <node id="{}">"#, escape_xml(&node.id))?;
+        let fields = [
+            Some(node.kind.clone()),
+            Some(node.label.clone()),
+            node.branch.clone(),
+            Some(node.created_at.to_rfc3339()),
+            Some(node.updated_at.to_rfc3339()),
+        ];
+        for (i, value) in fields.iter().enumerate() {
+            if let Some(value) = value {
+                writeln!(
+                    self.out,
+                    r#"      <data key="n{}">{}</data>"#,
+                    i,
+                    escape_xml(value)
+                )?;
+            }
+        }
+        for (i, value) in column_values(&node.props, &self.schema.node_props) {
+            writeln!(
+                self.out,
+                r#"      <data key="n{}">{}</data>"#,
+                NODE_FIELDS.len() + i,
+                escape_xml(&value_text(value))
+            )?;
+        }
+        writeln!(self.out, "    </node>")
+    }
+
+    fn write_edge(&mut self, edge: &KgEdge) -> io::Result<()> {
+        writeln!(
+            self.out,
+            r#"    <edge id="{}" source="{}" target="{}">"#,
+            escape_xml(&edge.id),
+            escape_xml(&edge.from),
+            escape_xml(&edge.to)
+        )?;
+        let fields = [
+            Some(edge.kind.clone()),
+            edge.branch.clone(),
+            Some(edge.created_at.to_rfc3339()),
+            Some(edge.updated_at.to_rfc3339()),
+        ];
+        for (i, value) in fields.iter().enumerate() {
+            if let Some(value) = value {
+                writeln!(
+                    self.out,
+                    r#"      <data key="e{}">{}</data>"#,
+                    i,
+                    escape_xml(value)
+                )?;
+            }
+        }
+        for (i, value) in column_values(&edge.props, &self.schema.edge_props) {
+            writeln!(
+                self.out,
+                r#"      <data key="e{}">{}</data>"#,
+                EDGE_FIELDS.len() + i,
+                escape_xml(&value_text(value))
+            )?;
+        }
+        writeln!(self.out, "    </edge>")
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        writeln!(self.out, "  </graph>")?;
+        writeln!(self.out, "</graphml>")?;
+        self.out.flush()
+    }
+}
+
+// ============================================================================
+// GEXF
+// ============================================================================
+
+/// GEXF 1.3 writer. Node labels and edge kinds map to GEXF `label`s; the
+/// remaining fields and props become attributes.
+pub struct GexfWriter<W: Write> {
+    out: W,
+    title: Option<String>,
+    schema: KgPropSchema,
+    in_edges: bool,
+}
+
+impl<W: Write> GexfWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            title: None,
+            schema: KgPropSchema::default(),
+            in_edges: false,
+        }
+    }
+
+    /// Set the graph description.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl<W: Write> KgGraphWriter for GexfWriter<W> {
+    fn begin(&mut self, schema: &KgPropSchema) -> io::Result<()> {
+        self.schema = schema.clone();
+        writeln!(self.out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            self.out,
+            r#"<gexf xmlns="http://gexf.net/1.3" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://gexf.net/1.3 http://gexf.net/1.3/gexf.xsd" version="1.3">"#
+        )?;
+        writeln!(self.out, "  <meta>")?;
+        writeln!(self.out, "    <creator>gik</creator>")?;
+        if let Some(title) = &self.title {
+            writeln!(
+                self.out,
+                "    <description>{}</description>",
+                escape_xml(title)
+            )?;
+        }
+        writeln!(self.out, "  </meta>")?;
+        writeln!(
+            self.out,
+            r#"  <graph defaultedgetype="directed" mode="static">"#
+        )?;
+
+        // `label` is a native node attribute, and `kind` a native edge label
+        let classes = [
+            (
+                "node",
+                &["kind", "branch", "createdAt", "updatedAt"][..],
+                &self.schema.node_props,
+            ),
+            (
+                "edge",
+                &["branch", "createdAt", "updatedAt"][..],
+                &self.schema.edge_props,
+            ),
+        ];
+        for (class, fields, props) in classes {
+            writeln!(self.out, r#"    <attributes class="{}">"#, class)?;
+            for (i, field) in fields.iter().enumerate() {
+                writeln!(
+                    self.out,
+                    r#"      <attribute id="{}" title="{}" type="string"/>"#,
+                    i, field
+                )?;
+            }
+            for (i, col) in props.iter().enumerate() {
+                writeln!(
+                    self.out,
+                    r#"      <attribute id="{}" title="{}" type="{}"/>"#,
+                    fields.len() + i,
+                    escape_xml(&col.name),
+                    col.prop_type.xml_name()
+                )?;
+            }
+            writeln!(self.out, "    </attributes>")?;
+        }
+        writeln!(self.out, "    <nodes>")
+    }
+
+    fn write_node(&mut self, node: &KgNode) -> io::Result<()> {
+        writeln!(
+            self.out,
+            r#"      <node id="{}" label="{}">"#,
+            escape_xml(&node.id),
+            escape_xml(&node.label)
+        )?;
+        let fields = [
+            Some(node.kind.clone()),
+            node.branch.clone(),
+            Some(node.created_at.to_rfc3339()),
+            Some(node.updated_at.to_rfc3339()),
+        ];
+        write_attvalues(&mut self.out, &fields, &node.props, &self.schema.node_props)?;
+        writeln!(self.out, "      </node>")
+    }
+
+    fn write_edge(&mut self, edge: &KgEdge) -> io::Result<()> {
+        if !self.in_edges {
+            writeln!(self.out, "    </nodes>")?;
+            writeln!(self.out, "    <edges>")?;
+            self.in_edges = true;
+        }
+        writeln!(
+            self.out,
+            r#"      <edge id="{}" source="{}" target="{}" label="{}">"#,
+            escape_xml(&edge.id),
+            escape_xml(&edge.from),
+            escape_xml(&edge.to),
+            escape_xml(&edge.kind)
+        )?;
+        let fields = [
+            edge.branch.clone(),
+            Some(edge.created_at.to_rfc3339()),
+            Some(edge.updated_at.to_rfc3339()),
+        ];
+        write_attvalues(&mut self.out, &fields, &edge.props, &self.schema.edge_props)?;
+        writeln!(self.out, "      </edge>")
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if self.in_edges {
+            writeln!(self.out, "    </edges>")?;
+        } else {
+            writeln!(self.out, "    </nodes>")?;
+        }
+        writeln!(self.out, "  </graph>")?;
+        writeln!(self.out, "</gexf>")?;
+        self.out.flush()
+    }
+}
+
+/// Write built-in fields, then props, as `<attvalues>`.
+fn write_attvalues(
+    out: &mut impl Write,
+    fields: &[Option<String>],
+    props: &Value,
+    columns: &[PropColumn],
+) -> io::Result<()> {
+    writeln!(out, "        <attvalues>")?;
+    for (i, value) in fields.iter().enumerate() {
+        if let Some(value) = value {
+            writeln!(
+                out,
+                r#"          <attvalue for="{}" value="{}"/>"#,
+                i,
+                escape_xml(value)
+            )?;
+        }
+    }
+    for (i, value) in column_values(props, columns) {
+        writeln!(
+            out,
+            r#"          <attvalue for="{}" value="{}"/>"#,
+            fields.len() + i,
+            escape_xml(&value_text(value))
+        )?;
+    }
+    writeln!(out, "        </attvalues>")
+}
+
+// ============================================================================
+// Neo4j CSV
+// ============================================================================
+
+/// File name of the Neo4j node table.
+pub const NEO4J_NODES_FILENAME: &str = "nodes.csv";
+
+/// File name of the Neo4j relationship table.
+pub const NEO4J_RELATIONSHIPS_FILENAME: &str = "relationships.csv";
+
+/// Writer for `neo4j-admin database import full` CSV tables.
+///
+/// Nodes get the labels `KgNode` and their kind in PascalCase (`file` →
+/// `File`); relationship types are the edge kind in UPPER_SNAKE_CASE
+/// (`definesEndpoint` → `DEFINES_ENDPOINT`). Headers carry the prop types
+/// (`count:long`). Values may contain newlines, so import with
+/// `--multiline-fields=true`.
+pub struct Neo4jCsvWriter<N: Write, R: Write> {
+    nodes: N,
+    relationships: R,
+    schema: KgPropSchema,
+}
+
+impl<N: Write, R: Write> Neo4jCsvWriter<N, R> {
+    pub fn new(nodes: N, relationships: R) -> Self {
+        Self {
+            nodes,
+            relationships,
+            schema: KgPropSchema::default(),
+        }
+    }
+}
+
+impl<N: Write, R: Write> KgGraphWriter for Neo4jCsvWriter<N, R> {
+    fn begin(&mut self, schema: &KgPropSchema) -> io::Result<()> {
+        self.schema = schema.clone();
+
+        let mut header: Vec<String> =
+            ["id:ID", "label", "kind", "branch", "createdAt", "updatedAt"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+        header.extend(self.schema.node_props.iter().map(neo4j_column));
+        header.push(":LABEL".to_string());
+        write_csv_row(&mut self.nodes, &header)?;
+
+        let mut header: Vec<String> = [
+            ":START_ID",
+            ":END_ID",
+            ":TYPE",
+            "id",
+            "branch",
+            "createdAt",
+            "updatedAt",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        header.extend(self.schema.edge_props.iter().map(neo4j_column));
+        write_csv_row(&mut self.relationships, &header)
+    }
+
+    fn write_node(&mut self, node: &KgNode) -> io::Result<()> {
+        let mut row = vec![
+            csv_string(&node.id),
+            csv_string(&node.label),
+            csv_string(&node.kind),
+            node.branch.as_deref().map(csv_string).unwrap_or_default(),
+            node.created_at.to_rfc3339(),
+            node.updated_at.to_rfc3339(),
+        ];
+        row.extend(csv_props(&node.props, &self.schema.node_props));
+        row.push(format!("KgNode;{}", pascal_case(&node.kind)));
+        write_csv_row(&mut self.nodes, &row)
+    }
+
+    fn write_edge(&mut self, edge: &KgEdge) -> io::Result<()> {
+        let mut row = vec![
+            csv_string(&edge.from),
+            csv_string(&edge.to),
+            upper_snake_case(&edge.kind),
+            csv_string(&edge.id),
+            edge.branch.as_deref().map(csv_string).unwrap_or_default(),
+            edge.created_at.to_rfc3339(),
+            edge.updated_at.to_rfc3339(),
+        ];
+        row.extend(csv_props(&edge.props, &self.schema.edge_props));
+        write_csv_row(&mut self.relationships, &row)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.nodes.flush()?;
+        self.relationships.flush()
+    }
+}
+
+/// Header entry for a prop column: `name` for strings, `name:type` otherwise.
+fn neo4j_column(col: &PropColumn) -> String {
+    match col.prop_type {
+        PropType::String => escape_csv(&col.name),
+        other => escape_csv(&format!("{}:{}", col.name, other.xml_name())),
+    }
+}
+
+/// Prop cells in column order. Missing values are empty (null); strings are
+/// always quoted so an empty string stays distinct from null.
+fn csv_props(props: &Value, columns: &[PropColumn]) -> Vec<String> {
+    columns
+        .iter()
+        .map(|col| match props.get(&col.key) {
+            None | Some(Value::Null) => String::new(),
+            Some(value) if col.prop_type == PropType::String => csv_string(&value_text(value)),
+            Some(value) => value_text(value),
+        })
+        .collect()
+}
+
+fn write_csv_row(out: &mut impl Write, cells: &[String]) -> io::Result<()> {
+    writeln!(out, "{}", cells.join(","))
+}
+
+/// Always-quoted CSV string cell.
+fn csv_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+/// Quote a CSV cell only when needed.
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        csv_string(s)
+    } else {
+        s.to_string()
+    }
+}
+
+/// `uiComponent` → `UiComponent`.
+fn pascal_case(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// `definesEndpoint` → `DEFINES_ENDPOINT`.
+fn upper_snake_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 4);
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if c.is_uppercase() && prev_lower {
+            out.push('_');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        if c.is_alphanumeric() {
+            out.extend(c.to_uppercase());
+        } else {
+            out.push('_');
+        }
+    }
+    out
+}
+
+// ============================================================================
+// JSON-LD
+// ============================================================================
+
+/// Vocabulary IRI for kinds and props.
+pub const JSONLD_VOCAB: &str = "urn:gik:kg#";
+
+/// JSON-LD writer. Nodes are typed by kind; edges are reified as `Edge`
+/// resources with `from` / `to` links, so edge props survive.
+pub struct JsonLdWriter<W: Write> {
+    out: W,
+    title: Option<String>,
+    first: bool,
+}
+
+impl<W: Write> JsonLdWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            title: None,
+            first: true,
+        }
+    }
+
+    /// Set the graph description.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    fn write_item(&mut self, item: &Value) -> io::Result<()> {
+        let sep = if self.first { "" } else { ",\n" };
+        self.first = false;
+        write!(self.out, "{}    {}", sep, item)
+    }
+}
+
+impl<W: Write> KgGraphWriter for JsonLdWriter<W> {
+    fn begin(&mut self, _schema: &KgPropSchema) -> io::Result<()> {
+        let context = json!({
+            "@vocab": JSONLD_VOCAB,
+            "id": "@id",
+            "type": "@type",
+            "from": { "@type": "@id" },
+            "to": { "@type": "@id" },
+            "createdAt": { "@type": "http://www.w3.org/2001/XMLSchema#dateTime" },
+            "updatedAt": { "@type": "http://www.w3.org/2001/XMLSchema#dateTime" },
+        });
+        writeln!(self.out, "{{")?;
+        writeln!(self.out, "  \"@context\": {},", context)?;
+        if let Some(title) = &self.title {
+            writeln!(self.out, "  \"description\": {},", json!(title))?;
+        }
+        writeln!(self.out, "  \"@graph\": [")
+    }
+
+    fn write_node(&mut self, node: &KgNode) -> io::Result<()> {
+        let mut item = Map::new();
+        item.insert("id".into(), json!(node_iri(&node.id)));
+        item.insert("type".into(), json!(node.kind));
+        item.insert("label".into(), json!(node.label));
+        if let Some(branch) = &node.branch {
+            item.insert("branch".into(), json!(branch));
+        }
+        item.insert("createdAt".into(), json!(node.created_at.to_rfc3339()));
+        item.insert("updatedAt".into(), json!(node.updated_at.to_rfc3339()));
+        insert_props(&mut item, &node.props);
+        self.write_item(&Value::Object(item))
+    }
+
+    fn write_edge(&mut self, edge: &KgEdge) -> io::Result<()> {
+        let mut item = Map::new();
+        item.insert(
+            "id".into(),
+            json!(format!("urn:gik:edge:{}", encode_iri(&edge.id))),
+        );
+        item.insert("type".into(), json!("Edge"));
+        item.insert("kind".into(), json!(edge.kind));
+        item.insert("from".into(), json!(node_iri(&edge.from)));
+        item.insert("to".into(), json!(node_iri(&edge.to)));
+        if let Some(branch) = &edge.branch {
+            item.insert("branch".into(), json!(branch));
+        }
+        item.insert("createdAt".into(), json!(edge.created_at.to_rfc3339()));
+        item.insert("updatedAt".into(), json!(edge.updated_at.to_rfc3339()));
+        insert_props(&mut item, &edge.props);
+        self.write_item(&Value::Object(item))
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if !self.first {
+            writeln!(self.out)?;
+        }
+        writeln!(self.out, "  ]")?;
+        writeln!(self.out, "}}")?;
+        self.out.flush()
+    }
+}
+
+/// Add props, prefixing keys that clash with built-in or JSON-LD keys.
+fn insert_props(item: &mut Map<String, Value>, props: &Value) {
+    let Some(obj) = props.as_object() else {
+        return;
+    };
+    for (key, value) in obj {
+        if value.is_null() {
+            continue;
+        }
+        let key = if item.contains_key(key) || key.starts_with('@') || key == "from" || key == "to"
+        {
+            format!("props.{}", key)
+        } else {
+            key.clone()
+        };
+        item.insert(key, value.clone());
+    }
+}
+
+fn node_iri(id: &str) -> String {
+    format!("urn:gik:node:{}", encode_iri(id))
+}
+
+/// Percent-encode characters not allowed in an IRI path, plus `#`, `?` and `%`.
+fn encode_iri(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_control()
+            || c.is_whitespace()
+            || matches!(
+                c,
+                '#' | '?' | '%' | '<' | '>' | '"' | '{' | '}' | '|' | '\\' | '^' | '`'
+            )
+        {
+            let mut buf = [0; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                out.push_str(&format!("%{:02X}", b));
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Escape text for XML content and attributes, dropping characters XML 1.0
+/// cannot represent.
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if (c as u32) < 0x20 || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> (Vec<KgNode>, Vec<KgEdge>) {
+        let nodes = vec![
+            KgNode::new("file:src/main.rs", "file", "main.rs")
+                .with_props(json!({ "path": "src/main.rs", "centrality": 0.5 }))
+                .with_branch("main"),
+            KgNode::new("sym:rs:src/lib.rs:function:run#2", "function", "run <T>")
+                .with_props(
+                    json!({ "startLine": 3, "centrality": 1, "label": "dup", "tags": ["a", "b"] }),
+                )
+                .with_branch("main"),
+        ];
+        let edges = vec![
+            KgEdge::new(
+                "file:src/main.rs",
+                "sym:rs:src/lib.rs:function:run#2",
+                "definesEndpoint",
+            )
+            .with_props(json!({ "count": 2, "note": "a \"quoted\", value" }))
+            .with_branch("main"),
+            KgEdge::new("file:src/main.rs", "external:rs:println", "calls"),
+        ];
+        (nodes, edges)
+    }
+
+    /// Serves [`sample_graph`] in pages.
+    struct SamplePages(Vec<KgNode>, Vec<KgEdge>);
+
+    impl KgPageSource for SamplePages {
+        fn node_page(&self, offset: usize, limit: usize) -> Result<Vec<KgNode>, GikError> {
+            Ok(self.0.iter().skip(offset).take(limit).cloned().collect())
+        }
+
+        fn edge_page(&self, offset: usize, limit: usize) -> Result<Vec<KgEdge>, GikError> {
+            Ok(self.1.iter().skip(offset).take(limit).cloned().collect())
+        }
+    }
+
+    #[test]
+    fn test_paged_export_matches_in_memory_export() {
+        // Both exports read the same graph, so timestamps match
+        let (nodes, edges) = sample_graph();
+        let mut buf = Vec::new();
+        let counts = write_graph(&mut GraphMlWriter::new(&mut buf), &nodes, &edges).unwrap();
+        let in_memory = String::from_utf8(buf).unwrap();
+
+        let pages = SamplePages(nodes, edges);
+        for page_size in [1, 2, EXPORT_PAGE_SIZE] {
+            let mut buf = Vec::new();
+            let paged_counts =
+                write_graph_paged(&mut GraphMlWriter::new(&mut buf), &pages, page_size).unwrap();
+            assert_eq!(paged_counts, counts);
+            assert_eq!(String::from_utf8(buf).unwrap(), in_memory);
+        }
+    }
+
+    fn render(
+        writer: impl FnOnce(&mut Vec<u8>) -> Box<dyn KgGraphWriter + '_>,
+    ) -> (String, KgWriteCounts) {
+        let (nodes, edges) = sample_graph();
+        let mut buf = Vec::new();
+        let counts = {
+            let mut w = writer(&mut buf);
+            write_graph(w.as_mut(), &nodes, &edges).unwrap()
+        };
+        (String::from_utf8(buf).unwrap(), counts)
+    }
+
+    #[test]
+    fn test_schema_infers_and_widens_types() {
+        let (nodes, edges) = sample_graph();
+        let schema = KgPropSchema::from_graph(&nodes, &edges);
+
+        let node_types: Vec<_> = schema
+            .node_props
+            .iter()
+            .map(|c| (c.name.as_str(), c.prop_type))
+            .collect();
+        assert_eq!(
+            node_types,
+            vec![
+                ("centrality", PropType::Double),
+                ("props.label", PropType::String),
+                ("path", PropType::String),
+                ("startLine", PropType::Long),
+                ("tags", PropType::String),
+            ]
+        );
+        assert_eq!(schema.edge_props[0].prop_type, PropType::Long);
+    }
+
+    #[test]
+    fn test_graphml_writer() {
+        let (xml, counts) = render(|buf| Box::new(GraphMlWriter::new(buf).with_title("KG")));
+        assert_eq!(
+            counts,
+            KgWriteCounts {
+                nodes: 2,
+                edges: 1,
+                skipped_edges: 1
+            }
+        );
+
+        assert!(
+            xml.contains(r#"<key id="n5" for="node" attr.name="centrality" attr.type="double"/>"#)
+        );
+        assert!(xml.contains(r#"<key id="e4" for="edge" attr.name="count" attr.type="long"/>"#));
+        assert!(xml.contains(r#"<node id="sym:rs:src/lib.rs:function:run#2">"#));
+        assert!(xml.contains(r#"<data key="n1">run &lt;T&gt;</data>"#));
+        assert!(xml.contains(r#"<data key="n9">[&quot;a&quot;,&quot;b&quot;]</data>"#));
+        assert!(!xml.contains("external:rs:println"));
+        assert!(xml.trim_end().ends_with("</graphml>"));
+    }
+
+    #[test]
+    fn test_gexf_writer() {
+        let (xml, _) = render(|buf| Box::new(GexfWriter::new(buf)));
+
+        assert!(xml.contains(r#"<attribute id="4" title="centrality" type="double"/>"#));
+        assert!(xml.contains(r#"<node id="file:src/main.rs" label="main.rs">"#));
+        assert!(xml.contains(r#"label="definesEndpoint">"#));
+        assert!(xml.contains(r#"<attvalue for="3" value="2"/>"#));
+        let nodes_end = xml.find("</nodes>").unwrap();
+        assert!(nodes_end < xml.find("<edges>").unwrap());
+
+        // A graph without edges still closes its node list
+        let mut buf = Vec::new();
+        let mut writer = GexfWriter::new(&mut buf);
+        write_graph(&mut writer, &sample_graph().0, &[]).unwrap();
+        let xml = String::from_utf8(buf).unwrap();
+        assert!(xml.contains("</nodes>") && !xml.contains("<edges>"));
+    }
+
+    #[test]
+    fn test_neo4j_csv_writer() {
+        let (nodes, edges) = sample_graph();
+        let (mut node_buf, mut rel_buf) = (Vec::new(), Vec::new());
+        let mut writer = Neo4jCsvWriter::new(&mut node_buf, &mut rel_buf);
+        write_graph(&mut writer, &nodes, &edges).unwrap();
+
+        let nodes_csv = String::from_utf8(node_buf).unwrap();
+        let mut lines = nodes_csv.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "id:ID,label,kind,branch,createdAt,updatedAt,centrality:double,props.label,path,startLine:long,tags,:LABEL"
+        );
+        let main = lines.next().unwrap();
+        assert!(main.starts_with(r#""file:src/main.rs","main.rs","file","main","#));
+        assert!(main.ends_with(r#",0.5,,"src/main.rs",,,KgNode;File"#));
+        assert!(lines
+            .next()
+            .unwrap()
+            .ends_with(r#","dup",,3,"[""a"",""b""]",KgNode;Function"#));
+
+        let rels = String::from_utf8(rel_buf).unwrap();
+        let mut lines = rels.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            ":START_ID,:END_ID,:TYPE,id,branch,createdAt,updatedAt,count:long,note"
+        );
+        let edge = lines.next().unwrap();
+        assert!(edge.contains(",DEFINES_ENDPOINT,"));
+        assert!(edge.ends_with(r#",2,"a ""quoted"", value""#));
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn test_jsonld_writer() {
+        let (text, _) = render(|buf| Box::new(JsonLdWriter::new(buf).with_title("KG")));
+        let doc: Value = serde_json::from_str(&text).expect("valid JSON");
+
+        assert_eq!(doc["@context"]["@vocab"], JSONLD_VOCAB);
+        assert_eq!(doc["description"], "KG");
+        let graph = doc["@graph"].as_array().unwrap();
+        assert_eq!(graph.len(), 3);
+        assert_eq!(
+            graph[1]["id"],
+            "urn:gik:node:sym:rs:src/lib.rs:function:run%232"
+        );
+        assert_eq!(graph[1]["type"], "function");
+        assert_eq!(graph[1]["props.label"], "dup");
+        assert_eq!(graph[1]["tags"], json!(["a", "b"]));
+        assert_eq!(graph[2]["type"], "Edge");
+        assert_eq!(graph[2]["kind"], "definesEndpoint");
+        assert_eq!(graph[2]["to"], graph[1]["id"]);
+        assert_eq!(graph[2]["count"], 2);
+
+        // Empty graphs stay valid
+        let mut buf = Vec::new();
+        write_graph(&mut JsonLdWriter::new(&mut buf), &[], &[]).unwrap();
+        let doc: Value = serde_json::from_slice(&buf).expect("valid JSON");
+        assert!(doc["@graph"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_case_conversions_and_escaping() {
+        assert_eq!(pascal_case("uiComponent"), "UiComponent");
+        assert_eq!(upper_snake_case("definesEndpoint"), "DEFINES_ENDPOINT");
+        assert_eq!(upper_snake_case("imports"), "IMPORTS");
+        assert_eq!(escape_xml("a & b\u{1}"), "a &amp; b");
+        assert_eq!(encode_iri("doc:My File.md"), "doc:My%20File.md");
+    }
+}
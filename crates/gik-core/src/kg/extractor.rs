@@ -26,9 +26,15 @@
 //!   - `pages/api/**.ts` (Pages Router)
 //!
 //! **NOT in scope**:
-//! - Full call-graph extraction (deferred to 9.4+)
 //! - Incremental extraction (full rebuild per sync)
 //!
+//! ## Call Graph
+//!
+//! `calls` edges run from the calling symbol (or file, for top-level code) to
+//! the called function or method. Callees are looked up by name in the calling
+//! file first, then in the files it imports; anything else points at a shared
+//! `external:<lang>:<name>` node. Edges carry the call-site `lines`.
+//!
 //! ## Import Detection
 //!
 //! Best-effort regex-based heuristics for common languages:
@@ -36,6 +42,11 @@
 //! - **Rust**: `use crate::...`, `mod ...`
 //! - **Python**: `import ...`, `from ... import ...`
 //!
+//! These resolve as relative paths. Go, Java/Kotlin, C#, PHP, Ruby and C/C++
+//! imports go through [`super::imports::ImportResolver`], which uses `go.mod`,
+//! package and namespace declarations, `composer.json` PSR-4 prefixes and
+//! include search paths. A package import can produce several file edges.
+//!
 //! ## Endpoint Detection (Phase 9.3)
 //!
 //! Best-effort detection for Next.js route files:
@@ -43,12 +54,60 @@
 //! - **Pages Router**: Files matching `pages/api/**.{ts,tsx,js,jsx}`
 //! - **HTTP Methods**: Extracted from exported handler functions (`GET`, `POST`, etc.)
 //!
-//! ## Future Extensions (TODO)
+//! Other frameworks (Express, Fastify, Flask, FastAPI, Django, Spring, ASP.NET,
+//! Rails, Laravel, Gin, Fiber, axum, actix) declare routes in source and are
+//! detected by [`super::endpoints`]. Endpoints with a named handler get a
+//! `handledBy` edge to the handler's function or method symbol.
+//!
+//! Endpoint ids follow `endpoint:<normalizedFilePath>:<METHOD>:<route>`, so the
+//! same route defined in two files (e.g. two services) yields two nodes.
+//!
+//! ## API Schemas
+//!
+//! OpenAPI documents, GraphQL SDL and `.proto` files in any base are parsed by
+//! [`super::schema`] into `operation`, `type`, `message` and `field` nodes.
+//! After all bases are extracted, operations get `implementedBy` edges to the
+//! endpoints and symbols that serve them.
+//!
+//! ## Infrastructure
+//!
+//! Dockerfiles, Compose files, Kubernetes manifests and Terraform are parsed
+//! by [`super::infra`] into `service`, `image` and `resource` nodes with
+//! `configures`, `dependsOn` and `exposes` edges. Kubernetes Service selectors
+//! are matched to workloads after all bases are extracted.
+//!
+//! ## Doc Mentions
+//!
+//! Markdown docs get `mentions` edges to the code files and symbols they link
+//! to, name in inline code, or call in fenced snippets (see [`super::mentions`]).
+//! Edges carry the enclosing heading as `section`.
+//!
+//! ## Packages
+//!
+//! Dependencies recorded by `gik stack` become `package` nodes with
+//! `declaresDependency` edges from their manifests. Imports that don't resolve
+//! to a workspace file but name a declared package get a `usesPackage` edge
+//! (see [`super::packages`]).
+//!
+//! ## Precise Indexes
+//!
+//! A SCIP index imported with `gik kg import --scip` is merged over the
+//! heuristic symbols of the files it covers, adding exact ranges, hover docs
+//! and `references` / `implements` edges (see [`super::scip`]).
+//!
+//! ## Ownership
+//!
+//! `CODEOWNERS` owners and the primary Git authors of each file become
+//! `owner` nodes with `owns` edges to file and doc nodes (see
+//! [`super::owners`]). Without Git, only `CODEOWNERS` is used.
+//!
+//! ## Test Links
 //!
-//! - Phase 9.4+: Full call-graph extraction with function→function edges
-//! - Phase 9.4+: Framework-specific endpoint detection (Express, FastAPI, Rails, etc.)
+//! Test files are marked `isTest` by language convention and get `tests`
+//! edges to the files and symbols they exercise, found through imports,
+//! matching file names and symbol references (see [`super::test_links`]).
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use regex::Regex;
@@ -56,9 +115,21 @@ use serde::{Deserialize, Serialize};
 
 use crate::base::{load_base_sources, sources_path, BaseSourceEntry};
 use crate::errors::GikError;
+use crate::git::read_path_history;
+use crate::stack::read_dependencies_jsonl;
 use crate::workspace::Workspace;
 
+use super::endpoints::{extract_endpoints, EndpointCandidate};
 use super::entities::{KgEdge, KgNode};
+use super::imports::{ImportKind, ImportRef, ImportResolver};
+use super::infra::{extract_infra, link_infra};
+use super::lang::{FrameworkHint, KgRelationCandidate};
+use super::mentions::{extract_mentions, MentionIndex};
+use super::owners::{build_owner_graph, primary_authors, read_codeowners, AUTHOR_HISTORY_LIMIT};
+use super::packages::{build_package_graph, extract_rust_crate_roots, PackageIndex};
+use super::schema::{extract_schema, link_schema_operations};
+use super::scip::{merge_precise_index, read_precise_index, scip_snapshot_path};
+use super::test_links::link_tests;
 
 // ============================================================================
 // Constants
@@ -106,6 +177,41 @@ pub struct KgExtractionConfig {
     /// Default: true (Phase 9.2.1)
     #[serde(default = "default_extract_symbols")]
     pub extract_symbols: bool,
+
+    /// Whether to extract operations and types from OpenAPI, GraphQL and
+    /// Protobuf schema files. Default: true
+    #[serde(default = "default_extract_schemas")]
+    pub extract_schemas: bool,
+
+    /// Whether to extract services, images and resources from Dockerfiles,
+    /// Compose files, Kubernetes manifests and Terraform. Default: true
+    #[serde(default = "default_extract_infra")]
+    pub extract_infra: bool,
+
+    /// Whether to link docs to the code files and symbols they mention.
+    /// Default: true
+    #[serde(default = "default_extract_mentions")]
+    pub extract_mentions: bool,
+
+    /// Whether to add package nodes from the stack dependency inventory,
+    /// with `declaresDependency` and `usesPackage` edges. Default: true
+    #[serde(default = "default_extract_packages")]
+    pub extract_packages: bool,
+
+    /// Whether to merge an imported SCIP index (`gik kg import --scip`) over
+    /// the heuristic symbols. Default: true
+    #[serde(default = "default_extract_precise")]
+    pub extract_precise: bool,
+
+    /// Whether to add owner nodes from `CODEOWNERS` and Git history, with
+    /// `owns` edges to files. Default: true
+    #[serde(default = "default_extract_owners")]
+    pub extract_owners: bool,
+
+    /// Whether to mark test files and add `tests` edges to the code they
+    /// exercise. Default: true
+    #[serde(default = "default_extract_tests")]
+    pub extract_tests: bool,
 }
 
 fn default_enabled_bases() -> Vec<String> {
@@ -124,6 +230,34 @@ fn default_extract_symbols() -> bool {
     true
 }
 
+fn default_extract_schemas() -> bool {
+    true
+}
+
+fn default_extract_infra() -> bool {
+    true
+}
+
+fn default_extract_mentions() -> bool {
+    true
+}
+
+fn default_extract_packages() -> bool {
+    true
+}
+
+fn default_extract_precise() -> bool {
+    true
+}
+
+fn default_extract_owners() -> bool {
+    true
+}
+
+fn default_extract_tests() -> bool {
+    true
+}
+
 impl Default for KgExtractionConfig {
     fn default() -> Self {
         Self {
@@ -134,6 +268,13 @@ impl Default for KgExtractionConfig {
             include_docs: true,
             extract_endpoints: true,
             extract_symbols: true,
+            extract_schemas: true,
+            extract_infra: true,
+            extract_mentions: true,
+            extract_packages: true,
+            extract_precise: true,
+            extract_owners: true,
+            extract_tests: true,
         }
     }
 }
@@ -179,6 +320,48 @@ impl KgExtractionConfig {
         self.extract_symbols = false;
         self
     }
+
+    /// Disable API schema extraction.
+    pub fn without_schemas(mut self) -> Self {
+        self.extract_schemas = false;
+        self
+    }
+
+    /// Disable infrastructure-as-code extraction.
+    pub fn without_infra(mut self) -> Self {
+        self.extract_infra = false;
+        self
+    }
+
+    /// Disable doc→code `mentions` edges.
+    pub fn without_mentions(mut self) -> Self {
+        self.extract_mentions = false;
+        self
+    }
+
+    /// Disable package nodes and their edges.
+    pub fn without_packages(mut self) -> Self {
+        self.extract_packages = false;
+        self
+    }
+
+    /// Ignore any imported SCIP index.
+    pub fn without_precise(mut self) -> Self {
+        self.extract_precise = false;
+        self
+    }
+
+    /// Disable owner nodes and `owns` edges.
+    pub fn without_owners(mut self) -> Self {
+        self.extract_owners = false;
+        self
+    }
+
+    /// Disable test classification and `tests` edges.
+    pub fn without_tests(mut self) -> Self {
+        self.extract_tests = false;
+        self
+    }
 }
 
 // ============================================================================
@@ -276,6 +459,7 @@ impl DefaultKgExtractor {
         branch: &str,
         base: &str,
         cfg: &KgExtractionConfig,
+        packages: &PackageIndex,
     ) -> Result<KgExtractionResult, GikError> {
         let base_root = crate::base::base_root(workspace.knowledge_root(), branch, base);
 
@@ -333,73 +517,160 @@ impl DefaultKgExtractor {
             result.files_processed += 1;
         }
 
+        // Imports and routes usually sit in a file's first chunk, so scan whole files
+        let file_texts = if base == "code" || cfg.extract_schemas || cfg.extract_infra {
+            collect_file_texts(&sources, &file_to_node_id)
+        } else {
+            HashMap::new()
+        };
+
         // Pass 2: Extract import edges (only for code base)
         if base == "code" {
+            let resolver = ImportResolver::new(workspace.root(), &file_to_node_id, &file_texts);
+
             for source in &files_to_process {
-                if let Some(text) = &source.text {
-                    let imports = extract_imports(text, &source.file_path, cfg.max_edges_per_file);
+                let Some(text) = file_texts.get(source.file_path.as_str()) else {
+                    continue;
+                };
+                let from_id = file_to_node_id
+                    .get(&source.file_path)
+                    .cloned()
+                    .unwrap_or_else(|| format!("file:{}", source.file_path));
+                let imports = extract_imports(text, &source.file_path, cfg.max_edges_per_file);
+                let mut seen: HashSet<String> = HashSet::new();
+                let mut unresolved: Vec<String> = Vec::new();
+
+                for import in imports {
+                    // Try to resolve import to known files
+                    let targets = match import.kind {
+                        ImportKind::Path => {
+                            resolve_import(&import.raw, &source.file_path, &file_to_node_id)
+                                .into_iter()
+                                .collect()
+                        }
+                        _ => resolver.resolve(&import, &source.file_path),
+                    };
+                    if targets.is_empty() {
+                        unresolved.push(import.raw.clone());
+                    }
 
-                    for import_path in imports {
-                        // Try to resolve import to a known file
-                        if let Some(resolved) =
-                            resolve_import(&import_path, &source.file_path, &file_to_node_id)
-                        {
-                            let from_id = file_to_node_id
-                                .get(&source.file_path)
-                                .cloned()
-                                .unwrap_or_else(|| format!("file:{}", source.file_path));
-
-                            let edge = KgEdge::new(&from_id, &resolved, "imports")
-                                .with_props(serde_json::json!({
-                                    "rawImport": import_path,
-                                }))
-                                .with_branch(branch);
-
-                            result.edges.push(edge);
-                            result.import_edges_created += 1;
+                    for resolved in targets {
+                        if !seen.insert(resolved.clone()) {
+                            continue;
                         }
+                        if cfg.max_edges_per_file.is_some_and(|max| seen.len() > max) {
+                            break;
+                        }
+
+                        let edge = KgEdge::new(&from_id, &resolved, "imports")
+                            .with_props(serde_json::json!({
+                                "rawImport": import.raw,
+                            }))
+                            .with_branch(branch);
+
+                        result.edges.push(edge);
+                        result.import_edges_created += 1;
+                    }
+                }
+
+                // Imports of declared dependencies point at their package nodes
+                if packages.is_empty() {
+                    continue;
+                }
+                if source.file_path.ends_with(".rs") {
+                    unresolved.extend(extract_rust_crate_roots(text));
+                }
+                let mut used: HashSet<&str> = HashSet::new();
+                for raw in &unresolved {
+                    let Some(package_id) = packages.resolve(&source.file_path, raw) else {
+                        continue;
+                    };
+                    if used.insert(package_id) {
+                        let edge = KgEdge::new(&from_id, package_id, "usesPackage")
+                            .with_props(serde_json::json!({
+                                "rawImport": raw,
+                            }))
+                            .with_branch(branch);
+                        result.edges.push(edge);
                     }
                 }
             }
         }
 
         // Pass 3: Extract endpoint nodes (Phase 9.3)
+        // Endpoint handlers are linked to symbols once those exist (Pass 4d)
+        let mut pending_handlers: Vec<(String, String, String)> = Vec::new();
         if base == "code" && cfg.extract_endpoints {
             for source in &files_to_process {
-                if let Some(endpoint_info) =
-                    detect_endpoint(&source.file_path, source.text.as_deref())
-                {
-                    let file_node_id = file_to_node_id
-                        .get(&source.file_path)
-                        .cloned()
-                        .unwrap_or_else(|| format!("file:{}", source.file_path));
+                let text = file_texts
+                    .get(source.file_path.as_str())
+                    .map(String::as_str);
+                let file_node_id = file_to_node_id
+                    .get(&source.file_path)
+                    .cloned()
+                    .unwrap_or_else(|| format!("file:{}", source.file_path));
+
+                // Next.js routes come from the file path; other frameworks from source
+                let endpoints: Vec<EndpointCandidate> =
+                    if let Some(endpoint_info) = detect_endpoint(&source.file_path, text) {
+                        endpoint_info
+                            .methods
+                            .iter()
+                            .map(|method| EndpointCandidate {
+                                method: method.to_uppercase(),
+                                route: endpoint_info.route.clone(),
+                                handler: Some(method.to_uppercase()),
+                                line: 0,
+                                framework: FrameworkHint::NextJs,
+                            })
+                            .collect()
+                    } else if let Some(text) = text {
+                        extract_endpoints(&source.file_path, text)
+                    } else {
+                        Vec::new()
+                    };
+
+                // Create endpoint node for each HTTP method. The id includes
+                // the defining file so services sharing a route stay distinct.
+                for endpoint in endpoints {
+                    let endpoint_id = format!(
+                        "endpoint:{}:{}:{}",
+                        source.file_path, endpoint.method, endpoint.route
+                    );
+
+                    let mut props = serde_json::json!({
+                        "base": base,
+                        "path": source.file_path,
+                        "route": endpoint.route,
+                        "httpMethod": endpoint.method,
+                        "framework": endpoint.framework,
+                    });
+                    if let Some(obj) = props.as_object_mut() {
+                        if let Some(handler) = &endpoint.handler {
+                            obj.insert("handler".to_string(), serde_json::json!(handler));
+                        }
+                        if endpoint.line > 0 {
+                            obj.insert("line".to_string(), serde_json::json!(endpoint.line));
+                        }
+                    }
+                    let endpoint_node = KgNode::new(&endpoint_id, "endpoint", &endpoint.route)
+                        .with_props(props)
+                        .with_branch(branch);
 
-                    // Create endpoint node for each HTTP method
-                    for method in &endpoint_info.methods {
-                        let endpoint_id =
-                            format!("endpoint:{}:{}", method.to_uppercase(), endpoint_info.route);
-
-                        let endpoint_node =
-                            KgNode::new(&endpoint_id, "endpoint", &endpoint_info.route)
-                                .with_props(serde_json::json!({
-                                    "base": base,
-                                    "path": source.file_path,
-                                    "route": endpoint_info.route,
-                                    "httpMethod": method.to_uppercase(),
-                                }))
-                                .with_branch(branch);
-
-                        result.nodes.push(endpoint_node);
-                        result.endpoints_created += 1;
-
-                        // Create edge from file to endpoint
-                        let edge = KgEdge::new(&file_node_id, &endpoint_id, "definesEndpoint")
-                            .with_props(serde_json::json!({
-                                "httpMethod": method.to_uppercase(),
-                            }))
-                            .with_branch(branch);
+                    result.nodes.push(endpoint_node);
+                    result.endpoints_created += 1;
 
-                        result.edges.push(edge);
+                    // Create edge from file to endpoint
+                    let edge = KgEdge::new(&file_node_id, &endpoint_id, "definesEndpoint")
+                        .with_props(serde_json::json!({
+                            "httpMethod": endpoint.method,
+                        }))
+                        .with_branch(branch);
+
+                    result.edges.push(edge);
+
+                    if let Some(handler) = endpoint.handler {
+                        pending_handlers.push((endpoint_id, source.file_path.clone(), handler));
                     }
                 }
             }
@@ -407,6 +678,10 @@ impl DefaultKgExtractor {
 
         // Pass 4: Extract symbol-level nodes (Phase 9.2.1)
         if base == "code" && cfg.extract_symbols {
+            // Callable symbols per file and call relations awaiting resolution
+            let mut callables: HashMap<String, Vec<CallableSymbol>> = HashMap::new();
+            let mut pending_calls: Vec<(String, KgRelationCandidate)> = Vec::new();
+
             for source in &files_to_process {
                 if let Some(text) = &source.text {
                     let file_node_id = file_to_node_id
@@ -435,16 +710,39 @@ impl DefaultKgExtractor {
 
                     // Create symbol nodes and file→symbol edges
                     for sym in symbols {
+                        if matches!(sym.kind.as_str(), "function" | "method") {
+                            callables.entry(source.file_path.clone()).or_default().push(
+                                CallableSymbol {
+                                    id: sym.id.clone(),
+                                    name: sym.name.clone(),
+                                    parent: sym.props["parent"].as_str().map(str::to_string),
+                                },
+                            );
+                        }
+
                         // Create symbol node
                         // Use tag_from_path for accurate language distinction (e.g., ts vs js)
                         let lang_tag = crate::kg::lang::tag_from_path(&source.file_path);
+                        let mut props = serde_json::json!({
+                            "base": base,
+                            "path": source.file_path,
+                            "language": lang_tag,
+                            "symbolKind": sym.kind,
+                        });
+                        if let Some(obj) = props.as_object_mut() {
+                            if let Some((start, end)) = sym.span {
+                                obj.insert("startLine".to_string(), serde_json::json!(start));
+                                obj.insert("endLine".to_string(), serde_json::json!(end));
+                            }
+                            // Extractor props (parent, doc, ...) never override the core keys
+                            if let Some(extra) = sym.props.as_object() {
+                                for (key, value) in extra {
+                                    obj.entry(key.clone()).or_insert_with(|| value.clone());
+                                }
+                            }
+                        }
                         let node = KgNode::new(&sym.id, &sym.kind, &sym.name)
-                            .with_props(serde_json::json!({
-                                "base": base,
-                                "path": source.file_path,
-                                "language": lang_tag,
-                                "symbolKind": sym.kind,
-                            }))
+                            .with_props(props)
                             .with_branch(branch);
 
                         result.nodes.push(node);
@@ -463,6 +761,12 @@ impl DefaultKgExtractor {
                     // Pass 4b: Process relation candidates (Phase 9.2.2)
                     // Creates edges for usesClass, usesUiComponent, belongsToModule, etc.
                     for rel in relations {
+                        // Calls are resolved once every file's symbols are known
+                        if rel.kind == "calls" && rel.props["unresolved"] == true {
+                            pending_calls.push((source.file_path.clone(), rel));
+                            continue;
+                        }
+
                         // Build props from the relation candidate
                         let mut edge_props = rel.props.clone();
                         // Add source file info if not present
@@ -492,14 +796,181 @@ impl DefaultKgExtractor {
                     ));
                 }
             }
+
+            // Pass 4c: Resolve call edges within the file, then through imports
+            resolve_calls(
+                &mut result,
+                branch,
+                &file_to_node_id,
+                &callables,
+                pending_calls,
+            );
+
+            // Pass 4d: Link endpoints to their handler functions
+            link_endpoint_handlers(&mut result, branch, &callables, pending_handlers);
         }
 
-        // TODO(gik.phase-9.3+): Extract doc→file "mentions" edges
-        // For docs base, we could scan for relative paths like "./src/..." and
-        // create edges of kind "mentions" or "documents" to code files.
+        // Pass 5: Extract API schema operations and types, and infrastructure
+        if cfg.extract_schemas || cfg.extract_infra {
+            let mut seen: HashSet<String> = HashSet::new();
+            for source in &files_to_process {
+                let Some(text) = file_texts.get(source.file_path.as_str()) else {
+                    continue;
+                };
+                let file_node_id = file_to_node_id
+                    .get(&source.file_path)
+                    .cloned()
+                    .unwrap_or_else(|| format!("file:{}", source.file_path));
+                let schema = cfg
+                    .extract_schemas
+                    .then(|| extract_schema(&source.file_path, &file_node_id, text))
+                    .flatten()
+                    .map(|g| (g.nodes, g.edges));
+                let infra = || {
+                    cfg.extract_infra
+                        .then(|| extract_infra(&source.file_path, &file_node_id, text))
+                        .flatten()
+                        .map(|g| (g.nodes, g.edges))
+                };
+                let Some((nodes, edges)) = schema.or_else(infra) else {
+                    continue;
+                };
+
+                // Type, image and Kubernetes ids are shared across files; keep the first
+                for mut node in nodes {
+                    if seen.insert(node.id.clone()) {
+                        if let Some(obj) = node.props.as_object_mut() {
+                            obj.insert("base".to_string(), serde_json::json!(base));
+                        }
+                        result.nodes.push(node.with_branch(branch));
+                    }
+                }
+                result
+                    .edges
+                    .extend(edges.into_iter().map(|e| e.with_branch(branch)));
+            }
+        }
 
         Ok(result)
     }
+
+    /// Add `mentions` edges from docs-base Markdown files to code nodes.
+    fn link_doc_mentions(
+        &self,
+        workspace: &Workspace,
+        branch: &str,
+        result: &mut KgExtractionResult,
+    ) -> Result<(), GikError> {
+        let base_root = crate::base::base_root(workspace.knowledge_root(), branch, "docs");
+        if !base_root.exists() {
+            return Ok(());
+        }
+        let sources = load_base_sources(&sources_path(&base_root))?;
+
+        let doc_nodes: HashMap<String, String> = result
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "doc")
+            .filter_map(|n| {
+                n.props["path"]
+                    .as_str()
+                    .map(|p| (p.to_string(), n.id.clone()))
+            })
+            .collect();
+        let texts = collect_file_texts(&sources, &doc_nodes);
+        let mut paths: Vec<&&str> = texts.keys().collect();
+        paths.sort();
+
+        let index = MentionIndex::new(&result.nodes);
+        let mut edges = Vec::new();
+        for path in paths {
+            let mentions = extract_mentions(path, &texts[*path]);
+            if let Some(doc_id) = doc_nodes.get(*path) {
+                edges.extend(index.resolve(doc_id, path, &mentions));
+            }
+        }
+
+        result
+            .edges
+            .extend(edges.into_iter().map(|e| e.with_branch(branch)));
+        Ok(())
+    }
+
+    /// Mark test files and add `tests` edges to the code they exercise.
+    fn link_tests(
+        &self,
+        workspace: &Workspace,
+        branch: &str,
+        result: &mut KgExtractionResult,
+    ) -> Result<(), GikError> {
+        let base_root = crate::base::base_root(workspace.knowledge_root(), branch, "code");
+        let sources = if base_root.exists() {
+            load_base_sources(&sources_path(&base_root))?
+        } else {
+            Vec::new()
+        };
+
+        let file_nodes: HashMap<String, String> = result
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "file")
+            .filter_map(|n| {
+                n.props["path"]
+                    .as_str()
+                    .map(|p| (p.to_string(), n.id.clone()))
+            })
+            .collect();
+        let texts = collect_file_texts(&sources, &file_nodes);
+
+        let edges = link_tests(&mut result.nodes, &result.edges, &texts);
+        result
+            .edges
+            .extend(edges.into_iter().map(|e| e.with_branch(branch)));
+        Ok(())
+    }
+
+    /// Add owner nodes and `owns` edges from `CODEOWNERS` and Git history.
+    fn link_owners(
+        &self,
+        workspace: &Workspace,
+        branch: &str,
+        result: &mut KgExtractionResult,
+    ) -> Result<(), GikError> {
+        let codeowners = read_codeowners(workspace.root())?;
+        let authors = match read_path_history(workspace.root(), AUTHOR_HISTORY_LIMIT) {
+            Ok(commits) => primary_authors(&commits),
+            Err(e) => {
+                tracing::debug!("Skipping Git authors for ownership: {}", e);
+                HashMap::new()
+            }
+        };
+        if codeowners.is_none() && authors.is_empty() {
+            return Ok(());
+        }
+
+        let file_nodes: HashMap<String, String> = result
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "file" || n.kind == "doc")
+            .filter_map(|n| {
+                n.props["path"]
+                    .as_str()
+                    .map(|p| (p.to_string(), n.id.clone()))
+            })
+            .collect();
+        let graph = build_owner_graph(codeowners.as_ref(), &authors, &file_nodes);
+
+        if let Some(codeowners) = codeowners {
+            result.warnings.extend(codeowners.warnings);
+        }
+        result
+            .nodes
+            .extend(graph.nodes.into_iter().map(|n| n.with_branch(branch)));
+        result
+            .edges
+            .extend(graph.edges.into_iter().map(|e| e.with_branch(branch)));
+        Ok(())
+    }
 }
 
 impl KgExtractor for DefaultKgExtractor {
@@ -511,6 +982,13 @@ impl KgExtractor for DefaultKgExtractor {
     ) -> Result<KgExtractionResult, GikError> {
         let mut result = KgExtractionResult::new();
 
+        let dependencies = if cfg.extract_packages {
+            read_dependencies_jsonl(&workspace.stack_dependencies_path(branch))?
+        } else {
+            Vec::new()
+        };
+        let packages = PackageIndex::new(&dependencies);
+
         for base in &cfg.enabled_bases {
             // Skip docs if disabled
             if base == "docs" && !cfg.include_docs {
@@ -522,14 +1000,264 @@ impl KgExtractor for DefaultKgExtractor {
                 continue;
             }
 
-            let base_result = self.extract_from_base(workspace, branch, base, cfg)?;
+            let base_result = self.extract_from_base(workspace, branch, base, cfg, &packages)?;
             result.merge(base_result);
         }
 
+        // Precise symbols replace heuristic ranges before anything links to them
+        if cfg.extract_precise {
+            if let Some(index) = read_precise_index(&scip_snapshot_path(workspace, branch))? {
+                let stats = merge_precise_index(&mut result, &index, branch);
+                result.symbols_created += stats.added;
+            }
+        }
+
+        if cfg.extract_tests {
+            self.link_tests(workspace, branch, &mut result)?;
+        }
+
+        // Schema files and the code implementing them usually live in different bases
+        if cfg.extract_schemas {
+            let links = link_schema_operations(&result.nodes, &result.edges);
+            result
+                .edges
+                .extend(links.into_iter().map(|e| e.with_branch(branch)));
+        }
+        if cfg.extract_infra {
+            let links = link_infra(&result.nodes);
+            result
+                .edges
+                .extend(links.into_iter().map(|e| e.with_branch(branch)));
+        }
+        let docs_enabled = cfg.include_docs && cfg.enabled_bases.iter().any(|b| b == "docs");
+        if cfg.extract_mentions && docs_enabled {
+            self.link_doc_mentions(workspace, branch, &mut result)?;
+        }
+        if !dependencies.is_empty() {
+            let file_nodes: HashMap<String, String> = result
+                .nodes
+                .iter()
+                .filter(|n| n.kind == "file" || n.kind == "doc")
+                .filter_map(|n| {
+                    n.props["path"]
+                        .as_str()
+                        .map(|p| (p.to_string(), n.id.clone()))
+                })
+                .collect();
+            let graph = build_package_graph(&dependencies, &file_nodes);
+            result
+                .nodes
+                .extend(graph.nodes.into_iter().map(|n| n.with_branch(branch)));
+            result
+                .edges
+                .extend(graph.edges.into_iter().map(|e| e.with_branch(branch)));
+        }
+        if cfg.extract_owners {
+            self.link_owners(workspace, branch, &mut result)?;
+        }
+
         Ok(result)
     }
 }
 
+// ============================================================================
+// Call Resolution Helpers
+// ============================================================================
+
+/// A function or method symbol that calls can resolve to.
+#[derive(Debug, Clone)]
+struct CallableSymbol {
+    id: String,
+    name: String,
+    parent: Option<String>,
+}
+
+/// Turn pending `calls` relations into edges.
+///
+/// A call resolves to a function or method of the same name in the calling
+/// file (preferring siblings of the caller), then in the files it imports.
+/// Unresolved calls point at an `external:<lang>:<name>` node.
+fn resolve_calls(
+    result: &mut KgExtractionResult,
+    branch: &str,
+    file_to_node_id: &HashMap<String, String>,
+    callables: &HashMap<String, Vec<CallableSymbol>>,
+    pending_calls: Vec<(String, KgRelationCandidate)>,
+) {
+    let node_to_file: HashMap<&str, &str> = file_to_node_id
+        .iter()
+        .map(|(path, id)| (id.as_str(), path.as_str()))
+        .collect();
+    let mut imports: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in result.edges.iter().filter(|e| e.kind == "imports") {
+        if let Some(path) = node_to_file.get(edge.to.as_str()) {
+            imports.entry(edge.from.as_str()).or_default().push(path);
+        }
+    }
+    let parents: HashMap<&str, Option<&str>> = callables
+        .values()
+        .flatten()
+        .map(|c| (c.id.as_str(), c.parent.as_deref()))
+        .collect();
+
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    let mut seen_external = HashSet::new();
+
+    for (file_path, rel) in pending_calls {
+        let Some(callee) = rel.props["callee"].as_str() else {
+            continue;
+        };
+        let named = |path: &str| -> Vec<&CallableSymbol> {
+            callables
+                .get(path)
+                .map(|c| c.iter().filter(|s| s.name == callee).collect())
+                .unwrap_or_default()
+        };
+        let file_node_id = file_to_node_id
+            .get(&file_path)
+            .cloned()
+            .unwrap_or_else(|| format!("file:{}", file_path));
+
+        let caller_parent = parents.get(rel.from_id.as_str()).copied().flatten();
+        let local = named(&file_path);
+        let local_target = local
+            .iter()
+            .find(|s| caller_parent.is_some() && s.parent.as_deref() == caller_parent)
+            .or_else(|| local.first());
+        let imported_target = || {
+            imports
+                .get(file_node_id.as_str())
+                .into_iter()
+                .flatten()
+                .find_map(|path| named(path).first().copied())
+        };
+
+        let (to_id, resolution) = if let Some(target) = local_target {
+            (target.id.clone(), "file")
+        } else if let Some(target) = imported_target() {
+            (target.id.clone(), "import")
+        } else {
+            let lang_tag = crate::kg::lang::tag_from_path(&file_path);
+            let external_id = format!("external:{}:{}", lang_tag, callee);
+            if seen_external.insert(external_id.clone()) {
+                let node = KgNode::new(&external_id, "external", callee)
+                    .with_props(serde_json::json!({
+                        "language": lang_tag,
+                        "name": callee,
+                    }))
+                    .with_branch(branch);
+                nodes.push(node);
+            }
+            (external_id, "external")
+        };
+
+        let mut props = rel.props.clone();
+        if let Some(obj) = props.as_object_mut() {
+            obj.insert("resolution".to_string(), serde_json::json!(resolution));
+            obj.insert(
+                "unresolved".to_string(),
+                serde_json::json!(resolution == "external"),
+            );
+            obj.entry("sourceFile".to_string())
+                .or_insert_with(|| serde_json::json!(file_path));
+        }
+
+        let edge = KgEdge::new(&rel.from_id, &to_id, "calls")
+            .with_props(props)
+            .with_branch(branch);
+        edges.push(edge);
+    }
+
+    result.nodes.extend(nodes);
+    result.edges.extend(edges);
+}
+
+/// Turn pending endpoint handlers into `handledBy` edges.
+///
+/// Handlers are written as `name` or `qualifier<sep>name` (`users#index`,
+/// `UserController@store`, `views.detail`, `handlers::list`). The handler
+/// resolves to a function or method of that name in the routing file, or
+/// else to one whose class or file matches the qualifier. Unqualified names
+/// outside the routing file must be unique in the code base.
+fn link_endpoint_handlers(
+    result: &mut KgExtractionResult,
+    branch: &str,
+    callables: &HashMap<String, Vec<CallableSymbol>>,
+    pending_handlers: Vec<(String, String, String)>,
+) {
+    let squash = |s: &str| -> String {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+
+    for (endpoint_id, file_path, handler) in pending_handlers {
+        let (qualifier, name) = match handler.rfind(['#', '@', '.', ':']) {
+            Some(idx) => {
+                let qualifier = handler[..idx].trim_end_matches(':');
+                let qualifier = qualifier.rsplit(['/', '.', ':', '\\']).next().unwrap_or("");
+                (squash(qualifier), &handler[idx + 1..])
+            }
+            None => (String::new(), handler.as_str()),
+        };
+        // The qualifier names the class, the file or (for Go) the package directory
+        let matches_qualifier = |path: &str, symbol: &CallableSymbol| {
+            let path = Path::new(path);
+            let stem = path.file_stem().map(|s| squash(&s.to_string_lossy()));
+            let dir = path
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|s| squash(&s.to_string_lossy()));
+            // Parents are symbol IDs; the name is the last segment
+            let parent = symbol
+                .parent
+                .as_deref()
+                .map(|p| squash(p.rsplit(':').next().unwrap_or(p)));
+            [parent, stem, dir]
+                .into_iter()
+                .flatten()
+                .any(|name| !name.is_empty() && name.starts_with(&qualifier))
+        };
+
+        let local = callables
+            .get(&file_path)
+            .into_iter()
+            .flatten()
+            .find(|s| s.name == name);
+        let target = local.or_else(|| {
+            let mut candidates = callables.iter().flat_map(|(path, symbols)| {
+                symbols
+                    .iter()
+                    .filter(move |s| s.name == name)
+                    .map(move |s| (path.as_str(), s))
+            });
+            if qualifier.is_empty() {
+                let first = candidates.next();
+                first
+                    .filter(|_| candidates.next().is_none())
+                    .map(|(_, s)| s)
+            } else {
+                let mut qualified: Vec<(&str, &CallableSymbol)> = candidates
+                    .filter(|(path, s)| matches_qualifier(path, s))
+                    .collect();
+                qualified.sort_by(|a, b| a.1.id.cmp(&b.1.id));
+                qualified.first().map(|(_, s)| *s)
+            }
+        });
+
+        if let Some(target) = target {
+            let edge = KgEdge::new(&endpoint_id, &target.id, "handledBy")
+                .with_props(serde_json::json!({
+                    "handler": handler,
+                }))
+                .with_branch(branch);
+            result.edges.push(edge);
+        }
+    }
+}
+
 // ============================================================================
 // Import Extraction Helpers
 // ============================================================================
@@ -537,20 +1265,30 @@ impl KgExtractor for DefaultKgExtractor {
 /// Extract import statements from source text.
 ///
 /// Uses regex-based heuristics for common languages. Returns raw import
-/// targets (not yet resolved to file paths).
-fn extract_imports(text: &str, file_path: &str, max_edges: Option<usize>) -> Vec<String> {
+/// targets (not yet resolved to file paths). JS/TS, Rust and Python imports
+/// are path-like; other languages are extracted by [`super::imports`].
+fn extract_imports(text: &str, file_path: &str, max_edges: Option<usize>) -> Vec<ImportRef> {
     let ext = Path::new(file_path)
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("");
 
-    let imports = match ext {
+    let paths = match ext {
         "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" => extract_js_imports(text),
         "rs" => extract_rust_imports(text),
         "py" => extract_python_imports(text),
-        _ => Vec::new(),
+        _ => return limit_imports(super::imports::extract_imports(text, file_path), max_edges),
     };
+    let imports = paths
+        .into_iter()
+        .map(|path| ImportRef::new(path, ImportKind::Path))
+        .collect();
+
+    limit_imports(imports, max_edges)
+}
 
+/// Apply the per-file edge limit to raw imports.
+fn limit_imports(imports: Vec<ImportRef>, max_edges: Option<usize>) -> Vec<ImportRef> {
     // Apply max_edges limit
     if let Some(max) = max_edges {
         imports.into_iter().take(max).collect()
@@ -559,6 +1297,38 @@ fn extract_imports(text: &str, file_path: &str, max_edges: Option<usize>) -> Vec
     }
 }
 
+/// Reassemble the indexed text of each known file from its chunks.
+///
+/// Chunks are joined in line order; overlapping chunks repeat a few lines,
+/// which is harmless for import detection.
+fn collect_file_texts<'a>(
+    sources: &'a [BaseSourceEntry],
+    known_files: &HashMap<String, String>,
+) -> HashMap<&'a str, String> {
+    let mut chunks: HashMap<&str, Vec<&BaseSourceEntry>> = HashMap::new();
+    for source in sources {
+        if source.text.is_some() && known_files.contains_key(&source.file_path) {
+            chunks
+                .entry(source.file_path.as_str())
+                .or_default()
+                .push(source);
+        }
+    }
+
+    chunks
+        .into_iter()
+        .map(|(path, mut entries)| {
+            entries.sort_by_key(|e| e.start_line);
+            let text = entries
+                .iter()
+                .filter_map(|e| e.text.as_deref())
+                .collect::<Vec<_>>()
+                .join("\n");
+            (path, text)
+        })
+        .collect()
+}
+
 /// Extract JavaScript/TypeScript imports.
 fn extract_js_imports(text: &str) -> Vec<String> {
     let mut imports = Vec::new();
@@ -952,6 +1722,11 @@ mod tests {
         assert!(cfg.include_docs);
         assert!(cfg.max_files.is_none());
         assert!(cfg.max_edges_per_file.is_none());
+        assert!(cfg.extract_schemas);
+        assert!(cfg.extract_infra);
+        assert!(cfg.extract_mentions);
+        assert!(cfg.extract_packages);
+        assert!(cfg.extract_precise);
     }
 
     #[test]
@@ -959,11 +1734,21 @@ mod tests {
         let cfg = KgExtractionConfig::new()
             .with_max_files(100)
             .with_max_edges_per_file(50)
-            .without_docs();
+            .without_docs()
+            .without_schemas()
+            .without_infra()
+            .without_mentions()
+            .without_packages()
+            .without_precise();
 
         assert_eq!(cfg.max_files, Some(100));
         assert_eq!(cfg.max_edges_per_file, Some(50));
         assert!(!cfg.include_docs);
+        assert!(!cfg.extract_schemas);
+        assert!(!cfg.extract_infra);
+        assert!(!cfg.extract_mentions);
+        assert!(!cfg.extract_packages);
+        assert!(!cfg.extract_precise);
     }
 
     #[test]
@@ -1018,6 +1803,18 @@ from ..models import User
         assert!(imports.contains(&"pathlib".to_string()));
     }
 
+    #[test]
+    fn test_extract_imports_dispatches_by_language() {
+        let imports = extract_imports("import { a } from './a';", "src/index.ts", None);
+        assert_eq!(imports, vec![ImportRef::new("./a", ImportKind::Path)]);
+
+        let go = "import (\n\t\"fmt\"\n\t\"example.com/app/billing\"\n)\n";
+        let imports = extract_imports(go, "cmd/main.go", Some(1));
+        assert_eq!(imports, vec![ImportRef::new("fmt", ImportKind::GoPackage)]);
+
+        assert!(extract_imports("SELECT 1;", "schema.sql", None).is_empty());
+    }
+
     #[test]
     fn test_resolve_import_relative() {
         let mut known_files = HashMap::new();
@@ -1091,6 +1888,321 @@ from ..models import User
         assert_eq!(r1.warnings.len(), 1);
     }
 
+    #[test]
+    fn test_resolve_calls_in_file_through_imports_and_external() {
+        let file_to_node_id: HashMap<String, String> = ["src/a.ts", "src/b.ts"]
+            .iter()
+            .map(|p| (p.to_string(), format!("file:{}", p)))
+            .collect();
+        let callable = |path: &str, kind: &str, name: &str, parent: Option<&str>| CallableSymbol {
+            id: format!("sym:ts:{}:{}:{}", path, kind, name),
+            name: name.to_string(),
+            parent: parent.map(str::to_string),
+        };
+        let mut callables: HashMap<String, Vec<CallableSymbol>> = HashMap::new();
+        callables.insert(
+            "src/a.ts".to_string(),
+            vec![
+                callable("src/a.ts", "function", "run", None),
+                callable("src/a.ts", "function", "save", None),
+                callable(
+                    "src/a.ts",
+                    "method",
+                    "save",
+                    Some("sym:ts:src/a.ts:class:Repo"),
+                ),
+                callable(
+                    "src/a.ts",
+                    "method",
+                    "flush",
+                    Some("sym:ts:src/a.ts:class:Repo"),
+                ),
+            ],
+        );
+        callables.insert(
+            "src/b.ts".to_string(),
+            vec![callable("src/b.ts", "function", "format", None)],
+        );
+
+        let mut result = KgExtractionResult::new();
+        result
+            .edges
+            .push(KgEdge::new("file:src/a.ts", "file:src/b.ts", "imports"));
+
+        let call = |from: &str, callee: &str| {
+            let rel =
+                KgRelationCandidate::new(from, &format!("sym:ts:*:function:{}", callee), "calls")
+                    .with_props(serde_json::json!({
+                        "callee": callee,
+                        "lines": [3],
+                        "unresolved": true,
+                    }));
+            ("src/a.ts".to_string(), rel)
+        };
+        let pending = vec![
+            call("sym:ts:src/a.ts:function:run", "save"),
+            call("sym:ts:src/a.ts:method:flush", "save"),
+            call("sym:ts:src/a.ts:function:run", "format"),
+            call("sym:ts:src/a.ts:function:run", "fetch"),
+            call("file:src/a.ts", "fetch"),
+        ];
+
+        resolve_calls(&mut result, "main", &file_to_node_id, &callables, pending);
+
+        let calls: Vec<&KgEdge> = result.edges.iter().filter(|e| e.kind == "calls").collect();
+        assert_eq!(calls.len(), 5);
+        assert_eq!(calls[0].to, "sym:ts:src/a.ts:function:save");
+        assert_eq!(calls[0].props["resolution"], "file");
+        // Methods prefer siblings in the same class
+        assert_eq!(calls[1].to, "sym:ts:src/a.ts:method:save");
+        assert_eq!(calls[2].to, "sym:ts:src/b.ts:function:format");
+        assert_eq!(calls[2].props["resolution"], "import");
+        assert_eq!(calls[3].to, "external:ts:fetch");
+        assert_eq!(calls[3].props["unresolved"], true);
+        assert_eq!(calls[3].props["lines"], serde_json::json!([3]));
+        assert_eq!(calls[4].from, "file:src/a.ts");
+
+        // One shared external node per callee
+        let externals: Vec<&KgNode> = result
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "external")
+            .collect();
+        assert_eq!(externals.len(), 1);
+        assert_eq!(externals[0].label, "fetch");
+    }
+
+    #[test]
+    fn test_extract_call_edges_from_source() {
+        use crate::base::ChunkId;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".guided/knowledge")).unwrap();
+        let workspace = Workspace::from_root(temp.path()).unwrap();
+
+        let files = [
+            (
+                "src/util.ts",
+                "export function formatName(name: string): string {\n  return name.trim();\n}\n",
+            ),
+            (
+                "src/app.ts",
+                r#"import { formatName } from './util';
+
+function greet(name: string): string {
+  return formatName(name);
+}
+
+export function main(): void {
+  greet("world");
+  fetchUsers();
+}
+"#,
+            ),
+        ];
+        let base_root = crate::base::base_root(workspace.knowledge_root(), "main", "code");
+        std::fs::create_dir_all(&base_root).unwrap();
+        let entries: Vec<BaseSourceEntry> = files
+            .iter()
+            .enumerate()
+            .map(|(i, (path, text))| {
+                let lines = text.lines().count() as u32;
+                BaseSourceEntry::new(
+                    ChunkId::new(format!("chunk-{}", i)),
+                    "code",
+                    "main",
+                    *path,
+                    1,
+                    lines,
+                    i as u64,
+                    "rev-1",
+                    "src-1",
+                )
+                .with_text(*text)
+            })
+            .collect();
+        crate::base::append_base_sources(&sources_path(&base_root), &entries).unwrap();
+
+        let result = DefaultKgExtractor::new()
+            .extract_for_branch(&workspace, "main", &KgExtractionConfig::default())
+            .unwrap();
+
+        let call = |from: &str, to: &str| {
+            result
+                .edges
+                .iter()
+                .find(|e| e.kind == "calls" && e.from == from && e.to == to)
+                .unwrap_or_else(|| panic!("missing call edge {} -> {}", from, to))
+        };
+        let local = call(
+            "sym:ts:src/app.ts:function:main",
+            "sym:ts:src/app.ts:function:greet",
+        );
+        assert_eq!(local.props["resolution"], "file");
+        let imported = call(
+            "sym:ts:src/app.ts:function:greet",
+            "sym:ts:src/util.ts:function:formatName",
+        );
+        assert_eq!(imported.props["resolution"], "import");
+        let external = call("sym:ts:src/app.ts:function:main", "external:ts:fetchUsers");
+        assert_eq!(external.props["unresolved"], true);
+        assert!(result
+            .nodes
+            .iter()
+            .any(|n| n.id == "external:ts:fetchUsers" && n.kind == "external"));
+    }
+
+    #[test]
+    fn test_same_route_in_two_files_yields_two_endpoints() {
+        use crate::base::ChunkId;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".guided/knowledge")).unwrap();
+        let workspace = Workspace::from_root(temp.path()).unwrap();
+
+        let text = "app.get('/health', (req, res) => res.send('ok'));\n";
+        let base_root = crate::base::base_root(workspace.knowledge_root(), "main", "code");
+        std::fs::create_dir_all(&base_root).unwrap();
+        let entries: Vec<BaseSourceEntry> =
+            ["services/users/server.js", "services/orders/server.js"]
+                .iter()
+                .enumerate()
+                .map(|(i, path)| {
+                    BaseSourceEntry::new(
+                        ChunkId::new(format!("chunk-{}", i)),
+                        "code",
+                        "main",
+                        *path,
+                        1,
+                        1,
+                        i as u64,
+                        "rev-1",
+                        "src-1",
+                    )
+                    .with_text(text)
+                })
+                .collect();
+        crate::base::append_base_sources(&sources_path(&base_root), &entries).unwrap();
+
+        let result = DefaultKgExtractor::new()
+            .extract_for_branch(&workspace, "main", &KgExtractionConfig::default())
+            .unwrap();
+
+        let mut ids: Vec<&str> = result
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "endpoint")
+            .map(|n| n.id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(
+            ids,
+            vec![
+                "endpoint:services/orders/server.js:GET:/health",
+                "endpoint:services/users/server.js:GET:/health",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_link_endpoint_handlers() {
+        let callable = |path: &str, kind: &str, name: &str, parent: Option<&str>| CallableSymbol {
+            id: format!("sym:{}:{}:{}", path, kind, name),
+            name: name.to_string(),
+            parent: parent.map(str::to_string),
+        };
+        let mut callables: HashMap<String, Vec<CallableSymbol>> = HashMap::new();
+        callables.insert(
+            "src/routes.ts".to_string(),
+            vec![callable("src/routes.ts", "function", "listUsers", None)],
+        );
+        callables.insert(
+            "app/controllers/users_controller.rb".to_string(),
+            vec![callable(
+                "app/controllers/users_controller.rb",
+                "method",
+                "index",
+                Some("sym:rb:app/controllers/users_controller.rb:class:UsersController"),
+            )],
+        );
+        callables.insert(
+            "app/controllers/teams_controller.rb".to_string(),
+            vec![callable(
+                "app/controllers/teams_controller.rb",
+                "method",
+                "index",
+                Some("sym:rb:app/controllers/teams_controller.rb:class:TeamsController"),
+            )],
+        );
+        callables.insert(
+            "internal/handlers/user.go".to_string(),
+            vec![callable(
+                "internal/handlers/user.go",
+                "function",
+                "GetUser",
+                None,
+            )],
+        );
+
+        let pending = |endpoint: &str, file: &str, handler: &str| {
+            (endpoint.to_string(), file.to_string(), handler.to_string())
+        };
+        let mut result = KgExtractionResult::new();
+        link_endpoint_handlers(
+            &mut result,
+            "main",
+            &callables,
+            vec![
+                pending(
+                    "endpoint:src/routes.ts:GET:/users",
+                    "src/routes.ts",
+                    "listUsers",
+                ),
+                pending(
+                    "endpoint:config/routes.rb:GET:/api/users",
+                    "config/routes.rb",
+                    "api/users#index",
+                ),
+                pending(
+                    "endpoint:cmd/main.go:GET:/u/:id",
+                    "cmd/main.go",
+                    "handlers.GetUser",
+                ),
+                // Ambiguous without a qualifier
+                pending(
+                    "endpoint:config/routes.rb:GET:/x",
+                    "config/routes.rb",
+                    "index",
+                ),
+                pending("endpoint:src/routes.ts:GET:/y", "src/routes.ts", "missing"),
+            ],
+        );
+
+        let edges: Vec<(&str, &str)> = result
+            .edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                (
+                    "endpoint:src/routes.ts:GET:/users",
+                    "sym:src/routes.ts:function:listUsers"
+                ),
+                (
+                    "endpoint:config/routes.rb:GET:/api/users",
+                    "sym:app/controllers/users_controller.rb:method:index"
+                ),
+                (
+                    "endpoint:cmd/main.go:GET:/u/:id",
+                    "sym:internal/handlers/user.go:function:GetUser"
+                ),
+            ]
+        );
+        assert!(result.edges.iter().all(|e| e.kind == "handledBy"));
+    }
+
     #[test]
     fn test_extract_route_from_path_app_router() {
         // Basic app router pattern
@@ -0,0 +1,2002 @@
+//! Declarative graph queries over the KG (`gik kg query`).
+//!
+//! A small Cypher-like language for structural questions the ask-time
+//! traversal in [`super::query`] cannot express:
+//!
+//! ```text
+//! MATCH (f:file)-[:defines]->(fn:function), (f)-[:imports]->(:file)-[:definesEndpoint]->(e:endpoint)
+//! WHERE f.path GLOB 'src/api/**' AND e.httpMethod = 'POST'
+//! RETURN DISTINCT fn.label AS function, f.path, e.route
+//! ORDER BY f.path
+//! ```
+//!
+//! ## Supported Syntax
+//!
+//! | Clause | Forms |
+//! |--------|-------|
+//! | `MATCH` | Comma-separated paths; repeating `MATCH` adds more paths |
+//! | Nodes | `(v)`, `(v:kind)`, `(v:file\|doc)`, `(v:file {path: 'src/main.rs'})`, `()` |
+//! | Relationships | `-[r:imports]->`, `<-[:defines]-`, `-[:calls\|uses]-` (either direction), `-->`, `--` |
+//! | Variable-length | `-[:imports*]->` (1 or more hops), `*2`, `*1..3`, `*..3`, `*0..` |
+//! | `WHERE` | `=`, `<>`, `<`, `<=`, `>`, `>=`, `CONTAINS`, `STARTS WITH`, `ENDS WITH`, `=~` (regex, whole string), `GLOB` (path glob), `IN [...]`, `IS [NOT] NULL`, `AND`, `OR`, `NOT` |
+//! | `RETURN` | `*`, `v`, `v.prop`, `count(*)`, `count(v)`, `count(DISTINCT v.prop)`, `AS` aliases, `DISTINCT` |
+//! | Modifiers | `ORDER BY expr [ASC\|DESC], ...`, `SKIP n`, `LIMIT n` |
+//!
+//! Labels match node kinds and relationship types match edge kinds. Keywords
+//! are case-insensitive; kinds, variables and property names are not.
+//!
+//! ## Properties
+//!
+//! `v.id`, `v.kind`, `v.label` and `v.branch` read node fields, and
+//! relationships also have `r.from` and `r.to`. Any other name reads the
+//! node or edge props (`f.path`, `s.startLine`); a missing prop is `null`.
+//!
+//! ## Semantics
+//!
+//! - A relationship is used at most once per match.
+//! - Variable-length relationships are walked breadth-first and each reachable
+//!   node is matched once, at its shortest distance. They can't be bound to
+//!   a variable.
+//! - Comparisons with `null` are `null`, and `WHERE` keeps rows where the
+//!   condition is `true`.
+//! - With `count()`, the other `RETURN` items are the grouping keys.
+//!
+//! ## Evaluation
+//!
+//! Queries run against a [`GraphQuerySource`], implemented for the LanceDB
+//! KG store. Only the edge kinds named by the query are loaded when every
+//! relationship has a type, and only the starting node kinds (plus the
+//! endpoints of loaded edges) when every path starts from a labelled node.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::db_adapter::DbKgStore;
+use crate::errors::GikError;
+
+use super::entities::{KgEdge, KgNode};
+use super::rules::Glob;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Maximum number of intermediate matches before a query is rejected.
+pub const MAX_QUERY_MATCHES: usize = 1_000_000;
+
+/// Node IDs fetched per store lookup.
+const NODE_ID_BATCH: usize = 1000;
+
+// ============================================================================
+// Source
+// ============================================================================
+
+/// Node and edge access needed to evaluate a [`GraphQuery`].
+pub trait GraphQuerySource {
+    /// All nodes.
+    fn all_nodes(&self) -> Result<Vec<KgNode>, GikError>;
+
+    /// Nodes of one kind.
+    fn nodes_by_kind(&self, kind: &str) -> Result<Vec<KgNode>, GikError>;
+
+    /// Nodes with the given IDs (missing IDs are skipped).
+    fn nodes_by_ids(&self, ids: &[&str]) -> Result<Vec<KgNode>, GikError>;
+
+    /// All edges.
+    fn all_edges(&self) -> Result<Vec<KgEdge>, GikError>;
+
+    /// Edges of one kind.
+    fn edges_by_kind(&self, kind: &str) -> Result<Vec<KgEdge>, GikError>;
+}
+
+impl GraphQuerySource for DbKgStore {
+    fn all_nodes(&self) -> Result<Vec<KgNode>, GikError> {
+        self.get_all_nodes()
+    }
+
+    fn nodes_by_kind(&self, kind: &str) -> Result<Vec<KgNode>, GikError> {
+        self.get_nodes_by_kind(kind)
+    }
+
+    fn nodes_by_ids(&self, ids: &[&str]) -> Result<Vec<KgNode>, GikError> {
+        self.get_nodes_by_ids(ids)
+    }
+
+    fn all_edges(&self) -> Result<Vec<KgEdge>, GikError> {
+        self.get_all_edges()
+    }
+
+    fn edges_by_kind(&self, kind: &str) -> Result<Vec<KgEdge>, GikError> {
+        self.get_edges_by_kind(kind)
+    }
+}
+
+// ============================================================================
+// Results
+// ============================================================================
+
+/// Rows returned by a graph query.
+///
+/// Nodes and relationships are returned as objects with their fields and
+/// props; everything else is a plain JSON value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphQueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl GraphQueryResult {
+    /// Rows as objects keyed by column name.
+    pub fn row_objects(&self) -> Vec<Map<String, Value>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                self.columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Result of `gik kg query`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQueryReport {
+    pub branch: String,
+    pub query: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Map<String, Value>>,
+}
+
+impl GraphQueryReport {
+    /// Build a report from a query result.
+    pub fn new(branch: &str, query: &str, result: &GraphQueryResult) -> Self {
+        Self {
+            branch: branch.to_string(),
+            query: query.to_string(),
+            columns: result.columns.clone(),
+            rows: result.row_objects(),
+        }
+    }
+}
+
+// ============================================================================
+// AST
+// ============================================================================
+
+/// A parsed graph query.
+#[derive(Debug)]
+pub struct GraphQuery {
+    /// Variable names; patterns and expressions refer to them by slot.
+    vars: Vec<String>,
+    paths: Vec<PathPattern>,
+    filter: Option<Expr>,
+    distinct: bool,
+    returns: Vec<ReturnItem>,
+    order: Vec<(Expr, bool)>,
+    skip: usize,
+    limit: Option<usize>,
+}
+
+#[derive(Debug)]
+struct PathPattern {
+    start: NodePattern,
+    steps: Vec<(RelPattern, NodePattern)>,
+}
+
+#[derive(Debug)]
+struct NodePattern {
+    var: Option<usize>,
+    kinds: Vec<String>,
+    props: Vec<(String, Value)>,
+}
+
+#[derive(Debug)]
+struct RelPattern {
+    var: Option<usize>,
+    kinds: Vec<String>,
+    props: Vec<(String, Value)>,
+    direction: Direction,
+    /// Hop range for variable-length relationships.
+    hops: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Out,
+    In,
+    Both,
+}
+
+#[derive(Debug)]
+struct ReturnItem {
+    expr: Expr,
+    name: String,
+}
+
+#[derive(Debug)]
+enum Expr {
+    Literal(Value),
+    List(Vec<Expr>),
+    Var(usize),
+    Prop(usize, String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    /// Subject, source pattern, compiled pattern.
+    Regex(Box<Expr>, String, Regex),
+    Glob(Box<Expr>, String, Glob),
+    IsNull(Box<Expr>, bool),
+    Count(Option<Box<Expr>>, bool),
+    /// A `RETURN` column named in `ORDER BY`.
+    Column(usize, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    StartsWith,
+    EndsWith,
+    In,
+}
+
+impl CompareOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Eq => "=",
+            Self::Ne => "<>",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Contains => "CONTAINS",
+            Self::StartsWith => "STARTS WITH",
+            Self::EndsWith => "ENDS WITH",
+            Self::In => "IN",
+        }
+    }
+}
+
+impl Expr {
+    fn has_count(&self) -> bool {
+        match self {
+            Self::Count(..) => true,
+            Self::Literal(_) | Self::Var(_) | Self::Prop(..) | Self::Column(..) => false,
+            Self::List(items) => items.iter().any(Expr::has_count),
+            Self::Not(e) | Self::Regex(e, ..) | Self::Glob(e, ..) | Self::IsNull(e, _) => {
+                e.has_count()
+            }
+            Self::And(a, b) | Self::Or(a, b) | Self::Compare(_, a, b) => {
+                a.has_count() || b.has_count()
+            }
+        }
+    }
+
+    /// Source-like text, used for column names.
+    fn describe(&self, vars: &[String]) -> String {
+        match self {
+            Self::Literal(v) => match v {
+                Value::String(s) => format!("'{}'", s),
+                v => v.to_string(),
+            },
+            Self::List(items) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(|e| e.describe(vars))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Var(slot) => vars[*slot].clone(),
+            Self::Prop(slot, key) => format!("{}.{}", vars[*slot], key),
+            Self::Not(e) => format!("NOT {}", e.describe(vars)),
+            Self::And(a, b) => format!("{} AND {}", a.describe(vars), b.describe(vars)),
+            Self::Or(a, b) => format!("{} OR {}", a.describe(vars), b.describe(vars)),
+            Self::Compare(op, a, b) => {
+                format!("{} {} {}", a.describe(vars), op.symbol(), b.describe(vars))
+            }
+            Self::Regex(e, pattern, _) => format!("{} =~ '{}'", e.describe(vars), pattern),
+            Self::Glob(e, pattern, _) => format!("{} GLOB '{}'", e.describe(vars), pattern),
+            Self::IsNull(e, negated) => format!(
+                "{} IS {}NULL",
+                e.describe(vars),
+                if *negated { "NOT " } else { "" }
+            ),
+            Self::Count(None, _) => "count(*)".to_string(),
+            Self::Count(Some(e), distinct) => format!(
+                "count({}{})",
+                if *distinct { "DISTINCT " } else { "" },
+                e.describe(vars)
+            ),
+            Self::Column(_, name) => name.clone(),
+        }
+    }
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    /// Backtick-quoted name, never a keyword.
+    Quoted(String),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Sym(&'static str),
+    End,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    /// 1-based character column.
+    col: usize,
+}
+
+const SYMBOLS: &[&str] = &[
+    "..", "<=", ">=", "<>", "!=", "=~", "(", ")", "[", "]", "{", "}", ":", ",", ".", "|", "*", "-",
+    "<", ">", "=",
+];
+
+fn invalid(reason: impl Into<String>) -> GikError {
+    GikError::KgQueryInvalid {
+        reason: reason.into(),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, GikError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let col = i + 1;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(Token {
+                tok: Tok::Ident(word),
+                col,
+            });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            // A '.' only continues the number when a digit follows (`1..3` is a range)
+            let is_float = i + 1 < chars.len() && chars[i] == '.' && chars[i + 1].is_ascii_digit();
+            if is_float {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let tok = if is_float {
+                Tok::Float(
+                    text.parse()
+                        .map_err(|_| invalid(format!("bad number at column {}", col)))?,
+                )
+            } else {
+                Tok::Int(
+                    text.parse()
+                        .map_err(|_| invalid(format!("bad number at column {}", col)))?,
+                )
+            };
+            tokens.push(Token { tok, col });
+        } else if c == '\'' || c == '"' || c == '`' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(invalid(format!(
+                            "unterminated {} at column {}",
+                            if c == '`' { "name" } else { "string" },
+                            col
+                        )))
+                    }
+                    Some(&q) if q == c => break,
+                    Some('\\') if c != '`' => {
+                        i += 1;
+                        match chars.get(i) {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(&other) => value.push(other),
+                            None => continue,
+                        }
+                    }
+                    Some(&other) => value.push(other),
+                }
+                i += 1;
+            }
+            i += 1;
+            let tok = if c == '`' {
+                Tok::Quoted(value)
+            } else {
+                Tok::Str(value)
+            };
+            tokens.push(Token { tok, col });
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let sym = SYMBOLS
+                .iter()
+                .find(|s| rest.starts_with(**s))
+                .ok_or_else(|| invalid(format!("unexpected '{}' at column {}", c, col)))?;
+            i += sym.chars().count();
+            tokens.push(Token {
+                tok: Tok::Sym(sym),
+                col,
+            });
+        }
+    }
+
+    tokens.push(Token {
+        tok: Tok::End,
+        col: chars.len() + 1,
+    });
+    Ok(tokens)
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+/// Parse a graph query.
+///
+/// # Errors
+///
+/// Returns [`GikError::KgQueryInvalid`] with the column of the first
+/// syntax error, or for unknown variables and misplaced `count()`.
+pub fn parse_query(input: &str) -> Result<GraphQuery, GikError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+        vars: Vec::new(),
+    };
+    parser.query()
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    vars: Vec<String>,
+}
+
+impl Parser {
+    fn peek(&self) -> &Tok {
+        &self.tokens[self.pos].tok
+    }
+
+    fn peek_at(&self, offset: usize) -> &Tok {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.pos + offset).min(last)].tok
+    }
+
+    fn advance(&mut self) -> Tok {
+        let tok = self.tokens[self.pos].tok.clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        tok
+    }
+
+    fn error(&self, message: &str) -> GikError {
+        let token = &self.tokens[self.pos];
+        match &token.tok {
+            Tok::End => invalid(format!("{} at end of query", message)),
+            _ => invalid(format!("{} at column {}", message, token.col)),
+        }
+    }
+
+    fn is_sym(&self, sym: &str) -> bool {
+        matches!(self.peek(), Tok::Sym(s) if *s == sym)
+    }
+
+    fn eat_sym(&mut self, sym: &str) -> bool {
+        let found = self.is_sym(sym);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect_sym(&mut self, sym: &str) -> Result<(), GikError> {
+        if self.eat_sym(sym) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", sym)))
+        }
+    }
+
+    fn is_kw(&self, kw: &str) -> bool {
+        matches!(self.peek(), Tok::Ident(w) if w.eq_ignore_ascii_case(kw))
+    }
+
+    fn eat_kw(&mut self, kw: &str) -> bool {
+        let found = self.is_kw(kw);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect_kw(&mut self, kw: &str) -> Result<(), GikError> {
+        if self.eat_kw(kw) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", kw)))
+        }
+    }
+
+    fn name(&mut self, what: &str) -> Result<String, GikError> {
+        match self.peek().clone() {
+            Tok::Ident(name) | Tok::Quoted(name) => {
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.error(&format!("expected {}", what))),
+        }
+    }
+
+    fn count(&mut self) -> Result<usize, GikError> {
+        match self.peek().clone() {
+            Tok::Int(n) if n >= 0 => {
+                self.advance();
+                Ok(n as usize)
+            }
+            _ => Err(self.error("expected a non-negative integer")),
+        }
+    }
+
+    /// Slot of a variable introduced by a pattern.
+    fn bind(&mut self, name: String) -> usize {
+        match self.vars.iter().position(|v| *v == name) {
+            Some(slot) => slot,
+            None => {
+                self.vars.push(name);
+                self.vars.len() - 1
+            }
+        }
+    }
+
+    /// Slot of a variable used in an expression.
+    fn lookup(&self, name: &str) -> Result<usize, GikError> {
+        self.vars
+            .iter()
+            .position(|v| v == name)
+            .ok_or_else(|| invalid(format!("unknown variable '{}'", name)))
+    }
+
+    fn query(&mut self) -> Result<GraphQuery, GikError> {
+        let mut paths = Vec::new();
+        self.expect_kw("MATCH")?;
+        loop {
+            paths.push(self.path()?);
+            if !self.eat_sym(",") && !self.eat_kw("MATCH") {
+                break;
+            }
+        }
+
+        let filter = if self.eat_kw("WHERE") {
+            let expr = self.expr()?;
+            if expr.has_count() {
+                return Err(invalid("count() is only allowed in RETURN"));
+            }
+            Some(expr)
+        } else {
+            None
+        };
+
+        self.expect_kw("RETURN")?;
+        let distinct = self.eat_kw("DISTINCT");
+        let returns = self.return_items()?;
+
+        let mut order = Vec::new();
+        if self.eat_kw("ORDER") {
+            self.expect_kw("BY")?;
+            loop {
+                let expr = self.order_key(&returns)?;
+                let descending = self.eat_kw("DESC") || self.eat_kw("DESCENDING");
+                if !descending && !self.eat_kw("ASC") {
+                    self.eat_kw("ASCENDING");
+                }
+                order.push((expr, descending));
+                if !self.eat_sym(",") {
+                    break;
+                }
+            }
+        }
+
+        let skip = if self.eat_kw("SKIP") {
+            self.count()?
+        } else {
+            0
+        };
+        let limit = if self.eat_kw("LIMIT") {
+            Some(self.count()?)
+        } else {
+            None
+        };
+
+        if *self.peek() != Tok::End {
+            return Err(self.error("unexpected input"));
+        }
+
+        Ok(GraphQuery {
+            vars: std::mem::take(&mut self.vars),
+            paths,
+            filter,
+            distinct,
+            returns,
+            order,
+            skip,
+            limit,
+        })
+    }
+
+    /// An `ORDER BY` expression, or a `RETURN` alias.
+    fn order_key(&mut self, returns: &[ReturnItem]) -> Result<Expr, GikError> {
+        if let Tok::Ident(name) | Tok::Quoted(name) = self.peek().clone() {
+            let column = returns.iter().position(|item| item.name == name);
+            if let (Some(col), false) = (column, *self.peek_at(1) == Tok::Sym(".")) {
+                self.advance();
+                return Ok(Expr::Column(col, name));
+            }
+        }
+        self.expr()
+    }
+
+    fn return_items(&mut self) -> Result<Vec<ReturnItem>, GikError> {
+        if self.eat_sym("*") {
+            if self.vars.is_empty() {
+                return Err(invalid("RETURN * needs at least one named variable"));
+            }
+            return Ok((0..self.vars.len())
+                .map(|slot| ReturnItem {
+                    expr: Expr::Var(slot),
+                    name: self.vars[slot].clone(),
+                })
+                .collect());
+        }
+
+        let mut items: Vec<ReturnItem> = Vec::new();
+        loop {
+            let expr = self.expr()?;
+            if expr.has_count() && !matches!(expr, Expr::Count(..)) {
+                return Err(invalid("count() can't be nested inside another expression"));
+            }
+            let name = if self.eat_kw("AS") {
+                self.name("a column name")?
+            } else {
+                expr.describe(&self.vars)
+            };
+            if items.iter().any(|item| item.name == name) {
+                return Err(invalid(format!(
+                    "duplicate column '{}'; rename it with AS",
+                    name
+                )));
+            }
+            items.push(ReturnItem { expr, name });
+            if !self.eat_sym(",") {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn path(&mut self) -> Result<PathPattern, GikError> {
+        let start = self.node()?;
+        let mut steps = Vec::new();
+        while self.is_sym("-") || self.is_sym("<") {
+            let rel = self.rel()?;
+            let node = self.node()?;
+            steps.push((rel, node));
+        }
+        Ok(PathPattern { start, steps })
+    }
+
+    fn node(&mut self) -> Result<NodePattern, GikError> {
+        self.expect_sym("(")?;
+        let var = match self.peek() {
+            Tok::Ident(_) | Tok::Quoted(_) => {
+                let name = self.name("a variable")?;
+                Some(self.bind(name))
+            }
+            _ => None,
+        };
+        let kinds = self.kinds()?;
+        let props = self.props()?;
+        self.expect_sym(")")?;
+        Ok(NodePattern { var, kinds, props })
+    }
+
+    fn rel(&mut self) -> Result<RelPattern, GikError> {
+        let incoming = self.eat_sym("<");
+        self.expect_sym("-")?;
+
+        let mut rel = RelPattern {
+            var: None,
+            kinds: Vec::new(),
+            props: Vec::new(),
+            direction: Direction::Both,
+            hops: None,
+        };
+        if self.eat_sym("[") {
+            if let Tok::Ident(_) | Tok::Quoted(_) = self.peek() {
+                let name = self.name("a variable")?;
+                rel.var = Some(self.bind(name));
+            }
+            rel.kinds = self.kinds()?;
+            if self.eat_sym("*") {
+                rel.hops = Some(self.hops()?);
+            }
+            rel.props = self.props()?;
+            self.expect_sym("]")?;
+        }
+
+        self.expect_sym("-")?;
+        let outgoing = self.eat_sym(">");
+        rel.direction = match (incoming, outgoing) {
+            (true, true) => return Err(self.error("relationship can't point both ways")),
+            (true, false) => Direction::In,
+            (false, true) => Direction::Out,
+            (false, false) => Direction::Both,
+        };
+        if rel.var.is_some() && rel.hops.is_some() {
+            return Err(invalid(
+                "variable-length relationships can't be bound to a variable",
+            ));
+        }
+        Ok(rel)
+    }
+
+    /// `:a|b` label or type alternatives.
+    fn kinds(&mut self) -> Result<Vec<String>, GikError> {
+        let mut kinds = Vec::new();
+        if self.eat_sym(":") {
+            loop {
+                kinds.push(self.name("a kind")?);
+                if !self.eat_sym("|") {
+                    break;
+                }
+                self.eat_sym(":");
+            }
+        }
+        Ok(kinds)
+    }
+
+    /// Hop range after `*`.
+    fn hops(&mut self) -> Result<(usize, usize), GikError> {
+        let min = match self.peek() {
+            Tok::Int(_) => Some(self.count()?),
+            _ => None,
+        };
+        if self.eat_sym("..") {
+            let max = match self.peek() {
+                Tok::Int(_) => self.count()?,
+                _ => usize::MAX,
+            };
+            let min = min.unwrap_or(1);
+            if min > max {
+                return Err(self.error("empty hop range"));
+            }
+            Ok((min, max))
+        } else {
+            Ok(min.map_or((1, usize::MAX), |n| (n, n)))
+        }
+    }
+
+    fn props(&mut self) -> Result<Vec<(String, Value)>, GikError> {
+        let mut props = Vec::new();
+        if self.eat_sym("{") {
+            if self.eat_sym("}") {
+                return Ok(props);
+            }
+            loop {
+                let key = self.name("a property name")?;
+                self.expect_sym(":")?;
+                let value = match self.atom()? {
+                    Expr::Literal(value) => value,
+                    _ => return Err(invalid(format!("property '{}' must be a literal", key))),
+                };
+                props.push((key, value));
+                if !self.eat_sym(",") {
+                    break;
+                }
+            }
+            self.expect_sym("}")?;
+        }
+        Ok(props)
+    }
+
+    fn expr(&mut self) -> Result<Expr, GikError> {
+        let mut left = self.and_expr()?;
+        while self.eat_kw("OR") {
+            let right = self.and_expr()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, GikError> {
+        let mut left = self.not_expr()?;
+        while self.eat_kw("AND") {
+            let right = self.not_expr()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn not_expr(&mut self) -> Result<Expr, GikError> {
+        if self.eat_kw("NOT") {
+            Ok(Expr::Not(Box::new(self.not_expr()?)))
+        } else {
+            self.comparison()
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr, GikError> {
+        let left = self.atom()?;
+
+        let op = match self.peek() {
+            Tok::Sym("=") => Some(CompareOp::Eq),
+            Tok::Sym("<>") | Tok::Sym("!=") => Some(CompareOp::Ne),
+            Tok::Sym("<") => Some(CompareOp::Lt),
+            Tok::Sym("<=") => Some(CompareOp::Le),
+            Tok::Sym(">") => Some(CompareOp::Gt),
+            Tok::Sym(">=") => Some(CompareOp::Ge),
+            _ if self.is_kw("CONTAINS") => Some(CompareOp::Contains),
+            _ if self.is_kw("IN") => Some(CompareOp::In),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.advance();
+            let right = self.atom()?;
+            return Ok(Expr::Compare(op, Box::new(left), Box::new(right)));
+        }
+
+        if self.eat_kw("STARTS") {
+            self.expect_kw("WITH")?;
+            let right = self.atom()?;
+            return Ok(Expr::Compare(
+                CompareOp::StartsWith,
+                Box::new(left),
+                Box::new(right),
+            ));
+        }
+        if self.eat_kw("ENDS") {
+            self.expect_kw("WITH")?;
+            let right = self.atom()?;
+            return Ok(Expr::Compare(
+                CompareOp::EndsWith,
+                Box::new(left),
+                Box::new(right),
+            ));
+        }
+        if self.eat_sym("=~") {
+            let pattern = self.string("=~ needs a string pattern")?;
+            let re = Regex::new(&format!("^(?:{})$", pattern))
+                .map_err(|e| invalid(format!("invalid regex '{}': {}", pattern, e)))?;
+            return Ok(Expr::Regex(Box::new(left), pattern, re));
+        }
+        if self.eat_kw("GLOB") {
+            let pattern = self.string("GLOB needs a string pattern")?;
+            let glob = Glob::new(&pattern);
+            return Ok(Expr::Glob(Box::new(left), pattern, glob));
+        }
+        if self.eat_kw("IS") {
+            let negated = self.eat_kw("NOT");
+            self.expect_kw("NULL")?;
+            return Ok(Expr::IsNull(Box::new(left), negated));
+        }
+
+        Ok(left)
+    }
+
+    fn string(&mut self, message: &str) -> Result<String, GikError> {
+        match self.peek().clone() {
+            Tok::Str(s) => {
+                self.advance();
+                Ok(s)
+            }
+            _ => Err(self.error(message)),
+        }
+    }
+
+    fn atom(&mut self) -> Result<Expr, GikError> {
+        match self.peek().clone() {
+            Tok::Str(s) => {
+                self.advance();
+                Ok(Expr::Literal(Value::String(s)))
+            }
+            Tok::Int(n) => {
+                self.advance();
+                Ok(Expr::Literal(json!(n)))
+            }
+            Tok::Float(f) => {
+                self.advance();
+                Ok(Expr::Literal(json!(f)))
+            }
+            Tok::Sym("-") => {
+                self.advance();
+                match self.advance() {
+                    Tok::Int(n) => Ok(Expr::Literal(json!(-n))),
+                    Tok::Float(f) => Ok(Expr::Literal(json!(-f))),
+                    _ => Err(self.error("expected a number after '-'")),
+                }
+            }
+            Tok::Sym("(") => {
+                self.advance();
+                let expr = self.expr()?;
+                self.expect_sym(")")?;
+                Ok(expr)
+            }
+            Tok::Sym("[") => {
+                self.advance();
+                let mut items = Vec::new();
+                if !self.eat_sym("]") {
+                    loop {
+                        items.push(self.expr()?);
+                        if !self.eat_sym(",") {
+                            break;
+                        }
+                    }
+                    self.expect_sym("]")?;
+                }
+                Ok(Expr::List(items))
+            }
+            Tok::Ident(word) if word.eq_ignore_ascii_case("true") => {
+                self.advance();
+                Ok(Expr::Literal(Value::Bool(true)))
+            }
+            Tok::Ident(word) if word.eq_ignore_ascii_case("false") => {
+                self.advance();
+                Ok(Expr::Literal(Value::Bool(false)))
+            }
+            Tok::Ident(word) if word.eq_ignore_ascii_case("null") => {
+                self.advance();
+                Ok(Expr::Literal(Value::Null))
+            }
+            Tok::Ident(word)
+                if word.eq_ignore_ascii_case("count") && *self.peek_at(1) == Tok::Sym("(") =>
+            {
+                self.advance();
+                self.advance();
+                if self.eat_sym("*") {
+                    self.expect_sym(")")?;
+                    return Ok(Expr::Count(None, false));
+                }
+                let distinct = self.eat_kw("DISTINCT");
+                let arg = self.expr()?;
+                self.expect_sym(")")?;
+                Ok(Expr::Count(Some(Box::new(arg)), distinct))
+            }
+            Tok::Ident(word) if *self.peek_at(1) == Tok::Sym("(") => {
+                Err(self.error(&format!("unknown function '{}'", word)))
+            }
+            Tok::Ident(_) | Tok::Quoted(_) => {
+                let name = self.name("a variable")?;
+                let slot = self.lookup(&name)?;
+                if self.eat_sym(".") {
+                    let key = self.name("a property name")?;
+                    Ok(Expr::Prop(slot, key))
+                } else {
+                    Ok(Expr::Var(slot))
+                }
+            }
+            _ => Err(self.error("expected an expression")),
+        }
+    }
+}
+
+// ============================================================================
+// Graph
+// ============================================================================
+
+/// In-memory view of the nodes and edges a query can touch.
+struct Graph {
+    nodes: Vec<KgNode>,
+    by_id: HashMap<String, usize>,
+    by_kind: HashMap<String, Vec<usize>>,
+    edges: Vec<KgEdge>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Load what `query` needs from `source`.
+    fn load(source: &dyn GraphQuerySource, query: &GraphQuery) -> Result<Self, GikError> {
+        let rels: Vec<&RelPattern> = query
+            .paths
+            .iter()
+            .flat_map(|p| p.steps.iter().map(|(rel, _)| rel))
+            .collect();
+
+        let edges = if rels.iter().all(|rel| !rel.kinds.is_empty()) {
+            let kinds: HashSet<&String> = rels.iter().flat_map(|rel| &rel.kinds).collect();
+            let mut edges = Vec::new();
+            for kind in kinds {
+                edges.extend(source.edges_by_kind(kind)?);
+            }
+            edges
+        } else {
+            source.all_edges()?
+        };
+
+        // Every path must start from a labelled node or one bound earlier
+        let mut bound = HashSet::new();
+        let mut labelled_starts = true;
+        for path in &query.paths {
+            let start = &path.start;
+            if start.kinds.is_empty() && !start.var.is_some_and(|v| bound.contains(&v)) {
+                labelled_starts = false;
+            }
+            bound.extend(path.start.var);
+            bound.extend(path.steps.iter().filter_map(|(_, node)| node.var));
+        }
+
+        let nodes = if labelled_starts {
+            let kinds: HashSet<&String> = query.paths.iter().flat_map(|p| &p.start.kinds).collect();
+            let mut nodes = Vec::new();
+            for kind in kinds {
+                nodes.extend(source.nodes_by_kind(kind)?);
+            }
+            let loaded: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+            let mut missing: Vec<&str> = edges
+                .iter()
+                .flat_map(|e| [e.from.as_str(), e.to.as_str()])
+                .filter(|id| !loaded.contains(id))
+                .collect();
+            missing.sort_unstable();
+            missing.dedup();
+            for ids in missing.chunks(NODE_ID_BATCH) {
+                nodes.extend(source.nodes_by_ids(ids)?);
+            }
+            nodes
+        } else {
+            source.all_nodes()?
+        };
+
+        Ok(Self::new(nodes, edges))
+    }
+
+    fn new(nodes: Vec<KgNode>, edges: Vec<KgEdge>) -> Self {
+        let mut graph = Self {
+            nodes: Vec::new(),
+            by_id: HashMap::new(),
+            by_kind: HashMap::new(),
+            edges: Vec::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        };
+
+        for node in nodes {
+            if graph.by_id.contains_key(&node.id) {
+                continue;
+            }
+            let index = graph.nodes.len();
+            graph.by_id.insert(node.id.clone(), index);
+            graph
+                .by_kind
+                .entry(node.kind.clone())
+                .or_default()
+                .push(index);
+            graph.nodes.push(node);
+        }
+        graph.outgoing = vec![Vec::new(); graph.nodes.len()];
+        graph.incoming = vec![Vec::new(); graph.nodes.len()];
+
+        let mut seen = HashSet::new();
+        for edge in edges {
+            // Dangling edges can't be matched
+            let (Some(&from), Some(&to)) = (graph.by_id.get(&edge.from), graph.by_id.get(&edge.to))
+            else {
+                continue;
+            };
+            if !seen.insert(edge.id.clone()) {
+                continue;
+            }
+            let index = graph.edges.len();
+            graph.outgoing[from].push(index);
+            graph.incoming[to].push(index);
+            graph.edges.push(edge);
+        }
+
+        graph
+    }
+
+    fn candidates(&self, pattern: &NodePattern) -> Vec<usize> {
+        if pattern.kinds.is_empty() {
+            return (0..self.nodes.len())
+                .filter(|&n| self.node_matches(n, pattern))
+                .collect();
+        }
+        let mut nodes: Vec<usize> = pattern
+            .kinds
+            .iter()
+            .filter_map(|kind| self.by_kind.get(kind))
+            .flatten()
+            .copied()
+            .filter(|&n| self.node_matches(n, pattern))
+            .collect();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    fn node_matches(&self, index: usize, pattern: &NodePattern) -> bool {
+        let node = &self.nodes[index];
+        (pattern.kinds.is_empty() || pattern.kinds.contains(&node.kind))
+            && pattern
+                .props
+                .iter()
+                .all(|(key, value)| values_equal(&node_field(node, key), value) == Some(true))
+    }
+
+    fn edge_matches(&self, index: usize, pattern: &RelPattern) -> bool {
+        let edge = &self.edges[index];
+        (pattern.kinds.is_empty() || pattern.kinds.contains(&edge.kind))
+            && pattern
+                .props
+                .iter()
+                .all(|(key, value)| values_equal(&edge_field(edge, key), value) == Some(true))
+    }
+
+    /// Edges matching `pattern` at `node`, with the node at the other end.
+    fn neighbours(&self, node: usize, pattern: &RelPattern) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        if pattern.direction != Direction::In {
+            for &e in &self.outgoing[node] {
+                if self.edge_matches(e, pattern) {
+                    found.push((e, self.by_id[&self.edges[e].to]));
+                }
+            }
+        }
+        if pattern.direction != Direction::Out {
+            for &e in &self.incoming[node] {
+                let edge = &self.edges[e];
+                // A self-loop was already found as outgoing
+                let self_loop = pattern.direction == Direction::Both && edge.from == edge.to;
+                if !self_loop && self.edge_matches(e, pattern) {
+                    found.push((e, self.by_id[&edge.from]));
+                }
+            }
+        }
+        found
+    }
+
+    /// Nodes reachable from `start` within the pattern's hop range.
+    fn reachable(
+        &self,
+        start: usize,
+        pattern: &RelPattern,
+        (min, max): (usize, usize),
+    ) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([(start, 0usize)]);
+
+        while let Some((node, depth)) = queue.pop_front() {
+            if depth >= min {
+                found.push(node);
+            }
+            if depth == max {
+                continue;
+            }
+            for (_, next) in self.neighbours(node, pattern) {
+                if visited.insert(next) {
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+
+        found
+    }
+}
+
+fn node_field(node: &KgNode, key: &str) -> Value {
+    match key {
+        "id" => json!(node.id),
+        "kind" => json!(node.kind),
+        "label" => json!(node.label),
+        "branch" => json!(node.branch),
+        _ => node.props.get(key).cloned().unwrap_or(Value::Null),
+    }
+}
+
+fn edge_field(edge: &KgEdge, key: &str) -> Value {
+    match key {
+        "id" => json!(edge.id),
+        "kind" => json!(edge.kind),
+        "from" => json!(edge.from),
+        "to" => json!(edge.to),
+        "branch" => json!(edge.branch),
+        _ => edge.props.get(key).cloned().unwrap_or(Value::Null),
+    }
+}
+
+// ============================================================================
+// Evaluation
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Bound {
+    Node(usize),
+    Edge(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    slots: Vec<Option<Bound>>,
+    /// Edges matched so far, so each is used once.
+    edges: Vec<usize>,
+}
+
+/// Run a parsed query against a KG source.
+///
+/// # Errors
+///
+/// Returns a store error, or [`GikError::KgQueryInvalid`] when the match
+/// grows past [`MAX_QUERY_MATCHES`] rows.
+pub fn execute_query(
+    source: &dyn GraphQuerySource,
+    query: &GraphQuery,
+) -> Result<GraphQueryResult, GikError> {
+    let graph = Graph::load(source, query)?;
+    let eval = Evaluator {
+        graph: &graph,
+        query,
+    };
+
+    let mut rows = vec![Row {
+        slots: vec![None; query.vars.len()],
+        edges: Vec::new(),
+    }];
+    for path in &query.paths {
+        rows = eval.match_path(rows, path)?;
+    }
+    if let Some(filter) = &query.filter {
+        rows.retain(|row| eval.eval(filter, row) == Value::Bool(true));
+    }
+
+    let columns = query.returns.iter().map(|item| item.name.clone()).collect();
+    let rows = if query.returns.iter().any(|item| item.expr.has_count()) {
+        eval.aggregate(&rows)?
+    } else {
+        eval.project(&rows)
+    };
+
+    let rows = rows
+        .into_iter()
+        .skip(query.skip)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect();
+    Ok(GraphQueryResult { columns, rows })
+}
+
+struct Evaluator<'a> {
+    graph: &'a Graph,
+    query: &'a GraphQuery,
+}
+
+impl Evaluator<'_> {
+    fn match_path(&self, rows: Vec<Row>, path: &PathPattern) -> Result<Vec<Row>, GikError> {
+        let mut matched = Vec::new();
+        for row in rows {
+            let starts = match path.start.var.and_then(|v| row.slots[v]) {
+                Some(Bound::Node(n)) if self.graph.node_matches(n, &path.start) => vec![n],
+                Some(_) => Vec::new(),
+                None => self.graph.candidates(&path.start),
+            };
+            for start in starts {
+                let mut row = row.clone();
+                if let Some(v) = path.start.var {
+                    row.slots[v] = Some(Bound::Node(start));
+                }
+                self.match_steps(row, start, &path.steps, &mut matched)?;
+            }
+        }
+        Ok(matched)
+    }
+
+    fn match_steps(
+        &self,
+        row: Row,
+        at: usize,
+        steps: &[(RelPattern, NodePattern)],
+        matched: &mut Vec<Row>,
+    ) -> Result<(), GikError> {
+        let Some(((rel, node), rest)) = steps.split_first() else {
+            if matched.len() >= MAX_QUERY_MATCHES {
+                return Err(invalid(format!(
+                    "query matched more than {} rows; narrow the pattern",
+                    MAX_QUERY_MATCHES
+                )));
+            }
+            matched.push(row);
+            return Ok(());
+        };
+
+        let hops: Vec<(Option<usize>, usize)> = match rel.hops {
+            Some(range) => self
+                .graph
+                .reachable(at, rel, range)
+                .into_iter()
+                .map(|n| (None, n))
+                .collect(),
+            None => self
+                .graph
+                .neighbours(at, rel)
+                .into_iter()
+                .filter(|(e, _)| !row.edges.contains(e))
+                .map(|(e, n)| (Some(e), n))
+                .collect(),
+        };
+
+        for (edge, next) in hops {
+            if !self.graph.node_matches(next, node) {
+                continue;
+            }
+            let mut row = row.clone();
+            if !bind(&mut row, node.var, Bound::Node(next)) {
+                continue;
+            }
+            if let Some(e) = edge {
+                if !bind(&mut row, rel.var, Bound::Edge(e)) {
+                    continue;
+                }
+                row.edges.push(e);
+            }
+            self.match_steps(row, next, rest, matched)?;
+        }
+        Ok(())
+    }
+
+    fn eval(&self, expr: &Expr, row: &Row) -> Value {
+        match expr {
+            Expr::Literal(value) => value.clone(),
+            Expr::List(items) => Value::Array(items.iter().map(|e| self.eval(e, row)).collect()),
+            Expr::Var(slot) => match row.slots[*slot] {
+                Some(Bound::Node(n)) => node_json(&self.graph.nodes[n]),
+                Some(Bound::Edge(e)) => edge_json(&self.graph.edges[e]),
+                None => Value::Null,
+            },
+            Expr::Prop(slot, key) => match row.slots[*slot] {
+                Some(Bound::Node(n)) => node_field(&self.graph.nodes[n], key),
+                Some(Bound::Edge(e)) => edge_field(&self.graph.edges[e], key),
+                None => Value::Null,
+            },
+            Expr::Not(e) => truth(&self.eval(e, row)).map_or(Value::Null, |b| json!(!b)),
+            Expr::And(a, b) => match (truth(&self.eval(a, row)), truth(&self.eval(b, row))) {
+                (Some(false), _) | (_, Some(false)) => json!(false),
+                (Some(true), Some(true)) => json!(true),
+                _ => Value::Null,
+            },
+            Expr::Or(a, b) => match (truth(&self.eval(a, row)), truth(&self.eval(b, row))) {
+                (Some(true), _) | (_, Some(true)) => json!(true),
+                (Some(false), Some(false)) => json!(false),
+                _ => Value::Null,
+            },
+            Expr::Compare(op, a, b) => compare(*op, &self.eval(a, row), &self.eval(b, row))
+                .map_or(Value::Null, Value::Bool),
+            Expr::Regex(e, _, re) => match self.eval(e, row) {
+                Value::String(s) => json!(re.is_match(&s)),
+                _ => Value::Null,
+            },
+            Expr::Glob(e, _, glob) => match self.eval(e, row) {
+                Value::String(s) => json!(glob.is_match(&s)),
+                _ => Value::Null,
+            },
+            Expr::IsNull(e, negated) => json!(self.eval(e, row).is_null() != *negated),
+            // Aggregates and columns are resolved after matching
+            Expr::Count(..) | Expr::Column(..) => Value::Null,
+        }
+    }
+
+    fn project(&self, rows: &[Row]) -> Vec<Vec<Value>> {
+        let order_columns = self.order_columns();
+        let mut projected: Vec<(Vec<Value>, Vec<Value>)> = rows
+            .iter()
+            .map(|row| {
+                let values: Vec<Value> = self
+                    .query
+                    .returns
+                    .iter()
+                    .map(|item| self.eval(&item.expr, row))
+                    .collect();
+                let keys = self
+                    .query
+                    .order
+                    .iter()
+                    .zip(&order_columns)
+                    .map(|((expr, _), col)| match col {
+                        Some(col) => values[*col].clone(),
+                        None => self.eval(expr, row),
+                    })
+                    .collect();
+                (keys, values)
+            })
+            .collect();
+        projected.sort_by(|a, b| self.compare_keys(&a.0, &b.0));
+
+        let rows = projected.into_iter().map(|(_, values)| values);
+        if self.query.distinct {
+            let mut seen = HashSet::new();
+            rows.filter(|values| seen.insert(Value::Array(values.clone()).to_string()))
+                .collect()
+        } else {
+            rows.collect()
+        }
+    }
+
+    fn aggregate(&self, rows: &[Row]) -> Result<Vec<Vec<Value>>, GikError> {
+        let returns = &self.query.returns;
+        let mut groups: Vec<Vec<Value>> = Vec::new();
+        let mut group_index: HashMap<String, usize> = HashMap::new();
+        let mut counts: Vec<Vec<usize>> = Vec::new();
+        let mut distinct_seen: Vec<Vec<HashSet<String>>> = Vec::new();
+
+        for row in rows {
+            let keys: Vec<Value> = returns
+                .iter()
+                .map(|item| match &item.expr {
+                    Expr::Count(..) => Value::Null,
+                    expr => self.eval(expr, row),
+                })
+                .collect();
+            let key = Value::Array(keys.clone()).to_string();
+            let group = *group_index.entry(key).or_insert_with(|| {
+                groups.push(keys);
+                counts.push(vec![0; returns.len()]);
+                distinct_seen.push(vec![HashSet::new(); returns.len()]);
+                groups.len() - 1
+            });
+
+            for (col, item) in returns.iter().enumerate() {
+                match &item.expr {
+                    Expr::Count(None, _) => counts[group][col] += 1,
+                    Expr::Count(Some(arg), distinct) => {
+                        let value = self.eval(arg, row);
+                        if value.is_null() {
+                            continue;
+                        }
+                        if !*distinct || distinct_seen[group][col].insert(value.to_string()) {
+                            counts[group][col] += 1;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // count(*) over no matches is still one row, when nothing is grouped
+        if groups.is_empty() && returns.iter().all(|item| item.expr.has_count()) {
+            groups.push(vec![Value::Null; returns.len()]);
+            counts.push(vec![0; returns.len()]);
+        }
+
+        let mut result: Vec<Vec<Value>> = groups
+            .into_iter()
+            .zip(counts)
+            .map(|(mut values, counts)| {
+                for (col, item) in returns.iter().enumerate() {
+                    if item.expr.has_count() {
+                        values[col] = json!(counts[col]);
+                    }
+                }
+                values
+            })
+            .collect();
+
+        let columns = self
+            .order_columns()
+            .into_iter()
+            .map(|col| {
+                col.ok_or_else(|| invalid("with count(), ORDER BY must use a returned column"))
+            })
+            .collect::<Result<Vec<usize>, GikError>>()?;
+        let key =
+            |row: &[Value]| -> Vec<Value> { columns.iter().map(|&c| row[c].clone()).collect() };
+        result.sort_by(|a, b| self.compare_keys(&key(a), &key(b)));
+
+        Ok(result)
+    }
+
+    /// `RETURN` column of each `ORDER BY` key, by alias or by expression.
+    fn order_columns(&self) -> Vec<Option<usize>> {
+        self.query
+            .order
+            .iter()
+            .map(|(expr, _)| {
+                if let Expr::Column(col, _) = expr {
+                    return Some(*col);
+                }
+                let text = expr.describe(&self.query.vars);
+                self.query
+                    .returns
+                    .iter()
+                    .position(|item| item.expr.describe(&self.query.vars) == text)
+            })
+            .collect()
+    }
+
+    fn compare_keys(&self, a: &[Value], b: &[Value]) -> Ordering {
+        for (i, (_, descending)) in self.query.order.iter().enumerate() {
+            let ordering = compare_values(&a[i], &b[i]);
+            let ordering = if *descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+/// Bind a pattern variable, or check it against an earlier binding.
+fn bind(row: &mut Row, var: Option<usize>, value: Bound) -> bool {
+    match var {
+        None => true,
+        Some(v) => match row.slots[v] {
+            Some(existing) => existing == value,
+            None => {
+                row.slots[v] = Some(value);
+                true
+            }
+        },
+    }
+}
+
+fn node_json(node: &KgNode) -> Value {
+    json!({
+        "id": node.id,
+        "kind": node.kind,
+        "label": node.label,
+        "props": node.props,
+    })
+}
+
+fn edge_json(edge: &KgEdge) -> Value {
+    json!({
+        "id": edge.id,
+        "kind": edge.kind,
+        "from": edge.from,
+        "to": edge.to,
+        "props": edge.props,
+    })
+}
+
+fn truth(value: &Value) -> Option<bool> {
+    value.as_bool()
+}
+
+fn values_equal(a: &Value, b: &Value) -> Option<bool> {
+    match (a, b) {
+        (Value::Null, _) | (_, Value::Null) => None,
+        (Value::Number(x), Value::Number(y)) => Some(x.as_f64() == y.as_f64()),
+        _ => Some(a == b),
+    }
+}
+
+fn compare(op: CompareOp, a: &Value, b: &Value) -> Option<bool> {
+    match op {
+        CompareOp::Eq => values_equal(a, b),
+        CompareOp::Ne => values_equal(a, b).map(|eq| !eq),
+        CompareOp::Lt | CompareOp::Le | CompareOp::Gt | CompareOp::Ge => {
+            let ordering = match (a, b) {
+                (Value::Number(x), Value::Number(y)) => x.as_f64()?.partial_cmp(&y.as_f64()?)?,
+                (Value::String(x), Value::String(y)) => x.cmp(y),
+                _ => return None,
+            };
+            Some(match op {
+                CompareOp::Lt => ordering == Ordering::Less,
+                CompareOp::Le => ordering != Ordering::Greater,
+                CompareOp::Gt => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            })
+        }
+        CompareOp::Contains | CompareOp::StartsWith | CompareOp::EndsWith => {
+            let (Value::String(x), Value::String(y)) = (a, b) else {
+                return None;
+            };
+            Some(match op {
+                CompareOp::Contains => x.contains(y.as_str()),
+                CompareOp::StartsWith => x.starts_with(y.as_str()),
+                _ => x.ends_with(y.as_str()),
+            })
+        }
+        CompareOp::In => match b {
+            Value::Array(items) if !a.is_null() => {
+                Some(items.iter().any(|item| values_equal(a, item) == Some(true)))
+            }
+            _ => None,
+        },
+    }
+}
+
+/// Sort order: numbers, strings, booleans, other values, then nulls.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    fn rank(v: &Value) -> u8 {
+        match v {
+            Value::Number(_) => 0,
+            Value::String(_) => 1,
+            Value::Bool(_) => 2,
+            Value::Array(_) | Value::Object(_) => 3,
+            Value::Null => 4,
+        }
+    }
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .partial_cmp(&y.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
+        _ if rank(a) == rank(b) => a.to_string().cmp(&b.to_string()),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MemoryGraph {
+        nodes: Vec<KgNode>,
+        edges: Vec<KgEdge>,
+    }
+
+    impl GraphQuerySource for MemoryGraph {
+        fn all_nodes(&self) -> Result<Vec<KgNode>, GikError> {
+            Ok(self.nodes.clone())
+        }
+
+        fn nodes_by_kind(&self, kind: &str) -> Result<Vec<KgNode>, GikError> {
+            Ok(self
+                .nodes
+                .iter()
+                .filter(|n| n.kind == kind)
+                .cloned()
+                .collect())
+        }
+
+        fn nodes_by_ids(&self, ids: &[&str]) -> Result<Vec<KgNode>, GikError> {
+            Ok(self
+                .nodes
+                .iter()
+                .filter(|n| ids.contains(&n.id.as_str()))
+                .cloned()
+                .collect())
+        }
+
+        fn all_edges(&self) -> Result<Vec<KgEdge>, GikError> {
+            Ok(self.edges.clone())
+        }
+
+        fn edges_by_kind(&self, kind: &str) -> Result<Vec<KgEdge>, GikError> {
+            Ok(self
+                .edges
+                .iter()
+                .filter(|e| e.kind == kind)
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn file(path: &str) -> KgNode {
+        KgNode::new(format!("file:{}", path), "file", path).with_props(json!({ "path": path }))
+    }
+
+    fn function(path: &str, name: &str, line: u32) -> KgNode {
+        KgNode::new(
+            format!("sym:ts:{}:function:{}", path, name),
+            "function",
+            name,
+        )
+        .with_props(json!({ "path": path, "startLine": line }))
+    }
+
+    fn endpoint(path: &str, method: &str, route: &str) -> KgNode {
+        KgNode::new(
+            format!("endpoint:{}:{}:{}", path, method, route),
+            "endpoint",
+            route,
+        )
+        .with_props(json!({ "path": path, "httpMethod": method, "route": route }))
+    }
+
+    fn edge(from: &str, to: &str, kind: &str) -> KgEdge {
+        KgEdge::new(from, to, kind)
+    }
+
+    fn graph() -> MemoryGraph {
+        MemoryGraph {
+            nodes: vec![
+                file("src/api/users.ts"),
+                file("src/api/health.ts"),
+                file("src/routes/users.ts"),
+                file("src/routes/status.ts"),
+                file("src/db.ts"),
+                function("src/api/users.ts", "listUsers", 3),
+                function("src/api/users.ts", "createUser", 12),
+                function("src/api/health.ts", "ping", 1),
+                endpoint("src/routes/users.ts", "POST", "/users"),
+                endpoint("src/routes/status.ts", "GET", "/status"),
+            ],
+            edges: vec![
+                edge(
+                    "file:src/api/users.ts",
+                    "sym:ts:src/api/users.ts:function:listUsers",
+                    "defines",
+                ),
+                edge(
+                    "file:src/api/users.ts",
+                    "sym:ts:src/api/users.ts:function:createUser",
+                    "defines",
+                ),
+                edge(
+                    "file:src/api/health.ts",
+                    "sym:ts:src/api/health.ts:function:ping",
+                    "defines",
+                ),
+                edge(
+                    "file:src/api/users.ts",
+                    "file:src/routes/users.ts",
+                    "imports",
+                ),
+                edge(
+                    "file:src/api/health.ts",
+                    "file:src/routes/status.ts",
+                    "imports",
+                ),
+                edge(
+                    "file:src/routes/users.ts",
+                    "endpoint:src/routes/users.ts:POST:/users",
+                    "definesEndpoint",
+                ),
+                edge(
+                    "file:src/routes/status.ts",
+                    "endpoint:src/routes/status.ts:GET:/status",
+                    "definesEndpoint",
+                ),
+                edge("file:src/routes/users.ts", "file:src/db.ts", "imports"),
+                edge("file:src/db.ts", "file:src/missing.ts", "imports"),
+            ],
+        }
+    }
+
+    fn run(query: &str) -> GraphQueryResult {
+        execute_query(&graph(), &parse_query(query).expect("parse")).expect("execute")
+    }
+
+    fn column(result: &GraphQueryResult, col: usize) -> Vec<Value> {
+        result.rows.iter().map(|row| row[col].clone()).collect()
+    }
+
+    #[test]
+    fn test_structural_query() {
+        let result = run("MATCH (f:file)-[:defines]->(fn:function), \
+                   (f)-[:imports]->(:file)-[:definesEndpoint]->(e:endpoint) \
+             WHERE f.path GLOB 'src/api/**' AND e.httpMethod = 'POST' \
+             RETURN fn.label AS name, e.route ORDER BY fn.startLine DESC");
+
+        assert_eq!(result.columns, ["name", "e.route"]);
+        assert_eq!(
+            result.rows,
+            [
+                vec![json!("createUser"), json!("/users")],
+                vec![json!("listUsers"), json!("/users")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_directions_and_inline_props() {
+        let result = run("MATCH (f {path: 'src/db.ts'})<-[:imports]-(src) RETURN src.path");
+        assert_eq!(column(&result, 0), [json!("src/routes/users.ts")]);
+
+        let result = run("MATCH (:file {path: 'src/db.ts'})-[:imports]-(other) RETURN other.path");
+        assert_eq!(column(&result, 0), [json!("src/routes/users.ts")]);
+
+        let result =
+            run("MATCH (a)-[r:definesEndpoint]->(e) WHERE e.route = '/users' RETURN r.from");
+        assert_eq!(column(&result, 0), [json!("file:src/routes/users.ts")]);
+    }
+
+    #[test]
+    fn test_variable_length_paths() {
+        let result = run(
+            "MATCH (f:file {path: 'src/api/users.ts'})-[:imports*]->(dep) \
+             RETURN dep.path ORDER BY dep.path",
+        );
+        // The edge to a missing file is dangling and skipped
+        assert_eq!(
+            column(&result, 0),
+            [json!("src/db.ts"), json!("src/routes/users.ts")]
+        );
+
+        let result =
+            run("MATCH (f:file {path: 'src/api/users.ts'})-[:imports*2..]->(dep) RETURN dep.path");
+        assert_eq!(column(&result, 0), [json!("src/db.ts")]);
+
+        let result = run("MATCH (f:file {path: 'src/db.ts'})-[:imports*0..1]-(x) RETURN count(*)");
+        assert_eq!(result.rows, [vec![json!(2)]]);
+    }
+
+    #[test]
+    fn test_count_groups_by_other_columns() {
+        let result = run("MATCH (f:file)-[:defines]->(fn:function) \
+             RETURN f.path, count(fn) AS functions ORDER BY functions DESC");
+        assert_eq!(
+            result.rows,
+            [
+                vec![json!("src/api/users.ts"), json!(2)],
+                vec![json!("src/api/health.ts"), json!(1)],
+            ]
+        );
+
+        let result = run("MATCH (n:endpoint) WHERE n.httpMethod = 'PUT' RETURN count(*)");
+        assert_eq!(result.rows, [vec![json!(0)]]);
+
+        let result = run("MATCH (fn:function) RETURN count(DISTINCT fn.path) AS files");
+        assert_eq!(result.rows, [vec![json!(2)]]);
+    }
+
+    #[test]
+    fn test_predicates() {
+        let names = |query: &str| {
+            let result = run(query);
+            column(&result, 0)
+        };
+
+        assert_eq!(
+            names("MATCH (n:function) WHERE n.label STARTS WITH 'list' RETURN n.label"),
+            [json!("listUsers")]
+        );
+        assert_eq!(
+            names("MATCH (n:function) WHERE n.label =~ '.*User' RETURN n.label ORDER BY n.label"),
+            [json!("createUser")]
+        );
+        assert_eq!(
+            names(
+                "MATCH (n:function) WHERE n.startLine >= 3 AND NOT n.label CONTAINS 'list' \
+                 RETURN n.label"
+            ),
+            [json!("createUser")]
+        );
+        assert_eq!(
+            names("MATCH (n:endpoint) WHERE n.httpMethod IN ['GET', 'PUT'] RETURN n.route"),
+            [json!("/status")]
+        );
+        assert_eq!(
+            names(
+                "MATCH (n:file) WHERE n.missing IS NULL AND n.path ENDS WITH 'db.ts' RETURN n.id"
+            ),
+            [json!("file:src/db.ts")]
+        );
+        // Comparisons with null are never true
+        assert!(
+            names("MATCH (n:file) WHERE n.missing = 1 OR n.missing <> 1 RETURN n.id").is_empty()
+        );
+    }
+
+    #[test]
+    fn test_projection_modifiers() {
+        let result = run(
+            "MATCH (f:file)-[:imports]->(g) RETURN DISTINCT f.path ORDER BY f.path SKIP 1 LIMIT 2",
+        );
+        assert_eq!(
+            column(&result, 0),
+            [json!("src/api/users.ts"), json!("src/routes/users.ts")]
+        );
+
+        let result = run("MATCH (e:endpoint {route: '/users'}) RETURN *");
+        assert_eq!(result.columns, ["e"]);
+        assert_eq!(
+            result.rows[0][0]["id"],
+            "endpoint:src/routes/users.ts:POST:/users"
+        );
+        assert_eq!(result.row_objects()[0]["e"]["props"]["httpMethod"], "POST");
+    }
+
+    #[test]
+    fn test_edges_are_used_once() {
+        let result = run(
+            "MATCH (a:file)-[:imports]-(b)-[:imports]-(c) WHERE a.path = 'src/db.ts' RETURN c.path",
+        );
+        assert_eq!(column(&result, 0), [json!("src/api/users.ts")]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |query: &str| parse_query(query).unwrap_err().to_string();
+
+        assert!(error("MATCH (n:file RETURN n").contains("expected ')' at column 15"));
+        assert!(error("MATCH (n) RETURN m").contains("unknown variable 'm'"));
+        assert!(error("MATCH (n) WHERE count(*) > 1 RETURN n").contains("only allowed in RETURN"));
+        assert!(error("MATCH (n)<-[:x]->(m) RETURN n").contains("both ways"));
+        assert!(error("MATCH (n)-[r*]->(m) RETURN n").contains("variable-length"));
+        assert!(error("MATCH (n) RETURN n.id, n.id").contains("duplicate column"));
+        assert!(error("MATCH (n) RETURN n LIMIT").contains("at end of query"));
+        assert!(error("MATCH (n) RETURN size(n)").contains("unknown function 'size'"));
+        assert!(error("RETURN 1").contains("expected MATCH"));
+    }
+
+    #[test]
+    fn test_loads_only_needed_kinds() {
+        use std::cell::RefCell;
+
+        struct Recording {
+            inner: MemoryGraph,
+            calls: RefCell<Vec<String>>,
+        }
+
+        impl GraphQuerySource for Recording {
+            fn all_nodes(&self) -> Result<Vec<KgNode>, GikError> {
+                self.calls.borrow_mut().push("all_nodes".into());
+                self.inner.all_nodes()
+            }
+            fn nodes_by_kind(&self, kind: &str) -> Result<Vec<KgNode>, GikError> {
+                self.calls.borrow_mut().push(format!("nodes:{}", kind));
+                self.inner.nodes_by_kind(kind)
+            }
+            fn nodes_by_ids(&self, ids: &[&str]) -> Result<Vec<KgNode>, GikError> {
+                self.calls.borrow_mut().push(format!("ids:{}", ids.len()));
+                self.inner.nodes_by_ids(ids)
+            }
+            fn all_edges(&self) -> Result<Vec<KgEdge>, GikError> {
+                self.calls.borrow_mut().push("all_edges".into());
+                self.inner.all_edges()
+            }
+            fn edges_by_kind(&self, kind: &str) -> Result<Vec<KgEdge>, GikError> {
+                self.calls.borrow_mut().push(format!("edges:{}", kind));
+                self.inner.edges_by_kind(kind)
+            }
+        }
+
+        let source = Recording {
+            inner: graph(),
+            calls: RefCell::new(Vec::new()),
+        };
+        let query =
+            parse_query("MATCH (e:endpoint)<-[:definesEndpoint]-(f) RETURN f.path").unwrap();
+        let result = execute_query(&source, &query).unwrap();
+
+        assert_eq!(result.rows.len(), 2);
+        assert_eq!(
+            *source.calls.borrow(),
+            ["edges:definesEndpoint", "nodes:endpoint", "ids:2"]
+        );
+    }
+}
@@ -0,0 +1,1041 @@
+//! Language-aware import resolution for the KG.
+//!
+//! JS/TS, Rust and Python imports are resolved by the relative-path logic in
+//! [`super::extractor`]. The languages handled here name their dependencies by
+//! module, package or namespace rather than by path, so resolving them needs
+//! project metadata:
+//!
+//! | Language | Import form | Resolution |
+//! |----------|-------------|------------|
+//! | Go | `import "example.com/app/internal/billing"` | Module path from the nearest `go.mod`, then every non-test `.go` file in the package directory |
+//! | Java / Kotlin | `import com.acme.billing.Invoice` | `package` declarations, falling back to `com/acme/billing/Invoice.{java,kt}` |
+//! | C# | `using Acme.Billing;` | Files declaring `namespace Acme.Billing` |
+//! | PHP | `use App\Models\User;`, `require __DIR__ . '/x.php'` | PSR-4 prefixes from `composer.json`, relative paths for `require`/`include` |
+//! | Ruby | `require_relative "x"`, `require "x"` | Relative to the file, or under a `lib/` load path |
+//! | C / C++ | `#include "x.h"`, `#include <x.h>` | Including directory, then `include/`, `inc/`, `src/` and the workspace root |
+//!
+//! Imports that don't resolve to a known file (standard library, third-party
+//! packages, system headers) produce no edge.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use regex::Regex;
+
+// ============================================================================
+// Import References
+// ============================================================================
+
+/// How an import target should be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    /// A relative or absolute file path (JS/TS, Rust, Python).
+    Path,
+    /// A Go package import path.
+    GoPackage,
+    /// A fully qualified Java/Kotlin name, optionally ending in `.*`.
+    JvmName,
+    /// A C# namespace or type (`using`, `using static`, aliases).
+    CsNamespace,
+    /// A fully qualified PHP class (`use App\Models\User`).
+    PhpClass,
+    /// A PHP `require`/`include` file path.
+    PhpFile,
+    /// A Ruby `require_relative` path.
+    RubyRelative,
+    /// A Ruby `require` path looked up on the load path.
+    RubyLoadPath,
+    /// A C/C++ `#include "..."`.
+    CLocal,
+    /// A C/C++ `#include <...>`.
+    CSystem,
+}
+
+/// A raw import found in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRef {
+    /// Import target as written in the source.
+    pub raw: String,
+    /// How the target should be resolved.
+    pub kind: ImportKind,
+}
+
+impl ImportRef {
+    pub fn new(raw: impl Into<String>, kind: ImportKind) -> Self {
+        Self {
+            raw: raw.into(),
+            kind,
+        }
+    }
+}
+
+// ============================================================================
+// Extraction
+// ============================================================================
+
+/// Extract imports for the languages resolved by [`ImportResolver`].
+///
+/// Returns an empty list for other file types.
+pub fn extract_imports(text: &str, file_path: &str) -> Vec<ImportRef> {
+    match extension(file_path) {
+        "go" => extract_go_imports(text),
+        "java" | "kt" | "kts" => extract_jvm_imports(text),
+        "cs" => extract_csharp_imports(text),
+        "php" => extract_php_imports(text),
+        "rb" => extract_ruby_imports(text),
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => extract_c_includes(text),
+        _ => Vec::new(),
+    }
+}
+
+/// Extract Go imports (single imports and import blocks).
+fn extract_go_imports(text: &str) -> Vec<ImportRef> {
+    let single_re =
+        Regex::new(r#"(?m)^\s*import\s+(?:[\w.]+\s+)?"([^"]+)""#).expect("Invalid regex");
+    let block_re = Regex::new(r#"(?m)^\s*import\s*\(([^)]*)\)"#).expect("Invalid regex");
+    let spec_re = Regex::new(r#"(?m)^\s*(?:[\w.]+\s+)?"([^"]+)""#).expect("Invalid regex");
+
+    let mut imports = Vec::new();
+    for cap in single_re.captures_iter(text) {
+        imports.push(ImportRef::new(&cap[1], ImportKind::GoPackage));
+    }
+    for block in block_re.captures_iter(text) {
+        for cap in spec_re.captures_iter(&block[1]) {
+            imports.push(ImportRef::new(&cap[1], ImportKind::GoPackage));
+        }
+    }
+    imports
+}
+
+/// Extract Java/Kotlin imports, including static and wildcard imports.
+fn extract_jvm_imports(text: &str) -> Vec<ImportRef> {
+    let import_re = Regex::new(r#"(?m)^\s*import\s+(?:static\s+)?(\w+(?:\.\w+)*(?:\.\*)?)"#)
+        .expect("Invalid regex");
+
+    import_re
+        .captures_iter(text)
+        .map(|cap| ImportRef::new(&cap[1], ImportKind::JvmName))
+        .collect()
+}
+
+/// Extract C# `using` directives (plain, `static`, `global` and aliases).
+fn extract_csharp_imports(text: &str) -> Vec<ImportRef> {
+    let using_re =
+        Regex::new(r#"(?m)^\s*(?:global\s+)?using\s+(?:static\s+)?(?:\w+\s*=\s*)?([\w.]+)\s*;"#)
+            .expect("Invalid regex");
+
+    using_re
+        .captures_iter(text)
+        .map(|cap| ImportRef::new(&cap[1], ImportKind::CsNamespace))
+        .collect()
+}
+
+/// Extract PHP `use` statements (including group use) and file includes.
+fn extract_php_imports(text: &str) -> Vec<ImportRef> {
+    let use_re = Regex::new(r#"(?m)^\s*use\s+([\\\w]+\\)\{([^}]*)\}\s*;|^\s*use\s+([^;{(]+);"#)
+        .expect("Invalid regex");
+    let include_re = Regex::new(
+        r#"(?:require|include)(?:_once)?\s*\(?\s*(?:__DIR__\s*\.\s*)?['"]([^'"]+\.php)['"]"#,
+    )
+    .expect("Invalid regex");
+
+    let mut imports = Vec::new();
+    for cap in use_re.captures_iter(text) {
+        let (prefix, names) = match (cap.get(1), cap.get(2), cap.get(3)) {
+            (Some(prefix), Some(names), _) => (prefix.as_str(), names.as_str()),
+            (_, _, Some(names)) => ("", names.as_str()),
+            _ => continue,
+        };
+        for name in names.split(',') {
+            // Drop aliases; `use function`/`use const` don't name a class file.
+            let name = name.split_whitespace().next().unwrap_or("");
+            if name.is_empty() || name == "function" || name == "const" {
+                continue;
+            }
+            let qualified = format!("{}{}", prefix, name);
+            let qualified = qualified.trim_start_matches('\\');
+            // Unqualified names are trait uses inside a class body.
+            if qualified.contains('\\') {
+                imports.push(ImportRef::new(qualified, ImportKind::PhpClass));
+            }
+        }
+    }
+    for cap in include_re.captures_iter(text) {
+        imports.push(ImportRef::new(&cap[1], ImportKind::PhpFile));
+    }
+    imports
+}
+
+/// Extract Ruby `require_relative` and `require` calls.
+fn extract_ruby_imports(text: &str) -> Vec<ImportRef> {
+    let require_re = Regex::new(r#"(?m)^\s*(require_relative|require)\s*\(?\s*['"]([^'"]+)['"]"#)
+        .expect("Invalid regex");
+
+    require_re
+        .captures_iter(text)
+        .map(|cap| {
+            let kind = if &cap[1] == "require_relative" {
+                ImportKind::RubyRelative
+            } else {
+                ImportKind::RubyLoadPath
+            };
+            ImportRef::new(&cap[2], kind)
+        })
+        .collect()
+}
+
+/// Extract C/C++ `#include` directives.
+fn extract_c_includes(text: &str) -> Vec<ImportRef> {
+    let include_re =
+        Regex::new(r#"(?m)^\s*#\s*include\s*([<"])([^>"]+)[>"]"#).expect("Invalid regex");
+
+    include_re
+        .captures_iter(text)
+        .map(|cap| {
+            let kind = if &cap[1] == "<" {
+                ImportKind::CSystem
+            } else {
+                ImportKind::CLocal
+            };
+            ImportRef::new(&cap[2], kind)
+        })
+        .collect()
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// Resolves [`ImportRef`]s to known file node IDs.
+///
+/// Built once per base from the file set, the indexed source text (for
+/// `package`/`namespace` declarations) and project metadata read from the
+/// workspace (`go.mod`, `composer.json`).
+pub struct ImportResolver<'a> {
+    /// Workspace-relative file path → node ID.
+    known_files: &'a HashMap<String, String>,
+    /// Directory → files directly inside it.
+    files_by_dir: HashMap<String, Vec<&'a str>>,
+    /// File name → files with that name.
+    files_by_name: HashMap<String, Vec<&'a str>>,
+    /// Go module path → directory containing its `go.mod`.
+    go_modules: Vec<(String, String)>,
+    /// PSR-4 namespace prefix (without trailing `\`) → directory.
+    psr4: Vec<(String, String)>,
+    /// Java/Kotlin package → files declaring it.
+    jvm_packages: HashMap<String, Vec<&'a str>>,
+    /// Fully qualified top-level Java/Kotlin declaration → files.
+    jvm_declarations: HashMap<String, Vec<&'a str>>,
+    /// C# namespace → files declaring it.
+    cs_namespaces: HashMap<String, Vec<&'a str>>,
+    /// Directories searched for `#include`s, most specific first.
+    include_dirs: Vec<String>,
+    /// `lib/` directories searched for Ruby `require`s.
+    ruby_load_paths: Vec<String>,
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Build a resolver for `known_files`.
+    ///
+    /// `texts` maps file paths to their source text; `root` is the workspace
+    /// root used to read `go.mod` and `composer.json`.
+    pub fn new(
+        root: &Path,
+        known_files: &'a HashMap<String, String>,
+        texts: &HashMap<&'a str, String>,
+    ) -> Self {
+        let mut resolver = Self {
+            known_files,
+            files_by_dir: HashMap::new(),
+            files_by_name: HashMap::new(),
+            go_modules: Vec::new(),
+            psr4: Vec::new(),
+            jvm_packages: HashMap::new(),
+            jvm_declarations: HashMap::new(),
+            cs_namespaces: HashMap::new(),
+            include_dirs: Vec::new(),
+            ruby_load_paths: Vec::new(),
+        };
+
+        let mut dirs: HashSet<String> = HashSet::new();
+        for path in known_files.keys() {
+            let path = path.as_str();
+            let dir = parent_dir(path);
+            resolver
+                .files_by_dir
+                .entry(dir.to_string())
+                .or_default()
+                .push(path);
+            resolver
+                .files_by_name
+                .entry(file_name(path).to_string())
+                .or_default()
+                .push(path);
+
+            // Collect the directory and all of its ancestors
+            let mut current = dir;
+            while dirs.insert(current.to_string()) && !current.is_empty() {
+                current = parent_dir(current);
+            }
+        }
+
+        let mut dirs: Vec<String> = dirs.into_iter().collect();
+        dirs.sort();
+        for dir in &dirs {
+            resolver.load_project_files(root, dir);
+
+            let name = file_name(dir);
+            if matches!(name, "include" | "inc" | "src") {
+                resolver.include_dirs.push(dir.clone());
+            }
+            if name == "lib" {
+                resolver.ruby_load_paths.push(dir.clone());
+            }
+        }
+        resolver.include_dirs.push(String::new());
+        resolver.ruby_load_paths.push(String::new());
+
+        // Longest prefixes first so nested modules win
+        resolver
+            .go_modules
+            .sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.1.cmp(&b.1)));
+        resolver
+            .psr4
+            .sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.1.cmp(&b.1)));
+
+        let package_re = Regex::new(r#"(?m)^\s*package\s+([\w.]+)"#).expect("Invalid regex");
+        let declaration_re = Regex::new(
+            r#"(?m)^(?:(?:public|internal|private|protected|abstract|final|sealed|open|data|enum|annotation|inline|value|const|suspend|static)\s+)*(?:class|interface|object|fun|val|var|typealias|record|enum)\s+(?:<[^>]*>\s*)?(?:[\w.]+\.)?(\w+)"#,
+        )
+        .expect("Invalid regex");
+        let namespace_re = Regex::new(r#"(?m)^\s*namespace\s+([\w.]+)"#).expect("Invalid regex");
+
+        let mut paths: Vec<&'a str> = known_files.keys().map(String::as_str).collect();
+        paths.sort();
+        for path in paths {
+            let Some(text) = texts.get(path) else {
+                continue;
+            };
+            match extension(path) {
+                "java" | "kt" | "kts" => {
+                    let Some(cap) = package_re.captures(text) else {
+                        continue;
+                    };
+                    let package = cap[1].to_string();
+                    let mut names = vec![file_stem(path).to_string()];
+                    names.extend(declaration_re.captures_iter(text).map(|c| c[1].to_string()));
+                    names.sort();
+                    names.dedup();
+                    for name in names {
+                        resolver
+                            .jvm_declarations
+                            .entry(format!("{}.{}", package, name))
+                            .or_default()
+                            .push(path);
+                    }
+                    resolver.jvm_packages.entry(package).or_default().push(path);
+                }
+                "cs" => {
+                    for cap in namespace_re.captures_iter(text) {
+                        let files = resolver
+                            .cs_namespaces
+                            .entry(cap[1].to_string())
+                            .or_default();
+                        if !files.contains(&path) {
+                            files.push(path);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        resolver
+    }
+
+    /// Read `go.mod` and `composer.json` in a workspace directory, if present.
+    fn load_project_files(&mut self, root: &Path, dir: &str) {
+        let dir_path = root.join(dir);
+
+        if let Ok(go_mod) = std::fs::read_to_string(dir_path.join("go.mod")) {
+            if let Some(module) = parse_go_module(&go_mod) {
+                self.go_modules.push((module, dir.to_string()));
+            }
+        }
+
+        if let Ok(composer) = std::fs::read_to_string(dir_path.join("composer.json")) {
+            for (prefix, target) in parse_psr4(&composer) {
+                self.psr4.push((prefix, join_path(dir, &target)));
+            }
+        }
+    }
+
+    /// Resolve an import from `source_file` to known file node IDs.
+    ///
+    /// Package and namespace imports can resolve to several files. The
+    /// importing file itself is never returned.
+    pub fn resolve(&self, import: &ImportRef, source_file: &str) -> Vec<String> {
+        let mut paths = match import.kind {
+            // Relative paths are handled by the extractor
+            ImportKind::Path => Vec::new(),
+            ImportKind::GoPackage => self.resolve_go(&import.raw),
+            ImportKind::JvmName => self.resolve_jvm(&import.raw),
+            ImportKind::CsNamespace => self.resolve_csharp(&import.raw),
+            ImportKind::PhpClass => self.resolve_php_class(&import.raw),
+            ImportKind::PhpFile => {
+                let relative = join_path(parent_dir(source_file), &import.raw);
+                self.first_known(&[relative, normalize(&import.raw)])
+            }
+            ImportKind::RubyRelative => {
+                let path = with_extension(&import.raw, "rb");
+                self.first_known(&[join_path(parent_dir(source_file), &path)])
+            }
+            ImportKind::RubyLoadPath => {
+                let path = with_extension(&import.raw, "rb");
+                let candidates: Vec<String> = self
+                    .ruby_load_paths
+                    .iter()
+                    .map(|dir| join_path(dir, &path))
+                    .collect();
+                self.first_known(&candidates)
+            }
+            ImportKind::CLocal | ImportKind::CSystem => self.resolve_include(import, source_file),
+        };
+
+        paths.retain(|p| p != source_file);
+        paths.sort();
+        paths.dedup();
+        paths
+            .iter()
+            .filter_map(|p| self.known_files.get(p).cloned())
+            .collect()
+    }
+
+    /// Go: strip the module path and take every non-test file in the package.
+    fn resolve_go(&self, import_path: &str) -> Vec<String> {
+        for (module, dir) in &self.go_modules {
+            let rest = if import_path == module {
+                ""
+            } else if let Some(rest) = import_path.strip_prefix(&format!("{}/", module)) {
+                rest
+            } else {
+                continue;
+            };
+            let package_dir = join_path(dir, rest);
+            return self
+                .files_by_dir
+                .get(&package_dir)
+                .map(|files| {
+                    files
+                        .iter()
+                        .filter(|f| f.ends_with(".go") && !f.ends_with("_test.go"))
+                        .map(|f| f.to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+        }
+        Vec::new()
+    }
+
+    /// Java/Kotlin: look the name up in declared packages, dropping trailing
+    /// segments for nested classes and static members, then fall back to the
+    /// package-to-directory convention.
+    fn resolve_jvm(&self, name: &str) -> Vec<String> {
+        if let Some(package) = name.strip_suffix(".*") {
+            if let Some(files) = self.jvm_packages.get(package) {
+                return files.iter().map(|f| f.to_string()).collect();
+            }
+            // `import a.b.Outer.*` imports members of a class
+            return self.resolve_jvm(package);
+        }
+
+        let mut qualified = name;
+        loop {
+            if let Some(files) = self.jvm_declarations.get(qualified) {
+                return files.iter().map(|f| f.to_string()).collect();
+            }
+            match qualified.rsplit_once('.') {
+                Some((prefix, _)) if prefix.contains('.') => qualified = prefix,
+                _ => break,
+            }
+        }
+
+        // Package-to-directory mapping for files without a package index entry
+        let mut qualified = name;
+        loop {
+            let relative = qualified.replace('.', "/");
+            for ext in ["java", "kt"] {
+                let path = format!("{}.{}", relative, ext);
+                let matches = self.files_with_suffix(&path);
+                if !matches.is_empty() {
+                    return matches;
+                }
+            }
+            match qualified.rsplit_once('.') {
+                Some((prefix, _)) if prefix.contains('.') => qualified = prefix,
+                _ => break,
+            }
+        }
+
+        Vec::new()
+    }
+
+    /// C#: a namespace imports every file declaring it; `using static` and
+    /// aliases name a type, matched by file name within its namespace.
+    fn resolve_csharp(&self, name: &str) -> Vec<String> {
+        if let Some(files) = self.cs_namespaces.get(name) {
+            return files.iter().map(|f| f.to_string()).collect();
+        }
+        if let Some((namespace, type_name)) = name.rsplit_once('.') {
+            if let Some(files) = self.cs_namespaces.get(namespace) {
+                return files
+                    .iter()
+                    .filter(|f| file_stem(f) == type_name)
+                    .map(|f| f.to_string())
+                    .collect();
+            }
+        }
+        Vec::new()
+    }
+
+    /// PHP: map the longest matching PSR-4 prefix to its directory.
+    fn resolve_php_class(&self, class: &str) -> Vec<String> {
+        for (prefix, dir) in &self.psr4 {
+            let rest = if prefix.is_empty() {
+                class
+            } else if let Some(rest) = class.strip_prefix(&format!("{}\\", prefix)) {
+                rest
+            } else {
+                continue;
+            };
+            let path = join_path(dir, &format!("{}.php", rest.replace('\\', "/")));
+            if self.known_files.contains_key(&path) {
+                return vec![path];
+            }
+        }
+        Vec::new()
+    }
+
+    /// C/C++: quoted includes try the including directory first, then both
+    /// forms search the include directories. A unique file name match is the
+    /// last resort for quoted includes.
+    fn resolve_include(&self, import: &ImportRef, source_file: &str) -> Vec<String> {
+        let mut candidates = Vec::new();
+        if import.kind == ImportKind::CLocal {
+            candidates.push(join_path(parent_dir(source_file), &import.raw));
+        }
+        candidates.extend(
+            self.include_dirs
+                .iter()
+                .map(|dir| join_path(dir, &import.raw)),
+        );
+
+        let found = self.first_known(&candidates);
+        if !found.is_empty() || import.kind == ImportKind::CSystem {
+            return found;
+        }
+
+        let matches = self.files_with_suffix(&normalize(&import.raw));
+        if matches.len() == 1 {
+            matches
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The first candidate that is a known file, if any.
+    fn first_known(&self, candidates: &[String]) -> Vec<String> {
+        candidates
+            .iter()
+            .find(|c| self.known_files.contains_key(c.as_str()))
+            .map(|c| vec![c.clone()])
+            .unwrap_or_default()
+    }
+
+    /// Known files whose path ends with `suffix` at a path boundary.
+    fn files_with_suffix(&self, suffix: &str) -> Vec<String> {
+        let Some(files) = self.files_by_name.get(file_name(suffix)) else {
+            return Vec::new();
+        };
+        files
+            .iter()
+            .filter(|f| {
+                **f == suffix
+                    || f.strip_suffix(suffix)
+                        .is_some_and(|prefix| prefix.ends_with('/'))
+            })
+            .map(|f| f.to_string())
+            .collect()
+    }
+}
+
+// ============================================================================
+// Project Metadata
+// ============================================================================
+
+/// Read the module path from a `go.mod` file.
+fn parse_go_module(go_mod: &str) -> Option<String> {
+    go_mod.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("module")?;
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let module = rest.trim().trim_matches('"');
+        (!module.is_empty()).then(|| module.to_string())
+    })
+}
+
+/// Read PSR-4 prefixes from `composer.json` (`autoload` and `autoload-dev`).
+///
+/// Returns `(prefix without trailing backslash, directory)` pairs.
+fn parse_psr4(composer: &str) -> Vec<(String, String)> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(composer) else {
+        return Vec::new();
+    };
+
+    let mut mappings = Vec::new();
+    for section in ["autoload", "autoload-dev"] {
+        let Some(psr4) = json
+            .get(section)
+            .and_then(|s| s.get("psr-4"))
+            .and_then(|p| p.as_object())
+        else {
+            continue;
+        };
+        for (prefix, dirs) in psr4 {
+            let prefix = prefix.trim_end_matches('\\').to_string();
+            let dirs: Vec<&str> = match dirs {
+                serde_json::Value::String(dir) => vec![dir.as_str()],
+                serde_json::Value::Array(dirs) => dirs.iter().filter_map(|d| d.as_str()).collect(),
+                _ => Vec::new(),
+            };
+            for dir in dirs {
+                mappings.push((prefix.clone(), dir.to_string()));
+            }
+        }
+    }
+    mappings
+}
+
+// ============================================================================
+// Path Helpers
+// ============================================================================
+
+fn extension(path: &str) -> &str {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit_once('/').map(|(_, name)| name).unwrap_or(path)
+}
+
+fn file_stem(path: &str) -> &str {
+    let name = file_name(path);
+    name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name)
+}
+
+fn with_extension(path: &str, ext: &str) -> String {
+    if extension(path) == ext {
+        path.to_string()
+    } else {
+        format!("{}.{}", path, ext)
+    }
+}
+
+/// Join a workspace-relative directory and a relative path.
+fn join_path(dir: &str, path: &str) -> String {
+    if dir.is_empty() {
+        normalize(path)
+    } else {
+        normalize(&format!("{}/{}", dir, path))
+    }
+}
+
+/// Resolve `.` and `..` segments and drop leading/duplicate slashes.
+fn normalize(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known(paths: &[&str]) -> HashMap<String, String> {
+        paths
+            .iter()
+            .map(|p| (p.to_string(), format!("file:{}", p)))
+            .collect()
+    }
+
+    fn resolve(
+        resolver: &ImportResolver,
+        raw: &str,
+        kind: ImportKind,
+        source: &str,
+    ) -> Vec<String> {
+        resolver.resolve(&ImportRef::new(raw, kind), source)
+    }
+
+    #[test]
+    fn test_extract_imports_per_language() {
+        let go = "package main\n\nimport \"fmt\"\nimport (\n\t\"os\"\n\tb \"example.com/app/internal/billing\"\n)\n";
+        let raws: Vec<String> = extract_imports(go, "main.go")
+            .into_iter()
+            .map(|i| i.raw)
+            .collect();
+        assert_eq!(raws, vec!["fmt", "os", "example.com/app/internal/billing"]);
+
+        let kt = "package a\n\nimport com.acme.billing.Invoice\nimport com.acme.util.*\nimport com.acme.Money as M\n";
+        let raws: Vec<String> = extract_imports(kt, "A.kt")
+            .into_iter()
+            .map(|i| i.raw)
+            .collect();
+        assert_eq!(
+            raws,
+            vec![
+                "com.acme.billing.Invoice",
+                "com.acme.util.*",
+                "com.acme.Money"
+            ]
+        );
+
+        let cs = "using System;\nusing static Acme.Math.Calc;\nusing Db = Acme.Data;\nusing (var x = Open()) {}\n";
+        let raws: Vec<String> = extract_imports(cs, "A.cs")
+            .into_iter()
+            .map(|i| i.raw)
+            .collect();
+        assert_eq!(raws, vec!["System", "Acme.Math.Calc", "Acme.Data"]);
+
+        let php = "<?php\nuse App\\Models\\User;\nuse App\\Http\\{Request, Response as R};\nuse function App\\helpers\\fmt;\nrequire_once __DIR__ . '/bootstrap.php';\nclass A { use HasFactory; }\n";
+        let imports = extract_imports(php, "src/A.php");
+        assert_eq!(
+            imports,
+            vec![
+                ImportRef::new("App\\Models\\User", ImportKind::PhpClass),
+                ImportRef::new("App\\Http\\Request", ImportKind::PhpClass),
+                ImportRef::new("App\\Http\\Response", ImportKind::PhpClass),
+                ImportRef::new("/bootstrap.php", ImportKind::PhpFile),
+            ]
+        );
+
+        let rb = "require 'json'\nrequire_relative \"../models/user\"\n";
+        let imports = extract_imports(rb, "app/a.rb");
+        assert_eq!(
+            imports,
+            vec![
+                ImportRef::new("json", ImportKind::RubyLoadPath),
+                ImportRef::new("../models/user", ImportKind::RubyRelative),
+            ]
+        );
+
+        let c = "#include <stdio.h>\n#include \"util/log.h\"\n";
+        let imports = extract_imports(c, "src/main.c");
+        assert_eq!(
+            imports,
+            vec![
+                ImportRef::new("stdio.h", ImportKind::CSystem),
+                ImportRef::new("util/log.h", ImportKind::CLocal),
+            ]
+        );
+
+        assert!(extract_imports("import x from './x'", "a.ts").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_go_packages_from_go_mod() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("backend")).unwrap();
+        std::fs::write(
+            dir.path().join("backend/go.mod"),
+            "module github.com/acme/backend\n\ngo 1.22\n",
+        )
+        .unwrap();
+
+        let files = known(&[
+            "backend/cmd/api/main.go",
+            "backend/internal/billing/invoice.go",
+            "backend/internal/billing/tax.go",
+            "backend/internal/billing/tax_test.go",
+        ]);
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+
+        let targets = resolve(
+            &resolver,
+            "github.com/acme/backend/internal/billing",
+            ImportKind::GoPackage,
+            "backend/cmd/api/main.go",
+        );
+        assert_eq!(
+            targets,
+            vec![
+                "file:backend/internal/billing/invoice.go",
+                "file:backend/internal/billing/tax.go",
+            ]
+        );
+        assert!(resolve(
+            &resolver,
+            "net/http",
+            ImportKind::GoPackage,
+            "backend/cmd/api/main.go"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_resolve_jvm_packages_and_directories() {
+        let files = known(&[
+            "app/src/main/kotlin/billing/Invoice.kt",
+            "app/src/main/kotlin/billing/Money.kt",
+            "app/src/main/kotlin/api/Routes.kt",
+            "lib/src/main/java/com/acme/util/Strings.java",
+        ]);
+        let mut texts = HashMap::new();
+        texts.insert(
+            "app/src/main/kotlin/billing/Invoice.kt",
+            "package com.acme.billing\n\ndata class Invoice(val id: String)\n\nfun Invoice.total(): Long = 0\n".to_string(),
+        );
+        texts.insert(
+            "app/src/main/kotlin/billing/Money.kt",
+            "package com.acme.billing\n\nfun formatMoney(cents: Long) = \"\"\n".to_string(),
+        );
+        let resolver = ImportResolver::new(Path::new("/nonexistent"), &files, &texts);
+        let source = "app/src/main/kotlin/api/Routes.kt";
+
+        // Directory layout differs from the package; the declaration wins
+        assert_eq!(
+            resolve(
+                &resolver,
+                "com.acme.billing.Invoice",
+                ImportKind::JvmName,
+                source
+            ),
+            vec!["file:app/src/main/kotlin/billing/Invoice.kt"]
+        );
+        // Top-level Kotlin function
+        assert_eq!(
+            resolve(
+                &resolver,
+                "com.acme.billing.formatMoney",
+                ImportKind::JvmName,
+                source
+            ),
+            vec!["file:app/src/main/kotlin/billing/Money.kt"]
+        );
+        // Wildcard imports the whole package
+        assert_eq!(
+            resolve(&resolver, "com.acme.billing.*", ImportKind::JvmName, source).len(),
+            2
+        );
+        // No indexed text: fall back to the package directory, ignoring the
+        // static member segment
+        assert_eq!(
+            resolve(
+                &resolver,
+                "com.acme.util.Strings.join",
+                ImportKind::JvmName,
+                source
+            ),
+            vec!["file:lib/src/main/java/com/acme/util/Strings.java"]
+        );
+        assert!(resolve(&resolver, "java.util.List", ImportKind::JvmName, source).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_csharp_namespaces() {
+        let files = known(&["Billing/Invoice.cs", "Billing/Tax.cs", "Api/Controller.cs"]);
+        let mut texts = HashMap::new();
+        texts.insert(
+            "Billing/Invoice.cs",
+            "namespace Acme.Billing;\n\npublic class Invoice {}\n".to_string(),
+        );
+        texts.insert(
+            "Billing/Tax.cs",
+            "namespace Acme.Billing\n{\n    public static class Tax {}\n}\n".to_string(),
+        );
+        let resolver = ImportResolver::new(Path::new("/nonexistent"), &files, &texts);
+
+        assert_eq!(
+            resolve(
+                &resolver,
+                "Acme.Billing",
+                ImportKind::CsNamespace,
+                "Api/Controller.cs"
+            ),
+            vec!["file:Billing/Invoice.cs", "file:Billing/Tax.cs"]
+        );
+        assert_eq!(
+            resolve(
+                &resolver,
+                "Acme.Billing.Tax",
+                ImportKind::CsNamespace,
+                "Api/Controller.cs"
+            ),
+            vec!["file:Billing/Tax.cs"]
+        );
+        assert!(resolve(
+            &resolver,
+            "System.Linq",
+            ImportKind::CsNamespace,
+            "Api/Controller.cs"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_resolve_php_psr4_and_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("composer.json"),
+            r#"{"autoload": {"psr-4": {"App\\": "src/"}}, "autoload-dev": {"psr-4": {"Tests\\": ["tests/"]}}}"#,
+        )
+        .unwrap();
+
+        let files = known(&[
+            "src/Models/User.php",
+            "src/Http/Controller.php",
+            "tests/UserTest.php",
+            "bootstrap.php",
+        ]);
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+
+        assert_eq!(
+            resolve(
+                &resolver,
+                "App\\Models\\User",
+                ImportKind::PhpClass,
+                "src/Http/Controller.php"
+            ),
+            vec!["file:src/Models/User.php"]
+        );
+        assert_eq!(
+            resolve(
+                &resolver,
+                "Tests\\UserTest",
+                ImportKind::PhpClass,
+                "src/Http/Controller.php"
+            ),
+            vec!["file:tests/UserTest.php"]
+        );
+        assert_eq!(
+            resolve(
+                &resolver,
+                "/../../bootstrap.php",
+                ImportKind::PhpFile,
+                "src/Http/Controller.php"
+            ),
+            vec!["file:bootstrap.php"]
+        );
+        assert!(resolve(
+            &resolver,
+            "Illuminate\\Support\\Str",
+            ImportKind::PhpClass,
+            "src/Http/Controller.php"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_resolve_ruby_requires() {
+        let files = known(&[
+            "app/models/user.rb",
+            "app/services/signup.rb",
+            "lib/acme/client.rb",
+        ]);
+        let resolver = ImportResolver::new(Path::new("/nonexistent"), &files, &HashMap::new());
+
+        assert_eq!(
+            resolve(
+                &resolver,
+                "../models/user",
+                ImportKind::RubyRelative,
+                "app/services/signup.rb"
+            ),
+            vec!["file:app/models/user.rb"]
+        );
+        assert_eq!(
+            resolve(
+                &resolver,
+                "acme/client",
+                ImportKind::RubyLoadPath,
+                "app/services/signup.rb"
+            ),
+            vec!["file:lib/acme/client.rb"]
+        );
+        assert!(resolve(
+            &resolver,
+            "json",
+            ImportKind::RubyLoadPath,
+            "app/services/signup.rb"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_resolve_c_includes() {
+        let files = known(&[
+            "src/main.c",
+            "src/config.h",
+            "include/acme/log.h",
+            "vendor/zlib/zlib.h",
+        ]);
+        let resolver = ImportResolver::new(Path::new("/nonexistent"), &files, &HashMap::new());
+
+        // Including directory first
+        assert_eq!(
+            resolve(&resolver, "config.h", ImportKind::CLocal, "src/main.c"),
+            vec!["file:src/config.h"]
+        );
+        // Include search path
+        assert_eq!(
+            resolve(&resolver, "acme/log.h", ImportKind::CSystem, "src/main.c"),
+            vec!["file:include/acme/log.h"]
+        );
+        // Unique file name match for quoted includes only
+        assert_eq!(
+            resolve(&resolver, "zlib.h", ImportKind::CLocal, "src/main.c"),
+            vec!["file:vendor/zlib/zlib.h"]
+        );
+        assert!(resolve(&resolver, "zlib.h", ImportKind::CSystem, "src/main.c").is_empty());
+        assert!(resolve(&resolver, "stdio.h", ImportKind::CSystem, "src/main.c").is_empty());
+    }
+
+    #[test]
+    fn test_parse_project_metadata() {
+        assert_eq!(
+            parse_go_module("// comment\nmodule example.com/app\n\ngo 1.21\n").as_deref(),
+            Some("example.com/app")
+        );
+        assert_eq!(parse_go_module("modules x\n"), None);
+        let mut psr4 = parse_psr4(r#"{"autoload": {"psr-4": {"App\\": "src/", "": "lib/"}}}"#);
+        psr4.sort();
+        assert_eq!(
+            psr4,
+            vec![
+                ("".to_string(), "lib/".to_string()),
+                ("App".to_string(), "src/".to_string()),
+            ]
+        );
+        assert!(parse_psr4("not json").is_empty());
+    }
+}
@@ -0,0 +1,1039 @@
+//! Infrastructure-as-code extraction for the KG.
+//!
+//! Deployment topology lives in Dockerfiles, Compose files, Kubernetes
+//! manifests and Terraform. This module turns it into graph structure:
+//!
+//! | Source | Files | Nodes |
+//! |--------|-------|-------|
+//! | Dockerfile | `Dockerfile`, `Dockerfile.*`, `*.dockerfile`, `Containerfile` | Built `image`, base `image`s |
+//! | Docker Compose | `docker-compose*.yml`, `compose*.yml` (and `.yaml`) | `service` per entry under `services` |
+//! | Kubernetes | YAML documents with `apiVersion` and `kind` | `service` for workloads, `resource` for everything else |
+//! | Terraform | `*.tf` | `resource` for `resource`, `data` and `module` blocks |
+//!
+//! Edges:
+//! - `configures`: file → what it declares; ConfigMap/Secret → workload using it
+//! - `dependsOn`: service → image, Compose `depends_on`, workload → volume
+//!   claim, Terraform reference
+//! - `exposes`: Kubernetes Service → selected workloads, Ingress → Service
+//!
+//! Service selectors can match workloads in other files, so they are resolved
+//! in [`link_infra`] once all bases have been extracted.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::entities::{KgEdge, KgNode};
+use super::text::{line_at, matching_brace};
+
+// ============================================================================
+// InfraGraph
+// ============================================================================
+
+/// Nodes and edges extracted from one infrastructure file.
+#[derive(Debug, Clone, Default)]
+pub struct InfraGraph {
+    /// Service, image and resource nodes.
+    pub nodes: Vec<KgNode>,
+    /// `configures`, `dependsOn` and `exposes` edges.
+    pub edges: Vec<KgEdge>,
+}
+
+impl InfraGraph {
+    fn node(&mut self, id: &str, kind: &str, label: &str, props: Value) {
+        self.nodes
+            .push(KgNode::new(id, kind, label).with_props(props));
+    }
+
+    fn edge(&mut self, from: &str, to: &str, kind: &str) {
+        self.edges.push(KgEdge::new(from, to, kind));
+    }
+
+    fn edge_with(&mut self, from: &str, to: &str, kind: &str, props: Value) {
+        self.edges
+            .push(KgEdge::new(from, to, kind).with_props(props));
+    }
+
+    /// Add an `image` node for an image reference and return its ID.
+    fn image(&mut self, reference: &str) -> String {
+        let id = format!("image:{}", reference);
+        let (name, tag) = split_image(reference);
+        self.node(&id, "image", reference, json!({ "name": name, "tag": tag }));
+        id
+    }
+}
+
+/// Extract infrastructure structure from a file, if it is an IaC file.
+///
+/// `file_node_id` is the node that `configures` edges start from.
+pub fn extract_infra(file_path: &str, file_node_id: &str, text: &str) -> Option<InfraGraph> {
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+    let lower = file_name.to_lowercase();
+
+    if lower == "dockerfile"
+        || lower.starts_with("dockerfile.")
+        || lower.ends_with(".dockerfile")
+        || lower == "containerfile"
+    {
+        return Some(extract_dockerfile(file_path, file_node_id, text));
+    }
+    if lower.ends_with(".tf") {
+        return Some(extract_terraform(file_path, file_node_id, text));
+    }
+    if !(lower.ends_with(".yml") || lower.ends_with(".yaml")) {
+        return None;
+    }
+    if lower.starts_with("docker-compose") || lower.starts_with("compose.") {
+        return extract_compose(file_path, file_node_id, text);
+    }
+    if text.contains("apiVersion") && text.contains("kind") {
+        return extract_kubernetes(file_path, file_node_id, text);
+    }
+    None
+}
+
+// ============================================================================
+// Dockerfile
+// ============================================================================
+
+/// ID of the image built from a Dockerfile.
+pub fn built_image_id(dockerfile_path: &str) -> String {
+    format!("image:build:{}", dockerfile_path)
+}
+
+/// Extract base images and exposed ports from a Dockerfile.
+fn extract_dockerfile(file_path: &str, file_node_id: &str, text: &str) -> InfraGraph {
+    let mut graph = InfraGraph::default();
+    let built_id = built_image_id(file_path);
+    let label = match parent_dir(file_path) {
+        "" => file_path.to_string(),
+        dir => dir.to_string(),
+    };
+
+    let from_re = regex::Regex::new(r"(?i)^FROM\s+(?:--\S+\s+)*(\S+)(?:\s+AS\s+(\S+))?")
+        .expect("Invalid regex");
+    let copy_from_re = regex::Regex::new(r"(?i)^COPY\s+.*--from=(\S+)").expect("Invalid regex");
+    let expose_re = regex::Regex::new(r"(?i)^EXPOSE\s+(.+)").expect("Invalid regex");
+
+    let mut stages: Vec<String> = Vec::new();
+    let mut ports: Vec<String> = Vec::new();
+    let mut bases: Vec<(String, u32, Option<String>)> = Vec::new();
+
+    for (line_no, line) in logical_lines(text) {
+        if let Some(cap) = from_re.captures(&line) {
+            let image = cap[1].to_string();
+            let stage = cap.get(2).map(|m| m.as_str().to_string());
+            // FROM <earlier stage> builds on this Dockerfile, not an external image
+            if !stages.iter().any(|s| s.eq_ignore_ascii_case(&image))
+                && image != "scratch"
+                && !image.contains('$')
+            {
+                bases.push((image, line_no, stage.clone()));
+            }
+            stages.push(stage.unwrap_or_else(|| stages.len().to_string()));
+        } else if let Some(cap) = copy_from_re.captures(&line) {
+            let image = cap[1].to_string();
+            if !stages.iter().any(|s| s.eq_ignore_ascii_case(&image)) && !image.contains('$') {
+                bases.push((image, line_no, None));
+            }
+        } else if let Some(cap) = expose_re.captures(&line) {
+            for port in cap[1].split_whitespace() {
+                if !port.contains('$') && !ports.iter().any(|p| p == port) {
+                    ports.push(port.to_string());
+                }
+            }
+        }
+    }
+
+    graph.node(
+        &built_id,
+        "image",
+        &label,
+        json!({
+            "path": file_path,
+            "dockerfile": file_path,
+            "stages": stages,
+            "exposedPorts": ports,
+        }),
+    );
+    graph.edge(file_node_id, &built_id, "configures");
+
+    let mut seen = HashSet::new();
+    for (image, line, stage) in bases {
+        if !seen.insert(image.clone()) {
+            continue;
+        }
+        let image_id = graph.image(&image);
+        graph.edge_with(
+            &built_id,
+            &image_id,
+            "dependsOn",
+            json!({ "line": line, "stage": stage }),
+        );
+    }
+
+    graph
+}
+
+/// Lines with `\` continuations joined and comments dropped, with 1-based
+/// starting line numbers.
+fn logical_lines(text: &str) -> Vec<(u32, String)> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut start = 0u32;
+
+    for (i, raw) in text.lines().enumerate() {
+        let trimmed = raw.trim();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        if current.is_empty() {
+            start = i as u32 + 1;
+        }
+        match trimmed.strip_suffix('\\') {
+            Some(part) => {
+                current.push_str(part);
+                current.push(' ');
+            }
+            None => {
+                current.push_str(trimmed);
+                if !current.trim().is_empty() {
+                    lines.push((start, current.trim().to_string()));
+                }
+                current.clear();
+            }
+        }
+    }
+    if !current.trim().is_empty() {
+        lines.push((start, current.trim().to_string()));
+    }
+    lines
+}
+
+// ============================================================================
+// Docker Compose
+// ============================================================================
+
+/// Extract services, their images and `depends_on` from a Compose file.
+fn extract_compose(file_path: &str, file_node_id: &str, text: &str) -> Option<InfraGraph> {
+    let doc: Value = serde_yaml::from_str(text).ok()?;
+    let services = doc.get("services")?.as_object()?;
+    let mut graph = InfraGraph::default();
+    let dir = parent_dir(file_path);
+    let service_id = |name: &str| format!("service:compose:{}:{}", file_path, name);
+
+    for (name, service) in services {
+        let id = service_id(name);
+        let ports: Vec<String> = service
+            .get("ports")
+            .and_then(|p| p.as_array())
+            .map(|ports| ports.iter().map(yaml_scalar).collect())
+            .unwrap_or_default();
+        graph.node(
+            &id,
+            "service",
+            name,
+            json!({
+                "path": file_path,
+                "platform": "compose",
+                "image": service.get("image"),
+                "ports": ports,
+                "containerName": service.get("container_name"),
+            }),
+        );
+        graph.edge(file_node_id, &id, "configures");
+
+        // build: ./api  or  build: { context: ./api, dockerfile: Dockerfile.prod }
+        let build = service.get("build");
+        let context = build.and_then(|b| {
+            b.as_str()
+                .or_else(|| b.get("context").and_then(|c| c.as_str()))
+        });
+        if let Some(context) = context {
+            let dockerfile = build
+                .and_then(|b| b.get("dockerfile"))
+                .and_then(|d| d.as_str())
+                .unwrap_or("Dockerfile");
+            let path = join_path(&join_path(dir, context), dockerfile);
+            graph.edge_with(
+                &id,
+                &built_image_id(&path),
+                "dependsOn",
+                json!({ "via": "build" }),
+            );
+        } else if let Some(image) = service.get("image").and_then(|i| i.as_str()) {
+            if !image.contains('$') {
+                let image_id = graph.image(image);
+                graph.edge_with(&id, &image_id, "dependsOn", json!({ "via": "image" }));
+            }
+        }
+
+        // depends_on: [db]  or  depends_on: { db: { condition: service_healthy } }
+        let depends_on: Vec<(String, Option<String>)> = match service.get("depends_on") {
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|d| d.as_str())
+                .map(|d| (d.to_string(), None))
+                .collect(),
+            Some(Value::Object(items)) => items
+                .iter()
+                .map(|(d, spec)| {
+                    let condition = spec
+                        .get("condition")
+                        .and_then(|c| c.as_str())
+                        .map(str::to_string);
+                    (d.clone(), condition)
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (dependency, condition) in depends_on {
+            graph.edge_with(
+                &id,
+                &service_id(&dependency),
+                "dependsOn",
+                json!({ "via": "depends_on", "condition": condition }),
+            );
+        }
+        for link in service
+            .get("links")
+            .and_then(|l| l.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if let Some(target) = link.as_str().and_then(|l| l.split(':').next()) {
+                graph.edge_with(
+                    &id,
+                    &service_id(target),
+                    "dependsOn",
+                    json!({ "via": "links" }),
+                );
+            }
+        }
+    }
+
+    Some(graph)
+}
+
+// ============================================================================
+// Kubernetes
+// ============================================================================
+
+/// Workload kinds that run containers, and the path to their pod template.
+const K8S_WORKLOADS: &[(&str, &str)] = &[
+    ("Deployment", "/spec/template"),
+    ("StatefulSet", "/spec/template"),
+    ("DaemonSet", "/spec/template"),
+    ("ReplicaSet", "/spec/template"),
+    ("Job", "/spec/template"),
+    ("CronJob", "/spec/jobTemplate/spec/template"),
+    ("Pod", ""),
+];
+
+/// Node ID for a Kubernetes object.
+fn k8s_id(namespace: &str, kind: &str, name: &str) -> String {
+    format!("k8s:{}/{}/{}", namespace, kind, name)
+}
+
+/// Extract objects from a (possibly multi-document) Kubernetes manifest.
+///
+/// Returns `None` if no document is a Kubernetes object.
+fn extract_kubernetes(file_path: &str, file_node_id: &str, text: &str) -> Option<InfraGraph> {
+    let mut graph = InfraGraph::default();
+
+    for document in serde_yaml::Deserializer::from_str(text) {
+        // Helm templates and other non-YAML documents are skipped
+        let Ok(doc) = Value::deserialize(document) else {
+            continue;
+        };
+        let (Some(kind), Some(_), Some(name)) = (
+            doc.get("kind").and_then(|k| k.as_str()),
+            doc.get("apiVersion").and_then(|a| a.as_str()),
+            doc.pointer("/metadata/name").and_then(|n| n.as_str()),
+        ) else {
+            continue;
+        };
+        let namespace = doc
+            .pointer("/metadata/namespace")
+            .and_then(|n| n.as_str())
+            .unwrap_or("default");
+        let id = k8s_id(namespace, kind, name);
+        let template = K8S_WORKLOADS
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .and_then(|(_, pointer)| doc.pointer(pointer));
+
+        let mut props = json!({
+            "path": file_path,
+            "platform": "kubernetes",
+            "k8sKind": kind,
+            "namespace": namespace,
+            "labels": doc.pointer("/metadata/labels"),
+        });
+        if kind == "Service" {
+            props["selector"] = doc
+                .pointer("/spec/selector")
+                .cloned()
+                .unwrap_or(Value::Null);
+            props["ports"] = doc.pointer("/spec/ports").cloned().unwrap_or(Value::Null);
+            props["serviceType"] = doc.pointer("/spec/type").cloned().unwrap_or(Value::Null);
+        }
+        if let Some(template) = template {
+            props["podLabels"] = template
+                .pointer("/metadata/labels")
+                .cloned()
+                .unwrap_or(Value::Null);
+        }
+        let node_kind = if template.is_some() {
+            "service"
+        } else {
+            "resource"
+        };
+        graph.node(&id, node_kind, name, props);
+        graph.edge(file_node_id, &id, "configures");
+
+        if let Some(template) = template {
+            extract_pod_spec(&mut graph, &id, namespace, template);
+        }
+
+        match kind {
+            "Ingress" => {
+                let mut backends: Vec<&str> = Vec::new();
+                let default_backend = doc
+                    .pointer("/spec/defaultBackend/service/name")
+                    .or_else(|| doc.pointer("/spec/backend/serviceName"));
+                backends.extend(default_backend.and_then(|b| b.as_str()));
+                let rules = doc.pointer("/spec/rules").and_then(|r| r.as_array());
+                for rule in rules.into_iter().flatten() {
+                    let paths = rule.pointer("/http/paths").and_then(|p| p.as_array());
+                    for path in paths.into_iter().flatten() {
+                        let backend = path
+                            .pointer("/backend/service/name")
+                            .or_else(|| path.pointer("/backend/serviceName"));
+                        backends.extend(backend.and_then(|b| b.as_str()));
+                    }
+                }
+                let mut seen = HashSet::new();
+                for backend in backends {
+                    if seen.insert(backend) {
+                        graph.edge(&id, &k8s_id(namespace, "Service", backend), "exposes");
+                    }
+                }
+            }
+            "HorizontalPodAutoscaler" => {
+                if let (Some(target_kind), Some(target_name)) = (
+                    doc.pointer("/spec/scaleTargetRef/kind")
+                        .and_then(|k| k.as_str()),
+                    doc.pointer("/spec/scaleTargetRef/name")
+                        .and_then(|n| n.as_str()),
+                ) {
+                    graph.edge(
+                        &id,
+                        &k8s_id(namespace, target_kind, target_name),
+                        "configures",
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if graph.nodes.is_empty() {
+        None
+    } else {
+        Some(graph)
+    }
+}
+
+/// Images, ConfigMaps, Secrets and volume claims used by a pod template.
+fn extract_pod_spec(graph: &mut InfraGraph, workload_id: &str, namespace: &str, template: &Value) {
+    let Some(spec) = template.get("spec") else {
+        return;
+    };
+    let mut images: Vec<&str> = Vec::new();
+    let mut configs: Vec<(&str, &str)> = Vec::new(); // (kind, name)
+    let mut claims: Vec<&str> = Vec::new();
+
+    for key in ["initContainers", "containers"] {
+        for container in spec
+            .get(key)
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+        {
+            images.extend(container.get("image").and_then(|i| i.as_str()));
+            let env_from = container.get("envFrom").and_then(|e| e.as_array());
+            for source in env_from.into_iter().flatten() {
+                if let Some(name) = source
+                    .pointer("/configMapRef/name")
+                    .and_then(|n| n.as_str())
+                {
+                    configs.push(("ConfigMap", name));
+                }
+                if let Some(name) = source.pointer("/secretRef/name").and_then(|n| n.as_str()) {
+                    configs.push(("Secret", name));
+                }
+            }
+            let env = container.get("env").and_then(|e| e.as_array());
+            for var in env.into_iter().flatten() {
+                let config_map = var.pointer("/valueFrom/configMapKeyRef/name");
+                if let Some(name) = config_map.and_then(|n| n.as_str()) {
+                    configs.push(("ConfigMap", name));
+                }
+                let secret = var.pointer("/valueFrom/secretKeyRef/name");
+                if let Some(name) = secret.and_then(|n| n.as_str()) {
+                    configs.push(("Secret", name));
+                }
+            }
+        }
+    }
+    for volume in spec
+        .get("volumes")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(name) = volume.pointer("/configMap/name").and_then(|n| n.as_str()) {
+            configs.push(("ConfigMap", name));
+        }
+        if let Some(name) = volume
+            .pointer("/secret/secretName")
+            .and_then(|n| n.as_str())
+        {
+            configs.push(("Secret", name));
+        }
+        let claim = volume.pointer("/persistentVolumeClaim/claimName");
+        claims.extend(claim.and_then(|n| n.as_str()));
+    }
+
+    let mut seen = HashSet::new();
+    for image in images {
+        if seen.insert(image) && !image.contains("{{") {
+            let image_id = graph.image(image);
+            graph.edge(workload_id, &image_id, "dependsOn");
+        }
+    }
+    let mut seen = HashSet::new();
+    for (kind, name) in configs {
+        if seen.insert((kind, name)) {
+            graph.edge(&k8s_id(namespace, kind, name), workload_id, "configures");
+        }
+    }
+    for claim in claims {
+        graph.edge(
+            workload_id,
+            &k8s_id(namespace, "PersistentVolumeClaim", claim),
+            "dependsOn",
+        );
+    }
+}
+
+// ============================================================================
+// Terraform
+// ============================================================================
+
+/// Reference prefixes that aren't resources (`var.region`, `local.tags`, ...).
+const TF_NON_RESOURCES: &[&str] = &["var", "local", "each", "count", "path", "terraform", "self"];
+
+/// Extract resources, data sources and modules from a Terraform file.
+///
+/// Node IDs are scoped to the file's directory (the Terraform module), so
+/// references resolve across files of the same module.
+fn extract_terraform(file_path: &str, file_node_id: &str, text: &str) -> InfraGraph {
+    let source = strip_hcl_comments(text);
+    let mut graph = InfraGraph::default();
+    let dir = parent_dir(file_path);
+    let tf_id = |address: &str| format!("tf:{}:{}", dir, address);
+
+    let block_re = regex::Regex::new(
+        r#"(?m)^\s*(?:(resource|data)\s+"([\w-]+)"\s+"([\w-]+)"|module\s+"([\w-]+)")\s*\{"#,
+    )
+    .expect("Invalid regex");
+    let ref_re = regex::Regex::new(
+        r"(?:^|[^\w.])((data\.[a-z][\w]*\.[\w-]+)|(module\.[\w-]+)|([a-z][a-z0-9]*_\w*\.[A-Za-z_][\w-]*))",
+    )
+    .expect("Invalid regex");
+    let source_re = regex::Regex::new(r#"(?m)^\s*source\s*=\s*"([^"]+)""#).expect("Invalid regex");
+
+    for cap in block_re.captures_iter(&source) {
+        let whole = cap.get(0).expect("match");
+        let (address, block, resource_type, name) = match cap.get(1).map(|m| m.as_str()) {
+            Some("data") => (
+                format!("data.{}.{}", &cap[2], &cap[3]),
+                "data",
+                Some(cap[2].to_string()),
+                cap[3].to_string(),
+            ),
+            Some(_) => (
+                format!("{}.{}", &cap[2], &cap[3]),
+                "resource",
+                Some(cap[2].to_string()),
+                cap[3].to_string(),
+            ),
+            None => (
+                format!("module.{}", &cap[4]),
+                "module",
+                None,
+                cap[4].to_string(),
+            ),
+        };
+        let end = matching_brace(&source, whole.end() - 1).unwrap_or(source.len());
+        let body = &source[whole.end()..end];
+        let id = tf_id(&address);
+
+        let mut props = json!({
+            "path": file_path,
+            "platform": "terraform",
+            "tfBlock": block,
+            "address": address,
+            "line": line_at(&source, whole.start() + whole.as_str().len() - whole.as_str().trim_start().len()),
+        });
+        if let Some(resource_type) = &resource_type {
+            props["resourceType"] = json!(resource_type);
+            props["provider"] = json!(resource_type.split('_').next().unwrap_or(resource_type));
+        }
+        if block == "module" {
+            props["source"] = json!(source_re.captures(body).map(|c| c[1].to_string()));
+        }
+        graph.node(&id, "resource", &name, props);
+        graph.edge(file_node_id, &id, "configures");
+
+        let mut seen = HashSet::new();
+        for reference in ref_re.captures_iter(body) {
+            let target = &reference[1];
+            let prefix = target.split('.').next().unwrap_or("");
+            if TF_NON_RESOURCES.contains(&prefix)
+                || target == address
+                || !seen.insert(target.to_string())
+            {
+                continue;
+            }
+            graph.edge(&id, &tf_id(target), "dependsOn");
+        }
+    }
+
+    graph
+}
+
+/// Blank out `#`, `//` and `/* */` comments, keeping offsets and newlines.
+fn strip_hcl_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut in_string = false;
+    let blank = |s: &str| -> String {
+        s.chars()
+            .map(|c| if c == '\n' { '\n' } else { ' ' })
+            .collect()
+    };
+
+    while let Some(c) = rest.chars().next() {
+        if in_string {
+            if c == '\\' {
+                let escaped: String = rest.chars().take(2).collect();
+                out.push_str(&escaped);
+                rest = &rest[escaped.len()..];
+                continue;
+            }
+            in_string = c != '"';
+        } else if rest.starts_with("/*") {
+            let end = rest.find("*/").map(|i| i + 2).unwrap_or(rest.len());
+            out.push_str(&blank(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        } else if c == '#' || rest.starts_with("//") {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            out.push_str(&blank(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        } else if c == '"' {
+            in_string = true;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+// ============================================================================
+// Cross-File Linking
+// ============================================================================
+
+/// Add `exposes` edges from Kubernetes Services to the workloads their
+/// selector matches in the same namespace.
+///
+/// Returns the edges to add.
+pub fn link_infra(nodes: &[KgNode]) -> Vec<KgEdge> {
+    let workloads: Vec<&KgNode> = nodes
+        .iter()
+        .filter(|n| n.props.get("podLabels").is_some_and(|l| l.is_object()))
+        .collect();
+    let mut links = Vec::new();
+    let mut seen = HashSet::new();
+
+    for service in nodes.iter().filter(|n| n.props["k8sKind"] == "Service") {
+        let Some(selector) = service.props["selector"].as_object() else {
+            continue;
+        };
+        if selector.is_empty() {
+            continue;
+        }
+        for workload in &workloads {
+            if workload.props["namespace"] != service.props["namespace"] {
+                continue;
+            }
+            let labels = &workload.props["podLabels"];
+            let matches = selector
+                .iter()
+                .all(|(key, value)| labels.get(key) == Some(value));
+            if matches && seen.insert((service.id.as_str(), workload.id.as_str())) {
+                links.push(
+                    KgEdge::new(&service.id, &workload.id, "exposes")
+                        .with_props(json!({ "via": "selector" })),
+                );
+            }
+        }
+    }
+
+    links
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Split `registry/name:tag` (or `name@digest`) into name and tag.
+fn split_image(reference: &str) -> (&str, Option<&str>) {
+    if let Some((name, digest)) = reference.split_once('@') {
+        return (name, Some(digest));
+    }
+    // A colon after the last slash is a tag; before it, a registry port
+    match reference.rfind(':') {
+        Some(i) if i > reference.rfind('/').unwrap_or(0) => {
+            (&reference[..i], Some(&reference[i + 1..]))
+        }
+        _ => (reference, None),
+    }
+}
+
+/// Compose port entries may be numbers, strings or long-syntax maps.
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Object(map) => {
+            let target = map.get("target").map(yaml_scalar).unwrap_or_default();
+            match map.get("published") {
+                Some(published) => format!("{}:{}", yaml_scalar(published), target),
+                None => target,
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+/// Join a relative path onto a directory, resolving `.` and `..`.
+fn join_path(dir: &str, relative: &str) -> String {
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in relative.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges_of<'a>(graph: &'a InfraGraph, kind: &str) -> Vec<(&'a str, &'a str)> {
+        graph
+            .edges
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_dockerfile() {
+        let dockerfile = r#"
+# syntax=docker/dockerfile:1
+FROM --platform=$BUILDPLATFORM golang:1.22 AS build
+WORKDIR /src
+COPY . .
+RUN go build -o /out/api \
+    ./cmd/api
+
+FROM gcr.io/distroless/static:nonroot
+COPY --from=build /out/api /api
+COPY --from=busybox:1.36 /bin/wget /wget
+EXPOSE 8080 9090/tcp
+"#;
+        let graph = extract_infra(
+            "services/api/Dockerfile",
+            "file:services/api/Dockerfile",
+            dockerfile,
+        )
+        .unwrap();
+
+        let built = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "image:build:services/api/Dockerfile")
+            .unwrap();
+        assert_eq!(built.label, "services/api");
+        assert_eq!(built.props["exposedPorts"], json!(["8080", "9090/tcp"]));
+        assert_eq!(built.props["stages"], json!(["build", "1"]));
+
+        let depends: Vec<&str> = edges_of(&graph, "dependsOn")
+            .into_iter()
+            .map(|(_, to)| to)
+            .collect();
+        assert_eq!(
+            depends,
+            vec![
+                "image:golang:1.22",
+                "image:gcr.io/distroless/static:nonroot",
+                "image:busybox:1.36"
+            ]
+        );
+        let distroless = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "image:gcr.io/distroless/static:nonroot")
+            .unwrap();
+        assert_eq!(distroless.props["name"], "gcr.io/distroless/static");
+        assert_eq!(distroless.props["tag"], "nonroot");
+        assert_eq!(graph.edges[1].props["line"], 3);
+    }
+
+    #[test]
+    fn test_extract_compose() {
+        let compose = r#"
+services:
+  api:
+    build:
+      context: ./services/api
+    ports: ["8080:8080"]
+    depends_on:
+      db:
+        condition: service_healthy
+  worker:
+    build: ./worker
+    depends_on: [api, db]
+  db:
+    image: postgres:16
+    ports:
+      - target: 5432
+        published: 15432
+"#;
+        let graph = extract_infra(
+            "deploy/docker-compose.yml",
+            "doc:deploy/docker-compose.yml",
+            compose,
+        )
+        .unwrap();
+
+        let services: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "service")
+            .map(|n| n.label.as_str())
+            .collect();
+        assert_eq!(services.len(), 3);
+
+        let depends = edges_of(&graph, "dependsOn");
+        let svc = |name: &str| format!("service:compose:deploy/docker-compose.yml:{}", name);
+        assert!(depends.contains(&(
+            svc("api").as_str(),
+            "image:build:deploy/services/api/Dockerfile"
+        )));
+        assert!(depends.contains(&(
+            svc("worker").as_str(),
+            "image:build:deploy/worker/Dockerfile"
+        )));
+        assert!(depends.contains(&(svc("worker").as_str(), svc("db").as_str())));
+        assert!(depends.contains(&(svc("db").as_str(), "image:postgres:16")));
+
+        let condition = graph
+            .edges
+            .iter()
+            .find(|e| e.from == svc("api") && e.to == svc("db"))
+            .unwrap();
+        assert_eq!(condition.props["condition"], "service_healthy");
+        let db = graph.nodes.iter().find(|n| n.id == svc("db")).unwrap();
+        assert_eq!(db.props["ports"], json!(["15432:5432"]));
+        assert_eq!(edges_of(&graph, "configures").len(), 3);
+    }
+
+    #[test]
+    fn test_extract_kubernetes() {
+        let manifest = r#"
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: api
+  namespace: shop
+spec:
+  template:
+    metadata:
+      labels: { app: api, tier: backend }
+    spec:
+      containers:
+        - name: api
+          image: ghcr.io/acme/api:1.4.0
+          envFrom:
+            - configMapRef: { name: api-config }
+          env:
+            - name: DB_PASSWORD
+              valueFrom:
+                secretKeyRef: { name: db, key: password }
+      volumes:
+        - name: data
+          persistentVolumeClaim: { claimName: api-data }
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: api
+  namespace: shop
+spec:
+  selector: { app: api }
+  ports: [{ port: 80, targetPort: 8080 }]
+---
+apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: web
+  namespace: shop
+spec:
+  rules:
+    - http:
+        paths:
+          - path: /
+            backend:
+              service: { name: api, port: { number: 80 } }
+---
+apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: api-config
+  namespace: shop
+data: { LOG_LEVEL: info }
+"#;
+        let graph = extract_infra("k8s/api.yaml", "doc:k8s/api.yaml", manifest).unwrap();
+
+        let deployment = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "k8s:shop/Deployment/api")
+            .unwrap();
+        assert_eq!(deployment.kind, "service");
+        let config_map = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "k8s:shop/ConfigMap/api-config")
+            .unwrap();
+        assert_eq!(config_map.kind, "resource");
+
+        let depends = edges_of(&graph, "dependsOn");
+        assert!(depends.contains(&("k8s:shop/Deployment/api", "image:ghcr.io/acme/api:1.4.0")));
+        assert!(depends.contains(&(
+            "k8s:shop/Deployment/api",
+            "k8s:shop/PersistentVolumeClaim/api-data"
+        )));
+        let configures = edges_of(&graph, "configures");
+        assert!(configures.contains(&("k8s:shop/ConfigMap/api-config", "k8s:shop/Deployment/api")));
+        assert!(configures.contains(&("k8s:shop/Secret/db", "k8s:shop/Deployment/api")));
+        assert!(
+            edges_of(&graph, "exposes").contains(&("k8s:shop/Ingress/web", "k8s:shop/Service/api"))
+        );
+
+        // The Service selector matches the Deployment's pod labels
+        let links = link_infra(&graph.nodes);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].from, "k8s:shop/Service/api");
+        assert_eq!(links[0].to, "k8s:shop/Deployment/api");
+
+        // Plain YAML is not a manifest
+        assert!(extract_infra(
+            "config/app.yaml",
+            "doc:config/app.yaml",
+            "kind: of\napiVersion:\n"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_extract_terraform() {
+        let tf = r#"
+# Networking
+resource "aws_vpc" "main" {
+  cidr_block = var.cidr
+}
+
+resource "aws_subnet" "private" {
+  vpc_id     = aws_vpc.main.id
+  depends_on = [aws_internet_gateway.gw]
+  tags       = { Name = "${local.prefix}-private" } // aws_fake.ignored
+}
+
+data "aws_ami" "ubuntu" {
+  most_recent = true
+}
+
+module "db" {
+  source    = "./modules/rds"
+  subnet_id = aws_subnet.private.id
+  ami       = data.aws_ami.ubuntu.id
+}
+"#;
+        let graph = extract_infra("infra/main.tf", "file:infra/main.tf", tf).unwrap();
+
+        let addresses: Vec<&str> = graph
+            .nodes
+            .iter()
+            .map(|n| n.props["address"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            addresses,
+            vec![
+                "aws_vpc.main",
+                "aws_subnet.private",
+                "data.aws_ami.ubuntu",
+                "module.db"
+            ]
+        );
+        let subnet = &graph.nodes[1];
+        assert_eq!(subnet.props["provider"], "aws");
+        assert_eq!(subnet.props["line"], 7);
+        assert_eq!(graph.nodes[3].props["source"], "./modules/rds");
+
+        let depends = edges_of(&graph, "dependsOn");
+        assert_eq!(
+            depends,
+            vec![
+                ("tf:infra:aws_subnet.private", "tf:infra:aws_vpc.main"),
+                (
+                    "tf:infra:aws_subnet.private",
+                    "tf:infra:aws_internet_gateway.gw"
+                ),
+                ("tf:infra:module.db", "tf:infra:aws_subnet.private"),
+                ("tf:infra:module.db", "tf:infra:data.aws_ami.ubuntu"),
+            ]
+        );
+    }
+}
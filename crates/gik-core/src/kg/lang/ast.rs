@@ -0,0 +1,1611 @@
+//! AST-backed symbol extraction using tree-sitter grammars.
+//!
+//! [`AstExtractor`] wraps a regex [`LanguageExtractor`] and replaces the
+//! symbols it can see in the syntax tree with exact ones:
+//!
+//! - **Spans**: 1-based start/end lines of the whole definition
+//! - **Containment**: `contains` relations from a symbol to the symbols nested
+//!   in it (method inside class inside module), plus a `parent` prop
+//! - **Doc comments**: preceding doc comments or Python docstrings, as a `doc` prop
+//! - **Calls**: `calls` relations from the enclosing symbol (or the file) to
+//!   the called name, with call-site lines; the KG extractor resolves them
+//!   to symbols in the same file or in imported files
+//!
+//! The wrapped regex extractor still provides framework detection,
+//! framework-specific symbols (React components, Angular decorators, ...) and
+//! relations. When a file has syntax errors, definitions and calls that parsed
+//! cleanly are still taken from the tree, and regex symbols the tree did not
+//! yield (those inside the broken regions) are kept alongside them.
+//!
+//! Every language in the supported-languages table of [`super`] has a grammar:
+//! Kotlin symbols come from class, object and function declarations, SQL
+//! symbols from `CREATE` statements, and Markdown headings from the section
+//! tree, so a heading contains the headings nested under it. CSS and HTML stay
+//! on the regex path.
+
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::rc::Rc;
+
+use tree_sitter::{Node, Parser};
+
+use super::{
+    FrameworkHint, KgRelationCandidate, KgSymbolCandidate, LanguageExtractor, LanguageKind,
+};
+
+/// Symbol kinds of Markdown headings, by level.
+const HEADING_KINDS: [&str; 6] = ["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// Maximum number of characters kept from a doc comment.
+const MAX_DOC_CHARS: usize = 1000;
+
+/// Ruby calls that are declarations rather than calls into project code.
+const RUBY_BUILTINS: &[&str] = &[
+    "require",
+    "require_relative",
+    "include",
+    "extend",
+    "attr_reader",
+    "attr_writer",
+    "attr_accessor",
+    "private",
+    "protected",
+    "public",
+    "puts",
+    "raise",
+];
+
+/// Symbol kinds that can own members defined outside their body
+/// (Rust `impl` blocks, Go receivers, C++ `Type::method` definitions).
+const OWNER_KINDS: &[&str] = &[
+    "class",
+    "struct",
+    "enum",
+    "trait",
+    "interface",
+    "record",
+    "type",
+];
+
+// ============================================================================
+// AstSymbol
+// ============================================================================
+
+/// A symbol definition found in a syntax tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstSymbol {
+    /// Symbol kind (e.g., "function", "class", "method").
+    pub kind: &'static str,
+
+    /// Symbol name as seen in code.
+    pub name: String,
+
+    /// 1-based line where the definition starts.
+    pub start_line: u32,
+
+    /// 1-based line where the definition ends.
+    pub end_line: u32,
+
+    /// Doc comment or docstring, markers stripped.
+    pub doc: Option<String>,
+
+    /// Index of the enclosing symbol in the same list.
+    pub parent: Option<usize>,
+}
+
+/// How a call names its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallType {
+    /// Bare name: `helper()`
+    Direct,
+    /// Called on a receiver: `user.save()`
+    Method,
+    /// Path-qualified: `User::new()`, `Foo::bar()`
+    Scoped,
+}
+
+impl CallType {
+    /// Prop value used on `calls` relations.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CallType::Direct => "direct",
+            CallType::Method => "method",
+            CallType::Scoped => "scoped",
+        }
+    }
+}
+
+/// A call site found in a syntax tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstCall {
+    /// Index of the innermost enclosing symbol, `None` for file-level code.
+    pub caller: Option<usize>,
+
+    /// Called name, without receiver or path.
+    pub callee: String,
+
+    /// Receiver expression or path qualifier, as written.
+    pub qualifier: Option<String>,
+
+    /// How the call names its target.
+    pub call_type: CallType,
+
+    /// 1-based line of the call site.
+    pub line: u32,
+}
+
+/// Symbols and call sites of one parsed file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AstFile {
+    /// Symbol definitions in source order.
+    pub symbols: Vec<AstSymbol>,
+
+    /// Call sites in source order.
+    pub calls: Vec<AstCall>,
+
+    /// Whether the tree contains syntax errors; definitions and calls inside
+    /// erroneous nodes are skipped.
+    pub has_errors: bool,
+}
+
+// ============================================================================
+// AstExtractor
+// ============================================================================
+
+/// Symbols and relations of one file, keyed by its path and a hash of its text.
+type CachedExtraction = (
+    (String, u64),
+    Vec<KgSymbolCandidate>,
+    Vec<KgRelationCandidate>,
+);
+
+/// AST-backed extractor with a regex fallback.
+#[derive(Debug, Clone, Default)]
+pub struct AstExtractor<E> {
+    inner: E,
+    /// Last result of the [`LanguageExtractor`] methods, so asking for the
+    /// symbols and then the relations of the same file parses it once.
+    last: RefCell<Option<CachedExtraction>>,
+}
+
+impl<E: LanguageExtractor> AstExtractor<E> {
+    /// Wrap a regex extractor.
+    pub fn new(inner: E) -> Self {
+        Self {
+            inner,
+            last: RefCell::new(None),
+        }
+    }
+
+    /// Run [`extract`](Self::extract), reusing the previous result when it was
+    /// for the same file and text.
+    fn extract_cached(
+        &self,
+        file_path: &str,
+        text: &str,
+    ) -> (Vec<KgSymbolCandidate>, Vec<KgRelationCandidate>) {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let key = (file_path.to_string(), hasher.finish());
+
+        if let Some((cached_key, symbols, relations)) = self.last.borrow().as_ref() {
+            if *cached_key == key {
+                return (symbols.clone(), relations.clone());
+            }
+        }
+
+        let (symbols, relations) = self.extract(file_path, text);
+        *self.last.borrow_mut() = Some((key, symbols.clone(), relations.clone()));
+        (symbols, relations)
+    }
+
+    /// Extract symbols and relations, parsing the file once.
+    pub fn extract(
+        &self,
+        file_path: &str,
+        text: &str,
+    ) -> (Vec<KgSymbolCandidate>, Vec<KgRelationCandidate>) {
+        let language = self.inner.language();
+        let mut regex_symbols = self.inner.extract_symbols(file_path, text);
+        let mut relations = self.inner.extract_relations(file_path, text);
+
+        let Some(AstFile {
+            symbols: ast_symbols,
+            calls,
+            has_errors,
+        }) = parse_file(file_path, language, text)
+        else {
+            return (regex_symbols, relations);
+        };
+
+        // Call sites come from the tree; the regex call heuristics are dropped
+        relations.retain(|r| r.kind != "calls");
+
+        // Regex symbols of kinds the grammar covers are replaced; the rest
+        // (framework-specific kinds) are kept and borrow the AST span. In a
+        // file with syntax errors, covered symbols the tree did not yield come
+        // from the broken regions and are kept as well.
+        let covered = ast_kinds(language);
+        let covered_names: HashSet<String> = regex_symbols
+            .iter()
+            .filter(|s| covered.contains(&s.kind.as_str()))
+            .map(|s| s.name.clone())
+            .collect();
+
+        // Props of replaced symbols (e.g. a Markdown heading's `title`) carry over
+        let mut covered_props: HashMap<(String, String), serde_json::Value> = HashMap::new();
+        for symbol in &regex_symbols {
+            if covered.contains(&symbol.kind.as_str()) && !symbol.props.is_null() {
+                covered_props
+                    .entry((symbol.kind.clone(), symbol.name.clone()))
+                    .or_insert_with(|| symbol.props.clone());
+            }
+        }
+        let mut symbols: Vec<KgSymbolCandidate> = ast_symbols
+            .iter()
+            .map(|ast| {
+                let props = covered_props
+                    .get(&(ast.kind.to_string(), ast.name.clone()))
+                    .cloned()
+                    .unwrap_or_default();
+                KgSymbolCandidate::new(ast.kind, &ast.name, language, file_path)
+                    .with_span(ast.start_line, ast.end_line)
+                    .with_props(props)
+            })
+            .collect();
+
+        let ast_names: HashSet<&str> = ast_symbols.iter().map(|s| s.name.as_str()).collect();
+        regex_symbols.retain(|s| {
+            !covered.contains(&s.kind.as_str())
+                || (has_errors && !ast_names.contains(s.name.as_str()))
+        });
+
+        // An AST symbol only represented by a framework symbol (e.g. a React
+        // component the regex pass does not report as a function) is merged
+        // into it instead of being emitted twice.
+        let mut replaced: Vec<Option<usize>> = vec![None; ast_symbols.len()];
+        for (i, ast) in ast_symbols.iter().enumerate() {
+            let Some(k) = regex_symbols.iter().position(|s| s.name == ast.name) else {
+                continue;
+            };
+            if regex_symbols[k].span.is_none() {
+                regex_symbols[k].span = Some((ast.start_line, ast.end_line));
+            }
+            if !covered_names.contains(&ast.name) {
+                replaced[i] = Some(k);
+            }
+        }
+
+        let framework = self.inner.detect_framework(file_path, text);
+        for symbol in &mut symbols {
+            symbol.framework = framework;
+        }
+        super::deduplicate_symbol_ids(&mut symbols);
+
+        let ids: Vec<String> = (0..ast_symbols.len())
+            .map(|i| match replaced[i] {
+                Some(k) => regex_symbols[k].id.clone(),
+                None => symbols[i].id.clone(),
+            })
+            .collect();
+
+        for (i, ast) in ast_symbols.iter().enumerate() {
+            let qualified = qualified_name(language, &ast_symbols, i);
+            let target = match replaced[i] {
+                Some(k) => &mut regex_symbols[k],
+                None => &mut symbols[i],
+            };
+
+            if let Some(p) = ast.parent {
+                relations.push(KgRelationCandidate::new(&ids[p], &ids[i], "contains"));
+                insert_prop(target, "parent", ids[p].clone());
+            }
+            if qualified != ast.name {
+                insert_prop(target, "qualifiedName", qualified);
+            }
+            if let Some(doc) = &ast.doc {
+                insert_prop(target, "doc", doc.clone());
+            }
+        }
+
+        relations.extend(call_relations(file_path, &ids, &calls));
+
+        let mut symbols: Vec<KgSymbolCandidate> = symbols
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| replaced[*i].is_none())
+            .map(|(_, s)| s)
+            .collect();
+        symbols.extend(regex_symbols);
+
+        (symbols, relations)
+    }
+}
+
+impl<E: LanguageExtractor> LanguageExtractor for AstExtractor<E> {
+    fn language(&self) -> LanguageKind {
+        self.inner.language()
+    }
+
+    fn detect_framework(&self, file_path: &str, text: &str) -> FrameworkHint {
+        self.inner.detect_framework(file_path, text)
+    }
+
+    fn extract_symbols(&self, file_path: &str, text: &str) -> Vec<KgSymbolCandidate> {
+        self.extract_cached(file_path, text).0
+    }
+
+    fn extract_relations(&self, file_path: &str, text: &str) -> Vec<KgRelationCandidate> {
+        self.extract_cached(file_path, text).1
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Get the tree-sitter grammar for a file, if one is bundled.
+pub fn grammar_for(file_path: &str, language: LanguageKind) -> Option<tree_sitter::Language> {
+    let ext = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let grammar = match language {
+        LanguageKind::JsTs => match ext.as_str() {
+            "ts" | "mts" | "cts" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
+            "tsx" => tree_sitter_typescript::LANGUAGE_TSX,
+            _ => tree_sitter_javascript::LANGUAGE,
+        },
+        LanguageKind::Python => tree_sitter_python::LANGUAGE,
+        LanguageKind::Ruby => tree_sitter_ruby::LANGUAGE,
+        LanguageKind::CSharp => tree_sitter_c_sharp::LANGUAGE,
+        LanguageKind::Java => tree_sitter_java::LANGUAGE,
+        LanguageKind::Rust => tree_sitter_rust::LANGUAGE,
+        LanguageKind::C => tree_sitter_c::LANGUAGE,
+        LanguageKind::Cpp => tree_sitter_cpp::LANGUAGE,
+        LanguageKind::Php => tree_sitter_php::LANGUAGE_PHP,
+        LanguageKind::Go => tree_sitter_go::LANGUAGE,
+        LanguageKind::Kotlin => tree_sitter_kotlin_ng::LANGUAGE,
+        LanguageKind::Sql => tree_sitter_sequel::LANGUAGE,
+        LanguageKind::Markdown => tree_sitter_md::LANGUAGE,
+        _ => return None,
+    };
+
+    Some(grammar.into())
+}
+
+/// Parse a file and collect its symbol definitions and call sites.
+///
+/// Returns `None` when there is no grammar for the language, so callers can
+/// fall back to regex extraction. Files with syntax errors still yield the
+/// definitions and calls whose nodes parsed cleanly.
+pub fn parse_file(file_path: &str, language: LanguageKind, text: &str) -> Option<AstFile> {
+    let grammar = grammar_for(file_path, language)?;
+    let mut parser = Parser::new();
+    parser.set_language(&grammar).ok()?;
+    let tree = parser.parse(text, None)?;
+    let root = tree.root_node();
+
+    let mut walker = Walker {
+        language,
+        src: text,
+        symbols: Vec::new(),
+        calls: Vec::new(),
+        owners: Vec::new(),
+    };
+    walker.walk(root);
+    walker.resolve_owners();
+
+    Some(AstFile {
+        symbols: walker.symbols,
+        calls: walker.calls,
+        has_errors: root.has_error(),
+    })
+}
+
+/// Group call sites by caller and target into `calls` relations.
+///
+/// Targets use the unresolved placeholder `sym:<lang>:*:function:<name>`;
+/// the KG extractor rewrites them once every file's symbols are known.
+fn call_relations(file_path: &str, ids: &[String], calls: &[AstCall]) -> Vec<KgRelationCandidate> {
+    let lang_tag = super::tag_from_path(file_path);
+    let file_node_id = format!("file:{}", file_path);
+
+    // One relation per caller and callee; the first call site sets the type
+    let mut groups: Vec<(&AstCall, Vec<u32>)> = Vec::new();
+    let mut index: HashMap<(Option<usize>, &str), usize> = HashMap::new();
+    for call in calls {
+        let key = (call.caller, call.callee.as_str());
+        let group = *index.entry(key).or_insert_with(|| {
+            groups.push((call, Vec::new()));
+            groups.len() - 1
+        });
+        let lines = &mut groups[group].1;
+        if !lines.contains(&call.line) {
+            lines.push(call.line);
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(first, lines)| {
+            let from_id = first
+                .caller
+                .map_or(file_node_id.as_str(), |c| ids[c].as_str());
+            let mut props = serde_json::json!({
+                "callee": first.callee,
+                "callType": first.call_type.as_str(),
+                "lines": lines,
+                "unresolved": true,
+            });
+            if let Some(qualifier) = &first.qualifier {
+                props["receiver"] = serde_json::json!(qualifier);
+            }
+            let to_id = format!("sym:{}:*:function:{}", lang_tag, first.callee);
+            KgRelationCandidate::new(from_id, &to_id, "calls").with_props(props)
+        })
+        .collect()
+}
+
+/// Symbol kinds produced from the syntax tree for a language.
+pub fn ast_kinds(language: LanguageKind) -> &'static [&'static str] {
+    match language {
+        LanguageKind::JsTs => &["function", "class", "method", "interface", "type", "enum"],
+        LanguageKind::Python => &["function", "method", "class", "constant"],
+        LanguageKind::Ruby => &["class", "module", "method", "constant"],
+        LanguageKind::CSharp => &[
+            "namespace",
+            "class",
+            "struct",
+            "interface",
+            "enum",
+            "record",
+            "method",
+        ],
+        LanguageKind::Java => &["class", "interface", "enum", "record", "method"],
+        LanguageKind::Rust => &[
+            "function", "struct", "enum", "trait", "module", "type", "constant",
+        ],
+        LanguageKind::C => &["function", "struct", "enum", "typedef", "macro"],
+        LanguageKind::Cpp => &[
+            "namespace",
+            "class",
+            "struct",
+            "enum",
+            "typedef",
+            "macro",
+            "function",
+        ],
+        LanguageKind::Php => &[
+            "namespace",
+            "class",
+            "interface",
+            "trait",
+            "enum",
+            "function",
+            "method",
+        ],
+        LanguageKind::Go => &["function", "struct", "interface", "type", "constant"],
+        LanguageKind::Kotlin => &["class", "object", "interface", "function"],
+        LanguageKind::Sql => &["table", "view", "function", "index"],
+        LanguageKind::Markdown => &HEADING_KINDS,
+        _ => &[],
+    }
+}
+
+/// Build a dotted (or `::`) path from the outermost parent to a symbol.
+fn qualified_name(language: LanguageKind, symbols: &[AstSymbol], index: usize) -> String {
+    let separator = match language {
+        LanguageKind::Rust
+        | LanguageKind::C
+        | LanguageKind::Cpp
+        | LanguageKind::Php
+        | LanguageKind::Ruby => "::",
+        _ => ".",
+    };
+
+    let mut parts = vec![symbols[index].name.as_str()];
+    let mut current = symbols[index].parent;
+    while let Some(p) = current {
+        parts.push(symbols[p].name.as_str());
+        current = symbols[p].parent;
+    }
+    parts.reverse();
+    parts.join(separator)
+}
+
+/// A definition recognised at a node.
+struct Definition {
+    kind: &'static str,
+    name: String,
+    /// Type the definition belongs to when declared outside its body.
+    owner: Option<String>,
+}
+
+/// A node still to visit, with its enclosing symbol and owner type name.
+type WalkItem<'t> = (Node<'t>, Option<usize>, Option<Rc<str>>);
+
+struct Walker<'a> {
+    language: LanguageKind,
+    src: &'a str,
+    symbols: Vec<AstSymbol>,
+    calls: Vec<AstCall>,
+    /// Owner type names for symbols declared outside their type's body.
+    owners: Vec<(usize, String)>,
+}
+
+impl<'a> Walker<'a> {
+    /// Visit every named descendant of `root` in document order.
+    ///
+    /// Uses an explicit stack rather than recursion: generated or minified
+    /// sources nest thousands of levels deep (long `a + b + c` chains), which
+    /// would otherwise overflow the thread stack and abort the process.
+    fn walk<'t>(&mut self, root: Node<'t>) {
+        let mut stack = Vec::new();
+        self.push_children(&mut stack, root, None, None);
+
+        while let Some((child, parent, owner)) = stack.pop() {
+            // Nodes containing syntax errors are not trusted as definitions or
+            // calls, but clean nodes nested inside them still are
+            if child.has_error() {
+                self.push_children(&mut stack, child, parent, owner);
+                continue;
+            }
+
+            if let Some((callee, qualifier, call_type)) = self.call(child) {
+                if !self.ignored_call(&callee, qualifier.as_deref(), call_type) {
+                    self.calls.push(AstCall {
+                        caller: parent,
+                        callee,
+                        qualifier,
+                        call_type,
+                        line: child.start_position().row as u32 + 1,
+                    });
+                }
+            }
+
+            let parent_kind = parent.map(|p| self.symbols[p].kind);
+            match self.definition(child, parent_kind) {
+                Some(def) => {
+                    let index = self.symbols.len();
+                    self.symbols.push(AstSymbol {
+                        kind: def.kind,
+                        name: def.name,
+                        start_line: child.start_position().row as u32 + 1,
+                        end_line: end_line(child),
+                        doc: self.doc_for(child),
+                        parent,
+                    });
+                    if let Some(owner) = def.owner.or_else(|| owner.as_deref().map(str::to_string))
+                    {
+                        self.owners.push((index, owner));
+                    }
+                    self.push_children(&mut stack, child, Some(index), None);
+                }
+                None => {
+                    let owner = self.impl_owner(child).map(Rc::from).or(owner);
+                    self.push_children(&mut stack, child, parent, owner);
+                }
+            }
+        }
+    }
+
+    /// Queue the named children of `node` so they pop in document order.
+    fn push_children<'t>(
+        &self,
+        stack: &mut Vec<WalkItem<'t>>,
+        node: Node<'t>,
+        parent: Option<usize>,
+        owner: Option<Rc<str>>,
+    ) {
+        let mut cursor = node.walk();
+        let start = stack.len();
+        stack.extend(
+            node.named_children(&mut cursor)
+                .map(|child| (child, parent, owner.clone())),
+        );
+        stack[start..].reverse();
+    }
+
+    /// Attach members declared outside their type (Rust `impl`, Go receivers,
+    /// C++ `Type::method`) to the type when it is defined in the same file.
+    fn resolve_owners(&mut self) {
+        for (index, owner) in std::mem::take(&mut self.owners) {
+            let found = self
+                .symbols
+                .iter()
+                .position(|s| s.name == owner && OWNER_KINDS.contains(&s.kind));
+            if let Some(p) = found {
+                if p != index {
+                    self.symbols[index].parent = Some(p);
+                }
+            }
+        }
+    }
+
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.src.as_bytes()).unwrap_or("")
+    }
+
+    fn field_text(&self, node: Node, field: &str) -> Option<String> {
+        let name = node.child_by_field_name(field)?;
+        let name = match name.kind() {
+            // Ruby `class Foo::Bar` – keep the last segment
+            "scope_resolution" => name.child_by_field_name("name").unwrap_or(name),
+            _ => name,
+        };
+        let text = self.text(name).trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    /// Recognise a symbol definition for the current language.
+    fn definition(&self, node: Node, parent_kind: Option<&str>) -> Option<Definition> {
+        let named = |kind: &'static str| {
+            self.field_text(node, "name").map(|name| Definition {
+                kind,
+                name,
+                owner: None,
+            })
+        };
+
+        match self.language {
+            LanguageKind::Rust => match node.kind() {
+                "function_item" | "function_signature_item" => named("function"),
+                "struct_item" | "union_item" => named("struct"),
+                "enum_item" => named("enum"),
+                "trait_item" => named("trait"),
+                "type_item" => named("type"),
+                "const_item" | "static_item" => named("constant"),
+                "mod_item" => {
+                    let def = named("module")?;
+                    // Test modules are walked but not reported, as in the regex pass
+                    (def.name != "tests" && def.name != "test").then_some(def)
+                }
+                _ => None,
+            },
+            LanguageKind::JsTs => match node.kind() {
+                "function_declaration" | "generator_function_declaration" => named("function"),
+                "class_declaration" | "abstract_class_declaration" => named("class"),
+                "method_definition" => named("method"),
+                "interface_declaration" => named("interface"),
+                "type_alias_declaration" => named("type"),
+                "enum_declaration" => named("enum"),
+                "variable_declarator" => {
+                    let value = node.child_by_field_name("value")?;
+                    let is_function = matches!(
+                        value.kind(),
+                        "arrow_function" | "function_expression" | "function"
+                    );
+                    let top_level =
+                        node.parent()
+                            .and_then(|decl| decl.parent())
+                            .is_some_and(|scope| {
+                                matches!(scope.kind(), "program" | "export_statement")
+                            });
+                    if is_function && top_level {
+                        named("function")
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            },
+            LanguageKind::Python => match node.kind() {
+                "class_definition" => named("class"),
+                "function_definition" if parent_kind == Some("class") => named("method"),
+                "function_definition" => named("function"),
+                "assignment" => {
+                    let module_level = node
+                        .parent()
+                        .and_then(|stmt| stmt.parent())
+                        .is_some_and(|scope| scope.kind() == "module");
+                    let left = node.child_by_field_name("left")?;
+                    let name = self.text(left);
+                    if module_level && left.kind() == "identifier" && is_constant_name(name) {
+                        named_from(name, "constant")
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            },
+            LanguageKind::Ruby => match node.kind() {
+                "class" => named("class"),
+                "module" => named("module"),
+                "method" | "singleton_method" => named("method"),
+                "assignment" => {
+                    let left = node.child_by_field_name("left")?;
+                    if left.kind() == "constant" {
+                        named_from(self.text(left), "constant")
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            },
+            LanguageKind::Java => match node.kind() {
+                "class_declaration" => named("class"),
+                "interface_declaration" => named("interface"),
+                "enum_declaration" => named("enum"),
+                "record_declaration" => named("record"),
+                "method_declaration" => named("method"),
+                _ => None,
+            },
+            LanguageKind::CSharp => match node.kind() {
+                "namespace_declaration" | "file_scoped_namespace_declaration" => named("namespace"),
+                "class_declaration" => named("class"),
+                "struct_declaration" => named("struct"),
+                "interface_declaration" => named("interface"),
+                "enum_declaration" => named("enum"),
+                "record_declaration" => named("record"),
+                "method_declaration" => named("method"),
+                _ => None,
+            },
+            LanguageKind::Php => match node.kind() {
+                "namespace_definition" => named("namespace"),
+                "class_declaration" => named("class"),
+                "interface_declaration" => named("interface"),
+                "trait_declaration" => named("trait"),
+                "enum_declaration" => named("enum"),
+                "function_definition" => named("function"),
+                "method_declaration" => named("method"),
+                _ => None,
+            },
+            LanguageKind::Go => match node.kind() {
+                "function_declaration" => named("function"),
+                "method_declaration" => {
+                    let receiver = node.child_by_field_name("receiver")?;
+                    let owner = self.first_of_kind(receiver, "type_identifier");
+                    let mut def = named("function")?;
+                    def.owner = owner;
+                    Some(def)
+                }
+                "type_spec" | "type_alias" => {
+                    let kind = match node.child_by_field_name("type").map(|t| t.kind()) {
+                        Some("struct_type") => "struct",
+                        Some("interface_type") => "interface",
+                        _ => "type",
+                    };
+                    named(kind)
+                }
+                "const_spec" => named("constant"),
+                _ => None,
+            },
+            LanguageKind::C | LanguageKind::Cpp => match node.kind() {
+                "function_definition" => {
+                    let (name, owner) = self.declarator_name(node)?;
+                    Some(Definition {
+                        kind: "function",
+                        name,
+                        owner,
+                    })
+                }
+                "struct_specifier" | "enum_specifier" | "class_specifier" => {
+                    node.child_by_field_name("body")?;
+                    let kind = match node.kind() {
+                        "struct_specifier" => "struct",
+                        "enum_specifier" => "enum",
+                        _ => "class",
+                    };
+                    named(kind)
+                }
+                "namespace_definition" => named("namespace"),
+                "type_definition" => {
+                    let (name, _) = self.declarator_name(node)?;
+                    named_from(&name, "typedef")
+                }
+                "preproc_def" | "preproc_function_def" => named("macro"),
+                _ => None,
+            },
+            LanguageKind::Kotlin => match node.kind() {
+                "class_declaration" => {
+                    let mut cursor = node.walk();
+                    let is_interface = node
+                        .children(&mut cursor)
+                        .any(|child| child.kind() == "interface");
+                    named(if is_interface { "interface" } else { "class" })
+                }
+                "object_declaration" | "companion_object" => named("object"),
+                "function_declaration" => named("function"),
+                _ => None,
+            },
+            LanguageKind::Sql => {
+                let kind = match node.kind() {
+                    "create_table" => "table",
+                    "create_view" | "create_materialized_view" => "view",
+                    "create_function" => "function",
+                    "create_index" => {
+                        let name = node.child_by_field_name("column")?;
+                        return named_from(unquote(self.text(name)), "index");
+                    }
+                    _ => return None,
+                };
+                // `CREATE TABLE [schema.]name` – keep the unqualified name
+                let mut cursor = node.walk();
+                let reference = node
+                    .named_children(&mut cursor)
+                    .find(|child| child.kind() == "object_reference")?;
+                let name = reference.child_by_field_name("name")?;
+                named_from(unquote(self.text(name)), kind)
+            }
+            LanguageKind::Markdown => {
+                if node.kind() != "section" {
+                    return None;
+                }
+                let heading = node.named_child(0)?;
+                let level = match heading.kind() {
+                    "atx_heading" => {
+                        let marker = heading.named_child(0)?.kind();
+                        marker
+                            .strip_prefix("atx_h")?
+                            .strip_suffix("_marker")?
+                            .parse::<usize>()
+                            .ok()?
+                    }
+                    "setext_heading" => {
+                        let mut cursor = heading.walk();
+                        let h1 = heading
+                            .named_children(&mut cursor)
+                            .any(|child| child.kind() == "setext_h1_underline");
+                        if h1 {
+                            1
+                        } else {
+                            2
+                        }
+                    }
+                    _ => return None,
+                };
+                let title = self.field_text(heading, "heading_content")?;
+                let kind = HEADING_KINDS.get(level.checked_sub(1)?)?;
+                named_from(&super::markdown_lang::slugify(&title), kind)
+            }
+            _ => None,
+        }
+    }
+
+    /// Recognise a call expression: callee name, receiver or path, call type.
+    fn call(&self, node: Node) -> Option<(String, Option<String>, CallType)> {
+        let (target, name_field, qualifier_field) = match (self.language, node.kind()) {
+            (LanguageKind::Java, "method_invocation") => (node, "name", "object"),
+            (LanguageKind::Ruby, "call") => (node, "method", "receiver"),
+            (LanguageKind::Php, "function_call_expression") => {
+                (node.child_by_field_name("function")?, "", "")
+            }
+            (LanguageKind::Php, "member_call_expression" | "nullsafe_member_call_expression") => {
+                (node, "name", "object")
+            }
+            (LanguageKind::Php, "scoped_call_expression") => {
+                let name = self.field_text(node, "name")?;
+                let scope = self.field_text(node, "scope");
+                return Some((name, scope, CallType::Scoped));
+            }
+            (LanguageKind::Kotlin, "call_expression") => {
+                return self.callee(node.named_child(0)?);
+            }
+            (LanguageKind::Python, "call")
+            | (LanguageKind::CSharp, "invocation_expression")
+            | (
+                LanguageKind::JsTs
+                | LanguageKind::Rust
+                | LanguageKind::Go
+                | LanguageKind::C
+                | LanguageKind::Cpp,
+                "call_expression",
+            ) => return self.callee(node.child_by_field_name("function")?),
+            _ => return None,
+        };
+
+        if name_field.is_empty() {
+            return self.callee(target);
+        }
+        let name = self.field_text(target, name_field)?;
+        match target.child_by_field_name(qualifier_field) {
+            Some(receiver) => Some((
+                name,
+                Some(self.text(receiver).to_string()),
+                CallType::Method,
+            )),
+            None => Some((name, None, CallType::Direct)),
+        }
+    }
+
+    /// Split the function part of a call into name, receiver/path and type.
+    fn callee(&self, function: Node) -> Option<(String, Option<String>, CallType)> {
+        let (name, qualifier, call_type) = match function.kind() {
+            "identifier" | "name" | "qualified_name" | "field_identifier" => {
+                (function, None, CallType::Direct)
+            }
+            // Rust `turbofish::<T>()`, C++ `make<T>()`, C# `Get<T>()`
+            "generic_function" | "template_function" | "generic_name" => {
+                let inner = function
+                    .child_by_field_name("function")
+                    .or_else(|| function.child_by_field_name("name"))
+                    .or_else(|| function.named_child(0))?;
+                return self.callee(inner);
+            }
+            "scoped_identifier" | "qualified_identifier" => {
+                let name = function.child_by_field_name("name")?;
+                let path = function
+                    .child_by_field_name("path")
+                    .or_else(|| function.child_by_field_name("scope"));
+                if matches!(name.kind(), "qualified_identifier" | "template_function") {
+                    return self.callee(name);
+                }
+                (name, path, CallType::Scoped)
+            }
+            "field_expression"
+            | "member_expression"
+            | "attribute"
+            | "selector_expression"
+            | "member_access_expression" => {
+                let name = ["field", "property", "attribute", "name"]
+                    .iter()
+                    .find_map(|f| function.child_by_field_name(f))?;
+                let receiver = ["value", "object", "argument", "operand", "expression"]
+                    .iter()
+                    .find_map(|f| function.child_by_field_name(f));
+                (name, receiver, CallType::Method)
+            }
+            // Kotlin `user.save()`: receiver and name are unnamed children
+            "navigation_expression" => {
+                let name = function.named_child(function.named_child_count().checked_sub(1)?)?;
+                if name.kind() != "identifier" {
+                    return None;
+                }
+                (name, function.named_child(0), CallType::Method)
+            }
+            _ => return None,
+        };
+
+        let name = self.text(name).trim();
+        if name.is_empty() {
+            return None;
+        }
+        // `std::io::stdin` / `App\helper` – keep the last path segment
+        let name = name.rsplit(['\\', ':']).next().unwrap_or(name);
+        let qualifier = qualifier.map(|q| self.text(q).to_string());
+        Some((name.to_string(), qualifier, call_type))
+    }
+
+    /// Skip built-ins and keyword-like calls that only add noise.
+    fn ignored_call(&self, callee: &str, qualifier: Option<&str>, call_type: CallType) -> bool {
+        let builtins: &[&str] = match self.language {
+            LanguageKind::Rust => super::rust_lang::BUILTIN_FUNCTIONS,
+            LanguageKind::JsTs => super::js_ts_lang::BUILTIN_FUNCTIONS,
+            LanguageKind::Python => super::python_lang::BUILTIN_FUNCTIONS,
+            LanguageKind::Ruby => RUBY_BUILTINS,
+            _ => &[],
+        };
+        // Path-qualified calls (`User::new()`) name project code explicitly
+        if call_type == CallType::Scoped || !builtins.contains(&callee) {
+            return false;
+        }
+        // Built-in names still count when called on the current object
+        !(call_type == CallType::Method && qualifier.is_some_and(is_self_receiver))
+    }
+
+    /// Type name of a Rust `impl` block, which owns the functions inside it.
+    fn impl_owner(&self, node: Node) -> Option<String> {
+        if self.language != LanguageKind::Rust || node.kind() != "impl_item" {
+            return None;
+        }
+        let mut ty = node.child_by_field_name("type")?;
+        loop {
+            ty = match ty.kind() {
+                "generic_type" => ty.child_by_field_name("type")?,
+                "scoped_type_identifier" => ty.child_by_field_name("name")?,
+                "reference_type" => ty.child_by_field_name("type")?,
+                _ => return Some(self.text(ty).to_string()),
+            };
+        }
+    }
+
+    /// Follow C/C++ declarators down to the declared name and its owning type.
+    fn declarator_name(&self, node: Node) -> Option<(String, Option<String>)> {
+        let mut current = node.child_by_field_name("declarator")?;
+        let mut owner = None;
+        loop {
+            match current.kind() {
+                "identifier" | "field_identifier" | "type_identifier" | "destructor_name"
+                | "operator_name" => {
+                    return Some((self.text(current).to_string(), owner));
+                }
+                "qualified_identifier" => {
+                    if let Some(scope) = current.child_by_field_name("scope") {
+                        owner = Some(self.text(scope).to_string());
+                    }
+                    current = current.child_by_field_name("name")?;
+                }
+                _ => current = current.child_by_field_name("declarator")?,
+            }
+        }
+    }
+
+    fn first_of_kind(&self, node: Node, kind: &str) -> Option<String> {
+        if node.kind() == kind {
+            return Some(self.text(node).to_string());
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        children
+            .into_iter()
+            .find_map(|child| self.first_of_kind(child, kind))
+    }
+
+    /// Doc comment attached to a definition.
+    fn doc_for(&self, node: Node) -> Option<String> {
+        if self.language == LanguageKind::Python {
+            return self.docstring(node);
+        }
+
+        // Step out of wrappers that own the leading comments
+        let mut anchor = node;
+        while let Some(parent) = anchor.parent() {
+            let first = anchor.prev_named_sibling().is_none();
+            let wrapper = matches!(
+                parent.kind(),
+                "export_statement"
+                    | "template_declaration"
+                    | "type_declaration"
+                    | "const_declaration"
+                    | "lexical_declaration"
+                    | "variable_declaration"
+            );
+            if !first || !(wrapper || parent.start_byte() == anchor.start_byte()) {
+                break;
+            }
+            anchor = parent;
+        }
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut next_row = anchor.start_position().row;
+        let mut sibling = anchor.prev_named_sibling();
+        while let Some(prev) = sibling {
+            if prev.kind() == "attribute_item" {
+                next_row = prev.start_position().row;
+                sibling = prev.prev_named_sibling();
+                continue;
+            }
+            if !prev.kind().contains("comment") || prev.end_position().row + 1 < next_row {
+                break;
+            }
+            let text = self.text(prev);
+            if self.language == LanguageKind::Rust
+                && !(text.starts_with("///") && !text.starts_with("////")
+                    || text.starts_with("/**"))
+            {
+                break;
+            }
+            let mut block: Vec<String> = text.lines().filter_map(strip_comment).collect();
+            block.append(&mut lines);
+            lines = block;
+            next_row = prev.start_position().row;
+            sibling = prev.prev_named_sibling();
+        }
+
+        join_doc(&lines)
+    }
+
+    /// First string statement in a Python class or function body.
+    fn docstring(&self, node: Node) -> Option<String> {
+        let body = node.child_by_field_name("body")?;
+        let first = body.named_child(0)?;
+        if first.kind() != "expression_statement" {
+            return None;
+        }
+        let string = first.named_child(0).filter(|s| s.kind() == "string")?;
+        let mut cursor = string.walk();
+        let content: String = string
+            .named_children(&mut cursor)
+            .filter(|c| c.kind() == "string_content")
+            .map(|c| self.text(c))
+            .collect();
+        let lines: Vec<String> = content.lines().map(|l| l.trim().to_string()).collect();
+        join_doc(&lines)
+    }
+}
+
+/// 1-based last line of a node, ignoring a trailing newline it swallowed.
+fn end_line(node: Node) -> u32 {
+    let end = node.end_position();
+    if end.column == 0 && end.row > node.start_position().row {
+        end.row as u32
+    } else {
+        end.row as u32 + 1
+    }
+}
+
+fn insert_prop(symbol: &mut KgSymbolCandidate, key: &str, value: String) {
+    if !symbol.props.is_object() {
+        symbol.props = serde_json::json!({});
+    }
+    if let Some(obj) = symbol.props.as_object_mut() {
+        obj.insert(key.to_string(), serde_json::Value::String(value));
+    }
+}
+
+/// Whether a receiver refers to the current object or type.
+pub fn is_self_receiver(receiver: &str) -> bool {
+    matches!(
+        receiver,
+        "self" | "Self" | "this" | "cls" | "$this" | "base" | "super"
+    )
+}
+
+/// Strip SQL identifier quoting (`"name"`, `` `name` ``, `[name]`).
+fn unquote(name: &str) -> &str {
+    name.trim()
+        .trim_matches(|c| matches!(c, '"' | '`' | '[' | ']'))
+}
+
+fn named_from(name: &str, kind: &'static str) -> Option<Definition> {
+    (!name.is_empty()).then(|| Definition {
+        kind,
+        name: name.to_string(),
+        owner: None,
+    })
+}
+
+/// UPPER_CASE names, as used for module-level constants.
+fn is_constant_name(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Strip comment markers from one comment line, dropping empty lines.
+fn strip_comment(line: &str) -> Option<String> {
+    let mut line = line.trim();
+    for marker in ["///", "//!", "//", "/**", "/*", "#", "--"] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            line = rest;
+            break;
+        }
+    }
+    let line = line.strip_suffix("*/").unwrap_or(line).trim();
+    let line = line.strip_prefix('*').unwrap_or(line).trim();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
+fn join_doc(lines: &[String]) -> Option<String> {
+    let doc = lines
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if doc.is_empty() {
+        return None;
+    }
+    Some(match doc.char_indices().nth(MAX_DOC_CHARS) {
+        Some((end, _)) => doc[..end].to_string(),
+        None => doc,
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kg::lang::extract_for_file;
+    use crate::kg::lang::js_ts_lang::JsTsExtractor;
+    use crate::kg::lang::rust_lang::RustExtractor;
+
+    fn find<'a>(symbols: &'a [KgSymbolCandidate], kind: &str, name: &str) -> &'a KgSymbolCandidate {
+        symbols
+            .iter()
+            .find(|s| s.kind == kind && s.name == name)
+            .unwrap_or_else(|| panic!("missing {} {}", kind, name))
+    }
+
+    fn prop<'a>(symbol: &'a KgSymbolCandidate, key: &str) -> Option<&'a str> {
+        symbol.props.get(key).and_then(|v| v.as_str())
+    }
+
+    #[test]
+    fn test_rust_spans_containment_and_docs() {
+        let code = r#"/// A user record.
+#[derive(Debug)]
+pub struct User {
+    id: u64,
+}
+
+impl User {
+    /// Create a user.
+    pub fn new(id: u64) -> Self {
+        User { id }
+    }
+}
+
+pub mod utils {
+    pub fn helper() {}
+}
+
+#[cfg(test)]
+mod tests {
+    fn test_new() {}
+}
+"#;
+        let (symbols, relations) =
+            AstExtractor::new(RustExtractor::new()).extract("src/user.rs", code);
+
+        let user = find(&symbols, "struct", "User");
+        assert_eq!(user.span, Some((3, 5)));
+        assert_eq!(prop(user, "doc"), Some("A user record."));
+
+        let new = find(&symbols, "function", "new");
+        assert_eq!(new.span, Some((9, 11)));
+        assert_eq!(prop(new, "doc"), Some("Create a user."));
+        assert_eq!(prop(new, "parent"), Some(user.id.as_str()));
+        assert_eq!(prop(new, "qualifiedName"), Some("User::new"));
+
+        let helper = find(&symbols, "function", "helper");
+        assert_eq!(prop(helper, "qualifiedName"), Some("utils::helper"));
+
+        // Test modules are not symbols, but their functions are
+        assert!(!symbols
+            .iter()
+            .any(|s| s.kind == "module" && s.name == "tests"));
+        assert!(prop(find(&symbols, "function", "test_new"), "parent").is_none());
+
+        assert!(relations
+            .iter()
+            .any(|r| r.kind == "contains" && r.from_id == user.id && r.to_id == new.id));
+    }
+
+    #[test]
+    fn test_python_methods_and_docstrings() {
+        let code = r#"MAX_RETRIES = 3
+
+class UserService:
+    """Loads users."""
+
+    def get_user(self, user_id):
+        """Fetch one user."""
+        def inner():
+            pass
+        return inner
+
+def process_data(items):
+    return items
+"#;
+        let (symbols, _) = extract_for_file("src/services.py", code);
+
+        assert_eq!(find(&symbols, "constant", "MAX_RETRIES").span, Some((1, 1)));
+        let class = find(&symbols, "class", "UserService");
+        assert_eq!(prop(class, "doc"), Some("Loads users."));
+
+        let method = find(&symbols, "method", "get_user");
+        assert_eq!(prop(method, "doc"), Some("Fetch one user."));
+        assert_eq!(prop(method, "qualifiedName"), Some("UserService.get_user"));
+
+        // Nested functions are found and attached to their enclosing method
+        let inner = find(&symbols, "function", "inner");
+        assert_eq!(prop(inner, "parent"), Some(method.id.as_str()));
+        assert!(prop(find(&symbols, "function", "process_data"), "parent").is_none());
+    }
+
+    #[test]
+    fn test_typescript_class_members() {
+        let code = r#"/** Talks to the API. */
+export class UserService {
+    /** Load a user. */
+    async getUser(id: string) {
+        return { id };
+    }
+}
+
+export const formatUser = (name: string) => name.trim();
+"#;
+        let (symbols, relations) = extract_for_file("src/users.ts", code);
+
+        let class = find(&symbols, "class", "UserService");
+        assert_eq!(class.span, Some((2, 7)));
+        assert_eq!(prop(class, "doc"), Some("Talks to the API."));
+
+        let method = find(&symbols, "method", "getUser");
+        assert_eq!(prop(method, "doc"), Some("Load a user."));
+        assert!(relations
+            .iter()
+            .any(|r| r.kind == "contains" && r.from_id == class.id && r.to_id == method.id));
+
+        assert_eq!(find(&symbols, "function", "formatUser").span, Some((9, 9)));
+    }
+
+    #[test]
+    fn test_framework_symbols_take_ast_spans() {
+        let tsx = r#"import React from "react";
+
+export function UserCard() {
+    return <div className="card">Hi</div>;
+}
+"#;
+        let (symbols, _) = AstExtractor::new(JsTsExtractor::new()).extract("src/UserCard.tsx", tsx);
+
+        let component = find(&symbols, "reactComponent", "UserCard");
+        assert_eq!(component.span, Some((3, 5)));
+        assert!(
+            !symbols
+                .iter()
+                .any(|s| s.kind == "function" && s.name == "UserCard"),
+            "React component should not also be reported as a function"
+        );
+    }
+
+    #[test]
+    fn test_owner_types_outside_body() {
+        let go = "package main\n\ntype Server struct{}\n\nfunc (s *Server) Start() {}\n";
+        let (symbols, _) = extract_for_file("main.go", go);
+        let server = find(&symbols, "struct", "Server");
+        assert_eq!(
+            prop(find(&symbols, "function", "Start"), "parent"),
+            Some(server.id.as_str())
+        );
+
+        let cpp = "class Engine {\n  void run();\n};\n\nvoid Engine::run() {}\n";
+        let (symbols, _) = extract_for_file("engine.cpp", cpp);
+        let engine = find(&symbols, "class", "Engine");
+        let run = find(&symbols, "function", "run");
+        assert_eq!(run.span, Some((5, 5)));
+        assert_eq!(prop(run, "parent"), Some(engine.id.as_str()));
+    }
+
+    #[test]
+    fn test_call_sites_grouped_by_caller() {
+        let code = r#"struct Repo;
+
+impl Repo {
+    fn save(&self) {
+        self.validate();
+        helper();
+        helper();
+        let items: Vec<u32> = Vec::new();
+        println!("{}", items.len());
+    }
+
+    fn validate(&self) {}
+}
+
+fn helper() {
+    let repo = Repo::new();
+}
+"#;
+        let (symbols, relations) =
+            AstExtractor::new(RustExtractor::new()).extract("src/repo.rs", code);
+        let save = find(&symbols, "function", "save");
+        let calls: Vec<&KgRelationCandidate> = relations
+            .iter()
+            .filter(|r| r.kind == "calls" && r.from_id == save.id)
+            .collect();
+
+        let validate = calls
+            .iter()
+            .find(|r| r.props["callee"] == "validate")
+            .unwrap();
+        assert_eq!(validate.to_id, "sym:rs:*:function:validate");
+        assert_eq!(validate.props["callType"], "method");
+        assert_eq!(validate.props["receiver"], "self");
+        assert_eq!(validate.props["lines"], serde_json::json!([5]));
+
+        let helper = calls
+            .iter()
+            .find(|r| r.props["callee"] == "helper")
+            .unwrap();
+        assert_eq!(helper.props["lines"], serde_json::json!([6, 7]));
+
+        // Built-ins are skipped unless path-qualified
+        assert!(!calls.iter().any(|r| r.props["callee"] == "len"));
+        let helper_fn = find(&symbols, "function", "helper");
+        assert!(relations.iter().any(|r| r.kind == "calls"
+            && r.from_id == helper_fn.id
+            && r.props["callee"] == "new"
+            && r.props["callType"] == "scoped"));
+    }
+
+    #[test]
+    fn test_kotlin_classes_objects_and_calls() {
+        let code = r#"package com.example
+
+/** Loads users. */
+class UserService(private val repo: UserRepository) {
+    fun getUser(id: Long): User {
+        return repo.findById(id)
+    }
+
+    companion object Factory {
+        fun create(): UserService = UserService(UserRepository())
+    }
+}
+
+interface UserRepository {
+    fun findById(id: Long): User
+}
+"#;
+        let (symbols, relations) = extract_for_file("src/UserService.kt", code);
+
+        let service = find(&symbols, "class", "UserService");
+        assert_eq!(service.span, Some((4, 12)));
+        assert_eq!(prop(service, "doc"), Some("Loads users."));
+
+        let get_user = find(&symbols, "function", "getUser");
+        assert_eq!(prop(get_user, "parent"), Some(service.id.as_str()));
+        assert_eq!(
+            prop(find(&symbols, "object", "Factory"), "parent"),
+            Some(service.id.as_str())
+        );
+        assert!(symbols
+            .iter()
+            .any(|s| s.kind == "interface" && s.name == "UserRepository"));
+
+        let call = relations
+            .iter()
+            .find(|r| r.kind == "calls" && r.from_id == get_user.id)
+            .unwrap();
+        assert_eq!(call.props["callee"], "findById");
+        assert_eq!(call.props["callType"], "method");
+        assert_eq!(call.props["receiver"], "repo");
+    }
+
+    #[test]
+    fn test_sql_create_statements() {
+        let code = r#"-- Registered users.
+CREATE TABLE app.users (
+    id INT PRIMARY KEY,
+    email TEXT
+);
+
+CREATE INDEX idx_users_email ON users (email);
+
+CREATE VIEW active_users AS SELECT * FROM users;
+"#;
+        let (symbols, _) = extract_for_file("db/schema.sql", code);
+
+        let users = find(&symbols, "table", "users");
+        assert_eq!(users.span, Some((2, 5)));
+        assert_eq!(prop(users, "doc"), Some("Registered users."));
+        assert_eq!(
+            find(&symbols, "index", "idx_users_email").span,
+            Some((7, 7))
+        );
+        assert_eq!(find(&symbols, "view", "active_users").span, Some((9, 9)));
+    }
+
+    #[test]
+    fn test_markdown_sections_nest_headings() {
+        let code = r#"# Guide
+
+Intro.
+
+## Install
+
+```bash
+# not a heading
+```
+
+## Usage
+
+Run it.
+"#;
+        let (symbols, relations) = extract_for_file("docs/guide.md", code);
+
+        let guide = find(&symbols, "h1", "guide");
+        assert_eq!(guide.span, Some((1, 13)));
+        let install = find(&symbols, "h2", "install");
+        assert_eq!(install.span, Some((5, 10)));
+        assert_eq!(prop(install, "title"), Some("Install"));
+        assert_eq!(prop(install, "parent"), Some(guide.id.as_str()));
+        assert!(relations
+            .iter()
+            .any(|r| r.kind == "contains" && r.from_id == guide.id && r.to_id == install.id));
+
+        // Comments inside code blocks are not headings
+        assert!(!symbols.iter().any(|s| s.name == "not-a-heading"));
+    }
+
+    #[test]
+    fn test_trait_methods_share_one_extraction() {
+        let code = "pub struct User;\n\nimpl User {\n    pub fn new() -> Self { User }\n}\n";
+        let extractor = AstExtractor::new(RustExtractor::new());
+        let (symbols, relations) = extractor.extract("src/user.rs", code);
+
+        let trait_symbols = extractor.extract_symbols("src/user.rs", code);
+        let cached = extractor
+            .last
+            .borrow()
+            .as_ref()
+            .map(|(key, _, _)| key.clone());
+        let trait_relations = extractor.extract_relations("src/user.rs", code);
+
+        let ids = |s: &[KgSymbolCandidate]| s.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&trait_symbols), ids(&symbols));
+        assert_eq!(trait_relations.len(), relations.len());
+        // The relations call reused the entry stored by the symbols call
+        assert_eq!(
+            extractor
+                .last
+                .borrow()
+                .as_ref()
+                .map(|(key, _, _)| key.clone()),
+            cached
+        );
+
+        // A different file replaces the cached entry
+        extractor.extract_symbols("src/other.rs", "fn other() {}\n");
+        assert_ne!(
+            extractor
+                .last
+                .borrow()
+                .as_ref()
+                .map(|(key, _, _)| key.clone()),
+            cached
+        );
+    }
+
+    #[test]
+    fn test_keeps_clean_nodes_in_files_with_syntax_errors() {
+        let code = r#"/// Still parses.
+pub struct Still {
+    id: u64,
+}
+
+impl Still {
+    pub fn get(&self) -> u64 {
+        self.id
+    }
+}
+
+pub fn broken( {
+"#;
+        let parsed = parse_file("src/broken.rs", LanguageKind::Rust, code).unwrap();
+        assert!(parsed.has_errors);
+        assert!(parsed.symbols.iter().any(|s| s.name == "Still"));
+
+        let (symbols, _) = AstExtractor::new(RustExtractor::new()).extract("src/broken.rs", code);
+
+        // Clean definitions keep their AST spans, containment and docs
+        let still = find(&symbols, "struct", "Still");
+        assert_eq!(still.span, Some((2, 4)));
+        assert_eq!(prop(still, "doc"), Some("Still parses."));
+        let get = find(&symbols, "function", "get");
+        assert_eq!(prop(get, "parent"), Some(still.id.as_str()));
+
+        // The broken definition falls back to the regex symbol
+        let regex = RustExtractor::new().extract_symbols("src/broken.rs", code);
+        if let Some(regex_broken) = regex.iter().find(|s| s.name == "broken") {
+            let broken = find(&symbols, "function", "broken");
+            assert_eq!(broken.id, regex_broken.id);
+            assert!(broken.span.is_none());
+        }
+    }
+
+    #[test]
+    fn test_deeply_nested_expressions_do_not_overflow() {
+        // Minified or generated code: a left-associative chain nests one
+        // binary expression per operand
+        let code = format!(
+            "const total = {}a;\nfunction after() {{ helper(); }}\n",
+            "a + ".repeat(5000)
+        );
+
+        // A small stack makes a recursive walk fail deterministically
+        let parsed = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || parse_file("dist/app.min.js", LanguageKind::JsTs, &code))
+            .unwrap()
+            .join()
+            .expect("walking a deep tree must not overflow the stack")
+            .unwrap();
+
+        assert!(parsed.symbols.iter().any(|s| s.name == "after"));
+        assert!(parsed.calls.iter().any(|c| c.callee == "helper"));
+    }
+}
@@ -68,6 +68,11 @@ impl LanguageExtractor for JsTsExtractor {
             return FrameworkHint::React;
         }
 
+        // Fastify detection
+        if text.contains("fastify(") || text.contains("from 'fastify'") {
+            return FrameworkHint::Fastify;
+        }
+
         // Express detection
         if text.contains("express()") || text.contains("from 'express'") {
             return FrameworkHint::Express;
@@ -661,7 +666,7 @@ fn extract_classname_relations(file_path: &str, text: &str) -> Vec<KgRelationCan
 // ============================================================================
 
 /// Common built-in functions/methods to skip (too noisy).
-const BUILTIN_FUNCTIONS: &[&str] = &[
+pub(crate) const BUILTIN_FUNCTIONS: &[&str] = &[
     // Console
     "log", "error", "warn", "info", "debug", "trace", "dir", "table",
     // Array methods
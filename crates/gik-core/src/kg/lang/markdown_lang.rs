@@ -70,7 +70,7 @@ fn extract_headings(file_path: &str, text: &str) -> Vec<KgSymbolCandidate> {
 }
 
 /// Convert a heading to a URL-friendly slug.
-fn slugify(text: &str) -> String {
+pub(crate) fn slugify(text: &str) -> String {
     text.to_lowercase()
         .chars()
         .map(|c| if c.is_alphanumeric() { c } else { '-' })
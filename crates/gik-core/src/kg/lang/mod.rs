@@ -3,29 +3,45 @@
 //! This module provides per-language extractors for deriving symbol-level
 //! nodes and relations from source code.
 //!
-//! ## Phase 9.2.1 Scope
+//! ## Extraction Paths
 //!
-//! - **Shallow symbol extraction**: top-level functions, classes, namespaces
-//! - **Structural relations only**: no call graphs or deep inter-symbol edges
-//! - **Best-effort regex heuristics**: not full AST parsing
+//! - **Tree-sitter AST** (primary): nested symbols at any depth, such as
+//!   methods inside classes inside modules, with `contains` relations
+//!   following that nesting and `calls` relations taken from call sites
+//! - **Regex fallback**: used for languages without a grammar and for the
+//!   parts of a file the parser could not read; it reports shallow,
+//!   mostly top-level symbols
+//!
+//! Unresolved `calls` relations from either path are resolved across files
+//! by the KG extractor.
+//!
+//! ## AST Extraction
+//!
+//! Languages with a bundled tree-sitter grammar are wrapped in
+//! [`ast::AstExtractor`], which reports nested symbols with exact line spans,
+//! `contains` relations from parents to children, and doc comments. The
+//! regex extractors still provide framework-specific symbols and relations,
+//! and cover the regions of a file with syntax errors that did not parse.
+//! Every language in the table below is parsed; CSS and HTML use the regex
+//! extractors only.
 //!
 //! ## Supported Languages
 //!
-//! | Language | Extension(s) | Symbols | Relations |
-//! |----------|--------------|---------|-----------|
-//! | JavaScript/TypeScript | `.js`, `.jsx`, `.ts`, `.tsx` | functions, classes, namespaces | imports, defines |
-//! | Python | `.py` | functions, classes, modules | imports, defines |
-//! | Ruby | `.rb` | classes, modules, methods | requires, defines |
-//! | C# | `.cs` | namespaces, classes, methods | usings, defines |
-//! | Java | `.java` | packages, classes, interfaces, methods | imports, defines |
-//! | Markdown | `.md` | sections, links | mentions |
-//! | Rust | `.rs` | modules, functions, structs, enums, traits | uses, defines |
-//! | C | `.c`, `.h` | functions | includes |
-//! | C++ | `.cpp`, `.hpp`, `.cc`, `.cxx` | namespaces, classes, functions | includes, defines |
-//! | SQL | `.sql` | tables, views, functions | declares |
-//! | PHP | `.php` | namespaces, classes, functions | uses, requires |
-//! | Go | `.go` | packages, types, functions | imports, defines |
-//! | Kotlin | `.kt`, `.kts` | packages, classes, functions | imports, defines |
+//! | Language | Extension(s) | Symbols | Relations | Parser |
+//! |----------|--------------|---------|-----------|--------|
+//! | JavaScript/TypeScript | `.js`, `.jsx`, `.ts`, `.tsx` | functions, classes, methods, interfaces | imports, defines | AST |
+//! | Python | `.py` | functions, classes, methods, constants | imports, defines | AST |
+//! | Ruby | `.rb` | classes, modules, methods | requires, defines | AST |
+//! | C# | `.cs` | namespaces, classes, methods | usings, defines | AST |
+//! | Java | `.java` | classes, interfaces, methods | imports, defines | AST |
+//! | Markdown | `.md` | sections, links | mentions | AST |
+//! | Rust | `.rs` | modules, functions, structs, enums, traits | uses, defines | AST |
+//! | C | `.c`, `.h` | functions, structs, typedefs, macros | includes | AST |
+//! | C++ | `.cpp`, `.hpp`, `.cc`, `.cxx` | namespaces, classes, functions | includes, defines | AST |
+//! | SQL | `.sql` | tables, views, functions | declares | AST |
+//! | PHP | `.php` | namespaces, classes, functions | uses, requires | AST |
+//! | Go | `.go` | packages, types, functions | imports, defines | AST |
+//! | Kotlin | `.kt`, `.kts` | packages, classes, functions | imports, defines | AST |
 //!
 //! ## Symbol ID Convention
 //!
@@ -42,7 +58,6 @@
 //!
 //! ## Future (TODO)
 //!
-//! - Phase 9.4+: Full call graph extraction
 //! - Phase 9.4+: Framework-specific endpoint detection beyond Next.js
 
 use serde::{Deserialize, Serialize};
@@ -50,6 +65,7 @@ use std::collections::HashMap;
 use std::path::Path;
 
 // Language-specific modules
+pub mod ast;
 pub mod c_lang;
 pub mod cpp_lang;
 pub mod csharp_lang;
@@ -168,10 +184,14 @@ pub enum FrameworkHint {
     NextJs,
     /// Express.js (Node.js web framework)
     Express,
+    /// Fastify (Node.js web framework)
+    Fastify,
     /// Django (Python web framework)
     Django,
     /// Flask (Python micro-framework)
     Flask,
+    /// FastAPI (Python web framework)
+    FastApi,
     /// Ruby on Rails
     Rails,
     /// Spring (Java framework)
@@ -184,6 +204,10 @@ pub enum FrameworkHint {
     Gin,
     /// Fiber (Go web framework)
     Fiber,
+    /// Axum (Rust web framework)
+    Axum,
+    /// Actix Web (Rust web framework)
+    Actix,
     /// React (generic React, not Next.js)
     React,
     /// shadcn/ui component library
@@ -466,95 +490,43 @@ pub fn extract_for_file(
 
     match lang {
         LanguageKind::JsTs => {
-            let extractor = js_ts_lang::JsTsExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(js_ts_lang::JsTsExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Python => {
-            let extractor = python_lang::PythonExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(python_lang::PythonExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Ruby => {
-            let extractor = ruby_lang::RubyExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(ruby_lang::RubyExtractor::new()).extract(file_path, text)
         }
         LanguageKind::CSharp => {
-            let extractor = csharp_lang::CSharpExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(csharp_lang::CSharpExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Java => {
-            let extractor = java_lang::JavaExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(java_lang::JavaExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Markdown => {
-            let extractor = markdown_lang::MarkdownExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(markdown_lang::MarkdownExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Rust => {
-            let extractor = rust_lang::RustExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(rust_lang::RustExtractor::new()).extract(file_path, text)
         }
         LanguageKind::C => {
-            let extractor = c_lang::CExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(c_lang::CExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Cpp => {
-            let extractor = cpp_lang::CppExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(cpp_lang::CppExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Sql => {
-            let extractor = sql_lang::SqlExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(sql_lang::SqlExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Php => {
-            let extractor = php_lang::PhpExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(php_lang::PhpExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Go => {
-            let extractor = go_lang::GoExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(go_lang::GoExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Kotlin => {
-            let extractor = kotlin_lang::KotlinExtractor::new();
-            (
-                extractor.extract_symbols(file_path, text),
-                extractor.extract_relations(file_path, text),
-            )
+            ast::AstExtractor::new(kotlin_lang::KotlinExtractor::new()).extract(file_path, text)
         }
         LanguageKind::Css => {
             let extractor = css_lang::CssTailwindExtractor::new();
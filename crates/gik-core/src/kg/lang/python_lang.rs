@@ -40,6 +40,11 @@ impl LanguageExtractor for PythonExtractor {
             return FrameworkHint::Django;
         }
 
+        // FastAPI detection
+        if text.contains("from fastapi import") || text.contains("import fastapi") {
+            return FrameworkHint::FastApi;
+        }
+
         // Flask detection
         if text.contains("from flask import") || text.contains("Flask(__name__)") {
             return FrameworkHint::Flask;
@@ -169,7 +174,7 @@ fn extract_constants(
 // ============================================================================
 
 /// Common built-in functions/methods to skip (too noisy).
-const BUILTIN_FUNCTIONS: &[&str] = &[
+pub(crate) const BUILTIN_FUNCTIONS: &[&str] = &[
     // Built-in functions
     "print", "len", "range", "str", "int", "float", "bool", "list", "dict", "set", "tuple",
     "type", "isinstance", "issubclass", "hasattr", "getattr", "setattr", "delattr",
@@ -394,6 +399,16 @@ some_variable = "value"
         );
     }
 
+    #[test]
+    fn test_detect_fastapi() {
+        let extractor = PythonExtractor::new();
+
+        assert_eq!(
+            extractor.detect_framework("api/main.py", "from fastapi import FastAPI, APIRouter"),
+            FrameworkHint::FastApi
+        );
+    }
+
     #[test]
     fn test_detect_flask() {
         let extractor = PythonExtractor::new();
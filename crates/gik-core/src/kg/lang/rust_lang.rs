@@ -35,12 +35,12 @@ impl LanguageExtractor for RustExtractor {
     fn detect_framework(&self, _file_path: &str, text: &str) -> FrameworkHint {
         // Axum detection
         if text.contains("axum::") || text.contains("use axum") {
-            return FrameworkHint::Generic;
+            return FrameworkHint::Axum;
         }
 
         // Actix-web detection
         if text.contains("actix_web::") || text.contains("use actix_web") {
-            return FrameworkHint::Generic;
+            return FrameworkHint::Actix;
         }
 
         FrameworkHint::None
@@ -243,7 +243,7 @@ fn extract_constants(
 // ============================================================================
 
 /// Common built-in functions/macros to skip (too noisy).
-const BUILTIN_FUNCTIONS: &[&str] = &[
+pub(crate) const BUILTIN_FUNCTIONS: &[&str] = &[
     // Macros
     "println", "print", "eprintln", "eprint", "format", "write", "writeln",
     "panic", "assert", "assert_eq", "assert_ne", "debug_assert", "debug_assert_eq",
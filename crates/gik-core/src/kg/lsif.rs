@@ -0,0 +1,756 @@
+//! LSIF dump reader for `gik kg import`.
+//!
+//! LSIF, the JSON predecessor of SCIP, is still emitted by lsif-node,
+//! lsif-go, lsif-clang and older language servers. A dump is a graph of
+//! vertices and edges, one JSON object per line (or a single JSON array).
+//! [`parse_lsif`] walks it into the same [`PreciseIndex`] that SCIP import
+//! produces, so merging does not care which format an index came from:
+//!
+//! - Ranges that reach the same `resultSet` through `next` edges are one
+//!   symbol, identified by its moniker (`<scheme> <identifier>`) or, without
+//!   one, by the result set's vertex ID.
+//! - Ranges listed by the symbol's `definitionResult` (or tagged as
+//!   definitions) are its definitions; every other range is a reference.
+//! - Names and kinds come from the range `tag` when the indexer emits one.
+//!   Otherwise the name is read from the source file and the kind from the
+//!   hover signature. Definitions of other kinds are skipped, as with SCIP.
+//! - A definition's parent is the innermost definition whose full range
+//!   contains it. `textDocument/implementation` results become `implements`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::scip::{
+    finish_index, hover_docs, project_prefix, PreciseDocument, PreciseIndex, PreciseMode,
+    PreciseReference, PreciseSymbol,
+};
+use crate::errors::GikError;
+
+/// `next` chains longer than this are treated as cycles.
+const MAX_CHAIN: usize = 32;
+
+/// Kinds whose `function` children are methods.
+const TYPE_KINDS: &[&str] = &["class", "struct", "interface", "trait", "enum"];
+
+// ============================================================================
+// Graph
+// ============================================================================
+
+/// The vertices and edges of a dump GIK reads, keyed by vertex ID.
+#[derive(Debug, Default)]
+struct LsifGraph {
+    project_root: String,
+    tool: Option<String>,
+    /// `document` vertex → URI.
+    documents: BTreeMap<String, String>,
+    /// `range` vertices.
+    ranges: HashMap<String, Value>,
+    /// `hoverResult` and `moniker` vertices.
+    results: HashMap<String, Value>,
+    /// Range → document, from `contains` edges.
+    range_document: HashMap<String, String>,
+    /// `next` edges: range or result set → result set.
+    next: HashMap<String, String>,
+    /// `textDocument/definition` edges.
+    definition: HashMap<String, String>,
+    /// `textDocument/hover` edges.
+    hover: HashMap<String, String>,
+    /// `textDocument/implementation` edges.
+    implementation: HashMap<String, String>,
+    /// `moniker` edges.
+    moniker: HashMap<String, String>,
+    /// `item` edges: result vertex → ranges.
+    items: HashMap<String, Vec<String>>,
+}
+
+impl LsifGraph {
+    fn add(&mut self, element: &Value) {
+        let label = element["label"].as_str().unwrap_or_default();
+        match element["type"].as_str() {
+            Some("vertex") => {
+                let id = vertex_id(&element["id"]);
+                match label {
+                    "metaData" => {
+                        self.project_root = element["projectRoot"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string();
+                        let tool = &element["toolInfo"];
+                        self.tool = tool["name"].as_str().map(|name| {
+                            format!("{} {}", name, tool["version"].as_str().unwrap_or_default())
+                                .trim()
+                                .to_string()
+                        });
+                    }
+                    "document" => {
+                        let uri = element["uri"].as_str().unwrap_or_default();
+                        self.documents.insert(id, uri.to_string());
+                    }
+                    "range" => {
+                        self.ranges.insert(id, element.clone());
+                    }
+                    "hoverResult" | "moniker" => {
+                        self.results.insert(id, element.clone());
+                    }
+                    _ => {}
+                }
+            }
+            Some("edge") => {
+                let out = vertex_id(&element["outV"]);
+                let ins: Vec<String> = match element["inVs"].as_array() {
+                    Some(ins) => ins.iter().map(vertex_id).collect(),
+                    None => vec![vertex_id(&element["inV"])],
+                };
+                let single = |map: &mut HashMap<String, String>| {
+                    if let Some(inv) = ins.first() {
+                        map.insert(out.clone(), inv.clone());
+                    }
+                };
+                match label {
+                    "contains" => {
+                        for range in &ins {
+                            self.range_document.insert(range.clone(), out.clone());
+                        }
+                    }
+                    "next" => single(&mut self.next),
+                    "textDocument/definition" => single(&mut self.definition),
+                    "textDocument/hover" => single(&mut self.hover),
+                    "textDocument/implementation" => single(&mut self.implementation),
+                    "moniker" => single(&mut self.moniker),
+                    "item" => self.items.entry(out).or_default().extend(ins),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Vertices from `id` along its `next` chain, starting with `id`.
+    fn chain<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        std::iter::successors(Some(id), |cur| self.next.get(*cur).map(String::as_str))
+            .take(MAX_CHAIN)
+    }
+
+    /// The result set a range or result set resolves to.
+    fn result_set<'a>(&'a self, id: &'a str) -> &'a str {
+        self.chain(id).last().unwrap_or(id)
+    }
+
+    /// First target of an edge kind along the `next` chain of `id`.
+    fn lookup<'a>(&'a self, edges: &'a HashMap<String, String>, id: &'a str) -> Option<&'a str> {
+        self.chain(id)
+            .find_map(|v| edges.get(v))
+            .map(String::as_str)
+    }
+
+    /// Stable symbol string for a result set.
+    fn symbol(&self, set: &str) -> String {
+        self.lookup(&self.moniker, set)
+            .and_then(|m| self.results.get(m))
+            .and_then(|m| {
+                let scheme = m["scheme"].as_str()?;
+                let identifier = m["identifier"].as_str()?;
+                Some(format!("{} {}", scheme, identifier))
+            })
+            .unwrap_or_else(|| format!("lsif:{}", set))
+    }
+
+    /// Hover text of a range, split into prose and signature.
+    fn hover(&self, range: &str) -> (Option<String>, Option<String>) {
+        let contents = self
+            .lookup(&self.hover, range)
+            .and_then(|h| self.results.get(h))
+            .map(|h| hover_contents(&h["result"]["contents"]))
+            .unwrap_or_default();
+        hover_docs(&contents, None)
+    }
+}
+
+/// Vertex IDs are numbers or strings; both become strings.
+fn vertex_id(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+/// Decode an LSIF dump. See [`super::scip::load_precise_file`].
+pub fn parse_lsif(
+    bytes: &[u8],
+    source: &Path,
+    workspace_root: &Path,
+    mode: PreciseMode,
+) -> Result<PreciseIndex, GikError> {
+    let invalid = |reason: String| GikError::KgImportInvalid {
+        path: source.to_path_buf(),
+        reason,
+    };
+    let text = std::str::from_utf8(bytes).map_err(|e| invalid(e.to_string()))?;
+
+    let mut graph = LsifGraph::default();
+    if text.trim_start().starts_with('[') {
+        let elements: Vec<Value> =
+            serde_json::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        elements.iter().for_each(|e| graph.add(e));
+    } else {
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let element: Value = serde_json::from_str(line)
+                .map_err(|e| invalid(format!("line {}: {}", i + 1, e)))?;
+            graph.add(&element);
+        }
+    }
+    if graph.documents.is_empty() {
+        return Err(invalid("index has no documents".to_string()));
+    }
+
+    // Definition ranges and the result set each one defines
+    let mut definitions: HashMap<&str, &str> = HashMap::new();
+    for (owner, result) in &graph.definition {
+        let set = graph.result_set(owner);
+        for range in graph.items.get(result).into_iter().flatten() {
+            definitions.insert(range.as_str(), set);
+        }
+    }
+    for (id, range) in &graph.ranges {
+        if range["tag"]["type"] == "definition" {
+            definitions
+                .entry(id.as_str())
+                .or_insert_with(|| graph.result_set(id));
+        }
+    }
+
+    // Implementing result set → implemented symbols
+    let mut implements: HashMap<&str, Vec<String>> = HashMap::new();
+    for (owner, result) in &graph.implementation {
+        let symbol = graph.symbol(graph.result_set(owner));
+        for range in graph.items.get(result).into_iter().flatten() {
+            implements
+                .entry(graph.result_set(range))
+                .or_default()
+                .push(symbol.clone());
+        }
+    }
+
+    let mut by_document: BTreeMap<&str, Vec<(&str, [u32; 4])>> = BTreeMap::new();
+    for (range, document) in &graph.range_document {
+        if let Some(position) = graph.ranges.get(range).and_then(to_range) {
+            by_document
+                .entry(document)
+                .or_default()
+                .push((range, position));
+        }
+    }
+
+    let prefix = project_prefix(&graph.project_root, workspace_root);
+    let root = uri_path(&graph.project_root);
+    let mut documents = Vec::new();
+    for (document, uri) in &graph.documents {
+        let Some(relative) = relative_path(&uri_path(uri), &root) else {
+            continue;
+        };
+        let path = format!("{}{}", prefix, relative);
+        let mut ranges = by_document.remove(document.as_str()).unwrap_or_default();
+        ranges.sort_by_key(|(_, position)| *position);
+
+        let source_text = std::cell::OnceCell::new();
+        let mut symbols = Vec::new();
+        let mut defined = HashSet::new();
+        let mut references: BTreeMap<(String, u32), u32> = BTreeMap::new();
+
+        for (range, position) in ranges {
+            let Some(&set) = definitions.get(range) else {
+                let set = graph.result_set(range);
+                if set != range {
+                    references
+                        .entry((graph.symbol(set), position[0]))
+                        .or_insert(position[1]);
+                }
+                continue;
+            };
+            if !defined.insert(set) {
+                continue;
+            }
+
+            let tag = &graph.ranges[range]["tag"];
+            let name = match tag["text"].as_str() {
+                Some(text) => text.to_string(),
+                None => {
+                    let text = source_text.get_or_init(|| {
+                        std::fs::read_to_string(workspace_root.join(&path)).unwrap_or_default()
+                    });
+                    slice_range(text, position).unwrap_or_default()
+                }
+            };
+            let (documentation, signature) = graph.hover(range);
+            let kind = tag["kind"]
+                .as_i64()
+                .and_then(lsp_kind)
+                .or_else(|| signature.as_deref().and_then(kind_from_signature));
+            let Some(kind) = kind.filter(|_| !name.is_empty()) else {
+                continue;
+            };
+
+            symbols.push(PreciseSymbol {
+                symbol: graph.symbol(set),
+                name,
+                kind: kind.to_string(),
+                parent: None,
+                range: position,
+                enclosing: to_range(&tag["fullRange"]),
+                documentation,
+                signature,
+                implements: implements.get(set).cloned().unwrap_or_default(),
+            });
+        }
+        assign_parents(&mut symbols);
+
+        documents.push(PreciseDocument {
+            path,
+            symbols,
+            references: references
+                .into_iter()
+                .map(|((symbol, line), column)| PreciseReference {
+                    symbol,
+                    line,
+                    column,
+                })
+                .collect(),
+        });
+    }
+
+    Ok(finish_index(documents, source, graph.tool.clone(), mode))
+}
+
+/// Make each symbol a child of the innermost other definition enclosing it.
+/// Functions inside types become methods.
+fn assign_parents(symbols: &mut [PreciseSymbol]) {
+    let contains = |outer: [u32; 4], inner: [u32; 4]| {
+        (outer[0], outer[1]) <= (inner[0], inner[1]) && (inner[2], inner[3]) <= (outer[2], outer[3])
+    };
+    let parents: Vec<Option<usize>> = symbols
+        .iter()
+        .enumerate()
+        .map(|(i, sym)| {
+            symbols
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| {
+                    *j != i && other.enclosing.is_some_and(|e| contains(e, sym.range))
+                })
+                .min_by_key(|(_, other)| {
+                    let e = other.enclosing.unwrap_or(other.range);
+                    (e[2] - e[0], u32::MAX - e[1])
+                })
+                .map(|(j, _)| j)
+        })
+        .collect();
+
+    for (i, parent) in parents.into_iter().enumerate() {
+        let Some(parent) = parent else {
+            continue;
+        };
+        let in_type = TYPE_KINDS.contains(&symbols[parent].kind.as_str());
+        symbols[i].parent = Some(symbols[parent].symbol.clone());
+        if in_type && symbols[i].kind == "function" {
+            symbols[i].kind = "method".to_string();
+        }
+    }
+}
+
+/// LSIF ranges are `{start: {line, character}, end: {line, character}}`.
+fn to_range(range: &Value) -> Option<[u32; 4]> {
+    let at =
+        |pos: &str, key: &str| -> Option<u32> { u32::try_from(range[pos][key].as_u64()?).ok() };
+    Some([
+        at("start", "line")?,
+        at("start", "character")?,
+        at("end", "line")?,
+        at("end", "character")?,
+    ])
+}
+
+/// Text of a single-line range.
+fn slice_range(text: &str, range: [u32; 4]) -> Option<String> {
+    if range[0] != range[2] {
+        return None;
+    }
+    let line = text.lines().nth(range[0] as usize)?;
+    let name: String = line
+        .chars()
+        .skip(range[1] as usize)
+        .take(range[3].saturating_sub(range[1]) as usize)
+        .collect();
+    Some(name.trim().to_string())
+}
+
+/// Map an LSP `SymbolKind` to a KG symbol kind, or None for kinds the KG
+/// skips (variables, fields, parameters, ...).
+fn lsp_kind(kind: i64) -> Option<&'static str> {
+    match kind {
+        2 | 4 => Some("module"),
+        3 => Some("namespace"),
+        5 => Some("class"),
+        6 | 9 => Some("method"),
+        10 => Some("enum"),
+        11 => Some("interface"),
+        12 => Some("function"),
+        14 => Some("constant"),
+        23 => Some("struct"),
+        _ => None,
+    }
+}
+
+/// Guess a symbol kind from the first line of its hover signature
+/// (`pub fn total(&self)`, `(method) Order.total(): number`, `class Order`).
+fn kind_from_signature(signature: &str) -> Option<&'static str> {
+    const MODIFIERS: &[&str] = &[
+        "pub",
+        "export",
+        "default",
+        "declare",
+        "async",
+        "public",
+        "private",
+        "protected",
+        "static",
+        "abstract",
+        "final",
+        "unsafe",
+        "extern",
+        "open",
+        "sealed",
+        "data",
+        "inline",
+    ];
+    for word in signature.lines().next()?.split_whitespace() {
+        let word = word.trim_matches(|c| c == '(' || c == ')');
+        let kind = match word {
+            "fn" | "func" | "function" | "def" => "function",
+            "method" | "constructor" => "method",
+            "class" => "class",
+            "struct" => "struct",
+            "interface" => "interface",
+            "trait" => "trait",
+            "enum" => "enum",
+            "type" => "type",
+            "mod" | "module" => "module",
+            "namespace" => "namespace",
+            _ if MODIFIERS.contains(&word) || word.starts_with("pub(") => continue,
+            _ => return None,
+        };
+        return Some(kind);
+    }
+    None
+}
+
+/// Flatten hover contents (`MarkupContent`, `MarkedString` or an array of
+/// them) into code blocks and prose.
+fn hover_contents(contents: &Value) -> Vec<String> {
+    match contents {
+        Value::Array(items) => items.iter().flat_map(hover_contents).collect(),
+        Value::String(text) => split_markdown(text),
+        Value::Object(obj) => {
+            let value = obj.get("value").and_then(Value::as_str).unwrap_or_default();
+            match obj.get("language").and_then(Value::as_str) {
+                Some(language) => vec![format!("```{}\n{}\n```", language, value.trim())],
+                None => split_markdown(value),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Split Markdown hover text into its leading code block and the prose after
+/// it, dropping the `---` rule language servers put between them.
+fn split_markdown(text: &str) -> Vec<String> {
+    let text = text.trim();
+    let (block, prose) = match text.strip_prefix("```").and_then(|rest| rest.find("\n```")) {
+        Some(close) => text.split_at(3 + close + 4),
+        None => ("", text),
+    };
+    let prose = prose.trim();
+    let prose = prose.strip_prefix("---").unwrap_or(prose).trim();
+    [block, prose]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Filesystem path of a `file://` URI, percent-decoded.
+fn uri_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).replace('\\', "/")
+}
+
+/// Document path relative to the project root; None outside of it.
+fn relative_path(path: &str, root: &str) -> Option<String> {
+    let root = root.trim_end_matches('/');
+    if root.is_empty() {
+        return Some(path.trim_start_matches('/').to_string());
+    }
+    path.strip_prefix(root)?
+        .strip_prefix('/')
+        .filter(|rel| !rel.is_empty())
+        .map(str::to_string)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    const ORDERS: &str = "export interface Priced {}\n\nexport class Order implements Priced {\n  total(): number {\n    return 0;\n  }\n}\n";
+
+    fn range(id: u32, start: (u32, u32), end: (u32, u32), tag: Value) -> Value {
+        let mut vertex = json!({
+            "id": id, "type": "vertex", "label": "range",
+            "start": { "line": start.0, "character": start.1 },
+            "end": { "line": end.0, "character": end.1 },
+        });
+        if !tag.is_null() {
+            vertex["tag"] = tag;
+        }
+        vertex
+    }
+
+    fn edge(id: u32, label: &str, out: u32, ins: &[u32]) -> Value {
+        json!({ "id": id, "type": "edge", "label": label, "outV": out, "inVs": ins })
+    }
+
+    /// `src/orders.ts` defines `Priced`, `Order` (implementing it) and
+    /// `Order.total`, which `src/main.ts` calls. `total` has no tag, so its
+    /// name comes from the source file and its kind from the hover.
+    fn sample_dump(root: &Path) -> String {
+        let root = format!("file://{}", root.display());
+        let full = |sl, sc, el, ec| json!({ "start": { "line": sl, "character": sc }, "end": { "line": el, "character": ec } });
+        let elements = vec![
+            json!({ "id": 1, "type": "vertex", "label": "metaData", "version": "0.4.3",
+                    "projectRoot": root, "toolInfo": { "name": "lsif-tsc", "version": "0.7" } }),
+            json!({ "id": 2, "type": "vertex", "label": "document",
+                    "uri": format!("{}/src/orders.ts", root), "languageId": "typescript" }),
+            json!({ "id": 3, "type": "vertex", "label": "document",
+                    "uri": format!("{}/src/main.ts", root), "languageId": "typescript" }),
+            // Priced
+            json!({ "id": 10, "type": "vertex", "label": "resultSet" }),
+            range(
+                11,
+                (0, 17),
+                (0, 23),
+                json!({ "type": "definition", "text": "Priced",
+                    "kind": 11, "fullRange": full(0, 0, 0, 26) }),
+            ),
+            edge(12, "next", 11, &[10]),
+            json!({ "id": 13, "type": "vertex", "label": "implementationResult" }),
+            edge(14, "textDocument/implementation", 10, &[13]),
+            // Order
+            json!({ "id": 20, "type": "vertex", "label": "resultSet" }),
+            range(
+                21,
+                (2, 13),
+                (2, 18),
+                json!({ "type": "definition", "text": "Order",
+                    "kind": 5, "fullRange": full(2, 0, 6, 1) }),
+            ),
+            edge(22, "next", 21, &[20]),
+            json!({ "id": 23, "type": "vertex", "label": "moniker", "kind": "export",
+                    "scheme": "tsc", "identifier": "orders:Order" }),
+            edge(24, "moniker", 20, &[23]),
+            json!({ "id": 25, "type": "vertex", "label": "hoverResult", "result": { "contents": [
+                    { "language": "typescript", "value": "class Order" }, "An order." ] } }),
+            edge(26, "textDocument/hover", 20, &[25]),
+            edge(27, "item", 13, &[21]),
+            // Order.total, declared without a tag
+            json!({ "id": 30, "type": "vertex", "label": "resultSet" }),
+            range(31, (3, 2), (3, 7), Value::Null),
+            edge(32, "next", 31, &[30]),
+            json!({ "id": 33, "type": "vertex", "label": "definitionResult" }),
+            edge(34, "textDocument/definition", 30, &[33]),
+            edge(35, "item", 33, &[31]),
+            json!({ "id": 36, "type": "vertex", "label": "hoverResult", "result": { "contents": {
+                    "kind": "markdown",
+                    "value": "```typescript\n(method) Order.total(): number\n```\n\n---\n\nSum of line items." } } }),
+            edge(37, "textDocument/hover", 30, &[36]),
+            // A local variable, skipped
+            json!({ "id": 40, "type": "vertex", "label": "resultSet" }),
+            range(
+                41,
+                (4, 4),
+                (4, 10),
+                json!({ "type": "definition", "text": "result", "kind": 13 }),
+            ),
+            edge(42, "next", 41, &[40]),
+            edge(43, "contains", 2, &[11, 21, 31, 41]),
+            // main.ts references Order and calls total twice on one line
+            range(50, (1, 8), (1, 13), Value::Null),
+            edge(51, "next", 50, &[20]),
+            range(52, (2, 4), (2, 9), Value::Null),
+            edge(53, "next", 52, &[30]),
+            range(54, (2, 20), (2, 25), Value::Null),
+            edge(55, "next", 54, &[30]),
+            edge(56, "contains", 3, &[50, 52, 54]),
+        ];
+        elements
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn workspace() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("src")).unwrap();
+        std::fs::write(temp.path().join("src/orders.ts"), ORDERS).unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_parse_lsif_definitions_and_references() {
+        let temp = workspace();
+        let dump = sample_dump(temp.path());
+        let index = parse_lsif(
+            dump.as_bytes(),
+            Path::new("dump.lsif"),
+            temp.path(),
+            PreciseMode::Merge,
+        )
+        .unwrap();
+
+        assert_eq!(index.tool.as_deref(), Some("lsif-tsc 0.7"));
+        assert_eq!(index.documents.len(), 2);
+        let main = &index.documents[0];
+        let orders = &index.documents[1];
+        assert_eq!(orders.path, "src/orders.ts");
+
+        let names: Vec<(&str, &str)> = orders
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Priced", "interface"),
+                ("Order", "class"),
+                ("total", "method")
+            ]
+        );
+
+        let order = &orders.symbols[1];
+        assert_eq!(order.symbol, "tsc orders:Order");
+        assert_eq!(order.enclosing, Some([2, 0, 6, 1]));
+        assert_eq!(order.signature.as_deref(), Some("class Order"));
+        assert_eq!(order.documentation.as_deref(), Some("An order."));
+        assert_eq!(order.implements, vec!["lsif:10".to_string()]);
+
+        let total = &orders.symbols[2];
+        assert_eq!(total.parent.as_deref(), Some("tsc orders:Order"));
+        assert_eq!(
+            total.signature.as_deref(),
+            Some("(method) Order.total(): number")
+        );
+        assert_eq!(total.documentation.as_deref(), Some("Sum of line items."));
+
+        // One reference per symbol and line
+        assert_eq!(main.path, "src/main.ts");
+        let refs: Vec<(&str, u32, u32)> = main
+            .references
+            .iter()
+            .map(|r| (r.symbol.as_str(), r.line, r.column))
+            .collect();
+        assert_eq!(refs, vec![("lsif:30", 2, 4), ("tsc orders:Order", 1, 8)]);
+        assert_eq!(index.implementation_count(), 1);
+    }
+
+    #[test]
+    fn test_load_precise_file_detects_lsif() {
+        let temp = workspace();
+        let elements: Vec<Value> = sample_dump(temp.path())
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        let path = temp.path().join("dump.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&elements).unwrap()).unwrap();
+
+        let index = super::super::scip::load_precise_file(&path, temp.path(), PreciseMode::Replace)
+            .unwrap();
+        assert_eq!(index.symbol_count(), 3);
+        assert_eq!(index.mode, PreciseMode::Replace);
+    }
+
+    #[test]
+    fn test_parse_lsif_rejects_bad_lines() {
+        let err = parse_lsif(
+            b"{\"id\": 1, \"type\": \"vertex\", \"label\": \"metaData\"}\nnot json\n",
+            Path::new("dump.lsif"),
+            Path::new("."),
+            PreciseMode::Merge,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+
+        let empty = parse_lsif(
+            b"{\"id\": 1, \"type\": \"vertex\", \"label\": \"metaData\"}\n",
+            Path::new("dump.lsif"),
+            Path::new("."),
+            PreciseMode::Merge,
+        );
+        assert!(empty.is_err());
+    }
+
+    #[test]
+    fn test_hover_and_signature_helpers() {
+        assert_eq!(
+            kind_from_signature("pub(crate) fn total(&self)"),
+            Some("function")
+        );
+        assert_eq!(
+            kind_from_signature("export declare class Order"),
+            Some("class")
+        );
+        assert_eq!(kind_from_signature("let x: number"), None);
+        assert_eq!(
+            split_markdown("```rust\nfn a()\n```\n---\nDocs."),
+            vec!["```rust\nfn a()\n```".to_string(), "Docs.".to_string()]
+        );
+        assert_eq!(uri_path("file:///tmp/my%20repo/a.ts"), "/tmp/my repo/a.ts");
+        assert_eq!(
+            relative_path("/tmp/repo/src/a.ts", "/tmp/repo/"),
+            Some("src/a.ts".to_string())
+        );
+        assert_eq!(relative_path("/elsewhere/a.ts", "/tmp/repo"), None);
+    }
+}
@@ -0,0 +1,521 @@
+//! Doc-to-code `mentions` edges for the KG.
+//!
+//! Markdown files in the `docs` base reference code in three ways:
+//!
+//! | Via | Source | Resolves to |
+//! |-----|--------|-------------|
+//! | `link` | `[text](../src/lib.rs)`, `[ref]: src/lib.rs` | File at the path relative to the doc |
+//! | `code` | `` `src/lib.rs` ``, `` `UserService::create()` `` | File by path or unique suffix, or symbol by (qualified) name |
+//! | `snippet` | Calls in fenced code blocks (`create_user(...)`) | Function or method by name |
+//!
+//! Each mention records the enclosing heading, so ask context can point at the
+//! README section that covers a piece of code. Mentions are resolved after all
+//! bases are extracted, since docs and code live in different bases.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::json;
+
+use super::entities::{KgEdge, KgNode};
+use super::lang::markdown_lang::slugify;
+
+// ============================================================================
+// Mention Extraction
+// ============================================================================
+
+/// How a doc refers to code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MentionVia {
+    /// Markdown link or reference definition.
+    Link,
+    /// Inline code span.
+    CodeSpan,
+    /// Call inside a fenced code block.
+    Snippet,
+}
+
+impl MentionVia {
+    /// Value stored in the edge's `via` prop.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MentionVia::Link => "link",
+            MentionVia::CodeSpan => "code",
+            MentionVia::Snippet => "snippet",
+        }
+    }
+}
+
+/// A reference to code found in a doc, before resolution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocMention {
+    /// Referenced path or name as written.
+    pub raw: String,
+    /// How the doc refers to it.
+    pub via: MentionVia,
+    /// Title of the enclosing heading, if any.
+    pub section: Option<String>,
+    /// 1-based line of the mention.
+    pub line: u32,
+}
+
+/// Fence languages that hold commands or data rather than code.
+const NON_CODE_FENCES: &[&str] = &[
+    "sh", "bash", "shell", "console", "zsh", "text", "txt", "json", "yaml", "yml", "toml", "ini",
+    "diff", "mermaid",
+];
+
+/// Call-like words in snippets that are never project symbols.
+const SNIPPET_KEYWORDS: &[&str] = &[
+    "if",
+    "for",
+    "while",
+    "match",
+    "switch",
+    "return",
+    "fn",
+    "def",
+    "function",
+    "new",
+    "catch",
+    "print",
+    "println",
+    "format",
+    "require",
+    "import",
+    "assert",
+    "assert_eq",
+    "typeof",
+    "sizeof",
+    "super",
+    "self",
+    "this",
+    "await",
+    "async",
+    "Some",
+    "Ok",
+    "Err",
+    "vec",
+];
+
+/// Extract code references from a Markdown document.
+///
+/// Returns nothing for files that aren't Markdown.
+pub fn extract_mentions(file_path: &str, text: &str) -> Vec<DocMention> {
+    let lower = file_path.to_lowercase();
+    if !(lower.ends_with(".md") || lower.ends_with(".mdx") || lower.ends_with(".markdown")) {
+        return Vec::new();
+    }
+
+    let heading_re = regex::Regex::new(r"^#{1,6}\s+(.+?)\s*#*\s*$").expect("Invalid regex");
+    let fence_re = regex::Regex::new(r"^\s*(```+|~~~+)\s*([\w+-]*)").expect("Invalid regex");
+    let link_re =
+        regex::Regex::new(r#"\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).expect("Invalid regex");
+    let reference_re =
+        regex::Regex::new(r"^\s*\[[^\]]+\]:\s*<?(\S+?)>?(?:\s|$)").expect("Invalid regex");
+    let span_re = regex::Regex::new(r"`([^`\s][^`]*?)`").expect("Invalid regex");
+    let call_re = regex::Regex::new(r"\b([A-Za-z_][A-Za-z0-9_]*)\s*\(").expect("Invalid regex");
+
+    let mut mentions = Vec::new();
+    let mut section: Option<String> = None;
+    let mut fence: Option<(String, bool)> = None; // (marker, scan for calls)
+
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i as u32 + 1;
+
+        if let Some((marker, is_code)) = &fence {
+            let trimmed = line.trim();
+            let fence_char = marker.chars().next().unwrap_or('`');
+            if trimmed.len() >= marker.len() && trimmed.chars().all(|c| c == fence_char) {
+                fence = None;
+                continue;
+            }
+            if *is_code {
+                for cap in call_re.captures_iter(line) {
+                    let name = &cap[1];
+                    if !SNIPPET_KEYWORDS.contains(&name) {
+                        mentions.push(DocMention {
+                            raw: name.to_string(),
+                            via: MentionVia::Snippet,
+                            section: section.clone(),
+                            line: line_no,
+                        });
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(cap) = fence_re.captures(line) {
+            let language = cap[2].to_lowercase();
+            fence = Some((
+                cap[1].to_string(),
+                !NON_CODE_FENCES.contains(&language.as_str()),
+            ));
+            continue;
+        }
+        if let Some(cap) = heading_re.captures(line) {
+            section = Some(cap[1].trim().to_string());
+            continue;
+        }
+
+        let mut push = |raw: &str, via: MentionVia| {
+            mentions.push(DocMention {
+                raw: raw.to_string(),
+                via,
+                section: section.clone(),
+                line: line_no,
+            });
+        };
+        for cap in link_re.captures_iter(line) {
+            push(&cap[1], MentionVia::Link);
+        }
+        if let Some(cap) = reference_re.captures(line) {
+            push(&cap[1], MentionVia::Link);
+        }
+        for cap in span_re.captures_iter(line) {
+            let span = cap[1].trim();
+            // Commands and prose (`cargo build --release`) aren't references
+            if !span.contains(char::is_whitespace) {
+                push(span, MentionVia::CodeSpan);
+            }
+        }
+    }
+
+    mentions
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// Symbol kinds that docs can mention by name.
+const MENTIONABLE_KINDS: &[&str] = &[
+    "function",
+    "method",
+    "class",
+    "struct",
+    "trait",
+    "interface",
+    "enum",
+    "type",
+    "record",
+    "module",
+    "namespace",
+    "constant",
+    "macro",
+];
+
+/// Maximum symbols a bare name may resolve to before it's considered ambiguous.
+const MAX_NAME_MATCHES: usize = 3;
+
+/// Lookup tables for resolving mentions against extracted nodes.
+pub struct MentionIndex<'a> {
+    files: HashMap<&'a str, &'a str>,
+    symbols_by_name: HashMap<&'a str, Vec<&'a KgNode>>,
+    symbols_by_qualified: HashMap<String, Vec<&'a KgNode>>,
+}
+
+impl<'a> MentionIndex<'a> {
+    /// Index code file nodes by path and symbol nodes by name.
+    pub fn new(nodes: &'a [KgNode]) -> Self {
+        let mut index = Self {
+            files: HashMap::new(),
+            symbols_by_name: HashMap::new(),
+            symbols_by_qualified: HashMap::new(),
+        };
+        for node in nodes {
+            if node.kind == "file" {
+                if let Some(path) = node.props["path"].as_str() {
+                    index.files.insert(path, &node.id);
+                }
+            } else if MENTIONABLE_KINDS.contains(&node.kind.as_str()) && node.id.starts_with("sym:")
+            {
+                index
+                    .symbols_by_name
+                    .entry(node.label.as_str())
+                    .or_default()
+                    .push(node);
+                if let Some(qualified) = node.props["qualifiedName"].as_str() {
+                    index
+                        .symbols_by_qualified
+                        .entry(normalize_qualified(qualified))
+                        .or_default()
+                        .push(node);
+                }
+            }
+        }
+        index
+    }
+
+    /// Node ID for a path, exact or as a unique suffix (`users.rs` → `src/api/users.rs`).
+    fn file(&self, path: &str) -> Option<&'a str> {
+        let path = path.trim_start_matches("./");
+        if let Some(id) = self.files.get(path) {
+            return Some(id);
+        }
+        let suffix = format!("/{}", path);
+        let mut matches = self.files.iter().filter(|(p, _)| p.ends_with(&suffix));
+        match (matches.next(), matches.next()) {
+            (Some((_, id)), None) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Symbols named `name`, unless ambiguous.
+    fn symbols(&self, name: &str, callables_only: bool) -> Vec<&'a KgNode> {
+        let matches: Vec<&KgNode> = self
+            .symbols_by_name
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter(|n| !callables_only || n.kind == "function" || n.kind == "method")
+            .copied()
+            .collect();
+        if matches.len() > MAX_NAME_MATCHES {
+            Vec::new()
+        } else {
+            matches
+        }
+    }
+
+    /// Resolve a doc's mentions to `mentions` edges from `doc_id`.
+    pub fn resolve(&self, doc_id: &str, doc_path: &str, mentions: &[DocMention]) -> Vec<KgEdge> {
+        let doc_dir = doc_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        let mut edges = Vec::new();
+        let mut seen: HashSet<&str> = HashSet::new();
+
+        for mention in mentions {
+            let targets: Vec<&str> = match mention.via {
+                MentionVia::Link => resolve_link(&mention.raw, doc_dir)
+                    .and_then(|path| self.files.get(path.as_str()).copied())
+                    .into_iter()
+                    .collect(),
+                MentionVia::CodeSpan => self.resolve_span(&mention.raw),
+                MentionVia::Snippet if mention.raw.len() >= 4 => self
+                    .symbols(&mention.raw, true)
+                    .into_iter()
+                    .map(|n| n.id.as_str())
+                    .collect(),
+                MentionVia::Snippet => Vec::new(),
+            };
+
+            for target in targets {
+                if !seen.insert(target) {
+                    continue;
+                }
+                edges.push(KgEdge::new(doc_id, target, "mentions").with_props(json!({
+                    "via": mention.via.as_str(),
+                    "raw": mention.raw,
+                    "line": mention.line,
+                    "section": mention.section,
+                    "anchor": mention.section.as_deref().map(slugify),
+                })));
+            }
+        }
+
+        edges
+    }
+
+    /// A code span is a path if it has a separator or extension, else a symbol name.
+    fn resolve_span(&self, span: &str) -> Vec<&'a str> {
+        let span = span.trim_end_matches("()").trim_end_matches('!');
+        if span.contains('/') || looks_like_file_name(span) {
+            let path = span.split(['#', ':']).next().unwrap_or(span);
+            return self.file(path).into_iter().collect();
+        }
+
+        let qualified = normalize_qualified(span);
+        if qualified.contains('.') {
+            if let Some(nodes) = self.symbols_by_qualified.get(&qualified) {
+                return nodes.iter().map(|n| n.id.as_str()).collect();
+            }
+            let last = qualified.rsplit('.').next().unwrap_or(&qualified);
+            return self
+                .symbols(last, false)
+                .into_iter()
+                .map(|n| n.id.as_str())
+                .collect();
+        }
+        if span.len() < 3 || !span.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Vec::new();
+        }
+        self.symbols(span, false)
+            .into_iter()
+            .map(|n| n.id.as_str())
+            .collect()
+    }
+}
+
+/// Path a relative link points at, or `None` for URLs and in-page anchors.
+fn resolve_link(link: &str, doc_dir: &str) -> Option<String> {
+    if link.starts_with('#') || link.contains("://") || link.starts_with("mailto:") {
+        return None;
+    }
+    let path = link.split(['#', '?']).next().unwrap_or(link);
+    if path.is_empty() {
+        return None;
+    }
+    let base = if path.starts_with('/') { "" } else { doc_dir };
+    let mut parts: Vec<&str> = base.split('/').filter(|p| !p.is_empty()).collect();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// `Foo::bar`, `Foo.bar` and `Foo#bar` all become `foo.bar`.
+fn normalize_qualified(name: &str) -> String {
+    name.replace("::", ".")
+        .replace(['#', '\\'], ".")
+        .to_lowercase()
+}
+
+/// `main.rs`, `package.json`: a name with a short alphabetic extension.
+fn looks_like_file_name(span: &str) -> bool {
+    match span.rsplit_once('.') {
+        Some((stem, ext)) => {
+            !stem.is_empty()
+                && (1..=5).contains(&ext.len())
+                && ext.chars().all(|c| c.is_ascii_lowercase())
+                && !stem.contains("::")
+                && !stem.chars().next().is_some_and(|c| c.is_uppercase())
+        }
+        None => false,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_mentions() {
+        let md = r#"# Users
+
+See [the handler](../src/api/users.rs) and [docs](https://example.com).
+Call `UserService::create()` or edit `config.toml`. Run `cargo build --release`.
+
+## Example
+
+```rust
+let user = create_user(&db, "ada")?;
+if valid(user) { println!("ok"); }
+```
+
+```bash
+gik ask "how()"
+```
+
+[users]: ./users.md#top
+"#;
+        let mentions = extract_mentions("docs/users.md", md);
+        let found: Vec<(&str, MentionVia, Option<&str>, u32)> = mentions
+            .iter()
+            .map(|m| (m.raw.as_str(), m.via, m.section.as_deref(), m.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("../src/api/users.rs", MentionVia::Link, Some("Users"), 3),
+                ("https://example.com", MentionVia::Link, Some("Users"), 3),
+                (
+                    "UserService::create()",
+                    MentionVia::CodeSpan,
+                    Some("Users"),
+                    4
+                ),
+                ("config.toml", MentionVia::CodeSpan, Some("Users"), 4),
+                ("create_user", MentionVia::Snippet, Some("Example"), 9),
+                ("valid", MentionVia::Snippet, Some("Example"), 10),
+                ("./users.md#top", MentionVia::Link, Some("Example"), 17),
+            ]
+        );
+
+        assert!(extract_mentions("docs/notes.txt", md).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_mentions() {
+        let file = |path: &str| {
+            KgNode::new(format!("file:{}", path), "file", path).with_props(json!({ "path": path }))
+        };
+        let symbol = |id: &str, kind: &str, name: &str, qualified: Option<&str>| {
+            KgNode::new(id, kind, name).with_props(json!({ "qualifiedName": qualified }))
+        };
+        let nodes = vec![
+            file("src/api/users.rs"),
+            file("config.toml"),
+            file("src/main.rs"),
+            file("tools/main.rs"),
+            symbol(
+                "sym:rs:src/api/users.rs:method:create",
+                "method",
+                "create",
+                Some("UserService::create"),
+            ),
+            symbol(
+                "sym:rs:src/db.rs:method:create",
+                "method",
+                "create",
+                Some("Db::create"),
+            ),
+            symbol(
+                "sym:rs:src/api/users.rs:function:create_user",
+                "function",
+                "create_user",
+                None,
+            ),
+            symbol(
+                "sym:rs:src/api/users.rs:struct:User",
+                "struct",
+                "User",
+                None,
+            ),
+        ];
+        let index = MentionIndex::new(&nodes);
+
+        let mention = |raw: &str, via: MentionVia| DocMention {
+            raw: raw.to_string(),
+            via,
+            section: Some("Getting Started".to_string()),
+            line: 1,
+        };
+        let mentions = vec![
+            mention("../src/api/users.rs#L10", MentionVia::Link),
+            mention("https://example.com/src/main.rs", MentionVia::Link),
+            mention("UserService::create()", MentionVia::CodeSpan),
+            mention("config.toml", MentionVia::CodeSpan),
+            mention("main.rs", MentionVia::CodeSpan), // ambiguous suffix
+            mention("User", MentionVia::CodeSpan),
+            mention("create_user", MentionVia::Snippet),
+            mention("User", MentionVia::Snippet), // not callable
+        ];
+
+        let edges = index.resolve("doc:docs/guide.md", "docs/guide.md", &mentions);
+        let targets: Vec<&str> = edges.iter().map(|e| e.to.as_str()).collect();
+        assert_eq!(
+            targets,
+            vec![
+                "file:src/api/users.rs",
+                "sym:rs:src/api/users.rs:method:create",
+                "file:config.toml",
+                "sym:rs:src/api/users.rs:struct:User",
+                "sym:rs:src/api/users.rs:function:create_user",
+            ]
+        );
+        assert!(edges.iter().all(|e| e.kind == "mentions"));
+        assert_eq!(edges[0].props["via"], "link");
+        assert_eq!(edges[0].props["anchor"], "getting-started");
+        assert_eq!(edges[4].props["via"], "snippet");
+    }
+}
@@ -22,31 +22,68 @@
 //! - Phase 9.1-9.2: Initial JSONL-based storage
 //! - Phase 4 (Migration 4): Migrated to gik-db LanceDB backend
 
+pub mod centrality;
+pub mod endpoints;
 pub mod entities;
 pub mod export;
+pub mod export_stream;
 pub mod extractor;
+pub mod graph_query;
+pub mod imports;
+pub mod infra;
 pub mod lang;
+pub mod lsif;
+pub mod mentions;
+pub mod owners;
+pub mod packages;
 pub mod query;
+pub mod rules;
+pub mod schema;
+pub mod scip;
 pub mod store;
+pub mod symbols;
 pub mod sync;
+pub mod test_links;
+mod text;
 
 // Re-export core types
 pub use entities::{KgEdge, KgNode, KgStats, KG_VERSION};
 
 // Re-export export types
 pub use export::{export_kg, export_to_dot, export_to_mermaid, KgExportFormat, KgExportOptions};
+pub use export_stream::{write_graph, KgExportReport, KgGraphWriter};
 
 // Re-export extractor types
 pub use extractor::{
     DefaultKgExtractor, KgExtractionConfig, KgExtractionResult, KgExtractor, DEFAULT_KG_BASES,
 };
 
+// Re-export graph query types
+pub use graph_query::{execute_query, parse_query, GraphQuery, GraphQueryReport, GraphQueryResult};
+
+// Re-export ownership types
+pub use owners::{read_ownership_index, CodeOwners, FileOwnership, OwnershipIndex};
+
 // Re-export query types
 pub use query::{
     build_ask_kg_context, detect_exhaustive_intent, search_kg_exhaustive, AskKgResult,
     ExhaustiveQueryIntent, KgQueryConfig, RagChunkRef,
 };
 
+// Re-export test link types
+pub use test_links::{is_test_path, CoveringTest, TestCoverageReport};
+
+// Re-export rules types
+pub use rules::{check_rules, KgCheckOptions, KgCheckReport, KgRules, KgViolation};
+
+// Re-export precise index types
+pub use scip::{PreciseMode, ScipImportReport};
+
+// Re-export symbol index types
+pub use symbols::{
+    SymbolIndex, SymbolMatch, SymbolMatchKind, SymbolSearchOptions, SymbolSearchReport,
+};
+
 // Re-export sync types
 pub use sync::{clear_branch_kg, sync_branch_kg, sync_branch_kg_default, KgSyncResult};
 
@@ -0,0 +1,803 @@
+//! Code ownership for the KG, from `CODEOWNERS` and Git history.
+//!
+//! Two sources feed `owner` nodes with `owns` edges to file and doc nodes:
+//!
+//! - **`CODEOWNERS`**: the first of `.github/CODEOWNERS`, `.gitlab/CODEOWNERS`,
+//!   `CODEOWNERS` and `docs/CODEOWNERS` in the workspace root. Owners are
+//!   `@user`, `@org/team`, GitLab `@@role` or email addresses, with node IDs
+//!   `owner:<owner>`.
+//! - **Git history**: the primary authors of each file, ranked by commits
+//!   weighted for recency (see [`primary_authors`]). Authors get
+//!   `owner:git:<name>` nodes.
+//!
+//! ## CODEOWNERS Syntax
+//!
+//! Patterns follow GitHub's rules: `*.js` matches at any depth, a leading or
+//! inner `/` anchors to the root, a trailing `/` matches everything below a
+//! directory, `docs/*` matches direct children only and `**` spans
+//! directories. The last matching rule wins.
+//!
+//! GitLab sections (`[Section]`, `^[Optional]`, `[Section][2]`) are
+//! evaluated separately: the last match in each section applies and owners
+//! from all sections are combined. Rules without owners inherit the section's
+//! default owners.
+//!
+//! ## Edges
+//!
+//! `owns` edges carry the file `path` and a `source`:
+//!
+//! | Source | Props |
+//! |--------|-------|
+//! | `codeowners` | `file`, `pattern`, `line`, `section` |
+//! | `git` | `commits`, `lastCommit`, `share` |
+//!
+//! At ask time [`OwnershipIndex`] maps paths back to their owners for
+//! `RagChunk` results and `gik ask --owner`.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::errors::GikError;
+use crate::git::GitCommitInfo;
+use crate::workspace::Workspace;
+
+use super::entities::{KgEdge, KgNode};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Locations searched for a `CODEOWNERS` file, in order.
+pub const CODEOWNERS_PATHS: &[&str] = &[
+    ".github/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+];
+
+/// Maximum number of commits read for primary authors.
+pub const AUTHOR_HISTORY_LIMIT: usize = 5000;
+
+/// Age in days at which a commit counts half as much as the newest one.
+const AUTHOR_HALF_LIFE_DAYS: f64 = 180.0;
+
+/// Maximum number of primary authors kept per file.
+const MAX_PRIMARY_AUTHORS: usize = 3;
+
+/// Minimum share of a file's weighted commits for a primary author.
+const MIN_AUTHOR_SHARE: f64 = 0.2;
+
+/// Build the node ID for a `CODEOWNERS` owner.
+pub fn owner_id(owner: &str) -> String {
+    format!("owner:{}", owner)
+}
+
+/// Build the node ID for a Git author.
+pub fn author_id(name: &str) -> String {
+    format!("owner:git:{}", name)
+}
+
+// ============================================================================
+// CODEOWNERS
+// ============================================================================
+
+/// A single `CODEOWNERS` rule.
+#[derive(Debug, Clone)]
+pub struct CodeOwnerRule {
+    /// Pattern as written.
+    pub pattern: String,
+    /// Owners, after section defaults are applied. Empty unsets ownership.
+    pub owners: Vec<String>,
+    /// GitLab section name, if any.
+    pub section: Option<String>,
+    /// 1-based line number.
+    pub line: usize,
+    matcher: Regex,
+}
+
+impl CodeOwnerRule {
+    /// Whether the rule's pattern matches a workspace-relative path.
+    pub fn matches(&self, path: &str) -> bool {
+        self.matcher.is_match(path)
+    }
+}
+
+/// A parsed `CODEOWNERS` file.
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    /// Workspace-relative path of the file.
+    pub path: String,
+    /// Rules in file order.
+    pub rules: Vec<CodeOwnerRule>,
+    /// Lines that couldn't be parsed.
+    pub warnings: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Parse `CODEOWNERS` text read from `path`.
+    pub fn parse(path: &str, text: &str) -> Self {
+        let mut codeowners = CodeOwners {
+            path: path.to_string(),
+            ..Default::default()
+        };
+        let mut section: Option<String> = None;
+        let mut section_owners: Vec<String> = Vec::new();
+
+        for (idx, raw) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some((name, defaults)) = parse_section_header(line) {
+                section = Some(name);
+                section_owners = defaults;
+                continue;
+            }
+
+            let mut tokens = tokenize(line).into_iter();
+            let Some(pattern) = tokens.next() else {
+                continue;
+            };
+            let mut owners = Vec::new();
+            for token in tokens {
+                if is_owner(&token) {
+                    owners.push(token);
+                } else {
+                    codeowners
+                        .warnings
+                        .push(format!("{}:{}: invalid owner '{}'", path, line_no, token));
+                }
+            }
+            if owners.is_empty() {
+                owners = section_owners.clone();
+            }
+
+            let Some(matcher) = compile_pattern(&pattern) else {
+                codeowners.warnings.push(format!(
+                    "{}:{}: invalid pattern '{}'",
+                    path, line_no, pattern
+                ));
+                continue;
+            };
+            codeowners.rules.push(CodeOwnerRule {
+                pattern,
+                owners,
+                section: section.clone(),
+                line: line_no,
+                matcher,
+            });
+        }
+
+        codeowners
+    }
+
+    /// The rules that apply to `path`: the last match in each section.
+    pub fn matching_rules(&self, path: &str) -> Vec<&CodeOwnerRule> {
+        let mut seen: HashSet<Option<String>> = HashSet::new();
+        let mut matched: Vec<&CodeOwnerRule> = self
+            .rules
+            .iter()
+            .rev()
+            .filter(|r| r.matches(path))
+            .filter(|r| seen.insert(r.section.as_ref().map(|s| s.to_lowercase())))
+            .collect();
+        matched.reverse();
+        matched
+    }
+
+    /// Owners of `path`, combined across sections.
+    pub fn owners_of(&self, path: &str) -> Vec<String> {
+        let mut owners: Vec<String> = Vec::new();
+        for rule in self.matching_rules(path) {
+            for owner in &rule.owners {
+                if !owners.contains(owner) {
+                    owners.push(owner.clone());
+                }
+            }
+        }
+        owners
+    }
+}
+
+/// Read the workspace's `CODEOWNERS` file, if it has one.
+pub fn read_codeowners(root: &Path) -> Result<Option<CodeOwners>, GikError> {
+    for path in CODEOWNERS_PATHS {
+        let full = root.join(path);
+        if full.is_file() {
+            let text = fs::read_to_string(&full)?;
+            return Ok(Some(CodeOwners::parse(path, &text)));
+        }
+    }
+    Ok(None)
+}
+
+/// Parse a GitLab section header into its name and default owners.
+fn parse_section_header(line: &str) -> Option<(String, Vec<String>)> {
+    let rest = line.strip_prefix('^').unwrap_or(line);
+    let rest = rest.strip_prefix('[')?;
+    let end = rest.find(']')?;
+    let name = rest[..end].trim();
+    if name.is_empty() {
+        return None;
+    }
+    let mut tail = &rest[end + 1..];
+    // Required approvals: `[Section][2]`
+    if let Some(after) = tail.strip_prefix('[') {
+        tail = &after[after.find(']')? + 1..];
+    }
+    let owners = tokenize(tail).into_iter().filter(|t| is_owner(t)).collect();
+    Some((name.to_string(), owners))
+}
+
+/// Split a line on unescaped whitespace, dropping a trailing `#` comment.
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '#' if current.is_empty() => break,
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Whether a token names an owner: `@user`, `@org/team`, `@@role` or an email.
+fn is_owner(token: &str) -> bool {
+    match token.strip_prefix('@') {
+        Some(name) => !name.is_empty(),
+        None => token
+            .split_once('@')
+            .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.')),
+    }
+}
+
+/// Classify an owner as `user`, `team`, `role` or `email`.
+fn owner_type(owner: &str) -> &'static str {
+    if owner.starts_with("@@") {
+        "role"
+    } else if let Some(name) = owner.strip_prefix('@') {
+        if name.contains('/') {
+            "team"
+        } else {
+            "user"
+        }
+    } else {
+        "email"
+    }
+}
+
+/// Compile a `CODEOWNERS` pattern into a regex over workspace-relative paths.
+fn compile_pattern(pattern: &str) -> Option<Regex> {
+    let dir_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.contains('/');
+    let body = trimmed.trim_start_matches('/');
+    if body.is_empty() {
+        return Regex::new("^.*$").ok();
+    }
+
+    let mut re = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let chars: Vec<char> = body.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    re.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    re.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+        i += 1;
+    }
+
+    // `docs/*` only matches direct children; a plain name also matches below it
+    let leaf = body.rsplit('/').next().unwrap_or(body);
+    re.push_str(if dir_only {
+        "/.*$"
+    } else if leaf.contains(['*', '?']) {
+        "$"
+    } else {
+        "(?:/.*)?$"
+    });
+    Regex::new(&re).ok()
+}
+
+// ============================================================================
+// Git Authors
+// ============================================================================
+
+/// A primary author of a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileAuthor {
+    /// Author name (after `.mailmap`).
+    pub name: String,
+    /// Commits touching the file.
+    pub commits: usize,
+    /// Most recent commit touching the file.
+    pub last_commit: DateTime<Utc>,
+    /// Share of the file's recency-weighted commits (0.0-1.0).
+    pub share: f64,
+}
+
+/// Recency-weighted commits of one author to one file.
+struct AuthorWeight {
+    weight: f64,
+    commits: usize,
+    last_commit: DateTime<Utc>,
+}
+
+/// Rank the primary authors of every path touched in `commits`.
+///
+/// Each commit counts `0.5^(age / 180 days)`, with age measured from the
+/// newest commit so results don't drift between syncs of the same history.
+/// Up to three authors holding at least 20% of a file's weight are kept, and
+/// always the top one.
+pub fn primary_authors(commits: &[GitCommitInfo]) -> HashMap<String, Vec<FileAuthor>> {
+    let Some(newest) = commits.iter().map(|c| c.timestamp).max() else {
+        return HashMap::new();
+    };
+
+    let mut weights: HashMap<&str, HashMap<&str, AuthorWeight>> = HashMap::new();
+    for commit in commits {
+        let age_days = (newest - commit.timestamp).num_seconds().max(0) as f64 / 86_400.0;
+        let weight = 0.5f64.powf(age_days / AUTHOR_HALF_LIFE_DAYS);
+        for path in &commit.paths {
+            let entry = weights
+                .entry(path.as_str())
+                .or_default()
+                .entry(commit.author.as_str())
+                .or_insert(AuthorWeight {
+                    weight: 0.0,
+                    commits: 0,
+                    last_commit: commit.timestamp,
+                });
+            entry.weight += weight;
+            entry.commits += 1;
+            entry.last_commit = entry.last_commit.max(commit.timestamp);
+        }
+    }
+
+    weights
+        .into_iter()
+        .map(|(path, by_author)| {
+            let total: f64 = by_author.values().map(|a| a.weight).sum();
+            let mut ranked: Vec<FileAuthor> = by_author
+                .into_iter()
+                .map(|(name, a)| FileAuthor {
+                    name: name.to_string(),
+                    commits: a.commits,
+                    last_commit: a.last_commit,
+                    share: if total > 0.0 { a.weight / total } else { 0.0 },
+                })
+                .collect();
+            ranked.sort_by(|a, b| {
+                b.share
+                    .partial_cmp(&a.share)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+            let keep = ranked
+                .iter()
+                .take(MAX_PRIMARY_AUTHORS)
+                .take_while(|a| a.share >= MIN_AUTHOR_SHARE)
+                .count()
+                .max(1);
+            ranked.truncate(keep);
+            (path.to_string(), ranked)
+        })
+        .collect()
+}
+
+// ============================================================================
+// Owner Nodes
+// ============================================================================
+
+/// Nodes and edges built from ownership sources.
+#[derive(Debug, Clone, Default)]
+pub struct OwnerGraph {
+    /// `owner` nodes.
+    pub nodes: Vec<KgNode>,
+    /// `owns` edges.
+    pub edges: Vec<KgEdge>,
+}
+
+/// Build owner nodes and `owns` edges.
+///
+/// `file_nodes` maps workspace-relative paths to existing file/doc node IDs;
+/// paths outside it aren't linked.
+pub fn build_owner_graph(
+    codeowners: Option<&CodeOwners>,
+    authors: &HashMap<String, Vec<FileAuthor>>,
+    file_nodes: &HashMap<String, String>,
+) -> OwnerGraph {
+    let mut graph = OwnerGraph::default();
+    let mut owner_nodes: HashSet<String> = HashSet::new();
+    let mut paths: Vec<(&String, &String)> = file_nodes.iter().collect();
+    paths.sort();
+
+    for (path, file_id) in paths {
+        let mut linked: HashSet<String> = HashSet::new();
+
+        for rule in codeowners
+            .map(|c| c.matching_rules(path))
+            .unwrap_or_default()
+        {
+            for owner in &rule.owners {
+                let id = owner_id(owner);
+                if !linked.insert(id.clone()) {
+                    continue;
+                }
+                if owner_nodes.insert(id.clone()) {
+                    graph
+                        .nodes
+                        .push(KgNode::new(&id, "owner", owner).with_props(json!({
+                            "ownerType": owner_type(owner),
+                            "source": "codeowners",
+                        })));
+                }
+                graph
+                    .edges
+                    .push(KgEdge::new(&id, file_id, "owns").with_props(json!({
+                        "source": "codeowners",
+                        "path": path,
+                        "file": codeowners.map(|c| c.path.as_str()),
+                        "pattern": rule.pattern,
+                        "line": rule.line,
+                        "section": rule.section,
+                    })));
+            }
+        }
+
+        for author in authors.get(path).into_iter().flatten() {
+            let id = author_id(&author.name);
+            if owner_nodes.insert(id.clone()) {
+                graph
+                    .nodes
+                    .push(KgNode::new(&id, "owner", &author.name).with_props(json!({
+                        "ownerType": "author",
+                        "source": "git",
+                    })));
+            }
+            graph
+                .edges
+                .push(KgEdge::new(&id, file_id, "owns").with_props(json!({
+                    "source": "git",
+                    "path": path,
+                    "commits": author.commits,
+                    "lastCommit": author.last_commit.to_rfc3339(),
+                    "share": (author.share * 100.0).round() / 100.0,
+                })));
+        }
+    }
+
+    graph
+}
+
+// ============================================================================
+// Ask-time Lookup
+// ============================================================================
+
+/// Owners of a file, as attached to ask results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileOwnership {
+    /// `CODEOWNERS` owners.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+    /// Primary Git authors, most significant first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+}
+
+impl FileOwnership {
+    /// Whether `owner` is one of the owners or authors.
+    ///
+    /// Case-insensitive; a leading `@` is optional, so `alice` matches `@alice`.
+    pub fn matches(&self, owner: &str) -> bool {
+        let wanted = owner.trim_start_matches('@');
+        self.owners
+            .iter()
+            .chain(&self.authors)
+            .any(|o| o.trim_start_matches('@').eq_ignore_ascii_case(wanted))
+    }
+
+    /// Whether no owner or author is known.
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty() && self.authors.is_empty()
+    }
+}
+
+/// Path → ownership lookup built from the KG's `owns` edges.
+#[derive(Debug, Clone, Default)]
+pub struct OwnershipIndex {
+    by_path: HashMap<String, FileOwnership>,
+}
+
+impl OwnershipIndex {
+    /// Build the index from `owner` nodes and `owns` edges.
+    pub fn from_graph(owner_nodes: &[KgNode], owns_edges: &[KgEdge]) -> Self {
+        let labels: HashMap<&str, &str> = owner_nodes
+            .iter()
+            .filter(|n| n.kind == "owner")
+            .map(|n| (n.id.as_str(), n.label.as_str()))
+            .collect();
+
+        let mut shares: HashMap<String, Vec<(f64, String)>> = HashMap::new();
+        let mut by_path: HashMap<String, FileOwnership> = HashMap::new();
+        for edge in owns_edges.iter().filter(|e| e.kind == "owns") {
+            let (Some(path), Some(label)) =
+                (edge.props["path"].as_str(), labels.get(edge.from.as_str()))
+            else {
+                continue;
+            };
+            if edge.props["source"] == "git" {
+                let share = edge.props["share"].as_f64().unwrap_or_default();
+                shares
+                    .entry(path.to_string())
+                    .or_default()
+                    .push((share, label.to_string()));
+            } else {
+                let ownership = by_path.entry(path.to_string()).or_default();
+                if !ownership.owners.iter().any(|o| o == label) {
+                    ownership.owners.push(label.to_string());
+                }
+            }
+        }
+
+        for (path, mut authors) in shares {
+            authors.sort_by(|a, b| {
+                b.0.partial_cmp(&a.0)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.1.cmp(&b.1))
+            });
+            by_path.entry(path).or_default().authors =
+                authors.into_iter().map(|(_, name)| name).collect();
+        }
+
+        Self { by_path }
+    }
+
+    /// Ownership of a workspace-relative path.
+    pub fn get(&self, path: &str) -> Option<&FileOwnership> {
+        self.by_path.get(path)
+    }
+
+    /// Whether any file has an owner.
+    pub fn is_empty(&self) -> bool {
+        self.by_path.is_empty()
+    }
+}
+
+/// Load the ownership index for a branch; empty when there is no KG.
+pub fn read_ownership_index(
+    workspace: &Workspace,
+    branch: &str,
+) -> Result<OwnershipIndex, GikError> {
+    if !super::kg_exists(workspace, branch) {
+        return Ok(OwnershipIndex::default());
+    }
+    let store = super::open_kg_store(workspace, branch)?;
+    let owners = store.get_nodes_by_kind("owner")?;
+    if owners.is_empty() {
+        return Ok(OwnershipIndex::default());
+    }
+    let edges = store.get_edges_by_kind("owns")?;
+    Ok(OwnershipIndex::from_graph(&owners, &edges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, days_ago: i64, paths: &[&str]) -> GitCommitInfo {
+        let base = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        GitCommitInfo {
+            sha: format!("{}-{}", author, days_ago),
+            author: author.to_string(),
+            timestamp: base - chrono::Duration::days(days_ago),
+            subject: String::new(),
+            body: String::new(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_patterns() {
+        let cases = [
+            ("*", "src/main.rs", true),
+            ("*.js", "web/app/index.js", true),
+            ("*.js", "web/app/index.ts", false),
+            ("/docs", "docs/guide/intro.md", true),
+            ("/docs", "src/docs/intro.md", false),
+            ("apps/", "web/apps/main.ts", true),
+            ("apps/", "apps", false),
+            ("docs/*", "docs/intro.md", true),
+            ("docs/*", "docs/guide/intro.md", false),
+            ("**/logs", "deep/build/logs/out.txt", true),
+            ("docs/**/*.md", "docs/a/b/c.md", true),
+            ("src/api/", "src/api/users.ts", true),
+            ("src/api/", "lib/src/api/users.ts", false),
+            ("README.md", "pkg/README.md", true),
+            ("/build/logs/", "build/logs/x/y.log", true),
+        ];
+        for (pattern, path, expected) in cases {
+            let re = compile_pattern(pattern).unwrap();
+            assert_eq!(re.is_match(path), expected, "{} vs {}", pattern, path);
+        }
+    }
+
+    #[test]
+    fn test_github_last_match_wins() {
+        let codeowners = CodeOwners::parse(
+            ".github/CODEOWNERS",
+            "# Default owners\n\
+             *       @acme/core\n\
+             *.md    @alice docs@acme.io # docs team\n\
+             /src/billing/ @bob\n\
+             /src/billing/vendor/\n\
+             /secrets  not-an-owner\n\
+             /my\\ file.txt @carol\n",
+        );
+
+        assert_eq!(codeowners.owners_of("src/main.rs"), vec!["@acme/core"]);
+        assert_eq!(codeowners.owners_of("src/billing/README.md"), vec!["@bob"]);
+        assert_eq!(
+            codeowners.owners_of("guide/intro.md"),
+            vec!["@alice", "docs@acme.io"]
+        );
+        // A rule without owners unsets ownership
+        assert!(codeowners.owners_of("src/billing/vendor/lib.rs").is_empty());
+        assert_eq!(codeowners.owners_of("my file.txt"), vec!["@carol"]);
+
+        assert_eq!(codeowners.warnings.len(), 1);
+        assert!(codeowners.warnings[0].contains(":6: invalid owner 'not-an-owner'"));
+        assert_eq!(codeowners.matching_rules("src/billing/api.rs")[0].line, 4);
+    }
+
+    #[test]
+    fn test_gitlab_sections() {
+        let codeowners = CodeOwners::parse(
+            ".gitlab/CODEOWNERS",
+            "* @admin\n\
+             \n\
+             [Backend][2] @backend-team\n\
+             src/\n\
+             src/api/ @api-owner\n\
+             \n\
+             ^[Docs] @tech-writers\n\
+             *.md\n\
+             \n\
+             [backend]\n\
+             src/db/ @@maintainer\n",
+        );
+
+        assert_eq!(
+            codeowners.owners_of("src/main.rs"),
+            vec!["@admin", "@backend-team"]
+        );
+        assert_eq!(
+            codeowners.owners_of("src/api/README.md"),
+            vec!["@admin", "@api-owner", "@tech-writers"]
+        );
+        // Section names are case-insensitive, so `[backend]` continues `[Backend]`
+        assert_eq!(
+            codeowners.owners_of("src/db/schema.sql"),
+            vec!["@admin", "@@maintainer"]
+        );
+        assert_eq!(codeowners.rules[1].section.as_deref(), Some("Backend"));
+    }
+
+    #[test]
+    fn test_primary_authors_weighs_recency() {
+        let commits = vec![
+            commit("Alice", 0, &["src/lib.rs"]),
+            commit("Alice", 5, &["src/lib.rs", "README.md"]),
+            commit("Bob", 900, &["src/lib.rs"]),
+            commit("Bob", 910, &["src/lib.rs"]),
+            commit("Bob", 920, &["src/lib.rs"]),
+            commit("Carol", 10, &["README.md"]),
+            commit("Dave", 12, &["README.md"]),
+        ];
+        let authors = primary_authors(&commits);
+
+        // Bob has more commits, but they are years old
+        let lib = &authors["src/lib.rs"];
+        assert_eq!(lib.len(), 1);
+        assert_eq!(lib[0].name, "Alice");
+        assert_eq!(lib[0].commits, 2);
+        assert!(lib[0].share > 0.9);
+
+        let readme: Vec<&str> = authors["README.md"]
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect();
+        assert_eq!(readme, vec!["Alice", "Carol", "Dave"]);
+        assert!(primary_authors(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_owner_graph_and_index() {
+        let codeowners = CodeOwners::parse("CODEOWNERS", "*.rs @acme/rust\n/src/api/ @alice\n");
+        let authors = primary_authors(&[
+            commit("Alice Smith", 0, &["src/api/users.rs"]),
+            commit("Bob", 1, &["src/api/users.rs", "README.md"]),
+        ]);
+        let file_nodes: HashMap<String, String> = [
+            ("src/api/users.rs", "file:src/api/users.rs"),
+            ("src/main.rs", "file:src/main.rs"),
+            ("README.md", "doc:README.md"),
+        ]
+        .into_iter()
+        .map(|(p, id)| (p.to_string(), id.to_string()))
+        .collect();
+
+        let graph = build_owner_graph(Some(&codeowners), &authors, &file_nodes);
+        let ids: HashSet<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            HashSet::from([
+                "owner:@alice",
+                "owner:@acme/rust",
+                "owner:git:Alice Smith",
+                "owner:git:Bob",
+            ])
+        );
+        let team = graph.nodes.iter().find(|n| n.id == "owner:@acme/rust");
+        assert_eq!(team.unwrap().props["ownerType"], "team");
+
+        let api_edge = graph
+            .edges
+            .iter()
+            .find(|e| e.from == "owner:@alice")
+            .unwrap();
+        assert_eq!(api_edge.to, "file:src/api/users.rs");
+        assert_eq!(api_edge.props["pattern"], "/src/api/");
+        assert_eq!(api_edge.props["line"], 2);
+
+        let index = OwnershipIndex::from_graph(&graph.nodes, &graph.edges);
+        let users = index.get("src/api/users.rs").unwrap();
+        assert_eq!(users.owners, vec!["@alice"]);
+        assert_eq!(users.authors, vec!["Alice Smith", "Bob"]);
+        assert!(users.matches("alice"));
+        assert!(users.matches("@ALICE"));
+        assert!(users.matches("bob"));
+        assert!(!users.matches("@acme/rust"));
+
+        assert!(index.get("src/main.rs").unwrap().matches("@acme/rust"));
+        assert_eq!(index.get("README.md").unwrap().authors, vec!["Bob"]);
+        assert!(index.get("Cargo.toml").is_none());
+    }
+}
@@ -0,0 +1,502 @@
+//! Package nodes for the KG, built from the stack dependency inventory.
+//!
+//! `gik stack` records every dependency declared in a manifest
+//! (`Cargo.toml`, `package.json`, `pyproject.toml`, `go.mod`, ...) in
+//! `stack/dependencies.jsonl`. This module turns those entries into
+//! `package:<manager>:<name>` nodes and connects them to the code:
+//!
+//! - `declaresDependency`: manifest file → package, with `version` and `scope`
+//! - `usesPackage`: source file → package, for imports that don't resolve to a
+//!   workspace file but name a declared dependency
+//!
+//! Import names don't always match package names, so lookups are normalized
+//! per ecosystem:
+//!
+//! | Ecosystem | Import | Package |
+//! |-----------|--------|---------|
+//! | JS/TS | `lodash/fp`, `@tanstack/react-query` | `lodash`, `@tanstack/react-query` |
+//! | Python | `yaml.loader` | `PyYAML` only if spelled alike (`-`, `_`, `.` and case ignored) |
+//! | Rust | `use tokio_util::codec` | `tokio-util` |
+//! | Go | `github.com/spf13/cobra/doc` | Longest module path prefix (`github.com/spf13/cobra`) |
+//! | PHP | `use Symfony\Component\HttpFoundation\Request` | Composer vendor, then package name (`symfony/http-foundation`) |
+//! | Ruby | `require "active_support/core_ext"` | `activesupport` |
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use regex::Regex;
+use serde_json::json;
+
+use crate::stack::StackDependencyEntry;
+
+use super::entities::{KgEdge, KgNode};
+
+// ============================================================================
+// Package Nodes
+// ============================================================================
+
+/// Build the node ID for a package.
+pub fn package_id(manager: &str, name: &str) -> String {
+    format!("package:{}:{}", manager, name)
+}
+
+/// Nodes and edges built from the dependency inventory.
+#[derive(Debug, Clone, Default)]
+pub struct PackageGraph {
+    /// `package` nodes, plus `file` nodes for manifests missing from the graph.
+    pub nodes: Vec<KgNode>,
+    /// `declaresDependency` edges.
+    pub edges: Vec<KgEdge>,
+}
+
+/// Build package nodes and `declaresDependency` edges.
+///
+/// `file_nodes` maps workspace-relative paths to existing file/doc node IDs.
+/// Manifests that weren't indexed get a bare `file` node so their edges have
+/// a source.
+pub fn build_package_graph(
+    deps: &[StackDependencyEntry],
+    file_nodes: &HashMap<String, String>,
+) -> PackageGraph {
+    let mut graph = PackageGraph::default();
+    let mut package_index: HashMap<String, usize> = HashMap::new();
+    let mut missing_manifests: HashSet<&str> = HashSet::new();
+    let mut declared: HashSet<(String, String)> = HashSet::new();
+
+    for dep in deps {
+        let id = package_id(&dep.manager, &dep.name);
+        let idx = *package_index.entry(id.clone()).or_insert_with(|| {
+            graph
+                .nodes
+                .push(KgNode::new(&id, "package", &dep.name).with_props(json!({
+                    "manager": dep.manager,
+                    "ecosystem": ecosystem_for_manager(&dep.manager),
+                    "version": dep.version,
+                    "versions": [],
+                    "scopes": [],
+                    "manifests": [],
+                })));
+            graph.nodes.len() - 1
+        });
+        if let Some(obj) = graph.nodes[idx].props.as_object_mut() {
+            for (key, value) in [
+                ("versions", &dep.version),
+                ("scopes", &dep.scope),
+                ("manifests", &dep.manifest_path),
+            ] {
+                if let Some(list) = obj.get_mut(key).and_then(|v| v.as_array_mut()) {
+                    if !value.is_empty() && !list.iter().any(|v| v == value) {
+                        list.push(json!(value));
+                    }
+                }
+            }
+            if obj["version"].as_str().is_some_and(str::is_empty) {
+                obj.insert("version".to_string(), json!(dep.version));
+            }
+        }
+
+        let manifest_id = match file_nodes.get(&dep.manifest_path) {
+            Some(node_id) => node_id.clone(),
+            None => {
+                let node_id = format!("file:{}", dep.manifest_path);
+                if missing_manifests.insert(&dep.manifest_path) {
+                    let label = Path::new(&dep.manifest_path)
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or(&dep.manifest_path);
+                    graph
+                        .nodes
+                        .push(KgNode::new(&node_id, "file", label).with_props(json!({
+                            "path": dep.manifest_path,
+                        })));
+                }
+                node_id
+            }
+        };
+        if declared.insert((manifest_id.clone(), id.clone())) {
+            graph.edges.push(
+                KgEdge::new(&manifest_id, &id, "declaresDependency").with_props(json!({
+                    "version": dep.version,
+                    "scope": dep.scope,
+                })),
+            );
+        }
+    }
+
+    graph
+}
+
+// ============================================================================
+// Import Resolution
+// ============================================================================
+
+/// Map a package manager to the ecosystem whose imports it serves.
+fn ecosystem_for_manager(manager: &str) -> Option<&'static str> {
+    match manager {
+        "npm" | "yarn" | "pnpm" | "bun" => Some("js"),
+        "pip" | "poetry" | "pipenv" | "pdm" | "uv" => Some("python"),
+        "cargo" => Some("rust"),
+        "go" => Some("go"),
+        "composer" => Some("php"),
+        "bundler" => Some("ruby"),
+        _ => None,
+    }
+}
+
+/// Map a source file to the ecosystem of its imports.
+fn ecosystem_for_path(path: &str) -> Option<&'static str> {
+    let ext = Path::new(path).extension().and_then(|e| e.to_str())?;
+    match ext {
+        "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "vue" | "svelte" => Some("js"),
+        "py" => Some("python"),
+        "rs" => Some("rust"),
+        "go" => Some("go"),
+        "php" => Some("php"),
+        "rb" => Some("ruby"),
+        _ => None,
+    }
+}
+
+/// Lowercase a name and drop separators, so `active_support` matches
+/// `activesupport` and `python-dateutil` matches `python_dateutil`.
+fn squash(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '-' | '_' | '.'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Resolves raw imports to declared packages.
+#[derive(Debug, Clone, Default)]
+pub struct PackageIndex {
+    /// Normalized import root → package ID, per ecosystem (JS, Python, Rust, Ruby).
+    by_name: HashMap<(&'static str, String), String>,
+    /// Go module paths and their package IDs, longest first.
+    go_modules: Vec<(String, String)>,
+    /// Composer vendor (squashed) → (squashed package name, package ID).
+    php_vendors: HashMap<String, Vec<(String, String)>>,
+}
+
+impl PackageIndex {
+    /// Index the declared dependencies.
+    pub fn new(deps: &[StackDependencyEntry]) -> Self {
+        let mut index = Self::default();
+
+        for dep in deps {
+            let id = package_id(&dep.manager, &dep.name);
+            let Some(ecosystem) = ecosystem_for_manager(&dep.manager) else {
+                continue;
+            };
+            let key = match ecosystem {
+                "js" => dep.name.clone(),
+                "rust" => dep.name.replace('-', "_"),
+                "python" | "ruby" => squash(&dep.name),
+                "go" => {
+                    index.go_modules.push((dep.name.clone(), id));
+                    continue;
+                }
+                _ => {
+                    if let Some((vendor, package)) = dep.name.split_once('/') {
+                        index
+                            .php_vendors
+                            .entry(squash(vendor))
+                            .or_default()
+                            .push((squash(package), id));
+                    }
+                    continue;
+                }
+            };
+            index.by_name.entry((ecosystem, key)).or_insert(id);
+        }
+
+        index
+            .go_modules
+            .sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        index
+    }
+
+    /// Whether no dependency could ever match an import.
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty() && self.go_modules.is_empty() && self.php_vendors.is_empty()
+    }
+
+    /// Find the package a raw import in `file_path` refers to.
+    pub fn resolve(&self, file_path: &str, raw: &str) -> Option<&str> {
+        let ecosystem = ecosystem_for_path(file_path)?;
+        let key = match ecosystem {
+            "js" => {
+                if raw.starts_with(['.', '/', '#', '~']) || raw.starts_with("node:") {
+                    return None;
+                }
+                let mut segments = raw.split('/');
+                let first = segments.next()?;
+                if first.starts_with('@') {
+                    format!("{}/{}", first, segments.next()?)
+                } else {
+                    first.to_string()
+                }
+            }
+            "python" => {
+                if raw.starts_with('.') {
+                    return None;
+                }
+                squash(raw.split('.').next()?)
+            }
+            "rust" => raw.trim_start_matches("::").split("::").next()?.to_string(),
+            "ruby" => squash(raw.split('/').next()?),
+            "go" => {
+                return self
+                    .go_modules
+                    .iter()
+                    .find(|(module, _)| {
+                        raw == module
+                            || raw
+                                .strip_prefix(module.as_str())
+                                .is_some_and(|rest| rest.starts_with('/'))
+                    })
+                    .map(|(_, id)| id.as_str());
+            }
+            _ => return self.resolve_php(raw),
+        };
+
+        self.by_name.get(&(ecosystem, key)).map(String::as_str)
+    }
+
+    /// Match a PHP namespace to a Composer package: vendor first, then the
+    /// package name against the remaining namespace segments.
+    fn resolve_php(&self, raw: &str) -> Option<&str> {
+        let segments: Vec<String> = raw
+            .trim_start_matches('\\')
+            .split('\\')
+            .map(squash)
+            .collect();
+        let candidates = self.php_vendors.get(segments.first()?)?;
+        if let [(_, id)] = candidates.as_slice() {
+            return Some(id);
+        }
+        candidates
+            .iter()
+            .find(|(package, _)| segments[1..].contains(package))
+            .map(|(_, id)| id.as_str())
+    }
+}
+
+/// Crate roots named by `use` and `extern crate` in Rust source.
+///
+/// Complements the extractor's Rust imports, which only follow
+/// `crate::`/`super::`/`self::` paths into the workspace.
+pub fn extract_rust_crate_roots(text: &str) -> Vec<String> {
+    let re = Regex::new(
+        r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?(?:use\s+(?:::)?([A-Za-z_][A-Za-z0-9_]*)::|extern\s+crate\s+([A-Za-z_][A-Za-z0-9_]*))",
+    )
+    .expect("Invalid regex");
+
+    let mut roots: Vec<String> = Vec::new();
+    for cap in re.captures_iter(text) {
+        let Some(m) = cap.get(1).or_else(|| cap.get(2)) else {
+            continue;
+        };
+        let root = m.as_str();
+        if matches!(
+            root,
+            "crate" | "super" | "self" | "Self" | "std" | "core" | "alloc"
+        ) {
+            continue;
+        }
+        if !roots.iter().any(|r| r == root) {
+            roots.push(root.to_string());
+        }
+    }
+    roots
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dep(
+        manager: &str,
+        name: &str,
+        version: &str,
+        scope: &str,
+        manifest: &str,
+    ) -> StackDependencyEntry {
+        StackDependencyEntry {
+            manager: manager.to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+            scope: scope.to_string(),
+            manifest_path: manifest.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_package_graph() {
+        let deps = vec![
+            dep("npm", "lodash", "^4.17.21", "runtime", "web/package.json"),
+            dep("npm", "lodash", "^4.17.0", "dev", "admin/package.json"),
+            dep("cargo", "reqwest", "0.12", "runtime", "Cargo.toml"),
+        ];
+        let mut file_nodes = HashMap::new();
+        file_nodes.insert(
+            "web/package.json".to_string(),
+            "file:web/package.json".to_string(),
+        );
+
+        let graph = build_package_graph(&deps, &file_nodes);
+
+        let lodash = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "package:npm:lodash")
+            .unwrap();
+        assert_eq!(lodash.kind, "package");
+        assert_eq!(lodash.props["version"], "^4.17.21");
+        assert_eq!(lodash.props["ecosystem"], "js");
+        assert_eq!(lodash.props["versions"].as_array().unwrap().len(), 2);
+        assert_eq!(lodash.props["manifests"].as_array().unwrap().len(), 2);
+
+        // Unindexed manifests get a bare file node
+        assert!(graph
+            .nodes
+            .iter()
+            .any(|n| n.id == "file:admin/package.json" && n.kind == "file"));
+        assert!(!graph.nodes.iter().any(|n| n.id == "file:web/package.json"));
+
+        assert_eq!(graph.edges.len(), 3);
+        let edge = graph
+            .edges
+            .iter()
+            .find(|e| e.from == "file:admin/package.json")
+            .unwrap();
+        assert_eq!(edge.kind, "declaresDependency");
+        assert_eq!(edge.to, "package:npm:lodash");
+        assert_eq!(edge.props["scope"], "dev");
+    }
+
+    #[test]
+    fn test_package_index_resolve() {
+        let deps = vec![
+            dep("npm", "lodash", "4", "runtime", "package.json"),
+            dep(
+                "npm",
+                "@tanstack/react-query",
+                "5",
+                "runtime",
+                "package.json",
+            ),
+            dep(
+                "poetry",
+                "python-dateutil",
+                "2",
+                "runtime",
+                "pyproject.toml",
+            ),
+            dep("pip", "requests", "2", "runtime", "requirements.txt"),
+            dep("cargo", "tokio-util", "0.7", "runtime", "Cargo.toml"),
+            dep(
+                "go",
+                "github.com/spf13/cobra",
+                "v1.8.0",
+                "runtime",
+                "go.mod",
+            ),
+            dep(
+                "go",
+                "github.com/spf13/cobra/extra",
+                "v1.0.0",
+                "runtime",
+                "go.mod",
+            ),
+            dep(
+                "composer",
+                "guzzlehttp/guzzle",
+                "^7",
+                "runtime",
+                "composer.json",
+            ),
+            dep(
+                "composer",
+                "symfony/http-foundation",
+                "^6",
+                "runtime",
+                "composer.json",
+            ),
+            dep(
+                "composer",
+                "symfony/console",
+                "^6",
+                "runtime",
+                "composer.json",
+            ),
+            dep("bundler", "activesupport", "7", "runtime", "Gemfile"),
+        ];
+        let index = PackageIndex::new(&deps);
+
+        assert_eq!(
+            index.resolve("src/a.ts", "lodash/fp"),
+            Some("package:npm:lodash")
+        );
+        assert_eq!(
+            index.resolve("src/a.tsx", "@tanstack/react-query"),
+            Some("package:npm:@tanstack/react-query")
+        );
+        assert_eq!(index.resolve("src/a.ts", "./lodash"), None);
+        assert_eq!(index.resolve("src/a.ts", "node:fs"), None);
+        assert_eq!(
+            index.resolve("app/x.py", "requests.adapters"),
+            Some("package:pip:requests")
+        );
+        assert_eq!(index.resolve("app/x.py", ".requests"), None);
+        assert_eq!(
+            index.resolve("src/lib.rs", "tokio_util::codec"),
+            Some("package:cargo:tokio-util")
+        );
+        assert_eq!(
+            index.resolve("cmd/main.go", "github.com/spf13/cobra/doc"),
+            Some("package:go:github.com/spf13/cobra")
+        );
+        assert_eq!(
+            index.resolve("cmd/main.go", "github.com/spf13/cobra/extra/x"),
+            Some("package:go:github.com/spf13/cobra/extra")
+        );
+        assert_eq!(
+            index.resolve("cmd/main.go", "github.com/spf13/cobrax"),
+            None
+        );
+        assert_eq!(
+            index.resolve("src/A.php", "GuzzleHttp\\Client"),
+            Some("package:composer:guzzlehttp/guzzle")
+        );
+        assert_eq!(
+            index.resolve("src/A.php", "Symfony\\Component\\HttpFoundation\\Request"),
+            Some("package:composer:symfony/http-foundation")
+        );
+        assert_eq!(
+            index.resolve("lib/a.rb", "active_support/core_ext"),
+            Some("package:bundler:activesupport")
+        );
+        // Lodash is a JS package; a Python import of the same name doesn't match
+        assert_eq!(index.resolve("app/x.py", "lodash"), None);
+    }
+
+    #[test]
+    fn test_extract_rust_crate_roots() {
+        let text = r#"
+extern crate serde;
+use std::collections::HashMap;
+use crate::errors::GikError;
+pub(crate) use tokio_util::codec::Framed;
+use ::reqwest::Client;
+use serde::Deserialize;
+"#;
+        assert_eq!(
+            extract_rust_crate_roots(text),
+            vec!["serde", "tokio_util", "reqwest"]
+        );
+    }
+}
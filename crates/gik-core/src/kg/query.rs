@@ -20,6 +20,8 @@
 //! - "all routes", "all endpoints", "all GET/POST/PUT/DELETE handlers"
 //! - "all functions", "all methods", "all classes"
 //! - "todas as rotas", "todos os métodos", "liste todas as funções"
+//! - "all RPCs that return User", "all mutations", "all messages"
+//! - "all services", "all images", "all resources" (infrastructure)
 //!
 //! ## Strategy
 //!
@@ -306,18 +308,22 @@ pub fn build_ask_kg_context(
     }
 
     // Build lookup maps
-    let node_by_id: HashMap<String, &KgNode> =
-        all_nodes.iter().map(|n| (n.id.clone(), n)).collect();
+    let node_by_id: HashMap<String, &KgNode> = all_nodes.iter().map(|n| (n.id.clone(), n)).collect();
 
-    let node_by_path: HashMap<String, &KgNode> = all_nodes
-        .iter()
-        .filter_map(|n| {
-            n.props
-                .get("path")
-                .and_then(|v| v.as_str())
-                .map(|p| (p.to_string(), n))
-        })
-        .collect();
+    // Endpoints, schema and infrastructure nodes also carry `path`; the
+    // file/doc node wins so chunks root at the whole file
+    let is_file = |n: &KgNode| n.kind == "file" || n.kind == "doc";
+    let mut node_by_path: HashMap<String, &KgNode> = HashMap::new();
+    for n in &all_nodes {
+        if let Some(p) = n.props.get("path").and_then(|v| v.as_str()) {
+            let keep_existing = node_by_path
+                .get(p)
+                .is_some_and(|existing| is_file(existing) || !is_file(n));
+            if !keep_existing {
+                node_by_path.insert(p.to_string(), n);
+            }
+        }
+    }
 
     debug!("Built node_by_path with {} entries", node_by_path.len());
 
@@ -364,6 +370,10 @@ pub fn build_ask_kg_context(
         }
     }
 
+    // Packages named in the question ("which files use reqwest") come first
+    let package_roots = packages_named_in(question, &all_nodes);
+    root_nodes.splice(0..0, package_roots);
+
     // Deduplicate roots
     let mut seen_roots: HashSet<String> = HashSet::new();
     root_nodes.retain(|(id, _)| seen_roots.insert(id.clone()));
@@ -403,10 +413,55 @@ pub fn build_ask_kg_context(
         }
     }
 
+    // Docs covering the matched code, and code covered by matched docs
+    for (root_id, _) in &root_nodes {
+        if let Some(related) = build_mention_context(
+            root_id,
+            &node_by_id,
+            &outgoing,
+            &incoming,
+            cfg.max_nodes_per_subgraph,
+        ) {
+            results.push(related);
+        }
+        if let Some(tests) = build_test_context(
+            root_id,
+            &node_by_id,
+            &outgoing,
+            &incoming,
+            cfg.max_nodes_per_subgraph,
+        ) {
+            results.push(tests);
+        }
+    }
+
     debug!("Built {} KG subgraphs total", results.len());
     Ok(results)
 }
 
+/// Find package nodes whose name appears as a word in the question.
+fn packages_named_in(question: &str, nodes: &[KgNode]) -> Vec<(String, String)> {
+    let words: HashSet<String> = question
+        .to_lowercase()
+        .split(|c: char| {
+            c.is_whitespace() || matches!(c, ',' | '?' | '!' | '"' | '\'' | '`' | '(' | ')')
+        })
+        .map(|w| w.trim_end_matches(['.', ':', ';']).to_string())
+        .filter(|w| w.len() >= 3)
+        .collect();
+
+    nodes
+        .iter()
+        .filter(|n| n.kind == "package" && words.contains(&n.label.to_lowercase()))
+        .map(|n| {
+            (
+                n.id.clone(),
+                format!("Package named in question: {}", n.label),
+            )
+        })
+        .collect()
+}
+
 /// Check if a question looks like it's about endpoints/APIs.
 fn looks_like_endpoint_question(question: &str) -> bool {
     let q = question.to_lowercase();
@@ -524,6 +579,179 @@ fn build_subgraph(
     }
 }
 
+/// Collect `mentions` edges between a root and docs or code.
+///
+/// For a doc root, returns the code it mentions. For a code file, returns the
+/// docs mentioning the file or the symbols it defines, with the doc sections
+/// named in the reason.
+fn build_mention_context(
+    root_id: &str,
+    node_by_id: &HashMap<String, &KgNode>,
+    outgoing: &HashMap<String, Vec<&KgEdge>>,
+    incoming: &HashMap<String, Vec<&KgEdge>>,
+    max_nodes: usize,
+) -> Option<AskKgResult> {
+    let root = node_by_id.get(root_id)?;
+    let is_doc = root.kind == "doc";
+
+    let mentions: Vec<&KgEdge> = if is_doc {
+        outgoing
+            .get(root_id)
+            .into_iter()
+            .flatten()
+            .filter(|e| e.kind == "mentions")
+            .copied()
+            .collect()
+    } else {
+        let mut targets = vec![root_id];
+        targets.extend(
+            outgoing
+                .get(root_id)
+                .into_iter()
+                .flatten()
+                .filter(|e| e.kind == "defines")
+                .map(|e| e.to.as_str()),
+        );
+        targets
+            .iter()
+            .flat_map(|t| incoming.get(*t).into_iter().flatten())
+            .filter(|e| e.kind == "mentions")
+            .copied()
+            .collect()
+    };
+    if mentions.is_empty() {
+        return None;
+    }
+
+    let mut result = AskKgResult::with_reason(String::new(), vec![root_id.to_string()]);
+    let mut seen_nodes: HashSet<String> = HashSet::new();
+    let mut add_node = |result: &mut AskKgResult, id: &str| {
+        if seen_nodes.insert(id.to_string()) {
+            if let Some(node) = node_by_id.get(id) {
+                result.nodes.push((*node).clone());
+            }
+        }
+    };
+    add_node(&mut result, root_id);
+
+    let mut refs: Vec<String> = Vec::new();
+    for edge in mentions {
+        if result.nodes.len() >= max_nodes {
+            break;
+        }
+        add_node(&mut result, &edge.from);
+        add_node(&mut result, &edge.to);
+        result.edges.push(edge.clone());
+
+        let other = if is_doc { &edge.to } else { &edge.from };
+        let mut label = node_by_id
+            .get(other)
+            .map(|n| n.label.clone())
+            .unwrap_or_else(|| other.clone());
+        if let (false, Some(anchor)) = (is_doc, edge.props["anchor"].as_str()) {
+            label = format!("{}#{}", label, anchor);
+        }
+        if !refs.contains(&label) {
+            refs.push(label);
+        }
+    }
+
+    result.reason = if is_doc {
+        format!("Code mentioned in {}: {}", root.label, refs.join(", "))
+    } else {
+        format!("Docs mentioning {}: {}", root.label, refs.join(", "))
+    };
+    Some(result)
+}
+
+/// Collect `tests` edges between a root and test files.
+///
+/// For a test file, returns the code it exercises. For code, returns the
+/// tests covering the file, the symbols it defines, or the symbol itself, so
+/// the answer can say which tests to run after a change.
+fn build_test_context(
+    root_id: &str,
+    node_by_id: &HashMap<String, &KgNode>,
+    outgoing: &HashMap<String, Vec<&KgEdge>>,
+    incoming: &HashMap<String, Vec<&KgEdge>>,
+    max_nodes: usize,
+) -> Option<AskKgResult> {
+    let root = node_by_id.get(root_id)?;
+    let is_test = root.props["isTest"] == true;
+
+    let links: Vec<&KgEdge> = if is_test {
+        outgoing
+            .get(root_id)
+            .into_iter()
+            .flatten()
+            .filter(|e| e.kind == "tests")
+            .copied()
+            .collect()
+    } else {
+        let mut targets = vec![root_id];
+        targets.extend(
+            outgoing
+                .get(root_id)
+                .into_iter()
+                .flatten()
+                .filter(|e| e.kind == "defines")
+                .map(|e| e.to.as_str()),
+        );
+        targets
+            .iter()
+            .flat_map(|t| incoming.get(*t).into_iter().flatten())
+            .filter(|e| e.kind == "tests")
+            .copied()
+            .collect()
+    };
+    if links.is_empty() {
+        return None;
+    }
+
+    let mut result = AskKgResult::with_reason(String::new(), vec![root_id.to_string()]);
+    let mut seen_nodes: HashSet<String> = HashSet::new();
+    let mut add_node = |result: &mut AskKgResult, id: &str| {
+        if seen_nodes.insert(id.to_string()) {
+            if let Some(node) = node_by_id.get(id) {
+                result.nodes.push((*node).clone());
+            }
+        }
+    };
+    add_node(&mut result, root_id);
+
+    let mut refs: Vec<String> = Vec::new();
+    for edge in links {
+        if result.nodes.len() >= max_nodes {
+            break;
+        }
+        add_node(&mut result, &edge.from);
+        add_node(&mut result, &edge.to);
+        result.edges.push(edge.clone());
+
+        let other = if is_test { &edge.to } else { &edge.from };
+        let label = node_by_id
+            .get(other)
+            .map(|n| {
+                n.props["path"]
+                    .as_str()
+                    .filter(|_| n.kind == "file")
+                    .unwrap_or(&n.label)
+                    .to_string()
+            })
+            .unwrap_or_else(|| other.clone());
+        if !refs.contains(&label) {
+            refs.push(label);
+        }
+    }
+
+    result.reason = if is_test {
+        format!("Code tested by {}: {}", root.label, refs.join(", "))
+    } else {
+        format!("Tests covering {}: {}", root.label, refs.join(", "))
+    };
+    Some(result)
+}
+
 // ============================================================================
 // Exhaustive Query Support (Phase 9.3.1)
 // ============================================================================
@@ -543,6 +771,10 @@ pub struct ExhaustiveQueryIntent {
     /// Optional filter on props (key-value pairs).
     pub prop_filters: HashMap<String, String>,
 
+    /// Optional result type: matches must have a `returns` edge to a node with
+    /// this name (e.g. "all RPCs that return User").
+    pub returns_filter: Option<String>,
+
     /// Human-readable reason for the intent.
     pub reason: String,
 }
@@ -560,6 +792,9 @@ pub struct ExhaustiveQueryIntent {
 /// Portuguese:
 /// - "todas as rotas", "todos os endpoints", "todos os métodos GET"
 /// - "liste todas as funções", "mostre todos os métodos"
+///
+/// Schema operations:
+/// - "all RPCs that return User", "all mutations", "every operation returning Order"
 pub fn detect_exhaustive_intent(question: &str) -> ExhaustiveQueryIntent {
     let q = question.to_lowercase();
 
@@ -594,6 +829,7 @@ pub fn detect_exhaustive_intent(question: &str) -> ExhaustiveQueryIntent {
         target_kinds: Vec::new(),
         http_method_filter: None,
         prop_filters: HashMap::new(),
+        returns_filter: None,
         reason: String::new(),
     };
 
@@ -661,6 +897,63 @@ pub fn detect_exhaustive_intent(question: &str) -> ExhaustiveQueryIntent {
             ],
             "class",
         ),
+        // API schema operations (OpenAPI, GraphQL, gRPC)
+        (
+            &[
+                "rpc",
+                "rpcs",
+                "operation",
+                "operations",
+                "operação",
+                "operações",
+                "mutation",
+                "mutations",
+                "subscription",
+                "subscriptions",
+            ],
+            "operation",
+        ),
+        // Schema messages (Protobuf)
+        (&["message", "messages", "mensagem", "mensagens"], "message"),
+        // Runtime services (Compose services, Kubernetes workloads)
+        (
+            &[
+                "service",
+                "services",
+                "serviço",
+                "serviços",
+                "container",
+                "containers",
+                "deployment",
+                "deployments",
+            ],
+            "service",
+        ),
+        // Third-party packages from the dependency inventory
+        (
+            &[
+                "package",
+                "packages",
+                "pacote",
+                "pacotes",
+                "dependency",
+                "dependencies",
+                "dependência",
+                "dependências",
+                "library",
+                "libraries",
+                "biblioteca",
+                "bibliotecas",
+            ],
+            "package",
+        ),
+        // Container images
+        (&["image", "images", "imagem", "imagens"], "image"),
+        // Infrastructure resources (Terraform, Kubernetes objects)
+        (
+            &["resource", "resources", "recurso", "recursos", "infra"],
+            "resource",
+        ),
         // Components (React, Vue, etc.)
         (
             &["component", "components", "componente", "componentes"],
@@ -695,6 +988,15 @@ pub fn detect_exhaustive_intent(question: &str) -> ExhaustiveQueryIntent {
         }
     }
 
+    // Detect result type filter, keeping the type name's case
+    let returns_re = regex::Regex::new(
+        r"(?i)\b(?:returns?|returning|retorna|retornam)\s+(?:an?\s+|the\s+|um\s+|uma\s+|o\s+)?([A-Za-z_][\w.]*)",
+    )
+    .expect("Invalid regex");
+    if let Some(cap) = returns_re.captures(question) {
+        intent.returns_filter = Some(cap[1].to_string());
+    }
+
     // If endpoint with HTTP method filter, refine the search
     if intent.http_method_filter.is_some() && intent.target_kinds.is_empty() {
         intent.target_kinds.push("endpoint".to_string());
@@ -708,6 +1010,9 @@ pub fn detect_exhaustive_intent(question: &str) -> ExhaustiveQueryIntent {
         } else {
             intent.reason = format!("Exhaustive search for {} entities", kinds_str);
         }
+        if let Some(returned) = &intent.returns_filter {
+            intent.reason.push_str(&format!(" returning {}", returned));
+        }
     } else {
         intent.reason = "Exhaustive search (generic)".to_string();
     }
@@ -731,6 +1036,28 @@ pub fn search_kg_exhaustive(
     let mut matching_nodes: Vec<KgNode> = Vec::new();
     let mut matching_node_ids: HashSet<String> = HashSet::new();
 
+    // Names of the types each node returns (label, or the id's last segment
+    // for types defined outside the graph)
+    let mut returned_types: HashMap<&str, Vec<String>> = HashMap::new();
+    if intent.returns_filter.is_some() {
+        let labels: HashMap<&str, &str> = all_nodes
+            .iter()
+            .map(|n| (n.id.as_str(), n.label.as_str()))
+            .collect();
+        for edge in all_edges.iter().filter(|e| e.kind == "returns") {
+            let name = labels.get(edge.to.as_str()).copied().unwrap_or_else(|| {
+                edge.to
+                    .rsplit([':', '.'])
+                    .next()
+                    .unwrap_or(edge.to.as_str())
+            });
+            returned_types
+                .entry(edge.from.as_str())
+                .or_default()
+                .push(name.to_lowercase());
+        }
+    }
+
     // Find nodes matching the target kinds
     for node in all_nodes {
         if matching_nodes.len() >= max_nodes {
@@ -742,9 +1069,13 @@ pub fn search_kg_exhaustive(
         let kind_matches = intent.target_kinds.iter().any(|target| {
             node.kind == *target
                 || (target == "endpoint" && (node.kind == "route" || node.kind == "api"))
+                || (target == "endpoint"
+                    && node.kind == "operation"
+                    && node.props.get("httpMethod").is_some())
                 || (target == "function" && (node.kind == "fn" || node.kind == "method"))
                 || (target == "class"
                     && (node.kind == "type"
+                        || node.kind == "message"
                         || node.kind == "interface"
                         || node.kind == "struct"
                         || node.kind == "entity"))
@@ -796,6 +1127,19 @@ pub fn search_kg_exhaustive(
             continue;
         }
 
+        // Apply result type filter (nested types match on their last segment)
+        if let Some(returned) = &intent.returns_filter {
+            let wanted = returned.to_lowercase();
+            let returns_match = returned_types.get(node.id.as_str()).is_some_and(|names| {
+                names
+                    .iter()
+                    .any(|n| *n == wanted || n.rsplit('.').next() == Some(wanted.as_str()))
+            });
+            if !returns_match {
+                continue;
+            }
+        }
+
         matching_node_ids.insert(node.id.clone());
         matching_nodes.push(node.clone());
     }
@@ -890,6 +1234,157 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_packages_named_in_question() {
+        let nodes = vec![
+            KgNode::new("package:cargo:reqwest", "package", "reqwest"),
+            KgNode::new(
+                "package:npm:@tanstack/react-query",
+                "package",
+                "@tanstack/react-query",
+            ),
+            KgNode::new("package:npm:ms", "package", "ms"),
+            KgNode::new("file:src/reqwest.rs", "file", "reqwest"),
+        ];
+
+        let roots = packages_named_in("Which files use Reqwest?", &nodes);
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].0, "package:cargo:reqwest");
+
+        let roots = packages_named_in("what depends on `@tanstack/react-query`.", &nodes);
+        assert_eq!(roots[0].0, "package:npm:@tanstack/react-query");
+
+        // Short names are too ambiguous to match
+        assert!(packages_named_in("what uses ms", &nodes).is_empty());
+
+        let intent = detect_exhaustive_intent("list all dependencies");
+        assert!(intent.target_kinds.contains(&"package".to_string()));
+    }
+
+    #[test]
+    fn test_build_mention_context() {
+        let nodes = [
+            make_node("file:src/users.rs", "file", Some("src/users.rs")),
+            make_node("sym:rs:src/users.rs:function:create_user", "function", None),
+            KgNode {
+                label: "README.md".to_string(),
+                ..make_node("doc:README.md", "doc", Some("README.md"))
+            },
+        ];
+        let mut mention = make_edge(
+            "doc:README.md",
+            "sym:rs:src/users.rs:function:create_user",
+            "mentions",
+        );
+        mention.props = serde_json::json!({ "via": "code", "anchor": "creating-users" });
+        let edges = vec![
+            make_edge(
+                "file:src/users.rs",
+                "sym:rs:src/users.rs:function:create_user",
+                "defines",
+            ),
+            mention,
+        ];
+
+        let node_by_id: HashMap<String, &KgNode> =
+            nodes.iter().map(|n| (n.id.clone(), n)).collect();
+        let mut outgoing: HashMap<String, Vec<&KgEdge>> = HashMap::new();
+        let mut incoming: HashMap<String, Vec<&KgEdge>> = HashMap::new();
+        for edge in &edges {
+            outgoing.entry(edge.from.clone()).or_default().push(edge);
+            incoming.entry(edge.to.clone()).or_default().push(edge);
+        }
+
+        // Code file: docs mentioning a symbol it defines
+        let related =
+            build_mention_context("file:src/users.rs", &node_by_id, &outgoing, &incoming, 32)
+                .unwrap();
+        assert_eq!(
+            related.reason,
+            "Docs mentioning file:src/users.rs: README.md#creating-users"
+        );
+        assert_eq!(related.nodes.len(), 3);
+        assert_eq!(related.edges.len(), 1);
+
+        // Doc: the code it mentions
+        let related =
+            build_mention_context("doc:README.md", &node_by_id, &outgoing, &incoming, 32).unwrap();
+        assert_eq!(related.root_node_ids, vec!["doc:README.md"]);
+        assert_eq!(related.nodes.len(), 2);
+
+        // No mentions, no context
+        assert!(build_mention_context(
+            "sym:rs:src/users.rs:function:create_user",
+            &node_by_id,
+            &HashMap::new(),
+            &HashMap::new(),
+            32
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_build_test_context() {
+        let mut test_file = make_node("file:tests/users.rs", "file", Some("tests/users.rs"));
+        test_file.props["isTest"] = serde_json::json!(true);
+        let nodes = [
+            make_node("file:src/users.rs", "file", Some("src/users.rs")),
+            make_node("sym:rs:src/users.rs:function:create_user", "function", None),
+            test_file,
+        ];
+        let edges = vec![
+            make_edge(
+                "file:src/users.rs",
+                "sym:rs:src/users.rs:function:create_user",
+                "defines",
+            ),
+            make_edge(
+                "file:tests/users.rs",
+                "sym:rs:src/users.rs:function:create_user",
+                "tests",
+            ),
+            make_edge("file:tests/users.rs", "file:src/users.rs", "tests"),
+        ];
+
+        let node_by_id: HashMap<String, &KgNode> =
+            nodes.iter().map(|n| (n.id.clone(), n)).collect();
+        let mut outgoing: HashMap<String, Vec<&KgEdge>> = HashMap::new();
+        let mut incoming: HashMap<String, Vec<&KgEdge>> = HashMap::new();
+        for edge in &edges {
+            outgoing.entry(edge.from.clone()).or_default().push(edge);
+            incoming.entry(edge.to.clone()).or_default().push(edge);
+        }
+
+        // Code file: tests of the file and of its symbols, listed once
+        let related =
+            build_test_context("file:src/users.rs", &node_by_id, &outgoing, &incoming, 32).unwrap();
+        assert_eq!(
+            related.reason,
+            "Tests covering file:src/users.rs: tests/users.rs"
+        );
+        assert_eq!(related.nodes.len(), 3);
+        assert_eq!(related.edges.len(), 2);
+
+        // Test file: the code it exercises
+        let related =
+            build_test_context("file:tests/users.rs", &node_by_id, &outgoing, &incoming, 32)
+                .unwrap();
+        assert_eq!(
+            related.reason,
+            "Code tested by file:tests/users.rs: \
+             sym:rs:src/users.rs:function:create_user, src/users.rs"
+        );
+
+        assert!(build_test_context(
+            "file:src/users.rs",
+            &node_by_id,
+            &HashMap::new(),
+            &HashMap::new(),
+            32
+        )
+        .is_none());
+    }
+
     #[test]
     fn test_looks_like_endpoint_question() {
         assert!(looks_like_endpoint_question("What API endpoints exist?"));
@@ -1235,4 +1730,72 @@ mod tests {
         // Should include edges connecting to/from the endpoints
         assert_eq!(result.edges.len(), 2);
     }
+
+    #[test]
+    fn test_detect_exhaustive_intent_returns_filter() {
+        let intent = detect_exhaustive_intent("List all RPCs that return User");
+        assert!(intent.is_exhaustive);
+        assert_eq!(intent.target_kinds, vec!["operation"]);
+        assert_eq!(intent.returns_filter, Some("User".to_string()));
+        assert!(intent.http_method_filter.is_none());
+
+        let intent = detect_exhaustive_intent("Show all mutations returning an Order");
+        assert_eq!(intent.target_kinds, vec!["operation"]);
+        assert_eq!(intent.returns_filter, Some("Order".to_string()));
+
+        let intent = detect_exhaustive_intent("List all messages");
+        assert_eq!(intent.target_kinds, vec!["message"]);
+        assert!(intent.returns_filter.is_none());
+    }
+
+    #[test]
+    fn test_detect_exhaustive_intent_infrastructure() {
+        let intent = detect_exhaustive_intent("List all services and their images");
+        assert_eq!(intent.target_kinds, vec!["service", "image"]);
+
+        let intent = detect_exhaustive_intent("Show all Terraform resources");
+        assert_eq!(intent.target_kinds, vec!["resource"]);
+    }
+
+    #[test]
+    fn test_search_kg_exhaustive_operations_returning_type() {
+        let nodes = vec![
+            make_node("op:grpc:acme.Users/GetUser", "operation", None),
+            make_node("op:grpc:acme.Users/ListTeams", "operation", None),
+            make_node("op:graphql:Query.me", "operation", None),
+            KgNode {
+                label: "User".to_string(),
+                ..make_node("type:proto:acme.User", "message", None)
+            },
+            KgNode {
+                label: "Team".to_string(),
+                ..make_node("type:proto:acme.Team", "message", None)
+            },
+        ];
+        let edges = vec![
+            make_edge(
+                "op:grpc:acme.Users/GetUser",
+                "type:proto:acme.User",
+                "returns",
+            ),
+            make_edge(
+                "op:grpc:acme.Users/ListTeams",
+                "type:proto:acme.Team",
+                "returns",
+            ),
+            // Type defined outside the graph: matched by id
+            make_edge("op:graphql:Query.me", "type:graphql:User", "returns"),
+        ];
+
+        let intent = detect_exhaustive_intent("List all RPCs that return User");
+        let result = search_kg_exhaustive(&nodes, &edges, &intent, 100).unwrap();
+
+        let mut roots = result.root_node_ids.clone();
+        roots.sort();
+        assert_eq!(
+            roots,
+            vec!["op:graphql:Query.me", "op:grpc:acme.Users/GetUser"]
+        );
+        assert!(result.reason.contains("returning User"));
+    }
 }
@@ -0,0 +1,888 @@
+//! Architecture rule checks over the KG import graph.
+//!
+//! `gik kg check` loads `.guided/knowledge/rules.yaml` (if present) and
+//! checks the `imports` edges between `file` nodes against it:
+//!
+//! | Rule | Default | Description |
+//! |------|---------|-------------|
+//! | `cycles` | on, error | Strongly connected components of the import graph, per file or per directory |
+//! | `forbidden` | none | `from` → `to` glob pairs that must not import each other |
+//! | `orphans` | on, warning | Files no other file imports (entry points and tests are ignored) |
+//!
+//! Example rules file:
+//!
+//! ```yaml
+//! cycles:
+//!   granularity: directory
+//! forbidden:
+//!   - name: domain-is-pure
+//!     from: "src/domain/**"
+//!     to: "src/infra/**"
+//!     reason: Domain code must not depend on infrastructure
+//! orphans:
+//!   ignore:
+//!     - "tools/**"
+//! ```
+//!
+//! Globs match workspace-relative paths: `*` stays within a path segment,
+//! `**` crosses segments, and a pattern without wildcards matches the path
+//! itself or anything below it.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::entities::{KgEdge, KgNode};
+use crate::errors::GikError;
+use crate::memory::{MemoryEntry, MemoryScope, MemorySource};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Rules file name under `.guided/knowledge/`.
+pub const KG_RULES_FILENAME: &str = "rules.yaml";
+
+/// Files never reported as orphans: entry points, tests, and tooling.
+const DEFAULT_ORPHAN_IGNORES: &[&str] = &[
+    "**/main.*",
+    "**/index.*",
+    "**/lib.rs",
+    "**/mod.rs",
+    "**/build.rs",
+    "**/__init__.py",
+    "**/__main__.py",
+    "**/setup.py",
+    "**/conftest.py",
+    "**/manage.py",
+    "**/*.d.ts",
+    "**/*.config.*",
+    "**/*_test.*",
+    "**/*.test.*",
+    "**/*.spec.*",
+    "**/test_*",
+    "**/test/**",
+    "**/tests/**",
+    "**/__tests__/**",
+    "**/spec/**",
+    "**/examples/**",
+    "**/benches/**",
+    "**/bin/**",
+    "**/scripts/**",
+    "**/migrations/**",
+];
+
+// ============================================================================
+// Rules File
+// ============================================================================
+
+/// Severity of a rule violation. Only errors fail `gik kg check` unless
+/// `--strict` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    #[default]
+    Error,
+    Warning,
+}
+
+/// Node granularity for cycle detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CycleGranularity {
+    /// Cycles between individual files.
+    #[default]
+    File,
+    /// Cycles between directories (files collapsed into their parent).
+    Directory,
+}
+
+/// Contents of `.guided/knowledge/rules.yaml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct KgRules {
+    /// Import cycle detection.
+    #[serde(default)]
+    pub cycles: CycleRule,
+    /// Forbidden dependency directions.
+    #[serde(default)]
+    pub forbidden: Vec<ForbiddenDependency>,
+    /// Unreferenced file detection.
+    #[serde(default)]
+    pub orphans: OrphanRule,
+}
+
+/// Import cycle rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CycleRule {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub granularity: CycleGranularity,
+    #[serde(default)]
+    pub severity: RuleSeverity,
+}
+
+impl Default for CycleRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            granularity: CycleGranularity::default(),
+            severity: RuleSeverity::Error,
+        }
+    }
+}
+
+/// A dependency direction that must not appear in the import graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ForbiddenDependency {
+    /// Optional rule name, shown in reports.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Glob for importing files.
+    pub from: String,
+    /// Glob for imported files.
+    pub to: String,
+    /// Why the dependency is forbidden.
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub severity: RuleSeverity,
+}
+
+/// Unreferenced file rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct OrphanRule {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Extra globs to skip, on top of the built-in entry point and test globs.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    #[serde(default = "default_warning")]
+    pub severity: RuleSeverity,
+}
+
+impl Default for OrphanRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore: Vec::new(),
+            severity: RuleSeverity::Warning,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_warning() -> RuleSeverity {
+    RuleSeverity::Warning
+}
+
+impl KgRules {
+    /// Load rules from a YAML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GikError::KgRulesInvalid`] if the file cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self, GikError> {
+        let invalid = |reason: String| GikError::KgRulesInvalid {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let content = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        let rules: KgRules = serde_yaml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+        for rule in &rules.forbidden {
+            if rule.from.trim().is_empty() || rule.to.trim().is_empty() {
+                return Err(invalid(
+                    "forbidden rules need non-empty 'from' and 'to' globs".to_string(),
+                ));
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Load `rules.yaml` from a knowledge root, falling back to the defaults.
+    ///
+    /// Returns the rules and the path they were loaded from (None for defaults).
+    pub fn load_or_default(knowledge_root: &Path) -> Result<(Self, Option<PathBuf>), GikError> {
+        let path = knowledge_root.join(KG_RULES_FILENAME);
+        if path.exists() {
+            Ok((Self::load(&path)?, Some(path)))
+        } else {
+            Ok((Self::default(), None))
+        }
+    }
+}
+
+// ============================================================================
+// Report Types
+// ============================================================================
+
+/// Which rule a violation comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KgRuleKind {
+    Cycle,
+    Forbidden,
+    Orphan,
+}
+
+impl std::fmt::Display for KgRuleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KgRuleKind::Cycle => write!(f, "cycle"),
+            KgRuleKind::Forbidden => write!(f, "forbidden"),
+            KgRuleKind::Orphan => write!(f, "orphan"),
+        }
+    }
+}
+
+/// A single rule violation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KgViolation {
+    pub rule: KgRuleKind,
+    pub severity: RuleSeverity,
+    /// Name of the forbidden rule, if it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub message: String,
+    /// Files (or directories, for directory cycles) involved.
+    pub paths: Vec<String>,
+}
+
+impl KgViolation {
+    /// Convert the violation to an `Observation` memory entry.
+    ///
+    /// IDs are content-addressed, so recording the same violation twice is a
+    /// no-op.
+    pub fn to_memory_entry(&self, branch: &str) -> MemoryEntry {
+        let mut text = self.message.clone();
+        if !self.paths.is_empty() {
+            text.push_str("\n\nFiles: ");
+            text.push_str(&self.paths.join(", "));
+        }
+        let title = match &self.name {
+            Some(name) => format!("KG check: {} ({})", self.rule, name),
+            None => format!("KG check: {}", self.rule),
+        };
+
+        MemoryEntry::new_dedup(MemoryScope::Project, MemorySource::Observation, text)
+            .with_title(title)
+            .with_tags(vec!["kg-check".to_string(), self.rule.to_string()])
+            .with_extra(serde_json::json!({
+                "kgRule": self.rule,
+                "severity": self.severity,
+                "branch": branch,
+                "paths": self.paths,
+            }))
+    }
+}
+
+/// Result of `gik kg check`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KgCheckReport {
+    pub branch: String,
+    /// Rules file used (None when running with the defaults).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules_file: Option<PathBuf>,
+    /// Number of file nodes in the import graph.
+    pub files_checked: usize,
+    /// Number of distinct file→file import edges.
+    pub import_edges: usize,
+    pub violations: Vec<KgViolation>,
+    pub errors: usize,
+    pub warnings: usize,
+    /// Revision created when violations were recorded as memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision_id: Option<String>,
+}
+
+impl KgCheckReport {
+    /// Whether the check passes (warnings fail only in strict mode).
+    pub fn passed(&self, strict: bool) -> bool {
+        self.errors == 0 && (!strict || self.warnings == 0)
+    }
+}
+
+/// Options for `gik kg check`.
+#[derive(Debug, Clone, Default)]
+pub struct KgCheckOptions {
+    /// Record violations as `Observation` memory entries.
+    pub record: bool,
+}
+
+// ============================================================================
+// Checks
+// ============================================================================
+
+/// File-level import graph: sorted paths and deduplicated edges.
+struct ImportGraph {
+    paths: Vec<String>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl ImportGraph {
+    fn build(nodes: &[KgNode], edges: &[KgEdge]) -> Self {
+        let mut by_id: BTreeMap<&str, &str> = BTreeMap::new();
+        for node in nodes.iter().filter(|n| n.kind == "file") {
+            let path = node.props["path"].as_str().unwrap_or(&node.label);
+            by_id.insert(node.id.as_str(), path);
+        }
+
+        let mut paths: Vec<String> = by_id.values().map(|p| p.to_string()).collect();
+        paths.sort();
+        paths.dedup();
+        let index: HashMap<&str, usize> = paths
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.as_str(), i))
+            .collect();
+
+        let mut seen = BTreeSet::new();
+        for edge in edges.iter().filter(|e| e.kind == "imports") {
+            let (Some(from), Some(to)) =
+                (by_id.get(edge.from.as_str()), by_id.get(edge.to.as_str()))
+            else {
+                continue;
+            };
+            let (from, to) = (index[from], index[to]);
+            if from != to {
+                seen.insert((from, to));
+            }
+        }
+
+        Self {
+            paths,
+            edges: seen.into_iter().collect(),
+        }
+    }
+}
+
+/// Check the KG import graph against `rules`.
+///
+/// The returned report has no `rules_file` or `revision_id`; callers fill
+/// those in.
+pub fn check_rules(
+    branch: &str,
+    nodes: &[KgNode],
+    edges: &[KgEdge],
+    rules: &KgRules,
+) -> KgCheckReport {
+    let graph = ImportGraph::build(nodes, edges);
+    let mut violations = Vec::new();
+
+    if rules.cycles.enabled {
+        violations.extend(find_cycles(&graph, &rules.cycles));
+    }
+    for rule in &rules.forbidden {
+        violations.extend(find_forbidden(&graph, rule));
+    }
+    if rules.orphans.enabled {
+        violations.extend(find_orphans(&graph, &rules.orphans));
+    }
+
+    let errors = violations
+        .iter()
+        .filter(|v| v.severity == RuleSeverity::Error)
+        .count();
+    KgCheckReport {
+        branch: branch.to_string(),
+        rules_file: None,
+        files_checked: graph.paths.len(),
+        import_edges: graph.edges.len(),
+        warnings: violations.len() - errors,
+        errors,
+        violations,
+        revision_id: None,
+    }
+}
+
+/// Report each strongly connected component with more than one member.
+fn find_cycles(graph: &ImportGraph, rule: &CycleRule) -> Vec<KgViolation> {
+    // Collapse files into directories when checking directory cycles
+    let (labels, edges): (Vec<String>, Vec<(usize, usize)>) = match rule.granularity {
+        CycleGranularity::File => (graph.paths.clone(), graph.edges.clone()),
+        CycleGranularity::Directory => {
+            let dirs: Vec<String> = graph.paths.iter().map(|p| parent_dir(p)).collect();
+            let mut labels: Vec<String> = dirs.clone();
+            labels.sort();
+            labels.dedup();
+            let index: HashMap<&str, usize> = labels
+                .iter()
+                .enumerate()
+                .map(|(i, d)| (d.as_str(), i))
+                .collect();
+            let edges: BTreeSet<(usize, usize)> = graph
+                .edges
+                .iter()
+                .map(|&(from, to)| (index[dirs[from].as_str()], index[dirs[to].as_str()]))
+                .filter(|(from, to)| from != to)
+                .collect();
+            (labels, edges.into_iter().collect())
+        }
+    };
+
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); labels.len()];
+    for &(from, to) in &edges {
+        outgoing[from].push(to);
+    }
+
+    let unit = match rule.granularity {
+        CycleGranularity::File => "files",
+        CycleGranularity::Directory => "directories",
+    };
+    strongly_connected_components(&outgoing)
+        .into_iter()
+        .filter(|scc| scc.len() > 1)
+        .map(|scc| {
+            let cycle = shortest_cycle(&outgoing, &scc);
+            let path: Vec<&str> = cycle.iter().map(|&i| labels[i].as_str()).collect();
+            KgViolation {
+                rule: KgRuleKind::Cycle,
+                severity: rule.severity,
+                name: None,
+                message: format!(
+                    "Import cycle between {} {}: {}",
+                    scc.len(),
+                    unit,
+                    path.join(" -> ")
+                ),
+                paths: scc.iter().map(|&i| labels[i].clone()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Tarjan's algorithm (iterative). Components and their members are sorted
+/// by index so reports are stable.
+fn strongly_connected_components(outgoing: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = outgoing.len();
+    let mut index = vec![usize::MAX; n];
+    let mut lowlink = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut components = Vec::new();
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        // (node, next child position)
+        let mut work = vec![(root, 0)];
+        index[root] = next_index;
+        lowlink[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&mut (v, ref mut child)) = work.last_mut() {
+            if let Some(&w) = outgoing[v].get(*child) {
+                *child += 1;
+                if index[w] == usize::MAX {
+                    index[w] = next_index;
+                    lowlink[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    work.push((w, 0));
+                } else if on_stack[w] {
+                    lowlink[v] = lowlink[v].min(index[w]);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[v]);
+            }
+            if lowlink[v] == index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                component.sort();
+                components.push(component);
+            }
+        }
+    }
+
+    components.sort();
+    components
+}
+
+/// Shortest cycle through the first member of `scc`, staying inside it.
+/// The first node is repeated at the end.
+fn shortest_cycle(outgoing: &[Vec<usize>], scc: &[usize]) -> Vec<usize> {
+    let members: HashSet<usize> = scc.iter().copied().collect();
+    let start = scc[0];
+    let mut parent: HashMap<usize, usize> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(v) = queue.pop_front() {
+        for &w in &outgoing[v] {
+            if !members.contains(&w) {
+                continue;
+            }
+            if w == start {
+                let mut cycle = vec![start];
+                let mut node = v;
+                while node != start {
+                    cycle.push(node);
+                    node = parent[&node];
+                }
+                cycle.push(start);
+                cycle.reverse();
+                return cycle;
+            }
+            if let std::collections::hash_map::Entry::Vacant(e) = parent.entry(w) {
+                e.insert(v);
+                queue.push_back(w);
+            }
+        }
+    }
+
+    // Unreachable for a real SCC; fall back to listing the members
+    scc.to_vec()
+}
+
+/// Report every import from a `from` file to a `to` file.
+fn find_forbidden(graph: &ImportGraph, rule: &ForbiddenDependency) -> Vec<KgViolation> {
+    let from_glob = Glob::new(&rule.from);
+    let to_glob = Glob::new(&rule.to);
+    let label = rule
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("{} -> {}", rule.from, rule.to));
+
+    graph
+        .edges
+        .iter()
+        .filter(|&&(from, to)| {
+            from_glob.is_match(&graph.paths[from]) && to_glob.is_match(&graph.paths[to])
+        })
+        .map(|&(from, to)| {
+            let (from, to) = (&graph.paths[from], &graph.paths[to]);
+            let mut message = format!("Forbidden import ({}): {} imports {}", label, from, to);
+            if let Some(reason) = &rule.reason {
+                message.push_str(&format!(". {}", reason));
+            }
+            KgViolation {
+                rule: KgRuleKind::Forbidden,
+                severity: rule.severity,
+                name: rule.name.clone(),
+                message,
+                paths: vec![from.clone(), to.clone()],
+            }
+        })
+        .collect()
+}
+
+/// Report files no other file imports.
+///
+/// Only files whose extension appears on an import edge are considered, so
+/// languages without import resolution are not flagged wholesale.
+fn find_orphans(graph: &ImportGraph, rule: &OrphanRule) -> Vec<KgViolation> {
+    if graph.edges.is_empty() {
+        return Vec::new();
+    }
+
+    let mut imported = vec![false; graph.paths.len()];
+    let mut extensions: HashSet<&str> = HashSet::new();
+    for &(from, to) in &graph.edges {
+        imported[to] = true;
+        extensions.insert(extension(&graph.paths[from]));
+        extensions.insert(extension(&graph.paths[to]));
+    }
+
+    let ignores: Vec<Glob> = DEFAULT_ORPHAN_IGNORES
+        .iter()
+        .copied()
+        .chain(rule.ignore.iter().map(String::as_str))
+        .map(Glob::new)
+        .collect();
+
+    graph
+        .paths
+        .iter()
+        .enumerate()
+        .filter(|&(i, path)| {
+            !imported[i]
+                && extensions.contains(extension(path))
+                && !ignores.iter().any(|g| g.is_match(path))
+        })
+        .map(|(_, path)| KgViolation {
+            rule: KgRuleKind::Orphan,
+            severity: rule.severity,
+            name: None,
+            message: format!("{} is not imported by any file", path),
+            paths: vec![path.clone()],
+        })
+        .collect()
+}
+
+fn parent_dir(path: &str) -> String {
+    match path.rfind('/') {
+        Some(pos) => path[..pos].to_string(),
+        None => ".".to_string(),
+    }
+}
+
+fn extension(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("")
+}
+
+// ============================================================================
+// Globs
+// ============================================================================
+
+/// A path glob compiled to an anchored regex.
+#[derive(Debug)]
+pub(crate) struct Glob(Regex);
+
+impl Glob {
+    pub(crate) fn new(pattern: &str) -> Self {
+        let pattern = pattern.trim().trim_start_matches("./");
+        let mut re = String::from("^");
+
+        if !pattern.contains(['*', '?']) {
+            // Plain paths match themselves and everything below them
+            re.push_str(&regex::escape(pattern.trim_end_matches('/')));
+            re.push_str("(?:/.*)?");
+        } else {
+            let mut chars = pattern.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '*' if chars.peek() == Some(&'*') => {
+                        chars.next();
+                        if chars.peek() == Some(&'/') {
+                            chars.next();
+                            re.push_str("(?:.*/)?");
+                        } else {
+                            re.push_str(".*");
+                        }
+                    }
+                    '*' => re.push_str("[^/]*"),
+                    '?' => re.push_str("[^/]"),
+                    c => re.push_str(&regex::escape(&c.to_string())),
+                }
+            }
+        }
+
+        re.push('$');
+        Self(Regex::new(&re).expect("Invalid regex"))
+    }
+
+    pub(crate) fn is_match(&self, path: &str) -> bool {
+        self.0.is_match(path)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn file(path: &str) -> KgNode {
+        KgNode::new(format!("file:{}", path), "file", path).with_props(json!({ "path": path }))
+    }
+
+    fn imports(from: &str, to: &str) -> KgEdge {
+        KgEdge::new(format!("file:{}", from), format!("file:{}", to), "imports")
+    }
+
+    fn graph() -> (Vec<KgNode>, Vec<KgEdge>) {
+        let nodes = vec![
+            file("src/main.ts"),
+            file("src/domain/order.ts"),
+            file("src/domain/price.ts"),
+            file("src/infra/db.ts"),
+            file("src/infra/repo.ts"),
+            file("src/util/unused.ts"),
+            file("src/domain/order.test.ts"),
+            file("README.md"),
+        ];
+        let edges = vec![
+            imports("src/main.ts", "src/domain/order.ts"),
+            imports("src/domain/order.ts", "src/domain/price.ts"),
+            imports("src/domain/price.ts", "src/domain/order.ts"),
+            imports("src/domain/order.ts", "src/infra/repo.ts"),
+            imports("src/infra/repo.ts", "src/infra/db.ts"),
+            imports("src/infra/db.ts", "src/domain/price.ts"),
+            imports("src/domain/order.test.ts", "src/domain/order.ts"),
+        ];
+        (nodes, edges)
+    }
+
+    #[test]
+    fn test_glob_matching() {
+        assert!(Glob::new("src/domain/**").is_match("src/domain/a/b.ts"));
+        assert!(!Glob::new("src/domain/**").is_match("src/domainx/b.ts"));
+        assert!(Glob::new("**/tests/**").is_match("tests/a.rs"));
+        assert!(Glob::new("**/tests/**").is_match("crates/x/tests/a.rs"));
+        assert!(Glob::new("src/*.ts").is_match("src/a.ts"));
+        assert!(!Glob::new("src/*.ts").is_match("src/a/b.ts"));
+        assert!(Glob::new("src/infra").is_match("src/infra/db.ts"));
+        assert!(Glob::new("./src/infra/").is_match("src/infra"));
+        assert!(!Glob::new("src/infra").is_match("src/infrastructure/db.ts"));
+    }
+
+    #[test]
+    fn test_check_rules_finds_cycles_forbidden_and_orphans() {
+        let (nodes, edges) = graph();
+        let rules = KgRules {
+            forbidden: vec![ForbiddenDependency {
+                name: Some("domain-is-pure".to_string()),
+                from: "src/domain/**".to_string(),
+                to: "src/infra/**".to_string(),
+                reason: Some("Use ports instead".to_string()),
+                severity: RuleSeverity::Error,
+            }],
+            ..Default::default()
+        };
+
+        let report = check_rules("main", &nodes, &edges, &rules);
+        assert_eq!(report.files_checked, 8);
+        assert_eq!(report.import_edges, 7);
+
+        // order -> price -> order and order -> repo -> db -> price -> order
+        // form one component
+        let cycles: Vec<_> = report
+            .violations
+            .iter()
+            .filter(|v| v.rule == KgRuleKind::Cycle)
+            .collect();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].paths.len(), 4);
+        assert!(cycles[0]
+            .message
+            .ends_with("src/domain/order.ts -> src/domain/price.ts -> src/domain/order.ts"));
+
+        let forbidden: Vec<_> = report
+            .violations
+            .iter()
+            .filter(|v| v.rule == KgRuleKind::Forbidden)
+            .collect();
+        assert_eq!(forbidden.len(), 1);
+        assert_eq!(
+            forbidden[0].paths,
+            vec!["src/domain/order.ts", "src/infra/repo.ts"]
+        );
+        assert!(forbidden[0].message.contains("Use ports instead"));
+
+        // main.ts and the test file are ignored, README.md is not a .ts file
+        let orphans: Vec<_> = report
+            .violations
+            .iter()
+            .filter(|v| v.rule == KgRuleKind::Orphan)
+            .collect();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].paths, vec!["src/util/unused.ts"]);
+        assert_eq!(orphans[0].severity, RuleSeverity::Warning);
+
+        assert_eq!(report.errors, 2);
+        assert_eq!(report.warnings, 1);
+        assert!(!report.passed(false));
+    }
+
+    #[test]
+    fn test_directory_cycles_and_disabled_rules() {
+        let (nodes, edges) = graph();
+        let rules = KgRules {
+            cycles: CycleRule {
+                granularity: CycleGranularity::Directory,
+                ..Default::default()
+            },
+            orphans: OrphanRule {
+                ignore: vec!["src/util".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let report = check_rules("main", &nodes, &edges, &rules);
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].paths, vec!["src/domain", "src/infra"]);
+        assert!(report.violations[0]
+            .message
+            .contains("src/domain -> src/infra -> src/domain"));
+
+        let rules = KgRules {
+            cycles: CycleRule {
+                enabled: false,
+                ..Default::default()
+            },
+            orphans: OrphanRule {
+                enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let report = check_rules("main", &nodes, &edges, &rules);
+        assert!(report.violations.is_empty());
+        assert!(report.passed(true));
+    }
+
+    #[test]
+    fn test_load_rules_file() {
+        let temp = TempDir::new().unwrap();
+        let (rules, path) = KgRules::load_or_default(temp.path()).unwrap();
+        assert!(path.is_none());
+        assert!(rules.cycles.enabled);
+        assert_eq!(rules.orphans.severity, RuleSeverity::Warning);
+
+        std::fs::write(
+            temp.path().join(KG_RULES_FILENAME),
+            "cycles:\n  granularity: directory\nforbidden:\n  - from: \"domain/**\"\n    to: \"infra/**\"\n    severity: warning\norphans:\n  enabled: false\n",
+        )
+        .unwrap();
+        let (rules, path) = KgRules::load_or_default(temp.path()).unwrap();
+        assert!(path.is_some());
+        assert_eq!(rules.cycles.granularity, CycleGranularity::Directory);
+        assert_eq!(rules.forbidden[0].severity, RuleSeverity::Warning);
+        assert!(!rules.orphans.enabled);
+
+        std::fs::write(temp.path().join(KG_RULES_FILENAME), "forbiden: []\n").unwrap();
+        let err = KgRules::load_or_default(temp.path()).unwrap_err();
+        assert!(matches!(err, GikError::KgRulesInvalid { .. }));
+    }
+
+    #[test]
+    fn test_violation_to_memory_entry_is_stable() {
+        let violation = KgViolation {
+            rule: KgRuleKind::Forbidden,
+            severity: RuleSeverity::Error,
+            name: Some("domain-is-pure".to_string()),
+            message: "Forbidden import".to_string(),
+            paths: vec!["a.ts".to_string(), "b.ts".to_string()],
+        };
+        let a = violation.to_memory_entry("main");
+        let b = violation.to_memory_entry("main");
+        assert_eq!(a.id, b.id);
+        assert_eq!(a.source, MemorySource::Observation);
+        assert_eq!(
+            a.title.as_deref(),
+            Some("KG check: forbidden (domain-is-pure)")
+        );
+        assert!(a.tags.contains(&"kg-check".to_string()));
+    }
+}
@@ -0,0 +1,1192 @@
+//! API schema extraction for the KG (OpenAPI, GraphQL, Protobuf).
+//!
+//! Service contracts are indexed into the `docs` base as plain text. This
+//! module parses them into graph structure:
+//!
+//! | Schema | Files | Operations | Types |
+//! |--------|-------|------------|-------|
+//! | OpenAPI / Swagger | `*.yaml`, `*.yml`, `*.json` with a top-level `openapi`/`swagger` key | `paths.<route>.<method>` | `components.schemas`, `definitions` |
+//! | GraphQL | `*.graphql`, `*.graphqls`, `*.gql` | Fields of `Query`, `Mutation`, `Subscription` | `type`, `input`, `interface`, `enum`, `union` |
+//! | Protobuf | `*.proto` | `rpc` in `service` blocks | `message`, `enum` |
+//!
+//! Nodes are `operation`, `type`, `message` and `field`. Edges:
+//! - `definesEndpoint`: schema file → operation
+//! - `contains`: type/message → field
+//! - `references`: field → type, operation → input type
+//! - `returns`: operation → result type
+//!
+//! Operations are linked to implementing code in [`link_schema_operations`],
+//! which runs once all bases have been extracted.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::json;
+
+use super::entities::{KgEdge, KgNode};
+use super::text::{line_at, matching_brace};
+
+// ============================================================================
+// SchemaGraph
+// ============================================================================
+
+/// Nodes and edges extracted from one schema file.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaGraph {
+    /// Operation, type, message and field nodes.
+    pub nodes: Vec<KgNode>,
+    /// `definesEndpoint`, `contains`, `references` and `returns` edges.
+    pub edges: Vec<KgEdge>,
+}
+
+impl SchemaGraph {
+    fn node(&mut self, id: &str, kind: &str, label: &str, props: serde_json::Value) {
+        self.nodes
+            .push(KgNode::new(id, kind, label).with_props(props));
+    }
+
+    fn edge(&mut self, from: &str, to: &str, kind: &str) {
+        self.edges.push(KgEdge::new(from, to, kind));
+    }
+}
+
+/// Extract schema structure from a file, if it is an API schema.
+///
+/// `file_node_id` is the node that `definesEndpoint` edges start from.
+pub fn extract_schema(file_path: &str, file_node_id: &str, text: &str) -> Option<SchemaGraph> {
+    let ext = file_path
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "proto" => Some(extract_proto(file_path, file_node_id, text)),
+        "graphql" | "graphqls" | "gql" => Some(extract_graphql(file_path, file_node_id, text)),
+        "yaml" | "yml" | "json" => extract_openapi(file_path, file_node_id, text),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// OpenAPI
+// ============================================================================
+
+const OPENAPI_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Extract operations and component schemas from an OpenAPI/Swagger document.
+///
+/// Returns `None` for YAML/JSON files that aren't OpenAPI documents.
+fn extract_openapi(file_path: &str, file_node_id: &str, text: &str) -> Option<SchemaGraph> {
+    // Cheap check before parsing arbitrary config files
+    if !text.contains("openapi") && !text.contains("swagger") {
+        return None;
+    }
+    let doc: serde_json::Value = serde_yaml::from_str(text).ok()?;
+    if doc.get("openapi").is_none() && doc.get("swagger").is_none() {
+        return None;
+    }
+
+    let mut graph = SchemaGraph::default();
+    let type_id = |name: &str| format!("type:openapi:{}:{}", file_path, name);
+
+    // Component schemas (OpenAPI 3) and definitions (Swagger 2)
+    let schemas = doc
+        .pointer("/components/schemas")
+        .or_else(|| doc.get("definitions"))
+        .and_then(|s| s.as_object());
+    for (name, schema) in schemas.into_iter().flatten() {
+        let id = type_id(name);
+        graph.node(
+            &id,
+            "type",
+            name,
+            json!({
+                "schema": "openapi",
+                "path": file_path,
+                "name": name,
+                "description": schema.get("description"),
+            }),
+        );
+
+        let required: HashSet<&str> = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        for (field, field_schema) in properties.into_iter().flatten() {
+            let field_id = format!("{}.{}", id, field);
+            graph.node(
+                &field_id,
+                "field",
+                field,
+                json!({
+                    "schema": "openapi",
+                    "path": file_path,
+                    "type": openapi_type_name(field_schema),
+                    "required": required.contains(field.as_str()),
+                }),
+            );
+            graph.edge(&id, &field_id, "contains");
+            for target in openapi_refs(field_schema) {
+                graph.edge(&field_id, &type_id(&target), "references");
+            }
+        }
+
+        // Composition: allOf / oneOf / anyOf
+        for key in ["allOf", "oneOf", "anyOf"] {
+            for part in schema
+                .get(key)
+                .and_then(|p| p.as_array())
+                .into_iter()
+                .flatten()
+            {
+                if let Some(target) = ref_name(part) {
+                    graph.edge(&id, &type_id(&target), "references");
+                }
+            }
+        }
+    }
+
+    // Operations
+    let paths = doc.get("paths").and_then(|p| p.as_object());
+    for (route, item) in paths.into_iter().flatten() {
+        for method in OPENAPI_METHODS {
+            let Some(op) = item.get(*method) else {
+                continue;
+            };
+            let http_method = method.to_uppercase();
+            let operation_id = op.get("operationId").and_then(|v| v.as_str());
+            let label = operation_id
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{} {}", http_method, route));
+            let id = format!("op:openapi:{}:{} {}", file_path, http_method, route);
+            graph.node(
+                &id,
+                "operation",
+                &label,
+                json!({
+                    "schema": "openapi",
+                    "path": file_path,
+                    "name": operation_id,
+                    "operationType": "http",
+                    "httpMethod": http_method,
+                    "route": route,
+                    "summary": op.get("summary"),
+                    "tags": op.get("tags"),
+                }),
+            );
+            graph.edge(file_node_id, &id, "definesEndpoint");
+
+            // Inputs: request body and parameters
+            let mut inputs = Vec::new();
+            let request = op
+                .pointer("/requestBody/content")
+                .and_then(|c| c.as_object());
+            for media in request.into_iter().flat_map(|c| c.values()) {
+                inputs.extend(media.get("schema").map(openapi_refs).unwrap_or_default());
+            }
+            for param in op
+                .get("parameters")
+                .and_then(|p| p.as_array())
+                .into_iter()
+                .flatten()
+            {
+                inputs.extend(param.get("schema").map(openapi_refs).unwrap_or_default());
+            }
+            for target in dedup(inputs) {
+                graph.edge(&id, &type_id(&target), "references");
+            }
+
+            // Outputs: 2xx responses (OpenAPI 3 content, Swagger 2 schema)
+            let mut outputs = Vec::new();
+            let responses = op.get("responses").and_then(|r| r.as_object());
+            for (status, response) in responses.into_iter().flatten() {
+                if !status.starts_with('2') && status != "default" {
+                    continue;
+                }
+                if let Some(schema) = response.get("schema") {
+                    outputs.extend(openapi_refs(schema));
+                }
+                let content = response.get("content").and_then(|c| c.as_object());
+                for media in content.into_iter().flat_map(|c| c.values()) {
+                    outputs.extend(media.get("schema").map(openapi_refs).unwrap_or_default());
+                }
+            }
+            for target in dedup(outputs) {
+                graph.edge(&id, &type_id(&target), "returns");
+            }
+        }
+    }
+
+    Some(graph)
+}
+
+/// Name of the schema a `$ref` points to (`#/components/schemas/User` → `User`).
+fn ref_name(schema: &serde_json::Value) -> Option<String> {
+    let reference = schema.get("$ref")?.as_str()?;
+    reference.rsplit('/').next().map(str::to_string)
+}
+
+/// Schemas referenced directly or through array items.
+fn openapi_refs(schema: &serde_json::Value) -> Vec<String> {
+    if let Some(name) = ref_name(schema) {
+        return vec![name];
+    }
+    let mut refs = Vec::new();
+    if let Some(items) = schema.get("items") {
+        refs.extend(openapi_refs(items));
+    }
+    for key in ["allOf", "oneOf", "anyOf"] {
+        for part in schema
+            .get(key)
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+        {
+            refs.extend(openapi_refs(part));
+        }
+    }
+    refs
+}
+
+/// Display type of a property schema (`string`, `User`, `array<User>`).
+fn openapi_type_name(schema: &serde_json::Value) -> String {
+    if let Some(name) = ref_name(schema) {
+        return name;
+    }
+    match schema.get("type").and_then(|t| t.as_str()) {
+        Some("array") => format!(
+            "array<{}>",
+            schema
+                .get("items")
+                .map(openapi_type_name)
+                .unwrap_or_else(|| "any".to_string())
+        ),
+        Some(t) => t.to_string(),
+        None => "object".to_string(),
+    }
+}
+
+// ============================================================================
+// GraphQL
+// ============================================================================
+
+const GRAPHQL_SCALARS: &[&str] = &["ID", "String", "Int", "Float", "Boolean"];
+
+/// Extract types and root operations from a GraphQL SDL file.
+fn extract_graphql(file_path: &str, file_node_id: &str, text: &str) -> SchemaGraph {
+    let source = strip_comments(text, "#", None);
+    let mut graph = SchemaGraph::default();
+    let type_id = |name: &str| format!("type:graphql:{}", name);
+
+    // schema { query: RootQuery } renames the root types
+    let mut roots: HashMap<String, &str> = [
+        ("Query".to_string(), "query"),
+        ("Mutation".to_string(), "mutation"),
+        ("Subscription".to_string(), "subscription"),
+    ]
+    .into_iter()
+    .collect();
+    let schema_re =
+        regex::Regex::new(r"\bschema\s*(?:@\w+\s*)*\{([^}]*)\}").expect("Invalid regex");
+    let root_re =
+        regex::Regex::new(r"(query|mutation|subscription)\s*:\s*(\w+)").expect("Invalid regex");
+    if let Some(schema) = schema_re.captures(&source) {
+        roots.clear();
+        for cap in root_re.captures_iter(&schema[1]) {
+            let kind = match &cap[1] {
+                "query" => "query",
+                "mutation" => "mutation",
+                _ => "subscription",
+            };
+            roots.insert(cap[2].to_string(), kind);
+        }
+    }
+
+    let def_re = regex::Regex::new(
+        r"(?m)^\s*(extend\s+)?(type|input|interface|enum|union|scalar)\s+(\w+)([^{=\n]*)(\{|=)?",
+    )
+    .expect("Invalid regex");
+    let field_re = regex::Regex::new(r"(?m)^\s*(\w+)\s*(\([^)]*\))?\s*:\s*([\[\]\w!]+)")
+        .expect("Invalid regex");
+    let arg_re = regex::Regex::new(r"(\w+)\s*:\s*([\[\]\w!]+)").expect("Invalid regex");
+
+    let mut defined: HashSet<String> = HashSet::new();
+    for cap in def_re.captures_iter(&source) {
+        let whole = cap.get(0).expect("match");
+        let keyword = &cap[2];
+        let name = &cap[3];
+        let line = line_at(
+            &source,
+            whole.start() + whole.as_str().find(keyword).unwrap_or(0),
+        );
+        let id = type_id(name);
+        let root = roots.get(name).copied();
+
+        if root.is_none() && defined.insert(name.to_string()) {
+            graph.node(
+                &id,
+                "type",
+                name,
+                json!({
+                    "schema": "graphql",
+                    "path": file_path,
+                    "name": name,
+                    "typeKind": keyword,
+                    "line": line,
+                }),
+            );
+        }
+
+        match cap.get(5).map(|m| m.as_str()) {
+            // union SearchResult = User | Post
+            Some("=") => {
+                let rest = &source[whole.end()..];
+                let members = rest.lines().next().unwrap_or("");
+                for member in members.split('|').map(str::trim).filter(|m| !m.is_empty()) {
+                    graph.edge(&id, &type_id(member), "references");
+                }
+            }
+            Some("{") => {
+                let body_end = source[whole.end()..]
+                    .find('}')
+                    .map(|i| whole.end() + i)
+                    .unwrap_or(source.len());
+                let body = &source[whole.end()..body_end];
+
+                if keyword == "enum" {
+                    let values: Vec<&str> = body.split_whitespace().collect();
+                    if let Some(node) = graph.nodes.iter_mut().find(|n| n.id == id) {
+                        node.props["values"] = json!(values);
+                    }
+                    continue;
+                }
+
+                for field in field_re.captures_iter(body) {
+                    let field_name = &field[1];
+                    let field_type = &field[3];
+                    let named = field_type.trim_matches(|c| c == '[' || c == ']' || c == '!');
+                    let field_line =
+                        line_at(&source, whole.end() + field.get(1).expect("name").start());
+
+                    if let Some(operation_type) = root {
+                        let op_id = format!("op:graphql:{}.{}", name, field_name);
+                        graph.node(
+                            &op_id,
+                            "operation",
+                            field_name,
+                            json!({
+                                "schema": "graphql",
+                                "path": file_path,
+                                "name": field_name,
+                                "operationType": operation_type,
+                                "returnType": field_type,
+                                "line": field_line,
+                            }),
+                        );
+                        graph.edge(file_node_id, &op_id, "definesEndpoint");
+                        if !GRAPHQL_SCALARS.contains(&named) {
+                            graph.edge(&op_id, &type_id(named), "returns");
+                        }
+                        for arg in field
+                            .get(2)
+                            .map(|a| arg_re.captures_iter(a.as_str()).collect::<Vec<_>>())
+                            .unwrap_or_default()
+                        {
+                            let arg_type =
+                                arg[2].trim_matches(|c| c == '[' || c == ']' || c == '!');
+                            if !GRAPHQL_SCALARS.contains(&arg_type) {
+                                graph.edge(&op_id, &type_id(arg_type), "references");
+                            }
+                        }
+                    } else {
+                        let field_id = format!("{}.{}", id, field_name);
+                        graph.node(
+                            &field_id,
+                            "field",
+                            field_name,
+                            json!({
+                                "schema": "graphql",
+                                "path": file_path,
+                                "type": field_type,
+                                "required": field_type.ends_with('!'),
+                                "line": field_line,
+                            }),
+                        );
+                        graph.edge(&id, &field_id, "contains");
+                        if !GRAPHQL_SCALARS.contains(&named) {
+                            graph.edge(&field_id, &type_id(named), "references");
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    graph
+}
+
+// ============================================================================
+// Protobuf
+// ============================================================================
+
+const PROTO_SCALARS: &[&str] = &[
+    "double", "float", "int32", "int64", "uint32", "uint64", "sint32", "sint64", "fixed32",
+    "fixed64", "sfixed32", "sfixed64", "bool", "string", "bytes",
+];
+
+/// Extract messages, enums and services from a `.proto` file.
+fn extract_proto(file_path: &str, file_node_id: &str, text: &str) -> SchemaGraph {
+    let source = strip_comments(text, "//", Some(("/*", "*/")));
+    let mut graph = SchemaGraph::default();
+
+    let package = regex::Regex::new(r"\bpackage\s+([\w.]+)\s*;")
+        .expect("Invalid regex")
+        .captures(&source)
+        .map(|c| c[1].to_string())
+        .unwrap_or_default();
+    let qualify = |name: &str| {
+        if package.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", package, name)
+        }
+    };
+
+    // First pass: every message and enum, with nesting, so references resolve
+    let block_re =
+        regex::Regex::new(r"\b(message|enum|service|oneof)\s+(\w+)\s*\{").expect("Invalid regex");
+    let mut blocks: Vec<(usize, usize, String, String)> = Vec::new(); // (start, end, keyword, scoped name)
+    let mut stack: Vec<(usize, String)> = Vec::new(); // (end, scoped name)
+    for cap in block_re.captures_iter(&source) {
+        let whole = cap.get(0).expect("match");
+        while stack.last().is_some_and(|(end, _)| *end < whole.start()) {
+            stack.pop();
+        }
+        let end = matching_brace(&source, whole.end() - 1).unwrap_or(source.len());
+        let scope = stack
+            .iter()
+            .rev()
+            .find(|(_, name)| !name.is_empty())
+            .map(|(_, name)| name.clone());
+        let keyword = cap[1].to_string();
+        // oneof blocks don't open a naming scope
+        let scoped = match (&scope, keyword.as_str()) {
+            (_, "oneof") => String::new(),
+            (Some(outer), _) => format!("{}.{}", outer, &cap[2]),
+            (None, _) => cap[2].to_string(),
+        };
+        stack.push((end, scoped.clone()));
+        blocks.push((whole.start(), end, keyword, scoped));
+    }
+    let known: HashSet<&str> = blocks
+        .iter()
+        .filter(|(_, _, kw, _)| kw == "message" || kw == "enum")
+        .map(|(_, _, _, name)| name.as_str())
+        .collect();
+
+    // Resolve a type reference from within `scope` to a fully qualified name
+    let resolve = |name: &str, scope: &str| -> Option<String> {
+        if PROTO_SCALARS.contains(&name) {
+            return None;
+        }
+        if let Some(absolute) = name.strip_prefix('.') {
+            return Some(absolute.to_string());
+        }
+        let mut scope = scope.to_string();
+        loop {
+            let candidate = if scope.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", scope, name)
+            };
+            if known.contains(candidate.as_str()) {
+                return Some(qualify(&candidate));
+            }
+            match scope.rsplit_once('.') {
+                Some((outer, _)) => scope = outer.to_string(),
+                None if !scope.is_empty() => scope.clear(),
+                None => break,
+            }
+        }
+        // Defined in another file: already qualified, or in this package
+        Some(if name.contains('.') {
+            name.to_string()
+        } else {
+            qualify(name)
+        })
+    };
+    let type_id = |qualified: &str| format!("type:proto:{}", qualified);
+
+    let field_re = regex::Regex::new(
+        r"(?m)^\s*(?:(repeated|optional|required)\s+)?(map\s*<\s*[\w.]+\s*,\s*[\w.]+\s*>|[\w.]+)\s+(\w+)\s*=\s*(\d+)",
+    )
+    .expect("Invalid regex");
+    let map_re =
+        regex::Regex::new(r"map\s*<\s*([\w.]+)\s*,\s*([\w.]+)\s*>").expect("Invalid regex");
+    let rpc_re = regex::Regex::new(
+        r"\brpc\s+(\w+)\s*\(\s*(stream\s+)?([\w.]+)\s*\)\s*returns\s*\(\s*(stream\s+)?([\w.]+)\s*\)",
+    )
+    .expect("Invalid regex");
+
+    for (start, end, keyword, scoped) in &blocks {
+        let line = line_at(&source, *start);
+        let body_start = source[*start..]
+            .find('{')
+            .map(|i| start + i + 1)
+            .unwrap_or(*start);
+        // Only the block's own lines, not nested messages
+        let nested: Vec<(usize, usize)> = blocks
+            .iter()
+            .filter(|(s, e, kw, _)| s > start && e <= end && kw != "oneof")
+            .map(|(s, e, _, _)| (*s, *e))
+            .collect();
+        let own = |offset: usize| !nested.iter().any(|(s, e)| offset >= *s && offset <= *e);
+
+        match keyword.as_str() {
+            "message" | "enum" => {
+                let id = type_id(&qualify(scoped));
+                let kind = if keyword == "message" {
+                    "message"
+                } else {
+                    "type"
+                };
+                graph.node(
+                    &id,
+                    kind,
+                    scoped,
+                    json!({
+                        "schema": "proto",
+                        "path": file_path,
+                        "name": scoped,
+                        "package": package,
+                        "typeKind": keyword,
+                        "line": line,
+                    }),
+                );
+                if keyword == "enum" {
+                    continue;
+                }
+                for field in field_re.captures_iter(&source[body_start..*end]) {
+                    let offset = body_start + field.get(3).expect("name").start();
+                    if !own(offset) || &field[2] == "option" || &field[2] == "reserved" {
+                        continue;
+                    }
+                    let field_id = format!("{}.{}", id, &field[3]);
+                    let field_type = &field[2];
+                    graph.node(
+                        &field_id,
+                        "field",
+                        &field[3],
+                        json!({
+                            "schema": "proto",
+                            "path": file_path,
+                            "type": field_type,
+                            "number": field[4].parse::<u32>().unwrap_or(0),
+                            "repeated": field.get(1).is_some_and(|m| m.as_str() == "repeated"),
+                            "line": line_at(&source, offset),
+                        }),
+                    );
+                    graph.edge(&id, &field_id, "contains");
+                    let referenced: Vec<&str> = match map_re.captures(field_type) {
+                        Some(map) => {
+                            vec![map.get(1).unwrap().as_str(), map.get(2).unwrap().as_str()]
+                        }
+                        None => vec![field_type],
+                    };
+                    for name in referenced {
+                        if let Some(target) = resolve(name, scoped) {
+                            graph.edge(&field_id, &type_id(&target), "references");
+                        }
+                    }
+                }
+            }
+            "service" => {
+                for rpc in rpc_re.captures_iter(&source[body_start..*end]) {
+                    let offset = body_start + rpc.get(0).expect("match").start();
+                    let method = &rpc[1];
+                    let service = qualify(scoped);
+                    let id = format!("op:grpc:{}/{}", service, method);
+                    graph.node(
+                        &id,
+                        "operation",
+                        method,
+                        json!({
+                            "schema": "proto",
+                            "path": file_path,
+                            "name": method,
+                            "service": service,
+                            "operationType": "rpc",
+                            "requestType": &rpc[3],
+                            "returnType": &rpc[5],
+                            "clientStreaming": rpc.get(2).is_some(),
+                            "serverStreaming": rpc.get(4).is_some(),
+                            "line": line_at(&source, offset),
+                        }),
+                    );
+                    graph.edge(file_node_id, &id, "definesEndpoint");
+                    if let Some(request) = resolve(&rpc[3], "") {
+                        graph.edge(&id, &type_id(&request), "references");
+                    }
+                    if let Some(response) = resolve(&rpc[5], "") {
+                        graph.edge(&id, &type_id(&response), "returns");
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    graph
+}
+
+// ============================================================================
+// Linking Operations to Code
+// ============================================================================
+
+/// Maximum symbols a single operation may link to by name.
+const MAX_NAME_MATCHES: usize = 5;
+
+/// Add `implementedBy` edges from schema operations to the code that serves them.
+///
+/// - Operations with an HTTP route (OpenAPI) match code endpoints with the same
+///   method and route pattern, and through their `handledBy` edges the handler
+///   symbols.
+/// - Any operation matches function/method symbols with the same name, ignoring
+///   case and separators (`GetUser`, `getUser`, `get_user`). GraphQL fields also
+///   match `resolve<Name>`. Generated stubs (`*.pb.go`, `*_pb2.py`, ...) are skipped.
+///
+/// Returns the edges to add.
+pub fn link_schema_operations(nodes: &[KgNode], edges: &[KgEdge]) -> Vec<KgEdge> {
+    let squash = |s: &str| -> String {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+
+    let mut symbols_by_name: HashMap<String, Vec<&KgNode>> = HashMap::new();
+    let mut endpoints_by_route: HashMap<(String, String), Vec<&str>> = HashMap::new();
+    for node in nodes {
+        let path = node.props["path"].as_str().unwrap_or("");
+        match node.kind.as_str() {
+            "function" | "method" if !is_generated(path) => {
+                symbols_by_name
+                    .entry(squash(&node.label))
+                    .or_default()
+                    .push(node);
+            }
+            "endpoint" => {
+                if let (Some(method), Some(route)) = (
+                    node.props["httpMethod"].as_str(),
+                    node.props["route"].as_str(),
+                ) {
+                    endpoints_by_route
+                        .entry((method.to_uppercase(), route_key(route)))
+                        .or_default()
+                        .push(&node.id);
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut handlers: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in edges.iter().filter(|e| e.kind == "handledBy") {
+        handlers.entry(&edge.from).or_default().push(&edge.to);
+    }
+
+    let mut links = Vec::new();
+    let mut seen: HashSet<(String, String)> = HashSet::new();
+    let mut link = |from: &str, to: &str, matched: &str| {
+        if seen.insert((from.to_string(), to.to_string())) {
+            links.push(KgEdge::new(from, to, "implementedBy").with_props(json!({
+                "match": matched,
+            })));
+        }
+    };
+
+    for op in nodes.iter().filter(|n| n.kind == "operation") {
+        if let (Some(method), Some(route)) =
+            (op.props["httpMethod"].as_str(), op.props["route"].as_str())
+        {
+            for key in [
+                (method.to_uppercase(), route_key(route)),
+                ("ANY".to_string(), route_key(route)),
+            ] {
+                for endpoint in endpoints_by_route.get(&key).into_iter().flatten() {
+                    link(&op.id, endpoint, "route");
+                    for handler in handlers.get(endpoint).into_iter().flatten() {
+                        link(&op.id, handler, "route");
+                    }
+                }
+            }
+        }
+
+        let Some(name) = op.props["name"].as_str() else {
+            continue;
+        };
+        let mut keys = vec![squash(name)];
+        if op.props["schema"] == "graphql" {
+            keys.push(format!("resolve{}", squash(name)));
+        }
+        let matches: Vec<&KgNode> = keys
+            .iter()
+            .filter_map(|k| symbols_by_name.get(k))
+            .flatten()
+            .copied()
+            .collect();
+        if matches.len() <= MAX_NAME_MATCHES {
+            for symbol in matches {
+                link(&op.id, &symbol.id, "name");
+            }
+        }
+    }
+
+    links
+}
+
+/// Route pattern with parameters and case normalized (`/Users/{id}/` and
+/// `/users/:userId` both become `/users/{}`).
+fn route_key(route: &str) -> String {
+    let segments: Vec<String> = route
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            if s.starts_with(':') || s.starts_with('{') || s.starts_with('<') || s.starts_with('[')
+            {
+                "{}".to_string()
+            } else {
+                s.to_lowercase()
+            }
+        })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Whether a path looks like generated protobuf/gRPC code.
+fn is_generated(path: &str) -> bool {
+    path.contains(".pb.")
+        || path.contains("_pb2")
+        || path.contains("_grpc")
+        || path.contains("/generated/")
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Blank out comments and string literals, keeping offsets and newlines.
+fn strip_comments(text: &str, line_comment: &str, block_comment: Option<(&str, &str)>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    let blank = |s: &str| -> String {
+        s.chars()
+            .map(|c| if c == '\n' { '\n' } else { ' ' })
+            .collect()
+    };
+
+    while !rest.is_empty() {
+        if let Some((open, close)) = block_comment {
+            if rest.starts_with(open) {
+                let end = rest[open.len()..]
+                    .find(close)
+                    .map(|i| open.len() + i + close.len())
+                    .unwrap_or(rest.len());
+                out.push_str(&blank(&rest[..end]));
+                rest = &rest[end..];
+                continue;
+            }
+        }
+        if rest.starts_with(line_comment) {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            out.push_str(&blank(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+        if rest.starts_with("\"\"\"") {
+            let end = rest[3..]
+                .find("\"\"\"")
+                .map(|i| i + 6)
+                .unwrap_or(rest.len());
+            out.push_str(&blank(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+        if rest.starts_with('"') {
+            let end = rest[1..]
+                .find(['"', '\n'])
+                .map(|i| i + 2)
+                .unwrap_or(rest.len())
+                .min(rest.len());
+            out.push_str(&blank(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+        let c = rest.chars().next().expect("non-empty");
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+fn dedup(mut names: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    names.retain(|n| seen.insert(n.clone()));
+    names
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges_of<'a>(graph: &'a SchemaGraph, kind: &str) -> Vec<(&'a str, &'a str)> {
+        graph
+            .edges
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_openapi() {
+        let yaml = r##"
+openapi: 3.0.0
+info: { title: Users, version: "1" }
+paths:
+  /users/{id}:
+    get:
+      operationId: getUser
+      parameters:
+        - name: id
+          in: path
+          schema: { type: string }
+      responses:
+        "200":
+          content:
+            application/json:
+              schema: { $ref: "#/components/schemas/User" }
+  /users:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: "#/components/schemas/NewUser" }
+      responses:
+        "201":
+          content:
+            application/json:
+              schema:
+                type: array
+                items: { $ref: "#/components/schemas/User" }
+components:
+  schemas:
+    User:
+      type: object
+      required: [id]
+      properties:
+        id: { type: string }
+        team: { $ref: "#/components/schemas/Team" }
+    NewUser:
+      allOf:
+        - $ref: "#/components/schemas/User"
+"##;
+        let graph = extract_schema("api/openapi.yaml", "doc:api/openapi.yaml", yaml).unwrap();
+
+        let mut ops: Vec<&KgNode> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "operation")
+            .collect();
+        ops.sort_by(|a, b| a.label.cmp(&b.label));
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[0].label, "POST /users");
+        assert_eq!(ops[1].label, "getUser");
+        assert_eq!(ops[1].props["httpMethod"], "GET");
+        assert_eq!(ops[1].props["route"], "/users/{id}");
+
+        let returns = edges_of(&graph, "returns");
+        assert!(returns.contains(&(
+            "op:openapi:api/openapi.yaml:GET /users/{id}",
+            "type:openapi:api/openapi.yaml:User"
+        )));
+        assert!(returns.contains(&(
+            "op:openapi:api/openapi.yaml:POST /users",
+            "type:openapi:api/openapi.yaml:User"
+        )));
+        let references = edges_of(&graph, "references");
+        assert!(references.contains(&(
+            "op:openapi:api/openapi.yaml:POST /users",
+            "type:openapi:api/openapi.yaml:NewUser"
+        )));
+        assert!(references.contains(&(
+            "type:openapi:api/openapi.yaml:User.team",
+            "type:openapi:api/openapi.yaml:Team"
+        )));
+        assert!(references.contains(&(
+            "type:openapi:api/openapi.yaml:NewUser",
+            "type:openapi:api/openapi.yaml:User"
+        )));
+        assert_eq!(edges_of(&graph, "definesEndpoint").len(), 2);
+
+        let id_field = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "type:openapi:api/openapi.yaml:User.id")
+            .unwrap();
+        assert_eq!(id_field.props["required"], true);
+
+        // Other YAML files are not schemas
+        assert!(extract_schema("config.yaml", "doc:config.yaml", "name: x\n").is_none());
+    }
+
+    #[test]
+    fn test_extract_graphql() {
+        let sdl = r#"
+# Users API
+type Query {
+  "Look up a user"
+  user(id: ID!): User
+  users(filter: UserFilter): [User!]!
+}
+
+type Mutation {
+  createUser(input: NewUser!): User!
+}
+
+type User {
+  id: ID!
+  team: Team
+}
+
+input UserFilter { name: String }
+
+enum Role { ADMIN MEMBER }
+
+union SearchResult = User | Team
+"#;
+        let graph = extract_schema("schema.graphql", "doc:schema.graphql", sdl).unwrap();
+
+        let ops: Vec<(&str, &str)> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "operation")
+            .map(|n| (n.label.as_str(), n.props["operationType"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                ("user", "query"),
+                ("users", "query"),
+                ("createUser", "mutation")
+            ]
+        );
+        let user_op = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "op:graphql:Query.user")
+            .unwrap();
+        assert_eq!(user_op.props["line"], 5);
+
+        let returns = edges_of(&graph, "returns");
+        assert_eq!(returns.len(), 3);
+        assert!(returns.iter().all(|(_, to)| *to == "type:graphql:User"));
+        let references = edges_of(&graph, "references");
+        assert!(references.contains(&("op:graphql:Query.users", "type:graphql:UserFilter")));
+        assert!(references.contains(&("type:graphql:User.team", "type:graphql:Team")));
+        assert!(references.contains(&("type:graphql:SearchResult", "type:graphql:Team")));
+        // Scalars get no edges
+        assert!(!references.iter().any(|(_, to)| to.ends_with(":ID")));
+
+        let role = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "type:graphql:Role")
+            .unwrap();
+        assert_eq!(role.props["values"], json!(["ADMIN", "MEMBER"]));
+        assert!(!graph.nodes.iter().any(|n| n.id == "type:graphql:Query"));
+    }
+
+    #[test]
+    fn test_extract_proto() {
+        let proto = r#"
+syntax = "proto3";
+package acme.users.v1;
+
+import "google/protobuf/timestamp.proto";
+
+// A user account.
+message User {
+  string id = 1;
+  repeated Address addresses = 2;
+  google.protobuf.Timestamp created_at = 3;
+  map<string, Role> roles = 4;
+
+  message Address {
+    string city = 1;
+  }
+}
+
+enum Role { ROLE_UNSPECIFIED = 0; }
+
+message GetUserRequest { string id = 1; }
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User);
+  rpc WatchUsers(GetUserRequest) returns (stream User) {}
+}
+"#;
+        let graph = extract_schema("proto/users.proto", "doc:proto/users.proto", proto).unwrap();
+
+        let rpc = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "op:grpc:acme.users.v1.UserService/GetUser")
+            .unwrap();
+        assert_eq!(rpc.props["line"], 24);
+        let watch = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "op:grpc:acme.users.v1.UserService/WatchUsers")
+            .unwrap();
+        assert_eq!(watch.props["serverStreaming"], true);
+
+        let returns = edges_of(&graph, "returns");
+        assert_eq!(returns.len(), 2);
+        assert!(returns
+            .iter()
+            .all(|(_, to)| *to == "type:proto:acme.users.v1.User"));
+        let references = edges_of(&graph, "references");
+        assert!(references.contains(&(
+            "op:grpc:acme.users.v1.UserService/GetUser",
+            "type:proto:acme.users.v1.GetUserRequest"
+        )));
+        assert!(references.contains(&(
+            "type:proto:acme.users.v1.User.addresses",
+            "type:proto:acme.users.v1.User.Address"
+        )));
+        assert!(references.contains(&(
+            "type:proto:acme.users.v1.User.created_at",
+            "type:proto:google.protobuf.Timestamp"
+        )));
+        assert!(references.contains(&(
+            "type:proto:acme.users.v1.User.roles",
+            "type:proto:acme.users.v1.Role"
+        )));
+
+        // Nested message fields stay with the nested message
+        let contains = edges_of(&graph, "contains");
+        assert!(contains.contains(&(
+            "type:proto:acme.users.v1.User.Address",
+            "type:proto:acme.users.v1.User.Address.city"
+        )));
+        assert!(!contains.contains(&(
+            "type:proto:acme.users.v1.User",
+            "type:proto:acme.users.v1.User.city"
+        )));
+        let kinds: HashSet<&str> = graph.nodes.iter().map(|n| n.kind.as_str()).collect();
+        assert!(kinds.contains("message") && kinds.contains("type") && kinds.contains("field"));
+    }
+
+    #[test]
+    fn test_link_schema_operations() {
+        let symbol = |id: &str, kind: &str, label: &str, path: &str| {
+            KgNode::new(id, kind, label).with_props(json!({ "path": path }))
+        };
+        let nodes = vec![
+            KgNode::new("op:grpc:acme.UserService/GetUser", "operation", "GetUser")
+                .with_props(json!({ "schema": "proto", "name": "GetUser" })),
+            KgNode::new("op:graphql:Query.user", "operation", "user")
+                .with_props(json!({ "schema": "graphql", "name": "user" })),
+            KgNode::new(
+                "op:openapi:api.yaml:GET /teams/{id}",
+                "operation",
+                "GET /teams/{id}",
+            )
+            .with_props(json!({
+                "schema": "openapi",
+                "name": null,
+                "httpMethod": "GET",
+                "route": "/teams/{id}",
+            })),
+            symbol(
+                "sym:go:server/users.go:method:GetUser",
+                "method",
+                "GetUser",
+                "server/users.go",
+            ),
+            symbol(
+                "sym:go:gen/users.pb.go:method:GetUser",
+                "method",
+                "GetUser",
+                "gen/users.pb.go",
+            ),
+            symbol(
+                "sym:ts:resolvers.ts:function:resolveUser",
+                "function",
+                "resolveUser",
+                "resolvers.ts",
+            ),
+            KgNode::new(
+                "endpoint:teams.ts:GET:/teams/:teamId",
+                "endpoint",
+                "/teams/:teamId",
+            )
+            .with_props(json!({ "httpMethod": "GET", "route": "/teams/:teamId" })),
+            symbol(
+                "sym:ts:teams.ts:function:showTeam",
+                "function",
+                "showTeam",
+                "teams.ts",
+            ),
+        ];
+        let edges = vec![KgEdge::new(
+            "endpoint:teams.ts:GET:/teams/:teamId",
+            "sym:ts:teams.ts:function:showTeam",
+            "handledBy",
+        )];
+
+        let links = link_schema_operations(&nodes, &edges);
+        let links: Vec<(&str, &str, &str)> = links
+            .iter()
+            .map(|e| {
+                (
+                    e.from.as_str(),
+                    e.to.as_str(),
+                    e.props["match"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                (
+                    "op:grpc:acme.UserService/GetUser",
+                    "sym:go:server/users.go:method:GetUser",
+                    "name"
+                ),
+                (
+                    "op:graphql:Query.user",
+                    "sym:ts:resolvers.ts:function:resolveUser",
+                    "name"
+                ),
+                (
+                    "op:openapi:api.yaml:GET /teams/{id}",
+                    "endpoint:teams.ts:GET:/teams/:teamId",
+                    "route"
+                ),
+                (
+                    "op:openapi:api.yaml:GET /teams/{id}",
+                    "sym:ts:teams.ts:function:showTeam",
+                    "route"
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,1370 @@
+//! Import of precise code-intelligence indexes (SCIP, LSIF) into the KG.
+//!
+//! Compiler-grade indexers (rust-analyzer, scip-typescript, scip-java,
+//! scip-python, ...) emit SCIP files with exact definition ranges, references
+//! and relationships. `gik kg import --scip index.scip` decodes one into a
+//! [`PreciseIndex`] snapshot stored at `<branch>/precise/scip.json`; LSIF
+//! dumps (`--lsif dump.lsif`) are read into the same snapshot by
+//! [`super::lsif`]. Every KG rebuild then merges it over the heuristic
+//! symbols of `kg::lang`:
+//!
+//! - Definitions become symbol nodes. A definition with a heuristic symbol of
+//!   the same name in the same file (same start line, else same kind) reuses
+//!   its `sym:` ID, so existing edges and ask's file/symbol mapping keep
+//!   working; others get `sym:<lang>:<path>:<kind>:<name>` IDs.
+//! - Symbol nodes get exact `startLine` / `endLine`, `scipSymbol`, `scipRange`
+//!   and hover docs (`doc`, `signature`), plus `precise: true`.
+//! - References become `references` edges from the innermost enclosing symbol
+//!   (or the file) to the referenced symbol, with `count` and first `line`.
+//! - `is_implementation` relationships become `implements` edges.
+//!
+//! In `replace` mode, heuristic symbols of covered files that the index does
+//! not define are dropped with their edges. Only files indexed in the `code`
+//! base are covered; local symbols, parameters, fields and variables are
+//! skipped.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::entities::{KgEdge, KgNode};
+use super::extractor::KgExtractionResult;
+use crate::errors::GikError;
+use crate::workspace::Workspace;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Directory under the branch holding imported index snapshots.
+pub const PRECISE_DIR_NAME: &str = "precise";
+
+/// Snapshot file for the imported SCIP index.
+pub const SCIP_SNAPSHOT_FILENAME: &str = "scip.json";
+
+/// `SymbolRole.Definition` bit.
+const ROLE_DEFINITION: i32 = 0x1;
+
+/// Path of the SCIP snapshot for a branch.
+///
+/// Returns `.guided/knowledge/{branch}/precise/scip.json`.
+pub fn scip_snapshot_path(workspace: &Workspace, branch: &str) -> PathBuf {
+    workspace
+        .branch_dir(branch)
+        .join(PRECISE_DIR_NAME)
+        .join(SCIP_SNAPSHOT_FILENAME)
+}
+
+// ============================================================================
+// SCIP Protobuf Messages
+// ============================================================================
+
+/// The subset of `scip.proto` GIK reads. Unknown fields are skipped.
+mod proto {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Index {
+        #[prost(message, optional, tag = "1")]
+        pub metadata: Option<Metadata>,
+        #[prost(message, repeated, tag = "2")]
+        pub documents: Vec<Document>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Metadata {
+        #[prost(message, optional, tag = "2")]
+        pub tool_info: Option<ToolInfo>,
+        #[prost(string, tag = "3")]
+        pub project_root: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ToolInfo {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, tag = "2")]
+        pub version: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Document {
+        #[prost(string, tag = "1")]
+        pub relative_path: String,
+        #[prost(message, repeated, tag = "2")]
+        pub occurrences: Vec<Occurrence>,
+        #[prost(message, repeated, tag = "3")]
+        pub symbols: Vec<SymbolInformation>,
+        #[prost(string, tag = "4")]
+        pub language: String,
+        #[prost(string, tag = "5")]
+        pub text: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Occurrence {
+        #[prost(int32, repeated, tag = "1")]
+        pub range: Vec<i32>,
+        #[prost(string, tag = "2")]
+        pub symbol: String,
+        #[prost(int32, tag = "3")]
+        pub symbol_roles: i32,
+        #[prost(int32, repeated, tag = "7")]
+        pub enclosing_range: Vec<i32>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SymbolInformation {
+        #[prost(string, tag = "1")]
+        pub symbol: String,
+        #[prost(string, repeated, tag = "3")]
+        pub documentation: Vec<String>,
+        #[prost(message, repeated, tag = "4")]
+        pub relationships: Vec<Relationship>,
+        #[prost(int32, tag = "5")]
+        pub kind: i32,
+        #[prost(string, tag = "6")]
+        pub display_name: String,
+        #[prost(message, optional, tag = "7")]
+        pub signature_documentation: Option<Document>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Relationship {
+        #[prost(string, tag = "1")]
+        pub symbol: String,
+        #[prost(bool, tag = "2")]
+        pub is_reference: bool,
+        #[prost(bool, tag = "3")]
+        pub is_implementation: bool,
+        #[prost(bool, tag = "4")]
+        pub is_type_definition: bool,
+        #[prost(bool, tag = "5")]
+        pub is_definition: bool,
+    }
+}
+
+// ============================================================================
+// Snapshot Types
+// ============================================================================
+
+/// How imported symbols combine with the heuristic ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreciseMode {
+    /// Keep heuristic symbols the index does not define.
+    #[default]
+    Merge,
+    /// Drop heuristic symbols of covered files the index does not define.
+    Replace,
+}
+
+impl std::fmt::Display for PreciseMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreciseMode::Merge => write!(f, "merge"),
+            PreciseMode::Replace => write!(f, "replace"),
+        }
+    }
+}
+
+/// A decoded code-intelligence index, normalized to workspace paths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreciseIndex {
+    /// File the index was imported from.
+    pub source: PathBuf,
+    /// Indexer name and version, when recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    pub imported_at: DateTime<Utc>,
+    pub mode: PreciseMode,
+    pub documents: Vec<PreciseDocument>,
+}
+
+/// Definitions and references of one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreciseDocument {
+    /// Workspace-relative path.
+    pub path: String,
+    pub symbols: Vec<PreciseSymbol>,
+    /// References to symbols defined in the index, one per symbol and line.
+    pub references: Vec<PreciseReference>,
+}
+
+/// A symbol definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreciseSymbol {
+    /// SCIP symbol string.
+    pub symbol: String,
+    pub name: String,
+    /// KG symbol kind (`function`, `method`, `class`, ...).
+    pub kind: String,
+    /// SCIP symbol of the enclosing definition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Name range: 0-based start line, start column, end line, end column.
+    pub range: [u32; 4],
+    /// Range of the whole definition, when the indexer reports it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enclosing: Option<[u32; 4]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// SCIP symbols this symbol implements.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub implements: Vec<String>,
+}
+
+/// A reference occurrence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreciseReference {
+    pub symbol: String,
+    /// 0-based line.
+    pub line: u32,
+    /// 0-based column.
+    pub column: u32,
+}
+
+impl PreciseIndex {
+    /// Number of symbol definitions.
+    pub fn symbol_count(&self) -> usize {
+        self.documents.iter().map(|d| d.symbols.len()).sum()
+    }
+
+    /// Number of (symbol, line) references.
+    pub fn reference_count(&self) -> usize {
+        self.documents.iter().map(|d| d.references.len()).sum()
+    }
+
+    /// Number of implementation relationships.
+    pub fn implementation_count(&self) -> usize {
+        self.documents
+            .iter()
+            .flat_map(|d| &d.symbols)
+            .map(|s| s.implements.len())
+            .sum()
+    }
+}
+
+/// Result of `gik kg import`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScipImportReport {
+    pub branch: String,
+    pub source: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    pub mode: PreciseMode,
+    /// Documents in the index.
+    pub documents: usize,
+    /// Documents whose file is indexed in the `code` base.
+    pub files_matched: usize,
+    pub symbols: usize,
+    pub references: usize,
+    pub implementations: usize,
+    /// KG symbol nodes carrying precise data after the rebuild.
+    pub precise_nodes: usize,
+    pub nodes_written: usize,
+    pub edges_written: usize,
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+/// Read and normalize a SCIP index or LSIF dump.
+///
+/// LSIF dumps are JSON, so a file whose first non-blank byte is `{` or `[`
+/// is read as LSIF and anything else as SCIP protobuf.
+///
+/// Document paths are made workspace-relative: when the index's project root
+/// lies inside `workspace_root`, its relative location is prefixed.
+///
+/// # Errors
+///
+/// Returns [`GikError::KgImportInvalid`] if the file cannot be read or is
+/// neither a SCIP index nor an LSIF dump.
+pub fn load_precise_file(
+    path: &Path,
+    workspace_root: &Path,
+    mode: PreciseMode,
+) -> Result<PreciseIndex, GikError> {
+    let bytes = std::fs::read(path).map_err(|e| GikError::KgImportInvalid {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+    let is_json = bytes
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .is_some_and(|b| matches!(b, b'{' | b'['));
+    if is_json {
+        super::lsif::parse_lsif(&bytes, path, workspace_root, mode)
+    } else {
+        parse_scip(&bytes, path, workspace_root, mode)
+    }
+}
+
+/// Decode SCIP protobuf bytes. See [`load_precise_file`].
+pub fn parse_scip(
+    bytes: &[u8],
+    source: &Path,
+    workspace_root: &Path,
+    mode: PreciseMode,
+) -> Result<PreciseIndex, GikError> {
+    let invalid = |reason: String| GikError::KgImportInvalid {
+        path: source.to_path_buf(),
+        reason,
+    };
+    let index = proto::Index::decode(bytes).map_err(|e| invalid(e.to_string()))?;
+    if index.documents.is_empty() {
+        return Err(invalid("index has no documents".to_string()));
+    }
+
+    let metadata = index.metadata.unwrap_or_default();
+    let prefix = project_prefix(&metadata.project_root, workspace_root);
+    let tool = metadata
+        .tool_info
+        .filter(|t| !t.name.is_empty())
+        .map(|t| format!("{} {}", t.name, t.version).trim().to_string());
+
+    let documents = index
+        .documents
+        .iter()
+        .map(|doc| normalize_document(doc, &prefix))
+        .collect();
+    Ok(finish_index(documents, source, tool, mode))
+}
+
+/// Assemble a snapshot, dropping references to symbols the index does not
+/// define and documents left empty.
+pub(super) fn finish_index(
+    mut documents: Vec<PreciseDocument>,
+    source: &Path,
+    tool: Option<String>,
+    mode: PreciseMode,
+) -> PreciseIndex {
+    let defined: HashSet<String> = documents
+        .iter()
+        .flat_map(|d| d.symbols.iter().map(|s| s.symbol.clone()))
+        .collect();
+    for doc in &mut documents {
+        doc.references.retain(|r| defined.contains(&r.symbol));
+    }
+    documents.retain(|d| !d.symbols.is_empty() || !d.references.is_empty());
+    documents.sort_by(|a, b| a.path.cmp(&b.path));
+
+    PreciseIndex {
+        source: source.to_path_buf(),
+        tool,
+        imported_at: Utc::now(),
+        mode,
+        documents,
+    }
+}
+
+fn normalize_document(doc: &proto::Document, prefix: &str) -> PreciseDocument {
+    let path = format!("{}{}", prefix, doc.relative_path.replace('\\', "/"));
+    let infos: HashMap<&str, &proto::SymbolInformation> =
+        doc.symbols.iter().map(|s| (s.symbol.as_str(), s)).collect();
+
+    let mut symbols = Vec::new();
+    let mut defined = HashSet::new();
+    let mut references: BTreeMap<(String, u32), u32> = BTreeMap::new();
+
+    for occ in &doc.occurrences {
+        if occ.symbol.is_empty() || occ.symbol.starts_with("local ") {
+            continue;
+        }
+        let Some(range) = to_range(&occ.range) else {
+            continue;
+        };
+
+        if occ.symbol_roles & ROLE_DEFINITION == 0 {
+            references
+                .entry((occ.symbol.clone(), range[0]))
+                .or_insert(range[1]);
+            continue;
+        }
+        if !defined.insert(occ.symbol.as_str()) {
+            continue;
+        }
+        let Some(parsed) = parse_symbol(&occ.symbol) else {
+            continue;
+        };
+        let info = infos.get(occ.symbol.as_str()).copied();
+        let Some(kind) = symbol_kind(&parsed, info.map_or(0, |i| i.kind)) else {
+            continue;
+        };
+        let (documentation, signature) = info
+            .map(|i| {
+                let signature = i.signature_documentation.as_ref().map(|d| d.text.as_str());
+                hover_docs(&i.documentation, signature)
+            })
+            .unwrap_or_default();
+
+        symbols.push(PreciseSymbol {
+            symbol: occ.symbol.clone(),
+            name: parsed
+                .descriptors
+                .last()
+                .map(|d| d.name.clone())
+                .unwrap_or_default(),
+            kind: kind.to_string(),
+            parent: parsed.parent,
+            range,
+            enclosing: to_range(&occ.enclosing_range),
+            documentation,
+            signature,
+            implements: info
+                .map(|i| {
+                    i.relationships
+                        .iter()
+                        .filter(|r| r.is_implementation)
+                        .map(|r| r.symbol.clone())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        });
+    }
+
+    PreciseDocument {
+        path,
+        symbols,
+        references: references
+            .into_iter()
+            .map(|((symbol, line), column)| PreciseReference {
+                symbol,
+                line,
+                column,
+            })
+            .collect(),
+    }
+}
+
+/// SCIP ranges are `[line, startCol, endCol]` or
+/// `[startLine, startCol, endLine, endCol]`.
+fn to_range(range: &[i32]) -> Option<[u32; 4]> {
+    if range.iter().any(|&v| v < 0) {
+        return None;
+    }
+    match *range {
+        [line, start, end] => Some([line as u32, start as u32, line as u32, end as u32]),
+        [sl, sc, el, ec] => Some([sl as u32, sc as u32, el as u32, ec as u32]),
+        _ => None,
+    }
+}
+
+/// Split hover docs into prose and signature. Indexers put the signature
+/// either in a separate field or as a leading fenced code block.
+pub(super) fn hover_docs(
+    documentation: &[String],
+    signature: Option<&str>,
+) -> (Option<String>, Option<String>) {
+    let mut docs: Vec<&str> = documentation.iter().map(|d| d.trim()).collect();
+    let mut signature = signature
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    if let Some(first) = docs.first().filter(|d| d.starts_with("```")) {
+        let code = first
+            .lines()
+            .filter(|l| !l.trim_start().starts_with("```"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if signature.is_none() && !code.trim().is_empty() {
+            signature = Some(code.trim().to_string());
+        }
+        docs.remove(0);
+    }
+
+    let doc = docs
+        .into_iter()
+        .filter(|d| !d.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    ((!doc.is_empty()).then_some(doc), signature)
+}
+
+/// Directory of the index's project root relative to the workspace, with a
+/// trailing slash (empty when it is the workspace root or lies elsewhere).
+pub(super) fn project_prefix(project_root: &str, workspace_root: &Path) -> String {
+    let root = project_root
+        .strip_prefix("file://")
+        .unwrap_or(project_root)
+        .trim_end_matches('/');
+    if root.is_empty() {
+        return String::new();
+    }
+    let workspace = workspace_root
+        .canonicalize()
+        .unwrap_or_else(|_| workspace_root.to_path_buf());
+    let project = Path::new(root)
+        .canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(root));
+
+    match project.strip_prefix(&workspace) {
+        Ok(rel) if !rel.as_os_str().is_empty() => {
+            format!("{}/", rel.to_string_lossy().replace('\\', "/"))
+        }
+        _ => String::new(),
+    }
+}
+
+// ============================================================================
+// Symbol Strings
+// ============================================================================
+
+/// Descriptor suffixes from the SCIP symbol grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Suffix {
+    Namespace,
+    Type,
+    Term,
+    Method,
+    TypeParameter,
+    Parameter,
+    Meta,
+    Macro,
+}
+
+#[derive(Debug, Clone)]
+struct Descriptor {
+    name: String,
+    suffix: Suffix,
+}
+
+/// A parsed global symbol: `<scheme> <manager> <package> <version> <descriptors>`.
+#[derive(Debug, Clone)]
+struct ParsedSymbol {
+    descriptors: Vec<Descriptor>,
+    /// The symbol without its last descriptor, if any descriptor remains.
+    parent: Option<String>,
+}
+
+/// Parse a global SCIP symbol. Returns None for local or malformed symbols.
+fn parse_symbol(symbol: &str) -> Option<ParsedSymbol> {
+    if symbol.starts_with("local ") {
+        return None;
+    }
+    let chars: Vec<char> = symbol.chars().collect();
+
+    // Four space-terminated header fields; a doubled space is a literal space
+    let mut i = 0;
+    let mut fields = 0;
+    while fields < 4 {
+        match chars.get(i)? {
+            ' ' if chars.get(i + 1) == Some(&' ') => i += 2,
+            ' ' => {
+                fields += 1;
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    let mut descriptors = Vec::new();
+    let mut starts = Vec::new();
+    while i < chars.len() {
+        starts.push(i);
+        let (name, suffix, next) = match chars[i] {
+            '[' => {
+                let (name, end) = read_name(&chars, i + 1)?;
+                (chars.get(end) == Some(&']')).then_some(())?;
+                (name, Suffix::TypeParameter, end + 1)
+            }
+            '(' => {
+                let (name, end) = read_name(&chars, i + 1)?;
+                (chars.get(end) == Some(&')')).then_some(())?;
+                (name, Suffix::Parameter, end + 1)
+            }
+            _ => {
+                let (name, end) = read_name(&chars, i)?;
+                match chars.get(end)? {
+                    '/' => (name, Suffix::Namespace, end + 1),
+                    '#' => (name, Suffix::Type, end + 1),
+                    '.' => (name, Suffix::Term, end + 1),
+                    ':' => (name, Suffix::Meta, end + 1),
+                    '!' => (name, Suffix::Macro, end + 1),
+                    '(' => {
+                        let close = end + chars[end..].iter().position(|&c| c == ')')?;
+                        (chars.get(close + 1) == Some(&'.')).then_some(())?;
+                        (name, Suffix::Method, close + 2)
+                    }
+                    _ => return None,
+                }
+            }
+        };
+        descriptors.push(Descriptor { name, suffix });
+        i = next;
+    }
+
+    let parent =
+        (descriptors.len() > 1).then(|| chars[..starts[starts.len() - 1]].iter().collect());
+    Some(ParsedSymbol {
+        descriptors,
+        parent,
+    })
+}
+
+/// Read a simple or backtick-escaped identifier starting at `start`.
+/// Returns the name and the index after it.
+fn read_name(chars: &[char], start: usize) -> Option<(String, usize)> {
+    let mut name = String::new();
+    let mut i = start;
+
+    if chars.get(i) == Some(&'`') {
+        i += 1;
+        loop {
+            match chars.get(i)? {
+                '`' if chars.get(i + 1) == Some(&'`') => {
+                    name.push('`');
+                    i += 2;
+                }
+                '`' => return Some((name, i + 1)),
+                &c => {
+                    name.push(c);
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    while let Some(&c) = chars.get(i) {
+        if c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-' | '$') {
+            name.push(c);
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    (!name.is_empty()).then_some((name, i))
+}
+
+/// Map a definition to a KG symbol kind, or None for symbols the KG skips.
+///
+/// `info_kind` is `SymbolInformation.Kind` from `scip.proto`; descriptors
+/// decide when the indexer leaves it unspecified.
+fn symbol_kind(parsed: &ParsedSymbol, info_kind: i32) -> Option<&'static str> {
+    let (last, ancestors) = parsed.descriptors.split_last()?;
+    let from_info = match info_kind {
+        7 | 33 | 75 => Some("class"),
+        49 | 59 => Some("struct"),
+        11 => Some("enum"),
+        21 | 42 => Some("interface"),
+        53 => Some("trait"),
+        54 | 55 => Some("type"),
+        17 => Some("function"),
+        9 | 26 | 66 | 68 | 69 | 70 | 71 | 76 | 80 => Some("method"),
+        29 | 35 => Some("module"),
+        30 => Some("namespace"),
+        8 => Some("constant"),
+        25 => Some("macro"),
+        _ => None,
+    };
+
+    match last.suffix {
+        Suffix::Type => Some(from_info.unwrap_or("class")),
+        Suffix::Method => {
+            let callable = from_info.filter(|k| matches!(*k, "function" | "method"));
+            let in_type = ancestors.iter().any(|d| d.suffix == Suffix::Type);
+            Some(callable.unwrap_or(if in_type { "method" } else { "function" }))
+        }
+        Suffix::Macro => Some("macro"),
+        // File-level namespaces (`src/utils.ts`/) duplicate file nodes
+        Suffix::Namespace if !last.name.contains(['.', '/']) => Some(from_info.unwrap_or("module")),
+        Suffix::Term => from_info.filter(|k| *k == "constant"),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Snapshot Storage
+// ============================================================================
+
+/// Write a snapshot, creating its directory.
+pub fn write_precise_index(path: &Path, index: &PreciseIndex) -> Result<(), GikError> {
+    let io_err = |message: String| GikError::BaseStoreIo {
+        path: path.to_path_buf(),
+        message,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| io_err(e.to_string()))?;
+    }
+    let json = serde_json::to_string(index).map_err(|e| io_err(e.to_string()))?;
+    std::fs::write(path, json).map_err(|e| io_err(e.to_string()))
+}
+
+/// Read a snapshot, or None if there is none.
+pub fn read_precise_index(path: &Path) -> Result<Option<PreciseIndex>, GikError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let io_err = |message: String| GikError::BaseStoreIo {
+        path: path.to_path_buf(),
+        message,
+    };
+    let content = std::fs::read_to_string(path).map_err(|e| io_err(e.to_string()))?;
+    let index = serde_json::from_str(&content).map_err(|e| io_err(e.to_string()))?;
+    Ok(Some(index))
+}
+
+// ============================================================================
+// Merge
+// ============================================================================
+
+/// Counts from merging a precise index into an extraction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreciseMergeStats {
+    /// Index documents with a `file` node.
+    pub files: usize,
+    /// Definitions that reused a heuristic symbol.
+    pub matched: usize,
+    /// Definitions added as new symbol nodes.
+    pub added: usize,
+    /// Heuristic symbols dropped in replace mode.
+    pub removed: usize,
+    pub references: usize,
+    pub implementations: usize,
+}
+
+/// Merge a precise index into the extracted nodes and edges.
+pub fn merge_precise_index(
+    result: &mut KgExtractionResult,
+    index: &PreciseIndex,
+    branch: &str,
+) -> PreciseMergeStats {
+    let mut stats = PreciseMergeStats::default();
+
+    let file_nodes: HashMap<String, String> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == "file")
+        .filter_map(|n| Some((n.props["path"].as_str()?.to_string(), n.id.clone())))
+        .collect();
+    let covered: Vec<&PreciseDocument> = index
+        .documents
+        .iter()
+        .filter(|d| file_nodes.contains_key(&d.path))
+        .collect();
+    stats.files = covered.len();
+    if covered.is_empty() {
+        return stats;
+    }
+
+    let covered_paths: HashSet<&str> = covered.iter().map(|d| d.path.as_str()).collect();
+    let mut heuristic: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, node) in result.nodes.iter().enumerate() {
+        let Some(path) = node.props["path"].as_str() else {
+            continue;
+        };
+        if node.id.starts_with("sym:") && covered_paths.contains(path) {
+            heuristic.entry(path.to_string()).or_default().push(i);
+        }
+    }
+
+    let mut node_ids: HashSet<String> = result.nodes.iter().map(|n| n.id.clone()).collect();
+    let mut claimed: HashSet<usize> = HashSet::new();
+    let mut ids: HashMap<&str, String> = HashMap::new();
+    let mut added: Vec<KgNode> = Vec::new();
+    let mut edges: Vec<KgEdge> = Vec::new();
+
+    // Definitions
+    for doc in &covered {
+        let tag = super::lang::tag_from_path(&doc.path);
+        let candidates = heuristic.get(&doc.path).map(Vec::as_slice).unwrap_or(&[]);
+
+        for sym in &doc.symbols {
+            let start_lines = [
+                u64::from(sym.range[0]) + 1,
+                u64::from(sym.enclosing.unwrap_or(sym.range)[0]) + 1,
+            ];
+            let same_name: Vec<usize> = candidates
+                .iter()
+                .copied()
+                .filter(|i| !claimed.contains(i) && result.nodes[*i].label == sym.name)
+                .collect();
+            let found = same_name
+                .iter()
+                .find(|&&i| {
+                    let line = result.nodes[i].props["startLine"].as_u64();
+                    line.is_some_and(|l| start_lines.contains(&l))
+                })
+                .or_else(|| {
+                    same_name
+                        .iter()
+                        .find(|&&i| result.nodes[i].kind == sym.kind)
+                })
+                .copied();
+
+            let id = match found {
+                Some(i) => {
+                    claimed.insert(i);
+                    apply_precise_props(&mut result.nodes[i].props, sym);
+                    stats.matched += 1;
+                    result.nodes[i].id.clone()
+                }
+                None => {
+                    let base_id = format!("sym:{}:{}:{}:{}", tag, doc.path, sym.kind, sym.name);
+                    let mut id = base_id.clone();
+                    let mut n = 2;
+                    while node_ids.contains(&id) {
+                        id = format!("{}#{}", base_id, n);
+                        n += 1;
+                    }
+                    node_ids.insert(id.clone());
+
+                    let mut props = json!({
+                        "base": "code",
+                        "path": doc.path,
+                        "language": tag,
+                        "symbolKind": sym.kind,
+                    });
+                    apply_precise_props(&mut props, sym);
+                    added.push(
+                        KgNode::new(&id, &sym.kind, &sym.name)
+                            .with_props(props)
+                            .with_branch(branch),
+                    );
+                    edges.push(
+                        KgEdge::new(&file_nodes[&doc.path], &id, "defines")
+                            .with_props(json!({ "symbolKind": sym.kind }))
+                            .with_branch(branch),
+                    );
+                    stats.added += 1;
+                    id
+                }
+            };
+            ids.insert(sym.symbol.as_str(), id);
+        }
+    }
+
+    if index.mode == PreciseMode::Replace {
+        let removed: HashSet<String> = heuristic
+            .values()
+            .flatten()
+            .filter(|i| !claimed.contains(i))
+            .map(|&i| result.nodes[i].id.clone())
+            .collect();
+        stats.removed = removed.len();
+        result.nodes.retain(|n| !removed.contains(&n.id));
+        result
+            .edges
+            .retain(|e| !removed.contains(&e.from) && !removed.contains(&e.to));
+    }
+
+    // Parents: props for every precise symbol, `contains` edges for new ones
+    let added_ids: HashSet<String> = added.iter().map(|n| n.id.clone()).collect();
+    result.nodes.extend(added);
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    for sym in covered.iter().flat_map(|d| &d.symbols) {
+        let parent = sym.parent.as_deref().and_then(|p| ids.get(p));
+        if let (Some(child), Some(parent)) = (ids.get(sym.symbol.as_str()), parent) {
+            parents.insert(child.as_str(), parent.as_str());
+            if added_ids.contains(child) {
+                edges.push(KgEdge::new(parent, child, "contains").with_branch(branch));
+            }
+        }
+    }
+    for node in &mut result.nodes {
+        if let Some(parent) = parents.get(node.id.as_str()) {
+            if let Some(obj) = node.props.as_object_mut() {
+                obj.entry("parent").or_insert_with(|| json!(parent));
+            }
+        }
+    }
+
+    // References, attributed to the innermost enclosing definition
+    let mut references: BTreeMap<(String, String), (u64, u64, String)> = BTreeMap::new();
+    for doc in &covered {
+        let spans: Vec<(u32, u32, &str)> = doc
+            .symbols
+            .iter()
+            .filter_map(|s| {
+                let enclosing = s.enclosing?;
+                Some((
+                    enclosing[0],
+                    enclosing[2],
+                    ids.get(s.symbol.as_str())?.as_str(),
+                ))
+            })
+            .collect();
+        let file_id = file_nodes[&doc.path].as_str();
+
+        for reference in &doc.references {
+            let Some(to) = ids.get(reference.symbol.as_str()) else {
+                continue;
+            };
+            let from = spans
+                .iter()
+                .filter(|(start, end, _)| *start <= reference.line && reference.line <= *end)
+                .min_by_key(|(start, end, _)| end - start)
+                .map(|(_, _, id)| *id)
+                .unwrap_or(file_id);
+            if from == to {
+                continue;
+            }
+            let line = u64::from(reference.line) + 1;
+            let entry = references.entry((from.to_string(), to.clone())).or_insert((
+                0,
+                line,
+                doc.path.clone(),
+            ));
+            entry.0 += 1;
+            entry.1 = entry.1.min(line);
+        }
+    }
+    stats.references = references.len();
+    for ((from, to), (count, line, source_file)) in references {
+        edges.push(
+            KgEdge::new(from, to, "references")
+                .with_props(json!({
+                    "count": count,
+                    "line": line,
+                    "sourceFile": source_file,
+                    "source": "scip",
+                }))
+                .with_branch(branch),
+        );
+    }
+
+    // Implementations
+    let mut implements: HashSet<(String, String)> = result
+        .edges
+        .iter()
+        .filter(|e| e.kind == "implements")
+        .map(|e| (e.from.clone(), e.to.clone()))
+        .collect();
+    for sym in covered.iter().flat_map(|d| &d.symbols) {
+        let Some(from) = ids.get(sym.symbol.as_str()) else {
+            continue;
+        };
+        for target in &sym.implements {
+            let Some(to) = ids.get(target.as_str()) else {
+                continue;
+            };
+            if from != to && implements.insert((from.clone(), to.clone())) {
+                edges.push(
+                    KgEdge::new(from, to, "implements")
+                        .with_props(json!({ "source": "scip" }))
+                        .with_branch(branch),
+                );
+                stats.implementations += 1;
+            }
+        }
+    }
+
+    result.edges.extend(edges);
+    stats
+}
+
+/// Store exact ranges and hover docs on a symbol node's props.
+fn apply_precise_props(props: &mut serde_json::Value, sym: &PreciseSymbol) {
+    if !props.is_object() {
+        *props = json!({});
+    }
+    let Some(obj) = props.as_object_mut() else {
+        return;
+    };
+    let span = sym.enclosing.unwrap_or(sym.range);
+    obj.insert("startLine".to_string(), json!(span[0] + 1));
+    obj.insert("endLine".to_string(), json!(span[2] + 1));
+    obj.insert("scipSymbol".to_string(), json!(sym.symbol));
+    obj.insert("scipRange".to_string(), json!(sym.range));
+    obj.insert("precise".to_string(), json!(true));
+    if let Some(doc) = &sym.documentation {
+        obj.insert("doc".to_string(), json!(doc));
+    }
+    if let Some(signature) = &sym.signature {
+        obj.insert("signature".to_string(), json!(signature));
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const PKG: &str = "rust-analyzer cargo shop 0.1.0 ";
+
+    fn occurrence(
+        symbol: &str,
+        range: Vec<i32>,
+        roles: i32,
+        enclosing: Vec<i32>,
+    ) -> proto::Occurrence {
+        proto::Occurrence {
+            range,
+            symbol: symbol.to_string(),
+            symbol_roles: roles,
+            enclosing_range: enclosing,
+        }
+    }
+
+    fn info(
+        symbol: &str,
+        kind: i32,
+        docs: &[&str],
+        implements: &[&str],
+    ) -> proto::SymbolInformation {
+        proto::SymbolInformation {
+            symbol: symbol.to_string(),
+            documentation: docs.iter().map(|d| d.to_string()).collect(),
+            relationships: implements
+                .iter()
+                .map(|s| proto::Relationship {
+                    symbol: s.to_string(),
+                    is_implementation: true,
+                    ..Default::default()
+                })
+                .collect(),
+            kind,
+            ..Default::default()
+        }
+    }
+
+    /// `src/orders.rs` defines `Order`, `Order::total` and `Priced`;
+    /// `src/main.rs` calls `Order::total` from `main`.
+    fn sample_index() -> Vec<u8> {
+        let order = format!("{}orders/Order#", PKG);
+        let total = format!("{}orders/Order#total().", PKG);
+        let priced = format!("{}orders/Priced#", PKG);
+        let main = format!("{}main().", PKG);
+
+        proto::Index {
+            metadata: Some(proto::Metadata {
+                tool_info: Some(proto::ToolInfo {
+                    name: "rust-analyzer".to_string(),
+                    version: "1.0".to_string(),
+                }),
+                project_root: String::new(),
+            }),
+            documents: vec![
+                proto::Document {
+                    relative_path: "src/orders.rs".to_string(),
+                    occurrences: vec![
+                        occurrence(&order, vec![2, 11, 16], 1, vec![2, 0, 4, 1]),
+                        occurrence(&total, vec![6, 11, 16], 1, vec![6, 4, 8, 5]),
+                        occurrence(&priced, vec![10, 10, 16], 1, vec![]),
+                        occurrence(&order, vec![6, 20, 25], 0, vec![]),
+                        occurrence("local 1", vec![7, 8, 9], 1, vec![]),
+                        occurrence(
+                            "scip-rust cargo std 1.0 vec/Vec#",
+                            vec![7, 12, 15],
+                            0,
+                            vec![],
+                        ),
+                    ],
+                    symbols: vec![
+                        info(
+                            &order,
+                            49,
+                            &["```rust\npub struct Order\n```", "An order."],
+                            &[&priced],
+                        ),
+                        info(&total, 0, &["Sum of line items."], &[]),
+                        info(&priced, 53, &[], &[]),
+                    ],
+                    ..Default::default()
+                },
+                proto::Document {
+                    relative_path: "src/main.rs".to_string(),
+                    occurrences: vec![
+                        occurrence(&main, vec![0, 3, 7], 1, vec![0, 0, 3, 1]),
+                        occurrence(&total, vec![2, 10, 15], 0, vec![]),
+                        occurrence(&total, vec![2, 30, 35], 0, vec![]),
+                    ],
+                    ..Default::default()
+                },
+                proto::Document {
+                    relative_path: "vendor/unindexed.rs".to_string(),
+                    occurrences: vec![occurrence(
+                        &format!("{}helper().", PKG),
+                        vec![0, 3, 9],
+                        1,
+                        vec![],
+                    )],
+                    ..Default::default()
+                },
+            ],
+        }
+        .encode_to_vec()
+    }
+
+    fn extraction() -> KgExtractionResult {
+        let file = |path: &str| {
+            KgNode::new(format!("file:{}", path), "file", path)
+                .with_props(json!({ "base": "code", "path": path }))
+        };
+        let heuristic = |kind: &str, name: &str, line: u64| {
+            KgNode::new(format!("sym:rs:src/orders.rs:{}:{}", kind, name), kind, name).with_props(
+                json!({ "base": "code", "path": "src/orders.rs", "symbolKind": kind, "startLine": line }),
+            )
+        };
+        let mut result = KgExtractionResult::new();
+        result.nodes = vec![
+            file("src/orders.rs"),
+            file("src/main.rs"),
+            heuristic("struct", "Order", 3),
+            heuristic("function", "total", 7),
+            heuristic("function", "legacy", 20),
+        ];
+        result.edges = vec![
+            KgEdge::new(
+                "file:src/orders.rs",
+                "sym:rs:src/orders.rs:struct:Order",
+                "defines",
+            ),
+            KgEdge::new(
+                "file:src/orders.rs",
+                "sym:rs:src/orders.rs:function:legacy",
+                "defines",
+            ),
+        ];
+        result
+    }
+
+    #[test]
+    fn test_parse_symbol_descriptors() {
+        let parsed =
+            parse_symbol("scip-typescript npm shop 1.0.0 src/`orders.ts`/Order#total().").unwrap();
+        let names: Vec<_> = parsed
+            .descriptors
+            .iter()
+            .map(|d| (d.name.as_str(), d.suffix))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("src", Suffix::Namespace),
+                ("orders.ts", Suffix::Namespace),
+                ("Order", Suffix::Type),
+                ("total", Suffix::Method),
+            ]
+        );
+        assert_eq!(
+            parsed.parent.as_deref(),
+            Some("scip-typescript npm shop 1.0.0 src/`orders.ts`/Order#")
+        );
+        assert_eq!(symbol_kind(&parsed, 0), Some("method"));
+
+        // Escaped spaces in the header and disambiguated overloads
+        let parsed = parse_symbol("scip-java maven my  lib 1.0 com/acme/Util#parse(+1).").unwrap();
+        assert_eq!(parsed.descriptors.last().unwrap().name, "parse");
+        assert_eq!(symbol_kind(&parsed, 0), Some("method"));
+
+        let module = parse_symbol("scip-typescript npm shop 1.0.0 src/`orders.ts`/").unwrap();
+        assert_eq!(symbol_kind(&module, 0), None);
+        let field = parse_symbol("rust-analyzer cargo shop 0.1.0 orders/Order#id.").unwrap();
+        assert_eq!(symbol_kind(&field, 15), None);
+        assert_eq!(symbol_kind(&field, 8), Some("constant"));
+
+        assert!(parse_symbol("local 12").is_none());
+        assert!(parse_symbol("incomplete header").is_none());
+    }
+
+    #[test]
+    fn test_parse_scip_normalizes_documents() {
+        let index = parse_scip(
+            &sample_index(),
+            Path::new("index.scip"),
+            Path::new("/nonexistent"),
+            PreciseMode::Merge,
+        )
+        .unwrap();
+        assert_eq!(index.tool.as_deref(), Some("rust-analyzer 1.0"));
+        assert_eq!(index.documents.len(), 3);
+
+        let orders = index
+            .documents
+            .iter()
+            .find(|d| d.path == "src/orders.rs")
+            .unwrap();
+        assert_eq!(orders.symbols.len(), 3);
+        let order = &orders.symbols[0];
+        assert_eq!(
+            (order.name.as_str(), order.kind.as_str()),
+            ("Order", "struct")
+        );
+        assert_eq!(order.range, [2, 11, 2, 16]);
+        assert_eq!(order.signature.as_deref(), Some("pub struct Order"));
+        assert_eq!(order.documentation.as_deref(), Some("An order."));
+        assert_eq!(order.implements.len(), 1);
+        assert_eq!(orders.symbols[1].kind, "method");
+        assert_eq!(
+            orders.symbols[1].parent.as_deref(),
+            Some(order.symbol.as_str())
+        );
+        // The std::Vec reference points outside the index
+        assert_eq!(orders.references.len(), 1);
+
+        let main = index
+            .documents
+            .iter()
+            .find(|d| d.path == "src/main.rs")
+            .unwrap();
+        // Two references on the same line collapse into one
+        assert_eq!(main.references.len(), 1);
+        assert_eq!(index.symbol_count(), 5);
+
+        let err = parse_scip(
+            b"not protobuf",
+            Path::new("x.scip"),
+            Path::new("."),
+            PreciseMode::Merge,
+        )
+        .unwrap_err();
+        assert!(matches!(err, GikError::KgImportInvalid { .. }));
+    }
+
+    #[test]
+    fn test_merge_reuses_heuristic_ids_and_adds_edges() {
+        let index = parse_scip(
+            &sample_index(),
+            Path::new("index.scip"),
+            Path::new("/nonexistent"),
+            PreciseMode::Merge,
+        )
+        .unwrap();
+        let mut result = extraction();
+        let stats = merge_precise_index(&mut result, &index, "main");
+
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.matched, 2);
+        assert_eq!(stats.added, 2);
+        assert_eq!(stats.removed, 0);
+
+        let node = |id: &str| result.nodes.iter().find(|n| n.id == id).unwrap();
+        let order = node("sym:rs:src/orders.rs:struct:Order");
+        assert_eq!(order.props["precise"], true);
+        assert_eq!(order.props["startLine"], 3);
+        assert_eq!(order.props["endLine"], 5);
+        assert_eq!(order.props["doc"], "An order.");
+        // Matched by start line: the heuristic ID survives
+        let total = node("sym:rs:src/orders.rs:function:total");
+        assert_eq!(total.props["scipRange"], json!([6, 11, 6, 16]));
+        assert_eq!(total.props["parent"], "sym:rs:src/orders.rs:struct:Order");
+        assert!(result
+            .nodes
+            .iter()
+            .any(|n| n.id == "sym:rs:src/orders.rs:function:legacy"));
+        assert!(!result
+            .nodes
+            .iter()
+            .any(|n| n.props["path"] == "vendor/unindexed.rs"));
+
+        let has_edge = |from: &str, to: &str, kind: &str| {
+            result
+                .edges
+                .iter()
+                .any(|e| e.from == from && e.to == to && e.kind == kind)
+        };
+        assert!(has_edge(
+            "file:src/orders.rs",
+            "sym:rs:src/orders.rs:trait:Priced",
+            "defines"
+        ));
+        assert!(has_edge(
+            "file:src/main.rs",
+            "sym:rs:src/main.rs:function:main",
+            "defines"
+        ));
+        assert!(has_edge(
+            "sym:rs:src/orders.rs:function:total",
+            "sym:rs:src/orders.rs:struct:Order",
+            "references"
+        ));
+        assert!(has_edge(
+            "sym:rs:src/main.rs:function:main",
+            "sym:rs:src/orders.rs:function:total",
+            "references"
+        ));
+        assert!(has_edge(
+            "sym:rs:src/orders.rs:struct:Order",
+            "sym:rs:src/orders.rs:trait:Priced",
+            "implements"
+        ));
+        let reference = result
+            .edges
+            .iter()
+            .find(|e| e.kind == "references" && e.from == "sym:rs:src/main.rs:function:main")
+            .unwrap();
+        assert_eq!(reference.props["count"], 1);
+        assert_eq!(reference.props["line"], 3);
+    }
+
+    #[test]
+    fn test_replace_mode_drops_unmatched_heuristic_symbols() {
+        let index = parse_scip(
+            &sample_index(),
+            Path::new("index.scip"),
+            Path::new("/nonexistent"),
+            PreciseMode::Replace,
+        )
+        .unwrap();
+        let mut result = extraction();
+        let stats = merge_precise_index(&mut result, &index, "main");
+
+        assert_eq!(stats.removed, 1);
+        assert!(!result
+            .nodes
+            .iter()
+            .any(|n| n.id == "sym:rs:src/orders.rs:function:legacy"));
+        assert!(result
+            .nodes
+            .iter()
+            .any(|n| n.id == "sym:rs:src/orders.rs:function:total"));
+        assert!(!result
+            .edges
+            .iter()
+            .any(|e| e.to == "sym:rs:src/orders.rs:function:legacy"));
+        assert!(result
+            .nodes
+            .iter()
+            .any(|n| n.id == "sym:rs:src/orders.rs:struct:Order"));
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("precise/scip.json");
+        assert!(read_precise_index(&path).unwrap().is_none());
+
+        let index = parse_scip(
+            &sample_index(),
+            Path::new("index.scip"),
+            Path::new("/nonexistent"),
+            PreciseMode::Replace,
+        )
+        .unwrap();
+        write_precise_index(&path, &index).unwrap();
+        let read = read_precise_index(&path).unwrap().unwrap();
+        assert_eq!(read.mode, PreciseMode::Replace);
+        assert_eq!(read.symbol_count(), index.symbol_count());
+        assert_eq!(read.reference_count(), index.reference_count());
+    }
+
+    #[test]
+    fn test_project_prefix() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("crates/shop")).unwrap();
+        let root = format!("file://{}/crates/shop/", temp.path().display());
+        assert_eq!(project_prefix(&root, temp.path()), "crates/shop/");
+        assert_eq!(
+            project_prefix(&format!("file://{}", temp.path().display()), temp.path()),
+            ""
+        );
+        assert_eq!(project_prefix("file:///elsewhere", temp.path()), "");
+        assert_eq!(project_prefix("", temp.path()), "");
+    }
+}
@@ -0,0 +1,588 @@
+//! Symbol name index for `gik symbols`.
+//!
+//! KG sync writes a compact list of every symbol node to
+//! `.guided/knowledge/<branch>/kg/symbols.json`. Searching it avoids opening
+//! the KG store, which keeps "go to symbol" lookups fast.
+//!
+//! ## Matching
+//!
+//! Queries match symbol names case-insensitively, in four tiers:
+//!
+//! 1. **Exact** - the whole name (`Parser` finds `Parser`)
+//! 2. **Prefix** - the start of the name (`pars` finds `parse_file`)
+//! 3. **Substring** - anywhere in the name (`file` finds `parse_file`)
+//! 4. **Fuzzy** - the query characters in order (`pf` finds `parse_file`)
+//!
+//! Earlier tiers always rank first. Within a tier, matches on word
+//! boundaries (`_`, `-`, `.`, camelCase humps) and runs of consecutive
+//! characters score higher, and shorter names win ties.
+//!
+//! A query containing `.`, `::` or `#` is matched against the qualified name
+//! (`Parser::parse`) instead of the bare name.
+
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::GikError;
+use crate::workspace::Workspace;
+
+use super::entities::KgNode;
+use super::lang::tag_from_extension;
+use super::rules::Glob;
+use super::store::kg_dir_for_branch;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Filename of the symbol index inside the KG directory.
+pub const SYMBOL_INDEX_FILENAME: &str = "symbols.json";
+
+/// Version of the symbol index format.
+pub const SYMBOL_INDEX_VERSION: u32 = 1;
+
+/// Default maximum number of matches returned by a search.
+pub const DEFAULT_SYMBOL_LIMIT: usize = 20;
+
+/// Returns the symbol index path for a branch.
+///
+/// Path: `.guided/knowledge/<branch>/kg/symbols.json`
+pub fn symbol_index_path(workspace: &Workspace, branch: &str) -> PathBuf {
+    kg_dir_for_branch(workspace, branch).join(SYMBOL_INDEX_FILENAME)
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+/// One symbol in the name index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolEntry {
+    /// KG node ID (`sym:...`).
+    pub id: String,
+    pub name: String,
+    /// Symbol kind (`function`, `struct`, `class`, ...).
+    pub kind: String,
+    /// Language tag (`rs`, `ts`, `py`, ...).
+    pub language: String,
+    /// Workspace-relative file path.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
+}
+
+impl SymbolEntry {
+    /// Build an entry from a symbol node, or None for other nodes.
+    pub fn from_node(node: &KgNode) -> Option<Self> {
+        if !node.id.starts_with("sym:") {
+            return None;
+        }
+        let path = node.props["path"].as_str()?;
+        let line = |key: &str| node.props[key].as_u64().map(|n| n as u32);
+
+        Some(Self {
+            id: node.id.clone(),
+            name: node.label.clone(),
+            kind: node.props["symbolKind"]
+                .as_str()
+                .unwrap_or(&node.kind)
+                .to_string(),
+            language: node.props["language"]
+                .as_str()
+                .unwrap_or("unknown")
+                .to_string(),
+            path: path.to_string(),
+            start_line: line("startLine"),
+            end_line: line("endLine"),
+            qualified_name: node.props["qualifiedName"].as_str().map(str::to_string),
+        })
+    }
+
+    /// Location as `path:line`, or just the path when the span is unknown.
+    pub fn location(&self) -> String {
+        match self.start_line {
+            Some(line) => format!("{}:{}", self.path, line),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Name index of every symbol in a branch's KG.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolIndex {
+    pub version: u32,
+    pub symbols: Vec<SymbolEntry>,
+}
+
+impl SymbolIndex {
+    /// Build the index from KG nodes, sorted by path and line.
+    pub fn from_nodes(nodes: &[KgNode]) -> Self {
+        let mut symbols: Vec<SymbolEntry> =
+            nodes.iter().filter_map(SymbolEntry::from_node).collect();
+        symbols.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then(a.start_line.cmp(&b.start_line))
+                .then(a.name.cmp(&b.name))
+        });
+
+        Self {
+            version: SYMBOL_INDEX_VERSION,
+            symbols,
+        }
+    }
+
+    /// Number of indexed symbols.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether the index has no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Write the index to `path`, creating parent directories.
+    pub fn write(&self, path: &Path) -> Result<(), GikError> {
+        let io_err = |message: String| GikError::BaseStoreIo {
+            path: path.to_path_buf(),
+            message,
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| io_err(e.to_string()))?;
+        }
+        let json = serde_json::to_string(self).map_err(|e| io_err(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| io_err(e.to_string()))
+    }
+
+    /// Read an index, or None if there is none.
+    pub fn read(path: &Path) -> Result<Option<Self>, GikError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let io_err = |message: String| GikError::BaseStoreIo {
+            path: path.to_path_buf(),
+            message,
+        };
+        let content = std::fs::read_to_string(path).map_err(|e| io_err(e.to_string()))?;
+        let index = serde_json::from_str(&content).map_err(|e| io_err(e.to_string()))?;
+        Ok(Some(index))
+    }
+
+    /// Find symbols matching `query`, best matches first.
+    ///
+    /// An empty query lists every symbol that passes the filters.
+    pub fn search(&self, query: &str, opts: &SymbolSearchOptions) -> Vec<SymbolMatch> {
+        let query = query.trim();
+        let qualified = query.contains("::") || query.contains('.') || query.contains('#');
+        let languages: Vec<String> = opts.languages.iter().map(|l| language_tag(l)).collect();
+        let path_glob = opts.path.as_deref().map(Glob::new);
+
+        let mut matches: Vec<SymbolMatch> = self
+            .symbols
+            .iter()
+            .filter(|s| {
+                opts.kinds.is_empty() || opts.kinds.iter().any(|k| k.eq_ignore_ascii_case(&s.kind))
+            })
+            .filter(|s| languages.is_empty() || languages.contains(&s.language))
+            .filter(|s| path_glob.as_ref().is_none_or(|g| g.is_match(&s.path)))
+            .filter_map(|s| {
+                let target = match (&s.qualified_name, qualified) {
+                    (Some(q), true) => q.as_str(),
+                    _ => s.name.as_str(),
+                };
+                let (match_kind, score) = score_name(query, target)?;
+                Some(SymbolMatch {
+                    entry: s.clone(),
+                    match_kind,
+                    score,
+                })
+            })
+            .collect();
+
+        matches.sort_by(compare_matches);
+        if opts.limit > 0 {
+            matches.truncate(opts.limit);
+        }
+        matches
+    }
+}
+
+// ============================================================================
+// Search
+// ============================================================================
+
+/// Filters and limits for [`SymbolIndex::search`].
+#[derive(Debug, Clone)]
+pub struct SymbolSearchOptions {
+    /// Only symbols of these kinds (any, when empty).
+    pub kinds: Vec<String>,
+    /// Only symbols in these languages, by tag, name or extension (any, when empty).
+    pub languages: Vec<String>,
+    /// Only symbols in files matching this path or glob.
+    pub path: Option<String>,
+    /// Maximum number of matches (0 = unlimited).
+    pub limit: usize,
+}
+
+impl Default for SymbolSearchOptions {
+    fn default() -> Self {
+        Self {
+            kinds: Vec::new(),
+            languages: Vec::new(),
+            path: None,
+            limit: DEFAULT_SYMBOL_LIMIT,
+        }
+    }
+}
+
+impl SymbolSearchOptions {
+    /// Create options with no filters and the default limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict matches to the given symbol kinds.
+    pub fn with_kinds(mut self, kinds: Vec<String>) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
+    /// Restrict matches to the given languages.
+    pub fn with_languages(mut self, languages: Vec<String>) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// Restrict matches to files under a path or matching a glob.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Set the maximum number of matches.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+/// How a query matched a symbol name, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolMatchKind {
+    Exact,
+    Prefix,
+    Substring,
+    Fuzzy,
+}
+
+impl SymbolMatchKind {
+    /// Lowercase name, as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::Prefix => "prefix",
+            Self::Substring => "substring",
+            Self::Fuzzy => "fuzzy",
+        }
+    }
+}
+
+/// A symbol matched by a search.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolMatch {
+    #[serde(flatten)]
+    pub entry: SymbolEntry,
+    pub match_kind: SymbolMatchKind,
+    /// Score within the match tier (higher is better).
+    pub score: i64,
+}
+
+/// Result of `gik symbols`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolSearchReport {
+    pub branch: String,
+    pub query: String,
+    /// Symbols in the branch index.
+    pub indexed_symbols: usize,
+    /// Matches before the limit was applied.
+    pub total_matches: usize,
+    pub symbols: Vec<SymbolMatch>,
+}
+
+impl SymbolSearchReport {
+    /// Search `index` for a branch, counting matches past the limit.
+    pub fn new(branch: &str, query: &str, index: &SymbolIndex, opts: &SymbolSearchOptions) -> Self {
+        let unlimited = SymbolSearchOptions {
+            limit: 0,
+            ..opts.clone()
+        };
+        let mut symbols = index.search(query, &unlimited);
+        let total_matches = symbols.len();
+        if opts.limit > 0 {
+            symbols.truncate(opts.limit);
+        }
+
+        Self {
+            branch: branch.to_string(),
+            query: query.to_string(),
+            indexed_symbols: index.len(),
+            total_matches,
+            symbols,
+        }
+    }
+}
+
+fn compare_matches(a: &SymbolMatch, b: &SymbolMatch) -> Ordering {
+    a.match_kind
+        .cmp(&b.match_kind)
+        .then(b.score.cmp(&a.score))
+        .then(a.entry.name.len().cmp(&b.entry.name.len()))
+        .then(a.entry.path.cmp(&b.entry.path))
+        .then(a.entry.start_line.cmp(&b.entry.start_line))
+}
+
+/// Normalize a language name or extension to its KG tag.
+fn language_tag(language: &str) -> String {
+    let language = language.trim().to_lowercase();
+    let tag = match language.as_str() {
+        "rust" => "rs",
+        "typescript" => "ts",
+        "javascript" => "js",
+        "python" => "py",
+        "ruby" => "rb",
+        "csharp" | "c#" => "cs",
+        "golang" => "go",
+        "kotlin" => "kt",
+        "c++" => "cpp",
+        "markdown" => "md",
+        other => match tag_from_extension(other) {
+            "unknown" => return language,
+            tag => tag,
+        },
+    };
+    tag.to_string()
+}
+
+/// Match tier and score of `query` against `name`, or None for no match.
+fn score_name(query: &str, name: &str) -> Option<(SymbolMatchKind, i64)> {
+    let query_lower = query.to_lowercase();
+    let name_lower = name.to_lowercase();
+    // Smart case: a query with capitals earns a point for matching case
+    let smart_case = query.chars().any(char::is_uppercase);
+    let case_bonus = |matched: &str| i64::from(smart_case && name.contains(matched));
+
+    if name_lower == query_lower {
+        return Some((SymbolMatchKind::Exact, case_bonus(query)));
+    }
+    if name_lower.starts_with(&query_lower) {
+        return Some((SymbolMatchKind::Prefix, case_bonus(query)));
+    }
+    if let Some(pos) = name_lower.find(&query_lower) {
+        let boundary = is_boundary(name, pos);
+        return Some((
+            SymbolMatchKind::Substring,
+            i64::from(boundary) * 10 + case_bonus(query),
+        ));
+    }
+    fuzzy_score(&query_lower, name).map(|score| (SymbolMatchKind::Fuzzy, score))
+}
+
+/// Score a subsequence match, or None if `query` is not a subsequence.
+///
+/// Characters are matched greedily, preferring a word boundary when one is
+/// reachable before the next plain occurrence is needed.
+fn fuzzy_score(query_lower: &str, name: &str) -> Option<i64> {
+    let chars: Vec<(usize, char)> = name.char_indices().collect();
+    let mut score = 0i64;
+    let mut next = 0usize;
+    let mut last: Option<usize> = None;
+
+    for q in query_lower.chars() {
+        let matches = |&(_, c): &(usize, char)| c.to_lowercase().eq(q.to_lowercase());
+        let first = chars[next..].iter().position(matches)? + next;
+        // A later boundary match beats a mid-word one, unless the mid-word
+        // match continues a run
+        let pos = if last.is_some_and(|l| l + 1 == first) || is_boundary(name, chars[first].0) {
+            first
+        } else {
+            chars[first..]
+                .iter()
+                .position(|ch| matches(ch) && is_boundary(name, ch.0))
+                .map_or(first, |p| p + first)
+        };
+
+        score += 1;
+        if is_boundary(name, chars[pos].0) {
+            score += 8;
+        }
+        match last {
+            Some(l) if l + 1 == pos => score += 5,
+            Some(l) => score -= (pos - l - 1).min(5) as i64,
+            None => score -= pos.min(5) as i64,
+        }
+        last = Some(pos);
+        next = pos + 1;
+    }
+
+    Some(score)
+}
+
+/// Whether the character at byte offset `pos` starts a word.
+fn is_boundary(name: &str, pos: usize) -> bool {
+    if pos == 0 {
+        return true;
+    }
+    let prev = name[..pos].chars().next_back();
+    let current = name[pos..].chars().next();
+    match (prev, current) {
+        (Some('_' | '-' | '.' | ':' | '#' | '/' | ' '), _) => true,
+        (Some(p), Some(c)) => p.is_lowercase() && c.is_uppercase(),
+        _ => false,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn symbol(path: &str, kind: &str, name: &str, line: u32) -> KgNode {
+        KgNode::new(format!("sym:rs:{}:{}:{}", path, kind, name), kind, name).with_props(json!({
+            "path": path,
+            "language": crate::kg::lang::tag_from_path(path),
+            "symbolKind": kind,
+            "startLine": line,
+            "endLine": line + 5,
+        }))
+    }
+
+    fn index() -> SymbolIndex {
+        SymbolIndex::from_nodes(&[
+            KgNode::new("file:src/parser.rs", "file", "parser.rs")
+                .with_props(json!({ "path": "src/parser.rs" })),
+            symbol("src/parser.rs", "function", "parse_file", 10),
+            symbol("src/parser.rs", "struct", "Parser", 3),
+            symbol("src/parser.rs", "function", "reparse", 40),
+            symbol("src/config.rs", "function", "print_frame", 12),
+            symbol("web/app.ts", "function", "parseFile", 7),
+            symbol("src/parser.rs", "method", "parse", 20).with_props(json!({
+                "path": "src/parser.rs",
+                "language": "rs",
+                "symbolKind": "method",
+                "startLine": 20,
+                "qualifiedName": "Parser::parse",
+            })),
+        ])
+    }
+
+    fn names(matches: &[SymbolMatch]) -> Vec<&str> {
+        matches.iter().map(|m| m.entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_index_keeps_only_symbols() {
+        let index = index();
+        assert_eq!(index.len(), 6);
+        assert_eq!(index.symbols[0].path, "src/config.rs");
+        assert_eq!(index.symbols[1].name, "Parser");
+        assert_eq!(index.symbols[1].location(), "src/parser.rs:3");
+    }
+
+    #[test]
+    fn test_search_ranks_exact_then_prefix_then_substring_then_fuzzy() {
+        let matches = index().search("parse", &SymbolSearchOptions::new());
+        let kinds: Vec<SymbolMatchKind> = matches.iter().map(|m| m.match_kind).collect();
+
+        assert_eq!(
+            names(&matches),
+            ["parse", "Parser", "parseFile", "parse_file", "reparse"]
+        );
+        assert_eq!(
+            kinds,
+            [
+                SymbolMatchKind::Exact,
+                SymbolMatchKind::Prefix,
+                SymbolMatchKind::Prefix,
+                SymbolMatchKind::Prefix,
+                SymbolMatchKind::Substring,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_prefers_word_boundaries() {
+        let matches = index().search("pf", &SymbolSearchOptions::new());
+
+        assert!(matches
+            .iter()
+            .all(|m| m.match_kind == SymbolMatchKind::Fuzzy));
+        // parse_file and parseFile hit both humps; print_frame too, but later
+        assert_eq!(names(&matches)[..2], ["parseFile", "parse_file"]);
+        assert!(names(&matches).contains(&"print_frame"));
+        assert!(fuzzy_score("pf", "parse_file") > fuzzy_score("pf", "shipfast"));
+        assert_eq!(fuzzy_score("xyz", "parse_file"), None);
+    }
+
+    #[test]
+    fn test_search_filters() {
+        let index = index();
+
+        let opts = SymbolSearchOptions::new().with_kinds(vec!["Struct".to_string()]);
+        assert_eq!(names(&index.search("p", &opts)), ["Parser"]);
+
+        let opts = SymbolSearchOptions::new().with_languages(vec!["typescript".to_string()]);
+        assert_eq!(names(&index.search("parse", &opts)), ["parseFile"]);
+
+        let opts = SymbolSearchOptions::new().with_path("src/**/config.rs");
+        assert_eq!(names(&index.search("", &opts)), ["print_frame"]);
+
+        let opts = SymbolSearchOptions::new().with_limit(2);
+        assert_eq!(index.search("parse", &opts).len(), 2);
+        let report = SymbolSearchReport::new("main", "parse", &index, &opts);
+        assert_eq!(report.total_matches, 5);
+        assert_eq!(report.symbols.len(), 2);
+        assert_eq!(report.indexed_symbols, 6);
+    }
+
+    #[test]
+    fn test_qualified_query_matches_qualified_name() {
+        let matches = index().search("Parser::pa", &SymbolSearchOptions::new());
+        assert_eq!(names(&matches), ["parse"]);
+        assert_eq!(matches[0].match_kind, SymbolMatchKind::Prefix);
+    }
+
+    #[test]
+    fn test_language_aliases() {
+        assert_eq!(language_tag("Rust"), "rs");
+        assert_eq!(language_tag("tsx"), "ts");
+        assert_eq!(language_tag("C#"), "cs");
+        assert_eq!(language_tag("elixir"), "elixir");
+    }
+
+    #[test]
+    fn test_index_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("kg").join(SYMBOL_INDEX_FILENAME);
+
+        assert_eq!(SymbolIndex::read(&path).unwrap(), None);
+        let index = index();
+        index.write(&path).unwrap();
+        assert_eq!(SymbolIndex::read(&path).unwrap(), Some(index));
+    }
+}
@@ -4,9 +4,11 @@
 //! results for a branch. Sync uses a **full rebuild** strategy:
 //!
 //! 1. Run extractor for the branch
-//! 2. Clear existing KG data
-//! 3. Write new nodes and edges
-//! 4. Stats are computed from store data
+//! 2. Compute import-graph centrality for file nodes
+//! 3. Clear existing KG data
+//! 4. Write new nodes and edges
+//! 5. Write the symbol name index (`kg/symbols.json`)
+//! 6. Stats are computed from store data
 //!
 //! ## Full Rebuild Strategy
 //!
@@ -25,8 +27,10 @@ use std::fs;
 use crate::errors::GikError;
 use crate::workspace::Workspace;
 
+use super::centrality::annotate_centrality;
 use super::extractor::{DefaultKgExtractor, KgExtractionConfig, KgExtractor};
 use super::store::{kg_dir_for_branch, open_kg_store};
+use super::symbols::{symbol_index_path, SymbolIndex};
 
 // ============================================================================
 // KgSyncResult
@@ -66,9 +70,11 @@ impl KgSyncResult {
 ///
 /// This function performs a **full rebuild** of the KG:
 /// 1. Runs extraction on all bases
-/// 2. Clears existing KG data (if any)
-/// 3. Writes new nodes and edges
-/// 4. Stats are derived from store data
+/// 2. Stores centrality scores on file nodes
+/// 3. Clears existing KG data (if any)
+/// 4. Writes new nodes and edges
+/// 5. Writes the symbol name index used by `gik symbols`
+/// 6. Stats are derived from store data
 ///
 /// # Arguments
 ///
@@ -91,7 +97,7 @@ pub fn sync_branch_kg(
     cfg: &KgExtractionConfig,
 ) -> Result<KgSyncResult, GikError> {
     // Run extraction
-    let extraction = extractor.extract_for_branch(workspace, branch, cfg)?;
+    let mut extraction = extractor.extract_for_branch(workspace, branch, cfg)?;
 
     // If no nodes extracted, skip KG creation (keep it lazy)
     if extraction.nodes.is_empty() {
@@ -104,6 +110,9 @@ pub fn sync_branch_kg(
         });
     }
 
+    // Rank files by their place in the import graph
+    annotate_centrality(&mut extraction.nodes, &extraction.edges);
+
     // Open the KG store (creates directory if needed)
     let store = open_kg_store(workspace, branch)?;
 
@@ -125,6 +134,9 @@ pub fn sync_branch_kg(
     // Flush to ensure data is persisted
     store.flush()?;
 
+    // Index symbol names for `gik symbols`
+    SymbolIndex::from_nodes(&extraction.nodes).write(&symbol_index_path(workspace, branch))?;
+
     Ok(KgSyncResult {
         nodes_written: nodes_count,
         edges_written: edges_count,
@@ -0,0 +1,694 @@
+//! Test-to-code linkage for the KG.
+//!
+//! Test files are classified by language convention and marked with
+//! `isTest: true`; Rust files with a `#[cfg(test)]` module get
+//! `inlineTests: true`. Each test file then gets `tests` edges to the code it
+//! exercises:
+//!
+//! | `via` | Evidence |
+//! |-------|----------|
+//! | `import` | The test file imports a non-test file |
+//! | `name` | The file names match (`users_test.go` → `users.go`) |
+//! | `symbol` | The test references a function or type defined elsewhere |
+//!
+//! Edges to files carry every `via` that applies, plus the referenced
+//! `symbols`; symbol references also get an edge to the symbol node.
+//!
+//! ## Conventions
+//!
+//! | Language | Test files |
+//! |----------|------------|
+//! | Go | `*_test.go` |
+//! | JS/TS | `*.test.ts`, `*.spec.tsx`, ..., anything under `__tests__/` |
+//! | Python | `test_*.py`, `*_test.py`, `conftest.py` |
+//! | Rust | `*_test.rs`, `*_tests.rs`; `#[cfg(test)]` modules are inline tests |
+//! | Java/Kotlin/Scala/C#/PHP/Swift | `*Test`, `*Tests`, `*IT`, `*Spec`; `src/test/`, `*.Tests/` |
+//! | Ruby | `*_spec.rb`, `*_test.rb` |
+//! | C/C++ | `*_test.cc`, `*_unittest.cpp`, `test_*.c` |
+//!
+//! Files under a `test/`, `tests/` or `spec/` directory are tests in any
+//! language.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::json;
+
+use super::entities::{KgEdge, KgNode};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Maximum symbol references linked per test file.
+const MAX_SYMBOL_LINKS_PER_TEST: usize = 50;
+
+/// Shortest symbol name linked from a reference in a test.
+const MIN_SYMBOL_NAME_LEN: usize = 4;
+
+/// Symbol kinds that tests are linked to.
+const LINKED_SYMBOL_KINDS: &[&str] = &[
+    "function",
+    "method",
+    "class",
+    "struct",
+    "interface",
+    "trait",
+    "enum",
+];
+
+/// File stems too generic to match a test to its subject by name.
+const GENERIC_STEMS: &[&str] = &[
+    "mod", "lib", "main", "index", "__init__", "conftest", "setup", "common", "helpers", "utils",
+    "fixtures",
+];
+
+// ============================================================================
+// Classification
+// ============================================================================
+
+/// Language family of a source file, for matching tests to code by name.
+fn family(ext: &str) -> Option<&'static str> {
+    Some(match ext {
+        "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts" => "js",
+        "py" => "py",
+        "rs" => "rs",
+        "go" => "go",
+        "java" | "kt" | "kts" | "scala" | "groovy" => "jvm",
+        "cs" => "cs",
+        "rb" => "rb",
+        "php" => "php",
+        "swift" => "swift",
+        "c" | "cc" | "cpp" | "cxx" | "h" | "hh" | "hpp" => "c",
+        _ => return None,
+    })
+}
+
+/// Split a path into directory segments, file stem and extension.
+fn split_path(path: &str) -> (Vec<&str>, &str, &str) {
+    let mut segments: Vec<&str> = path.split('/').collect();
+    let name = segments.pop().unwrap_or_default();
+    let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+    (segments, stem, ext)
+}
+
+/// Strip a test affix from a file stem, returning the subject's stem.
+fn strip_test_affix<'a>(stem: &'a str, family: &str) -> Option<&'a str> {
+    let stripped = match family {
+        "go" => stem.strip_suffix("_test"),
+        "js" => stem
+            .strip_suffix(".test")
+            .or_else(|| stem.strip_suffix(".spec")),
+        "py" => stem
+            .strip_prefix("test_")
+            .or_else(|| stem.strip_suffix("_test")),
+        "rs" => stem
+            .strip_suffix("_tests")
+            .or_else(|| stem.strip_suffix("_test")),
+        "rb" => stem
+            .strip_suffix("_spec")
+            .or_else(|| stem.strip_suffix("_test")),
+        "jvm" | "cs" | "php" | "swift" => ["Tests", "Test", "IT", "Spec"]
+            .iter()
+            .find_map(|suffix| stem.strip_suffix(suffix)),
+        "c" => stem
+            .strip_suffix("_unittest")
+            .or_else(|| stem.strip_suffix("_test"))
+            .or_else(|| stem.strip_prefix("test_")),
+        _ => None,
+    };
+    stripped.filter(|s| !s.is_empty())
+}
+
+/// Whether a directory segment holds tests.
+fn is_test_dir(segment: &str) -> bool {
+    matches!(segment, "test" | "tests" | "spec" | "__tests__" | "Tests")
+        || segment.ends_with(".Tests")
+        || segment.ends_with(".Test")
+}
+
+/// Whether a path is a test file by its language's conventions.
+pub fn is_test_path(path: &str) -> bool {
+    let (segments, stem, ext) = split_path(path);
+    let Some(family) = family(ext) else {
+        return false;
+    };
+    if family == "py" && stem == "conftest" {
+        return true;
+    }
+    strip_test_affix(stem, family).is_some() || segments.iter().any(|s| is_test_dir(s))
+}
+
+/// Whether a source file contains inline tests (a Rust `#[cfg(test)]` module).
+pub fn has_inline_tests(path: &str, text: &str) -> bool {
+    static CFG_TEST: OnceLock<Regex> = OnceLock::new();
+    let re = CFG_TEST.get_or_init(|| Regex::new(r"#\[cfg\(test\)\]\s*(?:pub\s+)?mod\s").unwrap());
+    path.ends_with(".rs") && re.is_match(text)
+}
+
+/// The subject a test file is named after: its stem without test affixes.
+fn subject_stem(path: &str) -> Option<(&str, &'static str)> {
+    let (_, stem, ext) = split_path(path);
+    let family = family(ext)?;
+    let subject = strip_test_affix(stem, family).unwrap_or(stem);
+    (!GENERIC_STEMS.contains(&subject)).then_some((subject, family))
+}
+
+// ============================================================================
+// Linking
+// ============================================================================
+
+/// Evidence collected for one `tests` edge.
+#[derive(Debug, Default)]
+struct TestLink {
+    via: BTreeSet<&'static str>,
+    symbols: BTreeSet<String>,
+}
+
+/// Mark test files and build `tests` edges.
+///
+/// `nodes` are updated in place: code `file` nodes that are tests get
+/// `isTest: true` and files with inline tests get `inlineTests: true`.
+/// `texts` maps workspace-relative paths to file contents; files without text
+/// are classified by path only.
+pub fn link_tests(
+    nodes: &mut [KgNode],
+    edges: &[KgEdge],
+    texts: &HashMap<&str, String>,
+) -> Vec<KgEdge> {
+    // path -> file node ID, split into tests and code
+    let mut tests: Vec<(String, String)> = Vec::new();
+    let mut code: HashMap<String, String> = HashMap::new();
+    for node in nodes.iter_mut().filter(|n| n.kind == "file") {
+        let Some(path) = node.props["path"].as_str().map(str::to_string) else {
+            continue;
+        };
+        let Some(props) = node.props.as_object_mut() else {
+            continue;
+        };
+        if is_test_path(&path) {
+            props.insert("isTest".to_string(), json!(true));
+            tests.push((path, node.id.clone()));
+        } else {
+            if texts
+                .get(path.as_str())
+                .is_some_and(|text| has_inline_tests(&path, text))
+            {
+                props.insert("inlineTests".to_string(), json!(true));
+            }
+            code.insert(node.id.clone(), path);
+        }
+    }
+    if tests.is_empty() {
+        return Vec::new();
+    }
+    tests.sort();
+
+    // (stem, family) -> code files, for name matching
+    let mut by_stem: HashMap<(&str, &str), Vec<(&str, &str)>> = HashMap::new();
+    for (id, path) in &code {
+        let (_, stem, ext) = split_path(path);
+        if let Some(family) = family(ext) {
+            by_stem
+                .entry((stem, family))
+                .or_default()
+                .push((path.as_str(), id.as_str()));
+        }
+    }
+
+    // name -> (symbol ID, file ID) for symbols defined in code files
+    let code_by_path: HashMap<&str, &str> = code
+        .iter()
+        .map(|(id, path)| (path.as_str(), id.as_str()))
+        .collect();
+    let mut symbols: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    for node in nodes.iter() {
+        if !node.id.starts_with("sym:") || !LINKED_SYMBOL_KINDS.contains(&node.kind.as_str()) {
+            continue;
+        }
+        let file_id = node.props["path"]
+            .as_str()
+            .and_then(|p| code_by_path.get(p));
+        if let Some(file_id) = file_id {
+            if node.label.chars().count() >= MIN_SYMBOL_NAME_LEN {
+                symbols
+                    .entry(node.label.as_str())
+                    .or_default()
+                    .push((node.id.as_str(), file_id));
+            }
+        }
+    }
+
+    let mut imports: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in edges.iter().filter(|e| e.kind == "imports") {
+        if code.contains_key(&edge.to) {
+            imports
+                .entry(edge.from.as_str())
+                .or_default()
+                .push(&edge.to);
+        }
+    }
+
+    let mut result = Vec::new();
+    for (path, test_id) in &tests {
+        let mut links: HashMap<String, TestLink> = HashMap::new();
+
+        for target in imports.get(test_id.as_str()).into_iter().flatten() {
+            links
+                .entry(target.to_string())
+                .or_default()
+                .via
+                .insert("import");
+        }
+        if let Some(target) = match_by_name(path, &by_stem) {
+            links
+                .entry(target.to_string())
+                .or_default()
+                .via
+                .insert("name");
+        }
+
+        let mut symbol_targets: Vec<&str> = Vec::new();
+        if let Some(text) = texts.get(path.as_str()) {
+            let linked_files: HashSet<String> = links.keys().cloned().collect();
+            for name in identifiers(text) {
+                let Some(defs) = symbols.get(name) else {
+                    continue;
+                };
+                // Names defined in several files only count in files already linked
+                let files: HashSet<&str> = defs.iter().map(|(_, f)| *f).collect();
+                let defs: Vec<&(&str, &str)> = if files.len() == 1 {
+                    defs.iter().collect()
+                } else {
+                    defs.iter()
+                        .filter(|(_, f)| linked_files.contains(*f))
+                        .collect()
+                };
+                for (symbol_id, file_id) in defs {
+                    if symbol_targets.len() >= MAX_SYMBOL_LINKS_PER_TEST {
+                        break;
+                    }
+                    symbol_targets.push(symbol_id);
+                    let link = links.entry(file_id.to_string()).or_default();
+                    link.via.insert("symbol");
+                    link.symbols.insert(name.to_string());
+                }
+            }
+        }
+
+        let mut targets: Vec<(String, TestLink)> = links.into_iter().collect();
+        targets.sort_by(|a, b| a.0.cmp(&b.0));
+        for (target, link) in targets {
+            let mut props = json!({ "via": link.via });
+            if !link.symbols.is_empty() {
+                props["symbols"] = json!(link.symbols);
+            }
+            result.push(KgEdge::new(test_id, &target, "tests").with_props(props));
+        }
+        symbol_targets.sort();
+        symbol_targets.dedup();
+        for symbol_id in symbol_targets {
+            result.push(
+                KgEdge::new(test_id, symbol_id, "tests").with_props(json!({ "via": ["symbol"] })),
+            );
+        }
+    }
+
+    result
+}
+
+/// Find the code file a test is named after.
+///
+/// Among code files with the subject's stem and language, the one sharing
+/// the most directory names with the test wins; ties link nothing.
+fn match_by_name<'a>(
+    test_path: &str,
+    by_stem: &HashMap<(&str, &str), Vec<(&str, &'a str)>>,
+) -> Option<&'a str> {
+    let (subject, family) = subject_stem(test_path)?;
+    let candidates = by_stem.get(&(subject, family))?;
+    let (test_dirs, _, _) = split_path(test_path);
+    let test_dirs: HashSet<&str> = test_dirs.into_iter().filter(|d| !is_test_dir(d)).collect();
+
+    let scored: Vec<(usize, &str)> = candidates
+        .iter()
+        .map(|(path, id)| {
+            let (dirs, _, _) = split_path(path);
+            (dirs.iter().filter(|d| test_dirs.contains(*d)).count(), *id)
+        })
+        .collect();
+    let best = scored.iter().map(|(score, _)| *score).max()?;
+    let mut winners = scored.iter().filter(|(score, _)| *score == best);
+    match (winners.next(), winners.next()) {
+        (Some((_, id)), None) => Some(id),
+        _ => None,
+    }
+}
+
+/// Distinct identifiers in a text, in order of first appearance.
+fn identifiers(text: &str) -> Vec<&str> {
+    static IDENT: OnceLock<Regex> = OnceLock::new();
+    let re = IDENT.get_or_init(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap());
+    let mut seen = HashSet::new();
+    re.find_iter(text)
+        .map(|m| m.as_str())
+        .filter(|name| !name.starts_with("test") && !name.starts_with("Test"))
+        .filter(|name| seen.insert(*name))
+        .collect()
+}
+
+// ============================================================================
+// Coverage Lookup
+// ============================================================================
+
+/// A test covering a file or symbol.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoveringTest {
+    /// Workspace-relative path of the test file.
+    pub path: String,
+    /// How the test was linked: `import`, `name`, `symbol` or `inline`.
+    pub via: Vec<String>,
+    /// Symbols of the target referenced by the test.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<String>,
+}
+
+/// Result of `gik kg tests`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCoverageReport {
+    /// Branch that was queried.
+    pub branch: String,
+    /// The file path or symbol name asked about.
+    pub target: String,
+    /// IDs of the nodes the target resolved to (empty if nothing matched).
+    pub resolved: Vec<String>,
+    /// Covering tests, symbol-level matches first.
+    pub tests: Vec<CoveringTest>,
+}
+
+impl TestCoverageReport {
+    /// Find the tests covering `target`: a file path, a symbol name or
+    /// qualified name, or a node ID.
+    ///
+    /// For a file, tests linked to the file or any symbol in it count; for a
+    /// symbol, tests linked to the symbol or its file.
+    pub fn new(branch: &str, target: &str, nodes: &[KgNode], edges: &[KgEdge]) -> Self {
+        let mut report = Self {
+            branch: branch.to_string(),
+            target: target.to_string(),
+            resolved: Vec::new(),
+            tests: Vec::new(),
+        };
+
+        let path_of = |n: &KgNode| n.props["path"].as_str().map(str::to_string);
+        let file = nodes
+            .iter()
+            .find(|n| n.kind == "file" && n.props["path"] == target);
+        let targets: Vec<&KgNode> = match file {
+            Some(file) => std::iter::once(file)
+                .chain(
+                    nodes
+                        .iter()
+                        .filter(|n| n.id.starts_with("sym:") && n.props["path"] == target),
+                )
+                .collect(),
+            None => {
+                let symbols: Vec<&KgNode> = nodes
+                    .iter()
+                    .filter(|n| n.id.starts_with("sym:"))
+                    .filter(|n| n.label == target || n.props["qualifiedName"] == target)
+                    .collect();
+                let matched = if symbols.is_empty() {
+                    nodes.iter().filter(|n| n.id == target).collect()
+                } else {
+                    symbols
+                };
+                report.resolved = matched.iter().map(|n| n.id.clone()).collect();
+                // Tests of a symbol's file may exercise it too
+                let files: HashSet<String> = matched.iter().filter_map(|n| path_of(n)).collect();
+                matched
+                    .into_iter()
+                    .chain(nodes.iter().filter(|n| {
+                        n.kind == "file" && path_of(n).is_some_and(|p| files.contains(&p))
+                    }))
+                    .collect()
+            }
+        };
+        if let Some(file) = file {
+            report.resolved = vec![file.id.clone()];
+        }
+        if report.resolved.is_empty() {
+            return report;
+        }
+
+        let target_ids: HashMap<&str, &KgNode> =
+            targets.iter().map(|n| (n.id.as_str(), *n)).collect();
+        let test_paths: HashMap<&str, &str> = nodes
+            .iter()
+            .filter(|n| n.kind == "file")
+            .filter_map(|n| n.props["path"].as_str().map(|p| (n.id.as_str(), p)))
+            .collect();
+
+        let mut by_test: HashMap<String, TestLink> = HashMap::new();
+        for edge in edges.iter().filter(|e| e.kind == "tests") {
+            let Some(target) = target_ids.get(edge.to.as_str()) else {
+                continue;
+            };
+            let Some(path) = test_paths.get(edge.from.as_str()) else {
+                continue;
+            };
+            let link = by_test.entry(path.to_string()).or_default();
+            for via in edge.props["via"].as_array().into_iter().flatten() {
+                match via.as_str() {
+                    Some("import") => link.via.insert("import"),
+                    Some("name") => link.via.insert("name"),
+                    Some("symbol") => link.via.insert("symbol"),
+                    _ => false,
+                };
+            }
+            if target.id.starts_with("sym:") {
+                link.symbols.insert(target.label.clone());
+            }
+        }
+        for node in targets.iter().filter(|n| n.kind == "file") {
+            if node.props["inlineTests"] == true {
+                if let Some(path) = path_of(node) {
+                    by_test.entry(path).or_default().via.insert("inline");
+                }
+            }
+        }
+
+        let mut tests: Vec<CoveringTest> = by_test
+            .into_iter()
+            .map(|(path, link)| CoveringTest {
+                path,
+                via: link.via.into_iter().map(str::to_string).collect(),
+                symbols: link.symbols.into_iter().collect(),
+            })
+            .collect();
+        tests.sort_by(|a, b| (a.symbols.is_empty(), &a.path).cmp(&(b.symbols.is_empty(), &b.path)));
+        report.tests = tests;
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str) -> KgNode {
+        KgNode::new(format!("file:{}", path), "file", path)
+            .with_props(json!({ "base": "code", "path": path }))
+    }
+
+    fn symbol(path: &str, kind: &str, name: &str) -> KgNode {
+        KgNode::new(format!("sym:{}:{}:{}", path, kind, name), kind, name)
+            .with_props(json!({ "path": path, "qualifiedName": name }))
+    }
+
+    #[test]
+    fn test_is_test_path() {
+        let tests = [
+            "pkg/users/users_test.go",
+            "web/src/Button.test.tsx",
+            "web/src/api.spec.ts",
+            "web/src/__tests__/Button.tsx",
+            "tests/test_users.py",
+            "app/users_test.py",
+            "app/conftest.py",
+            "crates/core/tests/sync.rs",
+            "src/parser_tests.rs",
+            "src/test/java/com/acme/UsersTest.java",
+            "src/main/kotlin/UsersIT.kt",
+            "Acme.Users.Tests/UsersServiceTests.cs",
+            "spec/models/user_spec.rb",
+            "tests/Unit/UserTest.php",
+            "base/strings_unittest.cc",
+        ];
+        for path in tests {
+            assert!(is_test_path(path), "{} should be a test", path);
+        }
+
+        let code = [
+            "pkg/users/users.go",
+            "web/src/Button.tsx",
+            "app/testing.py",
+            "src/contest.rs",
+            "src/main/java/com/acme/Users.java",
+            "docs/tests/README.md",
+            "src/latest.rs",
+        ];
+        for path in code {
+            assert!(!is_test_path(path), "{} should not be a test", path);
+        }
+
+        assert!(has_inline_tests(
+            "src/lib.rs",
+            "fn add() {}\n\n#[cfg(test)]\nmod tests {}\n"
+        ));
+        assert!(!has_inline_tests("src/lib.rs", "fn add() {}\n"));
+        assert!(!has_inline_tests("src/lib.py", "#[cfg(test)]\nmod tests"));
+    }
+
+    #[test]
+    fn test_link_tests_by_import_name_and_symbol() {
+        let mut nodes = vec![
+            file("src/users.ts"),
+            file("src/users.test.ts"),
+            file("src/orders.ts"),
+            file("lib/format.go"),
+            file("lib/format_test.go"),
+            file("src/db.rs"),
+            file("tests/db.rs"),
+            symbol("src/users.ts", "function", "createUser"),
+            symbol("src/orders.ts", "class", "OrderService"),
+            symbol("lib/format.go", "function", "FormatName"),
+            symbol("src/db.rs", "function", "get"),
+            symbol("src/db.rs", "struct", "Connection"),
+        ];
+        let edges = vec![KgEdge::new(
+            "file:src/users.test.ts",
+            "file:src/users.ts",
+            "imports",
+        )];
+        let texts: HashMap<&str, String> = [
+            (
+                "src/users.test.ts",
+                "import { createUser } from './users';\nnew OrderService();\n",
+            ),
+            (
+                "lib/format_test.go",
+                "func TestFormat(t *testing.T) { FormatName(\"a\") }",
+            ),
+            ("tests/db.rs", "use app::db::{get, Connection};\n"),
+            ("src/db.rs", "pub fn get() {}\n#[cfg(test)]\nmod tests {}\n"),
+        ]
+        .into_iter()
+        .map(|(p, t)| (p, t.to_string()))
+        .collect();
+
+        let links = link_tests(&mut nodes, &edges, &texts);
+        let find = |from: &str, to: &str| {
+            links
+                .iter()
+                .find(|e| e.from == format!("file:{}", from) && e.to == to)
+                .map(|e| e.props.clone())
+        };
+
+        let users = find("src/users.test.ts", "file:src/users.ts").unwrap();
+        assert_eq!(users["via"], json!(["import", "name", "symbol"]));
+        assert_eq!(users["symbols"], json!(["createUser"]));
+        let orders = find("src/users.test.ts", "file:src/orders.ts").unwrap();
+        assert_eq!(orders["via"], json!(["symbol"]));
+        assert!(find("src/users.test.ts", "sym:src/orders.ts:class:OrderService").is_some());
+
+        let format = find("lib/format_test.go", "file:lib/format.go").unwrap();
+        assert_eq!(format["via"], json!(["name", "symbol"]));
+
+        // `get` is too short to link by reference; `Connection` is not
+        let db = find("tests/db.rs", "file:src/db.rs").unwrap();
+        assert_eq!(db["via"], json!(["name", "symbol"]));
+        assert_eq!(db["symbols"], json!(["Connection"]));
+
+        let props = |path: &str| {
+            nodes
+                .iter()
+                .find(|n| n.props["path"] == path)
+                .unwrap()
+                .props
+                .clone()
+        };
+        assert_eq!(props("tests/db.rs")["isTest"], true);
+        assert_eq!(props("src/db.rs")["inlineTests"], true);
+        assert!(props("src/users.ts").get("isTest").is_none());
+    }
+
+    #[test]
+    fn test_match_by_name_prefers_closest_directory() {
+        let mut nodes = vec![
+            file("src/main/java/com/acme/users/Users.java"),
+            file("src/main/java/com/acme/admin/Users.java"),
+            file("src/test/java/com/acme/users/UsersTest.java"),
+            file("a/parser.py"),
+            file("b/parser.py"),
+            file("tests/test_parser.py"),
+        ];
+        let links = link_tests(&mut nodes, &[], &HashMap::new());
+        let targets: Vec<(&str, &str)> = links
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![(
+                "file:src/test/java/com/acme/users/UsersTest.java",
+                "file:src/main/java/com/acme/users/Users.java"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        let mut nodes = vec![
+            file("src/users.ts"),
+            file("src/users.test.ts"),
+            file("src/api.test.ts"),
+            symbol("src/users.ts", "function", "createUser"),
+            symbol("src/users.ts", "function", "deleteUser"),
+        ];
+        let edges = vec![KgEdge::new(
+            "file:src/api.test.ts",
+            "file:src/users.ts",
+            "imports",
+        )];
+        let texts: HashMap<&str, String> = [("src/users.test.ts", "deleteUser(1)".to_string())]
+            .into_iter()
+            .collect();
+        let links = link_tests(&mut nodes, &edges, &texts);
+
+        let report = TestCoverageReport::new("main", "src/users.ts", &nodes, &links);
+        assert_eq!(report.resolved, vec!["file:src/users.ts"]);
+        let paths: Vec<&str> = report.tests.iter().map(|t| t.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/users.test.ts", "src/api.test.ts"]);
+        assert_eq!(report.tests[0].via, vec!["name", "symbol"]);
+        assert_eq!(report.tests[0].symbols, vec!["deleteUser"]);
+        assert_eq!(report.tests[1].via, vec!["import"]);
+
+        // A symbol is covered by tests referencing it and tests of its file
+        let report = TestCoverageReport::new("main", "deleteUser", &nodes, &links);
+        assert_eq!(
+            report.resolved,
+            vec!["sym:src/users.ts:function:deleteUser"]
+        );
+        assert_eq!(report.tests.len(), 2);
+        assert_eq!(report.tests[0].symbols, vec!["deleteUser"]);
+
+        let report = TestCoverageReport::new("main", "missing", &nodes, &links);
+        assert!(report.resolved.is_empty());
+        assert!(report.tests.is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+//! Text helpers shared by the regex-based KG extractors.
+//!
+//! The endpoint, infrastructure and schema extractors scan raw source text
+//! and report byte offsets; these helpers turn those offsets into line
+//! numbers and block extents.
+
+/// 1-based line number of a byte offset.
+pub(crate) fn line_at(text: &str, offset: usize) -> u32 {
+    text[..offset.min(text.len())].matches('\n').count() as u32 + 1
+}
+
+/// Offset of the brace matching the one at `open`.
+pub(crate) fn matching_brace(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in text[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_at() {
+        let text = "a\nb\nc";
+        assert_eq!(line_at(text, 0), 1);
+        assert_eq!(line_at(text, 2), 2);
+        assert_eq!(line_at(text, 4), 3);
+        // Offsets past the end land on the last line
+        assert_eq!(line_at(text, 100), 3);
+    }
+
+    #[test]
+    fn test_matching_brace() {
+        let text = "x { a { b } c } y";
+        assert_eq!(matching_brace(text, 2), Some(14));
+        assert_eq!(matching_brace(text, 6), Some(10));
+        assert_eq!(matching_brace("{ unclosed", 0), None);
+    }
+}
@@ -51,13 +51,19 @@ pub mod config;
 pub mod constants;
 pub mod db_adapter;
 pub mod embedding;
+pub mod embedding_cache;
 pub mod embedding_config_bridge;
 pub mod engine;
 pub mod errors;
+pub mod eval;
+pub mod feedback;
+pub mod git;
+pub mod insights;
 pub mod kg;
 pub mod log;
 pub mod memory;
 pub mod model_adapter;
+pub mod prompt;
 pub mod query_expansion;
 pub mod reindex;
 pub mod release;
@@ -84,9 +90,9 @@ pub use ask::{
     MemoryEvent, RagChunk, StackSummary, DEFAULT_TOP_K, RAG_BASES,
 };
 pub use base::{
-    append_base_sources, load_base_sources, load_base_stats, save_base_stats, BaseHealthState,
-    BaseSourceEntry, BaseStats, BaseStatsReport, ChunkId, MAX_FILE_LINES, MAX_FILE_SIZE_BYTES,
-    SOURCES_FILENAME, STATS_FILENAME,
+    append_base_sources, load_base_sources, load_base_stats, save_base_stats, stable_content_hash,
+    BaseHealthState, BaseSourceEntry, BaseStats, BaseStatsReport, ChunkId, MAX_FILE_LINES,
+    MAX_FILE_SIZE_BYTES, SOURCES_FILENAME, STATS_FILENAME,
 };
 pub use bm25::{
     load_bm25_index, rrf_fusion, save_bm25_index, Bm25Config, Bm25Index, Bm25SearchResult,
@@ -106,6 +112,7 @@ pub use config::{
     ProjectConfig,
     // Performance constants (Phase 8.1)
     DEFAULT_EMBEDDING_BATCH_SIZE,
+    DEFAULT_EMBEDDING_CACHE,
     DEFAULT_EMBEDDING_WARMUP,
     DEFAULT_MAX_FILE_LINES,
     DEFAULT_MAX_FILE_SIZE_BYTES,
@@ -123,26 +130,45 @@ pub use embedding::{
     ModelCompatibility, ModelInfo, DEFAULT_DIMENSION, DEFAULT_MAX_TOKENS, DEFAULT_MODEL_ID,
     DEFAULT_MODEL_PATH,
 };
+pub use embedding_cache::{with_embedding_cache, CachedEmbeddingBackend, EmbeddingCache};
 pub use engine::GikEngine;
 pub use errors::GikError;
+pub use eval::{
+    run_eval, EvalConfigReport, EvalConfiguration, EvalOptions, EvalQuestion, EvalQuestionResult,
+    EvalReport, EvalSuite, DEFAULT_EVAL_K,
+};
+pub use feedback::{
+    feedback_eval_suite, AskFeedbackEntry, AskFeedbackOptions, FeedbackVerdict, FEEDBACK_FILENAME,
+};
+pub use git::{
+    install_hooks, read_git_log, read_head_commit, uninstall_hooks, GitCommitInfo, GitHookKind,
+    GitLogImportOptions, GitLogImportResult, HookAction, HookStatus, HooksReport,
+};
+pub use insights::{
+    build_insights, AddSuggestion, BaseInsight, FileInsight, InsightsOptions, InsightsReport,
+    QuestionInsight, StaleAskInsight, SuggestionReason, DEFAULT_INSIGHTS_LIMIT, DEFAULT_LOW_SCORE,
+};
 pub use kg::{
     build_ask_kg_context, clear_branch_kg, export_kg, export_to_dot, export_to_mermaid,
     init_kg_for_branch, kg_exists, sync_branch_kg, sync_branch_kg_default, DefaultKgExtractor,
-    KgEdge, KgExportFormat, KgExportOptions, KgExtractionConfig, KgExtractionResult, KgExtractor,
-    KgNode, KgQueryConfig, KgStats, KgSyncResult, RagChunkRef, EDGES_FILENAME as KG_EDGES_FILENAME,
-    KG_DIR_NAME, KG_VERSION, NODES_FILENAME as KG_NODES_FILENAME,
-    STATS_FILENAME as KG_STATS_FILENAME,
+    GraphQueryReport, KgCheckOptions, KgCheckReport, KgEdge, KgExportFormat, KgExportOptions,
+    KgExportReport, KgExtractionConfig, KgExtractionResult, KgExtractor, KgNode, KgQueryConfig,
+    KgStats, KgSyncResult, PreciseMode, RagChunkRef, ScipImportReport, SymbolSearchOptions,
+    SymbolSearchReport, EDGES_FILENAME as KG_EDGES_FILENAME, KG_DIR_NAME, KG_VERSION,
+    NODES_FILENAME as KG_NODES_FILENAME, STATS_FILENAME as KG_STATS_FILENAME,
 };
 pub use log::{
-    append_ask_log, run_log_query, AskLogEntry, AskLogView, LogEntry, LogKind, LogQueryResult,
-    LogQueryScope, TimelineLogEntry, TimelineOperationKind, ASKS_DIR, ASK_LOG_FILENAME,
+    append_ask_log, find_ask_entry, load_ask_bundle, run_log_query, save_ask_bundle, AskLogEntry,
+    AskLogSource, AskLogView, LogEntry, LogKind, LogQueryResult, LogQueryScope, TimelineLogEntry,
+    TimelineOperationKind, ASKS_DIR, ASK_LOG_FILENAME,
 };
 pub use memory::{
     ingest_memory_entries, MemoryEntry, MemoryIngestionOptions, MemoryIngestionResult, MemoryScope,
     MemorySource, MEMORY_BASE_NAME,
 };
+pub use prompt::{render_prompt, PromptContext, PromptFormat, PromptOptions};
 pub use query_expansion::{average_embeddings, ExpansionConfig, QueryExpander};
-pub use reindex::{reindex_base, run_reindex};
+pub use reindex::{migrate_base_index, reindex_base, run_reindex};
 pub use release::{
     gather_release_entries, group_entries_by_kind, render_changelog_markdown, run_release,
     ReleaseEntry, ReleaseEntryKind, ReleaseGroup, ReleaseMode, ReleaseOptions, ReleaseRange,
@@ -161,15 +187,16 @@ pub use status::{HeadInfo, StagedFile, StatusReport};
 pub use timeline::{resolve_revision_ref, Revision, RevisionId, RevisionOperation};
 pub use types::{
     AddOptions, AddResult, AddSourceSkip, AskOptions, BaseName, CommitOptions, CommitResult,
-    CommitResultBase, ConfigSourceInfo, ConfigValidationResult, EmbeddingProvider, MemoryIngestResult,
+    CommitResultBase, ConfigSourceInfo, ConfigValidationResult, EmbeddingProvider,
+    IndexMigrateOptions, IndexMigrateResult, MemoryIngestResult,
     ReindexBaseResult, ReindexEntry, ReindexOptions, ReindexResult, ResolvedConfig, SearchResult,
     StatsQuery, StatsReport, UnstageOptions, UnstageResult, UnstageSourceSkip, VectorIndex,
 };
 pub use vector_index::{
     check_index_compatibility, default_vector_index_config_for_base, load_index_meta,
-    write_index_meta, VectorId, VectorIndexBackend, VectorIndexBackendKind,
+    migrate_vector_index, write_index_meta, HnswParams, VectorId, VectorIndexBackend, VectorIndexBackendKind,
     VectorIndexCompatibility, VectorIndexConfig, VectorIndexMeta, VectorIndexStats, VectorInsert,
-    VectorMetric, VectorSearchResult, DEFAULT_BACKEND, DEFAULT_METRIC, INDEX_META_FILENAME,
+    VectorMetric, VectorSearchResult, VectorStorage, DEFAULT_BACKEND, DEFAULT_METRIC, INDEX_META_FILENAME,
     INDEX_RECORDS_FILENAME,
 };
 pub use workspace::{is_valid_branch_name, BranchName, Workspace};
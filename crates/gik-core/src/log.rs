@@ -33,6 +33,7 @@ use std::path::Path;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::ask::AskContextBundle;
 use crate::engine::GikEngine;
 use crate::errors::GikError;
 use crate::timeline::{read_timeline, Revision, RevisionOperation};
@@ -357,6 +358,9 @@ impl TimelineLogEntry {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AskLogEntry {
+    /// Unique ask identifier (absent in entries written before IDs existed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     /// Timestamp of the query.
     pub timestamp: DateTime<Utc>,
     /// The branch used for the query.
@@ -370,6 +374,30 @@ pub struct AskLogEntry {
     /// Path to the full context bundle (if saved).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bundle_path: Option<String>,
+    /// Revision the question was answered from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision_id: Option<String>,
+    /// Highest chunk score returned (absent when nothing was returned).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_score: Option<f32>,
+    /// Files returned, one per base and path with their best score.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<AskLogSource>,
+    /// Returned files that had changed on disk since they were indexed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stale_paths: Vec<String>,
+}
+
+/// A file returned by an ask, as summarized in the ask log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AskLogSource {
+    /// Base the file was returned from.
+    pub base: String,
+    /// Workspace-relative file path.
+    pub path: String,
+    /// Best score among the file's returned chunks.
+    pub score: f32,
 }
 
 impl AskLogEntry {
@@ -381,15 +409,49 @@ impl AskLogEntry {
         total_hits: u32,
     ) -> Self {
         Self {
+            id: Some(new_ask_id()),
             timestamp: Utc::now(),
             branch: branch.into(),
             question: question.into(),
             bases,
             total_hits,
             bundle_path: None,
+            revision_id: None,
+            top_score: None,
+            sources: Vec::new(),
+            stale_paths: Vec::new(),
         }
     }
 
+    /// Record the revision, top score and returned files of a bundle.
+    pub fn with_results(mut self, bundle: &AskContextBundle) -> Self {
+        self.revision_id = Some(bundle.revision_id.as_str().to_string());
+        self.top_score = bundle.rag_chunks.iter().map(|c| c.score).reduce(f32::max);
+
+        let mut sources: Vec<AskLogSource> = Vec::new();
+        for chunk in &bundle.rag_chunks {
+            match sources
+                .iter_mut()
+                .find(|s| s.base == chunk.base && s.path == chunk.path)
+            {
+                Some(source) => source.score = source.score.max(chunk.score),
+                None => sources.push(AskLogSource {
+                    base: chunk.base.clone(),
+                    path: chunk.path.clone(),
+                    score: chunk.score,
+                }),
+            }
+        }
+        self.sources = sources;
+        self
+    }
+
+    /// Set the returned files that were stale when the question was asked.
+    pub fn with_stale_paths(mut self, paths: Vec<String>) -> Self {
+        self.stale_paths = paths;
+        self
+    }
+
     /// Set the bundle path.
     pub fn with_bundle_path(mut self, path: impl Into<String>) -> Self {
         self.bundle_path = Some(path.into());
@@ -405,6 +467,9 @@ impl AskLogEntry {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AskLogView {
+    /// Unique ask identifier (if recorded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     /// Branch the query was run on.
     pub branch: String,
     /// Timestamp of the query.
@@ -418,17 +483,34 @@ pub struct AskLogView {
     /// Path to the full context bundle (if saved).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bundle_path: Option<String>,
+    /// Revision the question was answered from (if recorded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision_id: Option<String>,
+    /// Highest chunk score returned (if recorded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_score: Option<f32>,
+    /// Files returned with their best score.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<AskLogSource>,
+    /// Returned files that were stale when the question was asked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stale_paths: Vec<String>,
 }
 
 impl From<AskLogEntry> for AskLogView {
     fn from(entry: AskLogEntry) -> Self {
         Self {
+            id: entry.id,
             branch: entry.branch,
             timestamp: entry.timestamp,
             question: entry.question,
             bases: entry.bases,
             total_hits: entry.total_hits,
             bundle_path: entry.bundle_path,
+            revision_id: entry.revision_id,
+            top_score: entry.top_score,
+            sources: entry.sources,
+            stale_paths: entry.stale_paths,
         }
     }
 }
@@ -499,6 +581,12 @@ pub const ASK_LOG_FILENAME: &str = "ask.log.jsonl";
 /// The directory for ask-related files.
 pub const ASKS_DIR: &str = "asks";
 
+/// Generate a new ask identifier (`ask-` followed by 12 hex characters).
+pub fn new_ask_id() -> String {
+    let uuid = uuid::Uuid::new_v4().simple().to_string();
+    format!("ask-{}", &uuid[..12])
+}
+
 // ============================================================================
 // Core Query Function
 // ============================================================================
@@ -834,6 +922,113 @@ pub fn append_ask_log(workspace: &Workspace, entry: &AskLogEntry) -> Result<(),
     Ok(())
 }
 
+// ============================================================================
+// Saved Ask Bundles
+// ============================================================================
+
+/// Save a full ask bundle under the branch's asks directory.
+///
+/// The bundle is written to `<branch>/asks/<ask-id>.json`. Returns the path
+/// relative to the knowledge root, suitable for [`AskLogEntry::bundle_path`].
+pub fn save_ask_bundle(
+    workspace: &Workspace,
+    branch: &str,
+    ask_id: &str,
+    bundle: &AskContextBundle,
+) -> Result<String, GikError> {
+    let asks_dir = workspace.branch_dir(branch).join(ASKS_DIR);
+    std::fs::create_dir_all(&asks_dir).map_err(|e| GikError::LogIoError {
+        path: asks_dir.clone(),
+        reason: format!("Failed to create asks directory: {}", e),
+    })?;
+
+    let file_name = format!("{}.json", ask_id);
+    let bundle_path = asks_dir.join(&file_name);
+    let json = serde_json::to_string_pretty(bundle).map_err(|e| GikError::LogIoError {
+        path: bundle_path.clone(),
+        reason: format!("Failed to serialize ask bundle: {}", e),
+    })?;
+    std::fs::write(&bundle_path, json).map_err(|e| GikError::LogIoError {
+        path: bundle_path,
+        reason: format!("Failed to write ask bundle: {}", e),
+    })?;
+
+    Ok(format!("{}/{}/{}", branch, ASKS_DIR, file_name))
+}
+
+/// Find an ask log entry by position or identifier.
+///
+/// `selector` is either a 1-based position in the newest-first listing
+/// produced by `scope` (as shown by `gik log --kind ask`), or an ask ID or
+/// unique ID prefix.
+///
+/// # Errors
+///
+/// Returns [`GikError::AskNotFound`] if nothing matches, or
+/// [`GikError::InvalidArgument`] if an ID prefix is ambiguous.
+pub fn find_ask_entry(
+    workspace: &Workspace,
+    scope: &LogQueryScope,
+    selector: &str,
+) -> Result<AskLogView, GikError> {
+    let entries: Vec<AskLogView> = query_ask_log(workspace, scope)?
+        .into_iter()
+        .filter_map(|e| match e {
+            LogEntry::Ask(a) => Some(a),
+            LogEntry::Timeline(_) => None,
+        })
+        .collect();
+
+    if let Ok(position) = selector.parse::<usize>() {
+        return position
+            .checked_sub(1)
+            .and_then(|i| entries.get(i).cloned())
+            .ok_or_else(|| GikError::AskNotFound(selector.to_string()));
+    }
+
+    let mut matches = entries.into_iter().filter(|e| {
+        e.id.as_deref()
+            .is_some_and(|id| id == selector || id.starts_with(selector))
+    });
+    let found = matches
+        .next()
+        .ok_or_else(|| GikError::AskNotFound(selector.to_string()))?;
+    if matches.next().is_some() && found.id.as_deref() != Some(selector) {
+        return Err(GikError::InvalidArgument(format!(
+            "Ask ID prefix `{}` is ambiguous",
+            selector
+        )));
+    }
+    Ok(found)
+}
+
+/// Load the saved bundle of an ask log entry.
+///
+/// # Errors
+///
+/// Returns [`GikError::AskBundleNotSaved`] if the ask was logged without a
+/// bundle, or [`GikError::LogIoError`] if the bundle cannot be read.
+pub fn load_ask_bundle(
+    workspace: &Workspace,
+    entry: &AskLogView,
+) -> Result<AskContextBundle, GikError> {
+    let label = entry.id.clone().unwrap_or_else(|| entry.question.clone());
+    let relative = entry
+        .bundle_path
+        .as_ref()
+        .ok_or_else(|| GikError::AskBundleNotSaved(label))?;
+
+    let path = workspace.knowledge_root().join(relative);
+    let content = std::fs::read_to_string(&path).map_err(|e| GikError::LogIoError {
+        path: path.clone(),
+        reason: format!("Failed to read ask bundle: {}", e),
+    })?;
+    serde_json::from_str(&content).map_err(|e| GikError::LogIoError {
+        path,
+        reason: format!("Failed to parse ask bundle: {}", e),
+    })
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1014,12 +1209,17 @@ mod tests {
     #[test]
     fn test_ask_log_entry_serialization() {
         let entry = AskLogEntry {
+            id: Some("ask-0123456789ab".to_string()),
             timestamp: Utc::now(),
             branch: "main".to_string(),
             question: "What is this?".to_string(),
             bases: vec!["code".to_string()],
             total_hits: 5,
             bundle_path: Some("/path/to/bundle.json".to_string()),
+            revision_id: None,
+            top_score: None,
+            sources: Vec::new(),
+            stale_paths: Vec::new(),
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -1064,15 +1264,137 @@ mod tests {
         });
 
         let ask_entry = LogEntry::Ask(AskLogView {
+            id: None,
             branch: "main".to_string(),
             timestamp: Utc::now(),
             question: "Test?".to_string(),
             bases: vec!["docs".to_string()],
             total_hits: 3,
             bundle_path: None,
+            revision_id: None,
+            top_score: None,
+            sources: Vec::new(),
+            stale_paths: Vec::new(),
         });
 
         assert_eq!(timeline_entry.branch(), "main");
         assert_eq!(ask_entry.branch(), "main");
     }
+
+    fn test_bundle(question: &str) -> AskContextBundle {
+        AskContextBundle {
+            ask_id: None,
+            revision_id: RevisionId::new("rev-1"),
+            question: question.to_string(),
+            bases: vec!["code".to_string()],
+            rag_chunks: vec![],
+            kg_results: vec![],
+            memory_events: vec![],
+            stack_summary: None,
+            debug: crate::ask::AskDebugInfo {
+                embedding_model_id: "test-model".to_string(),
+                used_bases: vec!["code".to_string()],
+                per_base_counts: vec![],
+                embed_time_ms: None,
+                search_time_ms: None,
+                reranker_used: false,
+                rerank_time_ms: None,
+                hybrid_search_used: false,
+                dense_result_count: None,
+                sparse_result_count: None,
+                filename_detected: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_ask_log_entry_with_results() {
+        let chunk = |path: &str, score: f32| crate::ask::RagChunk {
+            base: "code".to_string(),
+            score,
+            path: path.to_string(),
+            start_line: 1,
+            end_line: 10,
+            snippet: String::new(),
+            dense_score: None,
+            reranker_score: None,
+            ownership: None,
+        };
+        let mut bundle = test_bundle("Where?");
+        bundle.rag_chunks = vec![
+            chunk("src/a.rs", 0.4),
+            chunk("src/b.rs", 0.7),
+            chunk("src/a.rs", 0.6),
+        ];
+
+        let entry = AskLogEntry::new("main", "Where?", vec!["code".to_string()], 3)
+            .with_results(&bundle)
+            .with_stale_paths(vec!["src/b.rs".to_string()]);
+
+        assert_eq!(entry.revision_id.as_deref(), Some("rev-1"));
+        assert_eq!(entry.top_score, Some(0.7));
+        assert_eq!(entry.sources.len(), 2);
+        assert_eq!(entry.sources[0].path, "src/a.rs");
+        assert_eq!(entry.sources[0].score, 0.6);
+
+        let view = AskLogView::from(entry);
+        assert_eq!(view.stale_paths, vec!["src/b.rs".to_string()]);
+
+        // Empty results leave the summary fields out of the stored line
+        let json = serde_json::to_string(
+            &AskLogEntry::new("main", "None?", vec![], 0).with_results(&test_bundle("None?")),
+        )
+        .unwrap();
+        assert!(!json.contains("topScore"));
+        assert!(!json.contains("sources"));
+    }
+
+    #[test]
+    fn test_legacy_ask_log_entry_without_id() {
+        let line = r#"{"timestamp":"2024-01-15T10:00:00Z","branch":"main","question":"Old?","bases":["code"],"totalHits":1}"#;
+        let entry: AskLogEntry = serde_json::from_str(line).unwrap();
+        assert!(entry.id.is_none());
+        assert!(AskLogEntry::new("main", "New?", vec![], 0)
+            .id
+            .is_some_and(|id| id.starts_with("ask-") && id.len() == 16));
+    }
+
+    #[test]
+    fn test_save_find_and_load_ask_bundle() {
+        let (_temp_dir, workspace) = create_test_workspace();
+
+        let first = AskLogEntry::new("main", "First?", vec!["code".to_string()], 0);
+        let first_id = first.id.clone().unwrap();
+        let bundle_path =
+            save_ask_bundle(&workspace, "main", &first_id, &test_bundle("First?")).unwrap();
+        assert_eq!(bundle_path, format!("main/asks/{}.json", first_id));
+        append_ask_log(&workspace, &first.with_bundle_path(bundle_path)).unwrap();
+
+        let second = AskLogEntry::new("main", "Second?", vec!["code".to_string()], 0);
+        let second_id = second.id.clone().unwrap();
+        append_ask_log(&workspace, &second).unwrap();
+
+        let scope = LogQueryScope::new().with_kind(LogKind::Ask);
+
+        // Positions follow the newest-first listing
+        let newest = find_ask_entry(&workspace, &scope, "1").unwrap();
+        assert_eq!(newest.question, "Second?");
+        let by_prefix = find_ask_entry(&workspace, &scope, &first_id[..12]).unwrap();
+        assert_eq!(by_prefix.id.as_deref(), Some(first_id.as_str()));
+        let by_id = find_ask_entry(&workspace, &scope, &first_id).unwrap();
+        assert_eq!(by_id.question, "First?");
+
+        let bundle = load_ask_bundle(&workspace, &by_id).unwrap();
+        assert_eq!(bundle.question, "First?");
+
+        let unsaved = find_ask_entry(&workspace, &scope, &second_id).unwrap();
+        assert!(matches!(
+            load_ask_bundle(&workspace, &unsaved),
+            Err(GikError::AskBundleNotSaved(_))
+        ));
+        assert!(matches!(
+            find_ask_entry(&workspace, &scope, "3"),
+            Err(GikError::AskNotFound(_))
+        ));
+    }
 }
@@ -59,6 +59,7 @@ use crate::embedding::{
     check_model_compatibility, create_backend, default_embedding_config_for_base, read_model_info,
     write_model_info, EmbeddingBackend, ModelCompatibility, ModelInfo,
 };
+use crate::embedding_cache::with_embedding_cache;
 use crate::errors::GikError;
 use crate::vector_index::{
     check_index_compatibility, index_meta_path, load_index_meta, open_vector_index,
@@ -459,6 +460,8 @@ pub struct MemoryIngestionOptions {
     pub use_mock_backend: bool,
     /// Device preference for embedding generation.
     pub device_pref: DevicePreference,
+    /// Performance settings (batch size, warm-up, embedding cache).
+    pub performance: PerformanceConfig,
 }
 
 // ============================================================================
@@ -542,13 +545,21 @@ pub fn ingest_memory_entries(
     let backend: Box<dyn EmbeddingBackend> = if opts.use_mock_backend {
         create_mock_backend(&embedding_config)
     } else {
-        create_backend(&embedding_config, opts.device_pref)?
+        with_embedding_cache(
+            create_backend(&embedding_config, opts.device_pref)?,
+            &embedding_config,
+            opts.performance.embedding_cache,
+        )
     };
 
     #[cfg(not(test))]
     let backend: Box<dyn EmbeddingBackend> = {
         let _ = opts; // Silence unused warning in production
-        create_backend(&embedding_config, opts.device_pref)?
+        with_embedding_cache(
+            create_backend(&embedding_config, opts.device_pref)?,
+            &embedding_config,
+            opts.performance.embedding_cache,
+        )
     };
 
     let dimension = backend.dimension();
@@ -621,11 +632,12 @@ pub fn ingest_memory_entries(
     }
 
     // Phase 2: Batch embedding (using PerformanceConfig)
-    let perf_config = PerformanceConfig::default();
-    let batch_size = perf_config.embedding_batch_size;
+    let batch_size = opts.performance.embedding_batch_size;
 
     // Warm up the embedding backend
-    let _ = backend.warm_up();
+    if opts.performance.enable_warmup {
+        let _ = backend.warm_up();
+    }
 
     let texts: Vec<String> = valid_entries.iter().map(|(_, text)| text.clone()).collect();
     let mut all_embeddings: Vec<Vec<f32>> = Vec::with_capacity(texts.len());
@@ -715,6 +727,7 @@ pub fn ingest_memory_entries(
             "tags": entry.tags,
             "created_at": entry.created_at.to_rfc3339(),
             "updated_at": entry.updated_at.to_rfc3339(),
+            "extra": entry.extra,
         }));
 
         source_entries.push(source_entry);
@@ -989,6 +1002,7 @@ mod tests {
         let opts = MemoryIngestionOptions {
             use_mock_backend: true,
             device_pref: DevicePreference::Auto,
+            ..Default::default()
         };
         let result = ingest_memory_entries(&knowledge_root, "main", entries, "rev-002", &opts);
 
@@ -1045,6 +1059,7 @@ mod tests {
         let opts = MemoryIngestionOptions {
             use_mock_backend: true,
             device_pref: DevicePreference::Auto,
+            ..Default::default()
         };
         let result = ingest_memory_entries(&knowledge_root, "main", entries, "rev-003", &opts);
 
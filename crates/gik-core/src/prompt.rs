@@ -0,0 +1,721 @@
+//! Prompt-ready rendering of an [`AskContextBundle`].
+//!
+//! This module packs the context returned by `gik ask` into a compact text
+//! block that can be pasted into an LLM prompt:
+//! - [`PromptFormat`] - tagged plain text (`prompt`) or Markdown (`markdown`)
+//! - [`PromptOptions`] - format and optional token budget
+//! - [`render_prompt`] - builds a [`PromptContext`] from a bundle
+//!
+//! ## Layout
+//!
+//! Sections always appear in the same order: question, stack, sources (RAG
+//! chunks with `path:start-end` citations), memory events and knowledge graph
+//! summaries. Items keep their retrieval rank within each section, so the same
+//! bundle and options always produce the same text.
+//!
+//! ## Token Budget
+//!
+//! Chunks from the same file whose line ranges overlap or touch are merged
+//! first. Items are then admitted in priority order (stack summary, sources,
+//! memory events, graph summaries; each by rank) and any item that would push
+//! the rendered text over `max_tokens` is dropped. Tokens are counted with
+//! [`estimate_tokens_from_text`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::ask::{AskContextBundle, AskKgResult, MemoryEvent, RagChunk, StackSummary};
+use crate::memory::metrics::estimate_tokens_from_text;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Maximum edges listed per knowledge graph summary.
+const MAX_KG_EDGES_PER_SUMMARY: usize = 12;
+
+// ============================================================================
+// PromptFormat
+// ============================================================================
+
+/// Text layout for prompt-ready ask output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PromptFormat {
+    /// Plain text with XML-style tags around each item.
+    #[default]
+    Prompt,
+    /// Markdown with headings and fenced code blocks.
+    Markdown,
+}
+
+impl std::fmt::Display for PromptFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Prompt => write!(f, "prompt"),
+            Self::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+impl std::str::FromStr for PromptFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "prompt" | "text" => Ok(Self::Prompt),
+            "markdown" | "md" => Ok(Self::Markdown),
+            other => Err(format!("Unknown prompt format: {}", other)),
+        }
+    }
+}
+
+// ============================================================================
+// PromptOptions / PromptContext
+// ============================================================================
+
+/// Options for [`render_prompt`].
+#[derive(Debug, Clone, Default)]
+pub struct PromptOptions {
+    /// Output layout.
+    pub format: PromptFormat,
+    /// Token budget for the whole rendered text (None = unlimited).
+    pub max_tokens: Option<usize>,
+}
+
+/// Rendered prompt context.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptContext {
+    /// The rendered text.
+    pub text: String,
+    /// Estimated token count of `text`.
+    pub tokens: usize,
+    /// Source blocks included (after merging adjacent chunks).
+    pub sources: usize,
+    /// Source blocks dropped to fit the budget.
+    pub omitted_sources: usize,
+    /// Memory events dropped to fit the budget.
+    pub omitted_memory_events: usize,
+    /// Knowledge graph summaries dropped to fit the budget.
+    pub omitted_kg_results: usize,
+    /// Whether the stack summary was dropped to fit the budget.
+    pub omitted_stack: bool,
+}
+
+impl PromptContext {
+    /// Whether any item was dropped to fit the budget.
+    pub fn is_trimmed(&self) -> bool {
+        self.omitted_sources > 0
+            || self.omitted_memory_events > 0
+            || self.omitted_kg_results > 0
+            || self.omitted_stack
+    }
+}
+
+// ============================================================================
+// Public Functions
+// ============================================================================
+
+/// Render an ask bundle as a prompt-ready context block.
+///
+/// If even the question alone exceeds `max_tokens`, the returned text holds
+/// just the question and `tokens` reports its size.
+pub fn render_prompt(bundle: &AskContextBundle, opts: &PromptOptions) -> PromptContext {
+    let sources = merge_adjacent_chunks(&bundle.rag_chunks);
+    let fits = |selection: &Selection| match opts.max_tokens {
+        Some(max) => {
+            let text = render(bundle, &sources, selection, opts.format);
+            estimate_tokens_from_text(&text) <= max
+        }
+        None => true,
+    };
+
+    let mut selection = Selection::default();
+
+    if bundle.stack_summary.is_some() {
+        selection.stack = true;
+        if !fits(&selection) {
+            selection.stack = false;
+        }
+    }
+    for i in 0..sources.len() {
+        selection.sources.push(i);
+        if !fits(&selection) {
+            selection.sources.pop();
+        }
+    }
+    for i in 0..bundle.memory_events.len() {
+        selection.memory.push(i);
+        if !fits(&selection) {
+            selection.memory.pop();
+        }
+    }
+    for (i, kg) in bundle.kg_results.iter().enumerate() {
+        if kg.is_empty() {
+            continue;
+        }
+        selection.kg.push(i);
+        if !fits(&selection) {
+            selection.kg.pop();
+        }
+    }
+
+    let text = render(bundle, &sources, &selection, opts.format);
+    let kg_total = bundle.kg_results.iter().filter(|kg| !kg.is_empty()).count();
+
+    PromptContext {
+        tokens: estimate_tokens_from_text(&text),
+        text,
+        sources: selection.sources.len(),
+        omitted_sources: sources.len() - selection.sources.len(),
+        omitted_memory_events: bundle.memory_events.len() - selection.memory.len(),
+        omitted_kg_results: kg_total - selection.kg.len(),
+        omitted_stack: bundle.stack_summary.is_some() && !selection.stack,
+    }
+}
+
+// ============================================================================
+// Merging
+// ============================================================================
+
+/// A source block: one chunk, or several adjacent chunks of the same file.
+#[derive(Debug, Clone)]
+struct SourceBlock {
+    base: String,
+    path: String,
+    start_line: u32,
+    end_line: u32,
+    score: f32,
+    text: String,
+    /// Best (lowest) rank among the merged chunks.
+    rank: usize,
+}
+
+/// Merge chunks of the same file whose line ranges overlap or touch.
+///
+/// Returns blocks ordered by the best rank of their chunks.
+fn merge_adjacent_chunks(chunks: &[RagChunk]) -> Vec<SourceBlock> {
+    let mut by_file: BTreeMap<(&str, &str), Vec<(usize, &RagChunk)>> = BTreeMap::new();
+    for (rank, chunk) in chunks.iter().enumerate() {
+        by_file
+            .entry((chunk.base.as_str(), chunk.path.as_str()))
+            .or_default()
+            .push((rank, chunk));
+    }
+
+    let mut blocks = Vec::new();
+    for mut group in by_file.into_values() {
+        group.sort_by_key(|(rank, c)| (c.start_line, c.end_line, *rank));
+
+        let mut current: Option<SourceBlock> = None;
+        for (rank, chunk) in group {
+            match current.as_mut() {
+                Some(block) if chunk.start_line <= block.end_line.saturating_add(1) => {
+                    if chunk.end_line > block.end_line {
+                        // Append only the lines past the block's current end
+                        let overlap = (block.end_line + 1).saturating_sub(chunk.start_line);
+                        let tail: Vec<&str> =
+                            chunk.snippet.lines().skip(overlap as usize).collect();
+                        if !tail.is_empty() {
+                            block.text.push('\n');
+                            block.text.push_str(&tail.join("\n"));
+                        }
+                        block.end_line = chunk.end_line;
+                    }
+                    block.score = block.score.max(chunk.score);
+                    block.rank = block.rank.min(rank);
+                }
+                _ => {
+                    blocks.extend(current.take());
+                    current = Some(SourceBlock {
+                        base: chunk.base.clone(),
+                        path: chunk.path.clone(),
+                        start_line: chunk.start_line,
+                        end_line: chunk.end_line,
+                        score: chunk.score,
+                        text: chunk.snippet.trim_end_matches('\n').to_string(),
+                        rank,
+                    });
+                }
+            }
+        }
+        blocks.extend(current);
+    }
+
+    blocks.sort_by_key(|b| b.rank);
+    blocks
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Items admitted into the rendered text (indices in rank order).
+#[derive(Debug, Default)]
+struct Selection {
+    stack: bool,
+    sources: Vec<usize>,
+    memory: Vec<usize>,
+    kg: Vec<usize>,
+}
+
+fn render(
+    bundle: &AskContextBundle,
+    sources: &[SourceBlock],
+    selection: &Selection,
+    format: PromptFormat,
+) -> String {
+    let stack = bundle.stack_summary.as_ref().filter(|_| selection.stack);
+    let sources: Vec<(usize, &SourceBlock)> = selection
+        .sources
+        .iter()
+        .map(|&i| (i + 1, &sources[i]))
+        .collect();
+    let memory: Vec<(usize, &MemoryEvent)> = selection
+        .memory
+        .iter()
+        .map(|&i| (i + 1, &bundle.memory_events[i]))
+        .collect();
+    let kg: Vec<(usize, &AskKgResult)> = selection
+        .kg
+        .iter()
+        .enumerate()
+        .map(|(n, &i)| (n + 1, &bundle.kg_results[i]))
+        .collect();
+
+    match format {
+        PromptFormat::Prompt => render_tagged(bundle, stack, &sources, &memory, &kg),
+        PromptFormat::Markdown => render_markdown(bundle, stack, &sources, &memory, &kg),
+    }
+}
+
+fn render_tagged(
+    bundle: &AskContextBundle,
+    stack: Option<&StackSummary>,
+    sources: &[(usize, &SourceBlock)],
+    memory: &[(usize, &MemoryEvent)],
+    kg: &[(usize, &AskKgResult)],
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<question revision=\"{}\">\n{}\n</question>",
+        attr(bundle.revision_id.as_str()),
+        bundle.question.trim()
+    );
+
+    if let Some(stack) = stack {
+        out.push_str("\n<stack>\n");
+        for (key, value) in stack_lines(stack) {
+            let _ = writeln!(out, "{}: {}", key, value);
+        }
+        out.push_str("</stack>\n");
+    }
+
+    for (n, block) in sources {
+        let _ = writeln!(
+            out,
+            "\n<source id=\"{}\" path=\"{}\" lines=\"{}-{}\" base=\"{}\" score=\"{:.2}\">\n{}\n</source>",
+            n,
+            attr(&block.path),
+            block.start_line,
+            block.end_line,
+            attr(&block.base),
+            block.score,
+            block.text
+        );
+    }
+
+    for (n, event) in memory {
+        let _ = writeln!(
+            out,
+            "\n<memory id=\"M{}\" ref=\"{}\" source=\"{}\" date=\"{}\" title=\"{}\">\n{}\n</memory>",
+            n,
+            attr(&event.id),
+            event.source,
+            event.created_at.format("%Y-%m-%d"),
+            attr(&event.title),
+            event.text.trim()
+        );
+    }
+
+    for (n, result) in kg {
+        let _ = writeln!(
+            out,
+            "\n<graph id=\"G{}\" reason=\"{}\">\n{}\n</graph>",
+            n,
+            attr(&result.reason),
+            kg_lines(result).join("\n")
+        );
+    }
+
+    out
+}
+
+fn render_markdown(
+    bundle: &AskContextBundle,
+    stack: Option<&StackSummary>,
+    sources: &[(usize, &SourceBlock)],
+    memory: &[(usize, &MemoryEvent)],
+    kg: &[(usize, &AskKgResult)],
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Question\n\n{}\n\n_Revision `{}`_",
+        bundle.question.trim(),
+        bundle.revision_id.as_str()
+    );
+
+    if let Some(stack) = stack {
+        out.push_str("\n## Stack\n\n");
+        for (key, value) in stack_lines(stack) {
+            let _ = writeln!(out, "- **{}:** {}", key, value);
+        }
+    }
+
+    if !sources.is_empty() {
+        out.push_str("\n## Sources\n");
+        for (n, block) in sources {
+            let fence = "`".repeat(longest_backtick_run(&block.text).max(2) + 1);
+            let _ = writeln!(
+                out,
+                "\n### [{}] `{}:{}-{}` ({}, score {:.2})\n\n{}{}\n{}\n{}",
+                n,
+                block.path,
+                block.start_line,
+                block.end_line,
+                block.base,
+                block.score,
+                fence,
+                fence_language(&block.path),
+                block.text,
+                fence
+            );
+        }
+    }
+
+    if !memory.is_empty() {
+        out.push_str("\n## Memory\n");
+        for (n, event) in memory {
+            let title = if event.title.is_empty() {
+                event.id.as_str()
+            } else {
+                event.title.as_str()
+            };
+            let _ = writeln!(
+                out,
+                "\n### [M{}] {} ({}, {})\n\n{}",
+                n,
+                title,
+                event.source,
+                event.created_at.format("%Y-%m-%d"),
+                event.text.trim()
+            );
+        }
+    }
+
+    if !kg.is_empty() {
+        out.push_str("\n## Knowledge Graph\n");
+        for (n, result) in kg {
+            let _ = writeln!(out, "\n### [G{}] {}\n", n, result.reason);
+            for line in kg_lines(result) {
+                let _ = writeln!(out, "- `{}`", line);
+            }
+        }
+    }
+
+    out
+}
+
+/// Non-empty stack fields as `(label, value)` pairs.
+fn stack_lines(stack: &StackSummary) -> Vec<(&'static str, String)> {
+    let mut lines = Vec::new();
+    for (key, values) in [
+        ("Languages", &stack.languages),
+        ("Frameworks", &stack.frameworks),
+        ("Services", &stack.services),
+        ("Managers", &stack.managers),
+    ] {
+        if !values.is_empty() {
+            lines.push((key, values.join(", ")));
+        }
+    }
+    if let Some(files) = stack.total_files {
+        lines.push(("Files", files.to_string()));
+    }
+    lines
+}
+
+/// Summarize a subgraph as `from -[kind]-> to` lines, plus unconnected nodes.
+fn kg_lines(result: &AskKgResult) -> Vec<String> {
+    let labels: HashMap<&str, &str> = result
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.label.as_str()))
+        .collect();
+    let label = |id: &str| -> String {
+        match labels.get(id) {
+            Some(l) if !l.is_empty() => (*l).to_string(),
+            _ => id.to_string(),
+        }
+    };
+
+    let mut lines: Vec<String> = result
+        .edges
+        .iter()
+        .take(MAX_KG_EDGES_PER_SUMMARY)
+        .map(|e| format!("{} -[{}]-> {}", label(&e.from), e.kind, label(&e.to)))
+        .collect();
+    if result.edges.len() > MAX_KG_EDGES_PER_SUMMARY {
+        lines.push(format!(
+            "... {} more edges",
+            result.edges.len() - MAX_KG_EDGES_PER_SUMMARY
+        ));
+    }
+
+    for node in &result.nodes {
+        let connected = result
+            .edges
+            .iter()
+            .any(|e| e.from == node.id || e.to == node.id);
+        if !connected {
+            lines.push(format!("{} {}", node.kind, label(&node.id)));
+        }
+    }
+    lines
+}
+
+/// Escape a value for use inside a double-quoted tag attribute.
+fn attr(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('\n', " ")
+}
+
+/// Length of the longest run of backticks in `text`.
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+/// Code fence language hint for a file path.
+fn fence_language(path: &str) -> &'static str {
+    let ext = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    match ext {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "cxx" | "hpp" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "swift" => "swift",
+        "sh" | "bash" => "bash",
+        "sql" => "sql",
+        "md" | "markdown" => "markdown",
+        "json" => "json",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "html" | "htm" => "html",
+        "css" | "scss" => "css",
+        _ => "",
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ask::AskDebugInfo;
+    use crate::kg::{KgEdge, KgNode};
+    use crate::timeline::RevisionId;
+
+    fn chunk(path: &str, start: u32, end: u32, score: f32, snippet: &str) -> RagChunk {
+        RagChunk {
+            base: "code".to_string(),
+            score,
+            path: path.to_string(),
+            start_line: start,
+            end_line: end,
+            snippet: snippet.to_string(),
+            dense_score: None,
+            reranker_score: None,
+            ownership: None,
+        }
+    }
+
+    fn bundle(rag_chunks: Vec<RagChunk>) -> AskContextBundle {
+        AskContextBundle {
+            ask_id: None,
+            revision_id: RevisionId::new("rev-1"),
+            question: "How are users loaded?".to_string(),
+            bases: vec!["code".to_string()],
+            rag_chunks,
+            kg_results: vec![],
+            memory_events: vec![],
+            stack_summary: None,
+            debug: AskDebugInfo {
+                embedding_model_id: "test-model".to_string(),
+                used_bases: vec!["code".to_string()],
+                per_base_counts: vec![],
+                embed_time_ms: None,
+                search_time_ms: None,
+                reranker_used: false,
+                rerank_time_ms: None,
+                hybrid_search_used: false,
+                dense_result_count: None,
+                sparse_result_count: None,
+                filename_detected: None,
+            },
+        }
+    }
+
+    fn options(format: PromptFormat, max_tokens: Option<usize>) -> PromptOptions {
+        PromptOptions { format, max_tokens }
+    }
+
+    #[test]
+    fn test_prompt_format_from_str() {
+        assert_eq!("prompt".parse::<PromptFormat>(), Ok(PromptFormat::Prompt));
+        assert_eq!("md".parse::<PromptFormat>(), Ok(PromptFormat::Markdown));
+        assert!("html".parse::<PromptFormat>().is_err());
+    }
+
+    #[test]
+    fn test_merges_adjacent_chunks_of_same_file() {
+        let chunks = vec![
+            chunk("src/users.rs", 11, 13, 0.9, "l11\nl12\nl13"),
+            chunk("src/db.rs", 1, 2, 0.8, "d1\nd2"),
+            chunk(
+                "src/users.rs",
+                1,
+                10,
+                0.7,
+                "l1\nl2\nl3\nl4\nl5\nl6\nl7\nl8\nl9\nl10",
+            ),
+            chunk("src/users.rs", 12, 15, 0.6, "l12\nl13\nl14\nl15"),
+            chunk("src/users.rs", 40, 41, 0.5, "l40\nl41"),
+        ];
+
+        let blocks = merge_adjacent_chunks(&chunks);
+        let summary: Vec<(&str, u32, u32)> = blocks
+            .iter()
+            .map(|b| (b.path.as_str(), b.start_line, b.end_line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/users.rs", 1, 15),
+                ("src/db.rs", 1, 2),
+                ("src/users.rs", 40, 41),
+            ]
+        );
+        let merged = &blocks[0];
+        assert_eq!(merged.text.lines().count(), 15);
+        assert!(merged.text.ends_with("l13\nl14\nl15"));
+        assert_eq!(merged.score, 0.9);
+    }
+
+    #[test]
+    fn test_prompt_layout_with_citations() {
+        let mut b = bundle(vec![chunk("src/users.rs", 3, 4, 0.91, "fn load() {}\n")]);
+        b.stack_summary = Some(StackSummary {
+            languages: vec!["Rust".to_string()],
+            frameworks: vec![],
+            services: vec![],
+            managers: vec!["cargo".to_string()],
+            total_files: Some(12),
+            scoped: false,
+            context_files: None,
+            context_chunks: None,
+        });
+        let mut kg = AskKgResult::with_reason("Imports of src/users.rs", vec![]);
+        kg.nodes = vec![
+            KgNode::new("file:src/users.rs", "file", "src/users.rs"),
+            KgNode::new("file:src/db.rs", "file", "src/db.rs"),
+        ];
+        kg.edges = vec![KgEdge::new(
+            "file:src/users.rs",
+            "file:src/db.rs",
+            "imports",
+        )];
+        b.kg_results = vec![kg];
+
+        let rendered = render_prompt(&b, &options(PromptFormat::Prompt, None));
+        let expected = "<question revision=\"rev-1\">\nHow are users loaded?\n</question>\n\
+             \n<stack>\nLanguages: Rust\nManagers: cargo\nFiles: 12\n</stack>\n\
+             \n<source id=\"1\" path=\"src/users.rs\" lines=\"3-4\" base=\"code\" score=\"0.91\">\nfn load() {}\n</source>\n\
+             \n<graph id=\"G1\" reason=\"Imports of src/users.rs\">\nsrc/users.rs -[imports]-> src/db.rs\n</graph>\n";
+        assert_eq!(rendered.text, expected);
+        assert_eq!(rendered.tokens, estimate_tokens_from_text(expected));
+        assert!(!rendered.is_trimmed());
+    }
+
+    #[test]
+    fn test_markdown_fences_and_language() {
+        let b = bundle(vec![chunk(
+            "README.md",
+            1,
+            3,
+            0.5,
+            "Run:\n```sh\nmake\n```",
+        )]);
+        let rendered = render_prompt(&b, &options(PromptFormat::Markdown, None));
+        assert!(rendered
+            .text
+            .contains("### [1] `README.md:1-3` (code, score 0.50)\n\n````markdown\nRun:"));
+        assert!(rendered.text.contains("```\n````\n"));
+    }
+
+    #[test]
+    fn test_budget_drops_lower_ranked_chunks() {
+        let long = "x".repeat(400);
+        let chunks = vec![
+            chunk("src/a.rs", 1, 1, 0.9, &long),
+            chunk("src/b.rs", 1, 1, 0.8, &long),
+            chunk("src/c.rs", 1, 1, 0.7, "short"),
+        ];
+        let b = bundle(chunks);
+
+        let full = render_prompt(&b, &options(PromptFormat::Prompt, None));
+        let budget = full.tokens - 50;
+        let rendered = render_prompt(&b, &options(PromptFormat::Prompt, Some(budget)));
+
+        assert!(rendered.tokens <= budget);
+        assert_eq!(rendered.sources, 2);
+        assert_eq!(rendered.omitted_sources, 1);
+        assert!(rendered.text.contains("src/a.rs"));
+        assert!(!rendered.text.contains("src/b.rs"));
+        assert!(rendered.text.contains("src/c.rs"));
+        // Deterministic
+        assert_eq!(
+            rendered.text,
+            render_prompt(&b, &options(PromptFormat::Prompt, Some(budget))).text
+        );
+    }
+
+    #[test]
+    fn test_tiny_budget_keeps_question_only() {
+        let b = bundle(vec![chunk("src/a.rs", 1, 1, 0.9, "fn a() {}")]);
+        let rendered = render_prompt(&b, &options(PromptFormat::Markdown, Some(1)));
+        assert_eq!(rendered.sources, 0);
+        assert_eq!(rendered.omitted_sources, 1);
+        assert!(rendered
+            .text
+            .starts_with("# Question\n\nHow are users loaded?"));
+    }
+}
@@ -24,6 +24,11 @@
 //!
 //! Sources that fail to re-read (when `text` is `None`) are skipped and
 //! recorded in `ReindexBaseResult.errors` rather than failing the entire operation.
+//!
+//! ## Backend Migration
+//!
+//! [`migrate_base_index`] moves a base's existing vectors to another vector
+//! index backend without re-embedding (`gik index migrate`).
 
 use std::fs;
 use std::path::Path;
@@ -32,17 +37,19 @@ use chrono::Utc;
 
 use crate::base::{load_base_sources, sources_path, BaseSourceEntry};
 use crate::bm25::{save_bm25_index, Bm25Index};
-use crate::config::{DevicePreference, PerformanceConfig};
+use crate::config::{GlobalConfig, PerformanceConfig, ProjectConfig};
 use crate::embedding::{
     check_model_compatibility, create_backend, read_model_info, write_model_info, EmbeddingBackend,
     EmbeddingConfig, ModelInfo,
 };
+use crate::embedding_cache::with_embedding_cache;
 use crate::errors::GikError;
 use crate::timeline::{RevisionId, RevisionOperation};
-use crate::types::{ReindexBaseResult, ReindexOptions, ReindexResult};
+use crate::types::{IndexMigrateResult, ReindexBaseResult, ReindexOptions, ReindexResult};
 use crate::vector_index::{
-    default_vector_index_config_for_base, index_meta_path, open_vector_index, write_index_meta,
-    VectorId, VectorIndexBackend, VectorIndexMeta, VectorInsert,
+    index_meta_path, load_index_meta, migrate_vector_index, open_vector_index, write_index_meta,
+    VectorId, VectorIndexBackend, VectorIndexBackendKind, VectorIndexConfig, VectorIndexMeta,
+    VectorInsert, VectorMetric,
 };
 use crate::workspace::Workspace;
 
@@ -70,6 +77,7 @@ const MODEL_INFO_FILENAME: &str = "model-info.json";
 /// * `embedding_config` - The embedding configuration to use
 /// * `force` - Force reindex even if model hasn't changed
 /// * `dry_run` - If true, don't write changes
+/// * `global_config` - Global configuration (device and index settings)
 ///
 /// # Returns
 ///
@@ -81,7 +89,7 @@ pub fn reindex_base(
     embedding_config: &EmbeddingConfig,
     force: bool,
     dry_run: bool,
-    device_pref: DevicePreference,
+    global_config: &GlobalConfig,
 ) -> Result<ReindexBaseResult, GikError> {
     let base_root = crate::base::base_root(workspace.knowledge_root(), branch, base);
 
@@ -166,11 +174,21 @@ pub fn reindex_base(
         });
     }
 
-    // Create embedding backend
-    let backend = create_backend(embedding_config, device_pref)?;
+    // Use the configured performance settings, as commit does
+    let perf_config = &global_config.performance;
 
-    // Use default performance config for reindex
-    let perf_config = PerformanceConfig::default();
+    // Create embedding backend (unchanged text is served from the embedding cache)
+    let backend = with_embedding_cache(
+        create_backend(embedding_config, global_config.device)?,
+        embedding_config,
+        perf_config.embedding_cache,
+    );
+
+    // Rebuild with the backend configured for this base, as commit does
+    let project_config = ProjectConfig::load_from_workspace(workspace.root())?;
+    let mut index_config =
+        project_config.resolve_vector_index_config(base, embedding_config, global_config);
+    index_config.dimension = backend.dimension();
 
     // Perform actual reindex
     do_reindex(
@@ -183,7 +201,8 @@ pub fn reindex_base(
         embedding_config,
         from_model_id,
         to_model_id,
-        &perf_config,
+        index_config,
+        perf_config,
     )
 }
 
@@ -199,6 +218,7 @@ pub fn reindex_base(
 /// * `embedding_config` - Embedding configuration to use
 /// * `revision_id` - The revision ID to use (if not dry_run and reindex occurs)
 /// * `git_commit` - Optional git commit hash
+/// * `global_config` - Global configuration (device and index settings)
 ///
 /// # Returns
 ///
@@ -209,7 +229,7 @@ pub fn run_reindex(
     embedding_config: &EmbeddingConfig,
     revision_id: Option<&RevisionId>,
     git_commit: Option<&str>,
-    device_pref: DevicePreference,
+    global_config: &GlobalConfig,
 ) -> Result<ReindexResult, GikError> {
     let branch = opts.branch.as_deref().unwrap_or("main");
 
@@ -221,7 +241,7 @@ pub fn run_reindex(
         embedding_config,
         opts.force,
         opts.dry_run,
-        device_pref,
+        global_config,
     )?;
 
     let reembedded_chunks = base_result.chunks_reembedded;
@@ -268,6 +288,97 @@ pub fn run_reindex(
     })
 }
 
+/// Move a base's vector index to another backend without re-embedding.
+///
+/// The stored vectors and payloads are copied as-is, so the embedding model,
+/// dimension and metric are unchanged. Backend parameters (HNSW graph
+/// settings, simple_file storage) are taken from the resolved configuration
+/// when it names the target backend, and default otherwise.
+///
+/// # Arguments
+///
+/// * `workspace` - The workspace containing the base
+/// * `branch` - The branch to migrate on
+/// * `base` - The base name to migrate
+/// * `to` - The target backend
+/// * `embedding_config` - The embedding configuration for the base
+/// * `global_config` - Global configuration (index settings)
+///
+/// # Returns
+///
+/// An [`IndexMigrateResult`] with the number of vectors copied.
+pub fn migrate_base_index(
+    workspace: &Workspace,
+    branch: &str,
+    base: &str,
+    to: VectorIndexBackendKind,
+    embedding_config: &EmbeddingConfig,
+    global_config: &GlobalConfig,
+) -> Result<IndexMigrateResult, GikError> {
+    let base_root = crate::base::base_root(workspace.knowledge_root(), branch, base);
+    let Some(meta) = load_index_meta(&index_meta_path(&base_root))? else {
+        return Err(GikError::BaseNotIndexed {
+            base: base.to_string(),
+        });
+    };
+
+    // Unknown names in existing metadata must not fall back to a default:
+    // the migration would open the wrong source and report success
+    let Ok(from) = meta.backend.parse::<VectorIndexBackendKind>();
+    if let VectorIndexBackendKind::Other(ref name) = from {
+        return Err(GikError::IndexMigrateError {
+            base: base.to_string(),
+            reason: format!("Index metadata names an unknown backend '{}'", name),
+        });
+    }
+    let metric =
+        VectorMetric::from_name(&meta.metric).ok_or_else(|| GikError::IndexMigrateError {
+            base: base.to_string(),
+            reason: format!("Index metadata names an unknown metric '{}'", meta.metric),
+        })?;
+    if let VectorIndexBackendKind::Other(ref name) = to {
+        return Err(GikError::IndexMigrateError {
+            base: base.to_string(),
+            reason: format!(
+                "Unknown backend '{}'. Expected one of: lancedb, hnsw, simple_file",
+                name
+            ),
+        });
+    }
+    if from == to {
+        return Err(GikError::IndexMigrateError {
+            base: base.to_string(),
+            reason: format!("Index already uses the '{}' backend", to),
+        });
+    }
+
+    let from_config = VectorIndexConfig::new(from.clone(), metric, meta.dimension, base);
+
+    let project_config = ProjectConfig::load_from_workspace(workspace.root())?;
+    let resolved =
+        project_config.resolve_vector_index_config(base, embedding_config, global_config);
+    let mut to_config = VectorIndexConfig::new(to.clone(), metric, meta.dimension, base);
+    if resolved.backend == to {
+        to_config = to_config
+            .with_storage(resolved.storage)
+            .with_hnsw(resolved.hnsw);
+    }
+
+    let vectors = migrate_vector_index(&base_root.join("index"), &from_config, &to_config)
+        .map_err(|e| GikError::IndexMigrateError {
+            base: base.to_string(),
+            reason: e.to_string(),
+        })?;
+
+    Ok(IndexMigrateResult {
+        base: base.to_string(),
+        from_backend: from.to_string(),
+        to_backend: to.to_string(),
+        vectors,
+        config_backend: (resolved.backend != to).then(|| resolved.backend.to_string()),
+    })
+}
+
 // ============================================================================
 // Private Functions
 // ============================================================================
@@ -284,6 +395,7 @@ fn do_reindex(
     embedding_config: &EmbeddingConfig,
     from_model_id: Option<String>,
     to_model_id: String,
+    index_config: VectorIndexConfig,
     perf_config: &PerformanceConfig,
 ) -> Result<ReindexBaseResult, GikError> {
     let mut errors: Vec<String> = Vec::new();
@@ -373,7 +485,7 @@ fn do_reindex(
     }
 
     // Rebuild vector index
-    let index_config = default_vector_index_config_for_base(base, embedding_config);
+    let index_meta_file = index_meta_path(base_root);
     let index_root = base_root.join("index");
 
     // Remove old index files and recreate
@@ -433,11 +545,7 @@ fn do_reindex(
     write_model_info(&model_info_path, &model_info)?;
 
     // Update index metadata
-    let index_meta_file = index_meta_path(base_root);
-    let index_meta = VectorIndexMeta::from_config(
-        &default_vector_index_config_for_base(base, embedding_config),
-        embedding_config,
-    );
+    let index_meta = VectorIndexMeta::from_config(&index_config, embedding_config);
     write_index_meta(&index_meta_file, &index_meta)?;
 
     Ok(ReindexBaseResult {
@@ -525,6 +633,7 @@ mod tests {
             source_id: "src-001".to_string(),
             indexed_mtime: None,
             indexed_size: None,
+            content_hash: None,
             extra: None,
         };
 
@@ -553,6 +662,7 @@ mod tests {
             source_id: "src-001".to_string(),
             indexed_mtime: None,
             indexed_size: None,
+            content_hash: None,
             extra: None,
         };
 
@@ -560,4 +670,43 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("file not found"));
     }
+
+    #[test]
+    fn test_migrate_rejects_unknown_names_in_meta() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = Workspace::from_root(temp_dir.path()).unwrap();
+        let embedding = EmbeddingConfig::default();
+        let base_root = crate::base::base_root(workspace.knowledge_root(), "main", "code");
+
+        let config = VectorIndexConfig::new(
+            VectorIndexBackendKind::SimpleFile,
+            VectorMetric::Cosine,
+            3,
+            "code",
+        );
+        for (backend, metric) in [("simple_fiel", "cosine"), ("simple_file", "cosin")] {
+            let mut meta = VectorIndexMeta::from_config(&config, &embedding);
+            meta.backend = backend.to_string();
+            meta.metric = metric.to_string();
+            let meta_path = index_meta_path(&base_root);
+            fs::create_dir_all(meta_path.parent().unwrap()).unwrap();
+            write_index_meta(&meta_path, &meta).unwrap();
+
+            let err = migrate_base_index(
+                &workspace,
+                "main",
+                "code",
+                VectorIndexBackendKind::Hnsw,
+                &embedding,
+                &GlobalConfig::default(),
+            )
+            .unwrap_err();
+            assert!(
+                matches!(err, GikError::IndexMigrateError { ref reason, .. } if reason.contains("unknown")),
+                "{}",
+                err
+            );
+        }
+    }
 }
@@ -2277,6 +2277,28 @@ pub fn read_files_jsonl(path: &Path) -> Result<Vec<StackFileEntry>, GikError> {
     Ok(entries)
 }
 
+/// Read dependency entries from a JSONL file.
+pub fn read_dependencies_jsonl(path: &Path) -> Result<Vec<StackDependencyEntry>, GikError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let mut entries = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: StackDependencyEntry = serde_json::from_str(&line)?;
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
 /// Read stats from a JSON file.
 pub fn read_stats_json(path: &Path) -> Result<Option<StackStats>, GikError> {
     if !path.exists() {
@@ -2528,6 +2550,30 @@ tempfile = "3"
         assert_eq!(read_back[1].path, "lib.rs");
     }
 
+    #[test]
+    fn test_write_and_read_dependencies_jsonl() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("dependencies.jsonl");
+
+        let deps = vec![StackDependencyEntry {
+            manager: "cargo".to_string(),
+            name: "serde".to_string(),
+            version: "1.0".to_string(),
+            scope: "runtime".to_string(),
+            manifest_path: "Cargo.toml".to_string(),
+        }];
+
+        write_dependencies_jsonl(&path, &deps).unwrap();
+        let read_back = read_dependencies_jsonl(&path).unwrap();
+
+        assert_eq!(read_back.len(), 1);
+        assert_eq!(read_back[0].name, "serde");
+        assert_eq!(read_back[0].manifest_path, "Cargo.toml");
+        assert!(read_dependencies_jsonl(&temp.path().join("missing.jsonl"))
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_write_and_read_stats_json() {
         let temp = TempDir::new().unwrap();
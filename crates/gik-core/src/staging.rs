@@ -444,14 +444,19 @@ pub struct IndexedFileInfo {
     pub indexed_mtime: Option<u64>,
     /// File size when indexed.
     pub indexed_size: Option<u64>,
+    /// Content hash when indexed (see [`crate::base::stable_content_hash`]).
+    pub content_hash: Option<String>,
 }
 
 /// Detect whether a file has changed since it was last indexed.
 ///
-/// Compares current file metadata (mtime, size) against stored indexed metadata.
 /// Returns `ChangeType::New` if the file wasn't previously indexed.
-/// Returns `ChangeType::Modified` if mtime or size differs.
-/// Returns `ChangeType::Unchanged` if both match.
+/// Otherwise:
+/// - A size difference always means `ChangeType::Modified` (no read needed).
+/// - If a content hash was stored, the file is hashed and compared. This is
+///   authoritative: `git checkout`, `touch` or a rebase that only bumps mtime
+///   stays `Unchanged`, and same-size edits within one second are detected.
+/// - Entries indexed before content hashing fall back to comparing mtime.
 ///
 /// # Arguments
 ///
@@ -472,20 +477,33 @@ pub fn detect_file_change(
     // Get current file metadata
     let metadata = std::fs::metadata(file_path)?;
     let current_size = metadata.len();
+
+    if info.indexed_size.is_some_and(|s| s != current_size) {
+        return Ok(ChangeType::Modified);
+    }
+
+    // Content hash is authoritative when available
+    if let Some(indexed_hash) = &info.content_hash {
+        let content = std::fs::read(file_path)?;
+        return if crate::base::stable_content_hash(&content) == *indexed_hash {
+            Ok(ChangeType::Unchanged)
+        } else {
+            Ok(ChangeType::Modified)
+        };
+    }
+
+    // Legacy entries: compare mtime
     let current_mtime = metadata
         .modified()
         .ok()
         .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
         .map(|d| d.as_secs());
-
-    // Compare with indexed metadata
-    let size_matches = info.indexed_size.map_or(true, |s| s == current_size);
     let mtime_matches = match (current_mtime, info.indexed_mtime) {
         (Some(curr), Some(idx)) => curr == idx,
         _ => true, // If we can't compare mtime, assume unchanged
     };
 
-    if size_matches && mtime_matches {
+    if mtime_matches {
         Ok(ChangeType::Unchanged)
     } else {
         Ok(ChangeType::Modified)
@@ -1863,4 +1881,58 @@ mod tests {
         assert_eq!(sources[0].uri, "feature.rs");
         assert_eq!(sources[0].branch, "feature");
     }
+
+    #[test]
+    fn test_detect_file_change_uses_content_hash() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("lib.rs");
+        fs::write(&path, "fn a() {}").unwrap();
+
+        let (_, size) = get_file_metadata(&path).unwrap();
+        let info = IndexedFileInfo {
+            file_path: "lib.rs".to_string(),
+            // Stale mtime (e.g., after git checkout) must not matter
+            indexed_mtime: Some(1),
+            indexed_size: Some(size),
+            content_hash: Some(crate::base::stable_content_hash(b"fn a() {}")),
+        };
+        assert_eq!(
+            detect_file_change(&path, Some(&info)).unwrap(),
+            ChangeType::Unchanged
+        );
+
+        // Same-size edit is detected regardless of mtime
+        fs::write(&path, "fn b() {}").unwrap();
+        assert_eq!(
+            detect_file_change(&path, Some(&info)).unwrap(),
+            ChangeType::Modified
+        );
+    }
+
+    #[test]
+    fn test_detect_file_change_legacy_mtime_fallback() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("lib.rs");
+        fs::write(&path, "fn a() {}").unwrap();
+
+        let (mtime, size) = get_file_metadata(&path).unwrap();
+        let mut info = IndexedFileInfo {
+            file_path: "lib.rs".to_string(),
+            indexed_mtime: Some(mtime),
+            indexed_size: Some(size),
+            content_hash: None,
+        };
+        assert_eq!(
+            detect_file_change(&path, Some(&info)).unwrap(),
+            ChangeType::Unchanged
+        );
+
+        info.indexed_mtime = Some(mtime.saturating_sub(10));
+        assert_eq!(
+            detect_file_change(&path, Some(&info)).unwrap(),
+            ChangeType::Modified
+        );
+
+        assert_eq!(detect_file_change(&path, None).unwrap(), ChangeType::New);
+    }
 }
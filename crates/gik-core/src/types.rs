@@ -149,6 +149,17 @@ pub struct ReindexOptions {
     pub dry_run: bool,
 }
 
+/// Options for the `index migrate` command.
+#[derive(Debug, Default, Clone)]
+pub struct IndexMigrateOptions {
+    /// The base whose index is migrated.
+    pub base: String,
+    /// Which branch to migrate on (defaults to current branch if None).
+    pub branch: Option<String>,
+    /// Target backend name (e.g., "hnsw", "lancedb", "simple_file").
+    pub to: String,
+}
+
 /// Query for the `stats` command.
 #[derive(Debug, Default, Clone)]
 pub struct StatsQuery {
@@ -290,6 +301,28 @@ pub struct ReindexBaseResult {
     pub errors: Vec<String>,
 }
 
+/// Result of the `index migrate` command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexMigrateResult {
+    /// The base name.
+    pub base: String,
+
+    /// The backend the index used before migration.
+    pub from_backend: String,
+
+    /// The backend the index uses now.
+    pub to_backend: String,
+
+    /// Number of vectors copied.
+    pub vectors: usize,
+
+    /// Backend the configuration resolves for this base, when it differs from
+    /// `to_backend` (later commits will refuse the index until it is updated).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_backend: Option<String>,
+}
+
 /// Result of memory ingestion via [`GikEngine::ingest_memory`].
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -351,6 +384,10 @@ pub struct StatsReport {
 
     /// Total on-disk size in bytes.
     pub total_on_disk_bytes: u64,
+
+    /// Most central files in the KG import graph, highest first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub central_files: Vec<crate::kg::centrality::CentralNode>,
 }
 
 // Note: ReleaseResult is defined in release.rs
@@ -68,8 +68,9 @@ pub const LANCEDB_TABLE_NAME: &str = "vectors";
 
 /// Supported vector index backend types.
 ///
-/// As of Phase 8.1, `LanceDb` is the default backend.
-/// `SimpleFile` is deprecated and should only be used for legacy indexes.
+/// As of Phase 8.1, `LanceDb` is the default backend. `Hnsw` is an embedded
+/// graph index without the Arrow/DataFusion dependency tree. `SimpleFile` is
+/// the zero-dependency linear-scan backend for small bases.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum VectorIndexBackendKind {
@@ -78,7 +79,10 @@ pub enum VectorIndexBackendKind {
     #[serde(rename = "lancedb")]
     LanceDb,
 
-    /// Simple file-based backend with linear scan search (deprecated).
+    /// Embedded HNSW graph backend (pure Rust, sub-linear search).
+    Hnsw,
+
+    /// Simple file-based backend with linear scan search.
     SimpleFile,
 
     /// Other/custom backend (for extensibility).
@@ -90,6 +94,7 @@ impl fmt::Display for VectorIndexBackendKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::LanceDb => write!(f, "lancedb"),
+            Self::Hnsw => write!(f, "hnsw"),
             Self::SimpleFile => write!(f, "simple_file"),
             Self::Other(s) => write!(f, "{}", s),
         }
@@ -102,7 +107,8 @@ impl FromStr for VectorIndexBackendKind {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s.to_lowercase().as_str() {
             "lancedb" | "lance_db" | "lance" => Self::LanceDb,
-            "simple_file" | "simplefile" => Self::SimpleFile,
+            "hnsw" => Self::Hnsw,
+            "simple_file" | "simplefile" | "simple" => Self::SimpleFile,
             other => Self::Other(other.to_string()),
         })
     }
@@ -137,15 +143,62 @@ impl fmt::Display for VectorMetric {
     }
 }
 
+impl VectorMetric {
+    /// Parse a metric name, returning `None` for unknown names.
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "cosine" => Some(Self::Cosine),
+            "dot" => Some(Self::Dot),
+            "l2" | "euclidean" => Some(Self::L2),
+            _ => None,
+        }
+    }
+}
+
 impl FromStr for VectorMetric {
     type Err = std::convert::Infallible;
 
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Default to cosine for unknown metrics
+        Ok(Self::from_name(s).unwrap_or_default())
+    }
+}
+
+// ============================================================================
+// VectorStorage
+// ============================================================================
+
+/// Element type used to persist raw vectors.
+///
+/// Only the `simple_file` backend honors this; `f16` halves the file size at
+/// a small precision cost.
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorStorage {
+    /// 32-bit floats (default).
+    #[default]
+    F32,
+
+    /// 16-bit half-precision floats.
+    F16,
+}
+
+impl fmt::Display for VectorStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::F32 => write!(f, "f32"),
+            Self::F16 => write!(f, "f16"),
+        }
+    }
+}
+
+impl FromStr for VectorStorage {
+    type Err = std::convert::Infallible;
+
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s.to_lowercase().as_str() {
-            "cosine" => Self::Cosine,
-            "dot" => Self::Dot,
-            "l2" | "euclidean" => Self::L2,
-            _ => Self::Cosine, // Default to cosine for unknown metrics
+            "f16" | "half" => Self::F16,
+            _ => Self::F32, // Default to f32 for unknown storage types
         })
     }
 }
@@ -206,6 +259,14 @@ pub struct VectorIndexConfig {
 
     /// Knowledge base name (e.g., "code", "docs").
     pub base: String,
+
+    /// On-disk vector element type, used when `backend` is `simple_file`.
+    #[serde(default)]
+    pub storage: VectorStorage,
+
+    /// Graph parameters, used when `backend` is `hnsw`.
+    #[serde(default)]
+    pub hnsw: HnswParams,
 }
 
 impl VectorIndexConfig {
@@ -221,6 +282,8 @@ impl VectorIndexConfig {
             metric,
             dimension,
             base: base.into(),
+            storage: VectorStorage::default(),
+            hnsw: HnswParams::default(),
         }
     }
 
@@ -233,6 +296,47 @@ impl VectorIndexConfig {
             metric: VectorMetric::Cosine,
             dimension,
             base: base.into(),
+            storage: VectorStorage::default(),
+            hnsw: HnswParams::default(),
+        }
+    }
+
+    /// Set the on-disk vector element type.
+    pub fn with_storage(mut self, storage: VectorStorage) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Set the HNSW graph parameters.
+    pub fn with_hnsw(mut self, hnsw: HnswParams) -> Self {
+        self.hnsw = hnsw;
+        self
+    }
+}
+
+/// HNSW graph parameters.
+///
+/// `m` and `ef_construction` are fixed when a graph is built; `ef_search`
+/// applies to every query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HnswParams {
+    /// Maximum neighbors per node (layer 0 keeps `2 * m`).
+    pub m: u32,
+
+    /// Candidate list size while building the graph.
+    pub ef_construction: u32,
+
+    /// Candidate list size while searching.
+    pub ef_search: u32,
+}
+
+impl Default for HnswParams {
+    fn default() -> Self {
+        Self {
+            m: gik_db::vector::DEFAULT_HNSW_M as u32,
+            ef_construction: gik_db::vector::DEFAULT_HNSW_EF_CONSTRUCTION as u32,
+            ef_search: gik_db::vector::DEFAULT_HNSW_EF_SEARCH as u32,
         }
     }
 }
@@ -593,7 +697,7 @@ pub fn check_index_compatibility(
 
     // Different backend that we can't handle
     let config_backend = config.backend.to_string();
-    if meta.backend != config_backend && meta.backend != "simple_file" {
+    if meta.backend != config_backend {
         return VectorIndexCompatibility::BackendMismatch {
             config_backend,
             meta_backend: meta.backend.clone(),
@@ -642,7 +746,7 @@ pub fn default_vector_index_config_for_base(
 /// # Errors
 ///
 /// Returns an error if:
-/// - A legacy SimpleFile index is detected (must run `gik reindex`)
+/// - A legacy `records.jsonl` index is detected (must run `gik reindex`)
 /// - Index creation fails
 pub fn open_vector_index(
     index_root: PathBuf,
@@ -654,13 +758,13 @@ pub fn open_vector_index(
         // Check if meta.json says it's a simple_file backend
         let meta_path = index_root.join(INDEX_META_FILENAME);
         if let Ok(Some(meta)) = load_index_meta(&meta_path) {
-            if meta.backend == "simple_file" && config.backend == VectorIndexBackendKind::LanceDb {
+            if meta.backend == "simple_file" {
                 return Err(GikError::VectorIndexIncompatible {
                     base: config.base.clone(),
                     reason: format!(
                         "Legacy SimpleFile index detected at {}. \
-                         The index format is obsolete. \
-                         Please run `gik reindex --base {}` to rebuild the index with LanceDB.",
+                         The records.jsonl format is obsolete. \
+                         Please run `gik reindex --base {}` to rebuild the index.",
                         index_root.display(),
                         config.base
                     ),
@@ -671,16 +775,12 @@ pub fn open_vector_index(
 
     // Open the appropriate backend
     match config.backend {
-        VectorIndexBackendKind::LanceDb => {
-            // Use gik-db via the adapter for LanceDB backend
-            use crate::db_adapter::DbVectorIndex;
-
-            let db_config =
-                gik_db::vector::VectorIndexConfig::new(config.dimension as usize, &index_root)
-                    .with_backend("lancedb")
-                    .with_metric(gik_db::vector::VectorMetric::Cosine);
-
-            let db_index = DbVectorIndex::open(&db_config)?;
+        VectorIndexBackendKind::LanceDb
+        | VectorIndexBackendKind::Hnsw
+        | VectorIndexBackendKind::SimpleFile => {
+            // Use gik-db via the adapter for all built-in backends
+            let db_config = to_db_index_config(&index_root, &config);
+            let db_index = crate::db_adapter::DbVectorIndex::open(&db_config)?;
 
             // Wrap in a boxed trait object with the right config
             let wrapped = DbVectorIndexWithConfig {
@@ -689,13 +789,6 @@ pub fn open_vector_index(
             };
             Ok(Box::new(wrapped))
         }
-        VectorIndexBackendKind::SimpleFile => {
-            // SimpleFile backend removed in Phase 4 migration
-            Err(GikError::VectorIndexIncompatible {
-                base: config.base.clone(),
-                reason: "SimpleFile backend is no longer supported. Run `gik reindex` to migrate to LanceDB.".to_string(),
-            })
-        }
         VectorIndexBackendKind::Other(ref name) => Err(GikError::VectorIndexIncompatible {
             base: config.base.clone(),
             reason: format!("Unknown vector index backend: {}", name),
@@ -703,6 +796,103 @@ pub fn open_vector_index(
     }
 }
 
+/// Build the gik-db configuration for an index directory.
+fn to_db_index_config(
+    index_root: &Path,
+    config: &VectorIndexConfig,
+) -> gik_db::vector::VectorIndexConfig {
+    use crate::db_adapter::{to_db_hnsw_config, to_db_storage};
+
+    // gik-db registers the simple_file backend as "simple"
+    let db_backend = match config.backend {
+        VectorIndexBackendKind::SimpleFile => "simple".to_string(),
+        ref other => other.to_string(),
+    };
+    gik_db::vector::VectorIndexConfig::new(config.dimension as usize, index_root)
+        .with_backend(db_backend)
+        .with_metric(gik_db::vector::VectorMetric::Cosine)
+        .with_storage(to_db_storage(config.storage))
+        .with_hnsw(to_db_hnsw_config(&config.hnsw))
+}
+
+/// Number of vectors read from the source per batch by [`migrate_vector_index`].
+const MIGRATE_BATCH_SIZE: usize = 1024;
+
+/// Move an index to another backend without re-embedding.
+///
+/// Vectors and payloads are read in batches from `index_root` (opened with
+/// `from`) and upserted batch by batch into a staging directory opened with
+/// `to`, which is persisted once at the end.
+/// Once the vector counts match, the staging directory replaces `index_root`
+/// and `meta.json` is rewritten with the new backend; on any error the
+/// original index is left untouched.
+///
+/// Returns the number of vectors migrated.
+///
+/// # Errors
+///
+/// Returns an error if `index_root` has no `meta.json`, the backends are not
+/// built-in, a copy fails, or the counts differ afterwards.
+pub fn migrate_vector_index(
+    index_root: &Path,
+    from: &VectorIndexConfig,
+    to: &VectorIndexConfig,
+) -> Result<usize, GikError> {
+    use crate::db_adapter::{DbVectorIndex, IntoGikResult};
+
+    let meta_path = index_root.join(INDEX_META_FILENAME);
+    let mut meta = load_index_meta(&meta_path)?.ok_or_else(|| GikError::VectorIndexIo {
+        path: meta_path.clone(),
+        message: "Index metadata not found".to_string(),
+    })?;
+
+    for config in [from, to] {
+        if let VectorIndexBackendKind::Other(ref name) = config.backend {
+            return Err(GikError::VectorIndexIncompatible {
+                base: config.base.clone(),
+                reason: format!("Unknown vector index backend: {}", name),
+            });
+        }
+    }
+
+    let io_error = |path: &Path, action: &str, e: std::io::Error| GikError::VectorIndexIo {
+        path: path.to_path_buf(),
+        message: format!("Failed to {}: {}", action, e),
+    };
+
+    let staging = index_root.with_file_name("index.migrating");
+    let retired = index_root.with_file_name("index.previous");
+    for leftover in [&staging, &retired] {
+        if leftover.exists() {
+            fs::remove_dir_all(leftover)
+                .map_err(|e| io_error(leftover, "remove leftover directory", e))?;
+        }
+    }
+
+    let copied = {
+        let source = DbVectorIndex::open(
+            &to_db_index_config(index_root, from).with_create_if_missing(false),
+        )?;
+        let target = DbVectorIndex::open(&to_db_index_config(&staging, to))?;
+        gik_db::vector::copy_vectors(source.inner(), target.inner(), MIGRATE_BATCH_SIZE)
+            .into_gik_result()?
+    };
+
+    meta.backend = to.backend.to_string();
+    meta.touch();
+    write_index_meta(&staging.join(INDEX_META_FILENAME), &meta)?;
+
+    // Swap directories; the old index is only removed once the new one is live
+    fs::rename(index_root, &retired).map_err(|e| io_error(index_root, "retire old index", e))?;
+    if let Err(e) = fs::rename(&staging, index_root) {
+        let _ = fs::rename(&retired, index_root);
+        return Err(io_error(&staging, "install migrated index", e));
+    }
+    fs::remove_dir_all(&retired).map_err(|e| io_error(&retired, "remove old index", e))?;
+
+    Ok(copied)
+}
+
 // ============================================================================
 // DbVectorIndexWithConfig wrapper
 // ============================================================================
@@ -776,6 +966,7 @@ mod tests {
     #[test]
     fn test_vector_index_backend_kind_display() {
         assert_eq!(VectorIndexBackendKind::LanceDb.to_string(), "lancedb");
+        assert_eq!(VectorIndexBackendKind::Hnsw.to_string(), "hnsw");
         assert_eq!(
             VectorIndexBackendKind::SimpleFile.to_string(),
             "simple_file"
@@ -800,6 +991,10 @@ mod tests {
             VectorIndexBackendKind::from_str("simple_file").unwrap(),
             VectorIndexBackendKind::SimpleFile
         );
+        assert_eq!(
+            VectorIndexBackendKind::from_str("HNSW").unwrap(),
+            VectorIndexBackendKind::Hnsw
+        );
     }
 
     #[test]
@@ -947,4 +1142,160 @@ mod tests {
         fs::write(index_dir.join(INDEX_RECORDS_FILENAME), "").unwrap();
         assert!(is_legacy_index(&index_dir));
     }
+
+    #[test]
+    fn test_open_hnsw_index_roundtrip() {
+        let dir = tempdir().unwrap();
+        let index_dir = dir.path().join("index");
+        let config = VectorIndexConfig::new(
+            VectorIndexBackendKind::Hnsw,
+            VectorMetric::Cosine,
+            3,
+            "code",
+        );
+
+        let mut index =
+            open_vector_index(index_dir.clone(), config.clone(), &test_embedding_config()).unwrap();
+        index
+            .upsert(&[
+                VectorInsert::new(1u64, vec![1.0, 0.0, 0.0], serde_json::json!({"n": 1})),
+                VectorInsert::new(2u64, vec![0.0, 1.0, 0.0], serde_json::json!({"n": 2})),
+            ])
+            .unwrap();
+        drop(index);
+
+        let index = open_vector_index(index_dir, config, &test_embedding_config()).unwrap();
+        let stats = index.stats().unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.backend, "hnsw");
+
+        let results = index.query(&[0.0, 0.9, 0.1], 1).unwrap();
+        assert_eq!(results[0].id, VectorId(2));
+    }
+
+    #[test]
+    fn test_open_simple_file_index_with_f16_storage() {
+        let dir = tempdir().unwrap();
+        let index_dir = dir.path().join("index");
+        let config = VectorIndexConfig::new(
+            VectorIndexBackendKind::SimpleFile,
+            VectorMetric::Cosine,
+            3,
+            "notes",
+        )
+        .with_storage(VectorStorage::F16);
+
+        let mut index =
+            open_vector_index(index_dir.clone(), config.clone(), &test_embedding_config()).unwrap();
+        index
+            .upsert(&[
+                VectorInsert::new(1u64, vec![1.0, 0.0, 0.0], serde_json::json!({"n": 1})),
+                VectorInsert::new(2u64, vec![0.0, 1.0, 0.0], serde_json::json!({"n": 2})),
+            ])
+            .unwrap();
+        drop(index);
+
+        let index = open_vector_index(index_dir, config, &test_embedding_config()).unwrap();
+        let stats = index.stats().unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.backend, "simple_file");
+        assert_eq!(index.config().storage, VectorStorage::F16);
+
+        let results = index.query(&[0.0, 0.9, 0.1], 1).unwrap();
+        assert_eq!(results[0].id, VectorId(2));
+    }
+
+    #[test]
+    fn test_migrate_vector_index_to_hnsw() {
+        let dir = tempdir().unwrap();
+        let index_dir = dir.path().join("index");
+        let embedding = test_embedding_config();
+        let from = VectorIndexConfig::new(
+            VectorIndexBackendKind::SimpleFile,
+            VectorMetric::Cosine,
+            3,
+            "code",
+        );
+        let to = VectorIndexConfig::new(
+            VectorIndexBackendKind::Hnsw,
+            VectorMetric::Cosine,
+            3,
+            "code",
+        );
+
+        let mut index = open_vector_index(index_dir.clone(), from.clone(), &embedding).unwrap();
+        index
+            .upsert(&[
+                VectorInsert::new(1u64, vec![1.0, 0.0, 0.0], serde_json::json!({"n": 1})),
+                VectorInsert::new(2u64, vec![0.0, 1.0, 0.0], serde_json::json!({"n": 2})),
+                VectorInsert::new(3u64, vec![0.0, 0.0, 1.0], serde_json::json!({"n": 3})),
+            ])
+            .unwrap();
+        drop(index);
+        let meta = VectorIndexMeta::from_config(&from, &embedding);
+        write_index_meta(&index_dir.join(INDEX_META_FILENAME), &meta).unwrap();
+
+        assert_eq!(migrate_vector_index(&index_dir, &from, &to).unwrap(), 3);
+
+        let migrated = load_index_meta(&index_dir.join(INDEX_META_FILENAME))
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated.backend, "hnsw");
+        assert_eq!(migrated.embedding_model_id, meta.embedding_model_id);
+        assert_eq!(migrated.created_at, meta.created_at);
+        assert!(!dir.path().join("index.migrating").exists());
+        assert!(!dir.path().join("index.previous").exists());
+
+        let index = open_vector_index(index_dir, to, &embedding).unwrap();
+        assert_eq!(index.stats().unwrap().count, 3);
+        let results = index.query(&[0.1, 0.9, 0.0], 1).unwrap();
+        assert_eq!(results[0].id, VectorId(2));
+        assert_eq!(results[0].payload, serde_json::json!({"n": 2}));
+    }
+
+    #[test]
+    fn test_migrate_vector_index_requires_meta() {
+        let dir = tempdir().unwrap();
+        let index_dir = dir.path().join("index");
+        let from = VectorIndexConfig::new(
+            VectorIndexBackendKind::SimpleFile,
+            VectorMetric::Cosine,
+            3,
+            "code",
+        );
+        let to = VectorIndexConfig::new(
+            VectorIndexBackendKind::Hnsw,
+            VectorMetric::Cosine,
+            3,
+            "code",
+        );
+
+        assert!(migrate_vector_index(&index_dir, &from, &to).is_err());
+        assert!(!index_dir.exists());
+    }
+
+    #[test]
+    fn test_check_index_compatibility_simple_file_backend() {
+        let embedding = test_embedding_config();
+        let config = VectorIndexConfig::new(
+            VectorIndexBackendKind::SimpleFile,
+            VectorMetric::Cosine,
+            4,
+            "code",
+        );
+        let meta = VectorIndexMeta::from_config(&config, &embedding);
+        assert!(check_index_compatibility(&config, &embedding, Some(&meta)).is_compatible());
+
+        // A simple_file index cannot be opened as another embedded backend
+        let hnsw = VectorIndexConfig::new(
+            VectorIndexBackendKind::Hnsw,
+            VectorMetric::Cosine,
+            4,
+            "code",
+        );
+        assert!(matches!(
+            check_index_compatibility(&hnsw, &embedding, Some(&meta)),
+            VectorIndexCompatibility::BackendMismatch { .. }
+        ));
+    }
 }
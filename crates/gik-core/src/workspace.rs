@@ -135,7 +135,8 @@ impl Workspace {
 
         let guided_dir = root.join(GUIDED_DIR);
         let knowledge_root = guided_dir.join(KNOWLEDGE_DIR);
-        let has_git = root.join(".git").is_dir();
+        // `.git` is a file in worktrees and submodules
+        let has_git = root.join(".git").exists();
         let initialized = knowledge_root.is_dir();
 
         Ok(Self {
@@ -177,8 +178,8 @@ impl Workspace {
                 return Self::from_root(current);
             }
 
-            // Check for .git directory (Git repository root)
-            if current.join(".git").is_dir() {
+            // Check for .git (Git repository root; a file in worktrees)
+            if current.join(".git").exists() {
                 return Self::from_root(current);
             }
 
@@ -204,6 +205,16 @@ impl Workspace {
         &self.knowledge_root
     }
 
+    /// The same workspace reading its knowledge from `knowledge_root`.
+    ///
+    /// Used to ask against a scratch copy of the knowledge (see `gik eval`).
+    pub(crate) fn with_knowledge_root(&self, knowledge_root: PathBuf) -> Self {
+        Self {
+            knowledge_root,
+            ..self.clone()
+        }
+    }
+
     /// Get the path to the `.guided` directory.
     pub fn guided_dir(&self) -> PathBuf {
         self.root.join(GUIDED_DIR)
@@ -597,6 +608,15 @@ mod tests {
         assert!(!workspace.is_initialized());
     }
 
+    #[test]
+    fn test_workspace_from_root_in_git_worktree() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join(".git"), "gitdir: /repo/.git/worktrees/wt\n").unwrap();
+
+        let workspace = Workspace::from_root(temp.path()).unwrap();
+        assert!(workspace.has_git());
+    }
+
     #[test]
     fn test_workspace_from_root_with_guided() {
         let temp = TempDir::new().unwrap();
@@ -743,6 +743,31 @@ impl KgStoreBackend for LanceDbKgStore {
         })
     }
 
+    fn get_nodes_page(&self, offset: usize, limit: usize) -> DbResult<Vec<KgNode>> {
+        trace!("Getting nodes page: offset={}, limit={}", offset, limit);
+
+        let table = self.get_nodes_table()?;
+
+        self.runtime.block_on(async {
+            let results = table
+                .query()
+                .offset(offset)
+                .limit(limit)
+                .execute()
+                .await
+                .map_err(|e| DbError::LanceDb {
+                    message: format!("Query nodes page failed: {}", e),
+                })?;
+
+            let batches: Vec<RecordBatch> =
+                results.try_collect().await.map_err(|e| DbError::LanceDb {
+                    message: format!("Failed to collect node results: {}", e),
+                })?;
+
+            self.parse_nodes_from_batches(batches)
+        })
+    }
+
     fn delete_nodes(&self, ids: &[&str]) -> DbResult<usize> {
         if ids.is_empty() {
             return Ok(0);
@@ -984,6 +1009,31 @@ impl KgStoreBackend for LanceDbKgStore {
         })
     }
 
+    fn get_edges_page(&self, offset: usize, limit: usize) -> DbResult<Vec<KgEdge>> {
+        trace!("Getting edges page: offset={}, limit={}", offset, limit);
+
+        let table = self.get_edges_table()?;
+
+        self.runtime.block_on(async {
+            let results = table
+                .query()
+                .offset(offset)
+                .limit(limit)
+                .execute()
+                .await
+                .map_err(|e| DbError::LanceDb {
+                    message: format!("Query edges page failed: {}", e),
+                })?;
+
+            let batches: Vec<RecordBatch> =
+                results.try_collect().await.map_err(|e| DbError::LanceDb {
+                    message: format!("Failed to collect edge results: {}", e),
+                })?;
+
+            self.parse_edges_from_batches(batches)
+        })
+    }
+
     fn delete_edges(&self, ids: &[&str]) -> DbResult<usize> {
         if ids.is_empty() {
             return Ok(0);
@@ -1125,6 +1175,40 @@ mod tests {
         assert_eq!(imports.len(), 2);
     }
 
+    #[test]
+    fn test_get_pages() {
+        let (_temp_dir, store) = create_test_store();
+
+        let nodes: Vec<KgNode> = (0..5)
+            .map(|i| KgNode::new(format!("file:{}.rs", i), "file", format!("{}.rs", i)))
+            .collect();
+        store.upsert_nodes(&nodes).unwrap();
+        let edges: Vec<KgEdge> = (0..4)
+            .map(|i| {
+                KgEdge::new(
+                    format!("file:{}.rs", i),
+                    format!("file:{}.rs", i + 1),
+                    "imports",
+                )
+            })
+            .collect();
+        store.upsert_edges(&edges).unwrap();
+
+        let mut node_ids: Vec<String> = (0..3)
+            .flat_map(|page| store.get_nodes_page(page * 2, 2).unwrap())
+            .map(|n| n.id)
+            .collect();
+        node_ids.sort();
+        assert_eq!(
+            node_ids,
+            nodes.iter().map(|n| n.id.clone()).collect::<Vec<_>>()
+        );
+
+        assert_eq!(store.get_edges_page(0, 3).unwrap().len(), 3);
+        assert_eq!(store.get_edges_page(3, 3).unwrap().len(), 1);
+        assert!(store.get_edges_page(4, 3).unwrap().is_empty());
+    }
+
     #[test]
     fn test_get_edges_from_to() {
         let (_temp_dir, store) = create_test_store();
@@ -72,6 +72,12 @@ pub trait KgStoreBackend: Send + Sync {
     /// Get nodes by branch.
     fn get_nodes_by_branch(&self, branch: &str) -> DbResult<Vec<KgNode>>;
 
+    /// Get up to `limit` nodes, skipping the first `offset`.
+    ///
+    /// Pages follow the store's scan order, which is stable while the store
+    /// is not written, so consecutive pages cover every node exactly once.
+    fn get_nodes_page(&self, offset: usize, limit: usize) -> DbResult<Vec<KgNode>>;
+
     /// Delete nodes by their IDs.
     fn delete_nodes(&self, ids: &[&str]) -> DbResult<usize>;
 
@@ -105,6 +111,10 @@ pub trait KgStoreBackend: Send + Sync {
     /// Get edges by branch.
     fn get_edges_by_branch(&self, branch: &str) -> DbResult<Vec<KgEdge>>;
 
+    /// Get up to `limit` edges, skipping the first `offset`. See
+    /// [`get_nodes_page`](Self::get_nodes_page).
+    fn get_edges_page(&self, offset: usize, limit: usize) -> DbResult<Vec<KgEdge>>;
+
     /// Delete edges by their IDs.
     fn delete_edges(&self, ids: &[&str]) -> DbResult<usize>;
 
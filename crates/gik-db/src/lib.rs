@@ -21,11 +21,12 @@
 //! ## Features
 //!
 //! - `lancedb` (default): LanceDB vector storage with ANN search
+//! - `hnsw` (default): Embedded HNSW vector backend (no extra dependencies)
 //! - `simple`: Simple file-based vector backend for testing
 //!
 //! ## Modules
 //!
-//! - `vector`: Vector index backends (LanceDB, SimpleFile)
+//! - `vector`: Vector index backends (LanceDB, HNSW, SimpleFile)
 //! - `kg`: Knowledge graph storage (LanceDB)
 //!
 //! ## Usage
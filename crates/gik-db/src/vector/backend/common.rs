@@ -0,0 +1,610 @@
+//! Shared building blocks for the embedded (file-based) vector backends.
+//!
+//! - [`StoredPayload`] - per-vector metadata persisted next to the vectors
+//! - [`matches_filter`] - in-process evaluation of [`VectorSearchFilter`]
+//! - Similarity kernels and [`Scorer`] (higher is better for every metric)
+//! - [`TopK`] - bounded heap for collecting the best-scoring rows
+//! - [`Generations`] - crash-safe replacement of a set of data files
+
+use super::super::metadata::VectorSearchFilter;
+use super::super::traits::{VectorId, VectorInsert, VectorMetric};
+use crate::error::{DbError, DbResult};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+/// Accumulator lanes used by the similarity kernels.
+const LANES: usize = 8;
+
+// ============================================================================
+// StoredPayload
+// ============================================================================
+
+/// Metadata stored for each vector (everything except the vector itself).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct StoredPayload {
+    pub id: u64,
+    pub payload: serde_json::Value,
+    pub base: String,
+    pub branch: Option<String>,
+    pub source_type: String,
+    pub path: Option<String>,
+    pub tags: Vec<String>,
+    pub revision_id: Option<String>,
+}
+
+impl From<&VectorInsert> for StoredPayload {
+    fn from(insert: &VectorInsert) -> Self {
+        Self {
+            id: insert.id.value(),
+            payload: insert.payload.clone(),
+            base: insert.base.clone(),
+            branch: insert.branch.clone(),
+            source_type: insert.source_type.clone(),
+            path: insert.path.clone(),
+            tags: insert.tags.clone(),
+            revision_id: insert.revision_id.clone(),
+        }
+    }
+}
+
+impl StoredPayload {
+    /// Rebuild the insert this record was stored from.
+    pub fn to_insert(&self, vector: &[f32]) -> VectorInsert {
+        VectorInsert {
+            id: VectorId::new(self.id),
+            vector: vector.to_vec(),
+            payload: self.payload.clone(),
+            base: self.base.clone(),
+            branch: self.branch.clone(),
+            source_type: self.source_type.clone(),
+            path: self.path.clone(),
+            tags: self.tags.clone(),
+            revision_id: self.revision_id.clone(),
+        }
+    }
+}
+
+/// Check if a stored vector matches the filter.
+pub(super) fn matches_filter(stored: &StoredPayload, filter: &VectorSearchFilter) -> bool {
+    // Check base
+    if let Some(ref base) = filter.base {
+        if &stored.base != base {
+            return false;
+        }
+    }
+
+    // Check branch
+    if let Some(ref branch) = filter.branch {
+        match &stored.branch {
+            Some(b) if b == branch => {}
+            _ => return false,
+        }
+    }
+
+    // Check source_type
+    if let Some(ref source_type) = filter.source_type {
+        if &stored.source_type != source_type {
+            return false;
+        }
+    }
+
+    // Check path prefix
+    if let Some(ref prefix) = filter.path_prefix {
+        match &stored.path {
+            Some(p) if p.starts_with(prefix) => {}
+            _ => return false,
+        }
+    }
+
+    // Check tags (must have ALL)
+    for tag in &filter.tags {
+        if !stored.tags.contains(tag) {
+            return false;
+        }
+    }
+
+    // Check revision_id
+    if let Some(ref revision_id) = filter.revision_id {
+        match &stored.revision_id {
+            Some(r) if r == revision_id => {}
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+// ============================================================================
+// Similarity Kernels
+// ============================================================================
+//
+// The kernels accumulate into `LANES` independent partial sums over fixed-size
+// chunks, which lets the compiler vectorize them (SSE/AVX/NEON) without
+// target-specific code.
+
+/// Compute dot product between two vectors.
+fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+    let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let tail: f32 = a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .map(|(x, y)| x * y)
+        .sum();
+
+    let mut acc = [0.0f32; LANES];
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for ((s, x), y) in acc.iter_mut().zip(x).zip(y) {
+            *s += x * y;
+        }
+    }
+    acc.iter().sum::<f32>() + tail
+}
+
+/// Compute `a · b` and `|b|²` in a single pass.
+fn dot_and_norm_sq(a: &[f32], b: &[f32]) -> (f32, f32) {
+    let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let (mut dot, mut norm) = a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .fold((0.0f32, 0.0f32), |(d, n), (x, y)| (d + x * y, n + y * y));
+
+    let mut dot_acc = [0.0f32; LANES];
+    let mut norm_acc = [0.0f32; LANES];
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for (((d, n), x), y) in dot_acc.iter_mut().zip(norm_acc.iter_mut()).zip(x).zip(y) {
+            *d += x * y;
+            *n += y * y;
+        }
+    }
+    dot += dot_acc.iter().sum::<f32>();
+    norm += norm_acc.iter().sum::<f32>();
+    (dot, norm)
+}
+
+/// Compute Euclidean (L2) distance between two vectors.
+fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let tail: f32 = a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum();
+
+    let mut acc = [0.0f32; LANES];
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for ((s, x), y) in acc.iter_mut().zip(x).zip(y) {
+            let d = x - y;
+            *s += d * d;
+        }
+    }
+    (acc.iter().sum::<f32>() + tail).sqrt()
+}
+
+// ============================================================================
+// Scoring
+// ============================================================================
+
+/// Scores rows against a fixed query, with per-query work done once.
+pub(super) struct Scorer<'a> {
+    metric: VectorMetric,
+    query: &'a [f32],
+    query_norm: f32,
+}
+
+impl<'a> Scorer<'a> {
+    pub fn new(metric: VectorMetric, query: &'a [f32]) -> Self {
+        let query_norm = match metric {
+            VectorMetric::Cosine => dot_product(query, query).sqrt(),
+            VectorMetric::Dot | VectorMetric::L2 => 0.0,
+        };
+        Self {
+            metric,
+            query,
+            query_norm,
+        }
+    }
+
+    /// Higher is better for every metric.
+    pub fn score(&self, row: &[f32]) -> f32 {
+        match self.metric {
+            VectorMetric::Cosine => {
+                let (dot, row_norm_sq) = dot_and_norm_sq(self.query, row);
+                if self.query_norm == 0.0 || row_norm_sq == 0.0 {
+                    return 0.0;
+                }
+                dot / (self.query_norm * row_norm_sq.sqrt())
+            }
+            VectorMetric::Dot => dot_product(self.query, row),
+            VectorMetric::L2 => -euclidean_distance(self.query, row), // Negate so higher is better
+        }
+    }
+}
+
+// ============================================================================
+// TopK
+// ============================================================================
+
+/// A scored row, ordered so that `BinaryHeap` keeps the *lowest* score on top.
+struct Candidate {
+    score: f32,
+    row: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .score
+            .total_cmp(&self.score)
+            .then_with(|| self.row.cmp(&other.row))
+    }
+}
+
+/// Bounded min-heap collecting the `limit` best-scoring rows.
+pub(super) struct TopK {
+    limit: usize,
+    heap: BinaryHeap<Candidate>,
+}
+
+impl TopK {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            heap: BinaryHeap::with_capacity(limit.saturating_add(1).min(1024)),
+        }
+    }
+
+    pub fn push(&mut self, score: f32, row: usize) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.heap.len() < self.limit {
+            self.heap.push(Candidate { score, row });
+        } else if let Some(worst) = self.heap.peek() {
+            if score > worst.score {
+                self.heap.pop();
+                self.heap.push(Candidate { score, row });
+            }
+        }
+    }
+
+    /// Results ordered by score, best first.
+    pub fn into_sorted(self) -> Vec<(f32, usize)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|c| (c.score, c.row))
+            .collect()
+    }
+}
+
+// ============================================================================
+// Generations
+// ============================================================================
+
+/// A set of data files that is replaced as a unit.
+///
+/// Each save writes every file under a new generation number (`vectors.bin`
+/// becomes `vectors.<gen>.bin`) and then atomically replaces a small manifest
+/// naming the live generation. A crash before the manifest rename leaves the
+/// previous generation live; files from older generations are removed after
+/// the next successful commit.
+///
+/// Generation numbers are never reused: [`Generations::allocate`] picks one
+/// above every generation on disk and reserves it with an exclusive create,
+/// and files are written to a private temporary before being renamed into
+/// place. A committed generation file is therefore never opened for writing,
+/// even by another process saving the same index.
+pub(super) struct Generations {
+    dir: PathBuf,
+    manifest: &'static str,
+    files: &'static [&'static str],
+}
+
+impl Generations {
+    pub fn new(dir: &Path, manifest: &'static str, files: &'static [&'static str]) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            manifest,
+            files,
+        }
+    }
+
+    /// The live generation, or `None` if nothing has been committed yet.
+    pub fn current(&self) -> DbResult<Option<u64>> {
+        let path = self.dir.join(self.manifest);
+        match fs::read_to_string(&path) {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| DbError::vector_parse(&path, "invalid generation manifest")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Path of `file` (one of the managed file names) in `generation`.
+    pub fn path(&self, file: &str, generation: u64) -> PathBuf {
+        let (stem, ext) = file.split_once('.').unwrap_or((file, ""));
+        self.dir.join(format!("{}.{}.{}", stem, generation, ext))
+    }
+
+    /// Reserve a new generation numbered above every generation on disk.
+    ///
+    /// The first managed file of the generation is created exclusively as a
+    /// placeholder, so concurrent writers never share a generation.
+    pub fn allocate(&self) -> DbResult<u64> {
+        fs::create_dir_all(&self.dir)?;
+        let mut generation = self.latest_on_disk()?.max(self.current()?.unwrap_or(0)) + 1;
+        loop {
+            let reserved = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.path(self.files[0], generation));
+            match reserved {
+                Ok(_) => return Ok(generation),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => generation += 1,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Write a data file of an allocated `generation` and sync it to disk.
+    ///
+    /// The contents go to a temporary file that is renamed into place once
+    /// complete.
+    pub fn write(
+        &self,
+        file: &str,
+        generation: u64,
+        contents: impl FnOnce(&mut BufWriter<File>) -> DbResult<()>,
+    ) -> DbResult<()> {
+        let path = self.path(file, generation);
+        let tmp = temp_path(&path);
+        let result = (|| {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            contents(&mut writer)?;
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+            fs::rename(&tmp, &path)?;
+            Ok(())
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        result
+    }
+
+    /// Make `generation` live and remove the files of older generations.
+    pub fn commit(&self, generation: u64) -> DbResult<()> {
+        let manifest = self.dir.join(self.manifest);
+        let tmp = temp_path(&manifest);
+        {
+            let mut file = File::create(&tmp)?;
+            writeln!(file, "{}", generation)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, &manifest)?;
+        self.remove_stale(generation);
+        Ok(())
+    }
+
+    /// The generation of a managed data file name, if it is one.
+    fn generation_of(&self, name: &str) -> Option<u64> {
+        self.files.iter().find_map(|file| {
+            let (stem, ext) = file.split_once('.').unwrap_or((file, ""));
+            name.strip_prefix(stem)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .and_then(|rest| rest.strip_suffix(ext))
+                .and_then(|rest| rest.strip_suffix('.'))
+                .and_then(|generation| generation.parse::<u64>().ok())
+        })
+    }
+
+    /// Highest generation with any data file on disk (0 if none).
+    fn latest_on_disk(&self) -> DbResult<u64> {
+        let mut latest = 0;
+        for entry in fs::read_dir(&self.dir)? {
+            if let Some(generation) = entry?
+                .file_name()
+                .to_str()
+                .and_then(|n| self.generation_of(n))
+            {
+                latest = latest.max(generation);
+            }
+        }
+        Ok(latest)
+    }
+
+    /// Best-effort removal of data files from generations older than `live`.
+    ///
+    /// Newer generations may belong to a save still in progress elsewhere.
+    fn remove_stale(&self, live: u64) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let stale = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| self.generation_of(name))
+                .is_some_and(|generation| generation < live);
+            if stale {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+/// A temporary path next to `path`, unique to this process and call.
+fn temp_path(path: &Path) -> PathBuf {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        NEXT.fetch_add(1, AtomicOrdering::Relaxed)
+    ));
+    PathBuf::from(name)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+        Scorer::new(VectorMetric::Cosine, a).score(b)
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        let a = vec![1.0, 0.0, 0.0];
+        let b = vec![1.0, 0.0, 0.0];
+        assert!((cosine_similarity(&a, &b) - 1.0).abs() < 1e-6);
+
+        let c = vec![0.0, 1.0, 0.0];
+        assert!(cosine_similarity(&a, &c).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dot_product() {
+        let a = vec![1.0, 2.0, 3.0];
+        let b = vec![4.0, 5.0, 6.0];
+        assert!((dot_product(&a, &b) - 32.0).abs() < 1e-6);
+
+        // Exercise the chunked path plus a remainder
+        let long: Vec<f32> = (0..19).map(|i| i as f32).collect();
+        let expected: f32 = long.iter().map(|x| x * x).sum();
+        assert!((dot_product(&long, &long) - expected).abs() < 1e-3);
+        assert!((dot_and_norm_sq(&long, &long).1 - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_euclidean_distance() {
+        let a = vec![0.0, 0.0, 0.0];
+        let b = vec![3.0, 4.0, 0.0];
+        assert!((euclidean_distance(&a, &b) - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_top_k_keeps_best() {
+        let mut top = TopK::new(2);
+        for (row, score) in [0.1f32, 0.9, 0.5, 0.7].into_iter().enumerate() {
+            top.push(score, row);
+        }
+        assert_eq!(top.into_sorted(), vec![(0.9, 1), (0.7, 3)]);
+    }
+
+    #[test]
+    fn test_generations_commit_and_cleanup() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let files = Generations::new(temp.path(), "data.manifest", &["data.bin", "meta.jsonl"]);
+        assert_eq!(files.current().unwrap(), None);
+
+        let first = files.allocate().unwrap();
+        let second = files.allocate().unwrap();
+        assert_eq!((first, second), (1, 2));
+        for generation in [first, second] {
+            for file in ["data.bin", "meta.jsonl"] {
+                files
+                    .write(file, generation, |w| Ok(w.write_all(b"x")?))
+                    .unwrap();
+            }
+        }
+        files.commit(first).unwrap();
+        assert_eq!(files.current().unwrap(), Some(1));
+        // A newer generation may still be committed by another writer
+        assert!(files.path("data.bin", 1).exists());
+        assert!(files.path("data.bin", 2).exists());
+
+        files.commit(second).unwrap();
+        assert_eq!(files.current().unwrap(), Some(2));
+        assert!(!files.path("data.bin", 1).exists());
+        assert!(!files.path("meta.jsonl", 1).exists());
+        assert!(temp.path().join("data.manifest").exists());
+
+        // No temporaries are left behind
+        let names: Vec<_> = fs::read_dir(temp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert!(names.iter().all(|n| !n.ends_with(".tmp")), "{:?}", names);
+    }
+
+    #[test]
+    fn test_generations_never_reuse_a_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let files = Generations::new(temp.path(), "data.manifest", &["data.bin", "meta.jsonl"]);
+        let committed = files.allocate().unwrap();
+        files
+            .write("data.bin", committed, |w| Ok(w.write_all(b"live")?))
+            .unwrap();
+        files.commit(committed).unwrap();
+
+        // A stale writer and leftover files from an interrupted save
+        fs::write(files.path("meta.jsonl", 4), b"partial").unwrap();
+        let next = files.allocate().unwrap();
+        assert_eq!(next, 5);
+        assert_eq!(files.allocate().unwrap(), 6);
+        assert_eq!(
+            fs::read(files.path("data.bin", committed)).unwrap(),
+            b"live"
+        );
+    }
+
+    #[test]
+    fn test_filter_matching() {
+        let stored = StoredPayload {
+            id: 123,
+            payload: serde_json::json!({}),
+            base: "code".to_string(),
+            branch: Some("main".to_string()),
+            source_type: "file".to_string(),
+            path: Some("src/lib.rs".to_string()),
+            tags: vec!["rust".to_string()],
+            revision_id: None,
+        };
+
+        // Empty filter matches all
+        let filter = VectorSearchFilter::new();
+        assert!(matches_filter(&stored, &filter));
+
+        // Matching base
+        let filter = VectorSearchFilter::new().with_base("code");
+        assert!(matches_filter(&stored, &filter));
+
+        // Non-matching base
+        let filter = VectorSearchFilter::new().with_base("docs");
+        assert!(!matches_filter(&stored, &filter));
+
+        // Matching path prefix
+        let filter = VectorSearchFilter::new().with_path_prefix("src/");
+        assert!(matches_filter(&stored, &filter));
+    }
+}
@@ -0,0 +1,1251 @@
+//! Embedded HNSW vector index backend.
+//!
+//! A pure-Rust Hierarchical Navigable Small World graph (Malkov & Yashunin)
+//! giving sub-linear approximate search without the LanceDB/Arrow dependency
+//! tree. The whole graph is held in memory and persisted on every write.
+//!
+//! ## On-disk layout
+//!
+//! ```text
+//! <index>/
+//! ├── hnsw.manifest         # Live generation number
+//! ├── hnsw.<gen>.bin        # Header, vectors, tombstones and adjacency lists
+//! └── payloads.<gen>.jsonl  # One metadata record per node, in node order
+//! ```
+//!
+//! As with the simple backend, a save writes both files under a new, never
+//! reused generation and switches to them with a single manifest rename, so a crash
+//! never leaves a graph paired with the wrong payloads.
+//!
+//! `hnsw.bin` is little-endian: a 48-byte header (magic `GIKHNSW\0`, version,
+//! metric, dimension, `m`, `ef_construction`, top layer, node count, entry
+//! node), followed by `count × dimension` f32 values, one tombstone byte per
+//! node, and for each node its layer count and per-layer neighbor lists.
+//!
+//! ## Deletions
+//!
+//! Deleting or replacing a vector turns its node into a tombstone: it keeps
+//! routing searches but is never returned. Once tombstones outnumber live
+//! nodes, the graph is rebuilt from the live nodes on the next write.
+//!
+//! ## Filtering
+//!
+//! [`VectorSearchFilter`] is applied to the graph candidates. When that yields
+//! fewer than `limit` matches, the search is retried with `ef` doubled each
+//! time. Once the beam would cover half the graph (e.g., a very selective
+//! filter), the query falls back to an exact scan over matching nodes, so
+//! filtered queries never silently drop results.
+
+use super::super::config::{HnswConfig, VectorIndexConfig};
+use super::super::metadata::VectorSearchFilter;
+use super::super::traits::{
+    VectorId, VectorIndexBackend, VectorInsert, VectorMetric, VectorSearchResult,
+};
+use super::common::{matches_filter, Generations, Scorer, StoredPayload, TopK};
+use crate::error::{DbError, DbResult};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tracing::{debug, trace};
+
+/// Filename of the manifest naming the live generation.
+const MANIFEST_FILENAME: &str = "hnsw.manifest";
+
+/// Filename for the serialized graph (stored per generation).
+const GRAPH_FILENAME: &str = "hnsw.bin";
+
+/// Filename for the per-node payload metadata (stored per generation).
+const PAYLOADS_FILENAME: &str = "payloads.jsonl";
+
+/// Magic bytes identifying a graph file.
+const MAGIC: [u8; 8] = *b"GIKHNSW\0";
+
+/// Current binary format version.
+const FORMAT_VERSION: u32 = 1;
+
+/// Marker for "no entry node" (empty graph).
+const NO_ENTRY: u64 = u64::MAX;
+
+/// Upper bound on node levels; with `m >= 2` this is never reached in practice.
+const MAX_LEVEL: usize = 16;
+
+// ============================================================================
+// Graph
+// ============================================================================
+
+/// A scored node, ordered by score (higher is better).
+#[derive(Debug, Clone, Copy)]
+struct Scored {
+    score: f32,
+    node: u32,
+}
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+/// In-memory HNSW graph.
+struct Graph {
+    dimension: usize,
+    metric: VectorMetric,
+    m: usize,
+    ef_construction: usize,
+
+    /// Row-major vectors, one row per node.
+    vectors: Vec<f32>,
+    /// `links[node][layer]` lists the node's neighbors on that layer.
+    links: Vec<Vec<Vec<u32>>>,
+    /// Tombstones for deleted or replaced nodes.
+    deleted: Vec<bool>,
+    /// Payload metadata, one per node.
+    payloads: Vec<StoredPayload>,
+    /// Live node for each vector ID.
+    nodes_by_id: HashMap<u64, u32>,
+
+    entry: Option<u32>,
+    max_level: usize,
+
+    /// Whether the graph has changes not yet written to disk.
+    dirty: bool,
+    /// Whether writes wait for `flush` instead of persisting immediately.
+    deferred: bool,
+}
+
+impl Graph {
+    fn new(dimension: usize, metric: VectorMetric, m: usize, ef_construction: usize) -> Self {
+        let m = m.max(2);
+        Self {
+            dimension,
+            metric,
+            m,
+            ef_construction: ef_construction.max(m),
+            vectors: Vec::new(),
+            links: Vec::new(),
+            deleted: Vec::new(),
+            payloads: Vec::new(),
+            nodes_by_id: HashMap::new(),
+            entry: None,
+            max_level: 0,
+            dirty: false,
+            deferred: false,
+        }
+    }
+
+    /// Total nodes, including tombstones.
+    fn node_count(&self) -> usize {
+        self.payloads.len()
+    }
+
+    /// Nodes that can be returned by searches.
+    fn live_count(&self) -> usize {
+        self.nodes_by_id.len()
+    }
+
+    fn vector(&self, node: u32) -> &[f32] {
+        let start = node as usize * self.dimension;
+        &self.vectors[start..start + self.dimension]
+    }
+
+    /// Maximum neighbors kept on a layer (layer 0 is twice as dense).
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.m * 2
+        } else {
+            self.m
+        }
+    }
+
+    /// Draw a level from the exponential distribution `floor(-ln(U) / ln(m))`.
+    ///
+    /// Seeded from the vector ID and node position so builds are reproducible.
+    fn random_level(&self, id: u64) -> usize {
+        let seed = splitmix64(id ^ (self.node_count() as u64).rotate_left(32));
+        let unit = ((seed >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level = (-unit.ln() / (self.m as f64).ln()).floor() as usize;
+        level.min(MAX_LEVEL)
+    }
+
+    /// Greedy best-first search on one layer, returning up to `ef` nodes
+    /// ordered best first.
+    fn search_layer(
+        &self,
+        scorer: &Scorer,
+        entry_points: &[Scored],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = HashSet::with_capacity(ef * self.m * 2);
+        let mut candidates: BinaryHeap<Scored> = BinaryHeap::new();
+        let mut results: BinaryHeap<Reverse<Scored>> = BinaryHeap::new();
+
+        for &ep in entry_points {
+            if visited.insert(ep.node) {
+                candidates.push(ep);
+                results.push(Reverse(ep));
+                if results.len() > ef {
+                    results.pop();
+                }
+            }
+        }
+
+        while let Some(current) = candidates.pop() {
+            if let Some(Reverse(worst)) = results.peek() {
+                if results.len() >= ef && current.score < worst.score {
+                    break;
+                }
+            }
+
+            for &neighbor in &self.links[current.node as usize][layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let scored = Scored {
+                    score: scorer.score(self.vector(neighbor)),
+                    node: neighbor,
+                };
+                let admit = results.len() < ef
+                    || results
+                        .peek()
+                        .map(|Reverse(worst)| scored.score > worst.score)
+                        .unwrap_or(true);
+                if admit {
+                    candidates.push(scored);
+                    results.push(Reverse(scored));
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        // Ascending order of `Reverse` is best-first
+        results
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(s)| s)
+            .collect()
+    }
+
+    /// Pick up to `m` diverse neighbors from best-first `candidates`.
+    ///
+    /// A candidate is preferred only if it is closer to the base node than to
+    /// every neighbor already selected (the paper's heuristic), which keeps
+    /// long-range links alive in clustered data. Remaining slots are filled
+    /// with the pruned candidates.
+    fn select_neighbors(&self, candidates: &[Scored], m: usize) -> Vec<u32> {
+        let mut selected: Vec<u32> = Vec::with_capacity(m);
+        let mut pruned: Vec<u32> = Vec::new();
+
+        for candidate in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            let scorer = Scorer::new(self.metric, self.vector(candidate.node));
+            let diverse = selected
+                .iter()
+                .all(|&s| scorer.score(self.vector(s)) < candidate.score);
+            if diverse {
+                selected.push(candidate.node);
+            } else {
+                pruned.push(candidate.node);
+            }
+        }
+
+        for node in pruned {
+            if selected.len() >= m {
+                break;
+            }
+            selected.push(node);
+        }
+        selected
+    }
+
+    /// Link `node` to `neighbors` on `layer`, pruning neighbors that overflow.
+    fn connect(&mut self, node: u32, neighbors: Vec<u32>, layer: usize) {
+        let cap = self.max_links(layer);
+
+        for &neighbor in &neighbors {
+            self.links[neighbor as usize][layer].push(node);
+            if self.links[neighbor as usize][layer].len() <= cap {
+                continue;
+            }
+
+            let scorer = Scorer::new(self.metric, self.vector(neighbor));
+            let mut scored: Vec<Scored> = self.links[neighbor as usize][layer]
+                .iter()
+                .map(|&n| Scored {
+                    score: scorer.score(self.vector(n)),
+                    node: n,
+                })
+                .collect();
+            scored.sort_unstable_by(|a, b| b.cmp(a));
+            let kept = self.select_neighbors(&scored, cap);
+            self.links[neighbor as usize][layer] = kept;
+        }
+
+        self.links[node as usize][layer] = neighbors;
+    }
+
+    /// Insert a new node. Callers must tombstone any previous node for the ID.
+    fn insert(&mut self, vector: &[f32], payload: StoredPayload) {
+        let node = self.node_count() as u32;
+        let level = self.random_level(payload.id);
+
+        self.vectors.extend_from_slice(vector);
+        self.links.push(vec![Vec::new(); level + 1]);
+        self.deleted.push(false);
+        self.nodes_by_id.insert(payload.id, node);
+        self.payloads.push(payload);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(node);
+            self.max_level = level;
+            return;
+        };
+
+        let scorer = Scorer::new(self.metric, vector);
+        let mut entry_points = vec![Scored {
+            score: scorer.score(self.vector(entry)),
+            node: entry,
+        }];
+
+        // Descend greedily through layers above the new node's level
+        for layer in (level + 1..=self.max_level).rev() {
+            entry_points = self.search_layer(&scorer, &entry_points, 1, layer);
+        }
+
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let candidates = self.search_layer(&scorer, &entry_points, self.ef_construction, layer);
+            let neighbors = self.select_neighbors(&candidates, self.m);
+            self.connect(node, neighbors, layer);
+            entry_points = candidates;
+        }
+
+        if level > self.max_level {
+            self.entry = Some(node);
+            self.max_level = level;
+        }
+    }
+
+    /// Tombstone the live node for `id`. Returns `false` if there was none.
+    fn remove(&mut self, id: u64) -> bool {
+        match self.nodes_by_id.remove(&id) {
+            Some(node) => {
+                self.deleted[node as usize] = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether tombstones outnumber live nodes.
+    fn needs_compaction(&self) -> bool {
+        self.node_count() - self.live_count() > self.live_count()
+    }
+
+    /// Rebuild the graph from live nodes only.
+    fn compact(&mut self) {
+        debug!(
+            "Compacting HNSW graph: {} live of {} nodes",
+            self.live_count(),
+            self.node_count()
+        );
+        let mut rebuilt = Graph::new(self.dimension, self.metric, self.m, self.ef_construction);
+        for node in 0..self.node_count() as u32 {
+            if !self.deleted[node as usize] {
+                rebuilt.insert(self.vector(node), self.payloads[node as usize].clone());
+            }
+        }
+        *self = rebuilt;
+    }
+
+    /// Top `limit` live nodes matching `filter`, best first.
+    fn search(
+        &self,
+        query: &[f32],
+        limit: usize,
+        ef_search: usize,
+        filter: Option<&VectorSearchFilter>,
+    ) -> Vec<Scored> {
+        if self.entry.is_none() || limit == 0 {
+            return Vec::new();
+        }
+
+        let scorer = Scorer::new(self.metric, query);
+        let accept = |node: u32| {
+            !self.deleted[node as usize]
+                && filter
+                    .map(|f| matches_filter(&self.payloads[node as usize], f))
+                    .unwrap_or(true)
+        };
+
+        if let Some(results) = self.search_graph(&scorer, limit, ef_search, &accept) {
+            return results;
+        }
+
+        // Too few graph candidates survived the filter/tombstones: scan exactly
+        trace!("HNSW candidates exhausted, falling back to exact scan");
+        let mut top = TopK::new(limit);
+        for node in 0..self.node_count() as u32 {
+            if accept(node) {
+                top.push(scorer.score(self.vector(node)), node as usize);
+            }
+        }
+        top.into_sorted()
+            .into_iter()
+            .map(|(score, node)| Scored {
+                score,
+                node: node as u32,
+            })
+            .collect()
+    }
+
+    /// Graph search that doubles `ef` until `limit` accepted nodes are found.
+    ///
+    /// Returns `None` once the beam would cover half the graph, or the graph
+    /// has no more reachable nodes to offer; an exact scan is cheaper then.
+    fn search_graph(
+        &self,
+        scorer: &Scorer,
+        limit: usize,
+        ef_search: usize,
+        accept: &dyn Fn(u32) -> bool,
+    ) -> Option<Vec<Scored>> {
+        let entry = self.entry?;
+        let mut entry_points = vec![Scored {
+            score: scorer.score(self.vector(entry)),
+            node: entry,
+        }];
+        for layer in (1..=self.max_level).rev() {
+            entry_points = self.search_layer(scorer, &entry_points, 1, layer);
+        }
+
+        let mut ef = ef_search.max(limit);
+        loop {
+            let candidates = self.search_layer(scorer, &entry_points, ef, 0);
+            let exhausted = candidates.len() < ef;
+            let results: Vec<Scored> = candidates
+                .into_iter()
+                .filter(|s| accept(s.node))
+                .take(limit)
+                .collect();
+
+            if results.len() >= limit || results.len() >= self.live_count() {
+                return Some(results);
+            }
+            if exhausted || ef * 2 > self.node_count() / 2 {
+                return None;
+            }
+            ef *= 2;
+            trace!("Widening HNSW search, ef={}", ef);
+        }
+    }
+}
+
+/// SplitMix64 finalizer, used as a small deterministic hash for level draws.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+fn metric_code(metric: VectorMetric) -> u32 {
+    match metric {
+        VectorMetric::Cosine => 0,
+        VectorMetric::Dot => 1,
+        VectorMetric::L2 => 2,
+    }
+}
+
+/// Bounds-checked little-endian reader over the graph file.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    path: &'a Path,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> DbResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| DbError::vector_parse(self.path, "truncated HNSW graph file"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> DbResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> DbResult<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> DbResult<u64> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+}
+
+fn write_graph(writer: &mut impl Write, graph: &Graph) -> DbResult<()> {
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(&metric_code(graph.metric).to_le_bytes())?;
+    writer.write_all(&(graph.dimension as u32).to_le_bytes())?;
+    writer.write_all(&(graph.m as u32).to_le_bytes())?;
+    writer.write_all(&(graph.ef_construction as u32).to_le_bytes())?;
+    writer.write_all(&(graph.max_level as u32).to_le_bytes())?;
+    writer.write_all(&(graph.node_count() as u64).to_le_bytes())?;
+    let entry = graph.entry.map(u64::from).unwrap_or(NO_ENTRY);
+    writer.write_all(&entry.to_le_bytes())?;
+
+    for v in &graph.vectors {
+        writer.write_all(&v.to_le_bytes())?;
+    }
+    for &deleted in &graph.deleted {
+        writer.write_all(&[deleted as u8])?;
+    }
+    for layers in &graph.links {
+        writer.write_all(&[layers.len() as u8])?;
+        for neighbors in layers {
+            writer.write_all(&(neighbors.len() as u32).to_le_bytes())?;
+            for n in neighbors {
+                writer.write_all(&n.to_le_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_graph(path: &Path, payloads: Vec<StoredPayload>, metric: VectorMetric) -> DbResult<Graph> {
+    let bytes = fs::read(path)?;
+    let mut r = Reader {
+        bytes: &bytes,
+        pos: 0,
+        path,
+    };
+
+    if r.take(MAGIC.len())? != MAGIC {
+        return Err(DbError::vector_parse(path, "not a GIK HNSW graph file"));
+    }
+    let version = r.u32()?;
+    if version != FORMAT_VERSION {
+        return Err(DbError::vector_parse(
+            path,
+            format!("unsupported HNSW graph version {}", version),
+        ));
+    }
+    let stored_metric = r.u32()?;
+    if stored_metric != metric_code(metric) {
+        return Err(DbError::index_incompatible(
+            "",
+            format!(
+                "HNSW graph was built for a different metric than '{}'",
+                metric
+            ),
+        ));
+    }
+    let dimension = r.u32()? as usize;
+    let m = r.u32()? as usize;
+    let ef_construction = r.u32()? as usize;
+    let max_level = r.u32()? as usize;
+    let count = r.u64()? as usize;
+    let entry = r.u64()?;
+
+    if payloads.len() != count {
+        return Err(DbError::vector_parse(
+            path,
+            format!("{} payload(s) for {} node(s)", payloads.len(), count),
+        ));
+    }
+
+    let mut graph = Graph::new(dimension, metric, m, ef_construction);
+    graph.max_level = max_level;
+    graph.entry = (entry != NO_ENTRY).then_some(entry as u32);
+
+    let vectors_len = count
+        .checked_mul(dimension)
+        .and_then(|n| n.checked_mul(4))
+        .ok_or_else(|| DbError::vector_parse(path, "vector section size overflows"))?;
+    let raw = r.take(vectors_len)?;
+    graph.vectors = raw
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    graph.deleted = r.take(count)?.iter().map(|&b| b != 0).collect();
+
+    graph.links.reserve(count);
+    for _ in 0..count {
+        let layer_count = r.u8()? as usize;
+        let mut layers = Vec::with_capacity(layer_count);
+        for _ in 0..layer_count {
+            let len = (r.u32()? as usize)
+                .checked_mul(4)
+                .ok_or_else(|| DbError::vector_parse(path, "neighbor list size overflows"))?;
+            let neighbors = r
+                .take(len)?
+                .chunks_exact(4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect::<Vec<u32>>();
+            if neighbors.iter().any(|&n| n as usize >= count) {
+                return Err(DbError::vector_parse(path, "neighbor out of range"));
+            }
+            layers.push(neighbors);
+        }
+        graph.links.push(layers);
+    }
+
+    // Searches follow links layer by layer from the entry node, so every
+    // node reached on a layer must have that layer
+    if let Some(entry) = graph.entry {
+        if entry as usize >= count {
+            return Err(DbError::vector_parse(path, "entry node out of range"));
+        }
+        if graph.links[entry as usize].len() != max_level + 1 {
+            return Err(DbError::vector_parse(
+                path,
+                "entry node does not span the top layer",
+            ));
+        }
+    }
+    for layers in &graph.links {
+        if layers.len() > max_level + 1 {
+            return Err(DbError::vector_parse(path, "node above the top layer"));
+        }
+        for (layer, neighbors) in layers.iter().enumerate() {
+            if neighbors
+                .iter()
+                .any(|&n| graph.links[n as usize].len() <= layer)
+            {
+                return Err(DbError::vector_parse(
+                    path,
+                    "neighbor missing from its layer",
+                ));
+            }
+        }
+    }
+
+    graph.nodes_by_id = payloads
+        .iter()
+        .enumerate()
+        .filter(|(node, _)| !graph.deleted[*node])
+        .map(|(node, p)| (p.id, node as u32))
+        .collect();
+    graph.payloads = payloads;
+
+    Ok(graph)
+}
+
+// ============================================================================
+// HnswVectorIndex
+// ============================================================================
+
+/// Embedded HNSW vector index.
+pub struct HnswVectorIndex {
+    /// Path to the index directory.
+    path: PathBuf,
+
+    /// Dimension of vectors.
+    dimension: usize,
+
+    /// Distance metric.
+    metric: VectorMetric,
+
+    /// Candidate list size for queries.
+    ef_search: usize,
+
+    /// The graph, vectors and payloads.
+    graph: RwLock<Graph>,
+}
+
+impl HnswVectorIndex {
+    /// Open or create an HNSW index.
+    ///
+    /// For an existing graph, the stored `m` and `ef_construction` are kept;
+    /// only `ef_search` is taken from the config.
+    pub fn open(config: &VectorIndexConfig) -> DbResult<Self> {
+        debug!("Opening HnswVectorIndex at {:?}", config.path);
+
+        let HnswConfig {
+            m,
+            ef_construction,
+            ef_search,
+        } = config.hnsw;
+
+        let files = Self::files(&config.path);
+        let graph = if let Some(generation) = files.current()? {
+            let payloads = load_payloads(&files.path(PAYLOADS_FILENAME, generation))?;
+            let graph = read_graph(
+                &files.path(GRAPH_FILENAME, generation),
+                payloads,
+                config.metric,
+            )?;
+            if graph.dimension != config.dimension {
+                return Err(DbError::DimensionMismatch {
+                    expected: config.dimension,
+                    actual: graph.dimension,
+                });
+            }
+            if graph.m != m.max(2) || graph.ef_construction != ef_construction.max(graph.m) {
+                debug!(
+                    "Keeping stored HNSW parameters m={}, ef_construction={} (rebuild to change)",
+                    graph.m, graph.ef_construction
+                );
+            }
+            debug!(
+                "Loaded HNSW graph: {} live of {} nodes",
+                graph.live_count(),
+                graph.node_count()
+            );
+            graph
+        } else {
+            Graph::new(config.dimension, config.metric, m, ef_construction)
+        };
+
+        Ok(Self {
+            path: config.path.clone(),
+            dimension: config.dimension,
+            metric: config.metric,
+            ef_search: ef_search.max(1),
+            graph: RwLock::new(graph),
+        })
+    }
+
+    /// The generation-managed data files of the index at `dir`.
+    fn files(dir: &Path) -> Generations {
+        Generations::new(dir, MANIFEST_FILENAME, &[GRAPH_FILENAME, PAYLOADS_FILENAME])
+    }
+
+    /// Write both files under a newly allocated generation and make it live.
+    fn save(&self, graph: &mut Graph) -> DbResult<()> {
+        fs::create_dir_all(&self.path)?;
+
+        let files = Self::files(&self.path);
+        let generation = files.allocate()?;
+        debug!(
+            "Saving HNSW graph to {:?} (generation {})",
+            self.path, generation
+        );
+
+        files.write(GRAPH_FILENAME, generation, |writer| {
+            write_graph(writer, graph)
+        })?;
+        files.write(PAYLOADS_FILENAME, generation, |writer| {
+            for payload in &graph.payloads {
+                serde_json::to_writer(&mut *writer, payload)?;
+                writer.write_all(b"\n")?;
+            }
+            Ok(())
+        })?;
+        files.commit(generation)?;
+
+        graph.dirty = false;
+        Ok(())
+    }
+}
+
+/// Read `payloads.jsonl`, one record per node.
+fn load_payloads(path: &Path) -> DbResult<Vec<StoredPayload>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let reader = BufReader::new(File::open(path)?);
+    let mut payloads = Vec::new();
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let payload = serde_json::from_str::<StoredPayload>(&line)
+            .map_err(|e| DbError::vector_parse(path, format!("line {}: {}", line_num + 1, e)))?;
+        payloads.push(payload);
+    }
+    Ok(payloads)
+}
+
+impl VectorIndexBackend for HnswVectorIndex {
+    fn query(
+        &self,
+        embedding: &[f32],
+        limit: usize,
+        filter: Option<&VectorSearchFilter>,
+    ) -> DbResult<Vec<VectorSearchResult>> {
+        trace!("Querying HnswVectorIndex, limit={}", limit);
+
+        if embedding.len() != self.dimension {
+            return Err(DbError::DimensionMismatch {
+                expected: self.dimension,
+                actual: embedding.len(),
+            });
+        }
+
+        let graph = self
+            .graph
+            .read()
+            .map_err(|e| DbError::internal(format!("Failed to acquire read lock: {}", e)))?;
+
+        let results = graph
+            .search(embedding, limit, self.ef_search, filter)
+            .into_iter()
+            .map(|s| {
+                let stored = &graph.payloads[s.node as usize];
+                VectorSearchResult::new(VectorId::new(stored.id), s.score, stored.payload.clone())
+            })
+            .collect::<Vec<_>>();
+
+        trace!("Found {} results", results.len());
+        Ok(results)
+    }
+
+    fn upsert(&self, vectors: &[VectorInsert]) -> DbResult<()> {
+        debug!("Upserting {} vectors", vectors.len());
+
+        // Validate dimensions before touching the graph
+        if let Some(bad) = vectors.iter().find(|v| v.vector.len() != self.dimension) {
+            return Err(DbError::DimensionMismatch {
+                expected: self.dimension,
+                actual: bad.vector.len(),
+            });
+        }
+
+        let mut graph = self
+            .graph
+            .write()
+            .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?;
+
+        for insert in vectors {
+            // Replacing an ID tombstones the old node
+            graph.remove(insert.id.value());
+            graph.insert(&insert.vector, StoredPayload::from(insert));
+        }
+
+        if graph.needs_compaction() {
+            graph.compact();
+        }
+        graph.dirty = true;
+
+        // Persist immediately unless a bulk load is in progress
+        if graph.deferred {
+            return Ok(());
+        }
+        self.save(&mut graph)
+    }
+
+    fn delete(&self, ids: &[VectorId]) -> DbResult<()> {
+        debug!("Deleting {} vectors", ids.len());
+
+        let mut graph = self
+            .graph
+            .write()
+            .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?;
+
+        let mut removed = 0;
+        for id in ids {
+            if graph.remove(id.value()) {
+                removed += 1;
+            }
+        }
+        if removed == 0 {
+            return Ok(());
+        }
+
+        if graph.needs_compaction() {
+            graph.compact();
+        }
+        graph.dirty = true;
+
+        // Persist immediately unless a bulk load is in progress
+        if graph.deferred {
+            return Ok(());
+        }
+        self.save(&mut graph)
+    }
+
+    fn flush(&self) -> DbResult<()> {
+        let mut graph = self
+            .graph
+            .write()
+            .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?;
+
+        // Writes are persisted eagerly unless deferred; otherwise only
+        // rewrite after a failed save
+        if graph.dirty {
+            self.save(&mut graph)?;
+        }
+        Ok(())
+    }
+
+    fn set_deferred_persist(&self, deferred: bool) -> DbResult<()> {
+        self.graph
+            .write()
+            .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?
+            .deferred = deferred;
+        Ok(())
+    }
+
+    fn scan(
+        &self,
+        batch_size: usize,
+        visit: &mut dyn FnMut(Vec<VectorInsert>) -> DbResult<()>,
+    ) -> DbResult<()> {
+        let graph = self
+            .graph
+            .read()
+            .map_err(|e| DbError::internal(format!("Failed to acquire read lock: {}", e)))?;
+
+        // Tombstoned nodes are skipped; only live vectors are visited
+        let mut batch = Vec::with_capacity(batch_size.max(1));
+        for (node, stored) in graph.payloads.iter().enumerate() {
+            if graph.deleted[node] {
+                continue;
+            }
+            batch.push(stored.to_insert(graph.vector(node as u32)));
+            if batch.len() >= batch_size.max(1) {
+                visit(std::mem::take(&mut batch))?;
+            }
+        }
+        if !batch.is_empty() {
+            visit(batch)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> DbResult<usize> {
+        let graph = self
+            .graph
+            .read()
+            .map_err(|e| DbError::internal(format!("Failed to acquire read lock: {}", e)))?;
+        Ok(graph.live_count())
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn metric(&self) -> VectorMetric {
+        self.metric
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const DIM: usize = 16;
+
+    /// Deterministic pseudo-random vectors in [-1, 1].
+    fn random_vectors(count: usize, seed: u64) -> Vec<Vec<f32>> {
+        (0..count)
+            .map(|i| {
+                (0..DIM)
+                    .map(|j| {
+                        let r = splitmix64(seed ^ ((i * DIM + j) as u64));
+                        (r >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn inserts(vectors: &[Vec<f32>]) -> Vec<VectorInsert> {
+        vectors
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let base = if i % 10 == 0 { "docs" } else { "code" };
+                VectorInsert::new(i as u64, v.clone(), serde_json::json!({ "i": i }))
+                    .with_base(base)
+            })
+            .collect()
+    }
+
+    fn open_index(dir: &Path) -> HnswVectorIndex {
+        let config = VectorIndexConfig::new(DIM, dir)
+            .with_backend("hnsw")
+            .with_hnsw(HnswConfig {
+                m: 8,
+                ef_construction: 64,
+                ef_search: 32,
+            });
+        HnswVectorIndex::open(&config).unwrap()
+    }
+
+    fn exact_top_k(vectors: &[Vec<f32>], query: &[f32], k: usize) -> Vec<u64> {
+        let scorer = Scorer::new(VectorMetric::Cosine, query);
+        let mut top = TopK::new(k);
+        for (i, v) in vectors.iter().enumerate() {
+            top.push(scorer.score(v), i);
+        }
+        top.into_sorted()
+            .into_iter()
+            .map(|(_, i)| i as u64)
+            .collect()
+    }
+
+    fn ids(results: &[VectorSearchResult]) -> Vec<u64> {
+        results.iter().map(|r| r.id.value()).collect()
+    }
+
+    #[test]
+    fn test_recall_against_exact_search() {
+        let temp = TempDir::new().unwrap();
+        let vectors = random_vectors(400, 1);
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&vectors)).unwrap();
+
+        let mut hits = 0;
+        let queries = random_vectors(20, 99);
+        for query in &queries {
+            let expected = exact_top_k(&vectors, query, 10);
+            let found = ids(&index.query(query, 10, None).unwrap());
+            hits += found.iter().filter(|id| expected.contains(id)).count();
+        }
+
+        let recall = hits as f32 / (queries.len() * 10) as f32;
+        assert!(recall >= 0.9, "recall {} below 0.9", recall);
+    }
+
+    #[test]
+    fn test_persist_and_reopen() {
+        let temp = TempDir::new().unwrap();
+        let vectors = random_vectors(100, 2);
+        let query = &vectors[7];
+
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&vectors)).unwrap();
+        let before = ids(&index.query(query, 5, None).unwrap());
+        assert_eq!(before[0], 7);
+        drop(index);
+
+        let index = open_index(temp.path());
+        assert_eq!(index.len().unwrap(), 100);
+        assert_eq!(ids(&index.query(query, 5, None).unwrap()), before);
+    }
+
+    #[test]
+    fn test_delete_and_replace() {
+        let temp = TempDir::new().unwrap();
+        let vectors = random_vectors(50, 3);
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&vectors)).unwrap();
+
+        index.delete(&[VectorId::new(7)]).unwrap();
+        assert_eq!(index.len().unwrap(), 49);
+        assert!(!ids(&index.query(&vectors[7], 10, None).unwrap()).contains(&7));
+
+        // Re-inserting an ID replaces its vector and payload
+        let replacement = VectorInsert::new(3u64, vectors[7].clone(), serde_json::json!({"i": 33}));
+        index.upsert(&[replacement]).unwrap();
+        let results = index.query(&vectors[7], 1, None).unwrap();
+        assert_eq!(ids(&results), vec![3]);
+        assert_eq!(results[0].payload, serde_json::json!({"i": 33}));
+        assert_eq!(index.len().unwrap(), 49);
+    }
+
+    #[test]
+    fn test_scan_visits_live_nodes_only() {
+        let temp = TempDir::new().unwrap();
+        let vectors = random_vectors(25, 4);
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&vectors)).unwrap();
+        index.delete(&[VectorId::new(5)]).unwrap();
+
+        let mut batches = Vec::new();
+        index
+            .scan(10, &mut |batch| {
+                batches.push(batch);
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![10, 10, 4]
+        );
+        let scanned: Vec<VectorInsert> = batches.into_iter().flatten().collect();
+        assert!(scanned.iter().all(|v| v.id.value() != 5));
+        let first = scanned.iter().find(|v| v.id.value() == 10).unwrap();
+        assert_eq!(first.vector, vectors[10]);
+        assert_eq!(first.base, "docs");
+        assert_eq!(first.payload, serde_json::json!({"i": 10}));
+    }
+
+    #[test]
+    fn test_selective_filter_returns_all_matches() {
+        let temp = TempDir::new().unwrap();
+        let vectors = random_vectors(200, 4);
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&vectors)).unwrap();
+
+        // Every 10th vector is in "docs"
+        let filter = VectorSearchFilter::new().with_base("docs");
+        let results = index.query(&vectors[1], 50, Some(&filter)).unwrap();
+        assert_eq!(results.len(), 20);
+        assert!(results.iter().all(|r| r.id.value() % 10 == 0));
+    }
+
+    #[test]
+    fn test_filtered_search_widens_ef_before_scanning() {
+        let temp = TempDir::new().unwrap();
+        let vectors = random_vectors(400, 6);
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&vectors)).unwrap();
+
+        // Half the nodes match, so ef=40 yields ~20 hits and a wider beam is
+        // enough without an exact scan
+        let graph = index.graph.read().unwrap();
+        let scorer = Scorer::new(VectorMetric::Cosine, &vectors[3]);
+        let even = |node: u32| node % 2 == 0;
+        let results = graph.search_graph(&scorer, 40, 32, &even).unwrap();
+        assert_eq!(results.len(), 40);
+        assert!(results.iter().all(|s| s.node % 2 == 0));
+
+        // A filter matching almost nothing gives up and leaves it to the scan
+        let rare = |node: u32| node == 7;
+        assert!(graph.search_graph(&scorer, 10, 32, &rare).is_none());
+    }
+
+    #[test]
+    fn test_compaction_drops_tombstones() {
+        let temp = TempDir::new().unwrap();
+        let vectors = random_vectors(60, 5);
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&vectors)).unwrap();
+
+        let doomed: Vec<VectorId> = (0..40).map(VectorId::new).collect();
+        index.delete(&doomed).unwrap();
+
+        let graph = index.graph.read().unwrap();
+        assert_eq!(graph.live_count(), 20);
+        assert_eq!(graph.node_count(), 20);
+        drop(graph);
+
+        let results = index.query(&vectors[45], 1, None).unwrap();
+        assert_eq!(ids(&results), vec![45]);
+    }
+    #[test]
+    fn test_interrupted_save_keeps_previous_generation() {
+        let temp = TempDir::new().unwrap();
+        let vectors = random_vectors(30, 6);
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&vectors)).unwrap();
+        let files = HnswVectorIndex::files(temp.path());
+        assert_eq!(files.current().unwrap(), Some(1));
+        drop(index);
+
+        // A crash after writing only the next graph file, before the manifest
+        fs::write(files.path(GRAPH_FILENAME, 2), b"partial").unwrap();
+
+        let index = open_index(temp.path());
+        assert_eq!(index.len().unwrap(), 30);
+
+        // The next save skips past the partial generation and drops both
+        index.delete(&[VectorId::new(0)]).unwrap();
+        assert_eq!(files.current().unwrap(), Some(3));
+        assert!(!files.path(GRAPH_FILENAME, 1).exists());
+        assert!(!files.path(PAYLOADS_FILENAME, 1).exists());
+        assert!(!files.path(GRAPH_FILENAME, 2).exists());
+
+        drop(index);
+        let index = open_index(temp.path());
+        assert_eq!(index.len().unwrap(), 29);
+    }
+
+    #[test]
+    fn test_flush_skips_clean_graph() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&random_vectors(10, 7))).unwrap();
+        index.flush().unwrap();
+        // Upsert already persisted generation 1; flush must not write another
+        assert_eq!(
+            HnswVectorIndex::files(temp.path()).current().unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_rejects_corrupt_section_sizes() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&random_vectors(2, 8))).unwrap();
+        drop(index);
+
+        // Patch the dimension so the vector section runs past the file end
+        let files = HnswVectorIndex::files(temp.path());
+        let path = files.path(GRAPH_FILENAME, 1);
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(&path, bytes).unwrap();
+
+        let payloads = load_payloads(&files.path(PAYLOADS_FILENAME, 1)).unwrap();
+        assert!(read_graph(&path, payloads, VectorMetric::Cosine).is_err());
+    }
+
+    #[test]
+    fn test_rejects_corrupt_graph_links() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path());
+        index.upsert(&inserts(&random_vectors(20, 9))).unwrap();
+        drop(index);
+
+        let files = HnswVectorIndex::files(temp.path());
+        let path = files.path(GRAPH_FILENAME, 1);
+        let original = fs::read(&path).unwrap();
+        let payloads = load_payloads(&files.path(PAYLOADS_FILENAME, 1)).unwrap();
+
+        // Entry node past the node count, then a top layer the entry lacks
+        let patches: [(usize, &[u8]); 2] =
+            [(40, &100u64.to_le_bytes()), (28, &40u32.to_le_bytes())];
+        for (offset, value) in patches {
+            let mut bytes = original.clone();
+            bytes[offset..offset + value.len()].copy_from_slice(value);
+            fs::write(&path, bytes).unwrap();
+
+            let result = read_graph(&path, payloads.clone(), VectorMetric::Cosine);
+            assert!(matches!(result, Err(DbError::VectorParse { .. })));
+        }
+
+        // A truncated file fails instead of panicking on open
+        fs::write(&path, &original[..original.len() - 3]).unwrap();
+        let config = VectorIndexConfig::new(DIM, temp.path()).with_backend("hnsw");
+        assert!(HnswVectorIndex::open(&config).is_err());
+    }
+}
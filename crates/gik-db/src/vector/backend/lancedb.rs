@@ -11,7 +11,7 @@ use super::super::traits::{
 };
 use crate::error::{DbError, DbResult};
 use arrow_array::{
-    Array, ArrayRef, FixedSizeListArray, Float32Array, Int64Array, RecordBatch,
+    Array, ArrayRef, FixedSizeListArray, Float32Array, Int64Array, ListArray, RecordBatch,
     RecordBatchIterator, StringArray,
 };
 use arrow_buffer::OffsetBuffer;
@@ -31,7 +31,6 @@ use tracing::{debug, trace};
 /// LanceDB vector index backend.
 pub struct LanceDbVectorIndex {
     /// Path to the index directory.
-    #[allow(dead_code)]
     path: PathBuf,
 
     /// Vector dimension.
@@ -201,7 +200,7 @@ impl LanceDbVectorIndex {
         // Empty tags list
         let tags_values = StringArray::from(Vec::<&str>::new());
         let tags_offsets = OffsetBuffer::new(vec![0i32].into());
-        let tags_array = arrow_array::ListArray::new(
+        let tags_array = ListArray::new(
             Arc::new(Field::new("item", DataType::Utf8, true)),
             tags_offsets,
             Arc::new(tags_values),
@@ -332,7 +331,7 @@ impl LanceDbVectorIndex {
         let tags_values = StringArray::from(all_tags);
         let tags_offsets = OffsetBuffer::new(offsets.into());
 
-        let tags_array = arrow_array::ListArray::new(
+        let tags_array = ListArray::new(
             Arc::new(Field::new("item", DataType::Utf8, true)),
             tags_offsets,
             Arc::new(tags_values),
@@ -342,6 +341,71 @@ impl LanceDbVectorIndex {
         Ok(Arc::new(tags_array))
     }
 
+    /// Decode a stored batch back into inserts (inverse of `inserts_to_batch`).
+    fn batch_to_inserts(&self, batch: &RecordBatch) -> DbResult<Vec<VectorInsert>> {
+        fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> DbResult<&'a T> {
+            batch
+                .column_by_name(name)
+                .and_then(|c| c.as_any().downcast_ref::<T>())
+                .ok_or_else(|| DbError::internal(format!("Missing or invalid column '{}'", name)))
+        }
+        fn optional(array: &StringArray, row: usize) -> Option<String> {
+            (!array.is_null(row)).then(|| array.value(row).to_string())
+        }
+
+        let ids = column::<Int64Array>(batch, "id")?;
+        let vectors = column::<FixedSizeListArray>(batch, "vector")?;
+        let payloads = column::<StringArray>(batch, "payload")?;
+        let bases = column::<StringArray>(batch, "base")?;
+        let branches = column::<StringArray>(batch, "branch")?;
+        let source_types = column::<StringArray>(batch, "source_type")?;
+        let paths = column::<StringArray>(batch, "path")?;
+        let tags = column::<ListArray>(batch, "tags")?;
+        let revision_ids = column::<StringArray>(batch, "revision_id")?;
+
+        let mut inserts = Vec::with_capacity(batch.num_rows());
+        for row in 0..batch.num_rows() {
+            let vector_values = vectors.value(row);
+            let vector = vector_values
+                .as_any()
+                .downcast_ref::<Float32Array>()
+                .ok_or_else(|| DbError::internal("Vector column is not Float32"))?
+                .values()
+                .to_vec();
+
+            let id = ids.value(row);
+            let payload = match optional(payloads, row) {
+                Some(p) => serde_json::from_str(&p).map_err(|e| {
+                    DbError::vector_parse(
+                        &self.path,
+                        format!("Invalid payload JSON for vector id {}: {}", id, e),
+                    )
+                })?,
+                None => serde_json::Value::Null,
+            };
+
+            let row_tags = tags.value(row);
+            let row_tags = row_tags
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .map(|t| t.iter().flatten().map(str::to_string).collect())
+                .unwrap_or_default();
+
+            inserts.push(VectorInsert {
+                id: VectorId::from(id),
+                vector,
+                payload,
+                base: bases.value(row).to_string(),
+                branch: optional(branches, row),
+                source_type: source_types.value(row).to_string(),
+                path: optional(paths, row),
+                tags: row_tags,
+                revision_id: optional(revision_ids, row),
+            });
+        }
+        Ok(inserts)
+    }
+
     /// Convert distance metric to LanceDB string.
     #[allow(dead_code)]
     fn metric_to_lance(&self) -> &'static str {
@@ -520,6 +584,41 @@ impl VectorIndexBackend for LanceDbVectorIndex {
         Ok(())
     }
 
+    fn scan(
+        &self,
+        batch_size: usize,
+        visit: &mut dyn FnMut(Vec<VectorInsert>) -> DbResult<()>,
+    ) -> DbResult<()> {
+        let table = self.get_table()?;
+
+        let mut stream = self
+            .runtime
+            .block_on(async { table.query().execute().await })
+            .map_err(|e| DbError::LanceDb {
+                message: format!("Scan failed: {}", e),
+            })?;
+
+        // Poll one record batch at a time so `visit` runs outside the runtime
+        let mut pending: Vec<VectorInsert> = Vec::new();
+        while let Some(batch) =
+            self.runtime
+                .block_on(stream.try_next())
+                .map_err(|e| DbError::LanceDb {
+                    message: format!("Failed to read scan results: {}", e),
+                })?
+        {
+            pending.extend(self.batch_to_inserts(&batch)?);
+            while pending.len() >= batch_size.max(1) {
+                let rest = pending.split_off(batch_size.max(1));
+                visit(std::mem::replace(&mut pending, rest))?;
+            }
+        }
+        if !pending.is_empty() {
+            visit(pending)?;
+        }
+        Ok(())
+    }
+
     fn len(&self) -> DbResult<usize> {
         let table = self.get_table()?;
 
@@ -548,6 +647,24 @@ impl VectorIndexBackend for LanceDbVectorIndex {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_batch_to_inserts_rejects_invalid_payload() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let index = LanceDbVectorIndex::open(&VectorIndexConfig::new(2, temp.path())).unwrap();
+        let insert = VectorInsert::new(VectorId::new(7), vec![1.0, 0.0], serde_json::json!({}))
+            .with_base("code");
+        let batch = index.inserts_to_batch(&[insert]).unwrap();
+
+        let (i, _) = batch.schema().column_with_name("payload").unwrap();
+        let mut columns = batch.columns().to_vec();
+        columns[i] = Arc::new(StringArray::from(vec!["{not json"]));
+        let batch = RecordBatch::try_new(batch.schema(), columns).unwrap();
+
+        let err = index.batch_to_inserts(&batch).unwrap_err();
+        assert!(matches!(err, DbError::VectorParse { .. }));
+        assert!(err.to_string().contains("vector id 7"), "{}", err);
+    }
+
     #[test]
     fn test_filter_to_lance() {
         let filter = VectorSearchFilter::new()
@@ -5,17 +5,27 @@
 //! ## Available Backends
 //!
 //! - `lancedb` (default): Production-ready LanceDB with ANN search
+//! - `hnsw` (default): Embedded pure-Rust HNSW graph
 //! - `simple`: File-based backend for testing/small indexes
 
 #[cfg(feature = "lancedb")]
 mod lancedb;
 
+#[cfg(any(feature = "simple", feature = "hnsw"))]
+mod common;
+
+#[cfg(feature = "hnsw")]
+mod hnsw;
+
 #[cfg(feature = "simple")]
 mod simple;
 
 #[cfg(feature = "lancedb")]
 pub use self::lancedb::LanceDbVectorIndex;
 
+#[cfg(feature = "hnsw")]
+pub use hnsw::HnswVectorIndex;
+
 #[cfg(feature = "simple")]
 pub use simple::SimpleFileVectorIndex;
 
@@ -102,6 +112,12 @@ pub fn open_vector_index(config: &VectorIndexConfig) -> DbResult<Arc<dyn VectorI
             Ok(Arc::new(index))
         }
 
+        #[cfg(feature = "hnsw")]
+        "hnsw" => {
+            let index = HnswVectorIndex::open(config)?;
+            Ok(Arc::new(index))
+        }
+
         #[cfg(feature = "simple")]
         "simple" => {
             let index = SimpleFileVectorIndex::open(config)?;
@@ -145,12 +161,64 @@ pub fn available_backends() -> Vec<&'static str> {
     #[cfg(feature = "lancedb")]
     backends.push("lancedb");
 
+    #[cfg(feature = "hnsw")]
+    backends.push("hnsw");
+
     #[cfg(feature = "simple")]
     backends.push("simple");
 
     backends
 }
 
+/// Copy every vector from `source` into `target` without re-embedding.
+///
+/// Vectors are read from `source` in batches of `batch_size` with their
+/// payloads and metadata and upserted into `target` as each batch is read,
+/// so only one batch is held in memory. Persisting is deferred on `target`
+/// for the duration (see [`VectorIndexBackend::set_deferred_persist`]): the
+/// embedded backends would otherwise rewrite their files on every batch.
+/// `target` is flushed once at the end and its count checked against
+/// `source`. Returns the number of vectors copied.
+///
+/// # Errors
+///
+/// Returns an error if the dimensions differ, a backend operation fails, or
+/// the target does not hold the same number of vectors afterwards.
+pub fn copy_vectors(
+    source: &dyn VectorIndexBackend,
+    target: &dyn VectorIndexBackend,
+    batch_size: usize,
+) -> DbResult<usize> {
+    if source.dimension() != target.dimension() {
+        return Err(DbError::DimensionMismatch {
+            expected: target.dimension(),
+            actual: source.dimension(),
+        });
+    }
+
+    let mut copied = 0;
+    target.set_deferred_persist(true)?;
+    let scanned = source.scan(batch_size, &mut |batch| {
+        copied += batch.len();
+        target.upsert(&batch)
+    });
+    target.set_deferred_persist(false)?;
+    scanned?;
+    target.flush()?;
+
+    let expected = source.len()?;
+    let actual = target.len()?;
+    if copied != expected || actual != expected {
+        return Err(DbError::internal(format!(
+            "Vector count mismatch after copy: source has {}, copied {}, target has {}",
+            expected, copied, actual
+        )));
+    }
+
+    info!("Copied {} vectors", copied);
+    Ok(copied)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -158,11 +226,82 @@ pub fn available_backends() -> Vec<&'static str> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(feature = "simple", feature = "hnsw"))]
+    use crate::vector::VectorInsert;
 
     #[test]
     fn test_available_backends() {
         let backends = available_backends();
         // At least one backend should be available
-        assert!(!backends.is_empty() || cfg!(not(any(feature = "lancedb", feature = "simple"))));
+        assert!(
+            !backends.is_empty()
+                || cfg!(not(any(
+                    feature = "lancedb",
+                    feature = "hnsw",
+                    feature = "simple"
+                )))
+        );
+    }
+
+    #[cfg(all(feature = "simple", feature = "hnsw"))]
+    #[test]
+    fn test_copy_vectors_between_backends() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let source_config =
+            VectorIndexConfig::new(3, temp.path().join("simple")).with_backend("simple");
+        let target_config =
+            VectorIndexConfig::new(3, temp.path().join("hnsw")).with_backend("hnsw");
+        let source = open_vector_index(&source_config).unwrap();
+        let target = open_vector_index(&target_config).unwrap();
+
+        let inserts: Vec<VectorInsert> = (0..7u64)
+            .map(|i| {
+                VectorInsert::new(i, vec![1.0, i as f32, 0.5], serde_json::json!({ "i": i }))
+                    .with_base("code")
+                    .with_branch("main")
+                    .with_path(format!("src/{}.rs", i))
+                    .with_tags(vec!["rust".to_string()])
+            })
+            .collect();
+        source.upsert(&inserts).unwrap();
+
+        assert_eq!(copy_vectors(&*source, &*target, 3).unwrap(), 7);
+        assert_eq!(target.len().unwrap(), 7);
+        // The target is written once, not once per source batch
+        let manifest = std::fs::read_to_string(temp.path().join("hnsw/hnsw.manifest")).unwrap();
+        assert_eq!(manifest.trim(), "1");
+
+        let results = target.query(&[1.0, 6.0, 0.5], 1, None).unwrap();
+        assert_eq!(results[0].id.value(), 6);
+        assert_eq!(results[0].payload, serde_json::json!({ "i": 6 }));
+
+        let mut copied = Vec::new();
+        target
+            .scan(100, &mut |batch| {
+                copied.extend(batch);
+                Ok(())
+            })
+            .unwrap();
+        let six = copied.iter().find(|v| v.id.value() == 6).unwrap();
+        assert_eq!(six.path.as_deref(), Some("src/6.rs"));
+        assert_eq!(six.branch.as_deref(), Some("main"));
+        assert_eq!(six.tags, vec!["rust".to_string()]);
+    }
+
+    #[cfg(all(feature = "simple", feature = "hnsw"))]
+    #[test]
+    fn test_copy_vectors_rejects_dimension_mismatch() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let source = open_vector_index(
+            &VectorIndexConfig::new(3, temp.path().join("a")).with_backend("simple"),
+        )
+        .unwrap();
+        let target = open_vector_index(
+            &VectorIndexConfig::new(4, temp.path().join("b")).with_backend("hnsw"),
+        )
+        .unwrap();
+
+        let err = copy_vectors(&*source, &*target, 10).unwrap_err();
+        assert!(matches!(err, DbError::DimensionMismatch { .. }));
     }
 }
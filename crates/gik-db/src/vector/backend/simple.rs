@@ -1,59 +1,298 @@
 //! Simple file-based vector index backend.
 //!
-//! This backend stores vectors in a JSONL file and uses linear scan for search.
-//! It is intended for testing and small indexes where the overhead of a full
-//! vector database is not justified.
+//! This backend uses a linear scan for search. It is intended for testing and
+//! small indexes where the overhead of a full vector database is not justified.
+//!
+//! ## On-disk layout
+//!
+//! ```text
+//! <index>/
+//! ├── vectors.manifest       # Live generation number
+//! ├── vectors.<gen>.bin      # Header + contiguous row-major vectors (little-endian)
+//! └── payloads.<gen>.jsonl   # One metadata record per row, in row order
+//! ```
+//!
+//! The vector file starts with a fixed 32-byte header:
+//!
+//! | Offset | Size | Field                          |
+//! |--------|------|--------------------------------|
+//! | 0      | 8    | Magic `GIKVEC\0\0`             |
+//! | 8      | 4    | Format version (`1`)           |
+//! | 12     | 4    | Element type (`0`=f32, `1`=f16)|
+//! | 16     | 4    | Dimension                      |
+//! | 20     | 4    | Reserved                       |
+//! | 24     | 8    | Row count                      |
+//!
+//! The vector file is memory-mapped on open, so it is paged in lazily: f32 rows
+//! are scored in place without copying and f16 rows are decoded one row at a
+//! time. Writes rebuild both files under a new, never reused generation and
+//! switch to them with a single manifest rename, so an interrupted write leaves
+//! the previous pair in place and a mapped generation is never modified.
+//!
+//! Indexes written by earlier versions (`vectors.jsonl`, one JSON record with
+//! an inline float array per vector) are converted on first open; the original
+//! file is kept as `vectors.jsonl.legacy`.
 
-use super::super::config::VectorIndexConfig;
+use super::super::config::{VectorIndexConfig, VectorStorage};
 use super::super::metadata::VectorSearchFilter;
 use super::super::traits::{
     VectorId, VectorIndexBackend, VectorInsert, VectorMetric, VectorSearchResult,
 };
+use super::common::{matches_filter, Generations, Scorer, StoredPayload, TopK};
 use crate::error::{DbError, DbResult};
-use serde::{Deserialize, Serialize};
+use memmap2::Mmap;
+use serde::Deserialize;
 use std::collections::HashMap;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
-use tracing::{debug, trace};
+use tracing::{debug, info, trace};
+
+/// Filename of the manifest naming the live generation.
+const MANIFEST_FILENAME: &str = "vectors.manifest";
 
-/// Filename for the JSONL data file.
-const DATA_FILENAME: &str = "vectors.jsonl";
+/// Filename for the binary vector file (stored per generation).
+const VECTORS_FILENAME: &str = "vectors.bin";
 
-/// A stored vector entry.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct StoredVector {
-    id: u64,
+/// Filename for the per-row payload metadata (stored per generation).
+const PAYLOADS_FILENAME: &str = "payloads.jsonl";
+
+/// Filename of the legacy JSONL data file.
+const LEGACY_DATA_FILENAME: &str = "vectors.jsonl";
+
+/// Filename the legacy data file is renamed to after conversion.
+const LEGACY_BACKUP_FILENAME: &str = "vectors.jsonl.legacy";
+
+/// Magic bytes identifying a vector file.
+const MAGIC: [u8; 8] = *b"GIKVEC\0\0";
+
+/// Current binary format version.
+const FORMAT_VERSION: u32 = 1;
+
+/// Size of the fixed header in bytes.
+const HEADER_LEN: usize = 32;
+
+/// A record from the legacy `vectors.jsonl` format.
+#[derive(Debug, Deserialize)]
+struct LegacyStoredVector {
     vector: Vec<f32>,
-    payload: serde_json::Value,
-    base: String,
-    branch: Option<String>,
-    source_type: String,
-    path: Option<String>,
-    tags: Vec<String>,
-    revision_id: Option<String>,
+    #[serde(flatten)]
+    meta: StoredPayload,
+}
+
+// ============================================================================
+// Binary Header
+// ============================================================================
+
+/// Decoded `vectors.bin` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Header {
+    storage: VectorStorage,
+    dimension: usize,
+    count: usize,
+}
+
+impl Header {
+    fn encode(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0u8; HEADER_LEN];
+        bytes[0..8].copy_from_slice(&MAGIC);
+        bytes[8..12].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+        let dtype: u32 = match self.storage {
+            VectorStorage::F32 => 0,
+            VectorStorage::F16 => 1,
+        };
+        bytes[12..16].copy_from_slice(&dtype.to_le_bytes());
+        bytes[16..20].copy_from_slice(&(self.dimension as u32).to_le_bytes());
+        bytes[24..32].copy_from_slice(&(self.count as u64).to_le_bytes());
+        bytes
+    }
+
+    fn decode(bytes: &[u8], path: &Path) -> DbResult<Self> {
+        if bytes.len() < HEADER_LEN || bytes[0..8] != MAGIC {
+            return Err(DbError::vector_parse(path, "not a GIK vector file"));
+        }
+
+        let read_u32 = |at: usize| {
+            u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+        };
+
+        let version = read_u32(8);
+        if version != FORMAT_VERSION {
+            return Err(DbError::vector_parse(
+                path,
+                format!("unsupported vector file version {}", version),
+            ));
+        }
+
+        let storage = match read_u32(12) {
+            0 => VectorStorage::F32,
+            1 => VectorStorage::F16,
+            other => {
+                return Err(DbError::vector_parse(
+                    path,
+                    format!("unknown element type {}", other),
+                ))
+            }
+        };
+
+        let mut count = [0u8; 8];
+        count.copy_from_slice(&bytes[24..32]);
+
+        Ok(Self {
+            storage,
+            dimension: read_u32(16) as usize,
+            count: u64::from_le_bytes(count) as usize,
+        })
+    }
+
+    /// Size in bytes of the vector data following the header, if it fits in memory.
+    fn body_len(&self) -> Option<usize> {
+        self.count
+            .checked_mul(self.dimension)?
+            .checked_mul(self.storage.element_size())
+    }
+}
+
+// ============================================================================
+// In-memory State
+// ============================================================================
+
+/// Backing storage for vector rows.
+enum VectorData {
+    /// Rows read directly from the memory-mapped `vectors.bin`.
+    Mapped { mmap: Mmap, storage: VectorStorage },
+    /// Row-major f32 rows owned in memory (after a write).
+    Owned(Vec<f32>),
+}
+
+impl VectorData {
+    /// Switch to owned storage, copying mapped rows, and return the rows.
+    fn make_owned(&mut self, dimension: usize, count: usize) -> &mut Vec<f32> {
+        if let VectorData::Mapped { .. } = self {
+            let mut values = Vec::with_capacity(count * dimension);
+            self.scan(dimension, count, |_, row| {
+                values.extend_from_slice(row);
+                Ok(())
+            })
+            .expect("copying rows cannot fail");
+            *self = VectorData::Owned(values);
+        }
+        match self {
+            VectorData::Owned(values) => values,
+            VectorData::Mapped { .. } => unreachable!("mapped data was just converted"),
+        }
+    }
+
+    /// Visit each row as an f32 slice, in row order, stopping at the first error.
+    ///
+    /// Mapped f32 rows are borrowed in place; f16 rows (or f32 rows that
+    /// cannot be reinterpreted on this target) are decoded into a scratch row.
+    fn scan(
+        &self,
+        dimension: usize,
+        count: usize,
+        mut visit: impl FnMut(usize, &[f32]) -> DbResult<()>,
+    ) -> DbResult<()> {
+        if dimension == 0 || count == 0 {
+            return Ok(());
+        }
+
+        match self {
+            VectorData::Owned(values) => {
+                for (row, vector) in values.chunks_exact(dimension).enumerate() {
+                    visit(row, vector)?;
+                }
+            }
+            VectorData::Mapped { mmap, storage } => {
+                let row_len = dimension * storage.element_size();
+                let body = &mmap[HEADER_LEN..HEADER_LEN + count * row_len];
+
+                if *storage == VectorStorage::F32 {
+                    if let Some(values) = as_f32_slice(body) {
+                        for (row, vector) in values.chunks_exact(dimension).enumerate() {
+                            visit(row, vector)?;
+                        }
+                        return Ok(());
+                    }
+                }
+
+                let mut scratch = vec![0.0f32; dimension];
+                for (row, bytes) in body.chunks_exact(row_len).enumerate() {
+                    match storage {
+                        VectorStorage::F32 => decode_f32_into(bytes, &mut scratch),
+                        VectorStorage::F16 => decode_f16_into(bytes, &mut scratch),
+                    }
+                    visit(row, &scratch)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rows and their metadata, kept in the same order.
+struct IndexState {
+    data: VectorData,
+    payloads: Vec<StoredPayload>,
+    rows_by_id: HashMap<u64, usize>,
+    /// Whether the in-memory rows differ from the files on disk.
+    dirty: bool,
+    /// Whether writes wait for `flush` instead of persisting immediately.
+    deferred: bool,
 }
 
-impl From<&VectorInsert> for StoredVector {
-    fn from(insert: &VectorInsert) -> Self {
+impl IndexState {
+    fn empty() -> Self {
         Self {
-            id: insert.id.value(),
-            vector: insert.vector.clone(),
-            payload: insert.payload.clone(),
-            base: insert.base.clone(),
-            branch: insert.branch.clone(),
-            source_type: insert.source_type.clone(),
-            path: insert.path.clone(),
-            tags: insert.tags.clone(),
-            revision_id: insert.revision_id.clone(),
+            data: VectorData::Owned(Vec::new()),
+            payloads: Vec::new(),
+            rows_by_id: HashMap::new(),
+            dirty: false,
+            deferred: false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.payloads.len()
+    }
+
+    /// Visit each row as an f32 slice, in row order.
+    fn scan(
+        &self,
+        dimension: usize,
+        visit: impl FnMut(usize, &[f32]) -> DbResult<()>,
+    ) -> DbResult<()> {
+        self.data.scan(dimension, self.len(), visit)
+    }
+
+    /// Remove a row by swapping the last row into its place.
+    fn swap_remove(&mut self, row: usize, dimension: usize) {
+        let count = self.len();
+        let values = self.data.make_owned(dimension, count);
+        let last = count - 1;
+
+        if row != last {
+            values.copy_within(last * dimension..count * dimension, row * dimension);
         }
+        values.truncate(last * dimension);
+
+        let removed = self.payloads.swap_remove(row);
+        self.rows_by_id.remove(&removed.id);
+        if let Some(moved) = self.payloads.get(row) {
+            self.rows_by_id.insert(moved.id, row);
+        }
+        self.dirty = true;
     }
 }
 
+// ============================================================================
+// SimpleFileVectorIndex
+// ============================================================================
+
 /// Simple file-based vector index.
 ///
-/// Uses JSONL storage and linear scan for search.
+/// Uses a memory-mapped binary vector file and linear scan for search.
 pub struct SimpleFileVectorIndex {
     /// Path to the index directory.
     path: PathBuf,
@@ -64,12 +303,18 @@ pub struct SimpleFileVectorIndex {
     /// Distance metric.
     metric: VectorMetric,
 
-    /// In-memory vector store.
-    vectors: RwLock<HashMap<u64, StoredVector>>,
+    /// Element type used when writing `vectors.bin`.
+    storage: VectorStorage,
+
+    /// Vector rows and payloads.
+    state: RwLock<IndexState>,
 }
 
 impl SimpleFileVectorIndex {
     /// Open or create a simple file vector index.
+    ///
+    /// Existing files are read in whatever element type they were written
+    /// with; the next write uses the configured [`VectorStorage`].
     pub fn open(config: &VectorIndexConfig) -> DbResult<Self> {
         debug!("Opening SimpleFileVectorIndex at {:?}", config.path);
 
@@ -77,29 +322,110 @@ impl SimpleFileVectorIndex {
             path: config.path.clone(),
             dimension: config.dimension,
             metric: config.metric,
-            vectors: RwLock::new(HashMap::new()),
+            storage: config.storage,
+            state: RwLock::new(IndexState::empty()),
         };
 
-        // Load existing data if present
-        let data_path = config.path.join(DATA_FILENAME);
-        if data_path.exists() {
-            index.load_from_file(&data_path)?;
-        }
+        let state = if let Some(generation) = index.files().current()? {
+            index.load_state(generation)?
+        } else if config.path.join(LEGACY_DATA_FILENAME).exists() {
+            index.convert_legacy()?
+        } else {
+            IndexState::empty()
+        };
+
+        *index
+            .state
+            .write()
+            .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))? =
+            state;
 
         Ok(index)
     }
 
-    /// Load vectors from a JSONL file.
-    fn load_from_file(&self, path: &PathBuf) -> DbResult<()> {
-        debug!("Loading vectors from {:?}", path);
+    /// The generation-managed data files of this index.
+    fn files(&self) -> Generations {
+        Generations::new(
+            &self.path,
+            MANIFEST_FILENAME,
+            &[VECTORS_FILENAME, PAYLOADS_FILENAME],
+        )
+    }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+    /// Map the vector file and load the matching payloads of `generation`.
+    fn load_state(&self, generation: u64) -> DbResult<IndexState> {
+        let vectors_path = self.files().path(VECTORS_FILENAME, generation);
+        debug!("Loading vectors from {:?}", vectors_path);
+
+        let file = File::open(&vectors_path)?;
+        // SAFETY: generation files are never opened for writing once in place;
+        // every save, from any process, renames complete files into a freshly
+        // reserved generation and only unlinks older ones, so the mapped
+        // contents stay unchanged for the lifetime of the mapping.
+        let mmap = unsafe { Mmap::map(&file) }
+            .map_err(|e| DbError::vector_io(&vectors_path, e.to_string()))?;
+
+        let header = Header::decode(&mmap, &vectors_path)?;
+        if header.dimension != self.dimension {
+            return Err(DbError::DimensionMismatch {
+                expected: self.dimension,
+                actual: header.dimension,
+            });
+        }
+        if header
+            .body_len()
+            .is_none_or(|len| mmap.len() - HEADER_LEN < len)
+        {
+            return Err(DbError::vector_parse(
+                &vectors_path,
+                format!("truncated vector file ({} rows expected)", header.count),
+            ));
+        }
 
-        let mut vectors = self
-            .vectors
-            .write()
-            .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?;
+        let payloads_path = self.files().path(PAYLOADS_FILENAME, generation);
+        let payloads = load_payloads(&payloads_path)?;
+        if payloads.len() != header.count {
+            return Err(DbError::vector_parse(
+                payloads_path,
+                format!(
+                    "{} payload(s) for {} vector(s)",
+                    payloads.len(),
+                    header.count
+                ),
+            ));
+        }
+
+        let rows_by_id = payloads
+            .iter()
+            .enumerate()
+            .map(|(row, p)| (p.id, row))
+            .collect();
+
+        debug!(
+            "Loaded {} vectors ({}, dim={})",
+            header.count, header.storage, header.dimension
+        );
+
+        Ok(IndexState {
+            data: VectorData::Mapped {
+                mmap,
+                storage: header.storage,
+            },
+            payloads,
+            rows_by_id,
+            dirty: false,
+            deferred: false,
+        })
+    }
+
+    /// Convert a legacy `vectors.jsonl` index to the binary layout.
+    fn convert_legacy(&self) -> DbResult<IndexState> {
+        let legacy_path = self.path.join(LEGACY_DATA_FILENAME);
+        info!("Converting legacy vector index at {:?}", legacy_path);
+
+        let reader = BufReader::new(File::open(&legacy_path)?);
+        let mut state = IndexState::empty();
+        let values = state.data.make_owned(self.dimension, 0);
 
         for (line_num, line) in reader.lines().enumerate() {
             let line = line?;
@@ -107,97 +433,82 @@ impl SimpleFileVectorIndex {
                 continue;
             }
 
-            match serde_json::from_str::<StoredVector>(&line) {
-                Ok(stored) => {
-                    vectors.insert(stored.id, stored);
-                }
+            let record = match serde_json::from_str::<LegacyStoredVector>(&line) {
+                Ok(record) => record,
                 Err(e) => {
                     debug!("Skipping invalid line {}: {}", line_num + 1, e);
+                    continue;
                 }
+            };
+            if record.vector.len() != self.dimension {
+                debug!(
+                    "Skipping line {}: dimension {} (expected {})",
+                    line_num + 1,
+                    record.vector.len(),
+                    self.dimension
+                );
+                continue;
             }
-        }
-
-        debug!("Loaded {} vectors", vectors.len());
-        Ok(())
-    }
-
-    /// Save all vectors to the JSONL file.
-    fn save_to_file(&self) -> DbResult<()> {
-        let data_path = self.path.join(DATA_FILENAME);
-        debug!("Saving vectors to {:?}", data_path);
-
-        let vectors = self
-            .vectors
-            .read()
-            .map_err(|e| DbError::internal(format!("Failed to acquire read lock: {}", e)))?;
-
-        let mut file = File::create(&data_path)?;
-        for stored in vectors.values() {
-            let line = serde_json::to_string(stored)?;
-            writeln!(file, "{}", line)?;
-        }
-
-        debug!("Saved {} vectors", vectors.len());
-        Ok(())
-    }
-
-    /// Check if a vector matches the filter.
-    fn matches_filter(stored: &StoredVector, filter: &VectorSearchFilter) -> bool {
-        // Check base
-        if let Some(ref base) = filter.base {
-            if &stored.base != base {
-                return false;
-            }
-        }
 
-        // Check branch
-        if let Some(ref branch) = filter.branch {
-            match &stored.branch {
-                Some(b) if b == branch => {}
-                _ => return false,
+            // Later records win, matching the old load-into-map behavior
+            match state.rows_by_id.get(&record.meta.id) {
+                Some(&row) => {
+                    values[row * self.dimension..(row + 1) * self.dimension]
+                        .copy_from_slice(&record.vector);
+                    state.payloads[row] = record.meta;
+                }
+                None => {
+                    state
+                        .rows_by_id
+                        .insert(record.meta.id, state.payloads.len());
+                    values.extend_from_slice(&record.vector);
+                    state.payloads.push(record.meta);
+                }
             }
         }
 
-        // Check source_type
-        if let Some(ref source_type) = filter.source_type {
-            if &stored.source_type != source_type {
-                return false;
-            }
-        }
+        self.save(&mut state)?;
+        fs::rename(&legacy_path, self.path.join(LEGACY_BACKUP_FILENAME))?;
 
-        // Check path prefix
-        if let Some(ref prefix) = filter.path_prefix {
-            match &stored.path {
-                Some(p) if p.starts_with(prefix) => {}
-                _ => return false,
-            }
-        }
+        info!("Converted {} vectors to {}", state.len(), VECTORS_FILENAME);
+        Ok(state)
+    }
 
-        // Check tags (must have ALL)
-        for tag in &filter.tags {
-            if !stored.tags.contains(tag) {
-                return false;
-            }
-        }
+    /// Write both files under a newly allocated generation and make it live.
+    fn save(&self, state: &mut IndexState) -> DbResult<()> {
+        fs::create_dir_all(&self.path)?;
+
+        let files = self.files();
+        let generation = files.allocate()?;
+        debug!(
+            "Saving vectors to {:?} (generation {})",
+            self.path, generation
+        );
+
+        let header = Header {
+            storage: self.storage,
+            dimension: self.dimension,
+            count: state.len(),
+        };
 
-        // Check revision_id
-        if let Some(ref revision_id) = filter.revision_id {
-            match &stored.revision_id {
-                Some(r) if r == revision_id => {}
-                _ => return false,
+        files.write(VECTORS_FILENAME, generation, |writer| {
+            writer.write_all(&header.encode())?;
+            state.scan(self.dimension, |_, row| {
+                write_row(writer, row, self.storage)
+            })
+        })?;
+        files.write(PAYLOADS_FILENAME, generation, |writer| {
+            for payload in &state.payloads {
+                serde_json::to_writer(&mut *writer, payload)?;
+                writer.write_all(b"\n")?;
             }
-        }
+            Ok(())
+        })?;
+        files.commit(generation)?;
 
-        true
-    }
-
-    /// Compute similarity between two vectors.
-    fn compute_similarity(&self, a: &[f32], b: &[f32]) -> f32 {
-        match self.metric {
-            VectorMetric::Cosine => cosine_similarity(a, b),
-            VectorMetric::Dot => dot_product(a, b),
-            VectorMetric::L2 => -euclidean_distance(a, b), // Negate so higher is better
-        }
+        state.dirty = false;
+        debug!("Saved {} vectors", state.len());
+        Ok(())
     }
 }
 
@@ -210,26 +521,35 @@ impl VectorIndexBackend for SimpleFileVectorIndex {
     ) -> DbResult<Vec<VectorSearchResult>> {
         trace!("Querying SimpleFileVectorIndex, limit={}", limit);
 
-        let vectors = self
-            .vectors
+        if embedding.len() != self.dimension {
+            return Err(DbError::DimensionMismatch {
+                expected: self.dimension,
+                actual: embedding.len(),
+            });
+        }
+
+        let state = self
+            .state
             .read()
             .map_err(|e| DbError::internal(format!("Failed to acquire read lock: {}", e)))?;
 
-        // Compute similarities
-        let mut scored: Vec<(f32, &StoredVector)> = vectors
-            .values()
-            .filter(|v| filter.map(|f| Self::matches_filter(v, f)).unwrap_or(true))
-            .map(|v| (self.compute_similarity(embedding, &v.vector), v))
-            .collect();
-
-        // Sort by score (descending)
-        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        let scorer = Scorer::new(self.metric, embedding);
+        let mut top = TopK::new(limit);
+        state.scan(self.dimension, |row, vector| {
+            let matches = filter
+                .map(|f| matches_filter(&state.payloads[row], f))
+                .unwrap_or(true);
+            if matches {
+                top.push(scorer.score(vector), row);
+            }
+            Ok(())
+        })?;
 
-        // Take top results
-        let results: Vec<VectorSearchResult> = scored
+        let results: Vec<VectorSearchResult> = top
+            .into_sorted()
             .into_iter()
-            .take(limit)
-            .map(|(score, stored)| {
+            .map(|(score, row)| {
+                let stored = &state.payloads[row];
                 VectorSearchResult::new(VectorId::new(stored.id), score, stored.payload.clone())
             })
             .collect();
@@ -241,60 +561,129 @@ impl VectorIndexBackend for SimpleFileVectorIndex {
     fn upsert(&self, vectors: &[VectorInsert]) -> DbResult<()> {
         debug!("Upserting {} vectors", vectors.len());
 
-        let mut stored = self
-            .vectors
+        // Validate dimensions before touching any state
+        if let Some(bad) = vectors.iter().find(|v| v.vector.len() != self.dimension) {
+            return Err(DbError::DimensionMismatch {
+                expected: self.dimension,
+                actual: bad.vector.len(),
+            });
+        }
+
+        let mut state = self
+            .state
             .write()
             .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?;
 
+        let count = state.len();
+        let IndexState {
+            data,
+            payloads,
+            rows_by_id,
+            dirty,
+            ..
+        } = &mut *state;
+        let values = data.make_owned(self.dimension, count);
+
         for insert in vectors {
-            // Validate dimension
-            if insert.vector.len() != self.dimension {
-                return Err(DbError::DimensionMismatch {
-                    expected: self.dimension,
-                    actual: insert.vector.len(),
-                });
+            let id = insert.id.value();
+            match rows_by_id.get(&id) {
+                Some(&row) => {
+                    values[row * self.dimension..(row + 1) * self.dimension]
+                        .copy_from_slice(&insert.vector);
+                    payloads[row] = StoredPayload::from(insert);
+                }
+                None => {
+                    rows_by_id.insert(id, payloads.len());
+                    values.extend_from_slice(&insert.vector);
+                    payloads.push(StoredPayload::from(insert));
+                }
             }
-
-            let entry = StoredVector::from(insert);
-            stored.insert(entry.id, entry);
         }
+        *dirty = true;
 
-        // Persist immediately
-        drop(stored);
-        self.save_to_file()?;
-
-        Ok(())
+        // Persist immediately unless a bulk load is in progress
+        if state.deferred {
+            return Ok(());
+        }
+        self.save(&mut state)
     }
 
     fn delete(&self, ids: &[VectorId]) -> DbResult<()> {
         debug!("Deleting {} vectors", ids.len());
 
-        let mut stored = self
-            .vectors
+        let mut state = self
+            .state
             .write()
             .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?;
 
+        let mut removed = 0;
         for id in ids {
-            stored.remove(&id.value());
+            if let Some(&row) = state.rows_by_id.get(&id.value()) {
+                state.swap_remove(row, self.dimension);
+                removed += 1;
+            }
         }
 
-        // Persist immediately
-        drop(stored);
-        self.save_to_file()?;
+        // Persist immediately unless a bulk load is in progress
+        if removed > 0 && !state.deferred {
+            self.save(&mut state)?;
+        }
 
         Ok(())
     }
 
     fn flush(&self) -> DbResult<()> {
-        self.save_to_file()
+        let mut state = self
+            .state
+            .write()
+            .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?;
+
+        // Writes are persisted eagerly unless deferred; otherwise only
+        // rewrite after a failed save
+        if state.dirty {
+            self.save(&mut state)?;
+        }
+        Ok(())
+    }
+
+    fn set_deferred_persist(&self, deferred: bool) -> DbResult<()> {
+        self.state
+            .write()
+            .map_err(|e| DbError::internal(format!("Failed to acquire write lock: {}", e)))?
+            .deferred = deferred;
+        Ok(())
+    }
+
+    fn scan(
+        &self,
+        batch_size: usize,
+        visit: &mut dyn FnMut(Vec<VectorInsert>) -> DbResult<()>,
+    ) -> DbResult<()> {
+        let state = self
+            .state
+            .read()
+            .map_err(|e| DbError::internal(format!("Failed to acquire read lock: {}", e)))?;
+
+        let mut batch = Vec::with_capacity(batch_size.max(1));
+        state.scan(self.dimension, |row, vector| {
+            batch.push(state.payloads[row].to_insert(vector));
+            if batch.len() >= batch_size.max(1) {
+                visit(std::mem::take(&mut batch))?;
+            }
+            Ok(())
+        })?;
+        if !batch.is_empty() {
+            visit(batch)?;
+        }
+        Ok(())
     }
 
     fn len(&self) -> DbResult<usize> {
-        let stored = self
-            .vectors
+        let state = self
+            .state
             .read()
             .map_err(|e| DbError::internal(format!("Failed to acquire read lock: {}", e)))?;
-        Ok(stored.len())
+        Ok(state.len())
     }
 
     fn dimension(&self) -> usize {
@@ -307,34 +696,130 @@ impl VectorIndexBackend for SimpleFileVectorIndex {
 }
 
 // ============================================================================
-// Similarity Functions
+// Encoding
 // ============================================================================
 
-/// Compute cosine similarity between two vectors.
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
-    let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
-    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
-    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+/// Read a payloads file, one record per row.
+fn load_payloads(path: &Path) -> DbResult<Vec<StoredPayload>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut payloads = Vec::new();
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let payload = serde_json::from_str::<StoredPayload>(&line)
+            .map_err(|e| DbError::vector_parse(path, format!("line {}: {}", line_num + 1, e)))?;
+        payloads.push(payload);
+    }
+    Ok(payloads)
+}
 
-    if norm_a == 0.0 || norm_b == 0.0 {
-        return 0.0;
+/// Reinterpret little-endian f32 bytes in place, if aligned.
+#[cfg(target_endian = "little")]
+fn as_f32_slice(bytes: &[u8]) -> Option<&[f32]> {
+    // SAFETY: every bit pattern is a valid f32, `align_to` only returns a
+    // non-empty middle slice for correctly aligned addresses, and the file's
+    // little-endian layout matches the in-memory layout on this target.
+    let (prefix, values, suffix) = unsafe { bytes.align_to::<f32>() };
+    (prefix.is_empty() && suffix.is_empty()).then_some(values)
+}
+
+#[cfg(not(target_endian = "little"))]
+fn as_f32_slice(_bytes: &[u8]) -> Option<&[f32]> {
+    None
+}
+
+fn decode_f32_into(bytes: &[u8], out: &mut [f32]) {
+    for (v, b) in out.iter_mut().zip(bytes.chunks_exact(4)) {
+        *v = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
     }
+}
 
-    dot / (norm_a * norm_b)
+fn decode_f16_into(bytes: &[u8], out: &mut [f32]) {
+    for (v, b) in out.iter_mut().zip(bytes.chunks_exact(2)) {
+        *v = f16_to_f32(u16::from_le_bytes([b[0], b[1]]));
+    }
 }
 
-/// Compute dot product between two vectors.
-fn dot_product(a: &[f32], b: &[f32]) -> f32 {
-    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+fn write_row(writer: &mut impl Write, row: &[f32], storage: VectorStorage) -> DbResult<()> {
+    match storage {
+        VectorStorage::F32 => {
+            for v in row {
+                writer.write_all(&v.to_le_bytes())?;
+            }
+        }
+        VectorStorage::F16 => {
+            for v in row {
+                writer.write_all(&f32_to_f16(*v).to_le_bytes())?;
+            }
+        }
+    }
+    Ok(())
 }
 
-/// Compute Euclidean (L2) distance between two vectors.
-fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
-    a.iter()
-        .zip(b.iter())
-        .map(|(x, y)| (x - y).powi(2))
-        .sum::<f32>()
-        .sqrt()
+/// Convert an f32 to IEEE 754 half precision (round to nearest even).
+fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x007f_ffff;
+
+    // Infinity / NaN
+    if exp == 0xff {
+        return sign | 0x7c00 | if mant != 0 { 0x0200 } else { 0 };
+    }
+
+    let half_exp = exp - 127 + 15;
+    if half_exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    if half_exp <= 0 {
+        // Subnormal half (or underflow to zero)
+        if half_exp < -10 {
+            return sign;
+        }
+        let mant = mant | 0x0080_0000;
+        let shift = (14 - half_exp) as u32;
+        let mut half_mant = mant >> shift;
+        let rem = mant & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        if rem > halfway || (rem == halfway && half_mant & 1 == 1) {
+            half_mant += 1;
+        }
+        return sign | half_mant as u16;
+    }
+
+    let mut half = ((half_exp as u32) << 10) | (mant >> 13);
+    let rem = mant & 0x1fff;
+    if rem > 0x1000 || (rem == 0x1000 && half & 1 == 1) {
+        // Carry may roll into the exponent, up to infinity, which is correct
+        half += 1;
+    }
+    sign | half as u16
+}
+
+/// Convert IEEE 754 half precision to f32.
+fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half as u32) & 0x8000) << 16;
+    let exp = ((half >> 10) & 0x1f) as u32;
+    let mant = (half & 0x03ff) as u32;
+
+    let bits = match exp {
+        0 => {
+            if mant == 0 {
+                sign
+            } else {
+                // Subnormal: mant × 2^-24
+                let value = mant as f32 * f32::from_bits(0x3380_0000);
+                sign | value.to_bits()
+            }
+        }
+        0x1f => sign | 0x7f80_0000 | (mant << 13),
+        _ => sign | ((exp + 127 - 15) << 23) | (mant << 13),
+    };
+    f32::from_bits(bits)
 }
 
 // ============================================================================
@@ -344,59 +829,251 @@ fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
-    #[test]
-    fn test_cosine_similarity() {
-        let a = vec![1.0, 0.0, 0.0];
-        let b = vec![1.0, 0.0, 0.0];
-        assert!((cosine_similarity(&a, &b) - 1.0).abs() < 1e-6);
+    fn open_index(dir: &Path, storage: VectorStorage) -> SimpleFileVectorIndex {
+        let config = VectorIndexConfig::new(3, dir)
+            .with_backend("simple")
+            .with_storage(storage);
+        SimpleFileVectorIndex::open(&config).unwrap()
+    }
 
-        let c = vec![0.0, 1.0, 0.0];
-        assert!(cosine_similarity(&a, &c).abs() < 1e-6);
+    fn sample_inserts() -> Vec<VectorInsert> {
+        vec![
+            VectorInsert::new(1u64, vec![1.0, 0.0, 0.0], serde_json::json!({"n": 1}))
+                .with_base("code")
+                .with_path("src/a.rs"),
+            VectorInsert::new(2u64, vec![0.0, 1.0, 0.0], serde_json::json!({"n": 2}))
+                .with_base("docs")
+                .with_path("docs/b.md"),
+            VectorInsert::new(3u64, vec![0.7, 0.7, 0.0], serde_json::json!({"n": 3}))
+                .with_base("code")
+                .with_path("src/c.rs"),
+        ]
+    }
+
+    /// Path of the live vector file.
+    fn vectors_file(index: &SimpleFileVectorIndex) -> PathBuf {
+        let files = index.files();
+        files.path(VECTORS_FILENAME, files.current().unwrap().unwrap())
+    }
+
+    fn result_ids(results: &[VectorSearchResult]) -> Vec<u64> {
+        results.iter().map(|r| r.id.value()).collect()
     }
 
     #[test]
-    fn test_dot_product() {
-        let a = vec![1.0, 2.0, 3.0];
-        let b = vec![4.0, 5.0, 6.0];
-        assert!((dot_product(&a, &b) - 32.0).abs() < 1e-6);
+    fn test_f16_conversion() {
+        for value in [0.0f32, 1.0, -2.5, 1365.0 / 4096.0, 65504.0, 2f32.powi(-14)] {
+            assert_eq!(f16_to_f32(f32_to_f16(value)), value, "{}", value);
+        }
+        // Subnormal, overflow and special values
+        assert_eq!(f16_to_f32(f32_to_f16(2f32.powi(-24))), 2f32.powi(-24));
+        assert_eq!(f16_to_f32(f32_to_f16(1e6)), f32::INFINITY);
+        assert_eq!(f16_to_f32(f32_to_f16(-0.0)).to_bits(), (-0.0f32).to_bits());
+        assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+        // Rounds to nearest
+        assert!((f16_to_f32(f32_to_f16(0.1)) - 0.1).abs() < 1e-4);
     }
 
     #[test]
-    fn test_euclidean_distance() {
-        let a = vec![0.0, 0.0, 0.0];
-        let b = vec![3.0, 4.0, 0.0];
-        assert!((euclidean_distance(&a, &b) - 5.0).abs() < 1e-6);
+    fn test_upsert_query_and_reopen() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path(), VectorStorage::F32);
+        index.upsert(&sample_inserts()).unwrap();
+
+        let results = index.query(&[1.0, 0.1, 0.0], 2, None).unwrap();
+        assert_eq!(result_ids(&results), vec![1, 3]);
+        assert!(results[0].score > results[1].score);
+
+        // Reopen from disk
+        drop(index);
+        let index = open_index(temp.path(), VectorStorage::F32);
+        assert_eq!(index.len().unwrap(), 3);
+        let reopened = index.query(&[1.0, 0.1, 0.0], 2, None).unwrap();
+        assert_eq!(result_ids(&reopened), vec![1, 3]);
+        assert_eq!(reopened[0].payload, serde_json::json!({"n": 1}));
+
+        let bytes = fs::metadata(vectors_file(&index)).unwrap().len();
+        assert_eq!(bytes as usize, HEADER_LEN + 3 * 3 * 4);
     }
 
     #[test]
-    fn test_filter_matching() {
-        let stored = StoredVector {
-            id: 123,
-            vector: vec![1.0, 2.0, 3.0],
-            payload: serde_json::json!({}),
-            base: "code".to_string(),
-            branch: Some("main".to_string()),
-            source_type: "file".to_string(),
-            path: Some("src/lib.rs".to_string()),
-            tags: vec!["rust".to_string()],
-            revision_id: None,
-        };
+    fn test_f16_storage() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path(), VectorStorage::F16);
+        index.upsert(&sample_inserts()).unwrap();
+        let bytes = fs::metadata(vectors_file(&index)).unwrap().len();
+        assert_eq!(bytes as usize, HEADER_LEN + 3 * 3 * 2);
+        drop(index);
+
+        // Reopened f16 rows stay mapped and are decoded per row when scored
+        let index = open_index(temp.path(), VectorStorage::F16);
+        assert!(matches!(
+            index.state.read().unwrap().data,
+            VectorData::Mapped {
+                storage: VectorStorage::F16,
+                ..
+            }
+        ));
+        let results = index.query(&[0.0, 1.0, 0.0], 3, None).unwrap();
+        assert_eq!(result_ids(&results), vec![2, 3, 1]);
+        assert!((results[0].score - 1.0).abs() < 1e-3);
+    }
 
-        // Empty filter matches all
-        let filter = VectorSearchFilter::new();
-        assert!(SimpleFileVectorIndex::matches_filter(&stored, &filter));
+    #[test]
+    fn test_update_delete_and_filter() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path(), VectorStorage::F32);
+        index.upsert(&sample_inserts()).unwrap();
+        drop(index);
+
+        // Mutate a reopened index
+        let index = open_index(temp.path(), VectorStorage::F32);
+        index.delete(&[VectorId::new(1)]).unwrap();
+        index
+            .upsert(&[
+                VectorInsert::new(2u64, vec![1.0, 0.0, 0.0], serde_json::json!({"n": 22}))
+                    .with_base("docs"),
+            ])
+            .unwrap();
+        assert_eq!(index.len().unwrap(), 2);
+
+        let results = index.query(&[1.0, 0.0, 0.0], 5, None).unwrap();
+        assert_eq!(result_ids(&results), vec![2, 3]);
+        assert_eq!(results[0].payload, serde_json::json!({"n": 22}));
 
-        // Matching base
         let filter = VectorSearchFilter::new().with_base("code");
-        assert!(SimpleFileVectorIndex::matches_filter(&stored, &filter));
+        let filtered = index.query(&[1.0, 0.0, 0.0], 5, Some(&filter)).unwrap();
+        assert_eq!(result_ids(&filtered), vec![3]);
 
-        // Non-matching base
-        let filter = VectorSearchFilter::new().with_base("docs");
-        assert!(!SimpleFileVectorIndex::matches_filter(&stored, &filter));
+        drop(index);
+        let index = open_index(temp.path(), VectorStorage::F32);
+        assert_eq!(index.len().unwrap(), 2);
+        assert!(index.query(&[1.0, 0.0], 5, None).is_err());
+    }
 
-        // Matching path prefix
-        let filter = VectorSearchFilter::new().with_path_prefix("src/");
-        assert!(SimpleFileVectorIndex::matches_filter(&stored, &filter));
+    #[test]
+    fn test_interrupted_save_keeps_previous_generation() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path(), VectorStorage::F32);
+        index.upsert(&sample_inserts()).unwrap();
+        let files = index.files();
+        assert_eq!(files.current().unwrap(), Some(1));
+        drop(index);
+
+        // A crash after writing only the next vector file, before the manifest
+        fs::write(files.path(VECTORS_FILENAME, 2), b"partial").unwrap();
+
+        let index = open_index(temp.path(), VectorStorage::F32);
+        assert_eq!(index.len().unwrap(), 3);
+        let results = index.query(&[0.0, 1.0, 0.0], 1, None).unwrap();
+        assert_eq!(result_ids(&results), vec![2]);
+
+        // The next save skips past the partial generation and drops both
+        index.delete(&[VectorId::new(2)]).unwrap();
+        assert_eq!(files.current().unwrap(), Some(3));
+        assert!(!files.path(VECTORS_FILENAME, 1).exists());
+        assert!(!files.path(PAYLOADS_FILENAME, 1).exists());
+        assert!(!files.path(VECTORS_FILENAME, 2).exists());
+
+        drop(index);
+        let index = open_index(temp.path(), VectorStorage::F32);
+        assert_eq!(index.len().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_stale_writer_does_not_rewrite_mapped_generation() {
+        let temp = TempDir::new().unwrap();
+        let writer = open_index(temp.path(), VectorStorage::F32);
+        writer.upsert(&sample_inserts()).unwrap();
+        let stale = open_index(temp.path(), VectorStorage::F32);
+
+        writer
+            .upsert(&[VectorInsert::new(
+                4u64,
+                vec![0.0, 0.0, 1.0],
+                serde_json::json!({}),
+            )])
+            .unwrap();
+        let reader = open_index(temp.path(), VectorStorage::F32);
+        let files = reader.files();
+        assert_eq!(files.current().unwrap(), Some(2));
+
+        // The stale writer saves a new generation instead of reusing the mapped one
+        stale.delete(&[VectorId::new(1)]).unwrap();
+        assert_eq!(files.current().unwrap(), Some(3));
+        assert_eq!(reader.len().unwrap(), 4);
+        let results = reader.query(&[0.0, 0.0, 1.0], 1, None).unwrap();
+        assert_eq!(result_ids(&results), vec![4]);
+    }
+
+    #[test]
+    fn test_flush_skips_clean_index() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path(), VectorStorage::F32);
+        index.upsert(&sample_inserts()).unwrap();
+        index.flush().unwrap();
+        // Upsert already persisted generation 1; flush must not write another
+        assert_eq!(index.files().current().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_deferred_persist_writes_once_on_flush() {
+        let temp = TempDir::new().unwrap();
+        let index = open_index(temp.path(), VectorStorage::F32);
+        index.set_deferred_persist(true).unwrap();
+        let inserts = sample_inserts();
+        index.upsert(&inserts[..2]).unwrap();
+        index.upsert(&inserts[2..]).unwrap();
+        index.delete(&[VectorId::new(2)]).unwrap();
+        assert_eq!(index.files().current().unwrap(), None);
+
+        index.set_deferred_persist(false).unwrap();
+        index.flush().unwrap();
+        assert_eq!(index.files().current().unwrap(), Some(1));
+
+        drop(index);
+        let index = open_index(temp.path(), VectorStorage::F32);
+        assert_eq!(index.len().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_converts_legacy_jsonl() {
+        let temp = TempDir::new().unwrap();
+        let legacy = [
+            r#"{"id":7,"vector":[0.0,0.0,1.0],"payload":{"k":"a"},"base":"code","branch":null,"source_type":"file","path":"a.rs","tags":[],"revision_id":null}"#,
+            "not json",
+            r#"{"id":8,"vector":[1.0,0.0],"payload":{},"base":"code","branch":null,"source_type":"file","path":"short.rs","tags":[],"revision_id":null}"#,
+            r#"{"id":9,"vector":[0.0,1.0,0.0],"payload":{"k":"b"},"base":"code","branch":null,"source_type":"file","path":"b.rs","tags":[],"revision_id":null}"#,
+        ];
+        fs::write(temp.path().join(LEGACY_DATA_FILENAME), legacy.join("\n")).unwrap();
+
+        let index = open_index(temp.path(), VectorStorage::F32);
+        assert_eq!(index.len().unwrap(), 2);
+        assert!(vectors_file(&index).exists());
+        assert!(temp.path().join(LEGACY_BACKUP_FILENAME).exists());
+        assert!(!temp.path().join(LEGACY_DATA_FILENAME).exists());
+
+        let results = index.query(&[0.0, 0.0, 1.0], 1, None).unwrap();
+        assert_eq!(result_ids(&results), vec![7]);
+        assert_eq!(results[0].payload, serde_json::json!({"k": "a"}));
+    }
+
+    #[test]
+    fn test_rejects_dimension_mismatch_on_open() {
+        let temp = TempDir::new().unwrap();
+        open_index(temp.path(), VectorStorage::F32)
+            .upsert(&sample_inserts())
+            .unwrap();
+
+        let config = VectorIndexConfig::new(4, temp.path()).with_backend("simple");
+        assert!(matches!(
+            SimpleFileVectorIndex::open(&config),
+            Err(DbError::DimensionMismatch {
+                expected: 4,
+                actual: 3
+            })
+        ));
     }
 }
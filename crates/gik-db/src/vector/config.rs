@@ -14,6 +14,15 @@ use tracing::debug;
 /// Default backend name.
 pub const DEFAULT_BACKEND: &str = "lancedb";
 
+/// Default HNSW graph degree (`M`).
+pub const DEFAULT_HNSW_M: usize = 16;
+
+/// Default HNSW candidate list size during construction.
+pub const DEFAULT_HNSW_EF_CONSTRUCTION: usize = 200;
+
+/// Default HNSW candidate list size during search.
+pub const DEFAULT_HNSW_EF_SEARCH: usize = 64;
+
 /// Filename for index metadata.
 pub const INDEX_META_FILENAME: &str = "index.meta.json";
 
@@ -45,6 +54,14 @@ pub struct VectorIndexConfig {
     /// Whether to create the index if it doesn't exist.
     #[serde(default = "default_create_if_missing")]
     pub create_if_missing: bool,
+
+    /// On-disk element type for backends that store raw vectors.
+    #[serde(default)]
+    pub storage: VectorStorage,
+
+    /// Graph parameters for the HNSW backend.
+    #[serde(default)]
+    pub hnsw: HnswConfig,
 }
 
 fn default_backend() -> String {
@@ -64,6 +81,8 @@ impl VectorIndexConfig {
             backend: DEFAULT_BACKEND.to_string(),
             metric: VectorMetric::Cosine,
             create_if_missing: true,
+            storage: VectorStorage::F32,
+            hnsw: HnswConfig::default(),
         }
     }
 
@@ -84,6 +103,108 @@ impl VectorIndexConfig {
         self.create_if_missing = create;
         self
     }
+
+    /// Set the on-disk vector element type.
+    pub fn with_storage(mut self, storage: VectorStorage) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Set the HNSW graph parameters.
+    pub fn with_hnsw(mut self, hnsw: HnswConfig) -> Self {
+        self.hnsw = hnsw;
+        self
+    }
+}
+
+// ============================================================================
+// HnswConfig
+// ============================================================================
+
+/// Parameters for the HNSW graph backend.
+///
+/// `m` and `ef_construction` shape the graph and are fixed when it is built;
+/// changing them for an existing index requires a rebuild. `ef_search` only
+/// affects queries and can be changed at any time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HnswConfig {
+    /// Maximum neighbors per node on upper layers (layer 0 allows `2 * m`).
+    #[serde(default = "default_hnsw_m")]
+    pub m: usize,
+
+    /// Candidate list size while inserting (higher = better graph, slower build).
+    #[serde(default = "default_hnsw_ef_construction")]
+    pub ef_construction: usize,
+
+    /// Candidate list size while searching (higher = better recall, slower query).
+    #[serde(default = "default_hnsw_ef_search")]
+    pub ef_search: usize,
+}
+
+fn default_hnsw_m() -> usize {
+    DEFAULT_HNSW_M
+}
+
+fn default_hnsw_ef_construction() -> usize {
+    DEFAULT_HNSW_EF_CONSTRUCTION
+}
+
+fn default_hnsw_ef_search() -> usize {
+    DEFAULT_HNSW_EF_SEARCH
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: DEFAULT_HNSW_M,
+            ef_construction: DEFAULT_HNSW_EF_CONSTRUCTION,
+            ef_search: DEFAULT_HNSW_EF_SEARCH,
+        }
+    }
+}
+
+// ============================================================================
+// VectorStorage
+// ============================================================================
+
+/// Element type used to persist raw vectors.
+///
+/// `F16` halves the file size at a small precision cost; scores are always
+/// computed in `f32`. Backends that manage their own encoding (LanceDB)
+/// ignore this setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorStorage {
+    /// 32-bit IEEE 754 floats (default).
+    #[default]
+    F32,
+    /// 16-bit IEEE 754 half-precision floats.
+    F16,
+}
+
+impl VectorStorage {
+    /// Get the storage name as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VectorStorage::F32 => "f32",
+            VectorStorage::F16 => "f16",
+        }
+    }
+
+    /// Size in bytes of a single element.
+    pub fn element_size(&self) -> usize {
+        match self {
+            VectorStorage::F32 => 4,
+            VectorStorage::F16 => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for VectorStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 // ============================================================================
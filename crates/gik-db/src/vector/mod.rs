@@ -5,6 +5,7 @@
 //! ## Available Backends
 //!
 //! - `lancedb` (default): Production-ready LanceDB with ANN search
+//! - `hnsw` (default): Embedded pure-Rust HNSW graph with sub-linear search
 //! - `simple`: File-based backend for testing/small indexes
 //!
 //! ## Usage
@@ -29,8 +30,10 @@ mod traits;
 
 // Re-export main types
 pub use config::{
-    check_index_compatibility, load_index_meta, write_index_meta, VectorIndexCompatibility,
-    VectorIndexConfig, VectorIndexMeta, DEFAULT_BACKEND, INDEX_META_FILENAME, LANCEDB_TABLE_NAME,
+    check_index_compatibility, load_index_meta, write_index_meta, HnswConfig,
+    VectorIndexCompatibility, VectorIndexConfig, VectorIndexMeta, VectorStorage, DEFAULT_BACKEND,
+    DEFAULT_HNSW_EF_CONSTRUCTION, DEFAULT_HNSW_EF_SEARCH, DEFAULT_HNSW_M, INDEX_META_FILENAME,
+    LANCEDB_TABLE_NAME,
 };
 pub use metadata::{
     VectorMetadata, VectorSearchFilter, SOURCE_TYPE_ARCHIVE, SOURCE_TYPE_FILE, SOURCE_TYPE_MEMORY,
@@ -39,7 +42,7 @@ pub use metadata::{
 pub use traits::{VectorId, VectorIndexBackend, VectorInsert, VectorMetric, VectorSearchResult};
 
 // Re-export backend factory function
-pub use backend::open_vector_index;
+pub use backend::{copy_vectors, open_vector_index};
 
 // Re-export backends
 #[cfg(feature = "lancedb")]
@@ -47,3 +50,6 @@ pub use backend::LanceDbVectorIndex;
 
 #[cfg(feature = "simple")]
 pub use backend::SimpleFileVectorIndex;
+
+#[cfg(feature = "hnsw")]
+pub use backend::HnswVectorIndex;
@@ -265,6 +265,27 @@ pub trait VectorIndexBackend: Send + Sync {
     /// all data is persisted.
     fn flush(&self) -> DbResult<()>;
 
+    /// Keep writes in memory until [`flush`](Self::flush) instead of
+    /// persisting each one.
+    ///
+    /// Used for bulk loads into backends that rewrite their files on every
+    /// write. Backends that write incrementally ignore it. Turning it off does
+    /// not write anything; call `flush` afterwards.
+    fn set_deferred_persist(&self, _deferred: bool) -> DbResult<()> {
+        Ok(())
+    }
+
+    /// Visit every stored vector, with its payload and metadata, in batches
+    /// of at most `batch_size`.
+    ///
+    /// Used to copy an index into another backend without re-embedding.
+    /// Batch order is backend-defined.
+    fn scan(
+        &self,
+        batch_size: usize,
+        visit: &mut dyn FnMut(Vec<VectorInsert>) -> DbResult<()>,
+    ) -> DbResult<()>;
+
     /// Get the number of vectors in the index.
     fn len(&self) -> DbResult<usize>;
 